@@ -0,0 +1,128 @@
+use crate::game_boy::components::cpu::PREFIX_INSTRUCTION_BYTE;
+use crate::game_boy::components::mmu::MMU;
+use crate::instructions::{data_byte_text, Instruction};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+pub mod listing;
+
+/// A decoded instruction together with where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisassembledInstruction {
+    pub address: u16,
+    /// The ROM bank the instruction was read from, only relevant for 0x4000-0x7FFF
+    pub bank: usize,
+    /// All bytes of the instruction, including the prefix and immediate operands
+    pub bytes: Vec<u8>,
+    /// None for a byte which can't be decoded, it is shown as a `DB` pseudo-instruction
+    pub instruction: Option<Instruction>,
+}
+
+impl DisassembledInstruction {
+    pub fn get_clear_text(&self) -> String {
+        let (lsb, msb) = self.get_operands();
+        match &self.instruction {
+            Some(instruction) => instruction.parse_clear_text(lsb, msb),
+            None => data_byte_text(self.bytes[0], false),
+        }
+    }
+
+    pub fn get_description(&self) -> String {
+        let (lsb, msb) = self.get_operands();
+        match &self.instruction {
+            Some(instruction) => instruction.parse_description(lsb, msb),
+            None => data_byte_text(self.bytes[0], true),
+        }
+    }
+
+    fn get_operands(&self) -> (u8, u8) {
+        if self.bytes[0] == PREFIX_INSTRUCTION_BYTE {
+            return (0, 0);
+        }
+        (
+            self.bytes.get(1).copied().unwrap_or(0),
+            self.bytes.get(2).copied().unwrap_or(0),
+        )
+    }
+}
+
+impl Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let bytes = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(
+            f,
+            "{:02X}:{:04X}  {:<8}  {}",
+            self.bank,
+            self.address,
+            bytes,
+            self.get_clear_text()
+        )
+    }
+}
+
+/// Decodes instructions lazily straight from the memory map, so a debugger can show
+/// a disassembly view around PC without copying the ROM.
+pub struct Disassembler<'a> {
+    mmu: &'a MMU,
+    bank: usize,
+    address: Option<u16>,
+}
+
+impl<'a> Disassembler<'a> {
+    /// Disassembles starting at the given address.
+    /// The switchable ROM area (0x4000-0x7FFF) is read from the given bank,
+    /// or from the currently mapped bank if none is given.
+    pub fn iter_from(mmu: &'a MMU, bank: Option<usize>, address: u16) -> Self {
+        Self {
+            mmu,
+            bank: bank.unwrap_or_else(|| mmu.get_current_rom_bank()),
+            address: Some(address),
+        }
+    }
+
+    fn read(&self, address: u16) -> u8 {
+        self.mmu.read_with_rom_bank(self.bank, address)
+    }
+}
+
+impl Iterator for Disassembler<'_> {
+    type Item = DisassembledInstruction;
+
+    /// Stops at the end of the address space, illegal opcodes become single data bytes
+    fn next(&mut self) -> Option<Self::Item> {
+        let address = self.address?;
+
+        let first_byte = self.read(address);
+        let instruction = match address.checked_add(1) {
+            Some(next) if first_byte == PREFIX_INSTRUCTION_BYTE => {
+                Instruction::from_byte(self.read(next), true).ok()
+            }
+            _ => Instruction::from_byte(first_byte, false).ok(),
+        };
+
+        let length = instruction.as_ref().map_or(1, Instruction::get_length) as u16;
+        let mut bytes = Vec::with_capacity(length as usize);
+        for offset in 0..length {
+            bytes.push(self.read(address.checked_add(offset)?));
+        }
+
+        self.address = address.checked_add(length);
+        Some(DisassembledInstruction {
+            address,
+            bank: if (0x4000..=0x7FFF).contains(&address) {
+                self.bank
+            } else {
+                0
+            },
+            bytes,
+            instruction,
+        })
+    }
+}
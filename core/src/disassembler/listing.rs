@@ -0,0 +1,384 @@
+//! Disassembly listings for external tools, either as JSON or as RGBDS assembly.
+//! Jump and call targets inside of the listing get labels, so the RGBDS output can be reassembled.
+//! https://rgbds.gbdev.io/docs/rgbasm.5
+
+use crate::disassembler::{DisassembledInstruction, Disassembler};
+use crate::enums::parameter_groups::{JumpCondition, R16Mem, R16Stack, R16, R8};
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::MMU;
+use crate::instructions::Instruction;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::str::FromStr;
+use serde::Serialize;
+
+/// Width of the instruction column in front of the address comments
+const RGBDS_INSTRUCTION_WIDTH: usize = 24;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ListingFormat {
+    /// The debugger view, one [`DisassembledInstruction`] per line
+    #[default]
+    Text,
+    Json,
+    Rgbds,
+}
+
+impl FromStr for ListingFormat {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "rgbds" | "asm" => Ok(Self::Rgbds),
+            _ => Err(format!("Unknown listing format '{s}', expected text, json or rgbds").into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListingOptions {
+    /// The ROM bank mapped into 0x4000-0x7FFF, the first switchable bank if None
+    pub bank: Option<usize>,
+    pub address: u16,
+    /// Number of instructions, data bytes count as one instruction each
+    pub count: usize,
+    pub format: ListingFormat,
+}
+
+impl Default for ListingOptions {
+    fn default() -> Self {
+        Self {
+            bank: None,
+            address: 0x0100,
+            count: 64,
+            format: ListingFormat::default(),
+        }
+    }
+}
+
+/// One instruction of a JSON listing
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListingEntry {
+    pub address: u16,
+    pub bank: usize,
+    pub bytes: Vec<u8>,
+    /// Lower case RGBDS mnemonic, `db` for bytes which can't be decoded
+    pub mnemonic: String,
+    /// RGBDS operands with numbers instead of labels
+    pub operands: Vec<String>,
+    /// The label of this instruction if another instruction of the listing jumps to it
+    pub label: Option<String>,
+    /// The address a jump or call goes to
+    pub target: Option<u16>,
+    /// Labels of the listing this instruction refers to
+    pub references: Vec<String>,
+}
+
+/// Names for the jump and call targets which are the start of an instruction of the listing
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Labels {
+    names: BTreeMap<u16, String>,
+}
+
+impl Labels {
+    pub fn collect(instructions: &[DisassembledInstruction]) -> Self {
+        let banks: BTreeMap<u16, usize> = instructions
+            .iter()
+            .map(|instruction| (instruction.address, instruction.bank))
+            .collect();
+        let names = instructions
+            .iter()
+            .filter_map(DisassembledInstruction::get_target)
+            .filter_map(|target| {
+                let bank = banks.get(&target)?;
+                Some((target, format!("label_{:02X}_{:04X}", bank, target)))
+            })
+            .collect();
+        Self { names }
+    }
+
+    pub fn get(&self, address: u16) -> Option<&str> {
+        self.names.get(&address).map(String::as_str)
+    }
+}
+
+impl DisassembledInstruction {
+    /// The address a jump or call goes to, None for every other instruction and for jumps to HL
+    pub fn get_target(&self) -> Option<u16> {
+        let lsb = self.bytes.get(1).copied().unwrap_or(0);
+        let msb = self.bytes.get(2).copied().unwrap_or(0);
+        match self.instruction.as_ref()? {
+            Instruction::JpImm16
+            | Instruction::JpCondImm16(_)
+            | Instruction::Call
+            | Instruction::CallCondition(_) => Some(u16::from_le_bytes([lsb, msb])),
+            Instruction::JrImm8 | Instruction::JrCondImm8(_) => {
+                Some(self.address.wrapping_add(2).wrapping_add(lsb as i8 as u16))
+            }
+            _ => None,
+        }
+    }
+
+    /// Lower case mnemonic and operands in RGBDS syntax, targets with a label use the label
+    pub fn get_rgbds_parts(&self, labels: &Labels) -> (&'static str, Vec<String>) {
+        let Some(instruction) = &self.instruction else {
+            return ("db", vec![hex8(self.bytes[0])]);
+        };
+        let lsb = self.bytes.get(1).copied().unwrap_or(0);
+        let msb = self.bytes.get(2).copied().unwrap_or(0);
+        let imm8 = hex8(lsb);
+        let imm16 = hex16(u16::from_le_bytes([lsb, msb]));
+        let target = self.get_target().map(|target| {
+            labels
+                .get(target)
+                .map_or_else(|| hex16(target), String::from)
+        });
+        let target = target.unwrap_or_default();
+
+        match instruction {
+            Instruction::Nop => ("nop", vec![]),
+            Instruction::AddHLR16(r16) => ("add", vec!["hl".into(), r16_text(r16)]),
+            Instruction::AddR8(r8) => ("add", vec!["a".into(), r8_text(r8)]),
+            Instruction::AddImm8 => ("add", vec!["a".into(), imm8]),
+            Instruction::AddCarryR8(r8) => ("adc", vec!["a".into(), r8_text(r8)]),
+            Instruction::AddCarryImm8 => ("adc", vec!["a".into(), imm8]),
+            Instruction::AddSpImm8 => ("add", vec!["sp".into(), (lsb as i8).to_string()]),
+            Instruction::AndR8(r8) => ("and", vec!["a".into(), r8_text(r8)]),
+            Instruction::AndImm8 => ("and", vec!["a".into(), imm8]),
+            Instruction::Call => ("call", vec![target]),
+            Instruction::CallCondition(cond) => ("call", vec![condition_text(cond), target]),
+            Instruction::CompareR8(r8) => ("cp", vec!["a".into(), r8_text(r8)]),
+            Instruction::CompareImm8 => ("cp", vec!["a".into(), imm8]),
+            Instruction::ComplementA => ("cpl", vec![]),
+            Instruction::ComplementCarryFlag => ("ccf", vec![]),
+            Instruction::DAA => ("daa", vec![]),
+            Instruction::DecR8(r8) => ("dec", vec![r8_text(r8)]),
+            Instruction::DecR16(r16) => ("dec", vec![r16_text(r16)]),
+            Instruction::DisableInterrupts => ("di", vec![]),
+            Instruction::EnableInterrupts => ("ei", vec![]),
+            Instruction::Halt => ("halt", vec![]),
+            Instruction::IncR8(r8) => ("inc", vec![r8_text(r8)]),
+            Instruction::IncR16(r16) => ("inc", vec![r16_text(r16)]),
+            Instruction::JpHL => ("jp", vec!["hl".into()]),
+            Instruction::JpImm16 => ("jp", vec![target]),
+            Instruction::JpCondImm16(cond) => ("jp", vec![condition_text(cond), target]),
+            Instruction::JrImm8 => ("jr", vec![target]),
+            Instruction::JrCondImm8(cond) => ("jr", vec![condition_text(cond), target]),
+            Instruction::LoadAR16(r16_mem) => ("ld", vec!["a".into(), r16_mem_text(r16_mem)]),
+            Instruction::LoadR16A(r16_mem) => ("ld", vec![r16_mem_text(r16_mem), "a".into()]),
+            Instruction::LoadR16Imm16(r16) => ("ld", vec![r16_text(r16), imm16]),
+            Instruction::LoadR8Imm8(r8) => ("ld", vec![r8_text(r8), imm8]),
+            Instruction::LoadR8R8((target, source)) => {
+                ("ld", vec![r8_text(target), r8_text(source)])
+            }
+            Instruction::LoadHighAC => ("ldh", vec!["a".into(), "[c]".into()]),
+            Instruction::LoadHighCA => ("ldh", vec!["[c]".into(), "a".into()]),
+            Instruction::LoadHighAImm8 => ("ldh", vec!["a".into(), high_text(lsb)]),
+            Instruction::LoadHighImm8A => ("ldh", vec![high_text(lsb), "a".into()]),
+            Instruction::LoadAImm16 => ("ld", vec!["a".into(), format!("[{imm16}]")]),
+            Instruction::LoadImm16A => ("ld", vec![format!("[{imm16}]"), "a".into()]),
+            Instruction::LoadImm16SP => ("ld", vec![format!("[{imm16}]"), "sp".into()]),
+            Instruction::LoadHlSpImm8 => {
+                let offset = lsb as i8;
+                let sign = if offset < 0 { '-' } else { '+' };
+                let text = format!("sp {sign} {}", offset.unsigned_abs());
+                ("ld", vec!["hl".into(), text])
+            }
+            Instruction::LoadSpHl => ("ld", vec!["sp".into(), "hl".into()]),
+            Instruction::OrR8(r8) => ("or", vec!["a".into(), r8_text(r8)]),
+            Instruction::OrImm8 => ("or", vec!["a".into(), imm8]),
+            Instruction::PopR16(r16_stack) => ("pop", vec![r16_stack_text(r16_stack)]),
+            Instruction::PushR16(r16_stack) => ("push", vec![r16_stack_text(r16_stack)]),
+            Instruction::RestartVector(address) => ("rst", vec![hex8(*address)]),
+            Instruction::Return => ("ret", vec![]),
+            Instruction::ReturnCondition(cond) => ("ret", vec![condition_text(cond)]),
+            Instruction::ReturnEnableInterrupts => ("reti", vec![]),
+            Instruction::RotateLeftA => ("rla", vec![]),
+            Instruction::RotateRightA => ("rra", vec![]),
+            Instruction::RotateLeftCircularA => ("rlca", vec![]),
+            Instruction::RotateRightCircularA => ("rrca", vec![]),
+            Instruction::SetCarryFlag => ("scf", vec![]),
+            Instruction::SubR8(r8) => ("sub", vec!["a".into(), r8_text(r8)]),
+            Instruction::SubImm8 => ("sub", vec!["a".into(), imm8]),
+            Instruction::SubCarryR8(r8) => ("sbc", vec!["a".into(), r8_text(r8)]),
+            Instruction::SubCarryImm8 => ("sbc", vec!["a".into(), imm8]),
+            Instruction::XorR8(r8) => ("xor", vec!["a".into(), r8_text(r8)]),
+            Instruction::XorImm8 => ("xor", vec!["a".into(), imm8]),
+            Instruction::BitCheckR8((bit, r8)) => ("bit", vec![bit.to_string(), r8_text(r8)]),
+            Instruction::BitResetR8((bit, r8)) => ("res", vec![bit.to_string(), r8_text(r8)]),
+            Instruction::BitSetR8((bit, r8)) => ("set", vec![bit.to_string(), r8_text(r8)]),
+            Instruction::RotateLeftR8(r8) => ("rl", vec![r8_text(r8)]),
+            Instruction::RotateLeftCircularR8(r8) => ("rlc", vec![r8_text(r8)]),
+            Instruction::RotateRightR8(r8) => ("rr", vec![r8_text(r8)]),
+            Instruction::RotateRightCircularR8(r8) => ("rrc", vec![r8_text(r8)]),
+            Instruction::ShiftLeftR8(r8) => ("sla", vec![r8_text(r8)]),
+            Instruction::ShiftRightR8(r8) => ("sra", vec![r8_text(r8)]),
+            Instruction::SwapR8(r8) => ("swap", vec![r8_text(r8)]),
+            Instruction::ShiftRightLogicallyR8(r8) => ("srl", vec![r8_text(r8)]),
+        }
+    }
+
+    pub fn get_listing_entry(&self, labels: &Labels) -> ListingEntry {
+        let (mnemonic, operands) = self.get_rgbds_parts(&Labels::default());
+        let target = self.get_target();
+        ListingEntry {
+            address: self.address,
+            bank: self.bank,
+            bytes: self.bytes.clone(),
+            mnemonic: mnemonic.into(),
+            operands,
+            label: labels.get(self.address).map(String::from),
+            target,
+            references: target
+                .and_then(|target| labels.get(target))
+                .map(String::from)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Disassembles a cartridge without running it, the cartridge's MBC starts out with its first switchable bank mapped
+pub fn disassemble_cartridge(
+    cartridge: &Cartridge,
+    options: &ListingOptions,
+) -> Result<String, Box<dyn Error>> {
+    let mmu = MMU::initialize(cartridge)?;
+    let instructions: Vec<_> = Disassembler::iter_from(&mmu, options.bank, options.address)
+        .take(options.count)
+        .collect();
+    format_listing(&instructions, options.format)
+}
+
+pub fn format_listing(
+    instructions: &[DisassembledInstruction],
+    format: ListingFormat,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        ListingFormat::Text => Ok(instructions
+            .iter()
+            .map(|instruction| format!("{instruction}\n"))
+            .collect()),
+        ListingFormat::Json => to_json(instructions),
+        ListingFormat::Rgbds => Ok(to_rgbds(instructions)),
+    }
+}
+
+pub fn to_json(instructions: &[DisassembledInstruction]) -> Result<String, Box<dyn Error>> {
+    let labels = Labels::collect(instructions);
+    let entries: Vec<ListingEntry> = instructions
+        .iter()
+        .map(|instruction| instruction.get_listing_entry(&labels))
+        .collect();
+    Ok(serde_json::to_string_pretty(&entries)?)
+}
+
+/// Starts a new section whenever the listing enters ROM bank 0 or a switchable bank,
+/// every line ends with a comment holding the address and the bytes
+pub fn to_rgbds(instructions: &[DisassembledInstruction]) -> String {
+    let labels = Labels::collect(instructions);
+    let mut output = String::new();
+    let mut current_bank = None;
+
+    for instruction in instructions {
+        let bank = get_rom_bank(instruction.address, instruction.bank);
+        if bank.is_some() && bank != current_bank {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(&get_section(instruction));
+            output.push('\n');
+        }
+        current_bank = bank;
+
+        if let Some(label) = labels.get(instruction.address) {
+            output.push_str(&format!("{label}:\n"));
+        }
+
+        let (mnemonic, operands) = instruction.get_rgbds_parts(&labels);
+        let text = if operands.is_empty() {
+            mnemonic.to_string()
+        } else {
+            format!("{mnemonic} {}", operands.join(", "))
+        };
+        let bytes = instruction
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(" ");
+        output.push_str(&format!(
+            "    {:<width$} ; ${:04X}: {}\n",
+            text,
+            instruction.address,
+            bytes,
+            width = RGBDS_INSTRUCTION_WIDTH
+        ));
+    }
+
+    output
+}
+
+/// The ROM bank of an address, None outside of ROM where code has no fixed section
+fn get_rom_bank(address: u16, bank: usize) -> Option<usize> {
+    match address {
+        0x0000..=0x3FFF => Some(0),
+        0x4000..=0x7FFF => Some(bank),
+        _ => None,
+    }
+}
+
+/// The SECTION directive placing the code from this ROM instruction on at its address
+fn get_section(instruction: &DisassembledInstruction) -> String {
+    let address = instruction.address;
+    if address < 0x4000 {
+        format!("SECTION \"ROM0 ${address:04X}\", ROM0[${address:04X}]")
+    } else {
+        let bank = instruction.bank;
+        format!(
+            "SECTION \"ROMX {bank:02X} ${address:04X}\", ROMX[${address:04X}], BANK[${bank:02X}]"
+        )
+    }
+}
+
+fn hex8(value: u8) -> String {
+    format!("${:02X}", value)
+}
+
+fn hex16(value: u16) -> String {
+    format!("${:04X}", value)
+}
+
+fn high_text(lsb: u8) -> String {
+    format!("[$FF{:02X}]", lsb)
+}
+
+fn r8_text(r8: &R8) -> String {
+    match r8 {
+        R8::HL => "[hl]".into(),
+        _ => r8.to_string().to_lowercase(),
+    }
+}
+
+fn r16_text(r16: &R16) -> String {
+    r16.to_string().to_lowercase()
+}
+
+fn r16_stack_text(r16_stack: &R16Stack) -> String {
+    r16_stack.to_string().to_lowercase()
+}
+
+fn r16_mem_text(r16_mem: &R16Mem) -> String {
+    format!("[{}]", r16_mem.to_string().to_lowercase())
+}
+
+fn condition_text(condition: &JumpCondition) -> String {
+    condition.to_string().to_lowercase()
+}
@@ -1,2 +1,3 @@
+pub mod button;
 pub mod interrupts;
 pub mod parameter_groups;
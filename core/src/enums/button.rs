@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+/// The 8 Game Boy buttons
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Button {
+    A,
+    B,
+    Select,
+    Start,
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+impl Button {
+    pub const ALL: [Button; 8] = [
+        Button::A,
+        Button::B,
+        Button::Select,
+        Button::Start,
+        Button::Right,
+        Button::Left,
+        Button::Up,
+        Button::Down,
+    ];
+
+    /// Bit of the button in [`Buttons`], action buttons in the lower and directions in the upper nibble like in P1
+    pub fn get_mask(&self) -> u8 {
+        match self {
+            Button::A => 0b0000_0001,
+            Button::B => 0b0000_0010,
+            Button::Select => 0b0000_0100,
+            Button::Start => 0b0000_1000,
+            Button::Right => 0b0001_0000,
+            Button::Left => 0b0010_0000,
+            Button::Up => 0b0100_0000,
+            Button::Down => 0b1000_0000,
+        }
+    }
+}
+
+/// A set of pressed buttons
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Buttons(u8);
+
+impl Buttons {
+    pub const NONE: Buttons = Buttons(0);
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub fn with(mut self, button: Button) -> Self {
+        self.press(button);
+        self
+    }
+
+    pub fn press(&mut self, button: Button) {
+        self.0 |= button.get_mask();
+    }
+
+    pub fn release(&mut self, button: Button) {
+        self.0 &= !button.get_mask();
+    }
+
+    pub fn set(&mut self, button: Button, pressed: bool) {
+        if pressed {
+            self.press(button);
+        } else {
+            self.release(button);
+        }
+    }
+
+    pub fn is_pressed(&self, button: Button) -> bool {
+        self.0 & button.get_mask() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Buttons) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Button> + '_ {
+        Button::ALL
+            .into_iter()
+            .filter(|button| self.is_pressed(*button))
+    }
+}
@@ -1,7 +1,8 @@
 //! These are parameter groups for CPU instructions as they are used in the Pan Docs
 //! https://gbdev.io/pandocs/CPU_Instruction_Set.html
 
-use std::fmt::Display;
+use alloc::string::String;
+use core::fmt::Display;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum R8 {
@@ -17,7 +18,7 @@ pub enum R8 {
 }
 
 impl Display for R8 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             R8::B => write!(f, "B"),
             R8::C => write!(f, "C"),
@@ -40,7 +41,7 @@ pub enum R16 {
 }
 
 impl Display for R16 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             R16::BC => write!(f, "BC"),
             R16::DE => write!(f, "DE"),
@@ -59,7 +60,7 @@ pub enum R16Stack {
 }
 
 impl Display for R16Stack {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             R16Stack::BC => write!(f, "BC"),
             R16Stack::DE => write!(f, "DE"),
@@ -78,7 +79,7 @@ pub enum R16Mem {
 }
 
 impl Display for R16Mem {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             R16Mem::BC => write!(f, "BC"),
             R16Mem::DE => write!(f, "DE"),
@@ -97,7 +98,7 @@ pub enum JumpCondition {
 }
 
 impl Display for JumpCondition {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             JumpCondition::NotZero => write!(f, "NZ"),
             JumpCondition::Zero => write!(f, "Z"),
@@ -0,0 +1,760 @@
+use crate::disassembler::{DisassembledInstruction, Disassembler};
+use crate::enums::button::{Button, Buttons};
+use crate::game_boy::battery_save::BatterySave;
+use crate::game_boy::cheats::{Cheat, CheatCode};
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::cpu::doctor::DoctorLogLine;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::dma::Dma;
+use crate::game_boy::components::mmu::io_hooks::IoHookId;
+use crate::game_boy::components::mmu::mbc::{MapperWriteEvent, RumbleEvent};
+use crate::game_boy::components::mmu::region::MemoryRegion;
+use crate::game_boy::components::mmu::stats::MemoryStats;
+use crate::game_boy::components::mmu::TileMap;
+use crate::game_boy::components::mmu::{IE_ADDRESS, MMU};
+use crate::game_boy::components::ppu::color_correction::ColorCorrection;
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use crate::game_boy::components::ppu::colorization;
+use crate::game_boy::components::ppu::debug;
+use crate::game_boy::components::ppu::debug::{
+    OamEntry, ScanlineRegisters, TileInfo, TileMapEntry,
+};
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::{VBlankInfo, PPU, RGBA_FRAME_BUFFER_SIZE};
+use crate::game_boy::components::serial::{LinkCable, Serial, SerialLink};
+use crate::game_boy::components::timer::{Timer, TimerOverflowEvent};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::counters::Counters;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::diagnostics::Diagnostic;
+use crate::game_boy::input_stats::InputStats;
+use crate::game_boy::save_state::GameBoySaveState;
+use crate::game_boy::shared_frame_buffer::SharedFrameBuffer;
+use crate::game_boy::thumbnail::Thumbnail;
+use crate::helpers::listeners::ListenerId;
+use crate::logging::Subsystem;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
+use log::warn;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+pub mod battery_save;
+pub mod cheats;
+pub mod components;
+pub mod config;
+pub mod counters;
+pub mod cycles;
+pub mod diagnostics;
+pub mod hardware_model;
+pub mod input_stats;
+#[cfg(feature = "std")]
+pub mod sample_buffer;
+pub mod save_state;
+pub mod shared_frame_buffer;
+pub mod thumbnail;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GameBoy {
+    /// Central Processing Unit
+    cpu: CPU,
+    /// Memory Management Unit
+    /// Handles all memory storage and access
+    mmu: MMU,
+    timer: Timer,
+    ppu: PPU,
+    serial: Serial,
+    dma: Dma,
+    config: GameBoyConfig,
+    /// Lag frames and input latency of this session, not part of the emulated state
+    input_stats: InputStats,
+    /// Receives every finished frame once [`share_frame_buffer`](Self::share_frame_buffer) was called
+    shared_frame_buffer: Option<SharedFrameBuffer>,
+    /// Cycles and frames since power on, not part of the emulated state so they never go backwards
+    counters: Counters,
+    /// Codes of the enabled cheats, kept across resets and loaded states
+    cheats: Vec<CheatCode>,
+    /// Kept across resets and loaded states like a real cable stays plugged in
+    link_cable: LinkCable,
+}
+
+impl GameBoy {
+    /// Fails if the cartridge uses a memory bank controller which isn't emulated
+    /// or if the model would run it in CGB mode
+    pub fn initialize(cartridge: &Cartridge) -> Result<Self, Box<dyn Error>> {
+        Self::initialize_with_config(cartridge, GameBoyConfig::default())
+    }
+
+    pub fn initialize_with_config(
+        cartridge: &Cartridge,
+        config: GameBoyConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        if config.model.runs_in_cgb_mode(&cartridge.header) {
+            return Err(format!(
+                "The {:?} would run {} in CGB mode, which isn't emulated",
+                config.model, cartridge.header.title
+            )
+            .into());
+        }
+        let rom = cartridge
+            .rom_banks
+            .first()
+            .map_or(&[][..], |bank| &bank[..]);
+        let mut game_boy = Self {
+            cpu: CPU::initialize(config.model, rom),
+            mmu: MMU::initialize_with_model(cartridge, config.model)?,
+            timer: Timer::initialize(config.get_div_counter()),
+            ppu: PPU::with_format(config.frame_buffer_format),
+            serial: Serial::default(),
+            dma: Dma::default(),
+            config,
+            input_stats: InputStats::default(),
+            shared_frame_buffer: None,
+            counters: Counters::default(),
+            cheats: Vec::new(),
+            link_cable: LinkCable::default(),
+        };
+        game_boy.sync_div();
+        game_boy.update_color_scheme();
+        game_boy.mmu.set_open_bus(game_boy.config.open_bus);
+        Ok(game_boy)
+    }
+
+    /// Starts a ROM given as bytes with the default config, the entry point for embedding the emulator.
+    /// ```
+    /// # use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+    /// # use lemon_gb_core::{Button, GameBoy};
+    /// // Selects the action buttons and copies them to 0xC000 forever, a pressed button reads as 0
+    /// let rom = RomBuilder::new()
+    ///     .program(&[0x3E, 0x10, 0xE0, 0x00, 0xF0, 0x00, 0xEA, 0x00, 0xC0, 0x18, 0xF5])
+    ///     .build();
+    /// let mut game_boy = GameBoy::headless(&rom)?;
+    ///
+    /// game_boy.press_button(Button::A);
+    /// let frame = game_boy.run_frame();
+    /// assert_eq!(frame.len(), 160 * 144 * 4);
+    /// assert_eq!(game_boy.read_memory(0xC000) & 0x01, 0);
+    ///
+    /// game_boy.release_button(Button::A);
+    /// game_boy.run_frame();
+    /// assert_eq!(game_boy.read_memory(0xC000) & 0x01, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn headless(rom: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::initialize(&Cartridge::from_bytes(rom)?)
+    }
+
+    /// Advances the whole system by one CPU instruction (or one interrupt dispatch or HALT cycle).
+    /// The timer, serial port, OAM DMA and PPU then advance by exactly the cycles the CPU consumed, in that order.
+    /// Interrupts they raise are written to IF right away and are seen by the CPU on the next step.
+    /// Returns true if the PPU finished a frame.
+    pub fn step(&mut self) -> bool {
+        // Only reads by the CPU count as polling, not the ones of debug tools between steps
+        self.mmu.take_joypad_polled();
+        let cycles = self.cpu.step(&mut self.mmu);
+        self.check_cpu_diagnostics();
+        let polled = self.mmu.take_joypad_polled();
+        self.input_stats.step(cycles, polled);
+        if polled {
+            self.ppu.record_joypad_poll();
+        }
+        self.timer.tick(cycles, &mut self.mmu);
+        match self.link_cable.lock() {
+            Some(mut partner) => {
+                self.serial
+                    .step_linked(cycles, &mut self.mmu, &mut *partner);
+            }
+            None => {
+                self.serial.tick(cycles, &mut self.mmu);
+            }
+        }
+        self.dma.tick(cycles, &mut self.mmu);
+        self.mmu.step_cartridge(cycles);
+        let frame_finished = self.ppu.tick(cycles, &mut self.mmu);
+        self.counters.step(cycles, frame_finished);
+        if frame_finished {
+            self.ppu.notify_frame(self.counters.get_frame_count() - 1);
+            self.apply_ram_cheats();
+            self.input_stats.end_frame();
+            if let Some(shared) = &self.shared_frame_buffer {
+                shared.update(self.ppu.get_frame_buffer());
+            }
+        }
+        frame_finished
+    }
+
+    /// Soft reset to the state right after the boot ROM, like pressing the power switch without swapping the cartridge.
+    /// The cartridge RAM, the config, held buttons, registered listeners, the input statistics and the counters survive.
+    pub fn reset(&mut self) {
+        let model = self.config.model;
+        self.cpu = CPU::initialize(model, &self.read_header());
+        self.mmu.reset(model);
+        self.timer.reset(&self.config);
+        self.sync_div();
+        self.ppu.reset(&self.config);
+        self.serial.reset(&self.config);
+        self.dma.reset(&self.config);
+    }
+
+    /// Reports a program counter or stack pointer which ended up where no game should put it
+    fn check_cpu_diagnostics(&self) {
+        if self.cpu.get_pc() == IE_ADDRESS {
+            self.mmu.report_diagnostic(Diagnostic::ExecutionAtFFFF);
+        }
+        if let Some(diagnostic) = Diagnostic::check_stack_pointer(self.cpu.get_sp()) {
+            self.mmu.report_diagnostic(diagnostic);
+        }
+    }
+
+    /// DIV of the model's IO registers has to follow a counter overridden in the config
+    fn sync_div(&mut self) {
+        self.mmu.timer_update_div((self.timer.counter >> 8) as u8);
+    }
+
+    pub fn finish_frame(&mut self) {
+        while !self.step() {}
+    }
+
+    /// Runs until the PPU finished the next frame and returns it in the configured format
+    pub fn run_frame(&mut self) -> &[u8] {
+        self.finish_frame();
+        self.get_frame_buffer()
+    }
+
+    pub fn save(&self) -> GameBoySaveState {
+        GameBoySaveState {
+            cartridge_header: self.mmu.cartridge_header.clone(),
+            cpu: self.cpu.clone(),
+            timer: self.timer.save(),
+            mmu_state: self.mmu.save(),
+            ppu_state: self.ppu.save(),
+            serial: self.serial.save(),
+            dma: self.dma.save(),
+            thumbnail: None,
+        }
+    }
+
+    /// Like [`GameBoy::save`], with a downscaled screenshot of the last frame for save state pickers
+    pub fn save_with_thumbnail(&self) -> GameBoySaveState {
+        GameBoySaveState {
+            thumbnail: Some(Thumbnail::from_rgba_frame(
+                &self.ppu.get_rgba_frame_buffer(),
+            )),
+            ..self.save()
+        }
+    }
+
+    pub fn load(state: GameBoySaveState, cartridge: &Cartridge) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_config(state, cartridge, GameBoyConfig::default())
+    }
+
+    pub fn load_with_config(
+        state: GameBoySaveState,
+        cartridge: &Cartridge,
+        config: GameBoyConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut game_boy = Self {
+            cpu: state.cpu,
+            mmu: MMU::load(state.mmu_state, cartridge)?,
+            timer: Timer::default(),
+            ppu: PPU::with_format(config.frame_buffer_format),
+            serial: Serial::default(),
+            dma: Dma::default(),
+            config,
+            input_stats: InputStats::default(),
+            shared_frame_buffer: None,
+            counters: Counters::default(),
+            cheats: Vec::new(),
+            link_cable: LinkCable::default(),
+        };
+        game_boy.timer.load(state.timer)?;
+        game_boy.ppu.load(state.ppu_state)?;
+        game_boy.serial.load(state.serial)?;
+        game_boy.dma.load(state.dma)?;
+        game_boy.update_color_scheme();
+        game_boy.mmu.set_open_bus(game_boy.config.open_bus);
+        Ok(game_boy)
+    }
+
+    /// Replaces the running state with one saved from the same cartridge, the config, input statistics and counters are kept.
+    /// Like [`GameBoy::load`] the listeners have to be registered again.
+    pub fn load_state(&mut self, state: GameBoySaveState) -> Result<(), Box<dyn Error>> {
+        state.check_cartridge(&self.mmu.cartridge_header)?;
+        let input_stats = core::mem::take(&mut self.input_stats);
+        let shared_frame_buffer = self.shared_frame_buffer.take();
+        let counters = self.counters;
+        let cheats = core::mem::take(&mut self.cheats);
+        let link_cable = core::mem::take(&mut self.link_cable);
+        let camera_image = self.mmu.get_camera_image().map(<[u8]>::to_vec);
+        *self = Self::load_with_config(state, &self.mmu.get_cartridge(), self.config.clone())?;
+        self.input_stats = input_stats;
+        self.shared_frame_buffer = shared_frame_buffer;
+        self.counters = counters;
+        self.activate_cheats(cheats);
+        self.link_cable = link_cable;
+        self.mmu.set_serial_capture(!self.link_cable.is_connected());
+        if let Some(camera_image) = camera_image {
+            self.mmu.set_camera_image(&camera_image)?;
+        }
+        Ok(())
+    }
+
+    /// The cartridge RAM for a `.sav` file, there is no RTC to store yet
+    pub fn battery_save(&self) -> BatterySave {
+        BatterySave {
+            ram: self.mmu.get_cartridge_ram(),
+            rtc: None,
+        }
+    }
+
+    /// Restores the cartridge RAM, fails if its size doesn't match the cartridge
+    pub fn load_battery_save(&mut self, save: &BatterySave) -> Result<(), Box<dyn Error>> {
+        self.mmu.set_cartridge_ram(&save.ram)?;
+        if save.rtc.is_some() {
+            warn!(
+                target: Subsystem::Mmu.target(),
+                "Ignoring the RTC footer of the battery save, the real time clock is not emulated"
+            );
+        }
+        Ok(())
+    }
+
+    /// Imports a `.sav` file, fails if the cartridge has no RAM or the file is of another size
+    #[cfg(feature = "std")]
+    pub fn load_battery_save_file(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let ram_size = self.mmu.get_cartridge_ram().len();
+        if ram_size == 0 {
+            return Err(format!(
+                "{} has no cartridge RAM to load",
+                self.mmu.cartridge_header.title
+            )
+            .into());
+        }
+        self.load_battery_save(&BatterySave::load(path, ram_size)?)
+    }
+
+    /// The current frame in the configured [`FrameBufferFormat`]
+    pub fn get_frame_buffer(&self) -> &[u8] {
+        self.ppu.get_frame_buffer()
+    }
+
+    /// The frame buffer itself as a fixed size array, None unless the format is RGBA8888.
+    /// Right after [`finish_frame`](Self::finish_frame) it holds the whole frame that was just finished.
+    /// After single [`step`](Self::step)s the lines above LY are already from the frame being drawn.
+    /// The borrow ends with the next call which runs the emulator, embedders copy it only if they need to keep it.
+    pub fn get_rgba_frame(&self) -> Option<&[u8; RGBA_FRAME_BUFFER_SIZE]> {
+        if self.get_frame_buffer_format() != FrameBufferFormat::Rgba8888 {
+            return None;
+        }
+        self.get_frame_buffer().try_into().ok()
+    }
+
+    /// A copy of the current frame converted to RGBA8888, whatever the configured format is
+    pub fn get_rgba_frame_buffer(&self) -> Vec<u8> {
+        self.ppu.get_rgba_frame_buffer()
+    }
+
+    /// A buffer other threads can read the last finished frame from, see [`SharedFrameBuffer`].
+    /// It starts with the current frame buffer, later calls return the same buffer.
+    /// Clones of the Game Boy write into the same buffer, it survives resets and loaded states.
+    pub fn share_frame_buffer(&mut self) -> SharedFrameBuffer {
+        self.shared_frame_buffer
+            .get_or_insert_with(|| SharedFrameBuffer::new(self.ppu.get_frame_buffer()))
+            .clone()
+    }
+
+    pub fn get_frame_buffer_format(&self) -> FrameBufferFormat {
+        self.ppu.get_frame_buffer_format()
+    }
+
+    /// Changes the colors from the next drawn pixel on, e.g. to switch a game's palette while it runs.
+    /// Turns off colorization, the given colors are used as they are.
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        self.config.color_scheme = color_scheme;
+        self.config.colorize = false;
+        self.update_color_scheme();
+    }
+
+    /// Switches between the colors the CGB boot ROM picks for the game and the configured color scheme
+    pub fn set_colorize(&mut self, colorize: bool) {
+        self.config.colorize = colorize;
+        self.update_color_scheme();
+    }
+
+    pub fn is_colorized(&self) -> bool {
+        self.config.colorize
+    }
+
+    /// Only affects the CGB colors of colorized games, a custom color scheme is used as it is
+    pub fn set_color_correction(&mut self, color_correction: ColorCorrection) {
+        self.config.color_correction = color_correction;
+        self.update_color_scheme();
+    }
+
+    pub fn get_color_correction(&self) -> ColorCorrection {
+        self.config.color_correction
+    }
+
+    /// The cartridge header with everything before it, as the boot ROM sees it
+    fn read_header(&self) -> Vec<u8> {
+        (0..colorization::HEADER_END)
+            .map(|address| self.mmu.read_with_rom_bank(0, address))
+            .collect()
+    }
+
+    fn update_color_scheme(&mut self) {
+        let color_scheme = if self.config.colorize {
+            self.config
+                .color_correction
+                .apply_to_scheme(&colorization::colorize(&self.read_header()))
+        } else {
+            self.config.color_scheme
+        };
+        self.ppu.set_color_scheme(color_scheme);
+    }
+
+    pub fn get_color_scheme(&self) -> &ColorScheme {
+        self.ppu.get_color_scheme()
+    }
+
+    /// Low power mode, only every nth frame is rendered (1 renders every frame)
+    pub fn set_render_interval(&mut self, interval: u8) {
+        self.ppu.set_render_interval(interval);
+    }
+
+    /// The buttons held down from now on, pressing a button may request the Joypad Interrupt
+    pub fn set_buttons(&mut self, buttons: Buttons) {
+        if buttons != self.mmu.get_buttons() {
+            self.input_stats.record_input_change();
+        }
+        self.mmu.set_buttons(buttons);
+    }
+
+    pub fn get_buttons(&self) -> Buttons {
+        self.mmu.get_buttons()
+    }
+
+    /// Holds the button down in addition to the ones already held
+    pub fn press_button(&mut self, button: Button) {
+        self.set_buttons(self.get_buttons().with(button));
+    }
+
+    pub fn release_button(&mut self, button: Button) {
+        let mut buttons = self.get_buttons();
+        buttons.release(button);
+        self.set_buttons(buttons);
+    }
+
+    /// T-cycles emulated by this instance, only ever increases: resets and loaded states don't change it
+    pub fn total_cycles(&self) -> u64 {
+        self.counters.get_total_cycles()
+    }
+
+    /// Frames finished by this instance, including the blank ones while the LCD is off.
+    /// Like [`total_cycles`](Self::total_cycles) it only ever increases.
+    pub fn frame_count(&self) -> u64 {
+        self.counters.get_frame_count()
+    }
+
+    /// Lag frames and input latency since the emulator was started or the statistics were reset
+    pub fn get_input_stats(&self) -> &InputStats {
+        &self.input_stats
+    }
+
+    pub fn reset_input_stats(&mut self) {
+        self.input_stats.reset();
+    }
+
+    /// Registers a callback which is invoked whenever the PPU finishes a frame, see [`VBlankInfo`]
+    pub fn on_frame(&mut self, callback: impl FnMut(&VBlankInfo) + Send + 'static) -> ListenerId {
+        self.ppu.on_frame(callback)
+    }
+
+    pub fn remove_frame_listener(&mut self, id: ListenerId) {
+        self.ppu.remove_frame_listener(id);
+    }
+
+    /// Registers a callback which is invoked on every TIMA overflow
+    pub fn on_timer_overflow(
+        &mut self,
+        callback: impl FnMut(&TimerOverflowEvent) + Send + 'static,
+    ) -> ListenerId {
+        self.timer.on_overflow(callback)
+    }
+
+    pub fn remove_timer_overflow_listener(&mut self, id: ListenerId) {
+        self.timer.remove_overflow_listener(id);
+    }
+
+    /// Registers a callback which is invoked whenever the game writes to the ROM area,
+    /// with how the memory bank controller interpreted it and the banks mapped afterwards
+    pub fn on_mapper_write(
+        &mut self,
+        callback: impl FnMut(&MapperWriteEvent) + Send + 'static,
+    ) -> ListenerId {
+        self.mmu.on_mapper_write(callback)
+    }
+
+    pub fn remove_mapper_write_listener(&mut self, id: ListenerId) {
+        self.mmu.remove_mapper_write_listener(id);
+    }
+
+    /// Registers a callback which is invoked whenever the rumble motor of the cartridge is switched on or off,
+    /// e.g. to forward it to the rumble of a gamepad
+    pub fn on_rumble(&mut self, callback: impl FnMut(&RumbleEvent) + Send + 'static) -> ListenerId {
+        self.mmu.on_rumble(callback)
+    }
+
+    pub fn remove_rumble_listener(&mut self, id: ListenerId) {
+        self.mmu.remove_rumble_listener(id);
+    }
+
+    /// Registers a callback which is invoked the first time after power on or a reset that the game does
+    /// a certain kind of suspicious thing, e.g. to show a hint in a log panel. Emulation continues regardless.
+    pub fn on_diagnostic(
+        &mut self,
+        callback: impl FnMut(&Diagnostic) + Send + 'static,
+    ) -> ListenerId {
+        self.mmu.on_diagnostic(callback)
+    }
+
+    pub fn remove_diagnostic_listener(&mut self, id: ListenerId) {
+        self.mmu.remove_diagnostic_listener(id);
+    }
+
+    /// Activates the enabled cheats in place of the previous ones, fails without changing anything if a code is invalid.
+    /// Game Genie codes apply right away, GameShark codes at the start of every VBlank.
+    pub fn set_cheats(&mut self, cheats: &[Cheat]) -> Result<(), Box<dyn Error>> {
+        let codes = cheats
+            .iter()
+            .filter(|cheat| cheat.enabled)
+            .map(Cheat::parse_code)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.activate_cheats(codes);
+        Ok(())
+    }
+
+    /// The codes of the enabled cheats
+    pub fn get_active_cheats(&self) -> &[CheatCode] {
+        &self.cheats
+    }
+
+    fn activate_cheats(&mut self, codes: Vec<CheatCode>) {
+        let patches = codes
+            .iter()
+            .filter_map(|code| match code {
+                CheatCode::GameGenie(patch) => Some(*patch),
+                CheatCode::GameShark(_) => None,
+            })
+            .collect();
+        self.mmu.set_rom_patches(patches);
+        self.cheats = codes;
+    }
+
+    /// GameShark codes write their value without any side effects, like a debugger
+    fn apply_ram_cheats(&mut self) {
+        for code in &self.cheats {
+            if let CheatCode::GameShark(write) = code {
+                self.mmu.poke(write.address, write.value);
+            }
+        }
+    }
+
+    /// Makes the game read the IO register address (0xFF00-0xFF7F) as whatever the hook returns,
+    /// e.g. to stub hardware which isn't emulated. Returning None leaves the read to the emulated register.
+    /// Hooks survive resets, but like listeners they have to be added again after loading a state.
+    pub fn add_io_read_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16) -> Option<u8> + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        self.mmu.add_io_read_hook(address, hook)
+    }
+
+    /// Passes writes of the game to the IO register address (0xFF00-0xFF7F) to the hook first,
+    /// e.g. for a debug port of homebrew. Returning true keeps the write from reaching the emulated register.
+    pub fn add_io_write_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16, u8) -> bool + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        self.mmu.add_io_write_hook(address, hook)
+    }
+
+    pub fn remove_io_hook(&mut self, id: IoHookId) {
+        self.mmu.remove_io_hook(id);
+    }
+
+    /// Tilts cartridges with an accelerometer (MBC7), e.g. from an analog stick or the mouse.
+    /// Both axes are in g and clamped to -1.0 to 1.0, positive x tilts the right side down and positive y the bottom side.
+    /// Like held buttons the tilt is not part of save states.
+    pub fn set_tilt(&mut self, x: f32, y: f32) {
+        self.mmu.set_tilt(x, y);
+    }
+
+    /// What the image sensor of the Pocket Camera sees, 128x112 brightness values row by row.
+    /// Without an image the sensor sees a flat gray. Like held buttons the image is not part of save states.
+    pub fn set_camera_image(&mut self, pixels: &[u8]) -> Result<(), Box<dyn Error>> {
+        self.mmu.set_camera_image(pixels)
+    }
+
+    /// None for other cartridges or if no image was set
+    pub fn get_camera_image(&self) -> Option<&[u8]> {
+        self.mmu.get_camera_image()
+    }
+
+    /// False for cartridges without a rumble motor
+    pub fn is_rumbling(&self) -> bool {
+        self.mmu.is_motor_on()
+    }
+
+    /// Cycles until the next TIMA overflow, None if the timer is disabled
+    pub fn cycles_until_timer_overflow(&self) -> Option<Cycles> {
+        self.timer.cycles_until_overflow(&self.mmu)
+    }
+
+    /// The current CPU state as a Gameboy Doctor log line
+    pub fn doctor_log_line(&self) -> DoctorLogLine {
+        DoctorLogLine::from_cpu(&self.cpu, &self.mmu)
+    }
+
+    /// The bytes the game sent over the serial port while no link partner was connected.
+    /// It holds what was sent since the last [`take_serial_output`](Self::take_serial_output), at most the last
+    /// [`SERIAL_OUTPUT_LIMIT`](crate::game_boy::components::mmu::SERIAL_OUTPUT_LIMIT) bytes.
+    pub fn get_serial_output(&self) -> &[u8] {
+        self.mmu.get_serial_output()
+    }
+
+    /// The bytes the game sent over the serial port since the last call, e.g. the text of Blargg's test ROMs
+    pub fn take_serial_output(&mut self) -> Vec<u8> {
+        self.mmu.take_serial_output()
+    }
+
+    /// Plugs a partner into the link port in place of the previous one, transfers then exchange bytes with it.
+    /// Sent bytes aren't captured as serial output while it is connected.
+    pub fn connect_link(&mut self, partner: impl SerialLink + 'static) {
+        self.link_cable.connect(partner);
+        self.mmu.set_serial_capture(false);
+    }
+
+    /// Transfers shift in 0xFF again like without a cable
+    pub fn disconnect_link(&mut self) {
+        self.link_cable.disconnect();
+        self.mmu.set_serial_capture(true);
+    }
+
+    pub fn is_link_connected(&self) -> bool {
+        self.link_cable.is_connected()
+    }
+
+    /// Reads like the emulated CPU, so IO hooks apply and the memory stats count it.
+    /// Reading P1 doesn't count as a joypad poll though, [`step`](Self::step) only counts the polls of the game.
+    pub fn read_memory(&self, address: u16) -> u8 {
+        self.mmu.cpu_read(address)
+    }
+
+    /// Writes like the emulated CPU through the memory map, so MBC and IO register side effects and IO hooks apply
+    pub fn write_memory(&mut self, address: u16, value: u8) {
+        self.mmu.cpu_write(address, value);
+    }
+
+    /// Reads for tooling like debuggers, without side effects and ignoring whether the cartridge RAM is enabled.
+    /// See [`MMU::peek`].
+    pub fn peek(&self, address: u16) -> u8 {
+        self.mmu.peek(address)
+    }
+
+    /// Writes for tooling like debuggers and cheats, the value is stored without side effects.
+    /// ROM addresses patch the mapped ROM bank instead of configuring the MBC. See [`MMU::poke`].
+    pub fn poke(&mut self, address: u16, value: u8) {
+        self.mmu.poke(address, value);
+    }
+
+    pub fn dump_memory(&self, start: u16, length: usize) -> Vec<u8> {
+        self.mmu.dump(start, length)
+    }
+
+    /// See [`MMU::get_region`]
+    pub fn get_memory_region(&self, region: MemoryRegion) -> Option<&[u8]> {
+        self.mmu.get_region(region)
+    }
+
+    pub fn snapshot_memory_region(&self, region: MemoryRegion) -> Vec<u8> {
+        self.mmu.snapshot_region(region)
+    }
+
+    pub fn search_memory(&self, pattern: &[u8]) -> Vec<u16> {
+        self.mmu.search(pattern)
+    }
+
+    /// Counts memory accesses per page, e.g. to find the RAM areas a game uses
+    pub fn set_memory_stats_enabled(&mut self, enabled: bool) {
+        self.mmu.set_stats_enabled(enabled);
+    }
+
+    pub fn get_memory_stats(&self) -> Option<&MemoryStats> {
+        self.mmu.get_stats()
+    }
+
+    pub fn get_pc(&self) -> u16 {
+        self.cpu.get_pc()
+    }
+
+    pub fn get_cpu(&self) -> &CPU {
+        &self.cpu
+    }
+
+    /// Direct register access, e.g. for a debugger register editor
+    pub fn get_cpu_mut(&mut self) -> &mut CPU {
+        &mut self.cpu
+    }
+
+    /// Disassembles up to `count` instructions starting at the given address, using the currently mapped ROM bank
+    pub fn disassemble(&self, address: u16, count: usize) -> Vec<DisassembledInstruction> {
+        Disassembler::iter_from(&self.mmu, None, address)
+            .take(count)
+            .collect()
+    }
+
+    /// RGBA image of all tiles in VRAM, see [`debug::render_tile_data`]
+    pub fn render_tile_data(&self) -> Vec<u8> {
+        debug::render_tile_data(&self.mmu)
+    }
+
+    /// RGBA image of a whole tile map, see [`debug::render_tile_map`]
+    pub fn render_tile_map(&self, map: TileMap) -> Vec<u8> {
+        debug::render_tile_map(&self.mmu, map)
+    }
+
+    pub fn get_tile_info(&self, x: usize, y: usize) -> Option<TileInfo> {
+        debug::get_tile_info(&self.mmu, x, y)
+    }
+
+    pub fn get_tile_map_entry(&self, map: TileMap, x: usize, y: usize) -> Option<TileMapEntry> {
+        debug::get_tile_map_entry(&self.mmu, map, x, y)
+    }
+
+    /// The OAM inspector, every sprite with the line and dot it was first drawn at this frame
+    pub fn get_oam_entries(&self) -> Vec<OamEntry> {
+        debug::get_oam_entries(&self.mmu, self.ppu.get_sprite_hits())
+    }
+
+    /// The palettes and scroll registers each line of the frame buffer was drawn with, indexed by line.
+    /// None for lines that are blank because the LCD was switched off or that weren't drawn yet.
+    pub fn get_scanline_registers(&self) -> &[Option<ScanlineRegisters>] {
+        self.ppu.get_scanline_log().get_lines()
+    }
+
+    pub fn get_cartridge_header(&self) -> &CartridgeHeader {
+        &self.mmu.cartridge_header
+    }
+
+    pub fn get_config(&self) -> &GameBoyConfig {
+        &self.config
+    }
+}
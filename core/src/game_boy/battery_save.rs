@@ -0,0 +1,136 @@
+//! Battery backed cartridge RAM in the `.sav` format used by most emulators.
+//! BGB and VBA append the MBC3 real time clock after the RAM:
+//! https://bgb.bircd.org/rtcsave.html
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// 5 current + 5 latched registers as u32 and a 64-bit UNIX timestamp
+pub const RTC_FOOTER_SIZE: usize = 48;
+/// Older variant of the footer with a 32-bit timestamp
+pub const RTC_FOOTER_SIZE_LEGACY: usize = 44;
+
+/// The MBC3 clock registers: seconds, minutes, hours, day counter low, day counter high/flags
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RtcRegisters {
+    pub seconds: u8,
+    pub minutes: u8,
+    pub hours: u8,
+    pub days_low: u8,
+    pub days_high: u8,
+}
+
+impl RtcRegisters {
+    fn parse(data: &[u8]) -> Self {
+        // Every register is stored as a little endian u32, only the lowest byte is meaningful
+        Self {
+            seconds: data[0],
+            minutes: data[4],
+            hours: data[8],
+            days_low: data[12],
+            days_high: data[16],
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for register in [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            self.days_low,
+            self.days_high,
+        ] {
+            out.extend_from_slice(&(register as u32).to_le_bytes());
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RtcFooter {
+    pub current: RtcRegisters,
+    pub latched: RtcRegisters,
+    /// UNIX time when the save was written, used to advance the clock by the time passed since
+    pub timestamp: u64,
+}
+
+impl RtcFooter {
+    fn parse(data: &[u8]) -> Self {
+        let timestamp = if data.len() == RTC_FOOTER_SIZE {
+            u64::from_le_bytes(data[40..48].try_into().unwrap())
+        } else {
+            u32::from_le_bytes(data[40..44].try_into().unwrap()) as u64
+        };
+
+        Self {
+            current: RtcRegisters::parse(&data[0..20]),
+            latched: RtcRegisters::parse(&data[20..40]),
+            timestamp,
+        }
+    }
+
+    /// Always written in the current 48 byte format
+    fn write(&self, out: &mut Vec<u8>) {
+        self.current.write(out);
+        self.latched.write(out);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BatterySave {
+    pub ram: Vec<u8>,
+    pub rtc: Option<RtcFooter>,
+}
+
+impl BatterySave {
+    /// Splits a `.sav` file into the cartridge RAM and an optional RTC footer.
+    /// Anything else after the RAM is rejected instead of being misread as RAM.
+    pub fn parse(data: &[u8], ram_size: usize) -> Result<Self, Box<dyn Error>> {
+        if data.len() < ram_size {
+            return Err(format!(
+                "Battery save is too small, expected {} bytes of RAM but got {}",
+                ram_size,
+                data.len()
+            )
+            .into());
+        }
+
+        let (ram, trailer) = data.split_at(ram_size);
+        let rtc = match trailer.len() {
+            0 => None,
+            RTC_FOOTER_SIZE | RTC_FOOTER_SIZE_LEGACY => Some(RtcFooter::parse(trailer)),
+            length => {
+                return Err(
+                    format!("Unknown {} byte trailer after the cartridge RAM", length).into(),
+                )
+            }
+        };
+
+        Ok(Self {
+            ram: ram.to_vec(),
+            rtc,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.ram.clone();
+        if let Some(rtc) = &self.rtc {
+            rtc.write(&mut bytes);
+        }
+        bytes
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load(path: &Path, ram_size: usize) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&std::fs::read(path)?, ram_size)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn store(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+}
@@ -0,0 +1,133 @@
+//! Game Genie and GameShark codes. Game Genie codes patch what the game reads from ROM,
+//! GameShark codes write a value to memory at the start of every VBlank.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use serde::{Deserialize, Serialize};
+
+/// What the game reads from a ROM address while a Game Genie code is active
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RomPatch {
+    pub address: u16,
+    pub value: u8,
+    /// The patch only applies while the ROM holds this value, which tells the banks mapped to 0x4000-0x7FFF apart
+    pub compare: Option<u8>,
+}
+
+impl RomPatch {
+    /// The value read from the address, given what the ROM holds there
+    pub fn apply(&self, address: u16, rom_value: u8) -> Option<u8> {
+        let matches =
+            address == self.address && self.compare.is_none_or(|compare| compare == rom_value);
+        matches.then_some(self.value)
+    }
+}
+
+/// A value a GameShark code keeps writing to memory
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RamWrite {
+    /// 0x01 for the memory currently mapped, other types select a bank on hardware.
+    /// Only the mapped bank is written here.
+    pub bank: u8,
+    pub address: u16,
+    pub value: u8,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheatCode {
+    /// `ABC-DEF-GHI` or `ABC-DEF` without compare value
+    GameGenie(RomPatch),
+    /// `TTVVLLHH`: type, value and the little endian address
+    GameShark(RamWrite),
+}
+
+impl CheatCode {
+    /// Accepts upper and lower case, the dashes of Game Genie codes are optional
+    pub fn parse(code: &str) -> Result<Self, Box<dyn Error>> {
+        let invalid = || {
+            format!("Invalid cheat code '{code}', expected a Game Genie code like 00A-17B-C49 or a GameShark code like 010238CD")
+        };
+        let is_game_genie = code.contains('-');
+        let digits: Vec<u8> = code
+            .chars()
+            .filter(|character| *character != '-')
+            .map(|character| character.to_digit(16).map(|digit| digit as u8))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+
+        match (is_game_genie, digits.len()) {
+            (false, 8) => Ok(Self::GameShark(decode_game_shark(&digits)?)),
+            (_, 6 | 9) => Ok(Self::GameGenie(decode_game_genie(&digits)?)),
+            _ => Err(invalid().into()),
+        }
+    }
+}
+
+/// `ABC-DEF-GHI`: AB is the value, the address is F inverted followed by CDE
+/// and the compare value is GI rotated right by 2 and XORed with 0xBA. H is not used.
+fn decode_game_genie(digits: &[u8]) -> Result<RomPatch, Box<dyn Error>> {
+    let value = digits[0] << 4 | digits[1];
+    let address = ((digits[5] ^ 0xF) as u16) << 12
+        | (digits[2] as u16) << 8
+        | (digits[3] as u16) << 4
+        | digits[4] as u16;
+    if address > 0x7FFF {
+        return Err(format!("Game Genie codes can only patch ROM, not 0x{address:04X}").into());
+    }
+    let compare = (digits.len() == 9).then(|| (digits[6] << 4 | digits[8]).rotate_right(2) ^ 0xBA);
+    Ok(RomPatch {
+        address,
+        value,
+        compare,
+    })
+}
+
+fn decode_game_shark(digits: &[u8]) -> Result<RamWrite, Box<dyn Error>> {
+    let byte = |index: usize| digits[index] << 4 | digits[index + 1];
+    let address = u16::from_le_bytes([byte(4), byte(6)]);
+    if address < 0x8000 {
+        return Err(
+            format!("GameShark codes can only write memory, not ROM at 0x{address:04X}").into(),
+        );
+    }
+    Ok(RamWrite {
+        bank: byte(0),
+        value: byte(2),
+        address,
+    })
+}
+
+/// A code as the user entered it, stored in the game's profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cheat {
+    pub code: String,
+    /// Shown next to the code, e.g. what the code does
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Cheat {
+    /// Fails if the code isn't valid, it is stored in upper case without surrounding whitespace
+    pub fn new(code: &str) -> Result<Self, Box<dyn Error>> {
+        let code = code.trim().to_ascii_uppercase();
+        CheatCode::parse(&code)?;
+        Ok(Self {
+            code,
+            name: String::new(),
+            enabled: true,
+        })
+    }
+
+    pub fn parse_code(&self) -> Result<CheatCode, Box<dyn Error>> {
+        CheatCode::parse(self.code.trim())
+    }
+}
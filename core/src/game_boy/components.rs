@@ -0,0 +1,10 @@
+pub mod cartridge;
+pub mod component;
+pub mod cpu;
+pub mod dma;
+pub mod interrupt_controller;
+pub mod joypad;
+pub mod mmu;
+pub mod ppu;
+pub mod serial;
+pub mod timer;
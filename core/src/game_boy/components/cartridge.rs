@@ -0,0 +1,44 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::mmu::ROM_BANK_SIZE;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+pub mod header;
+pub mod rom_builder;
+pub mod types;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Cartridge {
+    pub rom_banks: Vec<[u8; ROM_BANK_SIZE]>,
+    pub header: CartridgeHeader,
+}
+
+impl Cartridge {
+    #[cfg(feature = "std")]
+    pub fn load(path: PathBuf) -> Result<Cartridge, Box<dyn Error>> {
+        let data = std::fs::read(path)?;
+        Self::from_bytes(&data)
+    }
+
+    /// Builds a cartridge from raw ROM data, without touching the file system
+    pub fn from_bytes(data: &[u8]) -> Result<Cartridge, Box<dyn Error>> {
+        let header = CartridgeHeader::parse(data)?;
+
+        // Only populated banks are kept, the MBC mirrors them if the header declares more
+        let bank_count = header.rom_size.min(data.len().div_ceil(ROM_BANK_SIZE));
+        let mut rom_banks = Vec::with_capacity(bank_count);
+        for bank_index in 0..bank_count {
+            let mut bank = [0u8; ROM_BANK_SIZE];
+            let start = bank_index * ROM_BANK_SIZE;
+            let end = (start + ROM_BANK_SIZE).min(data.len());
+            bank[..(end - start)].copy_from_slice(&data[start..end]);
+
+            rom_banks.push(bank);
+        }
+
+        Ok(Cartridge { rom_banks, header })
+    }
+}
@@ -1,13 +1,19 @@
 use crate::game_boy::components::cartridge::types::{
     CartridgeCGBFlag, CartridgeDestinationCode, CartridgeType,
 };
+use crate::game_boy::components::mmu::mbc::Mbc;
+use crate::game_boy::components::mmu::{RAM_BANK_SIZE, ROM_BANK_SIZE};
 use crate::helpers::bit_operations::construct_u16;
 use crate::instructions::Instruction;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Debug;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::fmt::Debug;
 
-const NINTENDO_LOGO: [u8; 48] = [
+pub const NINTENDO_LOGO: [u8; 48] = [
     0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
     0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
     0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
@@ -41,7 +47,7 @@ impl CartridgeHeader {
         }
 
         let header = Self {
-            entry_point: Self::parse_entry_point(rom[0x100..=0x103].try_into()?)?,
+            entry_point: Self::parse_entry_point(rom[0x100..=0x103].try_into()?),
             valid_nintendo_logo: Self::parse_nintendo_logo(rom[0x104..=0x133].try_into()?),
             title: Self::parse_ascii(&rom[0x134..=0x143]),
             manufacturer_code: Self::parse_ascii(&rom[0x13F..=0x142]),
@@ -59,9 +65,25 @@ impl CartridgeHeader {
         Ok(header)
     }
 
-    fn parse_entry_point(entry_point: &[u8; 4]) -> Result<Vec<String>, Box<dyn Error>> {
+    /// Over the bytes 0x134-0x14C, the boot ROM locks up if it doesn't match
+    pub fn compute_header_checksum(rom: &[u8]) -> u8 {
+        rom[0x134..0x14D].iter().fold(0u8, |checksum, &byte| {
+            checksum.wrapping_sub(byte).wrapping_sub(1)
+        })
+    }
+
+    /// The sum of all bytes except for the global checksum itself, no hardware checks it
+    pub fn compute_global_checksum(rom: &[u8]) -> u16 {
+        rom.iter()
+            .enumerate()
+            .filter(|(address, _)| !(0x14E..=0x14F).contains(address))
+            .fold(0u16, |checksum, (_, &byte)| {
+                checksum.wrapping_add(byte as u16)
+            })
+    }
+
+    fn parse_entry_point(entry_point: &[u8; 4]) -> Vec<String> {
         Instruction::parse_clear_text_instructions_from_data(entry_point, true)
-            .map_err(|e| format!("Unable to parse cartridge entry point: {}", e).into())
     }
 
     fn parse_nintendo_logo(data: &[u8; 48]) -> bool {
@@ -339,4 +361,41 @@ impl CartridgeHeader {
     fn parse_global_checksum(data: [u8; 2]) -> u16 {
         construct_u16(data[1], data[0])
     }
+
+    /// What the `info` command prints, one property per line
+    pub fn describe(&self) -> String {
+        let capabilities = self.cartridge_type.capabilities();
+        let capabilities = if capabilities.is_empty() {
+            "none".to_string()
+        } else {
+            capabilities.join(", ")
+        };
+        let emulated = if Mbc::initialize(self.cartridge_type).is_ok() {
+            ""
+        } else {
+            ", not emulated"
+        };
+        [
+            format!("Title:        {}", self.title),
+            format!("Licensee:     {}", self.licensee),
+            format!(
+                "Type:         {:?} ({:?}{emulated})",
+                self.cartridge_type,
+                self.cartridge_type.mapper_kind()
+            ),
+            format!("Capabilities: {capabilities}"),
+            format!(
+                "ROM:          {} banks, {} KiB",
+                self.rom_size,
+                self.rom_size * ROM_BANK_SIZE / 1024
+            ),
+            format!(
+                "RAM:          {} banks, {} KiB",
+                self.ram_size,
+                self.ram_size * RAM_BANK_SIZE / 1024
+            ),
+            format!("CGB flag:     {:?}", self.cgb_flag),
+        ]
+        .join("\n")
+    }
 }
@@ -0,0 +1,69 @@
+//! Assembles small ROMs with a valid header from hand written code and data.
+//! https://gbdev.io/pandocs/The_Cartridge_Header.html
+
+use crate::game_boy::components::cartridge::header::{CartridgeHeader, NINTENDO_LOGO};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 2 banks of 16 KiB, the smallest ROM size
+const ROM_SIZE: usize = 0x8000;
+const ENTRY_POINT: usize = 0x100;
+const LOGO_START: usize = 0x104;
+const TITLE_START: usize = 0x134;
+/// The last title byte is the CGB flag, so it is left out
+const TITLE_LENGTH: usize = 15;
+const HEADER_CHECKSUM_ADDRESS: usize = 0x14D;
+const GLOBAL_CHECKSUM_ADDRESS: usize = 0x14E;
+/// Where the entry point jumps to, right after the header
+pub const PROGRAM_START: u16 = 0x150;
+
+/// A 32 KiB ROM without MBC or RAM, the checksums are filled in by [`RomBuilder::build`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RomBuilder {
+    rom: Vec<u8>,
+}
+
+impl RomBuilder {
+    pub fn new() -> Self {
+        let mut rom = vec![0u8; ROM_SIZE];
+        // NOP / JP 0x0150
+        rom[ENTRY_POINT..ENTRY_POINT + 4].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]);
+        rom[LOGO_START..LOGO_START + NINTENDO_LOGO.len()].copy_from_slice(&NINTENDO_LOGO);
+        Self { rom }
+    }
+
+    /// Upper case ASCII, cut off after 15 characters
+    pub fn title(mut self, title: &str) -> Self {
+        let title = &title.as_bytes()[..title.len().min(TITLE_LENGTH)];
+        self.rom[TITLE_START..TITLE_START + TITLE_LENGTH].fill(0);
+        self.rom[TITLE_START..TITLE_START + title.len()].copy_from_slice(title);
+        self
+    }
+
+    /// Places code or data at the given ROM address, panics if it doesn't fit into the ROM
+    pub fn bytes(mut self, address: u16, bytes: &[u8]) -> Self {
+        let start = address as usize;
+        self.rom[start..start + bytes.len()].copy_from_slice(bytes);
+        self
+    }
+
+    /// The code the entry point jumps to
+    pub fn program(self, program: &[u8]) -> Self {
+        self.bytes(PROGRAM_START, program)
+    }
+
+    pub fn build(mut self) -> Vec<u8> {
+        self.rom[HEADER_CHECKSUM_ADDRESS] = CartridgeHeader::compute_header_checksum(&self.rom);
+        // Stored big endian
+        let global_checksum = CartridgeHeader::compute_global_checksum(&self.rom);
+        self.rom[GLOBAL_CHECKSUM_ADDRESS..GLOBAL_CHECKSUM_ADDRESS + 2]
+            .copy_from_slice(&global_checksum.to_be_bytes());
+        self.rom
+    }
+}
+
+impl Default for RomBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
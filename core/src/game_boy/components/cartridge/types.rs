@@ -1,8 +1,11 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 /// This will tell the MMU how to behave during memory access
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MbcType {
     Unsupported(CartridgeType),
     // 32 KiB ROM, optionally 8 KiB of RAM
@@ -13,35 +16,7 @@ pub enum MbcType {
     MBC5,
     MBC6,
     MBC7,
-}
-
-impl From<CartridgeType> for MbcType {
-    fn from(value: CartridgeType) -> Self {
-        match value {
-            CartridgeType::RomOnly => MbcType::None,
-            CartridgeType::MBC1
-            | CartridgeType::MBC1Ram
-            | CartridgeType::MBC1RamBattery
-            | CartridgeType::MMM01
-            | CartridgeType::MMM01Ram
-            | CartridgeType::MMM01RamBattery => MbcType::MBC1,
-            CartridgeType::MBC2 | CartridgeType::MBC2Battery => MbcType::MBC2,
-            CartridgeType::MBC3
-            | CartridgeType::MBC3Ram
-            | CartridgeType::MBC3RamBattery
-            | CartridgeType::MBC3TimerBattery
-            | CartridgeType::MBC3TimerRamBattery => MbcType::MBC3,
-            CartridgeType::MBC5
-            | CartridgeType::MBC5Ram
-            | CartridgeType::MBC5RamBattery
-            | CartridgeType::MBC5Rumble
-            | CartridgeType::MBC5RumbleRam
-            | CartridgeType::MBC5RumbleRamBattery => MbcType::MBC5,
-            CartridgeType::MBC6 => MbcType::MBC6,
-            CartridgeType::MBC7SensorRumbleRamBattery => MbcType::MBC7,
-            _ => MbcType::Unsupported(value),
-        }
-    }
+    Camera,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
@@ -120,6 +95,119 @@ impl TryFrom<u8> for CartridgeType {
     }
 }
 
+impl CartridgeType {
+    /// The memory bank controller, MMM01 cartridges are treated like MBC1
+    pub fn mapper_kind(&self) -> MbcType {
+        match self {
+            CartridgeType::RomOnly => MbcType::None,
+            CartridgeType::MBC1
+            | CartridgeType::MBC1Ram
+            | CartridgeType::MBC1RamBattery
+            | CartridgeType::MMM01
+            | CartridgeType::MMM01Ram
+            | CartridgeType::MMM01RamBattery => MbcType::MBC1,
+            CartridgeType::MBC2 | CartridgeType::MBC2Battery => MbcType::MBC2,
+            CartridgeType::MBC3
+            | CartridgeType::MBC3Ram
+            | CartridgeType::MBC3RamBattery
+            | CartridgeType::MBC3TimerBattery
+            | CartridgeType::MBC3TimerRamBattery => MbcType::MBC3,
+            CartridgeType::MBC5
+            | CartridgeType::MBC5Ram
+            | CartridgeType::MBC5RamBattery
+            | CartridgeType::MBC5Rumble
+            | CartridgeType::MBC5RumbleRam
+            | CartridgeType::MBC5RumbleRamBattery => MbcType::MBC5,
+            CartridgeType::MBC6 => MbcType::MBC6,
+            CartridgeType::MBC7SensorRumbleRamBattery => MbcType::MBC7,
+            CartridgeType::PocketCamera => MbcType::Camera,
+            _ => MbcType::Unsupported(*self),
+        }
+    }
+
+    /// Cartridge RAM, including the 512 half bytes built into the MBC2 and the EEPROM of the MBC7
+    pub fn has_ram(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC1Ram
+                | CartridgeType::MBC1RamBattery
+                | CartridgeType::MBC2
+                | CartridgeType::MBC2Battery
+                | CartridgeType::RomRam
+                | CartridgeType::RomRamBattery
+                | CartridgeType::MMM01Ram
+                | CartridgeType::MMM01RamBattery
+                | CartridgeType::MBC3TimerRamBattery
+                | CartridgeType::MBC3Ram
+                | CartridgeType::MBC3RamBattery
+                | CartridgeType::MBC5Ram
+                | CartridgeType::MBC5RamBattery
+                | CartridgeType::MBC5RumbleRam
+                | CartridgeType::MBC5RumbleRamBattery
+                | CartridgeType::MBC7SensorRumbleRamBattery
+                | CartridgeType::PocketCamera
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    /// The RAM or the real time clock keep their contents without power, so they belong into a `.sav` file
+    pub fn has_battery(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC1RamBattery
+                | CartridgeType::MBC2Battery
+                | CartridgeType::RomRamBattery
+                | CartridgeType::MMM01RamBattery
+                | CartridgeType::MBC3TimerBattery
+                | CartridgeType::MBC3TimerRamBattery
+                | CartridgeType::MBC3RamBattery
+                | CartridgeType::MBC5RamBattery
+                | CartridgeType::MBC5RumbleRamBattery
+                | CartridgeType::MBC7SensorRumbleRamBattery
+                | CartridgeType::PocketCamera
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    /// A real time clock, which isn't emulated yet
+    pub fn has_rtc(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC3TimerBattery
+                | CartridgeType::MBC3TimerRamBattery
+                | CartridgeType::HuC3
+        )
+    }
+
+    /// Cartridges with a rumble motor, which the game switches through the MBC.
+    /// Only the motor of MBC5 cartridges is emulated so far, the one of MBC7 cartridges never runs.
+    pub fn has_rumble(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC5Rumble
+                | CartridgeType::MBC5RumbleRam
+                | CartridgeType::MBC5RumbleRamBattery
+                | CartridgeType::MBC7SensorRumbleRamBattery
+        )
+    }
+
+    /// The names of the extra hardware on the cartridge, e.g. `["RAM", "Battery"]`
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        [
+            (self.has_ram(), "RAM"),
+            (self.has_battery(), "Battery"),
+            (self.has_rtc(), "RTC"),
+            (self.has_rumble(), "Rumble"),
+        ]
+        .into_iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CartridgeCGBFlag {
     #[default]
@@ -0,0 +1,26 @@
+use crate::game_boy::components::mmu::MMU;
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use alloc::boxed::Box;
+use core::error::Error;
+
+/// A part of the Game Boy which runs alongside the CPU, driven by the cycles each CPU step consumed.
+/// The [`GameBoy`](crate::game_boy::GameBoy) resets, ticks, saves and loads all of them the same way,
+/// a new component only has to implement this and be added to those four places.
+/// The CPU drives the cycles and the MMU is the bus, so neither of them is a component.
+pub trait Component {
+    /// What the component stores in a [`GameBoySaveState`](crate::game_boy::save_state::GameBoySaveState)
+    type SaveState;
+
+    /// Back to the state right after the boot ROM, registered listeners are kept
+    fn reset(&mut self, config: &GameBoyConfig);
+
+    /// Advances by the given cycles, interrupts are requested through the MMU.
+    /// Returns true if the event the component times happened, e.g. a finished frame or a TIMA overflow.
+    fn tick(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool;
+
+    fn save(&self) -> Self::SaveState;
+
+    /// Restores a saved state, registered listeners and settings which aren't part of the state are kept
+    fn load(&mut self, state: Self::SaveState) -> Result<(), Box<dyn Error>>;
+}
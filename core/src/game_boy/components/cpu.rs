@@ -1,15 +1,23 @@
 use crate::enums::parameter_groups::R16Stack;
 use crate::enums::parameter_groups::{JumpCondition, R16Mem, R16, R8};
+#[cfg(test)]
 use crate::game_boy::components::cpu::builder::CpuBuilder;
 use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
-use crate::game_boy::components::mmu::{IF_ADDRESS, MMU};
+use crate::game_boy::components::mmu::MMU;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::hardware_model::HardwareModel;
 use crate::helpers::bit_operations::*;
 use crate::instructions::Instruction;
-use log::debug;
+use crate::logging::Subsystem;
+use log::{debug, log_enabled, warn, Level};
 use registers::CPURegisters;
 use serde::{Deserialize, Serialize};
 
-mod builder;
+#[cfg(test)]
+pub(crate) mod builder;
+pub mod doctor;
+#[cfg(feature = "opcode-coverage")]
+pub mod opcode_coverage;
 pub mod registers;
 
 /// This tells the CPU that the next instruction to be executed is a prefixed instruction
@@ -27,16 +35,20 @@ pub struct CPU {
     eeping: bool,
     /// This is true when the program counter should not be incremented
     halting_bug_active: bool,
+    /// Set by an illegal opcode, the CPU hangs and ignores interrupts until it is reset
+    locked: bool,
 }
 
 impl CPU {
-    pub fn builder() -> CpuBuilder {
+    #[cfg(test)]
+    pub(crate) fn builder() -> CpuBuilder {
         CpuBuilder::new()
     }
 
-    pub fn initialize() -> Self {
+    /// The state the boot ROM of the model leaves behind, `rom` has to start at 0x0000 of ROM bank 0
+    pub fn initialize(model: HardwareModel, rom: &[u8]) -> Self {
         Self {
-            registers: CPURegisters::initialize(),
+            registers: model.get_cpu_registers(rom),
             ..Default::default()
         }
     }
@@ -121,33 +133,60 @@ impl CPU {
         }
     }
 
-    pub fn step(&mut self, mmu: &mut MMU) -> u8 {
+    pub fn step(&mut self, mmu: &mut MMU) -> Cycles {
         // This helps checking if the deferred set of the ime was already scheduled before the current instruction
         let initial_deferred_set_ime = self.get_deferred_set_ime();
 
-        let has_interrupt = self.ime && self.handle_interrupts(mmu);
-        if has_interrupt {
-            self.eeping = false;
-            return 5; // The interrupt handling takes 5 m-cycles
+        if self.locked {
+            return Cycles::from_m(1);
         }
 
-        if self.eeping && !self.ime && self.is_interrupt_pending(mmu) {
+        // Leaving HALT takes an extra m-cycle, before servicing the interrupt as well as before continuing
+        // https://gbdev.io/pandocs/Interrupts.html#interrupt-handling
+        let wake_up_cycles = if self.eeping && self.is_interrupt_pending(mmu) {
             self.eeping = false;
+            Cycles::from_m(1)
         } else if self.eeping {
-            return 1; // Just stall a cycle
+            return Cycles::from_m(1); // Just stall a cycle
+        } else {
+            Cycles::ZERO
+        };
+
+        let has_interrupt = self.ime && self.handle_interrupts(mmu);
+        if has_interrupt {
+            return Cycles::from_m(5) + wake_up_cycles; // The interrupt handling takes 5 m-cycles
         }
 
-        let mut instruction_byte = mmu.read(self.get_pc());
+        let mut instruction_byte = mmu.cpu_read(self.get_pc());
         let prefixed = instruction_byte == PREFIX_INSTRUCTION_BYTE;
         if prefixed {
-            instruction_byte = mmu.read(self.get_pc().wrapping_add(1));
+            instruction_byte = mmu.cpu_read(self.get_pc().wrapping_add(1));
         }
 
-        let instruction = Instruction::from_byte(instruction_byte, prefixed).unwrap();
-        if self.should_trigger_halting_bug(&instruction, mmu) {
+        let Some(instruction) = Instruction::decode(instruction_byte, prefixed) else {
+            warn!(
+                target: Subsystem::Cpu.target(),
+                "Illegal opcode 0x{:02X} at PC(0x{:04X}), locking up",
+                instruction_byte,
+                self.get_pc()
+            );
+            self.locked = true;
+            return Cycles::from_m(1);
+        };
+        #[cfg(feature = "opcode-coverage")]
+        opcode_coverage::record(instruction_byte, prefixed);
+        // The halting bug can't chain, so this recurses at most once
+        if !self.halting_bug_active && self.should_trigger_halting_bug(&instruction, mmu) {
+            // With EI right before, IME is set as the HALT completes and the interrupt is serviced instead.
+            // The HALT didn't advance the PC, so the handler returns to it and it's executed again.
+            if initial_deferred_set_ime && self.deferred_set_ime {
+                self.deferred_set_ime = false;
+                self.ime = true;
+                return Cycles::from_m(1) + wake_up_cycles;
+            }
             self.set_pc(self.get_pc().wrapping_add(1));
             self.halting_bug_active = true;
-            return self.step(mmu);
+            return self.step(mmu) + wake_up_cycles;
         }
 
         self.log_instruction_execute(&instruction, instruction_byte, mmu);
@@ -164,11 +203,11 @@ impl CPU {
             self.ime = true;
         }
 
-        m_cycles
+        Cycles::from_m(m_cycles as u32) + wake_up_cycles
     }
 
     fn is_interrupt_pending(&self, mmu: &MMU) -> bool {
-        mmu.get_interrupt().is_some()
+        mmu.interrupts().has_pending()
     }
 
     /// https://gbdev.io/pandocs/halt.html#halt
@@ -182,13 +221,18 @@ impl CPU {
     }
 
     fn handle_interrupts(&mut self, mmu: &mut MMU) -> bool {
-        let Some(interrupt) = mmu.get_interrupt() else {
+        let Some(interrupt) = mmu.interrupts_mut().acknowledge() else {
             return false;
         };
-
-        let new_i_flag = set_bit_u8(mmu.read(IF_ADDRESS), interrupt.get_if_index(), false);
-        mmu.write(IF_ADDRESS, new_i_flag);
+        // An EI right before the dispatch must not enable the interrupts again inside the handler
         self.ime = false;
+        self.deferred_set_ime = false;
+        debug!(
+            target: Subsystem::Interrupt.target(),
+            "Servicing {:?} interrupt at PC(0x{:04X})",
+            interrupt,
+            self.get_pc()
+        );
 
         self.push_u16(self.get_pc(), mmu);
         self.set_pc(interrupt.get_target_address());
@@ -204,14 +248,24 @@ impl CPU {
         self.ime
     }
 
-    pub fn get_deferred_set_ime(&self) -> bool {
+    pub(crate) fn get_deferred_set_ime(&self) -> bool {
         self.deferred_set_ime
     }
+
+    /// True while the CPU sleeps after HALT
+    pub fn is_halted(&self) -> bool {
+        self.eeping
+    }
+
+    /// True after executing an illegal opcode, only a reset recovers from this
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
 }
 
 /// Direct instruction interfaces
 impl CPU {
-    pub fn add_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn add_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let (new_value, half_carry, carry) = add_u8(self.get_a(), source_value);
 
@@ -225,7 +279,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn add_carry_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn add_carry_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let (new_value, half_carry, carry) =
             add_carry_u8(self.get_a(), source_value, self.get_f_carry());
@@ -240,7 +294,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn add_hl_r16(&mut self, r16: R16) -> (u16, u8) {
+    pub(crate) fn add_hl_r16(&mut self, r16: R16) -> (u16, u8) {
         let source_value = self.get_r16(r16);
         let (new_value, half_carry, carry) = add_u16(self.get_hl(), source_value);
 
@@ -252,7 +306,7 @@ impl CPU {
         self.instruction_result(1, 2)
     }
 
-    pub fn add_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn add_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let (new_value, half_carry, carry) = add_u8(self.get_a(), source_value);
 
@@ -265,7 +319,7 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn add_carry_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn add_carry_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let (new_value, half_carry, carry) =
             add_carry_u8(self.get_a(), source_value, self.get_f_carry());
@@ -279,7 +333,7 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn and_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn and_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let new_value = self.get_a() & source_value;
 
@@ -293,7 +347,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn and_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn and_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let new_value = self.get_a() & source_value;
 
@@ -306,7 +360,7 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn add_sp_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn add_sp_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let value = self.read_next_imm8_signed(mmu);
         let (result, half_carry, carry) = add_u16_i8(self.get_sp(), value);
         self.set_sp(result);
@@ -318,7 +372,12 @@ impl CPU {
         self.instruction_result(2, 4)
     }
 
-    pub fn bit_check_r8(&mut self, bit_index: usize, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn bit_check_r8(
+        &mut self,
+        bit_index: usize,
+        register: R8,
+        mmu: &mut MMU,
+    ) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         self.set_f_zero(!get_bit_u8(value, bit_index));
         self.set_f_subtract(false);
@@ -328,7 +387,12 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn bit_reset_r8(&mut self, bit_index: usize, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn bit_reset_r8(
+        &mut self,
+        bit_index: usize,
+        register: R8,
+        mmu: &mut MMU,
+    ) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let new_value = set_bit_u8(value, bit_index, false);
         self.set_r8(register, new_value, mmu);
@@ -336,7 +400,12 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn bit_set_r8(&mut self, bit_index: usize, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn bit_set_r8(
+        &mut self,
+        bit_index: usize,
+        register: R8,
+        mmu: &mut MMU,
+    ) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let new_value = set_bit_u8(value, bit_index, true);
         self.set_r8(register, new_value, mmu);
@@ -344,23 +413,24 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn call(&mut self, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn call(&mut self, mmu: &mut MMU) -> (u16, u8) {
         let func_address = self.read_next_imm16(mmu);
         self.push_u16(self.get_pc().wrapping_add(3), mmu);
         (func_address, 6)
     }
 
-    pub fn call_conditional(&mut self, jump_condition: JumpCondition, mmu: &mut MMU) -> (u16, u8) {
-        let should_jump = self.check_jump_condition(jump_condition);
-
-        if should_jump {
-            self.call(mmu)
-        } else {
-            self.instruction_result(3, 3)
-        }
+    pub(crate) fn call_conditional(
+        &mut self,
+        jump_condition: JumpCondition,
+        mmu: &mut MMU,
+    ) -> (u16, u8) {
+        let target = self
+            .check_jump_condition(jump_condition)
+            .then(|| self.call(mmu).0);
+        self.conditional_result(Instruction::CallCondition(jump_condition), target)
     }
 
-    pub fn compare_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn compare_r8(&mut self, r8: R8, mmu: &MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let (ignored_result, half_carry, carry) = sub_u8(self.get_a(), source_value);
 
@@ -373,7 +443,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn compare_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn compare_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let (ignored_result, half_carry, carry) = sub_u8(self.get_a(), source_value);
 
@@ -385,21 +455,21 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn complement_a(&mut self) -> (u16, u8) {
+    pub(crate) fn complement_a(&mut self) -> (u16, u8) {
         self.set_a(!self.get_a());
         self.set_f_subtract(true);
         self.set_f_half_carry(true);
         self.instruction_result(1, 1)
     }
 
-    pub fn complement_carry(&mut self) -> (u16, u8) {
+    pub(crate) fn complement_carry(&mut self) -> (u16, u8) {
         self.set_f_carry(!self.get_f_carry());
         self.set_f_subtract(false);
         self.set_f_half_carry(false);
         self.instruction_result(1, 1)
     }
 
-    pub fn decimal_adjust_accumulator(&mut self) -> (u16, u8) {
+    pub(crate) fn decimal_adjust_accumulator(&mut self) -> (u16, u8) {
         let current_a = self.get_a();
         let mut new_carry = self.get_f_carry();
         let mut adjustment: u8 = 0;
@@ -432,7 +502,7 @@ impl CPU {
         self.instruction_result(1, 1)
     }
 
-    pub fn decrement_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn decrement_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(r8, mmu);
         let (new_value, half_carry, _) = sub_u8(value, 1);
 
@@ -445,28 +515,28 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn decrement_r16(&mut self, r16: R16) -> (u16, u8) {
+    pub(crate) fn decrement_r16(&mut self, r16: R16) -> (u16, u8) {
         self.set_r16(r16, self.get_r16(r16).wrapping_sub(1));
         self.instruction_result(1, 2)
     }
 
-    pub fn disable_interrupts(&mut self) -> (u16, u8) {
+    pub(crate) fn disable_interrupts(&mut self) -> (u16, u8) {
         self.ime = false;
         self.deferred_set_ime = false;
         self.instruction_result(1, 1)
     }
 
-    pub fn enable_interrupts(&mut self) -> (u16, u8) {
+    pub(crate) fn enable_interrupts(&mut self) -> (u16, u8) {
         self.deferred_set_ime = true;
         self.instruction_result(1, 1)
     }
 
-    pub fn halt(&mut self) -> (u16, u8) {
+    pub(crate) fn halt(&mut self) -> (u16, u8) {
         self.eeping = true;
         self.instruction_result(1, 1)
     }
 
-    pub fn increment_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn increment_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(r8, mmu);
         let (new_value, half_carry, _) = add_u8(value, 1);
 
@@ -479,36 +549,36 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn increment_r16(&mut self, r16: R16) -> (u16, u8) {
+    pub(crate) fn increment_r16(&mut self, r16: R16) -> (u16, u8) {
         self.set_r16(r16, self.get_r16(r16).wrapping_add(1));
         self.instruction_result(1, 2)
     }
 
-    pub fn load_r16_imm(&mut self, r16: R16, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn load_r16_imm(&mut self, r16: R16, mmu: &MMU) -> (u16, u8) {
         let value = self.read_next_imm16(mmu);
         self.set_r16(r16, value);
         self.instruction_result(3, 3)
     }
 
-    pub fn load_a_r16m(&mut self, r16_m: R16Mem, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_a_r16m(&mut self, r16_m: R16Mem, mmu: &mut MMU) -> (u16, u8) {
         let address = self.get_r16_mem(r16_m);
-        let value = mmu.read(address);
+        let value = mmu.cpu_read(address);
         self.set_a(value);
 
         self.process_r16m_register_update(r16_m);
         self.instruction_result(1, 2)
     }
 
-    pub fn load_r16m_a(&mut self, r16_m: R16Mem, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_r16m_a(&mut self, r16_m: R16Mem, mmu: &mut MMU) -> (u16, u8) {
         let address = self.get_r16_mem(r16_m);
         let value = self.get_a();
-        mmu.write(address, value);
+        mmu.cpu_write(address, value);
 
         self.process_r16m_register_update(r16_m);
         self.instruction_result(1, 2)
     }
 
-    pub fn load_r8_imm8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_r8_imm8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.read_next_imm8(mmu);
         self.set_r8(r8, value, mmu);
 
@@ -516,7 +586,7 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn load_r8_r8(&mut self, target_r8: R8, source_r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_r8_r8(&mut self, target_r8: R8, source_r8: R8, mmu: &mut MMU) -> (u16, u8) {
         if target_r8 == R8::HL && source_r8 == R8::HL {
             return self.halt();
         }
@@ -532,54 +602,54 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn load_high_a_c(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn load_high_a_c(&mut self, mmu: &MMU) -> (u16, u8) {
         let address = construct_u16(self.get_c(), 0xFF);
-        self.set_a(mmu.read(address));
+        self.set_a(mmu.cpu_read(address));
         self.instruction_result(1, 2)
     }
 
-    pub fn load_high_c_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_high_c_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
         let address = construct_u16(self.get_c(), 0xFF);
-        mmu.write(address, self.get_a());
+        mmu.cpu_write(address, self.get_a());
         self.instruction_result(1, 2)
     }
 
-    pub fn load_high_a_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn load_high_a_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let lsb = self.read_next_imm8(mmu);
         let address = construct_u16(lsb, 0xFF);
-        self.set_a(mmu.read(address));
+        self.set_a(mmu.cpu_read(address));
         self.instruction_result(2, 3)
     }
 
-    pub fn load_high_imm8_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_high_imm8_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
         let lsb = self.read_next_imm8(mmu);
         let address = construct_u16(lsb, 0xFF);
-        mmu.write(address, self.get_a());
+        mmu.cpu_write(address, self.get_a());
         self.instruction_result(2, 3)
     }
 
-    pub fn load_a_imm16(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn load_a_imm16(&mut self, mmu: &MMU) -> (u16, u8) {
         let address = self.read_next_imm16(mmu);
-        self.set_a(mmu.read(address));
+        self.set_a(mmu.cpu_read(address));
         self.instruction_result(3, 4)
     }
 
-    pub fn load_imm16_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_imm16_a(&mut self, mmu: &mut MMU) -> (u16, u8) {
         let address = self.read_next_imm16(mmu);
-        mmu.write(address, self.get_a());
+        mmu.cpu_write(address, self.get_a());
         self.instruction_result(3, 4)
     }
 
-    pub fn load_imm16_sp(&mut self, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn load_imm16_sp(&mut self, mmu: &mut MMU) -> (u16, u8) {
         let address = self.read_next_imm16(mmu);
         let (sp_lsb, sp_msb) = deconstruct_u16(self.get_sp());
-        mmu.write(address, sp_lsb);
-        mmu.write(address.wrapping_add(1), sp_msb);
+        mmu.cpu_write(address, sp_lsb);
+        mmu.cpu_write(address.wrapping_add(1), sp_msb);
 
         self.instruction_result(3, 5)
     }
 
-    pub fn load_hl_sp_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn load_hl_sp_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let value = self.read_next_imm8_signed(mmu);
         let (new_hl, half_carry, carry) = add_u16_i8(self.get_sp(), value);
 
@@ -592,49 +662,47 @@ impl CPU {
         self.instruction_result(2, 3)
     }
 
-    pub fn load_sp_hl(&mut self) -> (u16, u8) {
+    pub(crate) fn load_sp_hl(&mut self) -> (u16, u8) {
         self.set_sp(self.get_hl());
         self.instruction_result(1, 2)
     }
 
-    pub fn jump_hl(&mut self) -> (u16, u8) {
+    pub(crate) fn jump_hl(&mut self) -> (u16, u8) {
         let new_pc = self.get_hl();
         (new_pc, 1)
     }
 
-    pub fn jump_imm16(&self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn jump_imm16(&self, mmu: &MMU) -> (u16, u8) {
         let new_pc = self.read_next_imm16(mmu);
         (new_pc, 4)
     }
 
-    pub fn jump_condition_imm16(&self, condition: JumpCondition, mmu: &MMU) -> (u16, u8) {
-        let should_jump = self.check_jump_condition(condition);
-
-        if should_jump {
-            self.jump_imm16(mmu)
-        } else {
-            self.instruction_result(3, 3)
-        }
+    pub(crate) fn jump_condition_imm16(&self, condition: JumpCondition, mmu: &MMU) -> (u16, u8) {
+        let target = self
+            .check_jump_condition(condition)
+            .then(|| self.jump_imm16(mmu).0);
+        self.conditional_result(Instruction::JpCondImm16(condition), target)
     }
 
-    pub fn jump_relative_imm8(&self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn jump_relative_imm8(&self, mmu: &MMU) -> (u16, u8) {
         let value = self.read_next_imm8_signed(mmu);
         let (new_pc, _, _) = add_u16_i8(self.get_pc(), value);
         let new_pc = new_pc.wrapping_add(2); // The pc increments that occurred due to this instruction
         (new_pc, 3)
     }
 
-    pub fn jump_relative_condition_imm8(&self, condition: JumpCondition, mmu: &MMU) -> (u16, u8) {
-        let should_jump = self.check_jump_condition(condition);
-
-        if should_jump {
-            self.jump_relative_imm8(mmu)
-        } else {
-            self.instruction_result(2, 2)
-        }
+    pub(crate) fn jump_relative_condition_imm8(
+        &self,
+        condition: JumpCondition,
+        mmu: &MMU,
+    ) -> (u16, u8) {
+        let target = self
+            .check_jump_condition(condition)
+            .then(|| self.jump_relative_imm8(mmu).0);
+        self.conditional_result(Instruction::JrCondImm8(condition), target)
     }
 
-    pub fn or_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn or_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let new_value = self.get_a() | source_value;
 
@@ -648,7 +716,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn or_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn or_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let new_value = self.get_a() | source_value;
 
@@ -661,51 +729,52 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn pop_r16(&mut self, r16_stack: R16Stack, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn pop_r16(&mut self, r16_stack: R16Stack, mmu: &MMU) -> (u16, u8) {
         let value = self.pop_u16(mmu);
         self.set_r16_stack(r16_stack, value);
         self.instruction_result(1, 3)
     }
 
-    pub fn push_r16(&mut self, r16_stack: R16Stack, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn push_r16(&mut self, r16_stack: R16Stack, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r16_stack(r16_stack);
         self.push_u16(value, mmu);
         self.instruction_result(1, 4)
     }
 
-    pub fn restart_vector(&mut self, address_lsb: u8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn restart_vector(&mut self, address_lsb: u8, mmu: &mut MMU) -> (u16, u8) {
         let address = construct_u16(address_lsb, 0x00);
         self.push_u16(self.get_pc().wrapping_add(1), mmu);
         (address, 4)
     }
 
-    pub fn return_from_func(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn return_from_func(&mut self, mmu: &MMU) -> (u16, u8) {
         let return_to_pc = self.pop_u16(mmu);
         (return_to_pc, 4)
     }
 
-    pub fn return_from_func_cond(&mut self, condition: JumpCondition, mmu: &MMU) -> (u16, u8) {
-        let should_jump = self.check_jump_condition(condition);
-        if should_jump {
-            let (new_pc, _) = self.return_from_func(mmu);
-            (new_pc, 5)
-        } else {
-            self.instruction_result(1, 2)
-        }
+    pub(crate) fn return_from_func_cond(
+        &mut self,
+        condition: JumpCondition,
+        mmu: &MMU,
+    ) -> (u16, u8) {
+        let target = self
+            .check_jump_condition(condition)
+            .then(|| self.return_from_func(mmu).0);
+        self.conditional_result(Instruction::ReturnCondition(condition), target)
     }
 
-    pub fn return_from_func_enable_interrupts(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn return_from_func_enable_interrupts(&mut self, mmu: &MMU) -> (u16, u8) {
         self.ime = true;
         self.return_from_func(mmu)
     }
 
-    pub fn rotate_left_a(&mut self) -> (u16, u8) {
+    pub(crate) fn rotate_left_a(&mut self) -> (u16, u8) {
         let (new_a, new_carry) = rotate_left_through_carry_u8(self.get_a(), self.get_f_carry());
         self.update_a_and_flags_after_rotation(new_a, new_carry);
         self.instruction_result(1, 1)
     }
 
-    pub fn rotate_left_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn rotate_left_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let (new_value, new_carry) = rotate_left_through_carry_u8(value, self.get_f_carry());
 
@@ -719,13 +788,13 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn rotate_right_a(&mut self) -> (u16, u8) {
+    pub(crate) fn rotate_right_a(&mut self) -> (u16, u8) {
         let (new_a, new_carry) = rotate_right_through_carry_u8(self.get_a(), self.get_f_carry());
         self.update_a_and_flags_after_rotation(new_a, new_carry);
         self.instruction_result(1, 1)
     }
 
-    pub fn rotate_right_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn rotate_right_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let (new_value, new_carry) = rotate_right_through_carry_u8(value, self.get_f_carry());
 
@@ -739,13 +808,13 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn rotate_left_circular_a(&mut self) -> (u16, u8) {
+    pub(crate) fn rotate_left_circular_a(&mut self) -> (u16, u8) {
         let (new_a, new_carry) = rotate_left_get_carry_u8(self.get_a());
         self.update_a_and_flags_after_rotation(new_a, new_carry);
         self.instruction_result(1, 1)
     }
 
-    pub fn rotate_left_circular_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn rotate_left_circular_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let (new_value, new_carry) = rotate_left_get_carry_u8(value);
 
@@ -759,13 +828,13 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn rotate_right_circular_a(&mut self) -> (u16, u8) {
+    pub(crate) fn rotate_right_circular_a(&mut self) -> (u16, u8) {
         let (new_a, new_carry) = rotate_right_get_carry_u8(self.get_a());
         self.update_a_and_flags_after_rotation(new_a, new_carry);
         self.instruction_result(1, 1)
     }
 
-    pub fn rotate_right_circular_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn rotate_right_circular_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let (new_value, new_carry) = rotate_right_get_carry_u8(value);
 
@@ -779,14 +848,14 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn set_carry_flag(&mut self) -> (u16, u8) {
+    pub(crate) fn set_carry_flag(&mut self) -> (u16, u8) {
         self.set_f_carry(true);
         self.set_f_subtract(false);
         self.set_f_half_carry(false);
         self.instruction_result(1, 1)
     }
 
-    pub fn shift_left_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn shift_left_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let new_carry = get_bit_u8(value, 7);
         let new_value = value << 1;
@@ -801,7 +870,7 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn shift_right_arithmetical_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn shift_right_arithmetical_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let new_carry = get_bit_u8(value, 0);
         // Shift right while persisting the leftmost bit, this is important for signed values
@@ -817,7 +886,7 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn shift_right_logical_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn shift_right_logical_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
         let new_carry = get_bit_u8(value, 0);
         let new_value = value >> 1; // Shift right while filling up with 0's
@@ -832,7 +901,7 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn sub_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn sub_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let (new_value, half_carry, carry) = sub_u8(self.get_a(), source_value);
 
@@ -846,7 +915,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn sub_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn sub_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let (new_value, half_carry, carry) = sub_u8(self.get_a(), source_value);
 
@@ -859,7 +928,7 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn sub_carry_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn sub_carry_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let (new_value, half_carry, carry) =
             sub_carry_u8(self.get_a(), source_value, self.get_f_carry());
@@ -874,7 +943,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn sub_carry_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn sub_carry_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let (new_value, half_carry, carry) =
             sub_carry_u8(self.get_a(), source_value, self.get_f_carry());
@@ -888,9 +957,9 @@ impl CPU {
         self.instruction_result(2, 2)
     }
 
-    pub fn swap_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn swap_r8(&mut self, register: R8, mmu: &mut MMU) -> (u16, u8) {
         let value = self.get_r8(register, mmu);
-        let new_value = (value >> 4) | (value << 4);
+        let new_value = value.rotate_left(4);
 
         self.set_r8(register, new_value, mmu);
         self.set_f_zero(new_value == 0);
@@ -902,7 +971,7 @@ impl CPU {
         self.instruction_result(2, m)
     }
 
-    pub fn xor_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
+    pub(crate) fn xor_r8(&mut self, r8: R8, mmu: &mut MMU) -> (u16, u8) {
         let source_value = self.get_r8(r8, mmu);
         let new_value = self.get_a() ^ source_value;
 
@@ -916,7 +985,7 @@ impl CPU {
         self.instruction_result(1, m)
     }
 
-    pub fn xor_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
+    pub(crate) fn xor_imm8(&mut self, mmu: &MMU) -> (u16, u8) {
         let source_value = self.read_next_imm8(mmu);
         let new_value = self.get_a() ^ source_value;
 
@@ -932,24 +1001,24 @@ impl CPU {
 
 /// Basic operations
 impl CPU {
-    pub fn pop_u8(&mut self, mmu: &MMU) -> u8 {
-        let value = mmu.read(self.get_sp());
+    pub(crate) fn pop_u8(&mut self, mmu: &MMU) -> u8 {
+        let value = mmu.cpu_read(self.get_sp());
         self.increment_sp();
         value
     }
 
-    pub fn pop_u16(&mut self, mmu: &MMU) -> u16 {
+    pub(crate) fn pop_u16(&mut self, mmu: &MMU) -> u16 {
         let lsb = self.pop_u8(mmu);
         let msb = self.pop_u8(mmu);
         construct_u16(lsb, msb)
     }
 
-    pub fn push_u8(&mut self, value: u8, mmu: &mut MMU) {
+    pub(crate) fn push_u8(&mut self, value: u8, mmu: &mut MMU) {
         self.decrement_sp();
-        mmu.write(self.get_sp(), value);
+        mmu.cpu_write(self.get_sp(), value);
     }
 
-    pub fn push_u16(&mut self, value: u16, mmu: &mut MMU) {
+    pub(crate) fn push_u16(&mut self, value: u16, mmu: &mut MMU) {
         let (lsb, msb) = deconstruct_u16(value);
         self.push_u8(msb, mmu);
         self.push_u8(lsb, mmu);
@@ -962,16 +1031,28 @@ impl CPU {
         (self.get_pc().wrapping_add(pc_raise), m_cycles)
     }
 
+    /// Continues at the target if the condition was met, otherwise after the instruction.
+    /// The M-cycles of both outcomes come from the metadata table.
+    fn conditional_result(&self, instruction: Instruction, target: Option<u16>) -> (u16, u8) {
+        match target {
+            Some(target) => (target, instruction.get_cycles(true)),
+            None => self.instruction_result(
+                instruction.get_length() as u16,
+                instruction.get_cycles(false),
+            ),
+        }
+    }
+
     fn read_next_imm8(&self, mmu: &MMU) -> u8 {
-        mmu.read(self.get_pc().wrapping_add(1))
+        mmu.cpu_read(self.get_pc().wrapping_add(1))
     }
 
     fn read_next_imm8_signed(&self, mmu: &MMU) -> i8 {
-        mmu.read(self.get_pc().wrapping_add(1)) as i8
+        mmu.cpu_read(self.get_pc().wrapping_add(1)) as i8
     }
 
     fn read_next_imm16(&self, mmu: &MMU) -> u16 {
-        mmu.read_16(self.get_pc().wrapping_add(1))
+        mmu.cpu_read_16(self.get_pc().wrapping_add(1))
     }
 
     fn process_r16m_register_update(&mut self, r16_m: R16Mem) {
@@ -993,10 +1074,13 @@ impl CPU {
 
 /// Logging
 impl CPU {
+    /// The operands are peeked, so enabling the log doesn't run IO hooks or count memory stats
     fn log_instruction_execute(&self, instruction: &Instruction, instruction_byte: u8, mmu: &MMU) {
-        if log::log_enabled!(log::Level::Info) {
-            let (next_lsb, next_msb) = deconstruct_u16(self.read_next_imm16(mmu));
+        if log_enabled!(target: Subsystem::Cpu.target(), Level::Debug) {
+            let next_lsb = mmu.peek(self.get_pc().wrapping_add(1));
+            let next_msb = mmu.peek(self.get_pc().wrapping_add(2));
             debug!(
+                target: Subsystem::Cpu.target(),
                 "PC(0x{:04X}) [0x{:02X}]: {}",
                 self.get_pc(),
                 instruction_byte,
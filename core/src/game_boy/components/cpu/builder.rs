@@ -1,6 +1,10 @@
+use crate::game_boy::components::cpu::doctor::DoctorLogLine;
 use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
 use crate::game_boy::components::cpu::registers::{CPURegisters, CpuRegistersAccessTrait};
 use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::save_state::GameBoySaveState;
+use alloc::boxed::Box;
+use core::error::Error;
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct CpuBuilder {
@@ -13,6 +17,35 @@ impl CpuBuilder {
         Self::default()
     }
 
+    /// Starts from the exact CPU state of a save state
+    pub fn from_state(state: &GameBoySaveState) -> Self {
+        Self::from_cpu(&state.cpu)
+    }
+
+    pub fn from_cpu(cpu: &CPU) -> Self {
+        Self {
+            cpu: cpu.clone(),
+            registers: cpu.get_registers().clone(),
+        }
+    }
+
+    /// Seeds the registers from a Gameboy Doctor log line.
+    /// The PCMEM bytes can be placed in memory with [`DoctorLogLine::write_pcmem`].
+    pub fn from_doctor_line(line: &str) -> Result<Self, Box<dyn Error>> {
+        let line: DoctorLogLine = line.parse()?;
+        Ok(Self::new()
+            .a(line.a)
+            .f(line.f)
+            .b(line.b)
+            .c(line.c)
+            .d(line.d)
+            .e(line.e)
+            .h(line.h)
+            .l(line.l)
+            .sp(line.sp)
+            .pc(line.pc))
+    }
+
     pub fn build(mut self) -> CPU {
         self.cpu.set_registers(self.registers.clone());
         self.cpu
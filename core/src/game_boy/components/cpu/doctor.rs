@@ -0,0 +1,150 @@
+//! Log lines in the format of Gameboy Doctor
+//! https://github.com/robert/gameboy-doctor
+
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::CPU;
+#[cfg(test)]
+use crate::game_boy::components::mmu::builder::MMUBuilder;
+use crate::game_boy::components::mmu::MMU;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// A single line like `A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorLogLine {
+    pub a: u8,
+    pub f: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    pub sp: u16,
+    pub pc: u16,
+    /// The 4 bytes of memory starting at PC
+    pub pcmem: [u8; 4],
+}
+
+impl DoctorLogLine {
+    pub fn from_cpu(cpu: &CPU, mmu: &MMU) -> Self {
+        let pc = cpu.get_pc();
+        Self {
+            a: cpu.get_a(),
+            f: cpu.get_f(),
+            b: cpu.get_b(),
+            c: cpu.get_c(),
+            d: cpu.get_d(),
+            e: cpu.get_e(),
+            h: cpu.get_h(),
+            l: cpu.get_l(),
+            sp: cpu.get_sp(),
+            pc,
+            pcmem: [0, 1, 2, 3].map(|offset| mmu.read(pc.wrapping_add(offset))),
+        }
+    }
+
+    /// Places the PCMEM bytes at PC, so the logged instruction can be executed again
+    #[cfg(test)]
+    pub(crate) fn write_pcmem(&self, mut builder: MMUBuilder) -> MMUBuilder {
+        for (offset, byte) in self.pcmem.iter().enumerate() {
+            let address = self.pc.wrapping_add(offset as u16);
+            builder = if address < 0x8000 {
+                builder.rom(address, *byte)
+            } else {
+                builder.write(address, *byte)
+            };
+        }
+        builder
+    }
+}
+
+impl FromStr for DoctorLogLine {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut line = Self {
+            a: 0,
+            f: 0,
+            b: 0,
+            c: 0,
+            d: 0,
+            e: 0,
+            h: 0,
+            l: 0,
+            sp: 0,
+            pc: 0,
+            pcmem: [0; 4],
+        };
+        let mut found_fields = 0;
+
+        for field in s.split_whitespace() {
+            let (name, value) = field
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid field '{field}'"))?;
+            let parse_u8 = || {
+                u8::from_str_radix(value, 16).map_err(|e| format!("Invalid value for {name}: {e}"))
+            };
+            let parse_u16 = || {
+                u16::from_str_radix(value, 16).map_err(|e| format!("Invalid value for {name}: {e}"))
+            };
+
+            match name {
+                "A" => line.a = parse_u8()?,
+                "F" => line.f = parse_u8()?,
+                "B" => line.b = parse_u8()?,
+                "C" => line.c = parse_u8()?,
+                "D" => line.d = parse_u8()?,
+                "E" => line.e = parse_u8()?,
+                "H" => line.h = parse_u8()?,
+                "L" => line.l = parse_u8()?,
+                "SP" => line.sp = parse_u16()?,
+                "PC" => line.pc = parse_u16()?,
+                "PCMEM" => {
+                    let bytes = value
+                        .split(',')
+                        .map(|byte| u8::from_str_radix(byte, 16))
+                        .collect::<Result<Vec<u8>, _>>()
+                        .map_err(|e| format!("Invalid value for PCMEM: {e}"))?;
+                    line.pcmem = bytes
+                        .try_into()
+                        .map_err(|_| "PCMEM has to contain exactly 4 bytes")?;
+                }
+                _ => return Err(format!("Unknown field '{name}'").into()),
+            }
+            found_fields += 1;
+        }
+
+        if found_fields != 11 {
+            return Err(format!("Expected 11 fields, found {found_fields}").into());
+        }
+        Ok(line)
+    }
+}
+
+impl Display for DoctorLogLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+            self.a,
+            self.f,
+            self.b,
+            self.c,
+            self.d,
+            self.e,
+            self.h,
+            self.l,
+            self.sp,
+            self.pc,
+            self.pcmem[0],
+            self.pcmem[1],
+            self.pcmem[2],
+            self.pcmem[3]
+        )
+    }
+}
@@ -0,0 +1,50 @@
+//! Records which opcodes were executed in this process, used to check that the test suite exercises every instruction
+
+use crate::instructions::Instruction;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static UNPREFIXED: [AtomicBool; 256] = [const { AtomicBool::new(false) }; 256];
+static PREFIXED: [AtomicBool; 256] = [const { AtomicBool::new(false) }; 256];
+
+pub fn record(byte: u8, prefixed: bool) {
+    let table = if prefixed { &PREFIXED } else { &UNPREFIXED };
+    table[byte as usize].store(true, Ordering::Relaxed);
+}
+
+pub fn was_executed(byte: u8, prefixed: bool) -> bool {
+    let table = if prefixed { &PREFIXED } else { &UNPREFIXED };
+    table[byte as usize].load(Ordering::Relaxed)
+}
+
+/// Implemented opcodes which were never executed: (unprefixed, prefixed)
+pub fn missing_opcodes() -> (Vec<u8>, Vec<u8>) {
+    let missing = |prefixed: bool| {
+        (0..=255u8)
+            .filter(|&byte| Instruction::from_byte(byte, prefixed).is_ok())
+            .filter(|&byte| !was_executed(byte, prefixed))
+            .collect()
+    };
+    (missing(false), missing(true))
+}
+
+pub fn report() -> String {
+    let (unprefixed, prefixed) = missing_opcodes();
+    let mut lines = Vec::new();
+    for byte in unprefixed {
+        let instruction = Instruction::from_byte(byte, false).unwrap();
+        lines.push(format!(
+            "0x{:02X}    {}",
+            byte,
+            instruction.parse_clear_text(0, 0)
+        ));
+    }
+    for byte in prefixed {
+        let instruction = Instruction::from_byte(byte, true).unwrap();
+        lines.push(format!(
+            "0xCB{:02X}  {}",
+            byte,
+            instruction.parse_clear_text(0, 0)
+        ));
+    }
+    lines.join("\n")
+}
@@ -1,25 +1,13 @@
 use crate::enums::parameter_groups::{JumpCondition, R16Mem, R16Stack, R16, R8};
-use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilder;
-use crate::game_boy::components::cpu::registers::flags_register::CPUFlagsRegister;
+use crate::game_boy::components::cpu::registers::flags_register::{CPUFlagsRegister, FLAGS_MASK};
 use crate::game_boy::components::mmu::MMU;
 use crate::helpers::bit_operations::{construct_u16, deconstruct_u16};
 use serde::{Deserialize, Serialize};
 
-pub mod builder;
+#[cfg(test)]
+pub(crate) mod builder;
 pub mod flags_register;
 
-// Initial CPU register values according to: https://gbdev.io/pandocs/Power_Up_Sequence.html?highlight=state#console-state-after-boot-rom-hand-off
-// Model: DMG0
-const INITIAL_A: u8 = 0x01;
-const INITIAL_B: u8 = 0xFF;
-const INITIAL_C: u8 = 0x13;
-const INITIAL_D: u8 = 0x00;
-const INITIAL_E: u8 = 0xC1;
-const INITIAL_H: u8 = 0x84;
-const INITIAL_L: u8 = 0x03;
-const INITIAL_PC: u16 = 0x0100;
-const INITIAL_SP: u16 = 0xFFFE;
-
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CPURegisters {
     a: u8,
@@ -36,27 +24,6 @@ pub struct CPURegisters {
     sp: u16,
 }
 
-impl CPURegisters {
-    pub fn builder() -> CPURegistersBuilder {
-        CPURegistersBuilder::new()
-    }
-
-    pub fn initialize() -> Self {
-        Self {
-            a: INITIAL_A,
-            b: INITIAL_B,
-            c: INITIAL_C,
-            d: INITIAL_D,
-            e: INITIAL_E,
-            f: CPUFlagsRegister::initialize(),
-            h: INITIAL_H,
-            l: INITIAL_L,
-            pc: INITIAL_PC,
-            sp: INITIAL_SP,
-        }
-    }
-}
-
 impl CpuRegistersAccessTrait for CPURegisters {
     fn get_registers(&self) -> &CPURegisters {
         self
@@ -79,7 +46,7 @@ pub trait CpuRegistersAccessTrait {
             R8::E => self.get_e(),
             R8::H => self.get_h(),
             R8::L => self.get_l(),
-            R8::HL => mmu.read(self.get_hl()),
+            R8::HL => mmu.cpu_read(self.get_hl()),
             R8::A => self.get_a(),
         }
     }
@@ -92,7 +59,7 @@ pub trait CpuRegistersAccessTrait {
             R8::E => self.set_e(value),
             R8::H => self.set_h(value),
             R8::L => self.set_l(value),
-            R8::HL => mmu.write(self.get_hl(), value),
+            R8::HL => mmu.cpu_write(self.get_hl(), value),
             R8::A => self.set_a(value),
         }
     }
@@ -210,8 +177,9 @@ pub trait CpuRegistersAccessTrait {
         self.get_registers().f.into()
     }
 
+    /// Every write to F goes through here, the lower 4 bits are discarded
     fn set_f(&mut self, value: u8) {
-        self.get_registers_mut().f = value.into()
+        self.get_registers_mut().f = (value & FLAGS_MASK).into()
     }
 
     fn get_f_zero(&self) -> bool {
@@ -274,6 +242,7 @@ pub trait CpuRegistersAccessTrait {
         construct_u16(self.get_f(), self.get_a())
     }
 
+    /// Like [`Self::set_f`], the lower 4 bits of F are discarded
     fn set_af(&mut self, value: u16) {
         let (f, a) = deconstruct_u16(value);
         self.set_f(f);
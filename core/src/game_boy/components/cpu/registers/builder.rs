@@ -1,22 +1,7 @@
 use crate::enums::parameter_groups::R8;
-use crate::game_boy::components::cpu::registers::{CPURegisters, CpuRegistersAccessTrait};
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
 use crate::game_boy::components::mmu::MMU;
 
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct CPURegistersBuilder {
-    registers: CPURegisters,
-}
-
-impl CPURegistersBuilder {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    pub fn build(self) -> CPURegisters {
-        self.registers
-    }
-}
-
 pub trait CPURegistersBuilderTrait: CpuRegistersAccessTrait + Sized {
     fn a(mut self, value: u8) -> Self {
         self.get_registers_mut().set_a(value);
@@ -43,6 +28,11 @@ pub trait CPURegistersBuilderTrait: CpuRegistersAccessTrait + Sized {
         self
     }
 
+    fn f(mut self, value: u8) -> Self {
+        self.get_registers_mut().set_f(value);
+        self
+    }
+
     fn f_zero(mut self, value: bool) -> Self {
         self.get_registers_mut().set_f_zero(value);
         self
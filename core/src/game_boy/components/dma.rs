@@ -0,0 +1,75 @@
+//! https://gbdev.io/pandocs/OAM_DMA_Transfer.html
+
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::mmu::{MMU, OAM_ADDRESS, OAM_SIZE};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use alloc::boxed::Box;
+use core::error::Error;
+use serde::{Deserialize, Serialize};
+
+/// One byte is copied per M-cycle
+pub const DMA_DURATION: Cycles = Cycles::from_m(OAM_SIZE as u32);
+
+/// Copies XX00-XX9F to OAM after XX was written to DMA (0xFF46).
+/// A new write restarts the transfer from the new source.
+// ToDo: Restrict CPU memory access to HRAM while a transfer is running
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dma {
+    source: u16,
+    /// Bytes copied so far, None if no transfer is running
+    progress: Option<u16>,
+}
+
+impl Dma {
+    /// Returns true if a transfer finished during this step
+    pub fn step(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        if let Some(page) = mmu.take_dma_request() {
+            self.source = (page as u16) << 8;
+            self.progress = Some(0);
+        }
+
+        let Some(mut copied) = self.progress else {
+            return false;
+        };
+
+        for _ in 0..cycles.as_m() {
+            let data = mmu.read(self.source.wrapping_add(copied));
+            mmu.write(OAM_ADDRESS + copied, data);
+            copied += 1;
+
+            if copied as usize == OAM_SIZE {
+                self.progress = None;
+                return true;
+            }
+        }
+
+        self.progress = Some(copied);
+        false
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.progress.is_some()
+    }
+}
+
+impl Component for Dma {
+    type SaveState = Dma;
+
+    fn reset(&mut self, _config: &GameBoyConfig) {
+        *self = Self::default();
+    }
+
+    fn tick(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        self.step(cycles, mmu)
+    }
+
+    fn save(&self) -> Dma {
+        self.clone()
+    }
+
+    fn load(&mut self, state: Dma) -> Result<(), Box<dyn Error>> {
+        *self = state;
+        Ok(())
+    }
+}
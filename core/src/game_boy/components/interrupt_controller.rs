@@ -0,0 +1,73 @@
+//! https://gbdev.io/pandocs/Interrupts.html
+
+use crate::enums::interrupts::Interrupt;
+use serde::{Deserialize, Serialize};
+
+/// Owns the IF and IE registers.
+/// Components request interrupts through it and the CPU acknowledges them, nobody has to touch the registers directly.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterruptController {
+    /// IF (0xFF0F), requested interrupts
+    interrupt_flag: u8,
+    /// IE (0xFFFF), enabled interrupts
+    interrupt_enable: u8,
+}
+
+impl InterruptController {
+    pub fn new(interrupt_flag: u8, interrupt_enable: u8) -> Self {
+        Self {
+            interrupt_flag,
+            interrupt_enable,
+        }
+    }
+
+    /// Sets the IF bit of the given interrupt
+    pub fn request(&mut self, interrupt: Interrupt) {
+        self.interrupt_flag |= interrupt.get_mask();
+    }
+
+    /// Clears the IF bit of the given interrupt
+    pub fn clear(&mut self, interrupt: Interrupt) {
+        self.interrupt_flag &= !interrupt.get_mask();
+    }
+
+    pub fn is_requested(&self, interrupt: Interrupt) -> bool {
+        self.interrupt_flag & interrupt.get_mask() != 0
+    }
+
+    pub fn is_enabled(&self, interrupt: Interrupt) -> bool {
+        self.interrupt_enable & interrupt.get_mask() != 0
+    }
+
+    /// The highest priority interrupt which is both requested and enabled
+    pub fn get_pending(&self) -> Option<Interrupt> {
+        Interrupt::from_ie_if(self.interrupt_enable & self.interrupt_flag)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.get_pending().is_some()
+    }
+
+    /// Takes the highest priority pending interrupt and clears its IF bit, used when the CPU services it
+    pub fn acknowledge(&mut self) -> Option<Interrupt> {
+        let interrupt = self.get_pending()?;
+        self.clear(interrupt);
+        Some(interrupt)
+    }
+
+    pub fn read_if(&self) -> u8 {
+        self.interrupt_flag
+    }
+
+    pub fn write_if(&mut self, value: u8) {
+        self.interrupt_flag = value;
+    }
+
+    pub fn read_ie(&self) -> u8 {
+        self.interrupt_enable
+    }
+
+    pub fn write_ie(&mut self, value: u8) {
+        self.interrupt_enable = value;
+    }
+}
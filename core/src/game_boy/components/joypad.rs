@@ -0,0 +1,56 @@
+//! https://gbdev.io/pandocs/Joypad_Input.html
+
+use crate::enums::button::Buttons;
+use crate::helpers::bit_operations::get_bit_u8;
+use core::cell::Cell;
+
+/// The select lines in P1 (bits 4 and 5), the only writable bits
+pub const P1_SELECT_MASK: u8 = 0b0011_0000;
+
+/// The buttons currently held down.
+/// P1 is wired as a matrix, a selected line pulls the bits of its pressed buttons low.
+#[derive(Debug, Default, Clone)]
+pub struct Joypad {
+    pressed: Buttons,
+    /// Set when the game reads P1, reads only borrow the MMU immutably so this needs interior mutability
+    polled: Cell<bool>,
+}
+
+impl Joypad {
+    pub fn get_pressed(&self) -> Buttons {
+        self.pressed
+    }
+
+    pub fn set_pressed(&mut self, buttons: Buttons) {
+        self.pressed = buttons;
+    }
+
+    pub fn record_poll(&self) {
+        self.polled.set(true);
+    }
+
+    /// Whether P1 was read since the last call
+    pub fn take_polled(&mut self) -> bool {
+        self.polled.replace(false)
+    }
+
+    /// The value of P1 with the given select lines, unused bits read as 1.
+    /// With both lines selected the buttons of both are combined, with none selected the lower nibble is 0xF.
+    pub fn read_p1(&self, select: u8) -> u8 {
+        let mut lines = 0x0F;
+        if !get_bit_u8(select, 4) {
+            lines &= !(self.pressed.bits() >> 4);
+        }
+        if !get_bit_u8(select, 5) {
+            lines &= !(self.pressed.bits() & 0x0F);
+        }
+        0b1100_0000 | (select & P1_SELECT_MASK) | lines
+    }
+}
+
+/// The poll flag only lives until the end of the current step, it isn't part of the emulated state
+impl PartialEq for Joypad {
+    fn eq(&self, other: &Self) -> bool {
+        self.pressed == other.pressed
+    }
+}
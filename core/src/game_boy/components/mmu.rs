@@ -0,0 +1,1074 @@
+use crate::enums::button::Buttons;
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::cheats::RomPatch;
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::interrupt_controller::InterruptController;
+use crate::game_boy::components::joypad::{Joypad, P1_SELECT_MASK};
+#[cfg(test)]
+use crate::game_boy::components::mmu::builder::MMUBuilder;
+use crate::game_boy::components::mmu::io_hooks::{IoHookId, IoHooks, IO_HOOK_ADDRESSES};
+use crate::game_boy::components::mmu::io_registers::describe;
+use crate::game_boy::components::mmu::mbc::camera::{CAPTURE_OFFSET, CAPTURE_SIZE};
+use crate::game_boy::components::mmu::mbc::{MapperWriteEvent, Mbc, RumbleEvent};
+use crate::game_boy::components::mmu::region::MemoryRegion;
+use crate::game_boy::components::mmu::save_state::MMUSaveState;
+use crate::game_boy::components::mmu::stats::MemoryStats;
+use crate::game_boy::components::ppu::mode::PPUMode;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::diagnostics::{Diagnostic, Diagnostics};
+use crate::game_boy::hardware_model::HardwareModel;
+use crate::helpers::bit_operations::construct_u16;
+use crate::helpers::listeners::{ListenerId, Listeners};
+use crate::logging::Subsystem;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::error::Error;
+use log::{debug, trace};
+
+#[cfg(test)]
+pub(crate) mod builder;
+pub mod io_hooks;
+pub mod io_registers;
+pub mod mbc;
+pub mod region;
+pub mod save_state;
+pub mod stats;
+
+pub const ROM_BANK_SIZE: usize = 0x4000; // 16KB
+pub const RAM_BANK_SIZE: usize = 0x2000; // 8KB
+const VRAM_SIZE: usize = 0x2000; // 8KB
+const WRAM_SIZE: usize = 0x2000; // 8KB
+/// Echo RAM mirrors all of WRAM except for its last 512 bytes
+const ECHO_RAM_SIZE: usize = 0x1E00;
+pub const OAM_SIZE: usize = 160; // Bytes
+const HRAM_SIZE: usize = 127; // Bytes
+const IO_REGISTERS_SIZE: usize = 160; // Bytes
+/// The captured serial output keeps at most this many of the latest bytes, so it doesn't grow save states without bound
+pub const SERIAL_OUTPUT_LIMIT: usize = 0x4000; // 16KB
+
+// Initial hardware registers which are the same for every model, see HardwareModel::get_io_registers for the others
+// https://gbdev.io/pandocs/Power_Up_Sequence.html?highlight=state#console-state-after-boot-rom-hand-off
+const INITIAL_P1: u8 = 0xCF;
+const INITIAL_SB: u8 = 0x00;
+const INITIAL_TIMA: u8 = 0x00;
+const INITIAL_TMA: u8 = 0x00;
+const INITIAL_TAC: u8 = 0xF8;
+const INITIAL_IF: u8 = 0xE1;
+const INITIAL_NR10: u8 = 0x80;
+const INITIAL_NR11: u8 = 0xBF;
+const INITIAL_NR12: u8 = 0xF3;
+const INITIAL_NR13: u8 = 0xFF;
+const INITIAL_NR14: u8 = 0xBF;
+const INITIAL_NR21: u8 = 0x3F;
+const INITIAL_NR22: u8 = 0x00;
+const INITIAL_NR23: u8 = 0xFF;
+const INITIAL_NR24: u8 = 0xBF;
+const INITIAL_NR30: u8 = 0x7F;
+const INITIAL_NR31: u8 = 0xFF;
+const INITIAL_NR32: u8 = 0x9F;
+const INITIAL_NR33: u8 = 0xFF;
+const INITIAL_NR34: u8 = 0xBF;
+const INITIAL_NR41: u8 = 0xFF;
+const INITIAL_NR42: u8 = 0x00;
+const INITIAL_NR43: u8 = 0x00;
+const INITIAL_NR44: u8 = 0xBF;
+const INITIAL_NR50: u8 = 0x77;
+const INITIAL_NR51: u8 = 0xF3;
+const INITIAL_LCDC: u8 = 0x91;
+const INITIAL_SCY: u8 = 0x00;
+const INITIAL_SCX: u8 = 0x00;
+const INITIAL_LYC: u8 = 0x00;
+const INITIAL_BGP: u8 = 0xFC;
+const INITIAL_WY: u8 = 0x00;
+const INITIAL_WX: u8 = 0x00;
+const INITIAL_IE: u8 = 0x00;
+
+// IMPORTANT ADDRESSES
+// Joypad
+pub const P1_ADDRESS: u16 = 0xFF00;
+
+// Serial
+pub const SB_ADDRESS: u16 = 0xFF01;
+pub const SC_ADDRESS: u16 = 0xFF02;
+
+// Timer
+pub const DIV_ADDRESS: u16 = 0xFF04;
+pub const TIMA_ADDRESS: u16 = 0xFF05;
+pub const TMA_ADDRESS: u16 = 0xFF06;
+pub const TAC_ADDRESS: u16 = 0xFF07;
+
+// Interrupts
+pub const IF_ADDRESS: u16 = 0xFF0F;
+pub const IE_ADDRESS: u16 = 0xFFFF;
+
+// Graphics
+pub const LCDC_ADDRESS: u16 = 0xFF40;
+pub const STAT_ADDRESS: u16 = 0xFF41;
+/// The interrupt selects, the mode and LYC=LY bits are read-only
+const STAT_WRITABLE_MASK: u8 = 0b0111_1000;
+const STAT_UNUSED_BITS: u8 = 0b1000_0000;
+pub const SCY_ADDRESS: u16 = 0xFF42;
+pub const SCX_ADDRESS: u16 = 0xFF43;
+pub const LY_ADDRESS: u16 = 0xFF44;
+pub const LYC_ADDRESS: u16 = 0xFF45;
+pub const DMA_ADDRESS: u16 = 0xFF46;
+pub const BGP_ADDRESS: u16 = 0xFF47; // Background color palette
+pub const OBP0_ADDRESS: u16 = 0xFF48; // Object color palette 0
+pub const OBP1_ADDRESS: u16 = 0xFF49; // Object color palette 1
+pub const WY_ADDRESS: u16 = 0xFF4A;
+/// The window's X position plus 7
+pub const WX_ADDRESS: u16 = 0xFF4B;
+
+// Object attribute memory
+pub const OAM_ADDRESS: u16 = 0xFE00;
+
+/// What 0xFEA0-0xFEFF reads as on DMG while the PPU doesn't block OAM, https://gbdev.io/pandocs/Memory_Map.html#fea0feff-range
+const UNUSABLE_VALUE: u8 = 0x00;
+/// Nothing drives the data lines, the pull-ups make them read as 1
+const UNMAPPED_VALUE: u8 = 0xFF;
+
+/// The two background/window tile maps
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TileMap {
+    Map9800,
+    Map9C00,
+}
+
+impl TileMap {
+    pub fn get_address(&self) -> u16 {
+        match self {
+            TileMap::Map9800 => 0x9800,
+            TileMap::Map9C00 => 0x9C00,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MMU {
+    pub cartridge_header: CartridgeHeader,
+
+    mbc: Mbc,
+    rom_banks: Vec<[u8; ROM_BANK_SIZE]>,
+    ram_banks: Vec<[u8; RAM_BANK_SIZE]>,
+
+    vram: [u8; VRAM_SIZE],
+    wram: [u8; WRAM_SIZE],
+
+    oam: [u8; OAM_SIZE],
+    io_registers: [u8; IO_REGISTERS_SIZE],
+    hram: [u8; HRAM_SIZE],
+    /// Backs IF (0xFF0F) and IE (0xFFFF)
+    interrupts: InterruptController,
+    /// Backs the button lines of P1 (0xFF00), the select lines are stored with the IO registers
+    joypad: Joypad,
+    /// Source page of an OAM DMA transfer started by a write to DMA (0xFF46), until the DMA component picks it up
+    dma_request: Option<u8>,
+    /// Access counters, only present while enabled since they cost time on every access
+    stats: Option<Box<MemoryStats>>,
+    mapper_listeners: Listeners<MapperWriteEvent>,
+    rumble_listeners: Listeners<RumbleEvent>,
+    diagnostics: Diagnostics,
+    /// Custom handlers of IO register addresses, see [`MMU::add_io_read_hook`]
+    io_hooks: IoHooks,
+    /// Of the active Game Genie codes, they change what the CPU reads from ROM
+    rom_patches: Vec<RomPatch>,
+
+    /// The bytes sent over the serial port without a link partner, used by test ROMs to report their results
+    serial_output: Vec<u8>,
+    /// Off while a link partner is connected, see [`MMU::set_serial_capture`]
+    serial_capture: bool,
+    /// Accuracy option: reads of memory without a value behave like on hardware instead of returning fixed values,
+    /// see [`MMU::set_open_bus`]
+    open_bus: bool,
+    /// The last value on the external bus shared by the cartridge and WRAM, only tracked with open bus emulation.
+    /// Reads only borrow the MMU immutably, so this needs interior mutability.
+    external_bus: Cell<u8>,
+}
+
+impl MMU {
+    #[cfg(test)]
+    pub(crate) fn builder() -> MMUBuilder {
+        MMUBuilder::new()
+    }
+
+    /// Fails if the cartridge uses a memory bank controller which isn't emulated
+    pub fn initialize(cartridge: &Cartridge) -> Result<Self, Box<dyn Error>> {
+        Self::initialize_with_model(cartridge, HardwareModel::default())
+    }
+
+    /// The IO registers are the ones the boot ROM of the model leaves behind
+    pub fn initialize_with_model(
+        cartridge: &Cartridge,
+        model: HardwareModel,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            cartridge_header: cartridge.header.clone(),
+            mbc: Mbc::initialize(cartridge.header.cartridge_type)?,
+            rom_banks: cartridge.rom_banks.clone(),
+            ram_banks: vec![[0; RAM_BANK_SIZE]; cartridge.header.ram_size],
+            vram: [0; VRAM_SIZE],
+            wram: [0; WRAM_SIZE],
+            oam: [0; OAM_SIZE],
+            io_registers: Self::initialize_io_registers(model),
+            hram: [0; HRAM_SIZE],
+            interrupts: InterruptController::new(INITIAL_IF, INITIAL_IE),
+            joypad: Joypad::default(),
+            dma_request: None,
+            stats: None,
+            mapper_listeners: Listeners::default(),
+            rumble_listeners: Listeners::default(),
+            diagnostics: Diagnostics::default(),
+            io_hooks: IoHooks::default(),
+            rom_patches: Vec::new(),
+            serial_output: Vec::new(),
+            serial_capture: true,
+            open_bus: false,
+            external_bus: Cell::new(UNMAPPED_VALUE),
+        })
+    }
+
+    /// Returns to the state after [`MMU::initialize_with_model`], except for the cartridge RAM which is battery backed.
+    /// Held buttons, the access counters, the listeners and IO hooks are kept as well, a running rumble motor is switched off.
+    pub fn reset(&mut self, model: HardwareModel) {
+        let motor_was_on = self.mbc.motor_on();
+        self.mbc.reset();
+        self.notify_rumble(motor_was_on);
+        self.vram = [0; VRAM_SIZE];
+        self.wram = [0; WRAM_SIZE];
+        self.oam = [0; OAM_SIZE];
+        self.io_registers = Self::initialize_io_registers(model);
+        self.hram = [0; HRAM_SIZE];
+        self.interrupts = InterruptController::new(INITIAL_IF, INITIAL_IE);
+        self.dma_request = None;
+        self.serial_output.clear();
+        self.external_bus.set(UNMAPPED_VALUE);
+        self.diagnostics.clear();
+    }
+
+    pub fn initialize_io_registers(model: HardwareModel) -> [u8; IO_REGISTERS_SIZE] {
+        let absolute_address: usize = 0xFF00;
+        let mut io_registers = [0u8; IO_REGISTERS_SIZE];
+        io_registers[0xFF00 - absolute_address] = INITIAL_P1;
+        io_registers[0xFF01 - absolute_address] = INITIAL_SB;
+        io_registers[0xFF05 - absolute_address] = INITIAL_TIMA;
+        io_registers[0xFF06 - absolute_address] = INITIAL_TMA;
+        io_registers[0xFF07 - absolute_address] = INITIAL_TAC;
+        io_registers[0xFF10 - absolute_address] = INITIAL_NR10;
+        io_registers[0xFF11 - absolute_address] = INITIAL_NR11;
+        io_registers[0xFF12 - absolute_address] = INITIAL_NR12;
+        io_registers[0xFF13 - absolute_address] = INITIAL_NR13;
+        io_registers[0xFF14 - absolute_address] = INITIAL_NR14;
+        io_registers[0xFF16 - absolute_address] = INITIAL_NR21;
+        io_registers[0xFF17 - absolute_address] = INITIAL_NR22;
+        io_registers[0xFF18 - absolute_address] = INITIAL_NR23;
+        io_registers[0xFF19 - absolute_address] = INITIAL_NR24;
+        io_registers[0xFF1A - absolute_address] = INITIAL_NR30;
+        io_registers[0xFF1B - absolute_address] = INITIAL_NR31;
+        io_registers[0xFF1C - absolute_address] = INITIAL_NR32;
+        io_registers[0xFF1D - absolute_address] = INITIAL_NR33;
+        io_registers[0xFF1E - absolute_address] = INITIAL_NR34;
+        io_registers[0xFF20 - absolute_address] = INITIAL_NR41;
+        io_registers[0xFF21 - absolute_address] = INITIAL_NR42;
+        io_registers[0xFF22 - absolute_address] = INITIAL_NR43;
+        io_registers[0xFF23 - absolute_address] = INITIAL_NR44;
+        io_registers[0xFF24 - absolute_address] = INITIAL_NR50;
+        io_registers[0xFF25 - absolute_address] = INITIAL_NR51;
+        io_registers[0xFF40 - absolute_address] = INITIAL_LCDC;
+        io_registers[0xFF42 - absolute_address] = INITIAL_SCY;
+        io_registers[0xFF43 - absolute_address] = INITIAL_SCX;
+        io_registers[0xFF45 - absolute_address] = INITIAL_LYC;
+        io_registers[0xFF47 - absolute_address] = INITIAL_BGP;
+        io_registers[0xFF4A - absolute_address] = INITIAL_WY;
+        io_registers[0xFF4B - absolute_address] = INITIAL_WX;
+        for (address, value) in model.get_io_registers() {
+            io_registers[address as usize - absolute_address] = value;
+        }
+        io_registers
+    }
+
+    /// A read of the CPU, counted in the memory stats and answered by the IO hooks instead of the emulated register if they want.
+    /// The other components access memory with [`read`](Self::read), so neither their traffic is counted nor do hooks intercept it.
+    pub fn cpu_read(&self, address: u16) -> u8 {
+        if let Some(stats) = &self.stats {
+            stats.record_read(address);
+        }
+        let hooked = IO_HOOK_ADDRESSES
+            .contains(&address)
+            .then(|| self.io_hooks.read(address))
+            .flatten();
+        hooked.unwrap_or_else(|| self.read(address))
+    }
+
+    /// A write of the CPU, counted in the memory stats. The IO hooks see it first and may keep it from reaching the emulated register.
+    pub fn cpu_write(&mut self, address: u16, value: u8) {
+        if let Some(stats) = &mut self.stats {
+            stats.record_write(address);
+        }
+        if IO_HOOK_ADDRESSES.contains(&address) && self.io_hooks.write(address, value) {
+            self.log_io_write(address, value);
+            return;
+        }
+        self.write(address, value);
+    }
+
+    pub fn cpu_read_16(&self, address: u16) -> u16 {
+        let lsb = self.cpu_read(address);
+        let msb = self.cpu_read(address.wrapping_add(1));
+        construct_u16(lsb, msb)
+    }
+
+    /// Access through the memory map without IO hooks and memory stats, e.g. for the components' own registers
+    pub fn read(&self, address: u16) -> u8 {
+        let value = match address {
+            0x0000..=0x7FFF => self.read_rom(address),
+            0x8000..=0x9FFF => self.get_vram(address - 0x8000),
+            0xA000..=0xBFFF => self.get_ram(address - 0xA000),
+            0xC000..=0xDFFF => self.get_wram(address - 0xC000),
+            0xE000..=0xFDFF => self.get_wram(address - 0xE000),
+            0xFE00..=0xFE9F => self.get_oam(address - 0xFE00),
+            0xFEA0..=0xFEFF => self.get_unusable(),
+            0xFF00..=0xFF7F => self.get_io_register(address - 0xFF00),
+            0xFF80..=0xFFFE => self.get_hram(address - 0xFF80),
+            0xFFFF => self.interrupts.read_ie(),
+        };
+        if self.open_bus && is_external_bus(address) {
+            self.external_bus.set(value);
+        }
+        value
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) {
+        if self.open_bus && is_external_bus(address) {
+            self.external_bus.set(value);
+        }
+
+        match address {
+            0x0000..=0x7FFF => self.set_rom(address, value),
+            0x8000..=0x9FFF => self.set_vram(address - 0x8000, value),
+            0xA000..=0xBFFF => self.set_ram(address - 0xA000, value),
+            0xC000..=0xDFFF => self.set_wram(address - 0xC000, value),
+            0xE000..=0xFDFF => self.set_wram(address - 0xE000, value),
+            0xFE00..=0xFE9F => self.set_oam(address - 0xFE00, value),
+            0xFEA0..=0xFEFF => self.set_unusable(value),
+            0xFF00..=0xFF7F => {
+                self.log_io_write(address, value);
+                self.set_io_register(address - 0xFF00, value)
+            }
+            0xFF80..=0xFFFE => self.set_hram(address - 0xFF80, value),
+            0xFFFF => {
+                self.log_io_write(address, value);
+                self.interrupts.write_ie(value)
+            }
+        }
+    }
+
+    /// Debugger access: the value at the address without any side effects of a CPU read.
+    /// Memory stats aren't recorded, reading P1 doesn't count as a joypad poll
+    /// and the cartridge RAM can be read while it is disabled.
+    pub fn peek(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x7FFF => self.get_rom(address),
+            0x8000..=0x9FFF => self.get_vram(address - 0x8000),
+            0xA000..=0xBFFF => {
+                let index = address - 0xA000;
+                self.mbc.read_ram_registers(index).unwrap_or_else(|| {
+                    self.peek_ram_bank()
+                        .map_or(0xFF, |bank| self.ram_banks[bank][index as usize])
+                })
+            }
+            0xC000..=0xDFFF => self.get_wram(address - 0xC000),
+            0xE000..=0xFDFF => self.get_wram(address - 0xE000),
+            0xFE00..=0xFE9F => self.get_oam(address - 0xFE00),
+            0xFEA0..=0xFEFF => self.get_unusable(),
+            P1_ADDRESS => self.read_p1(),
+            0xFF01..=0xFF7F => self.get_io_register(address - 0xFF00),
+            0xFF80..=0xFFFE => self.get_hram(address - 0xFF80),
+            0xFFFF => self.interrupts.read_ie(),
+        }
+    }
+
+    /// Debugger access: stores the value without any side effects of a CPU write.
+    /// ROM addresses change the ROM of the mapped bank instead of configuring the MBC,
+    /// the cartridge RAM is written while it is disabled and IO registers are stored as they are,
+    /// e.g. writing DIV doesn't reset it and writing DMA doesn't start a transfer.
+    pub fn poke(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x7FFF => self.force_write_rom(address, value),
+            0x8000..=0x9FFF => self.set_vram(address - 0x8000, value),
+            0xA000..=0xBFFF => {
+                if let Some(bank) = self.peek_ram_bank() {
+                    self.ram_banks[bank][(address - 0xA000) as usize] = value;
+                }
+            }
+            0xC000..=0xDFFF => self.set_wram(address - 0xC000, value),
+            0xE000..=0xFDFF => self.set_wram(address - 0xE000, value),
+            0xFE00..=0xFE9F => self.set_oam(address - 0xFE00, value),
+            0xFEA0..=0xFEFF => {}
+            P1_ADDRESS => self.io_registers[0] = value & P1_SELECT_MASK,
+            IF_ADDRESS => self.interrupts.write_if(value),
+            0xFF01..=0xFF7F => self.io_registers[(address - 0xFF00) as usize] = value,
+            0xFF80..=0xFFFE => self.set_hram(address - 0xFF80, value),
+            0xFFFF => self.interrupts.write_ie(value),
+        }
+    }
+
+    /// The mapped RAM bank, whether or not the RAM is enabled
+    fn peek_ram_bank(&self) -> Option<usize> {
+        (!self.ram_banks.is_empty()).then(|| self.mbc.get_ram_bank(self.ram_banks.len()))
+    }
+
+    /// Traces the written value with its decoded bit fields, the register is only looked up if tracing is enabled
+    fn log_io_write(&self, address: u16, value: u8) {
+        trace!(
+            target: Subsystem::Mmu.target(),
+            "{}",
+            describe(address).map_or_else(
+                || format!("0x{:04X}=0x{:02X} (unmapped)", address, value),
+                |register| register.format(value)
+            )
+        );
+    }
+
+    pub fn read_16(&self, address: u16) -> u16 {
+        let lsb = self.read(address);
+        let msb = self.read(address.wrapping_add(1));
+        construct_u16(lsb, msb)
+    }
+
+    /// Reads memory like [`peek`](Self::peek), except that the switchable ROM area (0x4000-0x7FFF)
+    /// is read from the given bank instead of the currently mapped one.
+    /// Banks outside the cartridge read as 0xFF.
+    pub fn read_with_rom_bank(&self, bank: usize, address: u16) -> u8 {
+        match address {
+            0x4000..=0x7FFF => self
+                .rom_banks
+                .get(bank)
+                .map(|rom_bank| rom_bank[(address - 0x4000) as usize])
+                .unwrap_or(0xFF),
+            _ => self.peek(address),
+        }
+    }
+
+    /// Peeks `length` bytes starting at the given address.
+    /// Stops at the end of the address space.
+    pub fn dump(&self, start: u16, length: usize) -> Vec<u8> {
+        (start as usize..(start as usize).saturating_add(length).min(0x10000))
+            .map(|address| self.peek(address as u16))
+            .collect()
+    }
+
+    /// The memory backing a region, borrowed instead of copied, e.g. for scripts polling WRAM every frame.
+    /// None for regions which aren't stored in one piece: what ROM and cartridge RAM show depends on the MBC,
+    /// P1, IF and IE are kept by other components and the unusable area doesn't exist.
+    pub fn get_region(&self, region: MemoryRegion) -> Option<&[u8]> {
+        match region {
+            MemoryRegion::Vram => Some(&self.vram),
+            MemoryRegion::Wram => Some(&self.wram),
+            MemoryRegion::EchoRam => Some(&self.wram[..ECHO_RAM_SIZE]),
+            MemoryRegion::Oam => Some(&self.oam),
+            MemoryRegion::Hram => Some(&self.hram),
+            _ => None,
+        }
+    }
+
+    /// A copy of the whole region as [`peek`](Self::peek) sees it
+    pub fn snapshot_region(&self, region: MemoryRegion) -> Vec<u8> {
+        match self.get_region(region) {
+            Some(memory) => memory.to_vec(),
+            None => {
+                let range = region.get_range();
+                self.dump(*range.start(), range.len())
+            }
+        }
+    }
+
+    /// Every address where the given byte sequence starts
+    pub fn search(&self, pattern: &[u8]) -> Vec<u16> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        self.dump(0x0000, 0x10000)
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, window)| *window == pattern)
+            .map(|(address, _)| address as u16)
+            .collect()
+    }
+
+    pub fn get_buttons(&self) -> Buttons {
+        self.joypad.get_pressed()
+    }
+
+    /// Requests the Joypad Interrupt if a newly pressed button is on a selected line
+    pub fn set_buttons(&mut self, buttons: Buttons) {
+        let previous_p1 = self.read_p1();
+        self.joypad.set_pressed(buttons);
+        self.check_joypad_interrupt(previous_p1);
+    }
+
+    /// Whether P1 was read through the memory map since the last call, used to detect lag frames
+    pub fn take_joypad_polled(&mut self) -> bool {
+        self.joypad.take_polled()
+    }
+
+    /// Registers a callback which is invoked on every write to the ROM area, see [`MapperWriteEvent`]
+    pub fn on_mapper_write(
+        &mut self,
+        callback: impl FnMut(&MapperWriteEvent) + Send + 'static,
+    ) -> ListenerId {
+        self.mapper_listeners.subscribe(callback)
+    }
+
+    pub fn remove_mapper_write_listener(&mut self, id: ListenerId) {
+        self.mapper_listeners.unsubscribe(id);
+    }
+
+    /// Registers a callback which is invoked whenever the rumble motor is switched on or off, see [`RumbleEvent`]
+    pub fn on_rumble(&mut self, callback: impl FnMut(&RumbleEvent) + Send + 'static) -> ListenerId {
+        self.rumble_listeners.subscribe(callback)
+    }
+
+    pub fn remove_rumble_listener(&mut self, id: ListenerId) {
+        self.rumble_listeners.unsubscribe(id);
+    }
+
+    /// Registers a callback which is invoked the first time the game does something suspicious, see [`Diagnostic`]
+    pub fn on_diagnostic(
+        &mut self,
+        callback: impl FnMut(&Diagnostic) + Send + 'static,
+    ) -> ListenerId {
+        self.diagnostics.subscribe(callback)
+    }
+
+    pub fn remove_diagnostic_listener(&mut self, id: ListenerId) {
+        self.diagnostics.unsubscribe(id);
+    }
+
+    /// For suspicious behavior noticed outside of the memory map, like the CPU state
+    pub fn report_diagnostic(&self, diagnostic: Diagnostic) {
+        self.diagnostics.report(diagnostic);
+    }
+
+    /// Answers CPU reads of the IO register address (0xFF00-0xFF7F) with the value the hook returns.
+    /// If it returns None the emulated register answers, so a hook can also just observe reads.
+    pub fn add_io_read_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16) -> Option<u8> + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        self.io_hooks.add_read_hook(address, hook)
+    }
+
+    /// Sees CPU writes to the IO register address (0xFF00-0xFF7F) before the emulated register,
+    /// returning true keeps the write from reaching it
+    pub fn add_io_write_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16, u8) -> bool + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        self.io_hooks.add_write_hook(address, hook)
+    }
+
+    pub fn remove_io_hook(&mut self, id: IoHookId) {
+        self.io_hooks.remove(id);
+    }
+
+    /// Replaces the patches of the previously active Game Genie codes, debugger peeks still see the original ROM
+    pub fn set_rom_patches(&mut self, patches: Vec<RomPatch>) {
+        self.rom_patches = patches;
+    }
+
+    /// Tilt in g (-1.0 to 1.0) for cartridges with an accelerometer, ignored by all others
+    pub fn set_tilt(&mut self, x: f32, y: f32) {
+        self.mbc.set_tilt(x, y);
+    }
+
+    pub fn is_motor_on(&self) -> bool {
+        self.mbc.motor_on()
+    }
+
+    /// Brightness values of 128x112 pixels for the Pocket Camera sensor, fails for other cartridges
+    pub fn set_camera_image(&mut self, pixels: &[u8]) -> Result<(), Box<dyn Error>> {
+        let camera = self
+            .mbc
+            .get_camera_mut()
+            .ok_or("The cartridge has no camera")?;
+        Ok(camera.set_image(pixels)?)
+    }
+
+    /// None for other cartridges or if no image was set
+    pub fn get_camera_image(&self) -> Option<&[u8]> {
+        self.mbc.get_camera().and_then(|camera| camera.get_image())
+    }
+
+    /// Runs the mapper hardware, a finished camera capture is written to the start of RAM bank 0
+    pub fn step_cartridge(&mut self, cycles: Cycles) {
+        let Some(tiles) = self.mbc.step(cycles) else {
+            return;
+        };
+        debug!(target: Subsystem::Mmu.target(), "Camera capture finished");
+        if let Some(bank) = self.ram_banks.first_mut() {
+            bank[CAPTURE_OFFSET..CAPTURE_OFFSET + CAPTURE_SIZE].copy_from_slice(&tiles);
+        }
+    }
+
+    /// The inserted cartridge, including writes to ROM by debug tools
+    pub fn get_cartridge(&self) -> Cartridge {
+        Cartridge {
+            rom_banks: self.rom_banks.clone(),
+            header: self.cartridge_header.clone(),
+        }
+    }
+
+    /// All cartridge RAM banks back to back, as stored in a `.sav` file.
+    /// MBC7 cartridges have no RAM and return their EEPROM instead.
+    pub fn get_cartridge_ram(&self) -> Vec<u8> {
+        if let Some(eeprom) = self.mbc.get_eeprom() {
+            return eeprom.to_bytes();
+        }
+        self.ram_banks.concat()
+    }
+
+    pub fn set_cartridge_ram(&mut self, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        if let Some(eeprom) = self.mbc.get_eeprom_mut() {
+            return Ok(eeprom.load_bytes(data)?);
+        }
+        let ram_size = self.ram_banks.len() * RAM_BANK_SIZE;
+        if data.len() != ram_size {
+            return Err(format!(
+                "Expected {} bytes of cartridge RAM but got {}",
+                ram_size,
+                data.len()
+            )
+            .into());
+        }
+
+        for (bank, chunk) in self
+            .ram_banks
+            .iter_mut()
+            .zip(data.chunks_exact(RAM_BANK_SIZE))
+        {
+            bank.copy_from_slice(chunk);
+        }
+        Ok(())
+    }
+
+    /// Switches the emulation of the open bus on or off.
+    /// Without it, reads of disabled cartridge RAM return 0xFF, 0xFEA0-0xFEFF reads 0x00
+    /// and IO registers read back what was last written to them.
+    /// With it, disabled cartridge RAM returns the last value on the external bus, 0xFEA0-0xFEFF reads 0xFF while
+    /// the PPU blocks OAM, unmapped IO registers read 0xFF and bits which can't be read read as 1.
+    /// https://gbdev.io/pandocs/Memory_Map.html
+    pub fn set_open_bus(&mut self, enabled: bool) {
+        self.open_bus = enabled;
+    }
+
+    pub fn has_open_bus(&self) -> bool {
+        self.open_bus
+    }
+
+    /// Starts counting memory accesses from zero, or stops and drops the counters
+    pub fn set_stats_enabled(&mut self, enabled: bool) {
+        self.stats = enabled.then(Box::default);
+    }
+
+    pub fn get_stats(&self) -> Option<&MemoryStats> {
+        self.stats.as_deref()
+    }
+
+    /// Source page of a newly started OAM DMA transfer, if there is one
+    pub fn take_dma_request(&mut self) -> Option<u8> {
+        self.dma_request.take()
+    }
+
+    /// The captured serial output, at most the last [`SERIAL_OUTPUT_LIMIT`] bytes
+    pub fn get_serial_output(&self) -> &[u8] {
+        &self.serial_output
+    }
+
+    /// The captured serial output, which starts over empty afterwards
+    pub fn take_serial_output(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serial_output)
+    }
+
+    /// Whether SB is captured when a transfer starts. Transfers with a link partner are a conversation with it,
+    /// not output of a test ROM, so the Game Boy switches this off while a partner is connected.
+    pub fn set_serial_capture(&mut self, enabled: bool) {
+        self.serial_capture = enabled;
+    }
+
+    /// Once the limit is reached the older half of the captured output is dropped
+    fn capture_serial_byte(&mut self, value: u8) {
+        if self.serial_output.len() >= SERIAL_OUTPUT_LIMIT {
+            self.serial_output.drain(..SERIAL_OUTPUT_LIMIT / 2);
+        }
+        self.serial_output.push(value);
+    }
+
+    /// The ROM bank currently mapped into 0x4000-0x7FFF
+    pub fn get_current_rom_bank(&self) -> usize {
+        self.mbc.get_rom_bank(0x4000, self.rom_banks.len())
+    }
+
+    pub fn get_rom_bank_count(&self) -> usize {
+        self.rom_banks.len()
+    }
+
+    /// The PPU owns the mode and LYC=LY bits of STAT, the CPU can only write the interrupt selects
+    pub fn ppu_update_stat(&mut self, mode: PPUMode, lyc_equals_ly: bool) {
+        let stat = &mut self.io_registers[(STAT_ADDRESS - 0xFF00) as usize];
+        *stat = (*stat & STAT_WRITABLE_MASK)
+            | if lyc_equals_ly { 0b0000_0100 } else { 0 }
+            | mode.get_mode_bits();
+    }
+
+    /// LY is read-only for the CPU
+    pub fn ppu_update_ly(&mut self, line: u8) {
+        self.io_registers[(LY_ADDRESS - 0xFF00) as usize] = line;
+    }
+
+    pub fn timer_update_div(&mut self, value: u8) {
+        let div_index = DIV_ADDRESS - 0xFF00;
+        self.io_registers[div_index as usize] = value;
+    }
+
+    pub fn force_write_rom(&mut self, address: u16, value: u8) {
+        if address < 0x8000 {
+            let bank = self.mbc.get_rom_bank(address, self.rom_banks.len());
+            if let Some(rom_bank) = self.rom_banks.get_mut(bank) {
+                rom_bank[address as usize % ROM_BANK_SIZE] = value;
+            }
+        }
+    }
+
+    pub fn interrupts(&self) -> &InterruptController {
+        &self.interrupts
+    }
+
+    pub fn interrupts_mut(&mut self) -> &mut InterruptController {
+        &mut self.interrupts
+    }
+
+    pub fn save(&self) -> MMUSaveState {
+        // IF lives in the interrupt controller, but is stored with the other IO registers
+        let mut io_registers = self.io_registers;
+        io_registers[(IF_ADDRESS - 0xFF00) as usize] = self.interrupts.read_if();
+
+        MMUSaveState {
+            mbc: self.mbc.clone(),
+            ram: self.ram_banks.iter().map(|bank| bank.to_vec()).collect(),
+            vram: self.vram.to_vec(),
+            wram: self.wram.to_vec(),
+            oam: self.oam.to_vec(),
+            io_registers: io_registers.to_vec(),
+            hram: self.hram.to_vec(),
+            ie_register: self.interrupts.read_ie(),
+            serial_output: self.serial_output.clone(),
+            dma_request: self.dma_request,
+            external_bus: self.external_bus.get(),
+        }
+    }
+
+    /// Fails if the state doesn't fit the cartridge, e.g. because its mapper registers are of another MBC
+    pub fn load(state: MMUSaveState, cartridge: &Cartridge) -> Result<Self, Box<dyn Error>> {
+        let cartridge_mbc = Mbc::initialize(cartridge.header.cartridge_type)?;
+        if core::mem::discriminant(&cartridge_mbc) != core::mem::discriminant(&state.mbc) {
+            return Err(format!(
+                "The save state has {} registers, but the cartridge uses {}",
+                state.mbc.get_name(),
+                cartridge_mbc.get_name()
+            )
+            .into());
+        }
+        let ram_banks = state
+            .ram
+            .into_iter()
+            .map(|bank| bank.try_into().map_err(|_| "Failed to load RAM banks"))
+            .collect::<Result<Vec<[u8; RAM_BANK_SIZE]>, &str>>()?;
+        let mut io_registers: [u8; IO_REGISTERS_SIZE] = state
+            .io_registers
+            .try_into()
+            .map_err(|_| "Failed to load IO registers")?;
+        // IF is stored with the IO registers, but owned by the interrupt controller
+        let interrupt_flag = core::mem::take(&mut io_registers[(IF_ADDRESS - 0xFF00) as usize]);
+
+        Ok(Self {
+            cartridge_header: cartridge.header.clone(),
+            mbc: state.mbc,
+            rom_banks: cartridge.rom_banks.clone(),
+            ram_banks,
+            vram: state.vram.try_into().map_err(|_| "Failed to load VRAM")?,
+            wram: state.wram.try_into().map_err(|_| "Failed to load WRAM")?,
+            oam: state.oam.try_into().map_err(|_| "Failed to load OAM")?,
+            io_registers,
+            hram: state.hram.try_into().map_err(|_| "Failed to load HRAM")?,
+            interrupts: InterruptController::new(interrupt_flag, state.ie_register),
+            joypad: Joypad::default(),
+            dma_request: state.dma_request,
+            stats: None,
+            mapper_listeners: Listeners::default(),
+            rumble_listeners: Listeners::default(),
+            diagnostics: Diagnostics::default(),
+            io_hooks: IoHooks::default(),
+            rom_patches: Vec::new(),
+            serial_output: latest_serial_output(state.serial_output),
+            serial_capture: true,
+            open_bus: false,
+            external_bus: Cell::new(state.external_bus),
+        })
+    }
+}
+
+/// Memory access functions
+/// ToDo: Proper MBC Type Behavior
+impl MMU {
+    /// Reads as 0xFF if the cartridge has no ROM at all
+    fn get_rom(&self, address: u16) -> u8 {
+        let bank = self.mbc.get_rom_bank(address, self.rom_banks.len());
+        self.rom_banks
+            .get(bank)
+            .map_or(0xFF, |rom_bank| rom_bank[address as usize % ROM_BANK_SIZE])
+    }
+
+    /// The first matching Game Genie patch replaces the ROM value
+    fn read_rom(&self, address: u16) -> u8 {
+        let value = self.get_rom(address);
+        self.rom_patches
+            .iter()
+            .find_map(|patch| patch.apply(address, value))
+            .unwrap_or(value)
+    }
+
+    /// ROM can't be written, writes configure the MBC instead
+    /// Logs and reports how the memory bank controller interpreted the write
+    fn set_rom(&mut self, address: u16, value: u8) {
+        let motor_was_on = self.mbc.motor_on();
+        let effect = self.mbc.handle_write(address, value);
+        let event = MapperWriteEvent {
+            address,
+            value,
+            effect,
+            lower_rom_bank: self.mbc.get_rom_bank(0x0000, self.rom_banks.len()),
+            upper_rom_bank: self.mbc.get_rom_bank(0x4000, self.rom_banks.len()),
+            ram_bank: self.mbc.get_ram_bank(self.ram_banks.len()),
+            ram_enabled: self.mbc.ram_enabled(),
+        };
+        debug!(target: Subsystem::Mmu.target(), "{}", event);
+        self.mapper_listeners.notify(&event);
+        if matches!(self.mbc, Mbc::None) {
+            self.diagnostics
+                .report(Diagnostic::RomWriteWithoutMapper { address, value });
+        }
+        self.notify_rumble(motor_was_on);
+    }
+
+    /// Only changes of the motor are reported, games tend to write the same value over and over
+    fn notify_rumble(&self, motor_was_on: bool) {
+        let motor_on = self.mbc.motor_on();
+        if motor_on != motor_was_on {
+            debug!(target: Subsystem::Mmu.target(), "Rumble motor {}", if motor_on { "on" } else { "off" });
+            self.rumble_listeners.notify(&RumbleEvent { motor_on });
+        }
+    }
+
+    fn get_vram(&self, index: u16) -> u8 {
+        self.vram[index as usize]
+    }
+
+    fn set_vram(&mut self, index: u16, value: u8) {
+        self.vram[index as usize] = value;
+    }
+
+    fn get_ram(&self, index: u16) -> u8 {
+        if let Some(value) = self.mbc.read_ram_registers(index) {
+            return value;
+        }
+        if self.ram_banks.is_empty() {
+            return self.read_missing_ram();
+        }
+        if !self.mbc.ram_readable() {
+            self.diagnostics.report(Diagnostic::DisabledRamRead {
+                address: 0xA000 + index,
+            });
+            return self.read_missing_ram();
+        }
+        self.ram_banks[self.mbc.get_ram_bank(self.ram_banks.len())][index as usize]
+    }
+
+    /// Nothing answers reads of cartridge RAM which doesn't exist or is disabled
+    fn read_missing_ram(&self) -> u8 {
+        if self.open_bus {
+            self.external_bus.get()
+        } else {
+            // Pan Docs say this is not guaranteed, but often the case
+            UNMAPPED_VALUE
+        }
+    }
+
+    fn set_ram(&mut self, index: u16, value: u8) {
+        if self.mbc.write_ram_registers(index, value) {
+            return;
+        }
+        if !self.ram_banks.is_empty() && self.mbc.ram_enabled() {
+            let bank = self.mbc.get_ram_bank(self.ram_banks.len());
+            self.ram_banks[bank][index as usize] = value;
+        }
+    }
+
+    fn get_wram(&self, index: u16) -> u8 {
+        self.wram[index as usize]
+    }
+
+    fn set_wram(&mut self, index: u16, value: u8) {
+        self.wram[index as usize] = value;
+    }
+
+    fn get_oam(&self, index: u16) -> u8 {
+        self.oam[index as usize]
+    }
+
+    fn set_oam(&mut self, index: u16, value: u8) {
+        self.oam[index as usize] = value;
+    }
+
+    fn get_unusable(&self) -> u8 {
+        // ToDo: On OAM Block this should trigger OAM corruption
+        let stat = self.io_registers[(STAT_ADDRESS - 0xFF00) as usize];
+        let oam_blocked = matches!(
+            PPUMode::from(stat),
+            PPUMode::OAMSearch | PPUMode::PixelTransfer
+        );
+        if self.open_bus && oam_blocked {
+            UNMAPPED_VALUE
+        } else {
+            UNUSABLE_VALUE
+        }
+    }
+
+    fn set_unusable(&mut self, _value: u8) {
+        // ToDo: Potentially do something?
+    }
+
+    fn get_io_register(&self, index: u16) -> u8 {
+        let value = self.read_io_register(index);
+        if !self.open_bus {
+            return value;
+        }
+        match describe(0xFF00 + index) {
+            Some(register) => value | !register.read_mask,
+            None => UNMAPPED_VALUE,
+        }
+    }
+
+    fn read_io_register(&self, index: u16) -> u8 {
+        if index == IF_ADDRESS - 0xFF00 {
+            return self.interrupts.read_if();
+        }
+        if index == P1_ADDRESS - 0xFF00 {
+            self.joypad.record_poll();
+            return self.read_p1();
+        }
+        if index == STAT_ADDRESS - 0xFF00 {
+            return STAT_UNUSED_BITS | self.io_registers[index as usize];
+        }
+        self.io_registers[index as usize]
+    }
+
+    fn set_io_register(&mut self, index: u16, value: u8) {
+        let div_index: u16 = DIV_ADDRESS - 0xFF00;
+        let sc_index: u16 = SC_ADDRESS - 0xFF00;
+        if index == IF_ADDRESS - 0xFF00 {
+            self.interrupts.write_if(value);
+        } else if index == P1_ADDRESS - 0xFF00 {
+            let previous_p1 = self.read_p1();
+            self.io_registers[index as usize] = value & P1_SELECT_MASK;
+            self.check_joypad_interrupt(previous_p1);
+        } else if index == STAT_ADDRESS - 0xFF00 {
+            let stat = &mut self.io_registers[index as usize];
+            *stat = (*stat & !STAT_WRITABLE_MASK) | (value & STAT_WRITABLE_MASK);
+        } else if index == LY_ADDRESS - 0xFF00 {
+            // Read-only, the PPU updates it through ppu_update_ly
+        } else if index == DMA_ADDRESS - 0xFF00 {
+            self.io_registers[index as usize] = value;
+            self.dma_request = Some(value);
+            debug!(target: Subsystem::Mmu.target(), "OAM DMA from 0x{:02X}00", value);
+        } else if index == div_index {
+            // Write to DIV, reset it
+            self.io_registers[div_index as usize] = 0;
+        } else if index == sc_index && value & 0b1000_0000 != 0 {
+            // Transfer requested, without a link partner the byte in SB is captured right away.
+            // The serial component completes the transfer and clears the flag again.
+            if self.serial_capture {
+                self.capture_serial_byte(self.io_registers[(SB_ADDRESS - 0xFF00) as usize]);
+            }
+            self.io_registers[index as usize] = value;
+        } else {
+            self.io_registers[index as usize] = value;
+        }
+    }
+
+    fn read_p1(&self) -> u8 {
+        self.joypad
+            .read_p1(self.io_registers[(P1_ADDRESS - 0xFF00) as usize])
+    }
+
+    /// The Joypad Interrupt is requested when any P1 button line goes from high to low
+    fn check_joypad_interrupt(&mut self, previous_p1: u8) {
+        if previous_p1 & !self.read_p1() & 0x0F != 0 {
+            self.interrupts.request(Interrupt::Joypad);
+        }
+    }
+
+    fn get_hram(&self, index: u16) -> u8 {
+        self.hram[index as usize]
+    }
+
+    fn set_hram(&mut self, index: u16, value: u8) {
+        self.hram[index as usize] = value;
+    }
+}
+
+impl Default for MMU {
+    fn default() -> Self {
+        Self {
+            cartridge_header: CartridgeHeader::default(),
+            mbc: Mbc::None,
+            rom_banks: vec![[0; ROM_BANK_SIZE]; 2],
+            ram_banks: vec![[0; RAM_BANK_SIZE]; 1],
+            vram: [0; VRAM_SIZE],
+            wram: [0; WRAM_SIZE],
+            oam: [0; OAM_SIZE],
+            io_registers: [0; IO_REGISTERS_SIZE],
+            hram: [0; HRAM_SIZE],
+            interrupts: InterruptController::default(),
+            joypad: Joypad::default(),
+            dma_request: None,
+            stats: None,
+            mapper_listeners: Listeners::default(),
+            rumble_listeners: Listeners::default(),
+            diagnostics: Diagnostics::default(),
+            io_hooks: IoHooks::default(),
+            rom_patches: Vec::new(),
+            serial_output: Vec::new(),
+            serial_capture: true,
+            open_bus: false,
+            external_bus: Cell::new(UNMAPPED_VALUE),
+        }
+    }
+}
+
+/// The cartridge and WRAM share the external bus, VRAM, OAM, IO and HRAM are on busses of their own
+fn is_external_bus(address: u16) -> bool {
+    matches!(address, 0x0000..=0x7FFF | 0xA000..=0xFDFF)
+}
+
+/// States saved before the output was limited may hold more, only the latest bytes are kept
+fn latest_serial_output(mut serial_output: Vec<u8>) -> Vec<u8> {
+    let excess = serial_output.len().saturating_sub(SERIAL_OUTPUT_LIMIT);
+    serial_output.drain(..excess);
+    serial_output
+}
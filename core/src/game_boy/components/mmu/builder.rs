@@ -0,0 +1,63 @@
+use crate::game_boy::components::mmu::{TileMap, MMU, OAM_ADDRESS};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MMUBuilder {
+    mmu: MMU,
+}
+
+impl MMUBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(self) -> MMU {
+        self.mmu
+    }
+
+    pub fn write(mut self, address: u16, value: u8) -> Self {
+        self.mmu.write(address, value);
+        self
+    }
+
+    pub fn rom(mut self, address: u16, value: u8) -> Self {
+        self.mmu.force_write_rom(address, value);
+        self
+    }
+
+    /// Writes tile data using the 0x8000 addressing mode (index 0-383)
+    pub fn tile(mut self, index: u16, data: [u8; 16]) -> Self {
+        let address = 0x8000 + index * 16;
+        for (offset, byte) in data.iter().enumerate() {
+            self.mmu.write(address + offset as u16, *byte);
+        }
+        self
+    }
+
+    /// Sets the tile ID at the given tile coordinates (0-31) of a tile map
+    pub fn tilemap_entry(mut self, map: TileMap, x: u8, y: u8, tile: u8) -> Self {
+        let address = map.get_address() + (y as u16 % 32) * 32 + (x as u16 % 32);
+        self.mmu.write(address, tile);
+        self
+    }
+
+    /// Writes an OAM entry (index 0-39)
+    pub fn sprite(mut self, index: u8, y: u8, x: u8, tile: u8, attributes: u8) -> Self {
+        let address = OAM_ADDRESS + index as u16 * 4;
+        self.mmu.write(address, y);
+        self.mmu.write(address + 1, x);
+        self.mmu.write(address + 2, tile);
+        self.mmu.write(address + 3, attributes);
+        self
+    }
+
+    /// Writes an IO register (0xFF00-0xFF7F) or IE (0xFFFF)
+    pub fn io(mut self, register: u16, value: u8) -> Self {
+        assert!(
+            (0xFF00..=0xFF7F).contains(&register) || register == 0xFFFF,
+            "0x{:04X} is not an IO register",
+            register
+        );
+        self.mmu.write(register, value);
+        self
+    }
+}
@@ -0,0 +1,110 @@
+//! Custom handlers for IO register addresses, e.g. a fictional debug port for homebrew or a stub for hardware which isn't emulated.
+//! Hooks only see accesses of the CPU ([`MMU::cpu_read`](super::MMU::cpu_read) and [`MMU::cpu_write`](super::MMU::cpu_write)),
+//! the timer, PPU, serial port and DMA access their registers without them.
+//! Like listeners they are not part of the emulated state.
+
+use crate::helpers::shared::{share, try_lock, Shared};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Debug, Formatter};
+use core::ops::RangeInclusive;
+
+pub const IO_HOOK_ADDRESSES: RangeInclusive<u16> = 0xFF00..=0xFF7F;
+
+/// Returns the value the address reads as, or None to leave the read to the emulated register
+pub type IoReadHook = Shared<dyn FnMut(u16) -> Option<u8> + Send>;
+/// Returns true if the write was handled and must not reach the emulated register
+pub type IoWriteHook = Shared<dyn FnMut(u16, u8) -> bool + Send>;
+
+/// Handle returned when adding a hook, used to remove it again
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IoHookId(usize);
+
+#[derive(Default, Clone)]
+pub struct IoHooks {
+    read_hooks: Vec<(IoHookId, u16, IoReadHook)>,
+    write_hooks: Vec<(IoHookId, u16, IoWriteHook)>,
+    next_id: usize,
+}
+
+impl IoHooks {
+    pub fn add_read_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16) -> Option<u8> + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        let id = self.next_id(address)?;
+        self.read_hooks.push((id, address, share(hook)));
+        Ok(id)
+    }
+
+    pub fn add_write_hook(
+        &mut self,
+        address: u16,
+        hook: impl FnMut(u16, u8) -> bool + Send + 'static,
+    ) -> Result<IoHookId, Box<dyn Error>> {
+        let id = self.next_id(address)?;
+        self.write_hooks.push((id, address, share(hook)));
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, id: IoHookId) {
+        self.read_hooks.retain(|(hook_id, ..)| *hook_id != id);
+        self.write_hooks.retain(|(hook_id, ..)| *hook_id != id);
+    }
+
+    /// The value of the most recently added hook which answered the read
+    pub fn read(&self, address: u16) -> Option<u8> {
+        self.read_hooks
+            .iter()
+            .rev()
+            .filter(|(_, hook_address, _)| *hook_address == address)
+            .find_map(|(.., hook)| try_lock(hook).and_then(|mut hook| hook(address)))
+    }
+
+    /// Every hook of the address sees the write, it is handled if any of them handled it
+    pub fn write(&self, address: u16, value: u8) -> bool {
+        let mut handled = false;
+        for (_, hook_address, hook) in &self.write_hooks {
+            if *hook_address == address {
+                if let Some(mut hook) = try_lock(hook) {
+                    handled |= hook(address, value);
+                }
+            }
+        }
+        handled
+    }
+
+    fn next_id(&mut self, address: u16) -> Result<IoHookId, Box<dyn Error>> {
+        if !IO_HOOK_ADDRESSES.contains(&address) {
+            return Err(format!(
+                "0x{address:04X} is not an IO register address (0x{:04X}-0x{:04X})",
+                IO_HOOK_ADDRESSES.start(),
+                IO_HOOK_ADDRESSES.end()
+            )
+            .into());
+        }
+        let id = IoHookId(self.next_id);
+        self.next_id += 1;
+        Ok(id)
+    }
+}
+
+impl Debug for IoHooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "IoHooks({} read, {} write)",
+            self.read_hooks.len(),
+            self.write_hooks.len()
+        )
+    }
+}
+
+impl PartialEq for IoHooks {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
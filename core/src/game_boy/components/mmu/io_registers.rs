@@ -0,0 +1,387 @@
+//! Names, bit fields and access masks of the memory mapped IO registers, for human readable debug output.
+//! The masks are the ones of the DMG hardware, CGB only registers are not described.
+//! https://gbdev.io/pandocs/Hardware_Reg_List.html
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// One or more adjacent bits of a register with a meaning of their own
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BitField {
+    pub name: &'static str,
+    pub mask: u8,
+}
+
+impl BitField {
+    const fn new(name: &'static str, mask: u8) -> Self {
+        Self { name, mask }
+    }
+
+    /// The bits of the field, shifted down to start at bit 0
+    pub fn extract(&self, value: u8) -> u8 {
+        (value & self.mask) >> self.mask.trailing_zeros()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RegisterInfo {
+    pub address: u16,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Bits the CPU can read, the others read as 1
+    pub read_mask: u8,
+    /// Bits the CPU can write, writes to the others are ignored
+    pub write_mask: u8,
+    /// From the highest bit down, empty if the register is a plain number
+    pub fields: &'static [BitField],
+}
+
+impl RegisterInfo {
+    /// The value of every bit field
+    pub fn decode(&self, value: u8) -> Vec<(&'static str, u8)> {
+        self.fields
+            .iter()
+            .map(|field| (field.name, field.extract(value)))
+            .collect()
+    }
+
+    /// e.g. `TAC=0x05 (Enable=1, Clock select=1)`
+    pub fn format(&self, value: u8) -> String {
+        let mut formatted = format!("{}=0x{:02X}", self.name, value);
+        if !self.fields.is_empty() {
+            let fields: Vec<String> = self
+                .decode(value)
+                .into_iter()
+                .map(|(name, field_value)| format!("{name}={field_value}"))
+                .collect();
+            let _ = write!(formatted, " ({})", fields.join(", "));
+        }
+        formatted
+    }
+}
+
+const INTERRUPT_FIELDS: [BitField; 5] = [
+    BitField::new("Joypad", 0b0001_0000),
+    BitField::new("Serial", 0b0000_1000),
+    BitField::new("Timer", 0b0000_0100),
+    BitField::new("LCD", 0b0000_0010),
+    BitField::new("VBlank", 0b0000_0001),
+];
+const PALETTE_FIELDS: [BitField; 4] = [
+    BitField::new("Color 3", 0b1100_0000),
+    BitField::new("Color 2", 0b0011_0000),
+    BitField::new("Color 1", 0b0000_1100),
+    BitField::new("Color 0", 0b0000_0011),
+];
+const LENGTH_DUTY_FIELDS: [BitField; 2] = [
+    BitField::new("Wave duty", 0b1100_0000),
+    BitField::new("Initial length timer", 0b0011_1111),
+];
+const VOLUME_ENVELOPE_FIELDS: [BitField; 3] = [
+    BitField::new("Initial volume", 0b1111_0000),
+    BitField::new("Envelope direction", 0b0000_1000),
+    BitField::new("Sweep pace", 0b0000_0111),
+];
+const PERIOD_HIGH_CONTROL_FIELDS: [BitField; 3] = [
+    BitField::new("Trigger", 0b1000_0000),
+    BitField::new("Length enable", 0b0100_0000),
+    BitField::new("Period high", 0b0000_0111),
+];
+const WAVE_RAM_FIELDS: [BitField; 2] = [
+    BitField::new("Upper sample", 0b1111_0000),
+    BitField::new("Lower sample", 0b0000_1111),
+];
+const WAVE_RAM_NAMES: [&str; 16] = [
+    "WAVE0", "WAVE1", "WAVE2", "WAVE3", "WAVE4", "WAVE5", "WAVE6", "WAVE7", "WAVE8", "WAVE9",
+    "WAVE10", "WAVE11", "WAVE12", "WAVE13", "WAVE14", "WAVE15",
+];
+
+/// The register at the address, None for unmapped IO addresses and everything outside of 0xFF00-0xFF7F and IE
+pub fn describe(address: u16) -> Option<RegisterInfo> {
+    let register =
+        |name, description, read_mask, write_mask, fields: &'static [BitField]| RegisterInfo {
+            address,
+            name,
+            description,
+            read_mask,
+            write_mask,
+            fields,
+        };
+
+    let info = match address {
+        0xFF00 => register(
+            "P1",
+            "Joypad",
+            0x3F,
+            0x30,
+            const {
+                &[
+                    BitField::new("Select buttons", 0b0010_0000),
+                    BitField::new("Select d-pad", 0b0001_0000),
+                    BitField::new("Start / Down", 0b0000_1000),
+                    BitField::new("Select / Up", 0b0000_0100),
+                    BitField::new("B / Left", 0b0000_0010),
+                    BitField::new("A / Right", 0b0000_0001),
+                ]
+            },
+        ),
+        0xFF01 => register("SB", "Serial transfer data", 0xFF, 0xFF, &[]),
+        0xFF02 => register(
+            "SC",
+            "Serial transfer control",
+            0x81,
+            0x81,
+            const {
+                &[
+                    BitField::new("Transfer enable", 0b1000_0000),
+                    BitField::new("Clock select", 0b0000_0001),
+                ]
+            },
+        ),
+        0xFF04 => register("DIV", "Divider, any write resets it", 0xFF, 0xFF, &[]),
+        0xFF05 => register("TIMA", "Timer counter", 0xFF, 0xFF, &[]),
+        0xFF06 => register("TMA", "Timer modulo", 0xFF, 0xFF, &[]),
+        0xFF07 => register(
+            "TAC",
+            "Timer control",
+            0x07,
+            0x07,
+            const {
+                &[
+                    BitField::new("Enable", 0b0000_0100),
+                    BitField::new("Clock select", 0b0000_0011),
+                ]
+            },
+        ),
+        0xFF0F => register("IF", "Interrupt flag", 0x1F, 0x1F, &INTERRUPT_FIELDS),
+        0xFF10 => register(
+            "NR10",
+            "Channel 1 sweep",
+            0x7F,
+            0x7F,
+            const {
+                &[
+                    BitField::new("Pace", 0b0111_0000),
+                    BitField::new("Direction", 0b0000_1000),
+                    BitField::new("Individual step", 0b0000_0111),
+                ]
+            },
+        ),
+        0xFF11 => register(
+            "NR11",
+            "Channel 1 length timer and duty cycle",
+            0xC0,
+            0xFF,
+            &LENGTH_DUTY_FIELDS,
+        ),
+        0xFF12 => register(
+            "NR12",
+            "Channel 1 volume and envelope",
+            0xFF,
+            0xFF,
+            &VOLUME_ENVELOPE_FIELDS,
+        ),
+        0xFF13 => register("NR13", "Channel 1 period low", 0x00, 0xFF, &[]),
+        0xFF14 => register(
+            "NR14",
+            "Channel 1 period high and control",
+            0x40,
+            0xC7,
+            &PERIOD_HIGH_CONTROL_FIELDS,
+        ),
+        0xFF16 => register(
+            "NR21",
+            "Channel 2 length timer and duty cycle",
+            0xC0,
+            0xFF,
+            &LENGTH_DUTY_FIELDS,
+        ),
+        0xFF17 => register(
+            "NR22",
+            "Channel 2 volume and envelope",
+            0xFF,
+            0xFF,
+            &VOLUME_ENVELOPE_FIELDS,
+        ),
+        0xFF18 => register("NR23", "Channel 2 period low", 0x00, 0xFF, &[]),
+        0xFF19 => register(
+            "NR24",
+            "Channel 2 period high and control",
+            0x40,
+            0xC7,
+            &PERIOD_HIGH_CONTROL_FIELDS,
+        ),
+        0xFF1A => register(
+            "NR30",
+            "Channel 3 DAC enable",
+            0x80,
+            0x80,
+            const { &[BitField::new("DAC enable", 0b1000_0000)] },
+        ),
+        0xFF1B => register("NR31", "Channel 3 length timer", 0x00, 0xFF, &[]),
+        0xFF1C => register(
+            "NR32",
+            "Channel 3 output level",
+            0x60,
+            0x60,
+            const { &[BitField::new("Output level", 0b0110_0000)] },
+        ),
+        0xFF1D => register("NR33", "Channel 3 period low", 0x00, 0xFF, &[]),
+        0xFF1E => register(
+            "NR34",
+            "Channel 3 period high and control",
+            0x40,
+            0xC7,
+            &PERIOD_HIGH_CONTROL_FIELDS,
+        ),
+        0xFF20 => register(
+            "NR41",
+            "Channel 4 length timer",
+            0x00,
+            0x3F,
+            const { &[BitField::new("Initial length timer", 0b0011_1111)] },
+        ),
+        0xFF21 => register(
+            "NR42",
+            "Channel 4 volume and envelope",
+            0xFF,
+            0xFF,
+            &VOLUME_ENVELOPE_FIELDS,
+        ),
+        0xFF22 => register(
+            "NR43",
+            "Channel 4 frequency and randomness",
+            0xFF,
+            0xFF,
+            const {
+                &[
+                    BitField::new("Clock shift", 0b1111_0000),
+                    BitField::new("LFSR width", 0b0000_1000),
+                    BitField::new("Clock divider", 0b0000_0111),
+                ]
+            },
+        ),
+        0xFF23 => register(
+            "NR44",
+            "Channel 4 control",
+            0x40,
+            0xC0,
+            const {
+                &[
+                    BitField::new("Trigger", 0b1000_0000),
+                    BitField::new("Length enable", 0b0100_0000),
+                ]
+            },
+        ),
+        0xFF24 => register(
+            "NR50",
+            "Master volume and VIN panning",
+            0xFF,
+            0xFF,
+            const {
+                &[
+                    BitField::new("VIN left", 0b1000_0000),
+                    BitField::new("Left volume", 0b0111_0000),
+                    BitField::new("VIN right", 0b0000_1000),
+                    BitField::new("Right volume", 0b0000_0111),
+                ]
+            },
+        ),
+        0xFF25 => register(
+            "NR51",
+            "Sound panning",
+            0xFF,
+            0xFF,
+            const {
+                &[
+                    BitField::new("CH4 left", 0b1000_0000),
+                    BitField::new("CH3 left", 0b0100_0000),
+                    BitField::new("CH2 left", 0b0010_0000),
+                    BitField::new("CH1 left", 0b0001_0000),
+                    BitField::new("CH4 right", 0b0000_1000),
+                    BitField::new("CH3 right", 0b0000_0100),
+                    BitField::new("CH2 right", 0b0000_0010),
+                    BitField::new("CH1 right", 0b0000_0001),
+                ]
+            },
+        ),
+        0xFF26 => register(
+            "NR52",
+            "Sound on/off",
+            0x8F,
+            0x80,
+            const {
+                &[
+                    BitField::new("Audio enable", 0b1000_0000),
+                    BitField::new("CH4 on", 0b0000_1000),
+                    BitField::new("CH3 on", 0b0000_0100),
+                    BitField::new("CH2 on", 0b0000_0010),
+                    BitField::new("CH1 on", 0b0000_0001),
+                ]
+            },
+        ),
+        0xFF30..=0xFF3F => register(
+            WAVE_RAM_NAMES[(address - 0xFF30) as usize],
+            "Wave pattern RAM",
+            0xFF,
+            0xFF,
+            &WAVE_RAM_FIELDS,
+        ),
+        0xFF40 => register(
+            "LCDC",
+            "LCD control",
+            0xFF,
+            0xFF,
+            const {
+                &[
+                    BitField::new("LCD enable", 0b1000_0000),
+                    BitField::new("Window tile map", 0b0100_0000),
+                    BitField::new("Window enable", 0b0010_0000),
+                    BitField::new("BG and window tiles", 0b0001_0000),
+                    BitField::new("BG tile map", 0b0000_1000),
+                    BitField::new("OBJ size", 0b0000_0100),
+                    BitField::new("OBJ enable", 0b0000_0010),
+                    BitField::new("BG and window enable", 0b0000_0001),
+                ]
+            },
+        ),
+        0xFF41 => register(
+            "STAT",
+            "LCD status",
+            0x7F,
+            0x78,
+            const {
+                &[
+                    BitField::new("LYC int select", 0b0100_0000),
+                    BitField::new("Mode 2 int select", 0b0010_0000),
+                    BitField::new("Mode 1 int select", 0b0001_0000),
+                    BitField::new("Mode 0 int select", 0b0000_1000),
+                    BitField::new("LYC == LY", 0b0000_0100),
+                    BitField::new("PPU mode", 0b0000_0011),
+                ]
+            },
+        ),
+        0xFF42 => register("SCY", "Background viewport Y", 0xFF, 0xFF, &[]),
+        0xFF43 => register("SCX", "Background viewport X", 0xFF, 0xFF, &[]),
+        0xFF44 => register("LY", "LCD Y coordinate", 0xFF, 0x00, &[]),
+        0xFF45 => register("LYC", "LY compare", 0xFF, 0xFF, &[]),
+        0xFF46 => register("DMA", "OAM DMA source address high", 0xFF, 0xFF, &[]),
+        0xFF47 => register("BGP", "BG palette data", 0xFF, 0xFF, &PALETTE_FIELDS),
+        0xFF48 => register("OBP0", "OBJ palette 0 data", 0xFF, 0xFF, &PALETTE_FIELDS),
+        0xFF49 => register("OBP1", "OBJ palette 1 data", 0xFF, 0xFF, &PALETTE_FIELDS),
+        0xFF4A => register("WY", "Window Y position", 0xFF, 0xFF, &[]),
+        0xFF4B => register("WX", "Window X position plus 7", 0xFF, 0xFF, &[]),
+        0xFF50 => register(
+            "BANK",
+            "Boot ROM mapping",
+            0x00,
+            0x01,
+            const { &[BitField::new("Boot ROM disabled", 0b0000_0001)] },
+        ),
+        0xFFFF => register("IE", "Interrupt enable", 0xFF, 0xFF, &INTERRUPT_FIELDS),
+        _ => return None,
+    };
+    Some(info)
+}
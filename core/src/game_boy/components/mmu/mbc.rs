@@ -0,0 +1,324 @@
+use crate::game_boy::components::cartridge::types::{CartridgeType, MbcType};
+use crate::game_boy::components::mmu::mbc::camera::Camera;
+use crate::game_boy::components::mmu::mbc::eeprom::Eeprom;
+use crate::game_boy::components::mmu::mbc::mbc1::Mbc1;
+use crate::game_boy::components::mmu::mbc::mbc5::Mbc5;
+use crate::game_boy::components::mmu::mbc::mbc7::Mbc7;
+use crate::game_boy::cycles::Cycles;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+
+pub mod camera;
+pub mod eeprom;
+pub mod mbc1;
+pub mod mbc5;
+pub mod mbc7;
+
+/// The registers of the memory bank controller. The enum is stored in save states as it is,
+/// so every bit of mapper state (e.g. RTC latches or the rumble motor) has to live in its variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Mbc {
+    None,
+    Mbc1(Mbc1),
+    Mbc5(Mbc5),
+    Mbc7(Mbc7),
+    Camera(Camera),
+}
+
+impl Mbc {
+    pub fn initialize(cartridge_type: CartridgeType) -> Result<Mbc, Box<dyn Error>> {
+        let mbc_type = cartridge_type.mapper_kind();
+        match mbc_type {
+            MbcType::None => Ok(Mbc::None),
+            MbcType::MBC1 => Ok(Mbc::Mbc1(Mbc1::initialize(false))),
+            // Within MBC5 cartridges the rumble flag tells which ones switch the motor with bit 3 of the RAM bank
+            MbcType::MBC5 => Ok(Mbc::Mbc5(Mbc5::initialize(cartridge_type.has_rumble()))),
+            MbcType::MBC7 => Ok(Mbc::Mbc7(Mbc7::initialize())),
+            MbcType::Camera => Ok(Mbc::Camera(Camera::initialize())),
+            _ => Err(format!("Unsupported MBC type {:?}", mbc_type).into()),
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Mbc::None => "no MBC",
+            Mbc::Mbc1(_) => "MBC1",
+            Mbc::Mbc5(_) => "MBC5",
+            Mbc::Mbc7(_) => "MBC7",
+            Mbc::Camera(_) => "Pocket Camera",
+        }
+    }
+
+    /// Back to the registers at power on, e.g. bank 1 mapped and RAM disabled
+    pub fn reset(&mut self) {
+        match self {
+            Mbc::None => {}
+            Mbc::Mbc1(mbc1) => mbc1.reset(),
+            Mbc::Mbc5(mbc5) => mbc5.reset(),
+            Mbc::Mbc7(mbc7) => mbc7.reset(),
+            Mbc::Camera(camera) => camera.reset(),
+        }
+    }
+
+    /// Writes to the ROM area (0x0000-0x7FFF) go to the registers of the memory bank controller
+    pub fn handle_write(&mut self, address: u16, value: u8) -> MapperWrite {
+        match self {
+            Mbc::None => MapperWrite::Ignored,
+            Mbc::Mbc1(mbc1) => mbc1.handle_write(address, value),
+            Mbc::Mbc5(mbc5) => mbc5.handle_write(address, value),
+            Mbc::Mbc7(mbc7) => mbc7.handle_write(address, value),
+            Mbc::Camera(camera) => camera.handle_write(address, value),
+        }
+    }
+
+    pub fn get_lower_rom_index(&self) -> usize {
+        match self {
+            Mbc::None => 0,
+            Mbc::Mbc1(mbc1) => mbc1.get_lower_rom_index(),
+            Mbc::Mbc5(mbc5) => mbc5.get_lower_rom_index(),
+            Mbc::Mbc7(mbc7) => mbc7.get_lower_rom_index(),
+            Mbc::Camera(camera) => camera.get_lower_rom_index(),
+        }
+    }
+
+    pub fn get_upper_rom_index(&self) -> usize {
+        match self {
+            Mbc::None => 1,
+            Mbc::Mbc1(mbc1) => mbc1.get_upper_rom_index(),
+            Mbc::Mbc5(mbc5) => mbc5.get_upper_rom_index(),
+            Mbc::Mbc7(mbc7) => mbc7.get_upper_rom_index(),
+            Mbc::Camera(camera) => camera.get_upper_rom_index(),
+        }
+    }
+
+    pub fn get_ram_index(&self) -> usize {
+        match self {
+            Mbc::None => 0,
+            Mbc::Mbc1(mbc1) => mbc1.get_ram_index(),
+            Mbc::Mbc5(mbc5) => mbc5.get_ram_index(),
+            Mbc::Mbc7(_) => 0,
+            Mbc::Camera(camera) => camera.get_ram_index(),
+        }
+    }
+
+    /// The ROM bank mapped at the address (0x0000-0x7FFF), wrapped to the banks that exist
+    pub fn get_rom_bank(&self, address: u16, rom_bank_count: usize) -> usize {
+        let bank = if address < 0x4000 {
+            self.get_lower_rom_index()
+        } else {
+            self.get_upper_rom_index()
+        };
+        wrap_bank(bank, rom_bank_count)
+    }
+
+    /// The RAM bank mapped into 0xA000-0xBFFF, wrapped to the banks that exist
+    pub fn get_ram_bank(&self, ram_bank_count: usize) -> usize {
+        wrap_bank(self.get_ram_index(), ram_bank_count)
+    }
+
+    pub fn ram_enabled(&self) -> bool {
+        match self {
+            Mbc::None => true,
+            Mbc::Mbc1(mbc1) => mbc1.ram_enabled(),
+            Mbc::Mbc5(mbc5) => mbc5.ram_enabled(),
+            Mbc::Mbc7(mbc7) => mbc7.ram_enabled(),
+            Mbc::Camera(camera) => camera.ram_enabled(),
+        }
+    }
+
+    /// Whether the RAM can be read, the Pocket Camera allows it even while writes are disabled
+    pub fn ram_readable(&self) -> bool {
+        match self {
+            Mbc::Camera(_) => true,
+            _ => self.ram_enabled(),
+        }
+    }
+
+    /// Reads of 0xA000-0xBFFF (given as an offset) for mappers which have registers there instead of RAM
+    pub fn read_ram_registers(&self, index: u16) -> Option<u8> {
+        match self {
+            Mbc::Mbc7(mbc7) => Some(mbc7.read_registers(index)),
+            Mbc::Camera(camera) => camera.read_registers(index),
+            _ => None,
+        }
+    }
+
+    /// Returns false if the write is meant for the cartridge RAM instead
+    pub fn write_ram_registers(&mut self, index: u16, value: u8) -> bool {
+        match self {
+            Mbc::Mbc7(mbc7) => {
+                mbc7.write_registers(index, value);
+                true
+            }
+            Mbc::Camera(camera) => camera.write_registers(index, value),
+            _ => false,
+        }
+    }
+
+    /// The EEPROM which MBC7 cartridges have instead of RAM
+    pub fn get_eeprom(&self) -> Option<&Eeprom> {
+        match self {
+            Mbc::Mbc7(mbc7) => Some(mbc7.get_eeprom()),
+            _ => None,
+        }
+    }
+
+    pub fn get_eeprom_mut(&mut self) -> Option<&mut Eeprom> {
+        match self {
+            Mbc::Mbc7(mbc7) => Some(mbc7.get_eeprom_mut()),
+            _ => None,
+        }
+    }
+
+    /// Tilt in g for cartridges with an accelerometer, ignored by all others
+    pub fn set_tilt(&mut self, x: f32, y: f32) {
+        if let Mbc::Mbc7(mbc7) = self {
+            mbc7.set_tilt(x, y);
+        }
+    }
+
+    /// Advances mapper hardware which runs on its own, returns the tiles of a finished camera capture
+    pub fn step(&mut self, cycles: Cycles) -> Option<Vec<u8>> {
+        match self {
+            Mbc::Camera(camera) => camera.step(cycles),
+            _ => None,
+        }
+    }
+
+    /// The image sensor of the Pocket Camera, None for all other cartridges
+    pub fn get_camera(&self) -> Option<&Camera> {
+        match self {
+            Mbc::Camera(camera) => Some(camera),
+            _ => None,
+        }
+    }
+
+    pub fn get_camera_mut(&mut self) -> Option<&mut Camera> {
+        match self {
+            Mbc::Camera(camera) => Some(camera),
+            _ => None,
+        }
+    }
+
+    /// Whether the rumble motor of the cartridge is running, false for cartridges without one
+    pub fn motor_on(&self) -> bool {
+        match self {
+            Mbc::None | Mbc::Mbc1(_) | Mbc::Mbc7(_) | Mbc::Camera(_) => false,
+            Mbc::Mbc5(mbc5) => mbc5.motor_on(),
+        }
+    }
+}
+
+/// How the memory bank controller interpreted a write to the ROM area
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MapperWrite {
+    /// There is no register at the address, or no memory bank controller at all
+    Ignored,
+    RamEnable(bool),
+    /// The lower bits of the ROM bank number, after masking and turning 0 into 1 on MBC1
+    RomBankLow(u8),
+    /// The second RAM enable of MBC7, both have to be set
+    SecondRamEnable(bool),
+    /// Bit 8 of the ROM bank number on MBC5
+    RomBankHigh(u8),
+    /// The RAM bank register of MBC5, on the Pocket Camera bit 4 maps the capture registers instead
+    RamBank(u8),
+    /// The RAM bank register of MBC5 rumble cartridges, where bit 3 switches the motor
+    RamBankMotor {
+        ram_bank: u8,
+        motor_on: bool,
+    },
+    /// The 2 bit register which holds the upper ROM bank bits or the RAM bank number
+    UpperBits(u8),
+    /// True for the advanced banking mode, which applies the upper bits to 0x0000-0x3FFF and the RAM as well
+    BankingMode(bool),
+}
+
+impl Display for MapperWrite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MapperWrite::Ignored => write!(f, "ignored"),
+            MapperWrite::RamEnable(enabled) => {
+                write!(f, "RAM {}", if *enabled { "enabled" } else { "disabled" })
+            }
+            MapperWrite::RomBankLow(bits) => write!(f, "ROM bank low bits = 0x{:02X}", bits),
+            MapperWrite::SecondRamEnable(enabled) => write!(
+                f,
+                "second RAM enable {}",
+                if *enabled { "set" } else { "cleared" }
+            ),
+            MapperWrite::RomBankHigh(bit) => write!(f, "ROM bank high bit = {}", bit),
+            MapperWrite::RamBank(bank) => write!(f, "RAM bank = 0x{:02X}", bank),
+            MapperWrite::RamBankMotor { ram_bank, motor_on } => write!(
+                f,
+                "RAM bank = 0x{:02X}, motor {}",
+                ram_bank,
+                if *motor_on { "on" } else { "off" }
+            ),
+            MapperWrite::UpperBits(bits) => write!(f, "upper bank bits = {}", bits),
+            MapperWrite::BankingMode(advanced) => write!(
+                f,
+                "banking mode {}",
+                if *advanced {
+                    "1 (advanced)"
+                } else {
+                    "0 (simple)"
+                }
+            ),
+        }
+    }
+}
+
+/// A write to the ROM area together with the banks mapped afterwards
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MapperWriteEvent {
+    pub address: u16,
+    pub value: u8,
+    pub effect: MapperWrite,
+    /// The ROM bank at 0x0000-0x3FFF
+    pub lower_rom_bank: usize,
+    /// The ROM bank at 0x4000-0x7FFF
+    pub upper_rom_bank: usize,
+    pub ram_bank: usize,
+    pub ram_enabled: bool,
+}
+
+/// e.g. `[0x2000] <- 0x05: ROM bank low bits = 0x05 => ROM 0x00/0x05, RAM 0x00 disabled`
+impl Display for MapperWriteEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "[0x{:04X}] <- 0x{:02X}: {} => ROM 0x{:02X}/0x{:02X}, RAM 0x{:02X} {}",
+            self.address,
+            self.value,
+            self.effect,
+            self.lower_rom_bank,
+            self.upper_rom_bank,
+            self.ram_bank,
+            if self.ram_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        )
+    }
+}
+
+/// The rumble motor of the cartridge was switched on or off
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RumbleEvent {
+    pub motor_on: bool,
+}
+
+/// The memory chips ignore bank bits they have no address lines for, so banks beyond the end mirror the populated ones.
+/// Bank counts which aren't a power of two (odd homebrew sizes) wrap around at the end.
+pub fn wrap_bank(bank: usize, bank_count: usize) -> usize {
+    if bank_count == 0 {
+        return 0;
+    }
+    (bank & (bank_count.next_power_of_two() - 1)) % bank_count
+}
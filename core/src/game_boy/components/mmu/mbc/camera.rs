@@ -0,0 +1,222 @@
+use crate::game_boy::components::mmu::mbc::MapperWrite;
+use crate::game_boy::cycles::Cycles;
+use crate::helpers::graphics;
+use crate::helpers::graphics::{TILE_BYTES, TILE_SIZE};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The sensor image is 128x112 pixels, the same as 16x14 tiles
+pub const CAMERA_WIDTH: usize = 128;
+pub const CAMERA_HEIGHT: usize = 112;
+/// Where the captured tiles end up in RAM bank 0, as an offset from 0xA000
+pub const CAPTURE_OFFSET: usize = 0x0100;
+pub const CAPTURE_SIZE: usize = CAMERA_WIDTH * CAMERA_HEIGHT / 4;
+/// RAM bank values with this bit set map the registers instead of RAM
+const REGISTER_BANK: u8 = 0x10;
+/// A000 (start and flags), A001-A005 (sensor settings) and a 4x4 matrix of 3 dithering thresholds each
+const REGISTER_COUNT: usize = 0x36;
+const EXPOSURE_HIGH: usize = 0x02;
+const EXPOSURE_LOW: usize = 0x03;
+const INVERT_AND_VOLTAGE: usize = 0x04;
+const DITHER_MATRIX: usize = 0x06;
+/// Without an image the sensor sees this mid gray
+const DEFAULT_BRIGHTNESS: u8 = 0x80;
+/// The exposure time at which the sensor image is taken as it is, longer ones brighten it
+const NEUTRAL_EXPOSURE: u32 = 0x0800;
+
+/// The Game Boy Camera (Pocket Camera) mapper, which has the M64282FP image sensor attached.
+/// With bit 4 of the RAM bank set, 0xA000-0xA035 holds the capture registers instead of RAM.
+/// The RAM can always be read, but only written after enabling it.
+/// https://gbdev.io/pandocs/Gameboy_Camera.html
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Camera {
+    rom_bank: u8,
+    ram_bank: u8,
+    ram_enabled: bool,
+    registers: Vec<u8>,
+    /// T-cycles until the running capture is written to RAM, 0 without one
+    capture_cycles_left: u32,
+    /// What the sensor sees as 8 bit brightness values, row by row. An input like the held buttons,
+    /// so it is not part of save states. Empty until an image is set.
+    #[serde(skip)]
+    image: Vec<u8>,
+}
+
+impl Camera {
+    pub fn initialize() -> Self {
+        Self {
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            registers: vec![0; REGISTER_COUNT],
+            capture_cycles_left: 0,
+            image: Vec::new(),
+        }
+    }
+
+    /// A running capture is aborted, the sensor image is kept
+    pub fn reset(&mut self) {
+        *self = Self {
+            image: core::mem::take(&mut self.image),
+            ..Self::initialize()
+        };
+    }
+
+    pub fn handle_write(&mut self, address: u16, value: u8) -> MapperWrite {
+        match address {
+            0x0000..=0x1FFF => {
+                self.ram_enabled = value == 0x0A;
+                MapperWrite::RamEnable(self.ram_enabled)
+            }
+            0x2000..=0x3FFF => {
+                self.rom_bank = value & 0x3F;
+                MapperWrite::RomBankLow(self.rom_bank)
+            }
+            0x4000..=0x5FFF => {
+                self.ram_bank = value & 0x1F;
+                MapperWrite::RamBank(self.ram_bank)
+            }
+            _ => MapperWrite::Ignored,
+        }
+    }
+
+    /// Reads of 0xA000-0xBFFF (given as an offset), None if RAM is mapped there.
+    /// Only A000 can be read back, bit 0 is set while capturing.
+    pub fn read_registers(&self, index: u16) -> Option<u8> {
+        if !self.registers_mapped() {
+            return None;
+        }
+        Some(match index & 0x7F {
+            0x00 => self.registers[0] & 0b110 | self.is_capturing() as u8,
+            _ => 0x00,
+        })
+    }
+
+    /// Writes to 0xA000-0xBFFF (given as an offset), returns false if RAM is mapped there.
+    /// Setting bit 0 of A000 starts a capture, which takes longer the higher the exposure time is.
+    pub fn write_registers(&mut self, index: u16, value: u8) -> bool {
+        if !self.registers_mapped() {
+            return false;
+        }
+        let register = (index & 0x7F) as usize;
+        if register == 0 {
+            self.registers[0] = value & 0b111;
+            if value & 1 != 0 && !self.is_capturing() {
+                self.capture_cycles_left = self.capture_cycles();
+            }
+        } else if register < REGISTER_COUNT {
+            self.registers[register] = value;
+        }
+        true
+    }
+
+    /// Advances a running capture, returns the captured tiles when it finishes
+    pub fn step(&mut self, cycles: Cycles) -> Option<Vec<u8>> {
+        if !self.is_capturing() {
+            return None;
+        }
+        self.capture_cycles_left = self.capture_cycles_left.saturating_sub(cycles.as_t());
+        if self.is_capturing() {
+            return None;
+        }
+        self.registers[0] &= !1;
+        Some(self.capture())
+    }
+
+    /// Brightness values of 128x112 pixels, row by row
+    pub fn set_image(&mut self, pixels: &[u8]) -> Result<(), String> {
+        if pixels.len() != CAMERA_WIDTH * CAMERA_HEIGHT {
+            return Err(format!(
+                "Expected {}x{} camera pixels but got {}",
+                CAMERA_WIDTH,
+                CAMERA_HEIGHT,
+                pixels.len()
+            ));
+        }
+        self.image = pixels.to_vec();
+        Ok(())
+    }
+
+    /// None until an image is set
+    pub fn get_image(&self) -> Option<&[u8]> {
+        (!self.image.is_empty()).then_some(self.image.as_slice())
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capture_cycles_left > 0
+    }
+
+    pub fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub fn get_lower_rom_index(&self) -> usize {
+        0
+    }
+
+    pub fn get_upper_rom_index(&self) -> usize {
+        self.rom_bank as usize
+    }
+
+    pub fn get_ram_index(&self) -> usize {
+        (self.ram_bank & 0x0F) as usize
+    }
+
+    fn registers_mapped(&self) -> bool {
+        self.ram_bank & REGISTER_BANK != 0
+    }
+
+    fn exposure(&self) -> u32 {
+        (self.registers[EXPOSURE_HIGH] as u32) << 8 | self.registers[EXPOSURE_LOW] as u32
+    }
+
+    /// Reading the sensor takes a fixed time, plus the exposure time in steps of 16 µs.
+    /// The N flag (bit 7 of A001) saves 512 µs.
+    fn capture_cycles(&self) -> u32 {
+        let n_flag = self.registers[1] & 0x80 != 0;
+        129_792 + if n_flag { 0 } else { 2048 } + self.exposure() * 64
+    }
+
+    fn brightness(&self, x: usize, y: usize) -> u32 {
+        let sensor = self
+            .image
+            .get(y * CAMERA_WIDTH + x)
+            .copied()
+            .unwrap_or(DEFAULT_BRIGHTNESS);
+        let brightness = (sensor as u32 * self.exposure() / NEUTRAL_EXPOSURE).min(0xFF);
+        if self.registers[INVERT_AND_VOLTAGE] & 0b1000 != 0 {
+            0xFF - brightness
+        } else {
+            brightness
+        }
+    }
+
+    /// Turns the sensor image into 2 bit tiles. Every pixel is compared to the 3 thresholds of its
+    /// position in the 4x4 dithering matrix, pixels below all of them become black.
+    /// Gain and edge enhancement are not emulated, the auto exposure of the Camera ROM makes up for the gain.
+    fn capture(&self) -> Vec<u8> {
+        let mut tiles = vec![0; CAPTURE_SIZE];
+        for y in 0..CAMERA_HEIGHT {
+            for tile_x in 0..CAMERA_WIDTH / TILE_SIZE {
+                let color_ids =
+                    core::array::from_fn(|pixel| self.dither(tile_x * TILE_SIZE + pixel, y));
+                let tile = (y / TILE_SIZE) * (CAMERA_WIDTH / TILE_SIZE) + tile_x;
+                let row = tile * TILE_BYTES + (y % TILE_SIZE) * 2;
+                tiles[row..row + 2].copy_from_slice(&graphics::encode_row(color_ids));
+            }
+        }
+        tiles
+    }
+
+    fn dither(&self, x: usize, y: usize) -> u8 {
+        let matrix = DITHER_MATRIX + ((y & 3) * 4 + (x & 3)) * 3;
+        let brightness = self.brightness(x, y);
+        self.registers[matrix..matrix + 3]
+            .iter()
+            .filter(|&&threshold| brightness < threshold as u32)
+            .count() as u8
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// 256 bytes, organized as 128 words of 16 bits
+pub const EEPROM_WORDS: usize = 128;
+/// Start bit, 2 opcode bits and 8 address bits, of which the highest one is ignored
+const COMMAND_BITS: u8 = 11;
+const WORD_BITS: u8 = 16;
+
+/// The 93LC56 EEPROM of MBC7 cartridges, a serial chip driven bit by bit through the Microwire protocol.
+/// The game sets chip select and the data input, then raises the clock to shift the bit in.
+/// https://gbdev.io/pandocs/MBC7.html#eeprom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Eeprom {
+    words: Vec<u16>,
+    chip_select: bool,
+    clock: bool,
+    data_in: bool,
+    data_out: bool,
+    write_enabled: bool,
+    state: EepromState,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+enum EepromState {
+    /// Shifting in a command, waiting for the start bit while no bit was received
+    Command { bits: u8, command: u16 },
+    /// Shifting out a word, reads continue with the next word after the last bit
+    Read { address: u8, bits_left: u8 },
+    /// Shifting in the word for WRITE, or for WRAL if there is no address
+    Write {
+        address: Option<u8>,
+        bits: u8,
+        value: u16,
+    },
+}
+
+impl Default for EepromState {
+    fn default() -> Self {
+        Self::Command {
+            bits: 0,
+            command: 0,
+        }
+    }
+}
+
+impl Eeprom {
+    /// Unwritten cells read as 1
+    pub fn new() -> Self {
+        Self {
+            words: vec![0xFFFF; EEPROM_WORDS],
+            chip_select: false,
+            clock: false,
+            data_in: false,
+            data_out: true,
+            write_enabled: false,
+            state: EepromState::default(),
+        }
+    }
+
+    /// Back to the pins at power on, the stored words are kept
+    pub fn reset(&mut self) {
+        *self = Self {
+            words: core::mem::take(&mut self.words),
+            ..Self::new()
+        };
+    }
+
+    /// Bit 7: chip select, bit 6: clock, bit 1: data in, bit 0: data out
+    pub fn read_pins(&self) -> u8 {
+        (self.chip_select as u8) << 7
+            | (self.clock as u8) << 6
+            | (self.data_in as u8) << 1
+            | self.data_out as u8
+    }
+
+    pub fn write_pins(&mut self, value: u8) {
+        let chip_select = value & 0b1000_0000 != 0;
+        let clock = value & 0b0100_0000 != 0;
+        self.data_in = value & 0b0000_0010 != 0;
+
+        if !chip_select {
+            // Deselecting aborts an unfinished command, the chip signals ready again
+            self.state = EepromState::default();
+            self.data_out = true;
+        } else if clock && !self.clock {
+            self.clock_rising_edge();
+        }
+        self.chip_select = chip_select;
+        self.clock = clock;
+    }
+
+    /// The words as little endian bytes, like in a `.sav` file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+
+    /// Fails if there are not exactly 256 bytes
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
+        if bytes.len() != EEPROM_WORDS * 2 {
+            return Err(format!(
+                "Expected {} bytes of EEPROM but got {}",
+                EEPROM_WORDS * 2,
+                bytes.len()
+            ));
+        }
+        self.words = bytes
+            .chunks_exact(2)
+            .map(|word| u16::from_le_bytes([word[0], word[1]]))
+            .collect();
+        Ok(())
+    }
+
+    fn clock_rising_edge(&mut self) {
+        let bit = self.data_in as u16;
+        match self.state {
+            EepromState::Command { bits: 0, .. } if bit == 0 => {}
+            EepromState::Command { bits, command } => {
+                let command = command << 1 | bit;
+                if bits + 1 == COMMAND_BITS {
+                    self.execute(command);
+                } else {
+                    self.state = EepromState::Command {
+                        bits: bits + 1,
+                        command,
+                    };
+                }
+            }
+            EepromState::Read { address, bits_left } => {
+                let word = self.words[address as usize];
+                self.data_out = word & (1 << (bits_left - 1)) != 0;
+                self.state = if bits_left == 1 {
+                    EepromState::Read {
+                        address: (address + 1) % EEPROM_WORDS as u8,
+                        bits_left: WORD_BITS,
+                    }
+                } else {
+                    EepromState::Read {
+                        address,
+                        bits_left: bits_left - 1,
+                    }
+                };
+            }
+            EepromState::Write {
+                address,
+                bits,
+                value,
+            } => {
+                let value = value << 1 | bit;
+                if bits + 1 < WORD_BITS {
+                    self.state = EepromState::Write {
+                        address,
+                        bits: bits + 1,
+                        value,
+                    };
+                    return;
+                }
+                if self.write_enabled {
+                    match address {
+                        Some(address) => self.words[address as usize] = value,
+                        None => self.words.fill(value),
+                    }
+                }
+                self.state = EepromState::default();
+                self.data_out = true;
+            }
+        }
+    }
+
+    /// The command without its start bit: 2 opcode bits followed by 8 address bits
+    fn execute(&mut self, command: u16) {
+        let address = (command & 0x7F) as u8;
+        self.state = EepromState::default();
+        match (command >> 8) & 0b11 {
+            // READ, a dummy 0 bit comes before the word
+            0b10 => {
+                self.data_out = false;
+                self.state = EepromState::Read {
+                    address,
+                    bits_left: WORD_BITS,
+                };
+            }
+            // WRITE
+            0b01 => {
+                self.state = EepromState::Write {
+                    address: Some(address),
+                    bits: 0,
+                    value: 0,
+                };
+            }
+            // ERASE
+            0b11 => {
+                if self.write_enabled {
+                    self.words[address as usize] = 0xFFFF;
+                }
+            }
+            // The upper 2 address bits select the command
+            _ => match (command >> 6) & 0b11 {
+                // EWDS
+                0b00 => self.write_enabled = false,
+                // WRAL
+                0b01 => {
+                    self.state = EepromState::Write {
+                        address: None,
+                        bits: 0,
+                        value: 0,
+                    };
+                }
+                // ERAL
+                0b10 => {
+                    if self.write_enabled {
+                        self.words.fill(0xFFFF);
+                    }
+                }
+                // EWEN
+                _ => self.write_enabled = true,
+            },
+        }
+    }
+}
+
+impl Default for Eeprom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::game_boy::components::mmu::mbc::MapperWrite;
 use serde::{Deserialize, Serialize};
 
 // ToDo: Check if lower bit masking depending on ROM size is necessary
@@ -21,23 +22,31 @@ impl Mbc1 {
         }
     }
 
-    pub fn handle_write(&mut self, address: u16, value: u8) {
+    pub fn reset(&mut self) {
+        *self = Self::initialize(self.multicart);
+    }
+
+    pub fn handle_write(&mut self, address: u16, value: u8) -> MapperWrite {
         match address {
             0x0000..=0x1FFF => {
                 self.ram_enabled = value & 0b0000_1111 == 0xA;
+                MapperWrite::RamEnable(self.ram_enabled)
             }
             0x2000..=0x3FFF => {
                 let masked_value = value & 0b0001_1111;
-                self.bank1 = if masked_value == 0 { 1 } else { masked_value }
+                self.bank1 = if masked_value == 0 { 1 } else { masked_value };
+                MapperWrite::RomBankLow(self.bank1)
             }
             0x4000..=0x5FFF => {
                 let masked_value = value & 0b0000_0011;
                 self.bank2 = masked_value;
+                MapperWrite::UpperBits(self.bank2)
             }
             0x6000..=0x7FFF => {
                 self.banking_mode = value & 0b1 == 0b1;
+                MapperWrite::BankingMode(self.banking_mode)
             }
-            _ => (),
+            _ => MapperWrite::Ignored,
         }
     }
 
@@ -0,0 +1,82 @@
+use crate::game_boy::components::mmu::mbc::MapperWrite;
+use serde::{Deserialize, Serialize};
+
+/// https://gbdev.io/pandocs/MBC5.html
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mbc5 {
+    /// 9 bits, unlike MBC1 bank 0 can be mapped into 0x4000-0x7FFF
+    rom_bank: u16,
+    ram_bank: u8,
+    ram_enabled: bool,
+    /// Rumble cartridges wire bit 3 of the RAM bank register to the motor instead of the RAM
+    rumble: bool,
+    motor_on: bool,
+}
+
+impl Mbc5 {
+    pub fn initialize(rumble: bool) -> Self {
+        Self {
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            rumble,
+            motor_on: false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::initialize(self.rumble);
+    }
+
+    pub fn handle_write(&mut self, address: u16, value: u8) -> MapperWrite {
+        match address {
+            // Unlike MBC1 all 8 bits are checked
+            0x0000..=0x1FFF => {
+                self.ram_enabled = value == 0x0A;
+                MapperWrite::RamEnable(self.ram_enabled)
+            }
+            0x2000..=0x2FFF => {
+                self.rom_bank = (self.rom_bank & 0x100) | value as u16;
+                MapperWrite::RomBankLow(value)
+            }
+            0x3000..=0x3FFF => {
+                let high_bit = value & 0b1;
+                self.rom_bank = (self.rom_bank & 0xFF) | ((high_bit as u16) << 8);
+                MapperWrite::RomBankHigh(high_bit)
+            }
+            0x4000..=0x5FFF if self.rumble => {
+                self.ram_bank = value & 0b0000_0111;
+                self.motor_on = value & 0b0000_1000 != 0;
+                MapperWrite::RamBankMotor {
+                    ram_bank: self.ram_bank,
+                    motor_on: self.motor_on,
+                }
+            }
+            0x4000..=0x5FFF => {
+                self.ram_bank = value & 0b0000_1111;
+                MapperWrite::RamBank(self.ram_bank)
+            }
+            _ => MapperWrite::Ignored,
+        }
+    }
+
+    pub fn ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    pub fn motor_on(&self) -> bool {
+        self.motor_on
+    }
+
+    pub fn get_lower_rom_index(&self) -> usize {
+        0
+    }
+
+    pub fn get_upper_rom_index(&self) -> usize {
+        self.rom_bank as usize
+    }
+
+    pub fn get_ram_index(&self) -> usize {
+        self.ram_bank as usize
+    }
+}
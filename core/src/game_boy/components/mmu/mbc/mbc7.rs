@@ -0,0 +1,151 @@
+use crate::game_boy::components::mmu::mbc::eeprom::Eeprom;
+use crate::game_boy::components::mmu::mbc::MapperWrite;
+use serde::{Deserialize, Serialize};
+
+/// The accelerometer reads this while the cartridge is held flat
+const ACCELEROMETER_CENTER: u16 = 0x81D0;
+/// Change of the reading for 1 g of tilt
+const ACCELEROMETER_PER_G: f32 = 0x70 as f32;
+/// The value of both axes after erasing the latched reading
+const ACCELEROMETER_ERASED: u16 = 0x8000;
+
+/// Cartridges with a 2 axis accelerometer and an EEPROM instead of RAM, e.g. Kirby Tilt 'n' Tumble.
+/// With both RAM enables set, 0xA000-0xAFFF holds registers, selected by bits 4-7 of the address.
+/// https://gbdev.io/pandocs/MBC7.html
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mbc7 {
+    rom_bank: u8,
+    ram_enabled: bool,
+    /// The registers are only mapped if both enables are set
+    second_ram_enabled: bool,
+    /// The latched reading was erased, so the next latch command takes a new one
+    latch_erased: bool,
+    latched_x: u16,
+    latched_y: u16,
+    eeprom: Eeprom,
+    /// The tilt in g, an input like the held buttons, so it is not part of save states
+    #[serde(skip)]
+    tilt: (f32, f32),
+}
+
+impl Mbc7 {
+    pub fn initialize() -> Self {
+        Self {
+            rom_bank: 1,
+            ram_enabled: false,
+            second_ram_enabled: false,
+            latch_erased: false,
+            latched_x: ACCELEROMETER_ERASED,
+            latched_y: ACCELEROMETER_ERASED,
+            eeprom: Eeprom::new(),
+            tilt: (0.0, 0.0),
+        }
+    }
+
+    /// The EEPROM keeps its contents like battery backed RAM, the tilt is kept as well
+    pub fn reset(&mut self) {
+        let mut eeprom = core::mem::take(&mut self.eeprom);
+        eeprom.reset();
+        *self = Self {
+            eeprom,
+            tilt: self.tilt,
+            ..Self::initialize()
+        };
+    }
+
+    pub fn handle_write(&mut self, address: u16, value: u8) -> MapperWrite {
+        match address {
+            0x0000..=0x1FFF => {
+                self.ram_enabled = value == 0x0A;
+                MapperWrite::RamEnable(self.ram_enabled)
+            }
+            0x2000..=0x3FFF => {
+                self.rom_bank = value;
+                MapperWrite::RomBankLow(value)
+            }
+            0x4000..=0x5FFF => {
+                self.second_ram_enabled = value == 0x40;
+                MapperWrite::SecondRamEnable(self.second_ram_enabled)
+            }
+            _ => MapperWrite::Ignored,
+        }
+    }
+
+    /// Reads of 0xA000-0xBFFF, given as an offset from 0xA000
+    pub fn read_registers(&self, index: u16) -> u8 {
+        if !self.ram_enabled() || index >= 0x1000 {
+            return 0xFF;
+        }
+        match (index >> 4) & 0xF {
+            0x2 => self.latched_x as u8,
+            0x3 => (self.latched_x >> 8) as u8,
+            0x4 => self.latched_y as u8,
+            0x5 => (self.latched_y >> 8) as u8,
+            0x6 => 0x00,
+            0x8 => self.eeprom.read_pins(),
+            _ => 0xFF,
+        }
+    }
+
+    /// Writes to 0xA000-0xBFFF, given as an offset from 0xA000
+    pub fn write_registers(&mut self, index: u16, value: u8) {
+        if !self.ram_enabled() || index >= 0x1000 {
+            return;
+        }
+        match (index >> 4) & 0xF {
+            0x0 if value == 0x55 => {
+                self.latched_x = ACCELEROMETER_ERASED;
+                self.latched_y = ACCELEROMETER_ERASED;
+                self.latch_erased = true;
+            }
+            0x1 if value == 0xAA && self.latch_erased => {
+                (self.latched_x, self.latched_y) = self.read_accelerometer();
+                self.latch_erased = false;
+            }
+            0x8 => self.eeprom.write_pins(value),
+            _ => {}
+        }
+    }
+
+    /// In g, clamped to -1.0 to 1.0. Positive x tilts the right side down, positive y the bottom side.
+    pub fn set_tilt(&mut self, x: f32, y: f32) {
+        let clamp = |value: f32| {
+            if value.is_nan() {
+                0.0
+            } else {
+                value.clamp(-1.0, 1.0)
+            }
+        };
+        self.tilt = (clamp(x), clamp(y));
+    }
+
+    pub fn get_tilt(&self) -> (f32, f32) {
+        self.tilt
+    }
+
+    pub fn get_eeprom(&self) -> &Eeprom {
+        &self.eeprom
+    }
+
+    pub fn get_eeprom_mut(&mut self) -> &mut Eeprom {
+        &mut self.eeprom
+    }
+
+    pub fn ram_enabled(&self) -> bool {
+        self.ram_enabled && self.second_ram_enabled
+    }
+
+    pub fn get_lower_rom_index(&self) -> usize {
+        0
+    }
+
+    pub fn get_upper_rom_index(&self) -> usize {
+        self.rom_bank as usize
+    }
+
+    /// Tilting to the right lowers the x reading, tilting down raises the y reading
+    fn read_accelerometer(&self) -> (u16, u16) {
+        let axis = |tilt: f32| (ACCELEROMETER_CENTER as f32 + tilt * ACCELEROMETER_PER_G) as u16;
+        (axis(-self.tilt.0), axis(self.tilt.1))
+    }
+}
@@ -0,0 +1,90 @@
+use core::fmt::{Display, Formatter};
+use core::ops::RangeInclusive;
+
+/// The areas of the memory map
+/// https://gbdev.io/pandocs/Memory_Map.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryRegion {
+    RomBank0,
+    RomBankN,
+    Vram,
+    ExternalRam,
+    Wram,
+    /// Mirror of 0xC000-0xDDFF
+    EchoRam,
+    Oam,
+    Unusable,
+    IoRegisters,
+    Hram,
+    InterruptEnable,
+}
+
+impl MemoryRegion {
+    pub const ALL: [MemoryRegion; 11] = [
+        MemoryRegion::RomBank0,
+        MemoryRegion::RomBankN,
+        MemoryRegion::Vram,
+        MemoryRegion::ExternalRam,
+        MemoryRegion::Wram,
+        MemoryRegion::EchoRam,
+        MemoryRegion::Oam,
+        MemoryRegion::Unusable,
+        MemoryRegion::IoRegisters,
+        MemoryRegion::Hram,
+        MemoryRegion::InterruptEnable,
+    ];
+
+    pub fn from_address(address: u16) -> Self {
+        match address {
+            0x0000..=0x3FFF => MemoryRegion::RomBank0,
+            0x4000..=0x7FFF => MemoryRegion::RomBankN,
+            0x8000..=0x9FFF => MemoryRegion::Vram,
+            0xA000..=0xBFFF => MemoryRegion::ExternalRam,
+            0xC000..=0xDFFF => MemoryRegion::Wram,
+            0xE000..=0xFDFF => MemoryRegion::EchoRam,
+            0xFE00..=0xFE9F => MemoryRegion::Oam,
+            0xFEA0..=0xFEFF => MemoryRegion::Unusable,
+            0xFF00..=0xFF7F => MemoryRegion::IoRegisters,
+            0xFF80..=0xFFFE => MemoryRegion::Hram,
+            0xFFFF => MemoryRegion::InterruptEnable,
+        }
+    }
+
+    pub fn get_range(&self) -> RangeInclusive<u16> {
+        match self {
+            MemoryRegion::RomBank0 => 0x0000..=0x3FFF,
+            MemoryRegion::RomBankN => 0x4000..=0x7FFF,
+            MemoryRegion::Vram => 0x8000..=0x9FFF,
+            MemoryRegion::ExternalRam => 0xA000..=0xBFFF,
+            MemoryRegion::Wram => 0xC000..=0xDFFF,
+            MemoryRegion::EchoRam => 0xE000..=0xFDFF,
+            MemoryRegion::Oam => 0xFE00..=0xFE9F,
+            MemoryRegion::Unusable => 0xFEA0..=0xFEFF,
+            MemoryRegion::IoRegisters => 0xFF00..=0xFF7F,
+            MemoryRegion::Hram => 0xFF80..=0xFFFE,
+            MemoryRegion::InterruptEnable => 0xFFFF..=0xFFFF,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            MemoryRegion::RomBank0 => "ROM0",
+            MemoryRegion::RomBankN => "ROMX",
+            MemoryRegion::Vram => "VRAM",
+            MemoryRegion::ExternalRam => "SRAM",
+            MemoryRegion::Wram => "WRAM",
+            MemoryRegion::EchoRam => "ECHO",
+            MemoryRegion::Oam => "OAM",
+            MemoryRegion::Unusable => "----",
+            MemoryRegion::IoRegisters => "I/O",
+            MemoryRegion::Hram => "HRAM",
+            MemoryRegion::InterruptEnable => "IE",
+        }
+    }
+}
+
+impl Display for MemoryRegion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
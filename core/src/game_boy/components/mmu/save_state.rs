@@ -1,4 +1,5 @@
 use crate::game_boy::components::mmu::mbc::Mbc;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,4 +12,13 @@ pub struct MMUSaveState {
     pub io_registers: Vec<u8>,
     pub hram: Vec<u8>,
     pub ie_register: u8,
+    pub serial_output: Vec<u8>,
+    pub dma_request: Option<u8>,
+    /// Only read with open bus emulation, states from before it existed start with 0xFF
+    #[serde(default = "unmapped_value")]
+    pub external_bus: u8,
+}
+
+fn unmapped_value() -> u8 {
+    0xFF
 }
@@ -0,0 +1,97 @@
+//! Optional counters of memory accesses per 256 byte page.
+//! Only accesses of the CPU are counted, so the heatmap shows what the game touches and not the PPU, timer or DMA at work.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+pub const PAGE_SIZE: usize = 0x100;
+pub const PAGE_COUNT: usize = 0x100;
+/// The heatmap has one pixel per page, the high byte of the address selects the row
+pub const HEATMAP_SIZE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryStats {
+    /// Reads only borrow the MMU immutably, so their counters need interior mutability
+    reads: Vec<Cell<u64>>,
+    writes: Vec<u64>,
+}
+
+impl Default for MemoryStats {
+    fn default() -> Self {
+        Self {
+            reads: vec![Cell::new(0); PAGE_COUNT],
+            writes: vec![0; PAGE_COUNT],
+        }
+    }
+}
+
+impl MemoryStats {
+    pub fn record_read(&self, address: u16) {
+        let counter = &self.reads[address as usize / PAGE_SIZE];
+        counter.set(counter.get() + 1);
+    }
+
+    pub fn record_write(&mut self, address: u16) {
+        self.writes[address as usize / PAGE_SIZE] += 1;
+    }
+
+    /// Reads of the page starting at `page << 8`
+    pub fn get_reads(&self, page: u8) -> u64 {
+        self.reads[page as usize].get()
+    }
+
+    pub fn get_writes(&self, page: u8) -> u64 {
+        self.writes[page as usize]
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// One line per page: `start,end,reads,writes`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("start,end,reads,writes\n");
+        for page in 0..=0xFF {
+            let start = (page as u16) << 8;
+            csv.push_str(&format!(
+                "0x{:04X},0x{:04X},{},{}\n",
+                start,
+                start | 0xFF,
+                self.get_reads(page),
+                self.get_writes(page)
+            ));
+        }
+        csv
+    }
+
+    /// RGBA image of [`HEATMAP_SIZE`]x[`HEATMAP_SIZE`] pixels, one per page.
+    /// Reads are shown in green and writes in red, both on a logarithmic scale relative to the busiest page.
+    #[cfg(feature = "std")]
+    pub fn render_heatmap(&self) -> Vec<u8> {
+        let max_reads = self.reads.iter().map(Cell::get).max().unwrap_or(0);
+        let max_writes = self.writes.iter().copied().max().unwrap_or(0);
+
+        let mut image = Vec::with_capacity(PAGE_COUNT * 4);
+        for page in 0..=0xFF {
+            image.extend_from_slice(&[
+                intensity(self.get_writes(page), max_writes),
+                intensity(self.get_reads(page), max_reads),
+                0x00,
+                0xFF,
+            ]);
+        }
+        image
+    }
+}
+
+#[cfg(feature = "std")]
+fn intensity(count: u64, max: u64) -> u8 {
+    if count == 0 {
+        return 0;
+    }
+    let scaled = (count as f64).ln_1p() / (max as f64).ln_1p();
+    (scaled * 255.0).round() as u8
+}
@@ -0,0 +1,783 @@
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::mmu::{
+    BGP_ADDRESS, LCDC_ADDRESS, LYC_ADDRESS, MMU, OAM_ADDRESS, OBP0_ADDRESS, OBP1_ADDRESS,
+    SCX_ADDRESS, SCY_ADDRESS, STAT_ADDRESS, WX_ADDRESS, WY_ADDRESS,
+};
+use crate::game_boy::components::ppu::background_palette::BackgroundPalette;
+use crate::game_boy::components::ppu::color_scheme::{ColorScheme, Layer};
+use crate::game_boy::components::ppu::debug::{
+    ScanlineLog, ScanlineRegisters, SpriteHit, SpriteHits,
+};
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::lcd_control::LCDControl;
+use crate::game_boy::components::ppu::lcd_status::LCDStatus;
+use crate::game_boy::components::ppu::mode::PPUMode;
+use crate::game_boy::components::ppu::save_state::PPUSaveState;
+use crate::game_boy::components::ppu::sprite::Sprite;
+use crate::game_boy::components::ppu::timing::{
+    h_blank_dots, pixel_dot, pixel_transfer_dots, MIN_PIXEL_TRANSFER_DOTS, OAM_SEARCH_DOTS,
+    SCANLINE_DOTS,
+};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use crate::helpers::graphics;
+use crate::helpers::graphics::TILE_SIZE;
+use crate::helpers::listeners::{ListenerId, Listeners};
+use crate::logging::Subsystem;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use log::debug;
+
+mod background_palette;
+pub mod color_correction;
+pub mod color_scheme;
+pub mod color_scheme_preset;
+pub mod colorization;
+pub mod debug;
+pub mod frame_buffer_format;
+mod lcd_control;
+mod lcd_status;
+pub mod mode;
+pub mod save_state;
+pub mod sprite;
+pub mod timing;
+
+pub const SCREEN_WIDTH: usize = 160;
+pub const SCREEN_HEIGHT: usize = 144;
+/// Bytes of a frame in the RGBA8888 format
+pub const RGBA_FRAME_BUFFER_SIZE: usize = SCREEN_WIDTH * SCREEN_HEIGHT * 4;
+/// The PPU only renders the first 10 sprites (in OAM order) which are on a scanline
+pub const MAX_SPRITES_PER_LINE: usize = 10;
+pub const OAM_SPRITE_COUNT: u16 = 40;
+/// 154 lines of 456 dots
+const DOTS_PER_FRAME: u32 = 70224;
+/// WX is the window's screen X position plus 7
+const WINDOW_X_OFFSET: u8 = 7;
+/// The window is off screen from this WX on
+const WINDOW_X_MAX: u8 = 167;
+
+/// Using the Game Boy Pocket color scheme
+/// https://en.wikipedia.org/wiki/List_of_video_game_console_palettes
+pub const COLOR_SCHEME: [[u8; 4]; 4] = [
+    [0xC5, 0xCA, 0xA4, 0xFF],
+    [0x8C, 0x92, 0x6B, 0xFF],
+    [0x4A, 0x51, 0x38, 0xFF],
+    [0x18, 0x18, 0x18, 0xFF],
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PPU {
+    mode: PPUMode,
+    frame_buffer: Vec<u8>,
+    frame_buffer_format: FrameBufferFormat,
+    color_scheme: ColorScheme,
+    /// The 4 shades of every layer of the color scheme, already encoded in the frame buffer format
+    encoded_colors: [[[u8; 4]; 4]; 3],
+    /// Blank (white) pixel used while the background is disabled, encoded in the frame buffer format
+    encoded_blank: [u8; 4],
+    bytes_per_pixel: usize,
+    mode_clock: u32,
+    /// Length of the pixel transfer of the current line, decided when it starts
+    pixel_transfer_dots: u32,
+    current_line: u8,
+    /// LCDC bit 7 as of the last step, to detect the LCD being switched on or off
+    lcd_enabled: bool,
+    /// The window has its own line counter, it only advances on lines the window was drawn on
+    window_line: u8,
+    /// LY matched WY during this frame, the window is only drawn from then on
+    window_triggered: bool,
+    /// Only every nth frame is rendered, timing and interrupts are unaffected
+    render_interval: u8,
+    frame_counter: u8,
+    vblank_interrupt: bool,
+    stat_interrupt: bool,
+    /// All enabled STAT sources ORed together, https://gbdev.io/pandocs/Interrupt_Sources.html#int-48--stat-interrupt
+    stat_line: bool,
+    frame_complete: bool,
+    /// Dots since the last frame was finished
+    frame_dots: u32,
+    /// The game read P1 since the last frame was finished
+    joypad_polled: bool,
+    /// Waits for its number until [`PPU::notify_frame`] passes it to the frame listeners
+    finished_frame: Option<VBlankInfo>,
+    frame_listeners: Listeners<VBlankInfo>,
+    sprite_hits: SpriteHits,
+    scanline_log: ScanlineLog,
+}
+
+/// Passed to the frame listeners whenever a frame is finished
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VBlankInfo {
+    /// Counts up from 0 with every frame the Game Boy finished, including dropped ones.
+    /// Like [`GameBoy::frame_count`](crate::game_boy::GameBoy::frame_count) it isn't affected by resets or loaded states.
+    pub frame_number: u64,
+    /// The time since the previous frame was finished, 70224 dots unless the LCD was switched on or off
+    pub cycles_this_frame: Cycles,
+    /// A lag frame: the game never read P1 during it, so input given during it is only seen a frame later
+    pub dropped: bool,
+    /// The LCD was off, so the frame ended without a VBlank and nothing was drawn
+    pub lcd_off: bool,
+}
+
+impl PPU {
+    pub fn new() -> PPU {
+        Self::with_format(FrameBufferFormat::default())
+    }
+
+    pub fn with_format(format: FrameBufferFormat) -> PPU {
+        let bytes_per_pixel = format.bytes_per_pixel();
+        let color_scheme = ColorScheme::default();
+
+        PPU {
+            mode: PPUMode::OAMSearch,
+            frame_buffer: vec![0u8; SCREEN_HEIGHT * SCREEN_WIDTH * bytes_per_pixel],
+            frame_buffer_format: format,
+            color_scheme,
+            encoded_colors: encode_color_scheme(&color_scheme, format),
+            encoded_blank: format.encode([0xFF; 4], 0),
+            bytes_per_pixel,
+            mode_clock: 0,
+            pixel_transfer_dots: MIN_PIXEL_TRANSFER_DOTS,
+            current_line: 0,
+            lcd_enabled: true,
+            window_line: 0,
+            window_triggered: false,
+            render_interval: 1,
+            frame_counter: 0,
+            vblank_interrupt: false,
+            stat_interrupt: false,
+            stat_line: false,
+            frame_complete: false,
+            frame_dots: 0,
+            joypad_polled: false,
+            finished_frame: None,
+            frame_listeners: Listeners::default(),
+            sprite_hits: SpriteHits::default(),
+            scanline_log: ScanlineLog::default(),
+        }
+    }
+
+    /// Replaces the emulated state, the format, colors, render interval and frame listeners are kept
+    fn replace_keeping_settings(&mut self, mut replacement: PPU) {
+        replacement.render_interval = self.render_interval;
+        replacement.set_color_scheme(self.color_scheme);
+        replacement.frame_listeners = core::mem::take(&mut self.frame_listeners);
+        *self = replacement;
+    }
+
+    /// Registers a callback which is invoked with every finished frame passed to [`PPU::notify_frame`]
+    pub fn on_frame(&mut self, callback: impl FnMut(&VBlankInfo) + Send + 'static) -> ListenerId {
+        self.frame_listeners.subscribe(callback)
+    }
+
+    pub fn remove_frame_listener(&mut self, id: ListenerId) {
+        self.frame_listeners.unsubscribe(id);
+    }
+
+    /// Called whenever the CPU reads P1, frames without a poll are reported as dropped
+    pub fn record_joypad_poll(&mut self) {
+        self.joypad_polled = true;
+    }
+
+    /// Where every OAM entry was first drawn since the current frame started.
+    /// Frames skipped by the render interval aren't drawn, so they record no hits.
+    pub fn get_sprite_hits(&self) -> &SpriteHits {
+        &self.sprite_hits
+    }
+
+    /// The registers every line of the frame buffer was drawn with.
+    /// Frames skipped by the render interval aren't drawn, so they record nothing.
+    pub fn get_scanline_log(&self) -> &ScanlineLog {
+        &self.scanline_log
+    }
+
+    /// Applies from the next drawn pixel on, the indexed frame buffer format is unaffected
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        self.color_scheme = color_scheme;
+        self.encoded_colors = encode_color_scheme(&color_scheme, self.frame_buffer_format);
+    }
+
+    pub fn get_color_scheme(&self) -> &ColorScheme {
+        &self.color_scheme
+    }
+
+    /// Requests the VBlank and STAT interrupts itself, returns (VBlank interrupt, STAT interrupt, frame finished)
+    pub fn step(&mut self, cycles: Cycles, mmu: &mut MMU) -> (bool, bool, bool) {
+        self.vblank_interrupt = false;
+        self.stat_interrupt = false;
+        self.frame_complete = false;
+
+        let lcd_enabled = self.get_lcdc(mmu).lcd_ppu_enabled;
+        if lcd_enabled != self.lcd_enabled {
+            self.switch_lcd(lcd_enabled, mmu);
+        }
+
+        self.mode_clock = self.mode_clock.wrapping_add(cycles.as_t());
+        self.frame_dots = self.frame_dots.wrapping_add(cycles.as_t());
+        if lcd_enabled {
+            self.execute_mode(mmu);
+            self.update_memory_state(mmu);
+        } else {
+            self.run_lcd_off();
+        }
+
+        if self.frame_complete {
+            self.end_frame();
+        }
+
+        if self.vblank_interrupt {
+            mmu.interrupts_mut().request(Interrupt::Vblank);
+        }
+        if self.stat_interrupt {
+            mmu.interrupts_mut().request(Interrupt::Lcd);
+        }
+
+        (
+            self.vblank_interrupt,
+            self.stat_interrupt,
+            self.frame_complete,
+        )
+    }
+
+    /// Keeps the frame for [`PPU::notify_frame`], the dots the last step ran past the end of the frame already count towards the next one
+    fn end_frame(&mut self) {
+        let next_frame_dots = self.mode_clock;
+        self.finished_frame = Some(VBlankInfo {
+            frame_number: 0,
+            cycles_this_frame: Cycles::from_t(self.frame_dots.saturating_sub(next_frame_dots)),
+            dropped: !self.joypad_polled,
+            lcd_off: !self.lcd_enabled,
+        });
+        self.joypad_polled = false;
+        self.frame_dots = next_frame_dots;
+    }
+
+    /// Passes the frame finished by the last step to the frame listeners under the given number.
+    /// The PPU doesn't count frames itself, [`GameBoy::step`](crate::game_boy::GameBoy::step) numbers them by its counters.
+    pub fn notify_frame(&mut self, frame_number: u64) {
+        if let Some(mut info) = self.finished_frame.take() {
+            info.frame_number = frame_number;
+            self.frame_listeners.notify(&info);
+        }
+    }
+
+    fn execute_mode(&mut self, mmu: &mut MMU) {
+        match self.mode {
+            PPUMode::OAMSearch => self.run_oam_search(mmu),
+            PPUMode::PixelTransfer => self.run_pixel_transfer(mmu),
+            PPUMode::HBlank => self.run_h_blank(),
+            PPUMode::VBlank => self.run_v_blank(),
+        }
+    }
+
+    /// Only render every nth frame (1 renders every frame), e.g. while the window is minimized.
+    /// Timing, interrupts and STAT behave as usual, the frame buffer just keeps the last rendered frame.
+    pub fn set_render_interval(&mut self, interval: u8) {
+        self.render_interval = interval.max(1);
+        self.frame_counter = 0;
+    }
+
+    pub fn get_render_interval(&self) -> u8 {
+        self.render_interval
+    }
+
+    /// The mode STAT reports, HBlank while the LCD is off
+    pub fn get_mode(&self) -> PPUMode {
+        self.mode
+    }
+
+    /// How long the pixel transfer of the current (or last) line takes, 172 dots plus the penalties
+    /// for SCX, the window and the sprites on the line. The HBlank is shortened by the same amount.
+    pub fn get_pixel_transfer_dots(&self) -> u32 {
+        self.pixel_transfer_dots
+    }
+
+    pub fn get_frame_buffer(&self) -> &[u8] {
+        &self.frame_buffer
+    }
+
+    pub fn get_frame_buffer_format(&self) -> FrameBufferFormat {
+        self.frame_buffer_format
+    }
+
+    /// The frame buffer converted to RGBA, regardless of the configured format.
+    /// Indexed pixels don't know their layer, they are converted with the background colors.
+    pub fn get_rgba_frame_buffer(&self) -> Vec<u8> {
+        self.frame_buffer_format
+            .to_rgba(&self.frame_buffer, &self.color_scheme.background)
+    }
+}
+
+impl Component for PPU {
+    type SaveState = PPUSaveState;
+
+    /// Back to the first line with a blank frame buffer
+    fn reset(&mut self, _config: &GameBoyConfig) {
+        self.replace_keeping_settings(Self::with_format(self.frame_buffer_format));
+    }
+
+    /// Returns true if a frame was finished
+    fn tick(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        let (_, _, frame_finished) = self.step(cycles, mmu);
+        frame_finished
+    }
+
+    fn save(&self) -> PPUSaveState {
+        PPUSaveState {
+            mode: self.mode,
+            mode_clock: self.mode_clock,
+            pixel_transfer_dots: self.pixel_transfer_dots,
+            current_line: self.current_line,
+            lcd_enabled: self.lcd_enabled,
+            window_line: self.window_line,
+            window_triggered: self.window_triggered,
+            stat_line: self.stat_line,
+            frame_dots: self.frame_dots,
+            frame_buffer_format: self.frame_buffer_format,
+            frame_buffer: self.frame_buffer.clone(),
+        }
+    }
+
+    /// Keeps the frame buffer format of this PPU.
+    /// If the state was saved with another format the frame buffer is left blank until the next frame is drawn.
+    /// Fails on a frame buffer of the wrong size and on LY/mode combinations the PPU could never reach,
+    /// stepping from those would run LY past the last line.
+    fn load(&mut self, state: PPUSaveState) -> Result<(), Box<dyn Error>> {
+        if state.current_line > 153
+            || (state.current_line >= 144 && state.mode != PPUMode::VBlank)
+            || state.mode_clock >= DOTS_PER_FRAME
+            || state.pixel_transfer_dots < MIN_PIXEL_TRANSFER_DOTS
+            || state.pixel_transfer_dots > SCANLINE_DOTS - OAM_SEARCH_DOTS
+        {
+            return Err(format!(
+                "Invalid PPU state: LY {} in {:?} after {} dots",
+                state.current_line, state.mode, state.mode_clock
+            )
+            .into());
+        }
+
+        let format = self.frame_buffer_format;
+        let mut ppu = Self::with_format(format);
+        ppu.mode = state.mode;
+        ppu.mode_clock = state.mode_clock;
+        ppu.pixel_transfer_dots = state.pixel_transfer_dots;
+        ppu.current_line = state.current_line;
+        ppu.lcd_enabled = state.lcd_enabled;
+        ppu.window_line = state.window_line;
+        ppu.window_triggered = state.window_triggered;
+        ppu.stat_line = state.stat_line;
+        ppu.frame_dots = state.frame_dots;
+
+        if state.frame_buffer_format == format {
+            if state.frame_buffer.len() != ppu.frame_buffer.len() {
+                return Err("Failed to load frame buffer".into());
+            }
+            ppu.frame_buffer = state.frame_buffer;
+        }
+
+        self.replace_keeping_settings(ppu);
+        Ok(())
+    }
+}
+
+/// PPU Mode functions
+impl PPU {
+    fn run_oam_search(&mut self, mmu: &MMU) {
+        if self.mode_clock >= OAM_SEARCH_DOTS {
+            self.mode_clock -= OAM_SEARCH_DOTS;
+            self.mode = PPUMode::PixelTransfer;
+            self.pixel_transfer_dots = self.compute_pixel_transfer_dots(mmu);
+        }
+    }
+
+    fn run_pixel_transfer(&mut self, mmu: &mut MMU) {
+        if self.mode_clock >= self.pixel_transfer_dots {
+            self.mode_clock -= self.pixel_transfer_dots;
+            self.mode = PPUMode::HBlank;
+            self.render_line(mmu);
+        }
+    }
+
+    fn run_h_blank(&mut self) {
+        let h_blank_dots = h_blank_dots(self.pixel_transfer_dots);
+        if self.mode_clock >= h_blank_dots {
+            self.mode_clock -= h_blank_dots;
+            self.current_line += 1;
+
+            if self.current_line == 144 {
+                self.mode = PPUMode::VBlank;
+                self.vblank_interrupt = true;
+                self.frame_complete = true;
+                self.frame_counter = (self.frame_counter + 1) % self.render_interval;
+            } else {
+                self.mode = PPUMode::OAMSearch;
+            }
+        }
+    }
+
+    /// Switching the LCD off resets LY to 0 and STAT to HBlank and blanks the screen.
+    /// Switching it back on starts a new frame at the first line.
+    fn switch_lcd(&mut self, enabled: bool, mmu: &mut MMU) {
+        debug!(target: Subsystem::Ppu.target(), "LCD switched {}", if enabled { "on" } else { "off" });
+        self.lcd_enabled = enabled;
+        self.mode_clock = 0;
+        self.current_line = 0;
+        self.start_frame();
+        if enabled {
+            self.mode = PPUMode::OAMSearch;
+            return;
+        }
+
+        // STAT reads mode 0 while the LCD is off, the LYC=LY flag keeps the last comparison
+        self.mode = PPUMode::HBlank;
+        self.stat_line = false;
+        self.clear_frame_buffer();
+        let stat = self.get_stat(mmu);
+        mmu.ppu_update_stat(PPUMode::HBlank, stat.lyc_equals_ly);
+        mmu.ppu_update_ly(0);
+    }
+
+    /// No PPU work while the LCD is off, frames are still finished at the usual rate,
+    /// so frontends and headless runs keep going.
+    fn run_lcd_off(&mut self) {
+        if self.mode_clock >= DOTS_PER_FRAME {
+            self.mode_clock -= DOTS_PER_FRAME;
+            self.frame_complete = true;
+        }
+    }
+
+    fn run_v_blank(&mut self) {
+        if self.mode_clock >= SCANLINE_DOTS {
+            self.mode_clock -= SCANLINE_DOTS;
+            self.current_line += 1;
+        }
+        if self.current_line > 153 {
+            self.mode = PPUMode::OAMSearch;
+            self.current_line = 0;
+            self.start_frame();
+        }
+    }
+
+    /// The window and the sprite hits start over at the first line of every frame
+    fn start_frame(&mut self) {
+        self.window_line = 0;
+        self.window_triggered = false;
+        self.sprite_hits.clear();
+    }
+}
+
+/// Rendering
+impl PPU {
+    fn get_frame_buffer_index(&self, x: usize) -> usize {
+        (self.current_line as usize * SCREEN_WIDTH + x) * self.bytes_per_pixel
+    }
+
+    fn render_line(&mut self, mmu: &mut MMU) {
+        if self.current_line >= 144 {
+            return;
+        }
+        if self.current_line == mmu.read(WY_ADDRESS) {
+            self.window_triggered = true;
+        }
+        if self.frame_counter != 0 {
+            return;
+        }
+
+        let lcdc = self.get_lcdc(mmu);
+        self.scanline_log.record(
+            self.current_line,
+            ScanlineRegisters {
+                bgp: mmu.read(BGP_ADDRESS),
+                obp0: mmu.read(OBP0_ADDRESS),
+                obp1: mmu.read(OBP1_ADDRESS),
+                scx: mmu.read(SCX_ADDRESS),
+                scy: mmu.read(SCY_ADDRESS),
+            },
+        );
+
+        // Color IDs (before palette mapping) of the background, needed for the sprite priority
+        let mut bg_color_ids = [0u8; SCREEN_WIDTH];
+        if lcdc.bg_window_enable {
+            self.render_background(mmu, &mut bg_color_ids);
+        } else {
+            let bytes_per_pixel = self.bytes_per_pixel;
+            let blank = self.encoded_blank;
+            for pixel in self.get_line_mut().chunks_exact_mut(bytes_per_pixel) {
+                pixel.copy_from_slice(&blank[..bytes_per_pixel]);
+            }
+        }
+
+        if lcdc.obj_enable {
+            self.render_sprites(mmu, &lcdc, &bg_color_ids);
+        }
+    }
+
+    /// The LCD shows white while it is off
+    fn clear_frame_buffer(&mut self) {
+        self.scanline_log.clear();
+        let bytes_per_pixel = self.bytes_per_pixel;
+        for pixel in self.frame_buffer.chunks_exact_mut(bytes_per_pixel) {
+            pixel.copy_from_slice(&self.encoded_blank[..bytes_per_pixel]);
+        }
+    }
+
+    /// The frame buffer bytes of the current line
+    fn get_line_mut(&mut self) -> &mut [u8] {
+        let start = self.get_frame_buffer_index(0);
+        &mut self.frame_buffer[start..start + SCREEN_WIDTH * self.bytes_per_pixel]
+    }
+
+    /// The encoded color of every color ID, looked up once per line instead of once per pixel
+    fn encode_palette(&self, layer: Layer, palette: &BackgroundPalette) -> [[u8; 4]; 4] {
+        let colors = &self.encoded_colors[layer.index()];
+        core::array::from_fn(|color_id| colors[palette.get_color_by_id(color_id as u8) as usize])
+    }
+
+    /// Draws the background and the window on top of it
+    fn render_background(&mut self, mmu: &mut MMU, bg_color_ids: &mut [u8; SCREEN_WIDTH]) {
+        let colors = self.encode_palette(Layer::Background, &self.get_background_palette(mmu));
+        let lcd_control = self.get_lcdc(mmu);
+        let scroll_x = mmu.read(SCX_ADDRESS);
+        let scroll_y = mmu.read(SCY_ADDRESS);
+        let y_pos = scroll_y.wrapping_add(self.current_line) as u16;
+
+        // With WX 0-6 the window starts left of the screen, its first 7 - WX pixels are cut off
+        let window_x = mmu.read(WX_ADDRESS);
+        let draw_window =
+            lcd_control.window_enable && self.window_triggered && window_x < WINDOW_X_MAX;
+        let window_start = window_x.saturating_sub(WINDOW_X_OFFSET) as usize;
+        let window_scroll = WINDOW_X_OFFSET.saturating_sub(window_x) as u16;
+        let window_y = self.window_line as u16;
+
+        // Every tile is only fetched and decoded once, the key tells the window and background tiles apart
+        let mut tile: Option<((bool, u16), [u8; TILE_SIZE])> = None;
+        let bytes_per_pixel = self.bytes_per_pixel;
+        let line = self.get_line_mut();
+        for (x, pixel) in line.chunks_exact_mut(bytes_per_pixel).enumerate() {
+            let in_window = draw_window && x >= window_start;
+            let (tile_address, x_pos, tile_y) = if in_window {
+                let window_x_pos = (x - window_start) as u16 + window_scroll;
+                let tile_address =
+                    lcd_control.get_window_tile_address(window_x_pos / 8, window_y / 8);
+                (tile_address, window_x_pos, window_y)
+            } else {
+                // Scrolling wraps around at the edges of the 256x256 background
+                let x_pos = scroll_x.wrapping_add(x as u8) as u16;
+                (
+                    lcd_control.get_tile_address(x_pos / 8, y_pos / 8),
+                    x_pos,
+                    y_pos,
+                )
+            };
+
+            let key = (in_window, tile_address);
+            let row = match tile {
+                Some((cached_key, row)) if cached_key == key => row,
+                _ => {
+                    let row = Self::fetch_tile_row(mmu, &lcd_control, tile_address, tile_y);
+                    tile = Some((key, row));
+                    row
+                }
+            };
+
+            let color_index = row[(x_pos % 8) as usize];
+            bg_color_ids[x] = color_index;
+            pixel.copy_from_slice(&colors[color_index as usize][..bytes_per_pixel]);
+        }
+
+        if draw_window {
+            self.window_line = self.window_line.wrapping_add(1);
+        }
+    }
+
+    /// The color IDs of the line at y_pos of the tile the map entry at the given address points to
+    fn fetch_tile_row(
+        mmu: &MMU,
+        lcd_control: &LCDControl,
+        tile_address: u16,
+        y_pos: u16,
+    ) -> [u8; TILE_SIZE] {
+        let tile_id = mmu.read(tile_address);
+        let tile_line_data_address = lcd_control.get_tile_line_data_address(tile_id, y_pos);
+        graphics::decode_row(
+            mmu.read(tile_line_data_address),
+            mmu.read(tile_line_data_address + 1),
+        )
+    }
+
+    /// https://gbdev.io/pandocs/OAM.html#drawing-priority
+    fn render_sprites(&mut self, mmu: &MMU, lcdc: &LCDControl, bg_color_ids: &[u8; SCREEN_WIDTH]) {
+        let height = if lcdc.obj_size { 16 } else { 8 };
+        let mut sprites = self.get_line_sprites(mmu, height);
+
+        // On DMG the sprite with the lower X coordinate wins, ties are broken by the OAM index.
+        // Drawing from lowest to highest priority lets the higher priority sprites overwrite the others.
+        sprites.sort_by_key(|sprite| (sprite.x, sprite.oam_index));
+        // Where the sprites were first drawn is timed like the pixel transfer of the line
+        let scroll_x = mmu.read(SCX_ADDRESS);
+        let window_x = self.get_timing_window_x(mmu, lcdc);
+
+        // Winning sprite pixel per column: (color ID, BG priority, uses OBP1, OAM index)
+        let mut line_pixels: [Option<(u8, bool, bool, u8)>; SCREEN_WIDTH] = [None; SCREEN_WIDTH];
+        for sprite in sprites.iter().rev() {
+            let data_address = sprite.get_tile_line_data_address(self.current_line, height);
+            let row = graphics::decode_row(mmu.read(data_address), mmu.read(data_address + 1));
+
+            for pixel in 0..8u8 {
+                let screen_x = sprite.x as i16 - 8 + pixel as i16;
+                if !(0..SCREEN_WIDTH as i16).contains(&screen_x) {
+                    continue;
+                }
+
+                let column = if sprite.x_flip { 7 - pixel } else { pixel };
+                let color_index = row[column as usize];
+
+                // Color 0 is transparent for sprites
+                if color_index != 0 {
+                    line_pixels[screen_x as usize] = Some((
+                        color_index,
+                        sprite.bg_priority,
+                        sprite.use_obp1,
+                        sprite.oam_index,
+                    ));
+                }
+            }
+        }
+
+        let obp0 = self.encode_palette(Layer::Object0, &self.get_object_palette(mmu, false));
+        let obp1 = self.encode_palette(Layer::Object1, &self.get_object_palette(mmu, true));
+        let bytes_per_pixel = self.bytes_per_pixel;
+        for (x, pixel) in line_pixels.iter().enumerate() {
+            let Some((color_index, bg_priority, use_obp1, oam_index)) = *pixel else {
+                continue;
+            };
+            if bg_priority && bg_color_ids[x] != 0 {
+                continue;
+            }
+            if self.sprite_hits.get(oam_index).is_none() {
+                let dot = pixel_dot(scroll_x, window_x, &sprites, x);
+                let hit = SpriteHit {
+                    line: self.current_line,
+                    dot: dot as u16,
+                };
+                self.sprite_hits.record(oam_index, hit);
+            }
+
+            let colors = if use_obp1 { &obp1 } else { &obp0 };
+            let index = self.get_frame_buffer_index(x);
+            self.frame_buffer[index..index + bytes_per_pixel]
+                .copy_from_slice(&colors[color_index as usize][..bytes_per_pixel]);
+        }
+    }
+
+    /// Uses the registers as they are when the pixel transfer starts, later writes don't change its length
+    fn compute_pixel_transfer_dots(&self, mmu: &MMU) -> u32 {
+        let lcdc = self.get_lcdc(mmu);
+        // With sprites disabled the DMG doesn't fetch them at all
+        let sprites = if lcdc.obj_enable {
+            self.get_line_sprites(mmu, if lcdc.obj_size { 16 } else { 8 })
+        } else {
+            Vec::new()
+        };
+        pixel_transfer_dots(
+            mmu.read(SCX_ADDRESS),
+            self.get_timing_window_x(mmu, &lcdc),
+            &sprites,
+        )
+    }
+
+    /// WX if the window lengthens mode 3 of the current line, None otherwise
+    fn get_timing_window_x(&self, mmu: &MMU, lcdc: &LCDControl) -> Option<u8> {
+        let window_x = mmu.read(WX_ADDRESS);
+        let window_visible = lcdc.bg_window_enable
+            && lcdc.window_enable
+            && (self.window_triggered || self.current_line == mmu.read(WY_ADDRESS))
+            && window_x < WINDOW_X_MAX;
+        window_visible.then_some(window_x)
+    }
+
+    /// The first 10 sprites in OAM order which are on the current line.
+    /// Sprites outside the visible X range still count towards the limit.
+    fn get_line_sprites(&self, mmu: &MMU, height: u8) -> Vec<Sprite> {
+        (0..OAM_SPRITE_COUNT)
+            .map(|index| {
+                let address = OAM_ADDRESS + index * 4;
+                let bytes = [
+                    mmu.read(address),
+                    mmu.read(address + 1),
+                    mmu.read(address + 2),
+                    mmu.read(address + 3),
+                ];
+                Sprite::from_oam_bytes(bytes, index as u8)
+            })
+            .filter(|sprite| sprite.is_on_line(self.current_line, height))
+            .take(MAX_SPRITES_PER_LINE)
+            .collect()
+    }
+}
+
+/// Memory Access
+impl PPU {
+    fn get_lcdc(&self, mmu: &MMU) -> LCDControl {
+        mmu.read(LCDC_ADDRESS).into()
+    }
+
+    fn get_stat(&self, mmu: &MMU) -> LCDStatus {
+        mmu.read(STAT_ADDRESS).into()
+    }
+
+    fn get_background_palette(&self, mmu: &MMU) -> BackgroundPalette {
+        mmu.read(BGP_ADDRESS).into()
+    }
+
+    /// Object palettes share the BGP layout
+    fn get_object_palette(&self, mmu: &MMU, obp1: bool) -> BackgroundPalette {
+        if obp1 {
+            mmu.read(OBP1_ADDRESS).into()
+        } else {
+            mmu.read(OBP0_ADDRESS).into()
+        }
+    }
+
+    /// Update STAT and other important memory registers
+    fn update_memory_state(&mut self, mmu: &mut MMU) {
+        let mut current_stat = self.get_stat(mmu);
+        current_stat.ppu_mode = self.mode;
+
+        current_stat.lyc_equals_ly = self.current_line == mmu.read(LYC_ADDRESS);
+        let stat_line = (current_stat.lyc_equals_ly && current_stat.lyc_interrupt)
+            || match self.mode {
+                PPUMode::HBlank => current_stat.mode0_interrupt,
+                PPUMode::VBlank => current_stat.mode1_interrupt,
+                PPUMode::OAMSearch => current_stat.mode2_interrupt,
+                PPUMode::PixelTransfer => false,
+            };
+
+        // The STAT interrupt is only requested when the line goes high,
+        // another source becoming active while it already is doesn't request it again
+        if stat_line && !self.stat_line {
+            self.stat_interrupt = true;
+        }
+        self.stat_line = stat_line;
+
+        mmu.ppu_update_stat(self.mode, current_stat.lyc_equals_ly);
+        mmu.ppu_update_ly(self.current_line);
+    }
+}
+
+fn encode_color_scheme(color_scheme: &ColorScheme, format: FrameBufferFormat) -> [[[u8; 4]; 4]; 3] {
+    Layer::ALL.map(|layer| {
+        let shades = color_scheme.get_shades(layer);
+        core::array::from_fn(|shade| format.encode(shades[shade], shade as u8))
+    })
+}
+
+impl Default for PPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::helpers::graphics;
+
 /// This will determine which colors tiles with a certain color ID have
 /// https://gbdev.io/pandocs/Palettes.html?highlight=bgp#ff47--bgp-non-cgb-mode-only-bg-palette-data
 #[derive(Debug, Clone, PartialEq)]
@@ -10,13 +12,11 @@ pub struct BackgroundPalette {
 
 impl BackgroundPalette {
     pub fn get_color_by_id(&self, id: u8) -> u8 {
-        let id = id & 0b0000_0011;
-        match id {
+        match id & 0b0000_0011 {
             0 => self.id_0,
             1 => self.id_1,
             2 => self.id_2,
-            3 => self.id_3,
-            _ => unreachable!(),
+            _ => self.id_3,
         }
     }
 }
@@ -24,10 +24,10 @@ impl BackgroundPalette {
 impl From<u8> for BackgroundPalette {
     fn from(value: u8) -> Self {
         Self {
-            id_0: value & 0b0000_0011,
-            id_1: (value & 0b0000_1100) >> 2,
-            id_2: (value & 0b0011_0000) >> 4,
-            id_3: (value & 0b1100_0000) >> 6,
+            id_0: graphics::apply_palette(value, 0),
+            id_1: graphics::apply_palette(value, 1),
+            id_2: graphics::apply_palette(value, 2),
+            id_3: graphics::apply_palette(value, 3),
         }
     }
 }
@@ -0,0 +1,41 @@
+//! Curves approximating how CGB colors looked on the real LCD.
+//! The CGB screen was dim and washed out, so the raw colors look oversaturated on modern displays.
+
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorCorrection {
+    /// The colors as they are
+    #[default]
+    Raw,
+    /// Mixes the channels and lowers the saturation like the CGB LCD, using the curve of Gambatte
+    Cgb,
+}
+
+impl ColorCorrection {
+    pub fn apply(&self, color: [u8; 4]) -> [u8; 4] {
+        match self {
+            Self::Raw => color,
+            Self::Cgb => {
+                // The curve works on the 5 bit channels of the CGB
+                let [r, g, b] = [color[0], color[1], color[2]].map(|channel| (channel >> 3) as u16);
+                [
+                    ((r * 13 + g * 2 + b) >> 1) as u8,
+                    ((g * 3 + b) << 1) as u8,
+                    ((r * 3 + g * 2 + b * 11) >> 1) as u8,
+                    color[3],
+                ]
+            }
+        }
+    }
+
+    pub fn apply_to_scheme(&self, color_scheme: &ColorScheme) -> ColorScheme {
+        let correct = |shades: [[u8; 4]; 4]| shades.map(|color| self.apply(color));
+        ColorScheme {
+            background: correct(color_scheme.background),
+            object0: correct(color_scheme.object0),
+            object1: correct(color_scheme.object1),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::game_boy::components::ppu::COLOR_SCHEME;
+use serde::{Deserialize, Serialize};
+
+/// The palette a pixel was drawn with, each one has its own colors in a [`ColorScheme`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Layer {
+    /// Background and window, mapped through BGP
+    Background,
+    /// Objects mapped through OBP0
+    Object0,
+    /// Objects mapped through OBP1
+    Object1,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 3] = [Layer::Background, Layer::Object0, Layer::Object1];
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// RGBA colors of the 4 shades (0 = lightest) for every layer.
+/// The DMG only has one set of shades, separate layers allow colorizing games like a CGB does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorScheme {
+    pub background: [[u8; 4]; 4],
+    pub object0: [[u8; 4]; 4],
+    pub object1: [[u8; 4]; 4],
+}
+
+impl ColorScheme {
+    /// The same shades for every layer
+    pub const fn monochrome(shades: [[u8; 4]; 4]) -> Self {
+        Self {
+            background: shades,
+            object0: shades,
+            object1: shades,
+        }
+    }
+
+    pub fn get_shades(&self, layer: Layer) -> &[[u8; 4]; 4] {
+        match layer {
+            Layer::Background => &self.background,
+            Layer::Object0 => &self.object0,
+            Layer::Object1 => &self.object1,
+        }
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::monochrome(COLOR_SCHEME)
+    }
+}
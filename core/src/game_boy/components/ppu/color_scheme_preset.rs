@@ -0,0 +1,76 @@
+//! Built-in color schemes, including ones for players with a color vision deficiency.
+//! The color blind schemes tell the layers apart by blue and yellow hues and by brightness,
+//! which deuteranopes and protanopes both still see, instead of red against green.
+
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use serde::{Deserialize, Serialize};
+
+const WHITE: [u8; 4] = rgb(0xFFFFFF);
+const BLACK: [u8; 4] = rgb(0x000000);
+const BLUE: [[u8; 4]; 4] = [WHITE, rgb(0x8CB8E8), rgb(0x2F5F9E), rgb(0x0A1428)];
+const ORANGE: [[u8; 4]; 4] = [WHITE, rgb(0xF5A840), rgb(0x8F4E00), rgb(0x201000)];
+/// Protanopes see red darker, so objects use yellow instead of orange
+const YELLOW: [[u8; 4]; 4] = [WHITE, rgb(0xD8C040), rgb(0x6E6000), rgb(0x1C1800)];
+const GRAY: [[u8; 4]; 4] = [WHITE, rgb(0xB4B4B4), rgb(0x5A5A5A), BLACK];
+/// The luminance of neighboring shades differs by the same ratio of about 2.76,
+/// which is the largest contrast all 3 pairs can have at once
+const HIGH_CONTRAST: [[u8; 4]; 4] = [WHITE, rgb(0x9C9C9C), rgb(0x545454), BLACK];
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorSchemePreset {
+    /// The Game Boy Pocket shades of [`ColorScheme::default`]
+    #[default]
+    Pocket,
+    /// Blue background, orange and gray objects
+    Deuteranopia,
+    /// Blue background, yellow and gray objects
+    Protanopia,
+    /// Gray shades as far apart in brightness as possible
+    HighContrast,
+}
+
+impl ColorSchemePreset {
+    pub const ALL: [ColorSchemePreset; 4] = [
+        ColorSchemePreset::Pocket,
+        ColorSchemePreset::Deuteranopia,
+        ColorSchemePreset::Protanopia,
+        ColorSchemePreset::HighContrast,
+    ];
+
+    pub fn get_color_scheme(&self) -> ColorScheme {
+        match self {
+            Self::Pocket => ColorScheme::default(),
+            Self::Deuteranopia => ColorScheme {
+                background: BLUE,
+                object0: ORANGE,
+                object1: GRAY,
+            },
+            Self::Protanopia => ColorScheme {
+                background: BLUE,
+                object0: YELLOW,
+                object1: GRAY,
+            },
+            Self::HighContrast => ColorScheme::monochrome(HIGH_CONTRAST),
+        }
+    }
+
+    /// The preset with exactly these colors, None for custom color schemes
+    pub fn find(color_scheme: &ColorScheme) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.get_color_scheme() == *color_scheme)
+    }
+
+    /// The following preset, after the last one comes the first one again
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|preset| preset == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+const fn rgb(hex: u32) -> [u8; 4] {
+    [(hex >> 16) as u8, (hex >> 8) as u8, hex as u8, 0xFF]
+}
@@ -0,0 +1,85 @@
+//! The colors the CGB boot ROM picks when it runs a DMG game.
+//! Nintendo games are looked up by a checksum of their title, everything else gets the default colors.
+
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+
+/// [`colorize`] only looks at the cartridge header, which ends here
+pub const HEADER_END: u16 = 0x150;
+const TITLE_START: usize = 0x134;
+const TITLE_END: usize = 0x143;
+const NEW_LICENSEE_START: usize = 0x144;
+const OLD_LICENSEE_ADDRESS: usize = 0x14B;
+/// The old licensee code saying the new licensee code should be used instead
+const USE_NEW_LICENSEE: u8 = 0x33;
+const NINTENDO_OLD_LICENSEE: u8 = 0x01;
+const NINTENDO_NEW_LICENSEE: [u8; 2] = *b"01";
+
+const WHITE: [u8; 4] = rgb(0xFFFFFF);
+const BLACK: [u8; 4] = rgb(0x000000);
+const RED: [[u8; 4]; 4] = [WHITE, rgb(0xFF8484), rgb(0x943A3A), BLACK];
+const GREEN: [[u8; 4]; 4] = [WHITE, rgb(0x7BFF31), rgb(0x008400), BLACK];
+const BLUE: [[u8; 4]; 4] = [WHITE, rgb(0x63A5FF), rgb(0x0000FF), BLACK];
+
+/// Used for games which aren't from Nintendo or not in the title table,
+/// the same as holding Right while the boot logo is shown
+pub const DEFAULT_COLORIZATION: ColorScheme =
+    ColorScheme::monochrome([WHITE, rgb(0x52FF00), rgb(0xFF4200), BLACK]);
+
+/// Title checksums and their colors.
+/// This is only a part of the boot ROM's table, other titles fall back to [`DEFAULT_COLORIZATION`].
+const TITLE_COLORIZATIONS: [(u8, ColorScheme); 2] = [
+    // POKEMON RED, also used by GAMEBOYCAMERA G
+    (
+        0x14,
+        ColorScheme {
+            background: RED,
+            object0: GREEN,
+            object1: BLUE,
+        },
+    ),
+    // POKEMON BLUE
+    (
+        0x61,
+        ColorScheme {
+            background: BLUE,
+            object0: RED,
+            object1: GREEN,
+        },
+    ),
+];
+
+const fn rgb(hex: u32) -> [u8; 4] {
+    [(hex >> 16) as u8, (hex >> 8) as u8, hex as u8, 0xFF]
+}
+
+/// The sum of all 16 title bytes, including the ones newer cartridges use for the manufacturer code and CGB flag
+pub fn title_checksum(rom: &[u8]) -> u8 {
+    rom.get(TITLE_START..=TITLE_END)
+        .unwrap_or_default()
+        .iter()
+        .fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+}
+
+/// The boot ROM only colorizes Nintendo's own games by title
+pub fn is_nintendo_game(rom: &[u8]) -> bool {
+    match rom.get(OLD_LICENSEE_ADDRESS) {
+        Some(&USE_NEW_LICENSEE) => {
+            rom.get(NEW_LICENSEE_START..NEW_LICENSEE_START + 2) == Some(&NINTENDO_NEW_LICENSEE)
+        }
+        Some(&old_licensee) => old_licensee == NINTENDO_OLD_LICENSEE,
+        None => false,
+    }
+}
+
+/// The colors for a DMG game, `rom` has to start at 0x0000 of ROM bank 0
+pub fn colorize(rom: &[u8]) -> ColorScheme {
+    if !is_nintendo_game(rom) {
+        return DEFAULT_COLORIZATION;
+    }
+
+    let checksum = title_checksum(rom);
+    TITLE_COLORIZATIONS
+        .iter()
+        .find(|(title_checksum, _)| *title_checksum == checksum)
+        .map_or(DEFAULT_COLORIZATION, |(_, color_scheme)| *color_scheme)
+}
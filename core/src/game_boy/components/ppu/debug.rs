@@ -0,0 +1,237 @@
+//! Debug views of VRAM and OAM, rendered straight from memory with the current BGP.
+//! https://gbdev.io/pandocs/Tile_Data.html
+use crate::game_boy::components::mmu::TileMap;
+use crate::game_boy::components::mmu::{BGP_ADDRESS, LCDC_ADDRESS, MMU, OAM_ADDRESS};
+use crate::game_boy::components::ppu::background_palette::BackgroundPalette;
+use crate::game_boy::components::ppu::lcd_control::LCDControl;
+use crate::game_boy::components::ppu::sprite::Sprite;
+use crate::game_boy::components::ppu::{COLOR_SCHEME, OAM_SPRITE_COUNT, SCREEN_HEIGHT};
+use crate::helpers::graphics;
+use crate::helpers::graphics::{TILE_BYTES, TILE_SIZE};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// All 384 tiles of 0x8000-0x97FF, 16 per row
+pub const TILE_DATA_COLUMNS: usize = 16;
+pub const TILE_DATA_COUNT: usize = 384;
+pub const TILE_DATA_WIDTH: usize = TILE_DATA_COLUMNS * 8;
+pub const TILE_DATA_HEIGHT: usize = TILE_DATA_COUNT / TILE_DATA_COLUMNS * 8;
+/// Tile maps are 32x32 tiles
+pub const TILE_MAP_SIZE: usize = 256;
+
+const TILE_DATA_ADDRESS: u16 = 0x8000;
+
+/// A tile of the tile data view, e.g. for a hover tooltip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileInfo {
+    /// Index into 0x8000-0x97FF, 0-383
+    pub index: u16,
+    pub address: u16,
+    /// Raw BGP value the tile was rendered with
+    pub palette: u8,
+}
+
+/// A single entry of a tile map view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileMapEntry {
+    pub map_address: u16,
+    pub tile_id: u8,
+    /// Where the tile data is found, depending on the LCDC addressing mode
+    pub data_address: u16,
+    pub palette: u8,
+}
+
+/// Where an OAM entry was first drawn during a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteHit {
+    pub line: u8,
+    /// When the pixel was drawn, counted from the start of the line like the mode 3 timing,
+    /// including the penalties of scrolling, the window and the sprites fetched before it
+    pub dot: u16,
+}
+
+/// The first visible pixel of every OAM entry during the current frame.
+/// This is debug information and not emulated state, so it is ignored when comparing PPUs.
+#[derive(Debug, Clone)]
+pub struct SpriteHits([Option<SpriteHit>; OAM_SPRITE_COUNT as usize]);
+
+impl SpriteHits {
+    /// Only the first pixel of a frame is kept
+    pub fn record(&mut self, oam_index: u8, hit: SpriteHit) {
+        self.0[oam_index as usize].get_or_insert(hit);
+    }
+
+    pub fn get(&self, oam_index: u8) -> Option<SpriteHit> {
+        self.0.get(oam_index as usize).copied().flatten()
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = [None; OAM_SPRITE_COUNT as usize];
+    }
+}
+
+impl Default for SpriteHits {
+    fn default() -> Self {
+        Self([None; OAM_SPRITE_COUNT as usize])
+    }
+}
+
+impl PartialEq for SpriteHits {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// The palettes and scroll registers a scanline was drawn with, as they were when its pixel transfer ended.
+/// Lets upscaling filters and debuggers reproduce effects which change them in the middle of a frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScanlineRegisters {
+    pub bgp: u8,
+    pub obp0: u8,
+    pub obp1: u8,
+    pub scx: u8,
+    pub scy: u8,
+}
+
+/// The registers of every line currently in the frame buffer, None for lines which are blank since the LCD was
+/// switched off or which weren't drawn yet. Lines above LY already belong to the frame being drawn.
+/// This is debug information and not emulated state, so it is ignored when comparing PPUs.
+#[derive(Debug, Clone)]
+pub struct ScanlineLog([Option<ScanlineRegisters>; SCREEN_HEIGHT]);
+
+impl ScanlineLog {
+    pub fn record(&mut self, line: u8, registers: ScanlineRegisters) {
+        if let Some(entry) = self.0.get_mut(line as usize) {
+            *entry = Some(registers);
+        }
+    }
+
+    pub fn get(&self, line: u8) -> Option<ScanlineRegisters> {
+        self.0.get(line as usize).copied().flatten()
+    }
+
+    pub fn get_lines(&self) -> &[Option<ScanlineRegisters>] {
+        &self.0
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = [None; SCREEN_HEIGHT];
+    }
+}
+
+impl Default for ScanlineLog {
+    fn default() -> Self {
+        Self([None; SCREEN_HEIGHT])
+    }
+}
+
+impl PartialEq for ScanlineLog {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// A single entry of the OAM inspector
+#[derive(Debug, Clone, PartialEq)]
+pub struct OamEntry {
+    pub address: u16,
+    pub sprite: Sprite,
+    /// None if none of the sprite's pixels made it to the screen this frame
+    pub first_hit: Option<SpriteHit>,
+}
+
+/// RGBA image of all tiles, TILE_DATA_WIDTH x TILE_DATA_HEIGHT pixels
+pub fn render_tile_data(mmu: &MMU) -> Vec<u8> {
+    let palette: BackgroundPalette = mmu.read(BGP_ADDRESS).into();
+    let mut image = vec![0u8; TILE_DATA_WIDTH * TILE_DATA_HEIGHT * 4];
+    for tile_index in 0..TILE_DATA_COUNT {
+        let tile = read_tile(mmu, TILE_DATA_ADDRESS + (tile_index * TILE_BYTES) as u16);
+        let left = tile_index % TILE_DATA_COLUMNS * TILE_SIZE;
+        let top = tile_index / TILE_DATA_COLUMNS * TILE_SIZE;
+        for (y, row) in graphics::decode_tile(&tile).into_iter().enumerate() {
+            for (x, color_id) in row.into_iter().enumerate() {
+                let pixel = (top + y) * TILE_DATA_WIDTH + left + x;
+                write_rgba(&mut image, pixel, &palette, color_id);
+            }
+        }
+    }
+    image
+}
+
+/// RGBA image of a whole tile map, TILE_MAP_SIZE x TILE_MAP_SIZE pixels, using the LCDC addressing mode
+pub fn render_tile_map(mmu: &MMU, map: TileMap) -> Vec<u8> {
+    let palette: BackgroundPalette = mmu.read(BGP_ADDRESS).into();
+    let mut image = vec![0u8; TILE_MAP_SIZE * TILE_MAP_SIZE * 4];
+    for y in 0..TILE_MAP_SIZE {
+        for x in 0..TILE_MAP_SIZE {
+            let entry = get_tile_map_entry(mmu, map, x, y).unwrap();
+            let color_id = read_tile_pixel(mmu, entry.data_address, x as u8 % 8, y as u8 % 8);
+            write_rgba(&mut image, y * TILE_MAP_SIZE + x, &palette, color_id);
+        }
+    }
+    image
+}
+
+/// The tile under a pixel of the tile data image, None if out of bounds
+pub fn get_tile_info(mmu: &MMU, x: usize, y: usize) -> Option<TileInfo> {
+    if x >= TILE_DATA_WIDTH || y >= TILE_DATA_HEIGHT {
+        return None;
+    }
+    let index = ((y / 8) * TILE_DATA_COLUMNS + x / 8) as u16;
+    Some(TileInfo {
+        index,
+        address: TILE_DATA_ADDRESS + index * 16,
+        palette: mmu.read(BGP_ADDRESS),
+    })
+}
+
+/// The tile map entry under a pixel of the tile map image, None if out of bounds
+pub fn get_tile_map_entry(mmu: &MMU, map: TileMap, x: usize, y: usize) -> Option<TileMapEntry> {
+    if x >= TILE_MAP_SIZE || y >= TILE_MAP_SIZE {
+        return None;
+    }
+    let lcdc: LCDControl = mmu.read(LCDC_ADDRESS).into();
+    let map_address = map.get_address() + (y / 8 * 32 + x / 8) as u16;
+    let tile_id = mmu.read(map_address);
+    Some(TileMapEntry {
+        map_address,
+        tile_id,
+        data_address: lcdc.get_tile_line_data_address(tile_id, 0),
+        palette: mmu.read(BGP_ADDRESS),
+    })
+}
+
+/// All 40 OAM entries in OAM order, with where they were first drawn this frame
+pub fn get_oam_entries(mmu: &MMU, hits: &SpriteHits) -> Vec<OamEntry> {
+    (0..OAM_SPRITE_COUNT)
+        .map(|index| {
+            let address = OAM_ADDRESS + index * 4;
+            let bytes = [
+                mmu.read(address),
+                mmu.read(address + 1),
+                mmu.read(address + 2),
+                mmu.read(address + 3),
+            ];
+            OamEntry {
+                address,
+                sprite: Sprite::from_oam_bytes(bytes, index as u8),
+                first_hit: hits.get(index as u8),
+            }
+        })
+        .collect()
+}
+
+fn read_tile(mmu: &MMU, tile_address: u16) -> [u8; TILE_BYTES] {
+    core::array::from_fn(|offset| mmu.read(tile_address + offset as u16))
+}
+
+fn read_tile_pixel(mmu: &MMU, tile_address: u16, x: u8, y: u8) -> u8 {
+    let low_byte = mmu.read(tile_address + y as u16 * 2);
+    let high_byte = mmu.read(tile_address + y as u16 * 2 + 1);
+    graphics::decode_pixel(low_byte, high_byte, x)
+}
+
+fn write_rgba(image: &mut [u8], pixel: usize, palette: &BackgroundPalette, color_id: u8) {
+    let color = COLOR_SCHEME[palette.get_color_by_id(color_id) as usize];
+    image[pixel * 4..pixel * 4 + 4].copy_from_slice(&color);
+}
@@ -0,0 +1,73 @@
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The pixel layout of the frame buffer produced by the PPU.
+/// The format is fixed when the PPU is constructed, so rendering only copies precomputed pixel bytes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FrameBufferFormat {
+    /// 4 bytes per pixel: red, green, blue, alpha
+    #[default]
+    Rgba8888,
+    /// 2 bytes per pixel (little endian): 5 bits red, 6 bits green, 5 bits blue
+    Rgb565,
+    /// 1 byte per pixel holding the 2-bit shade (0 = lightest, 3 = darkest) after palette mapping,
+    /// so frontends can apply their own colors
+    Indexed,
+}
+
+impl FrameBufferFormat {
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            FrameBufferFormat::Rgba8888 => 4,
+            FrameBufferFormat::Rgb565 => 2,
+            FrameBufferFormat::Indexed => 1,
+        }
+    }
+
+    /// Encodes a single RGBA color into the pixel bytes of this format.
+    /// Only the first `bytes_per_pixel` bytes of the result are meaningful.
+    /// The shade is required for the indexed format, which does not store colors at all.
+    pub fn encode(&self, rgba: [u8; 4], shade: u8) -> [u8; 4] {
+        match self {
+            FrameBufferFormat::Rgba8888 => rgba,
+            FrameBufferFormat::Rgb565 => {
+                let [lsb, msb] = rgba_to_rgb565(rgba).to_le_bytes();
+                [lsb, msb, 0, 0]
+            }
+            FrameBufferFormat::Indexed => [shade & 0b11, 0, 0, 0],
+        }
+    }
+
+    /// Converts a frame buffer of this format back to RGBA, using the given color scheme for indexed pixels
+    pub fn to_rgba(&self, frame_buffer: &[u8], color_scheme: &[[u8; 4]; 4]) -> Vec<u8> {
+        match self {
+            FrameBufferFormat::Rgba8888 => frame_buffer.to_vec(),
+            FrameBufferFormat::Rgb565 => frame_buffer
+                .chunks_exact(2)
+                .flat_map(|pixel| rgb565_to_rgba(u16::from_le_bytes([pixel[0], pixel[1]])))
+                .collect(),
+            FrameBufferFormat::Indexed => frame_buffer
+                .iter()
+                .flat_map(|&shade| color_scheme[(shade & 0b11) as usize])
+                .collect(),
+        }
+    }
+}
+
+pub fn rgba_to_rgb565(rgba: [u8; 4]) -> u16 {
+    let [r, g, b, _] = rgba;
+    ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3)
+}
+
+pub fn rgb565_to_rgba(value: u16) -> [u8; 4] {
+    let r = ((value >> 11) & 0b1_1111) as u8;
+    let g = ((value >> 5) & 0b11_1111) as u8;
+    let b = (value & 0b1_1111) as u8;
+    // Replicate the upper bits into the lower ones so full intensity maps to 0xFF
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+        0xFF,
+    ]
+}
@@ -29,8 +29,21 @@ impl LCDControl {
         }
     }
 
+    pub fn get_window_tilemap_address(&self) -> u16 {
+        if self.window_tilemap {
+            0x9C00
+        } else {
+            0x9800
+        }
+    }
+
+    /// Tile coordinates wrap around at the edges of the 32x32 map
     pub fn get_tile_address(&self, tile_x: u16, tile_y: u16) -> u16 {
-        self.get_bg_tilemap_address() + tile_x + tile_y * 32
+        self.get_bg_tilemap_address() + (tile_x % 32) + (tile_y % 32) * 32
+    }
+
+    pub fn get_window_tile_address(&self, tile_x: u16, tile_y: u16) -> u16 {
+        self.get_window_tilemap_address() + (tile_x % 32) + (tile_y % 32) * 32
     }
 
     pub fn get_tile_line_data_address(&self, tile_id: u8, y_pos: u16) -> u16 {
@@ -26,8 +26,7 @@ impl From<u8> for PPUMode {
             0b00 => PPUMode::HBlank,
             0b01 => PPUMode::VBlank,
             0b10 => PPUMode::OAMSearch,
-            0b11 => PPUMode::PixelTransfer,
-            _ => unreachable!(),
+            _ => PPUMode::PixelTransfer,
         }
     }
 }
@@ -0,0 +1,19 @@
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::mode::PPUMode;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PPUSaveState {
+    pub mode: PPUMode,
+    pub mode_clock: u32,
+    pub pixel_transfer_dots: u32,
+    pub current_line: u8,
+    pub lcd_enabled: bool,
+    pub window_line: u8,
+    pub window_triggered: bool,
+    pub stat_line: bool,
+    pub frame_dots: u32,
+    pub frame_buffer_format: FrameBufferFormat,
+    pub frame_buffer: Vec<u8>,
+}
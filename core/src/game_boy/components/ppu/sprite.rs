@@ -0,0 +1,58 @@
+/// A single OAM entry
+/// https://gbdev.io/pandocs/OAM.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    /// Screen Y position + 16
+    pub y: u8,
+    /// Screen X position + 8
+    pub x: u8,
+    pub tile_index: u8,
+    /// If true, BG and window colors 1-3 are drawn over this sprite
+    pub bg_priority: bool,
+    pub y_flip: bool,
+    pub x_flip: bool,
+    /// If true, OBP1 is used instead of OBP0
+    pub use_obp1: bool,
+    /// Position in OAM (0-39), used to break priority ties
+    pub oam_index: u8,
+}
+
+impl Sprite {
+    pub fn from_oam_bytes(bytes: [u8; 4], oam_index: u8) -> Self {
+        let attributes = bytes[3];
+        Self {
+            y: bytes[0],
+            x: bytes[1],
+            tile_index: bytes[2],
+            bg_priority: (attributes & 0b1000_0000) != 0,
+            y_flip: (attributes & 0b0100_0000) != 0,
+            x_flip: (attributes & 0b0010_0000) != 0,
+            use_obp1: (attributes & 0b0001_0000) != 0,
+            oam_index,
+        }
+    }
+
+    /// Whether the sprite covers the given scanline, sprite height being 8 or 16
+    pub fn is_on_line(&self, line: u8, height: u8) -> bool {
+        let line = line as u16 + 16;
+        let top = self.y as u16;
+        line >= top && line < top + height as u16
+    }
+
+    /// Address of the tile data for the given scanline, objects always use the 0x8000 addressing mode
+    pub fn get_tile_line_data_address(&self, line: u8, height: u8) -> u16 {
+        let mut row = (line as u16 + 16 - self.y as u16) as u8;
+        if self.y_flip {
+            row = height - 1 - row;
+        }
+
+        // In 8x16 mode the lowest bit of the tile index is ignored
+        let tile_index = if height == 16 {
+            self.tile_index & 0xFE
+        } else {
+            self.tile_index
+        };
+
+        0x8000 + tile_index as u16 * 16 + row as u16 * 2
+    }
+}
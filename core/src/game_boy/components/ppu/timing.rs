@@ -0,0 +1,79 @@
+//! How long the pixel transfer (mode 3) of a line takes, the HBlank after it is shorter by as much as it is longer.
+//! https://gbdev.io/pandocs/Rendering.html#mode-3-length
+
+use crate::game_boy::components::ppu::sprite::Sprite;
+use crate::game_boy::components::ppu::{SCREEN_WIDTH, WINDOW_X_OFFSET};
+use alloc::vec::Vec;
+
+pub const OAM_SEARCH_DOTS: u32 = 80;
+/// Mode 3 without any scrolling, window or sprites
+pub const MIN_PIXEL_TRANSFER_DOTS: u32 = 172;
+pub const SCANLINE_DOTS: u32 = 456;
+/// The background fetcher restarts when it reaches the window
+const WINDOW_PENALTY: u32 = 6;
+/// Fetching the tile of every sprite pauses the background fetcher
+const SPRITE_FETCH_PENALTY: u32 = 6;
+/// A sprite at X 0 is entirely left of the screen, it waits for the first background fetch regardless of SCX
+const SPRITE_X_0_PENALTY: u32 = 11;
+/// Sprites from this X on are right of the screen, the fetcher never reaches them
+const SPRITE_X_OFFSCREEN: u8 = 168;
+
+/// The length of mode 3 for a line showing the given sprites (the ones selected for the line, in OAM order).
+/// window_x is None unless the window is drawn on the line.
+pub fn pixel_transfer_dots(scroll_x: u8, window_x: Option<u8>, sprites: &[Sprite]) -> u32 {
+    MIN_PIXEL_TRANSFER_DOTS + penalty_dots(scroll_x, window_x, sprites, SCREEN_WIDTH as i16)
+}
+
+/// The dot of the line at which the pixel at screen X is drawn, counted from the start of the OAM search.
+/// Only the penalties the fetcher ran into before reaching the pixel delay it, so the dot after the last pixel is
+/// the end of mode 3.
+pub fn pixel_dot(scroll_x: u8, window_x: Option<u8>, sprites: &[Sprite], x: usize) -> u32 {
+    let fetch_delay = MIN_PIXEL_TRANSFER_DOTS - SCREEN_WIDTH as u32;
+    OAM_SEARCH_DOTS + fetch_delay + x as u32 + penalty_dots(scroll_x, window_x, sprites, x as i16)
+}
+
+/// The dots mode 3 is lengthened by until the fetcher reaches the pixel at screen X
+fn penalty_dots(scroll_x: u8, window_x: Option<u8>, sprites: &[Sprite], until_x: i16) -> u32 {
+    // The pixels scrolled out of the first tile are fetched and discarded
+    let mut dots = (scroll_x % 8) as u32;
+    let window_start = window_x.map(|window_x| window_x as i16 - WINDOW_X_OFFSET as i16);
+    if window_start.is_some_and(|window_start| window_start <= until_x) {
+        dots += WINDOW_PENALTY;
+    }
+
+    // The fetcher meets the sprites from left to right
+    let mut sprites: Vec<&Sprite> = sprites.iter().collect();
+    sprites.sort_by_key(|sprite| (sprite.x, sprite.oam_index));
+
+    // Background or window tiles (told apart by the flag) which already waited for their fetch to finish
+    let mut waited_tiles: Vec<(bool, i16)> = Vec::new();
+    for sprite in sprites {
+        // The tile below the leftmost pixel of the sprite decides how long the fetch has to wait
+        let pixel = sprite.x as i16 - 8;
+        if sprite.x >= SPRITE_X_OFFSCREEN || pixel > until_x {
+            break;
+        }
+        if sprite.x == 0 {
+            dots += SPRITE_X_0_PENALTY;
+            continue;
+        }
+
+        let (in_window, position) = match window_start {
+            Some(window_start) if pixel >= window_start => (true, pixel - window_start),
+            _ => (false, pixel + scroll_x as i16),
+        };
+        let tile = (in_window, position.div_euclid(8));
+        if !waited_tiles.contains(&tile) {
+            waited_tiles.push(tile);
+            let pixels_right_of_sprite = 7 - position.rem_euclid(8);
+            dots += (pixels_right_of_sprite - 2).max(0) as u32;
+        }
+        dots += SPRITE_FETCH_PENALTY;
+    }
+    dots
+}
+
+/// What is left of the line after OAM search and pixel transfer
+pub fn h_blank_dots(pixel_transfer_dots: u32) -> u32 {
+    SCANLINE_DOTS - OAM_SEARCH_DOTS - pixel_transfer_dots
+}
@@ -0,0 +1,156 @@
+//! https://gbdev.io/pandocs/Serial_Data_Transfer_(Link_Cable).html
+
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::mmu::{MMU, SB_ADDRESS, SC_ADDRESS};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use crate::helpers::bit_operations::get_bit_u8;
+use crate::helpers::shared::{lock, share, Shared, SharedGuard};
+use alloc::boxed::Box;
+use core::error::Error;
+use core::fmt::{Debug, Formatter};
+use serde::{Deserialize, Serialize};
+
+/// With the internal clock a bit is shifted every 512 T-cycles (8192 Hz), so a whole byte takes 4096
+pub const TRANSFER_DURATION: Cycles = Cycles::from_t(8 * 512);
+
+/// The other end of the link cable, e.g. another emulator connected over the network
+pub trait SerialLink: Send {
+    /// Sends the byte of a transfer this Game Boy clocks, returns the partner's byte or None if it didn't answer
+    fn exchange(&mut self, byte: u8) -> Option<u8>;
+    /// The byte of a transfer the partner clocks, which has to be answered with [`respond`](Self::respond)
+    fn poll(&mut self) -> Option<u8>;
+    fn respond(&mut self, byte: u8);
+}
+
+/// The plug of the link port, clones of the Game Boy share the same partner.
+/// Not part of the emulated state.
+#[derive(Default, Clone)]
+pub struct LinkCable {
+    partner: Option<Shared<dyn SerialLink>>,
+}
+
+impl LinkCable {
+    /// Replaces the previous partner
+    pub fn connect(&mut self, partner: impl SerialLink + 'static) {
+        self.partner = Some(share(partner));
+    }
+
+    pub fn disconnect(&mut self) {
+        self.partner = None;
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.partner.is_some()
+    }
+
+    pub fn lock(&self) -> Option<SharedGuard<'_, dyn SerialLink + 'static>> {
+        self.partner.as_ref().map(lock)
+    }
+}
+
+impl Debug for LinkCable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "LinkCable(connected: {})", self.is_connected())
+    }
+}
+
+impl PartialEq for LinkCable {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Times transfers started with the internal clock.
+/// Without a link partner every transfer shifts in 0xFF, see [`step_linked`](Self::step_linked) for one with a partner.
+/// Without a partner the byte that was sent is captured by the MMU as soon as the transfer starts.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Serial {
+    /// Cycles the current transfer has been running for
+    elapsed: Cycles,
+}
+
+impl Serial {
+    /// Requests the Serial Interrupt when a transfer completes, returns true if it was triggered
+    pub fn step(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        let sc = mmu.read(SC_ADDRESS);
+        let transferring = get_bit_u8(sc, 7);
+        let internal_clock = get_bit_u8(sc, 0);
+        if !transferring || !internal_clock {
+            // Without a link partner an externally clocked transfer never progresses
+            self.elapsed = Cycles::ZERO;
+            return false;
+        }
+
+        self.elapsed += cycles;
+        if self.elapsed < TRANSFER_DURATION {
+            return false;
+        }
+
+        self.elapsed = Cycles::ZERO;
+        Self::finish_transfer(mmu, sc, 0xFF)
+    }
+
+    /// Like [`step`](Self::step), but the bytes are exchanged with the partner.
+    /// An internally clocked transfer waits for the partner's byte when it completes,
+    /// the partner clocks externally clocked ones whenever it starts a transfer.
+    pub fn step_linked(
+        &mut self,
+        cycles: Cycles,
+        mmu: &mut MMU,
+        partner: &mut dyn SerialLink,
+    ) -> bool {
+        let sc = mmu.read(SC_ADDRESS);
+        let transferring = get_bit_u8(sc, 7);
+        if transferring && get_bit_u8(sc, 0) {
+            self.elapsed += cycles;
+            if self.elapsed < TRANSFER_DURATION {
+                return false;
+            }
+            self.elapsed = Cycles::ZERO;
+            let received = partner.exchange(mmu.read(SB_ADDRESS)).unwrap_or(0xFF);
+            return Self::finish_transfer(mmu, sc, received);
+        }
+
+        self.elapsed = Cycles::ZERO;
+        let Some(received) = partner.poll() else {
+            return false;
+        };
+        if !transferring {
+            // Nothing is shifted out while no transfer was started, the partner sees an idle line
+            partner.respond(0xFF);
+            return false;
+        }
+        partner.respond(mmu.read(SB_ADDRESS));
+        Self::finish_transfer(mmu, sc, received)
+    }
+
+    fn finish_transfer(mmu: &mut MMU, sc: u8, received: u8) -> bool {
+        mmu.write(SB_ADDRESS, received);
+        mmu.write(SC_ADDRESS, sc & 0b0111_1111);
+        mmu.interrupts_mut().request(Interrupt::Serial);
+        true
+    }
+}
+
+impl Component for Serial {
+    type SaveState = Serial;
+
+    fn reset(&mut self, _config: &GameBoyConfig) {
+        *self = Self::default();
+    }
+
+    fn tick(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        self.step(cycles, mmu)
+    }
+
+    fn save(&self) -> Serial {
+        self.clone()
+    }
+
+    fn load(&mut self, state: Serial) -> Result<(), Box<dyn Error>> {
+        *self = state;
+        Ok(())
+    }
+}
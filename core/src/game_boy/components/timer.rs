@@ -0,0 +1,176 @@
+//! https://hacktix.github.io/GBEDG/timers/
+
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::mmu::{DIV_ADDRESS, MMU, TAC_ADDRESS, TIMA_ADDRESS, TMA_ADDRESS};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use crate::helpers::bit_operations::{get_bit_u16, get_bit_u8};
+use crate::helpers::listeners::{ListenerId, Listeners};
+use alloc::boxed::Box;
+use core::error::Error;
+use serde::{Deserialize, Serialize};
+
+// ToDo: Maybe add more accurate TIMA overflow timing, its 0 for 1 M-Cycle before getting reset to TMA and triggering the interrupt
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timer {
+    pub counter: u16,
+    last_and_result: bool,
+    #[serde(skip)]
+    overflow_listeners: Listeners<TimerOverflowEvent>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimerOverflowEvent {
+    /// The internal counter at the time of the overflow
+    pub counter: u16,
+    /// The value TIMA was reloaded with
+    pub tma: u8,
+}
+
+impl Timer {
+    /// Starts with the internal counter the boot ROM left behind, see [`get_div_counter`](crate::game_boy::hardware_model::HardwareModel::get_div_counter)
+    pub fn initialize(counter: u16) -> Self {
+        Self {
+            counter,
+            last_and_result: false,
+            overflow_listeners: Listeners::default(),
+        }
+    }
+
+    /// Registers a callback which is invoked on every TIMA overflow
+    pub fn on_overflow(
+        &mut self,
+        callback: impl FnMut(&TimerOverflowEvent) + Send + 'static,
+    ) -> ListenerId {
+        self.overflow_listeners.subscribe(callback)
+    }
+
+    pub fn remove_overflow_listener(&mut self, id: ListenerId) {
+        self.overflow_listeners.unsubscribe(id);
+    }
+
+    /// Predicts in how many cycles TIMA will overflow, assuming no timer register is written until then.
+    /// Returns None if the timer is disabled.
+    pub fn cycles_until_overflow(&self, mmu: &MMU) -> Option<Cycles> {
+        let tac = mmu.read(TAC_ADDRESS);
+        if !get_bit_u8(tac, 2) {
+            return None;
+        }
+
+        // TIMA increments whenever the selected counter bit falls, so once per full period of that bit
+        let period: u32 = match tac & 0b0000_0011 {
+            0b00 => 1 << 10,
+            0b01 => 1 << 4,
+            0b10 => 1 << 6,
+            0b11 => 1 << 8,
+            _ => unreachable!(),
+        };
+
+        // A DIV write only resets the counter on the next step
+        let counter = if mmu.read(DIV_ADDRESS) == 0 && (self.counter >> 8) != 0 {
+            0
+        } else {
+            self.counter as u32
+        };
+
+        let until_next_increment = period - (counter & (period - 1));
+        let remaining_increments = 0xFF - mmu.read(TIMA_ADDRESS) as u32;
+        Some(Cycles::from_t(
+            until_next_increment + remaining_increments * period,
+        ))
+    }
+
+    /// Requests the Timer Interrupt on TIMA overflow, returns true if it was triggered
+    pub fn step(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        let mut interrupt_triggered = false;
+
+        for _ in 0..cycles.as_m() {
+            self.update_counter(Cycles::from_m(1), mmu);
+            self.update_div(mmu);
+            if self.update_tima(mmu) {
+                mmu.interrupts_mut().request(Interrupt::Timer);
+                self.overflow_listeners.notify(&TimerOverflowEvent {
+                    counter: self.counter,
+                    tma: mmu.read(TMA_ADDRESS),
+                });
+                interrupt_triggered = true;
+            }
+        }
+
+        interrupt_triggered
+    }
+
+    fn update_counter(&mut self, cycles: Cycles, mmu: &MMU) {
+        let div = mmu.read(DIV_ADDRESS);
+        // If DIV is 0 but our counter's high byte isn't, DIV must have been reset
+        if div == 0 && (self.counter >> 8) != 0 {
+            self.counter = 0;
+        }
+        self.counter = self.counter.wrapping_add(cycles.as_t() as u16);
+    }
+
+    fn update_div(&self, mmu: &mut MMU) {
+        mmu.timer_update_div((self.counter >> 8) as u8);
+    }
+
+    /// Returns true if a Timer Interrupt should be requested
+    fn update_tima(&mut self, mmu: &mut MMU) -> bool {
+        let tac = mmu.read(TAC_ADDRESS);
+        let timer_enabled = get_bit_u8(tac, 2);
+        let and_value = match tac & 0b0000_0011 {
+            0b00 => get_bit_u16(self.counter, 9),
+            0b01 => get_bit_u16(self.counter, 3),
+            0b10 => get_bit_u16(self.counter, 5),
+            0b11 => get_bit_u16(self.counter, 7),
+            _ => unreachable!(),
+        };
+
+        let and_result = and_value && timer_enabled;
+
+        // No falling edge detected
+        if !self.last_and_result || and_result {
+            self.last_and_result = and_result;
+            return false;
+        } else {
+            self.last_and_result = false;
+        }
+
+        let last_tima = mmu.read(TIMA_ADDRESS);
+        if last_tima != 0xFF {
+            mmu.write(TIMA_ADDRESS, last_tima + 1);
+            false
+        } else {
+            let tma = mmu.read(TMA_ADDRESS);
+            mmu.write(TIMA_ADDRESS, tma);
+            true
+        }
+    }
+}
+
+impl Component for Timer {
+    type SaveState = Timer;
+
+    fn reset(&mut self, config: &GameBoyConfig) {
+        self.counter = config.get_div_counter();
+        self.last_and_result = false;
+    }
+
+    fn tick(&mut self, cycles: Cycles, mmu: &mut MMU) -> bool {
+        self.step(cycles, mmu)
+    }
+
+    /// Without the overflow listeners, which can't be saved
+    fn save(&self) -> Timer {
+        Timer {
+            overflow_listeners: Listeners::default(),
+            ..self.clone()
+        }
+    }
+
+    fn load(&mut self, state: Timer) -> Result<(), Box<dyn Error>> {
+        self.counter = state.counter;
+        self.last_and_result = state.last_and_result;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::game_boy::components::ppu::color_correction::ColorCorrection;
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::hardware_model::HardwareModel;
+use serde::{Deserialize, Serialize};
+
+/// Options which are fixed when constructing a [`GameBoy`](crate::game_boy::GameBoy)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameBoyConfig {
+    /// The hardware revision whose state after the boot ROM the Game Boy starts with
+    pub model: HardwareModel,
+    /// Pixel layout of the frame buffer returned by `get_frame_buffer`
+    pub frame_buffer_format: FrameBufferFormat,
+    /// Colors of the 4 shades, can still be changed later with `set_color_scheme`
+    pub color_scheme: ColorScheme,
+    /// Use the colors the CGB boot ROM picks for the game instead of `color_scheme`
+    pub colorize: bool,
+    /// Applied to the colors picked by `colorize`, can still be changed later with `set_color_correction`
+    pub color_correction: ColorCorrection,
+    /// Overrides the timer's internal counter at the entry point, e.g. to match another emulator or a measured console.
+    /// None uses the one of the model, see [`HardwareModel::get_div_counter`]
+    pub div_counter: Option<u16>,
+    /// Accuracy option: unmapped memory and disabled cartridge RAM read like on hardware instead of fixed values,
+    /// see [`MMU::set_open_bus`](crate::game_boy::components::mmu::MMU::set_open_bus)
+    pub open_bus: bool,
+}
+
+impl GameBoyConfig {
+    /// Also colorizes like the boot ROM of the model, which can be changed again with [`colorize`](Self::colorize)
+    pub fn model(mut self, model: HardwareModel) -> Self {
+        self.model = model;
+        self.colorize = model.colorizes_dmg_games();
+        self
+    }
+
+    pub fn frame_buffer_format(mut self, format: FrameBufferFormat) -> Self {
+        self.frame_buffer_format = format;
+        self
+    }
+
+    pub fn color_scheme(mut self, color_scheme: ColorScheme) -> Self {
+        self.color_scheme = color_scheme;
+        self
+    }
+
+    pub fn colorize(mut self, colorize: bool) -> Self {
+        self.colorize = colorize;
+        self
+    }
+
+    pub fn color_correction(mut self, color_correction: ColorCorrection) -> Self {
+        self.color_correction = color_correction;
+        self
+    }
+
+    pub fn div_counter(mut self, div_counter: u16) -> Self {
+        self.div_counter = Some(div_counter);
+        self
+    }
+
+    pub fn open_bus(mut self, open_bus: bool) -> Self {
+        self.open_bus = open_bus;
+        self
+    }
+
+    /// The timer's internal counter at the entry point
+    pub fn get_div_counter(&self) -> u16 {
+        self.div_counter
+            .unwrap_or_else(|| self.model.get_div_counter())
+    }
+}
@@ -0,0 +1,34 @@
+//! Running totals kept by the [`GameBoy`](crate::game_boy::GameBoy), so tracers, autosplitters,
+//! rewind and benchmarks don't each count cycles and frames on their own.
+
+use crate::game_boy::cycles::Cycles;
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Counters {
+    total_cycles: u64,
+    frame_count: u64,
+}
+
+impl Counters {
+    pub fn step(&mut self, cycles: Cycles, frame_finished: bool) {
+        self.total_cycles += cycles.as_t() as u64;
+        if frame_finished {
+            self.frame_count += 1;
+        }
+    }
+
+    pub fn get_total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+}
+
+/// The counters aren't part of the emulated state, Game Boys in the same state are equal regardless of them
+impl PartialEq for Counters {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
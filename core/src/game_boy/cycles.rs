@@ -0,0 +1,62 @@
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign, Sub};
+use serde::{Deserialize, Serialize};
+
+/// T-cycles (dots) per M-cycle on the DMG in normal speed
+pub const T_CYCLES_PER_M_CYCLE: u32 = 4;
+
+/// An amount of clock cycles, always stored as T-cycles.
+/// The CPU counts in M-cycles, the PPU and timer work with T-cycles, converting only happens here.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Cycles(u32);
+
+impl Cycles {
+    pub const ZERO: Cycles = Cycles(0);
+
+    pub const fn from_t(t_cycles: u32) -> Self {
+        Self(t_cycles)
+    }
+
+    pub const fn from_m(m_cycles: u32) -> Self {
+        Self(m_cycles * T_CYCLES_PER_M_CYCLE)
+    }
+
+    pub const fn as_t(&self) -> u32 {
+        self.0
+    }
+
+    /// Rounds down to full M-cycles
+    pub const fn as_m(&self) -> u32 {
+        self.0 / T_CYCLES_PER_M_CYCLE
+    }
+}
+
+impl Add for Cycles {
+    type Output = Cycles;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Cycles {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Cycles {
+    type Output = Cycles;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Display for Cycles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} T-cycles", self.0)
+    }
+}
@@ -0,0 +1,102 @@
+//! Non-fatal warnings about suspicious behavior of the running game, which the hardware silently tolerates.
+//! They usually point at a bug in a homebrew game or at an emulation issue.
+//! Every kind of warning is only reported once until the next reset, games tend to repeat their mistakes every frame.
+
+use crate::helpers::listeners::{ListenerId, Listeners};
+use crate::logging::Subsystem;
+use core::cell::Cell;
+use core::fmt::{Display, Formatter};
+use log::warn;
+
+/// The unusable region between OAM and the IO registers, https://gbdev.io/pandocs/Memory_Map.html#fea0feff-range
+const UNUSABLE_REGION: core::ops::RangeInclusive<u16> = 0xFEA0..=0xFEFF;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The cartridge has no memory bank controller, so the write has no effect at all
+    RomWriteWithoutMapper { address: u16, value: u8 },
+    /// The cartridge RAM has to be enabled by writing 0x0A to 0x0000-0x1FFF first
+    DisabledRamRead { address: u16 },
+    /// The last byte of the address space is IE, executing it usually means the program counter ran away
+    ExecutionAtFFFF,
+    /// The stack grew down from HRAM through the IO registers, or SP was never initialized
+    StackInUnusableRegion { sp: u16 },
+}
+
+impl Diagnostic {
+    /// The stack pointer is only reported while it points into the unusable region
+    pub fn check_stack_pointer(sp: u16) -> Option<Self> {
+        UNUSABLE_REGION
+            .contains(&sp)
+            .then_some(Self::StackInUnusableRegion { sp })
+    }
+
+    fn kind_bit(&self) -> u8 {
+        match self {
+            Self::RomWriteWithoutMapper { .. } => 0b0001,
+            Self::DisabledRamRead { .. } => 0b0010,
+            Self::ExecutionAtFFFF => 0b0100,
+            Self::StackInUnusableRegion { .. } => 0b1000,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::RomWriteWithoutMapper { address, value } => write!(
+                f,
+                "Write of 0x{value:02X} to ROM at 0x{address:04X}, but the cartridge has no mapper"
+            ),
+            Self::DisabledRamRead { address } => {
+                write!(f, "Read of disabled cartridge RAM at 0x{address:04X}")
+            }
+            Self::ExecutionAtFFFF => write!(f, "Execution reached 0xFFFF"),
+            Self::StackInUnusableRegion { sp } => write!(
+                f,
+                "Stack overflow into the unusable region, SP is 0x{sp:04X}"
+            ),
+        }
+    }
+}
+
+/// Reports every kind of [`Diagnostic`] once to its listeners and the log
+#[derive(Debug, Default, Clone)]
+pub struct Diagnostics {
+    /// One bit per kind of diagnostic. Reads report as well and only borrow the MMU immutably,
+    /// so this needs interior mutability.
+    reported: Cell<u8>,
+    listeners: Listeners<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn report(&self, diagnostic: Diagnostic) {
+        let bit = diagnostic.kind_bit();
+        if self.reported.get() & bit != 0 {
+            return;
+        }
+        self.reported.set(self.reported.get() | bit);
+        warn!(target: Subsystem::Mmu.target(), "{diagnostic}");
+        self.listeners.notify(&diagnostic);
+    }
+
+    /// Every kind of diagnostic is reported again
+    pub fn clear(&self) {
+        self.reported.set(0);
+    }
+
+    pub fn subscribe(&mut self, callback: impl FnMut(&Diagnostic) + Send + 'static) -> ListenerId {
+        self.listeners.subscribe(callback)
+    }
+
+    pub fn unsubscribe(&mut self, id: ListenerId) {
+        self.listeners.unsubscribe(id);
+    }
+}
+
+/// Not part of the emulated state
+impl PartialEq for Diagnostics {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
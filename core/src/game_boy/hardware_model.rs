@@ -0,0 +1,148 @@
+//! The hardware revisions whose state after the boot ROM can be emulated.
+//! There is no boot ROM, a Game Boy starts at 0x0100 with the registers the boot ROM of the model leaves behind.
+//! Only DMG games are supported, so the CGB and AGB are always in their DMG compatibility mode.
+//! https://gbdev.io/pandocs/Power_Up_Sequence.html#console-state-after-boot-rom-hand-off
+
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::types::CartridgeCGBFlag;
+use crate::game_boy::components::cpu::registers::{CPURegisters, CpuRegistersAccessTrait};
+use crate::game_boy::components::mmu::{
+    DIV_ADDRESS, DMA_ADDRESS, LY_ADDRESS, SC_ADDRESS, STAT_ADDRESS,
+};
+use crate::game_boy::components::ppu::colorization::{is_nintendo_game, title_checksum};
+use serde::{Deserialize, Serialize};
+
+const HEADER_CHECKSUM_ADDRESS: usize = 0x14D;
+const NR52_ADDRESS: u16 = 0xFF26;
+const ENTRY_POINT: u16 = 0x0100;
+const INITIAL_SP: u16 = 0xFFFE;
+/// The title checksums for which the CGB boot ROM leaves HL at 0x991A instead of 0x007C
+const CGB_SPECIAL_TITLE_CHECKSUMS: [u8; 2] = [0x43, 0x58];
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HardwareModel {
+    /// The first revision of the original Game Boy
+    #[default]
+    Dmg0,
+    /// The original Game Boy
+    Dmg,
+    /// The Game Boy Pocket
+    Mgb,
+    /// The Super Game Boy
+    Sgb,
+    /// The Super Game Boy 2
+    Sgb2,
+    /// The Game Boy Color
+    Cgb,
+    /// The Game Boy Advance, its boot ROM is the one of the CGB with one more INC B
+    Agb,
+}
+
+impl HardwareModel {
+    pub const ALL: [HardwareModel; 7] = [
+        HardwareModel::Dmg0,
+        HardwareModel::Dmg,
+        HardwareModel::Mgb,
+        HardwareModel::Sgb,
+        HardwareModel::Sgb2,
+        HardwareModel::Cgb,
+        HardwareModel::Agb,
+    ];
+
+    /// Whether the model runs games which support the CGB in CGB mode
+    pub fn supports_cgb_mode(&self) -> bool {
+        matches!(self, HardwareModel::Cgb | HardwareModel::Agb)
+    }
+
+    /// The model would run the game in CGB mode, which isn't emulated
+    pub fn runs_in_cgb_mode(&self, header: &CartridgeHeader) -> bool {
+        self.supports_cgb_mode() && header.cgb_flag != CartridgeCGBFlag::None
+    }
+
+    /// Whether 16 bit increments and decrements in the OAM range corrupt OAM during mode 2.
+    /// The bug isn't emulated yet, this tells which models it applies to.
+    pub fn has_oam_bug(&self) -> bool {
+        !self.supports_cgb_mode()
+    }
+
+    /// Whether the boot ROM colors DMG games, see [`colorize`](crate::game_boy::components::ppu::colorization::colorize)
+    pub fn colorizes_dmg_games(&self) -> bool {
+        self.supports_cgb_mode()
+    }
+
+    /// The CPU registers at the entry point, `rom` has to start at 0x0000 of ROM bank 0.
+    /// The DMG and MGB set the carry flags if the header checksum isn't 0,
+    /// the CGB and AGB leave the title checksum of Nintendo games in B.
+    pub fn get_cpu_registers(&self, rom: &[u8]) -> CPURegisters {
+        let header_checksum = rom.get(HEADER_CHECKSUM_ADDRESS).copied().unwrap_or(0);
+        let dmg_flags = if header_checksum == 0 { 0x80 } else { 0xB0 };
+        let cgb_b = if is_nintendo_game(rom) {
+            title_checksum(rom)
+        } else {
+            0
+        };
+        let cgb_hl = if CGB_SPECIAL_TITLE_CHECKSUMS.contains(&cgb_b) {
+            0x991A
+        } else {
+            0x007C
+        };
+        let (af, bc, de, hl) = match self {
+            HardwareModel::Dmg0 => (0x0100, 0xFF13, 0x00C1, 0x8403),
+            HardwareModel::Dmg => (0x0100 | dmg_flags, 0x0013, 0x00D8, 0x014D),
+            HardwareModel::Mgb => (0xFF00 | dmg_flags, 0x0013, 0x00D8, 0x014D),
+            HardwareModel::Sgb => (0x0100, 0x0014, 0x0000, 0xC060),
+            HardwareModel::Sgb2 => (0xFF00, 0x0014, 0x0000, 0xC060),
+            HardwareModel::Cgb => (0x1180, (cgb_b as u16) << 8, 0x0008, cgb_hl),
+            HardwareModel::Agb => {
+                // The flags are the ones of the final INC B, which leaves the carry alone
+                let b = cgb_b.wrapping_add(1);
+                let zero = if b == 0 { 0x80 } else { 0 };
+                let half_carry = if b & 0x0F == 0 { 0x20 } else { 0 };
+                (0x1100 | zero | half_carry, (b as u16) << 8, 0x0008, cgb_hl)
+            }
+        };
+        let mut registers = CPURegisters::default();
+        registers.set_af(af);
+        registers.set_bc(bc);
+        registers.set_de(de);
+        registers.set_hl(hl);
+        registers.set_sp(INITIAL_SP);
+        registers.set_pc(ENTRY_POINT);
+        registers
+    }
+
+    /// The timer's internal counter at the entry point, its upper byte is DIV.
+    /// It decides when DIV first increments and on which cycle TIMA first ticks.
+    /// Only the lower byte of the DMG and MGB is known, the other models start at the beginning of their DIV value.
+    /// Pan Docs don't know DIV for the SGB, CGB and AGB, which keep the DMG value.
+    pub fn get_div_counter(&self) -> u16 {
+        match self {
+            HardwareModel::Dmg0 => 0x1800,
+            HardwareModel::Dmg | HardwareModel::Mgb => 0xABCC,
+            _ => 0xAB00,
+        }
+    }
+
+    pub fn get_div(&self) -> u8 {
+        (self.get_div_counter() >> 8) as u8
+    }
+
+    /// The IO registers which differ between the models, all others are the same for every model.
+    /// STAT and LY of the SGB, CGB and AGB aren't known either, the DMG values are used.
+    pub fn get_io_registers(&self) -> [(u16, u8); 6] {
+        let (sc, stat, ly, dma, nr52) = match self {
+            HardwareModel::Dmg0 => (0x7E, 0x81, 0x91, 0xFF, 0xF1),
+            HardwareModel::Dmg | HardwareModel::Mgb => (0x7E, 0x85, 0x00, 0xFF, 0xF1),
+            HardwareModel::Sgb | HardwareModel::Sgb2 => (0x7E, 0x85, 0x00, 0xFF, 0xF0),
+            HardwareModel::Cgb | HardwareModel::Agb => (0x7F, 0x85, 0x00, 0x00, 0xF1),
+        };
+        [
+            (SC_ADDRESS, sc),
+            (DIV_ADDRESS, self.get_div()),
+            (NR52_ADDRESS, nr52),
+            (STAT_ADDRESS, stat),
+            (LY_ADDRESS, ly),
+            (DMA_ADDRESS, dma),
+        ]
+    }
+}
@@ -0,0 +1,103 @@
+//! Lag frames and input latency, measured by watching when the game reads the joypad register.
+//! A frame in which the game never read P1 is a lag frame: input given during it is only seen a frame later.
+
+use crate::game_boy::cycles::Cycles;
+
+#[derive(Debug, Default, Clone)]
+pub struct InputStats {
+    frames: u64,
+    lag_frames: u64,
+    /// Lag frames in a row up to the last finished frame
+    lag_streak: u64,
+    longest_lag_streak: u64,
+    polled_this_frame: bool,
+    /// Cycles since the buttons changed, until the game reads P1 for the first time afterwards
+    pending_input: Option<u32>,
+    input_changes: u64,
+    total_input_latency: u64,
+    max_input_latency: u32,
+}
+
+impl InputStats {
+    pub fn step(&mut self, cycles: Cycles, polled: bool) {
+        if let Some(pending) = &mut self.pending_input {
+            *pending = pending.saturating_add(cycles.as_t());
+        }
+        if !polled {
+            return;
+        }
+
+        self.polled_this_frame = true;
+        if let Some(latency) = self.pending_input.take() {
+            self.input_changes += 1;
+            self.total_input_latency += latency as u64;
+            self.max_input_latency = self.max_input_latency.max(latency);
+        }
+    }
+
+    /// Starts measuring the latency, unless an earlier change still waits for the game to read P1
+    pub fn record_input_change(&mut self) {
+        self.pending_input.get_or_insert(0);
+    }
+
+    /// Returns true if the finished frame was a lag frame
+    pub fn end_frame(&mut self) -> bool {
+        let lag = !self.polled_this_frame;
+        self.frames += 1;
+        self.polled_this_frame = false;
+        if lag {
+            self.lag_frames += 1;
+            self.lag_streak += 1;
+            self.longest_lag_streak = self.longest_lag_streak.max(self.lag_streak);
+        } else {
+            self.lag_streak = 0;
+        }
+        lag
+    }
+
+    pub fn get_frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn get_lag_frames(&self) -> u64 {
+        self.lag_frames
+    }
+
+    pub fn get_lag_streak(&self) -> u64 {
+        self.lag_streak
+    }
+
+    pub fn get_longest_lag_streak(&self) -> u64 {
+        self.longest_lag_streak
+    }
+
+    /// Input changes the game has read so far, the ones the latency statistics are based on
+    pub fn get_input_changes(&self) -> u64 {
+        self.input_changes
+    }
+
+    /// The average time from a button change to the next read of P1, None before the first change was read
+    pub fn get_average_input_latency(&self) -> Option<Cycles> {
+        if self.input_changes == 0 {
+            return None;
+        }
+        Some(Cycles::from_t(
+            (self.total_input_latency / self.input_changes) as u32,
+        ))
+    }
+
+    pub fn get_max_input_latency(&self) -> Option<Cycles> {
+        (self.input_changes > 0).then(|| Cycles::from_t(self.max_input_latency))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The statistics aren't part of the emulated state, Game Boys in the same state are equal regardless of them
+impl PartialEq for InputStats {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,160 @@
+//! Carries audio samples from the emulation thread to the audio callback of a frontend without locking,
+//! so the callback never waits for a frame to finish. There is one producer and one consumer,
+//! [`sample_buffer`] creates both ends.
+//! The counters tell why the audio crackles: underruns mean the emulation is too slow, overruns that it runs ahead.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Both ends of a buffer holding up to `capacity` samples
+pub fn sample_buffer(capacity: usize) -> (SampleProducer, SampleConsumer) {
+    let buffer = Arc::new(SampleBuffer {
+        samples: (0..capacity.max(1)).map(|_| AtomicU32::new(0)).collect(),
+        read_index: AtomicUsize::new(0),
+        write_index: AtomicUsize::new(0),
+        underruns: AtomicU64::new(0),
+        overruns: AtomicU64::new(0),
+    });
+    (
+        SampleProducer {
+            buffer: buffer.clone(),
+        },
+        SampleConsumer { buffer },
+    )
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SampleBufferStats {
+    /// Callbacks which ran out of samples and were filled up with silence
+    pub underruns: u64,
+    /// Samples which were dropped because the buffer was full
+    pub overruns: u64,
+}
+
+/// The indices only ever count up, the slot of an index is the index modulo the capacity.
+/// Samples are stored as the bits of their f32, so every slot is an atomic of its own.
+#[derive(Debug)]
+struct SampleBuffer {
+    samples: Box<[AtomicU32]>,
+    read_index: AtomicUsize,
+    write_index: AtomicUsize,
+    underruns: AtomicU64,
+    overruns: AtomicU64,
+}
+
+impl SampleBuffer {
+    fn capacity(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn slot(&self, index: usize) -> &AtomicU32 {
+        &self.samples[index % self.capacity()]
+    }
+
+    fn len(&self) -> usize {
+        let write_index = self.write_index.load(Ordering::Acquire);
+        write_index.wrapping_sub(self.read_index.load(Ordering::Acquire))
+    }
+
+    fn stats(&self) -> SampleBufferStats {
+        SampleBufferStats {
+            underruns: self.underruns.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The end the emulation writes to
+#[derive(Debug)]
+pub struct SampleProducer {
+    buffer: Arc<SampleBuffer>,
+}
+
+impl SampleProducer {
+    /// Returns false and counts an overrun if the buffer is full, the sample is dropped then
+    pub fn push(&mut self, sample: f32) -> bool {
+        let write_index = self.buffer.write_index.load(Ordering::Relaxed);
+        let read_index = self.buffer.read_index.load(Ordering::Acquire);
+        if write_index.wrapping_sub(read_index) >= self.buffer.capacity() {
+            self.buffer.overruns.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        self.buffer
+            .slot(write_index)
+            .store(sample.to_bits(), Ordering::Relaxed);
+        self.buffer
+            .write_index
+            .store(write_index.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    /// Returns how many of the samples fit, the rest are dropped as overruns
+    pub fn push_slice(&mut self, samples: &[f32]) -> usize {
+        samples.iter().filter(|sample| self.push(**sample)).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    pub fn get_stats(&self) -> SampleBufferStats {
+        self.buffer.stats()
+    }
+}
+
+/// The end the audio callback reads from
+#[derive(Debug)]
+pub struct SampleConsumer {
+    buffer: Arc<SampleBuffer>,
+}
+
+impl SampleConsumer {
+    pub fn pop(&mut self) -> Option<f32> {
+        let read_index = self.buffer.read_index.load(Ordering::Relaxed);
+        let write_index = self.buffer.write_index.load(Ordering::Acquire);
+        if read_index == write_index {
+            return None;
+        }
+        let bits = self.buffer.slot(read_index).load(Ordering::Relaxed);
+        self.buffer
+            .read_index
+            .store(read_index.wrapping_add(1), Ordering::Release);
+        Some(f32::from_bits(bits))
+    }
+
+    /// Fills the output of an audio callback. If the buffer runs out the rest is silence and an underrun is counted.
+    /// Returns how many samples were taken from the buffer.
+    pub fn fill(&mut self, output: &mut [f32]) -> usize {
+        for (filled, sample) in output.iter_mut().enumerate() {
+            match self.pop() {
+                Some(value) => *sample = value,
+                None => {
+                    output[filled..].fill(0.0);
+                    self.buffer.underruns.fetch_add(1, Ordering::Relaxed);
+                    return filled;
+                }
+            }
+        }
+        output.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_stats(&self) -> SampleBufferStats {
+        self.buffer.stats()
+    }
+}
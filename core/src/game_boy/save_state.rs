@@ -0,0 +1,137 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::dma::Dma;
+use crate::game_boy::components::mmu::save_state::MMUSaveState;
+use crate::game_boy::components::ppu::save_state::PPUSaveState;
+use crate::game_boy::components::serial::Serial;
+use crate::game_boy::components::timer::Timer;
+use crate::game_boy::thumbnail::Thumbnail;
+use alloc::boxed::Box;
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::io::{Error, ErrorKind};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Every zstd frame starts with this
+#[cfg(feature = "compression")]
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Size a single save state should stay below, so rewind buffers and netplay resyncs stay cheap.
+pub const SAVE_STATE_SIZE_BUDGET: usize = 128 * 1024;
+#[cfg(feature = "compression")]
+const COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameBoySaveState {
+    pub cartridge_header: CartridgeHeader,
+    pub cpu: CPU,
+    pub timer: Timer,
+    pub mmu_state: MMUSaveState,
+    pub ppu_state: PPUSaveState,
+    pub serial: Serial,
+    pub dma: Dma,
+    /// Only states saved to a slot get one, rewinds and netplay resyncs don't need it
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
+}
+
+impl GameBoySaveState {
+    /// Fails with a message naming both games if the state was saved with another cartridge.
+    /// Games are told apart like game profiles are, by their title and both header checksums.
+    pub fn check_cartridge(
+        &self,
+        header: &CartridgeHeader,
+    ) -> Result<(), Box<dyn core::error::Error>> {
+        let saved = &self.cartridge_header;
+        if saved.title == header.title
+            && saved.header_checksum == header.header_checksum
+            && saved.global_checksum == header.global_checksum
+        {
+            return Ok(());
+        }
+        Err(format!(
+            "The save state is for {} ({:02X}{:04X}), not {} ({:02X}{:04X})",
+            saved.title,
+            saved.header_checksum,
+            saved.global_checksum,
+            header.title,
+            header.header_checksum,
+            header.global_checksum
+        )
+        .into())
+    }
+
+    /// Loads a state stored in any of the formats, detected by its first bytes
+    #[cfg(feature = "std")]
+    pub fn load_any(path: &Path) -> std::io::Result<Self> {
+        let data = std::fs::read(path)?;
+        if data.first() == Some(&b'{') {
+            return Ok(serde_json::from_slice(&data)?);
+        }
+        #[cfg(feature = "compression")]
+        if data.starts_with(&ZSTD_MAGIC) {
+            return Self::from_compressed_bytes(&data);
+        }
+        Self::from_bytes(&data)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn store_json(&self, path: &Path) -> std::io::Result<()> {
+        let serialized = serde_json::to_string_pretty(&self)?;
+        std::fs::write(path, serialized)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load_json(path: &Path) -> std::io::Result<Self> {
+        let serialized = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&serialized)?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn store_binary(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load_binary(path: &Path) -> std::io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self) -> std::io::Result<Vec<u8>> {
+        bincode::serialize(&self).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        bincode::deserialize(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+    }
+
+    /// Binary encoding wrapped in a zstd frame, mostly empty VRAM and a flat frame buffer shrink a lot.
+    #[cfg(feature = "compression")]
+    pub fn to_compressed_bytes(&self) -> std::io::Result<Vec<u8>> {
+        zstd::encode_all(self.to_bytes()?.as_slice(), COMPRESSION_LEVEL)
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn from_compressed_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        Self::from_bytes(&zstd::decode_all(bytes)?)
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn store_compressed(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_compressed_bytes()?)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn load_compressed(path: &Path) -> std::io::Result<Self> {
+        Self::from_compressed_bytes(&std::fs::read(path)?)
+    }
+}
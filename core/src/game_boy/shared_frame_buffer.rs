@@ -0,0 +1,36 @@
+use crate::helpers::shared::{lock, ptr_eq, share, Shared, SharedGuard};
+use alloc::vec::Vec;
+
+/// A copy of the frame buffer that other threads can read, e.g. the render thread of a frontend.
+/// The Game Boy writes every finished frame into it once, readers lock it instead of copying the frame again.
+/// The emulation waits while a reader holds the lock at the end of a frame, so readers should only hold it briefly.
+#[derive(Debug, Clone)]
+pub struct SharedFrameBuffer {
+    frame: Shared<Vec<u8>>,
+}
+
+impl SharedFrameBuffer {
+    pub fn new(frame: &[u8]) -> Self {
+        Self {
+            frame: share(frame.to_vec()),
+        }
+    }
+
+    /// The last finished frame in the format of the Game Boy's frame buffer
+    pub fn lock(&self) -> SharedGuard<'_, Vec<u8>> {
+        lock(&self.frame)
+    }
+
+    pub fn update(&self, frame: &[u8]) {
+        let mut shared = self.lock();
+        shared.clear();
+        shared.extend_from_slice(frame);
+    }
+}
+
+/// Handles are equal if they share the same buffer
+impl PartialEq for SharedFrameBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        ptr_eq(&self.frame, &other.frame)
+    }
+}
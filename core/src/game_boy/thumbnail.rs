@@ -0,0 +1,48 @@
+use crate::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Thumbnails are the screen downscaled by 2, so 4 of them fill the screen
+pub const THUMBNAIL_WIDTH: usize = SCREEN_WIDTH / 2;
+pub const THUMBNAIL_HEIGHT: usize = SCREEN_HEIGHT / 2;
+
+/// A small screenshot stored with a save state, so save slots can be told apart at a glance.
+/// RGB without alpha, row by row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnail {
+    rgb: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// Every thumbnail pixel is the average of 2x2 pixels of the RGBA8888 frame
+    pub fn from_rgba_frame(frame: &[u8]) -> Self {
+        let mut rgb = Vec::with_capacity(THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3);
+        for y in 0..THUMBNAIL_HEIGHT {
+            for x in 0..THUMBNAIL_WIDTH {
+                for channel in 0..3 {
+                    let sum: u32 = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                        .iter()
+                        .map(|(dx, dy)| {
+                            let index = ((y * 2 + dy) * SCREEN_WIDTH + x * 2 + dx) * 4 + channel;
+                            frame[index] as u32
+                        })
+                        .sum();
+                    rgb.push((sum / 4) as u8);
+                }
+            }
+        }
+        Self { rgb }
+    }
+
+    /// Black if the position is outside of the thumbnail or the stored data is too short
+    pub fn get_pixel(&self, x: usize, y: usize) -> [u8; 3] {
+        if x >= THUMBNAIL_WIDTH || y >= THUMBNAIL_HEIGHT {
+            return [0; 3];
+        }
+        let index = (y * THUMBNAIL_WIDTH + x) * 3;
+        match self.rgb.get(index..index + 3) {
+            Some(pixel) => [pixel[0], pixel[1], pixel[2]],
+            None => [0; 3],
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bit_operations;
+pub mod graphics;
+pub mod listeners;
+pub mod shared;
@@ -114,7 +114,7 @@ pub fn sub_carry_u8(a: u8, b: u8, carry: bool) -> (u8, bool, bool) {
 }
 
 /// Rotates the value left by 1, returning (result, carry)
-/// ```
+/// ```text
 /// ┏━ Carry ━┓   ┏━━━━━━ u8 ━━━━━━━┓
 /// ┃    C   ←╂─┬─╂─ b7 ← ... ← b0 ←╂─┐
 /// ┗━━━━━━━━━┛ │ ┗━━━━━━━━━━━━━━━━━┛ │
@@ -127,7 +127,7 @@ pub fn rotate_left_get_carry_u8(value: u8) -> (u8, bool) {
 }
 
 /// Rotates the value right by 1, returning (result, carry)
-/// ```
+/// ```text
 ///   ┏━━━━━━━ u8 ━━━━━━┓   ┏━ Carry ━┓
 /// ┌─╂→ b7 → ... → b0 ─╂─┬─╂→   C    ┃
 /// │ ┗━━━━━━━━━━━━━━━━━┛ │ ┗━━━━━━━━━┛
@@ -140,7 +140,7 @@ pub fn rotate_right_get_carry_u8(value: u8) -> (u8, bool) {
 }
 
 /// Rotates the value right by 1 THROUGH the given carry, returning (result, new_carry)
-/// ```
+/// ```text
 ///   ┏━━━━━━━ u8 ━━━━━━┓ ┏━ Carry ━┓
 /// ┌─╂→ b7 → ... → b0 ─╂─╂→   C   ─╂─┐
 /// │ ┗━━━━━━━━━━━━━━━━━┛ ┗━━━━━━━━━┛ │
@@ -153,7 +153,7 @@ pub fn rotate_right_through_carry_u8(value: u8, carry: bool) -> (u8, bool) {
 }
 
 /// Rotates the value left by 1 THROUGH the given carry, returning (result, new_carry)
-/// ```
+/// ```text
 ///   ┏━ Carry ━┓ ┏━━━━━━ u8 ━━━━━━━┓
 /// ┌─╂─   C   ←╂─╂─ b7 ← ... ← b0 ←╂─┐
 /// │ ┗━━━━━━━━━┛ ┗━━━━━━━━━━━━━━━━━┛ │
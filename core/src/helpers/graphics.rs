@@ -0,0 +1,65 @@
+//! Decoding of the 2bpp tile format, shared by the PPU, the VRAM viewers and the camera.
+//! A tile is 8x8 pixels in 16 bytes. Every row is a byte with the low bits of its color IDs followed by
+//! a byte with the high bits, the leftmost pixel is in bit 7.
+//! https://gbdev.io/pandocs/Tile_Data.html
+
+pub const TILE_SIZE: usize = 8;
+pub const TILE_BYTES: usize = 16;
+
+/// The color ID (0-3) of column x (0 is the leftmost) of a tile row
+pub fn decode_pixel(low: u8, high: u8, x: u8) -> u8 {
+    let bit_index = 7 - (x & 7);
+    (((high >> bit_index) & 1) << 1) | ((low >> bit_index) & 1)
+}
+
+/// The bits of every byte spread out to one byte per pixel, left to right.
+/// Decoding a row with it is two lookups instead of 8 shifts per pixel, the PPU does it for every tile of every line.
+const SPREAD_BITS: [[u8; TILE_SIZE]; 256] = {
+    let mut table = [[0; TILE_SIZE]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut x = 0;
+        while x < TILE_SIZE {
+            table[byte][x] = ((byte >> (7 - x)) & 1) as u8;
+            x += 1;
+        }
+        byte += 1;
+    }
+    table
+};
+
+/// The color IDs of a tile row, left to right
+pub fn decode_row(low: u8, high: u8) -> [u8; TILE_SIZE] {
+    let low = &SPREAD_BITS[low as usize];
+    let high = &SPREAD_BITS[high as usize];
+    core::array::from_fn(|x| (high[x] << 1) | low[x])
+}
+
+/// The low and high byte of a row with the given color IDs, only the lower 2 bits of each ID are used
+pub fn encode_row(color_ids: [u8; TILE_SIZE]) -> [u8; 2] {
+    color_ids
+        .iter()
+        .enumerate()
+        .fold([0, 0], |[low, high], (x, &color_id)| {
+            let bit_index = 7 - x;
+            [
+                low | (color_id & 1) << bit_index,
+                high | ((color_id >> 1) & 1) << bit_index,
+            ]
+        })
+}
+
+/// The color IDs of row y (0 is the top) of a tile, which has to be at least 2 * (y + 1) bytes long
+pub fn get_tile_row(tile: &[u8], y: usize) -> [u8; TILE_SIZE] {
+    decode_row(tile[y * 2], tile[y * 2 + 1])
+}
+
+/// The color IDs of all 8 rows of a tile, top to bottom
+pub fn decode_tile(tile: &[u8; TILE_BYTES]) -> [[u8; TILE_SIZE]; TILE_SIZE] {
+    core::array::from_fn(|y| get_tile_row(tile, y))
+}
+
+/// The shade (0 is white, 3 is black) which a palette register like BGP assigns to a color ID
+pub fn apply_palette(palette: u8, color_id: u8) -> u8 {
+    (palette >> ((color_id & 0b11) * 2)) & 0b11
+}
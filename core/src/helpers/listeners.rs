@@ -0,0 +1,69 @@
+use crate::helpers::shared::{share, try_lock, Shared};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+
+pub type ListenerCallback<E> = Shared<dyn FnMut(&E) + Send>;
+
+/// Handle returned when subscribing, used to unsubscribe again
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ListenerId(usize);
+
+/// A list of callbacks which get notified about events of a component.
+/// Listeners are not part of the emulated state: they are skipped when serializing
+/// and ignored when comparing components.
+pub struct Listeners<E> {
+    callbacks: Vec<(ListenerId, ListenerCallback<E>)>,
+    next_id: usize,
+}
+
+impl<E> Listeners<E> {
+    pub fn subscribe(&mut self, callback: impl FnMut(&E) + Send + 'static) -> ListenerId {
+        let id = ListenerId(self.next_id);
+        self.next_id += 1;
+        self.callbacks.push((id, share(callback)));
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: ListenerId) {
+        self.callbacks.retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    pub fn notify(&self, event: &E) {
+        for (_, callback) in &self.callbacks {
+            if let Some(mut callback) = try_lock(callback) {
+                callback(event);
+            }
+        }
+    }
+}
+
+impl<E> Default for Listeners<E> {
+    fn default() -> Self {
+        Self {
+            callbacks: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+/// Clones share the same callbacks
+impl<E> Clone for Listeners<E> {
+    fn clone(&self) -> Self {
+        Self {
+            callbacks: self.callbacks.clone(),
+            next_id: self.next_id,
+        }
+    }
+}
+
+impl<E> Debug for Listeners<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Listeners({})", self.callbacks.len())
+    }
+}
+
+impl<E> PartialEq for Listeners<E> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
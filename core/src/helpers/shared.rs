@@ -0,0 +1,57 @@
+//! State shared between clones of a Game Boy and the frontend, like listeners, IO hooks and the link cable.
+//! With std it is an `Arc<Mutex>`, so the Game Boy can move to other threads. Without std it is an `Rc<RefCell>`,
+//! which also works on targets without atomic compare and swap like the RP2040.
+
+#[cfg(feature = "std")]
+pub type Shared<T> = std::sync::Arc<std::sync::Mutex<T>>;
+#[cfg(feature = "std")]
+pub type SharedGuard<'a, T> = std::sync::MutexGuard<'a, T>;
+
+#[cfg(not(feature = "std"))]
+pub type Shared<T> = alloc::rc::Rc<core::cell::RefCell<T>>;
+#[cfg(not(feature = "std"))]
+pub type SharedGuard<'a, T> = core::cell::RefMut<'a, T>;
+
+#[cfg(feature = "std")]
+pub fn share<T>(value: T) -> Shared<T> {
+    std::sync::Arc::new(std::sync::Mutex::new(value))
+}
+
+#[cfg(not(feature = "std"))]
+pub fn share<T>(value: T) -> Shared<T> {
+    alloc::rc::Rc::new(core::cell::RefCell::new(value))
+}
+
+/// None if the value is poisoned by a panic of a previous holder, or already borrowed without std
+#[cfg(feature = "std")]
+pub fn try_lock<T: ?Sized>(shared: &Shared<T>) -> Option<SharedGuard<'_, T>> {
+    shared.lock().ok()
+}
+
+#[cfg(not(feature = "std"))]
+pub fn try_lock<T: ?Sized>(shared: &Shared<T>) -> Option<SharedGuard<'_, T>> {
+    shared.try_borrow_mut().ok()
+}
+
+/// Ignores poisoning, the shared values stay usable after a panic of a previous holder
+#[cfg(feature = "std")]
+pub fn lock<T: ?Sized>(shared: &Shared<T>) -> SharedGuard<'_, T> {
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(not(feature = "std"))]
+pub fn lock<T: ?Sized>(shared: &Shared<T>) -> SharedGuard<'_, T> {
+    shared.borrow_mut()
+}
+
+#[cfg(feature = "std")]
+pub fn ptr_eq<T: ?Sized>(a: &Shared<T>, b: &Shared<T>) -> bool {
+    std::sync::Arc::ptr_eq(a, b)
+}
+
+#[cfg(not(feature = "std"))]
+pub fn ptr_eq<T: ?Sized>(a: &Shared<T>, b: &Shared<T>) -> bool {
+    alloc::rc::Rc::ptr_eq(a, b)
+}
@@ -0,0 +1,159 @@
+use crate::enums::button::{Button, Buttons};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+pub mod movie;
+pub mod replay;
+
+/// Turbo buttons are pressed for this many frames and then released for as long, 15 presses per second
+pub const DEFAULT_TURBO_INTERVAL: u8 = 2;
+
+/// A recorded sequence of inputs, one entry per frame
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputMacro {
+    frames: Vec<Buttons>,
+}
+
+impl InputMacro {
+    pub fn new(frames: Vec<Buttons>) -> Self {
+        Self { frames }
+    }
+
+    pub fn get_frames(&self) -> &[Buttons] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Sits between a frontend and the emulator and turns the buttons the player holds into
+/// the buttons the game sees, applying turbo and macros. Call [`InputLayer::next_frame`] once per frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputLayer {
+    turbo_buttons: Buttons,
+    turbo_interval: u8,
+    /// Frames the turbo buttons have been held, so every turbo press starts pressed
+    turbo_counter: u32,
+    macros: BTreeMap<u8, InputMacro>,
+    recording: Option<(u8, Vec<Buttons>)>,
+    playback: Option<(InputMacro, usize)>,
+}
+
+impl InputLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The buttons the game sees this frame
+    pub fn next_frame(&mut self, held: Buttons) -> Buttons {
+        let turbo_held = held.bits() & self.turbo_buttons.bits();
+        let mut output = held.bits() & !turbo_held;
+
+        if turbo_held != 0 {
+            if (self.turbo_counter / self.turbo_interval as u32).is_multiple_of(2) {
+                output |= turbo_held;
+            }
+            self.turbo_counter = self.turbo_counter.wrapping_add(1);
+        } else {
+            self.turbo_counter = 0;
+        }
+
+        if let Some((input_macro, index)) = &mut self.playback {
+            output |= input_macro.frames[*index].bits();
+            *index += 1;
+            if *index >= input_macro.len() {
+                self.playback = None;
+            }
+        }
+
+        let output = Buttons::from_bits(output);
+        if let Some((_, frames)) = &mut self.recording {
+            frames.push(output);
+        }
+        output
+    }
+
+    pub fn set_turbo(&mut self, button: Button, enabled: bool) {
+        self.turbo_buttons.set(button, enabled);
+    }
+
+    pub fn is_turbo(&self, button: Button) -> bool {
+        self.turbo_buttons.is_pressed(button)
+    }
+
+    /// Frames per turbo press and per release, at least 1
+    pub fn set_turbo_interval(&mut self, frames: u8) {
+        self.turbo_interval = frames.max(1);
+    }
+
+    pub fn get_turbo_interval(&self) -> u8 {
+        self.turbo_interval
+    }
+
+    /// Records the output of every following frame into the given macro slot, until recording is stopped
+    pub fn start_recording(&mut self, slot: u8) {
+        self.recording = Some((slot, Vec::new()));
+    }
+
+    /// Stores the recording in its slot and returns the slot, None if nothing was recorded
+    pub fn stop_recording(&mut self) -> Option<u8> {
+        let (slot, frames) = self.recording.take()?;
+        if frames.is_empty() {
+            return None;
+        }
+        self.macros.insert(slot, InputMacro::new(frames));
+        Some(slot)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn set_macro(&mut self, slot: u8, input_macro: InputMacro) {
+        self.macros.insert(slot, input_macro);
+    }
+
+    pub fn get_macro(&self, slot: u8) -> Option<&InputMacro> {
+        self.macros.get(&slot)
+    }
+
+    /// Replays the macro on top of the held buttons, starting with the next frame.
+    /// Returns false if the slot is empty.
+    pub fn play_macro(&mut self, slot: u8) -> bool {
+        match self.macros.get(&slot) {
+            Some(input_macro) if !input_macro.is_empty() => {
+                self.playback = Some((input_macro.clone(), 0));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+}
+
+impl Default for InputLayer {
+    fn default() -> Self {
+        Self {
+            turbo_buttons: Buttons::NONE,
+            turbo_interval: DEFAULT_TURBO_INTERVAL,
+            turbo_counter: 0,
+            macros: BTreeMap::new(),
+            recording: None,
+            playback: None,
+        }
+    }
+}
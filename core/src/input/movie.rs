@@ -0,0 +1,163 @@
+//! Importers for movies of other emulators, converting their inputs to an [`InputMacro`] with one entry per frame.
+//! Only movies starting from power on can be converted, the recording starts with the first frame.
+
+use crate::enums::button::{Button, Buttons};
+use crate::input::InputMacro;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// https://tasvideos.org/EmulatorResources/VBA/VBM
+const VBM_SIGNATURE: &[u8; 4] = b"VBM\x1A";
+const VBM_HEADER_SIZE: usize = 0x40;
+const VBM_FRAME_COUNT_OFFSET: usize = 0x0C;
+const VBM_START_FLAGS_OFFSET: usize = 0x14;
+const VBM_CONTROLLER_FLAGS_OFFSET: usize = 0x15;
+const VBM_CONTROLLER_DATA_OFFSET: usize = 0x3C;
+/// Start flags: the movie starts from a savestate or from existing cartridge RAM
+const VBM_START_FROM_SAVESTATE: u8 = 0b01;
+const VBM_START_FROM_SRAM: u8 = 0b10;
+/// The low byte of every controller word uses the same bits as [`Buttons`], bit 10 is a reset
+const VBM_RESET_MASK: u16 = 0b0000_0100_0000_0000;
+
+/// Zip archives start with this, BizHawk stores its BK2 movies in them
+const ZIP_SIGNATURE: &[u8; 2] = b"PK";
+const BK2_LOG_KEY_PREFIX: &str = "LogKey:";
+const BK2_POWER_KEY: &str = "Power";
+
+/// Reads a movie file, see [`import_movie_bytes`]
+#[cfg(feature = "std")]
+pub fn import_movie(path: &Path) -> Result<InputMacro, Box<dyn Error>> {
+    import_movie_bytes(std::fs::read(path)?)
+}
+
+/// Detects the format from the content, either a VBM movie or the `Input Log.txt` of a BK2 movie
+pub fn import_movie_bytes(data: Vec<u8>) -> Result<InputMacro, Box<dyn Error>> {
+    if data.starts_with(VBM_SIGNATURE) {
+        return import_vbm(&data);
+    }
+    if data.starts_with(ZIP_SIGNATURE) {
+        return Err(
+            "BK2 movies are zip archives, extract the 'Input Log.txt' and import that".into(),
+        );
+    }
+    import_bk2_input_log(&String::from_utf8(data)?)
+}
+
+/// Imports the inputs of the first controller of a VisualBoyAdvance movie
+pub fn import_vbm(data: &[u8]) -> Result<InputMacro, Box<dyn Error>> {
+    if data.len() < VBM_HEADER_SIZE || !data.starts_with(VBM_SIGNATURE) {
+        return Err("Not a VBM movie".into());
+    }
+
+    let start_flags = data[VBM_START_FLAGS_OFFSET];
+    if start_flags & (VBM_START_FROM_SAVESTATE | VBM_START_FROM_SRAM) != 0 {
+        return Err("VBM movies starting from a savestate or SRAM are not supported".into());
+    }
+
+    // Every frame holds one word per enabled controller
+    let controllers = (data[VBM_CONTROLLER_FLAGS_OFFSET] & 0x0F).count_ones() as usize;
+    if controllers == 0 {
+        return Err("The VBM movie has no controller enabled".into());
+    }
+
+    let frame_count = read_u32_le(data, VBM_FRAME_COUNT_OFFSET) as usize;
+    let start = read_u32_le(data, VBM_CONTROLLER_DATA_OFFSET) as usize;
+    let frame_size = controllers * 2;
+    let end = frame_count
+        .checked_mul(frame_size)
+        .and_then(|size| size.checked_add(start))
+        .filter(|end| *end <= data.len())
+        .ok_or("The VBM movie is shorter than its frame count")?;
+
+    let mut frames = Vec::with_capacity(frame_count);
+    for (frame, input) in data[start..end].chunks_exact(frame_size).enumerate() {
+        let word = u16::from_le_bytes([input[0], input[1]]);
+        if word & VBM_RESET_MASK != 0 {
+            return Err(format!("The VBM movie resets the game in frame {frame}").into());
+        }
+        frames.push(Buttons::from_bits(word as u8));
+    }
+    Ok(InputMacro::new(frames))
+}
+
+/// Imports the `Input Log.txt` of a BizHawk movie.
+/// The `LogKey` line names the buttons, every frame is a line with one character per button, `.` if it is released.
+pub fn import_bk2_input_log(log: &str) -> Result<InputMacro, Box<dyn Error>> {
+    let mut keys: Option<Vec<Option<Button>>> = None;
+    let mut frames = Vec::new();
+
+    for line in log.lines().map(str::trim) {
+        if let Some(log_key) = line.strip_prefix(BK2_LOG_KEY_PREFIX) {
+            keys = Some(parse_bk2_log_key(log_key)?);
+            continue;
+        }
+        if !line.starts_with('|') {
+            continue;
+        }
+
+        let keys = keys.as_ref().ok_or("The input log has no LogKey line")?;
+        let states: Vec<char> = line.chars().filter(|c| *c != '|').collect();
+        if states.len() != keys.len() {
+            return Err(format!(
+                "Frame {} has {} inputs, the LogKey names {}",
+                frames.len(),
+                states.len(),
+                keys.len()
+            )
+            .into());
+        }
+
+        let mut buttons = Buttons::NONE;
+        for (key, state) in keys.iter().zip(states) {
+            match key {
+                Some(button) => buttons.set(*button, state != '.'),
+                None if state != '.' => {
+                    return Err(
+                        format!("The movie resets the game in frame {}", frames.len()).into(),
+                    )
+                }
+                None => {}
+            }
+        }
+        frames.push(buttons);
+    }
+
+    if keys.is_none() {
+        return Err("The input log has no LogKey line".into());
+    }
+    Ok(InputMacro::new(frames))
+}
+
+/// The buttons in the order of the frame lines, None is the power button
+fn parse_bk2_log_key(log_key: &str) -> Result<Vec<Option<Button>>, Box<dyn Error>> {
+    log_key
+        .split(['#', '|'])
+        .filter(|key| !key.is_empty())
+        .map(|key| match key.strip_prefix("P1 ").unwrap_or(key) {
+            "Up" => Ok(Some(Button::Up)),
+            "Down" => Ok(Some(Button::Down)),
+            "Left" => Ok(Some(Button::Left)),
+            "Right" => Ok(Some(Button::Right)),
+            "Start" => Ok(Some(Button::Start)),
+            "Select" => Ok(Some(Button::Select)),
+            "B" => Ok(Some(Button::B)),
+            "A" => Ok(Some(Button::A)),
+            BK2_POWER_KEY => Ok(None),
+            other => Err(format!("Unknown button '{other}' in the LogKey").into()),
+        })
+        .collect()
+}
+
+fn read_u32_le(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
@@ -0,0 +1,148 @@
+//! Plays an [`InputMacro`] on a [`GameBoy`] frame by frame and checks that the video output is the same as in an earlier run.
+//! Every finished frame is reduced to a CRC-32 of its frame buffer, a mismatch pinpoints the first frame that looks different,
+//! even when the memory at the end of the replay still matches.
+//! The checksums only depend on the frames drawn during the replay, so a replay starting from a save state is verified
+//! just like one starting at power on, as long as both runs start from the same state and use the same frame buffer format.
+
+use crate::game_boy::GameBoy;
+use crate::input::InputMacro;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::error::Error;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Reversed polynomial of the CRC-32 used by zip and PNG
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// CRC-32 (ISO-HDLC) of a frame buffer
+pub fn frame_checksum(frame_buffer: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in frame_buffer {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (CRC32_POLYNOMIAL & mask);
+        }
+    }
+    !crc
+}
+
+/// One checksum per replayed frame, stored next to the replay to verify later runs against
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameChecksums {
+    checksums: Vec<u32>,
+}
+
+impl FrameChecksums {
+    pub fn new(checksums: Vec<u32>) -> Self {
+        Self { checksums }
+    }
+
+    pub fn get_checksums(&self) -> &[u32] {
+        &self.checksums
+    }
+
+    pub fn len(&self) -> usize {
+        self.checksums.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checksums.is_empty()
+    }
+
+    /// The first frame whose checksum differs, frames missing in either list aren't compared
+    pub fn first_divergence(&self, actual: &FrameChecksums) -> Option<FrameDivergence> {
+        self.checksums
+            .iter()
+            .zip(&actual.checksums)
+            .position(|(expected, actual)| expected != actual)
+            .map(|frame| FrameDivergence {
+                frame,
+                expected: self.checksums[frame],
+                actual: actual.checksums[frame],
+            })
+    }
+
+    #[cfg(feature = "std")]
+    pub fn store(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+}
+
+/// The first replayed frame whose video output differs from the reference, counted from 0
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FrameDivergence {
+    pub frame: usize,
+    pub expected: u32,
+    pub actual: u32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum FrameVerification {
+    /// Only plays the inputs
+    #[default]
+    Off,
+    /// Records the checksum of every frame
+    Record,
+    /// Records the checksums and compares them to the ones of an earlier run
+    Compare(FrameChecksums),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayResult {
+    pub frames: usize,
+    /// None if the verification was off
+    pub checksums: Option<FrameChecksums>,
+    pub divergence: Option<FrameDivergence>,
+}
+
+impl ReplayResult {
+    /// A single line, naming the first diverging frame if there is one
+    pub fn summary(&self) -> String {
+        match self.divergence {
+            Some(divergence) => format!(
+                "Video output diverged at frame {}: expected checksum {:08X}, got {:08X}",
+                divergence.frame, divergence.expected, divergence.actual
+            ),
+            None => format!("Replayed {} frames", self.frames),
+        }
+    }
+}
+
+/// Runs one frame per entry of the macro with exactly its buttons pressed
+pub fn play_replay(
+    game_boy: &mut GameBoy,
+    replay: &InputMacro,
+    verification: &FrameVerification,
+) -> ReplayResult {
+    let mut checksums = Vec::new();
+    for buttons in replay.get_frames() {
+        game_boy.set_buttons(*buttons);
+        game_boy.finish_frame();
+        if *verification != FrameVerification::Off {
+            checksums.push(frame_checksum(game_boy.get_frame_buffer()));
+        }
+    }
+
+    let checksums = FrameChecksums::new(checksums);
+    let divergence = match verification {
+        FrameVerification::Compare(reference) => reference.first_divergence(&checksums),
+        _ => None,
+    };
+    ReplayResult {
+        frames: replay.len(),
+        checksums: (*verification != FrameVerification::Off).then_some(checksums),
+        divergence,
+    }
+}
@@ -1,8 +1,14 @@
 use crate::enums::parameter_groups::{JumpCondition, R16Mem, R16Stack, R16, R8};
 use crate::game_boy::components::cpu::PREFIX_INSTRUCTION_BYTE;
-use std::error::Error;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
 
-#[derive(Debug, Default, Clone, PartialEq)]
+pub mod metadata;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub enum Instruction {
     /// Add value from the specified register to the HL register
     AddHLR16(R16),
@@ -113,7 +119,7 @@ pub enum Instruction {
     /// Return from a previous function call and enable interrupts
     ReturnEnableInterrupts,
     /// Rotate register A left by 1 bit, through the carry flag
-    /// ```
+    /// ```text
     ///   ┏━ Flags ━┓ ┏━━━━━━━ A ━━━━━━━┓
     /// ┌─╂─   C   ←╂─╂─ b7 ← ... ← b0 ←╂─┐
     /// │ ┗━━━━━━━━━┛ ┗━━━━━━━━━━━━━━━━━┛ │
@@ -121,7 +127,7 @@ pub enum Instruction {
     /// ```
     RotateLeftA,
     /// Rotate register A right by 1 bit, through the carry flag
-    /// ```
+    /// ```text
     ///   ┏━━━━━━━ A ━━━━━━━┓ ┏━ Flags ━┓
     /// ┌─╂→ b7 → ... → b0 ─╂─╂→   C   ─╂─┐
     /// │ ┗━━━━━━━━━━━━━━━━━┛ ┗━━━━━━━━━┛ │
@@ -129,7 +135,7 @@ pub enum Instruction {
     /// ```
     RotateRightA,
     /// Rotate register A left by 1 bit
-    /// ```
+    /// ```text
     /// ┏━ Flags ━┓   ┏━━━━━━━ A ━━━━━━━┓
     /// ┃    C   ←╂─┬─╂─ b7 ← ... ← b0 ←╂─┐
     /// ┗━━━━━━━━━┛ │ ┗━━━━━━━━━━━━━━━━━┛ │
@@ -137,7 +143,7 @@ pub enum Instruction {
     /// ```
     RotateLeftCircularA,
     /// Rotate register A right by 1 bit
-    /// ```
+    /// ```text
     ///   ┏━━━━━━━ A ━━━━━━━┓   ┏━ Flags ━┓
     /// ┌─╂→ b7 → ... → b0 ─╂─┬─╂→   C    ┃
     /// │ ┗━━━━━━━━━━━━━━━━━┛ │ ┗━━━━━━━━━┛
@@ -183,6 +189,28 @@ pub enum Instruction {
     ShiftRightLogicallyR8(R8),
 }
 
+/// Every unprefixed opcode decoded at compile time, None for the illegal ones
+static UNPREFIXED_INSTRUCTIONS: [Option<Instruction>; 256] = {
+    let mut table = [None; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = Instruction::decode_unprefixed(byte as u8);
+        byte += 1;
+    }
+    table
+};
+
+/// Every opcode following the 0xCB prefix decoded at compile time
+static PREFIXED_INSTRUCTIONS: [Instruction; 256] = {
+    let mut table = [Instruction::Nop; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = Instruction::decode_prefixed(byte as u8);
+        byte += 1;
+    }
+    table
+};
+
 impl Instruction {
     pub fn from_byte(byte: u8, prefixed: bool) -> Result<Self, Box<dyn Error>> {
         if prefixed {
@@ -192,258 +220,277 @@ impl Instruction {
         }
     }
 
+    /// Like [`from_byte`](Self::from_byte) without building an error for illegal opcodes,
+    /// only a table lookup since the CPU decodes an instruction on every step
+    pub fn decode(byte: u8, prefixed: bool) -> Option<Self> {
+        if prefixed {
+            Some(PREFIXED_INSTRUCTIONS[byte as usize])
+        } else {
+            UNPREFIXED_INSTRUCTIONS[byte as usize]
+        }
+    }
+
     pub fn from_byte_unprefixed(byte: u8) -> Result<Self, Box<dyn Error>> {
+        UNPREFIXED_INSTRUCTIONS[byte as usize]
+            .ok_or_else(|| format!("Illegal unprefixed instruction byte: {:02X}", byte).into())
+    }
+
+    pub fn from_byte_prefixed(byte: u8) -> Self {
+        PREFIXED_INSTRUCTIONS[byte as usize]
+    }
+
+    const fn decode_unprefixed(byte: u8) -> Option<Self> {
         match byte {
-            0b0000_0000 => Ok(Self::Nop),                                 // 0x00
-            0b0000_0001 => Ok(Self::LoadR16Imm16(R16::BC)),               // 0x01
-            0b0000_0010 => Ok(Self::LoadR16A(R16Mem::BC)),                // 0x02
-            0b0000_0011 => Ok(Self::IncR16(R16::BC)),                     // 0x03
-            0b0000_0100 => Ok(Self::IncR8(R8::B)),                        // 0x04
-            0b0000_0101 => Ok(Self::DecR8(R8::B)),                        // 0x05
-            0b0000_0110 => Ok(Self::LoadR8Imm8(R8::B)),                   // 0x06
-            0b0000_0111 => Ok(Self::RotateLeftCircularA),                 // 0x07
-            0b0000_1000 => Ok(Self::LoadImm16SP),                         // 0x08
-            0b0000_1001 => Ok(Self::AddHLR16(R16::BC)),                   // 0x09
-            0b0000_1010 => Ok(Self::LoadAR16(R16Mem::BC)),                // 0x0A
-            0b0000_1011 => Ok(Self::DecR16(R16::BC)),                     // 0x0B
-            0b0000_1100 => Ok(Self::IncR8(R8::C)),                        // 0x0C
-            0b0000_1101 => Ok(Self::DecR8(R8::C)),                        // 0x0D
-            0b0000_1110 => Ok(Self::LoadR8Imm8(R8::C)),                   // 0x0E
-            0b0000_1111 => Ok(Self::RotateRightCircularA),                // 0x0F
-            0b0001_0000 => Ok(Self::Nop),                                 // 0x10 ToDo: STOP
-            0b0001_0001 => Ok(Self::LoadR16Imm16(R16::DE)),               // 0x11
-            0b0001_0010 => Ok(Self::LoadR16A(R16Mem::DE)),                // 0x12
-            0b0001_0011 => Ok(Self::IncR16(R16::DE)),                     // 0x13
-            0b0001_0100 => Ok(Self::IncR8(R8::D)),                        // 0x14
-            0b0001_0101 => Ok(Self::DecR8(R8::D)),                        // 0x15
-            0b0001_0110 => Ok(Self::LoadR8Imm8(R8::D)),                   // 0x16
-            0b0001_0111 => Ok(Self::RotateLeftA),                         // 0x17
-            0b0001_1000 => Ok(Self::JrImm8),                              // 0x18
-            0b0001_1001 => Ok(Self::AddHLR16(R16::DE)),                   // 0x19
-            0b0001_1010 => Ok(Self::LoadAR16(R16Mem::DE)),                // 0x1A
-            0b0001_1011 => Ok(Self::DecR16(R16::DE)),                     // 0x1B
-            0b0001_1100 => Ok(Self::IncR8(R8::E)),                        // 0x1C
-            0b0001_1101 => Ok(Self::DecR8(R8::E)),                        // 0x1D
-            0b0001_1110 => Ok(Self::LoadR8Imm8(R8::E)),                   // 0x1E
-            0b0001_1111 => Ok(Self::RotateRightA),                        // 0x1F
-            0b0010_0000 => Ok(Self::JrCondImm8(JumpCondition::NotZero)),  // 0x20
-            0b0010_0001 => Ok(Self::LoadR16Imm16(R16::HL)),               // 0x21
-            0b0010_0010 => Ok(Self::LoadR16A(R16Mem::HLI)),               // 0x22
-            0b0010_0011 => Ok(Self::IncR16(R16::HL)),                     // 0x23
-            0b0010_0100 => Ok(Self::IncR8(R8::H)),                        // 0x24
-            0b0010_0101 => Ok(Self::DecR8(R8::H)),                        // 0x25
-            0b0010_0110 => Ok(Self::LoadR8Imm8(R8::H)),                   // 0x26
-            0b0010_0111 => Ok(Self::DAA),                                 // 0x27
-            0b0010_1000 => Ok(Self::JrCondImm8(JumpCondition::Zero)),     // 0x28
-            0b0010_1001 => Ok(Self::AddHLR16(R16::HL)),                   // 0x29
-            0b0010_1111 => Ok(Self::ComplementA),                         // 0x2F
-            0b0011_0000 => Ok(Self::JrCondImm8(JumpCondition::NotCarry)), // 0x30
-            0b0010_1010 => Ok(Self::LoadAR16(R16Mem::HLI)),               // 0x2A
-            0b0010_1011 => Ok(Self::DecR16(R16::HL)),                     // 0x2B
-            0b0010_1100 => Ok(Self::IncR8(R8::L)),                        // 0x2C
-            0b0010_1101 => Ok(Self::DecR8(R8::L)),                        // 0x2D
-            0b0010_1110 => Ok(Self::LoadR8Imm8(R8::L)),                   // 0x2E
-            0b0011_0001 => Ok(Self::LoadR16Imm16(R16::SP)),               // 0x31
-            0b0011_0010 => Ok(Self::LoadR16A(R16Mem::HLD)),               // 0x32
-            0b0011_0011 => Ok(Self::IncR16(R16::SP)),                     // 0x33
-            0b0011_0100 => Ok(Self::IncR8(R8::HL)),                       // 0x34
-            0b0011_0101 => Ok(Self::DecR8(R8::HL)),                       // 0x35
-            0b0011_0110 => Ok(Self::LoadR8Imm8(R8::HL)),                  // 0x36
-            0b0011_0111 => Ok(Self::SetCarryFlag),                        // 0x37
-            0b0011_1000 => Ok(Self::JrCondImm8(JumpCondition::Carry)),    // 0x38
-            0b0011_1001 => Ok(Self::AddHLR16(R16::SP)),                   // 0x39
-            0b0011_1010 => Ok(Self::LoadAR16(R16Mem::HLD)),               // 0x3A
-            0b0011_1011 => Ok(Self::DecR16(R16::SP)),                     // 0x3B
-            0b0011_1100 => Ok(Self::IncR8(R8::A)),                        // 0x3C
-            0b0011_1101 => Ok(Self::DecR8(R8::A)),                        // 0x3D
-            0b0011_1110 => Ok(Self::LoadR8Imm8(R8::A)),                   // 0x3E
-            0b0011_1111 => Ok(Self::ComplementCarryFlag),                 // 0x3F
-            0b0100_0000 => Ok(Self::LoadR8R8((R8::B, R8::B))),            // 0x40
-            0b0100_0001 => Ok(Self::LoadR8R8((R8::B, R8::C))),            // 0x41
-            0b0100_0010 => Ok(Self::LoadR8R8((R8::B, R8::D))),            // 0x42
-            0b0100_0011 => Ok(Self::LoadR8R8((R8::B, R8::E))),            // 0x43
-            0b0100_0100 => Ok(Self::LoadR8R8((R8::B, R8::H))),            // 0x44
-            0b0100_0101 => Ok(Self::LoadR8R8((R8::B, R8::L))),            // 0x45
-            0b0100_0110 => Ok(Self::LoadR8R8((R8::B, R8::HL))),           // 0x46
-            0b0100_0111 => Ok(Self::LoadR8R8((R8::B, R8::A))),            // 0x47
-            0b0100_1000 => Ok(Self::LoadR8R8((R8::C, R8::B))),            // 0x48
-            0b0100_1001 => Ok(Self::LoadR8R8((R8::C, R8::C))),            // 0x49
-            0b0100_1010 => Ok(Self::LoadR8R8((R8::C, R8::D))),            // 0x4A
-            0b0100_1011 => Ok(Self::LoadR8R8((R8::C, R8::E))),            // 0x4B
-            0b0100_1100 => Ok(Self::LoadR8R8((R8::C, R8::H))),            // 0x4C
-            0b0100_1101 => Ok(Self::LoadR8R8((R8::C, R8::L))),            // 0x4D
-            0b0100_1110 => Ok(Self::LoadR8R8((R8::C, R8::HL))),           // 0x4E
-            0b0100_1111 => Ok(Self::LoadR8R8((R8::C, R8::A))),            // 0x4F
-            0b0101_0000 => Ok(Self::LoadR8R8((R8::D, R8::B))),            // 0x50
-            0b0101_0001 => Ok(Self::LoadR8R8((R8::D, R8::C))),            // 0x51
-            0b0101_0010 => Ok(Self::LoadR8R8((R8::D, R8::D))),            // 0x52
-            0b0101_0011 => Ok(Self::LoadR8R8((R8::D, R8::E))),            // 0x53
-            0b0101_0100 => Ok(Self::LoadR8R8((R8::D, R8::H))),            // 0x54
-            0b0101_0101 => Ok(Self::LoadR8R8((R8::D, R8::L))),            // 0x55
-            0b0101_0110 => Ok(Self::LoadR8R8((R8::D, R8::HL))),           // 0x56
-            0b0101_0111 => Ok(Self::LoadR8R8((R8::D, R8::A))),            // 0x57
-            0b0101_1000 => Ok(Self::LoadR8R8((R8::E, R8::B))),            // 0x58
-            0b0101_1001 => Ok(Self::LoadR8R8((R8::E, R8::C))),            // 0x59
-            0b0101_1010 => Ok(Self::LoadR8R8((R8::E, R8::D))),            // 0x5A
-            0b0101_1011 => Ok(Self::LoadR8R8((R8::E, R8::E))),            // 0x5B
-            0b0101_1100 => Ok(Self::LoadR8R8((R8::E, R8::H))),            // 0x5C
-            0b0101_1101 => Ok(Self::LoadR8R8((R8::E, R8::L))),            // 0x5D
-            0b0101_1110 => Ok(Self::LoadR8R8((R8::E, R8::HL))),           // 0x5E
-            0b0101_1111 => Ok(Self::LoadR8R8((R8::E, R8::A))),            // 0x5F
-            0b0110_0000 => Ok(Self::LoadR8R8((R8::H, R8::B))),            // 0x60
-            0b0110_0001 => Ok(Self::LoadR8R8((R8::H, R8::C))),            // 0x61
-            0b0110_0010 => Ok(Self::LoadR8R8((R8::H, R8::D))),            // 0x62
-            0b0110_0011 => Ok(Self::LoadR8R8((R8::H, R8::E))),            // 0x63
-            0b0110_0100 => Ok(Self::LoadR8R8((R8::H, R8::H))),            // 0x64
-            0b0110_0101 => Ok(Self::LoadR8R8((R8::H, R8::L))),            // 0x65
-            0b0110_0110 => Ok(Self::LoadR8R8((R8::H, R8::HL))),           // 0x66
-            0b0110_0111 => Ok(Self::LoadR8R8((R8::H, R8::A))),            // 0x67
-            0b0110_1000 => Ok(Self::LoadR8R8((R8::L, R8::B))),            // 0x68
-            0b0110_1001 => Ok(Self::LoadR8R8((R8::L, R8::C))),            // 0x69
-            0b0110_1010 => Ok(Self::LoadR8R8((R8::L, R8::D))),            // 0x6A
-            0b0110_1011 => Ok(Self::LoadR8R8((R8::L, R8::E))),            // 0x6B
-            0b0110_1100 => Ok(Self::LoadR8R8((R8::L, R8::H))),            // 0x6C
-            0b0110_1101 => Ok(Self::LoadR8R8((R8::L, R8::L))),            // 0x6D
-            0b0110_1110 => Ok(Self::LoadR8R8((R8::L, R8::HL))),           // 0x6E
-            0b0110_1111 => Ok(Self::LoadR8R8((R8::L, R8::A))),            // 0x6F
-            0b0111_0000 => Ok(Self::LoadR8R8((R8::HL, R8::B))),           // 0x70
-            0b0111_0001 => Ok(Self::LoadR8R8((R8::HL, R8::C))),           // 0x71
-            0b0111_0010 => Ok(Self::LoadR8R8((R8::HL, R8::D))),           // 0x72
-            0b0111_0011 => Ok(Self::LoadR8R8((R8::HL, R8::E))),           // 0x73
-            0b0111_0100 => Ok(Self::LoadR8R8((R8::HL, R8::H))),           // 0x74
-            0b0111_0101 => Ok(Self::LoadR8R8((R8::HL, R8::L))),           // 0x75
-            0b0111_0110 => Ok(Self::Halt),                                // 0x76
-            0b0111_0111 => Ok(Self::LoadR8R8((R8::HL, R8::A))),           // 0x77
-            0b0111_1000 => Ok(Self::LoadR8R8((R8::A, R8::B))),            // 0x78
-            0b0111_1001 => Ok(Self::LoadR8R8((R8::A, R8::C))),            // 0x79
-            0b0111_1010 => Ok(Self::LoadR8R8((R8::A, R8::D))),            // 0x7A
-            0b0111_1011 => Ok(Self::LoadR8R8((R8::A, R8::E))),            // 0x7B
-            0b0111_1100 => Ok(Self::LoadR8R8((R8::A, R8::H))),            // 0x7C
-            0b0111_1101 => Ok(Self::LoadR8R8((R8::A, R8::L))),            // 0x7D
-            0b0111_1110 => Ok(Self::LoadR8R8((R8::A, R8::HL))),           // 0x7E
-            0b0111_1111 => Ok(Self::LoadR8R8((R8::A, R8::A))),            // 0x7F
-            0b1000_0000 => Ok(Self::AddR8(R8::B)),                        // 0x80
-            0b1000_0001 => Ok(Self::AddR8(R8::C)),                        // 0x81
-            0b1000_0010 => Ok(Self::AddR8(R8::D)),                        // 0x82
-            0b1000_0011 => Ok(Self::AddR8(R8::E)),                        // 0x83
-            0b1000_0100 => Ok(Self::AddR8(R8::H)),                        // 0x84
-            0b1000_0101 => Ok(Self::AddR8(R8::L)),                        // 0x85
-            0b1000_0110 => Ok(Self::AddR8(R8::HL)),                       // 0x86
-            0b1000_0111 => Ok(Self::AddR8(R8::A)),                        // 0x87
-            0b1000_1000 => Ok(Self::AddCarryR8(R8::B)),                   // 0x88
-            0b1000_1001 => Ok(Self::AddCarryR8(R8::C)),                   // 0x89
-            0b1000_1010 => Ok(Self::AddCarryR8(R8::D)),                   // 0x8A
-            0b1000_1011 => Ok(Self::AddCarryR8(R8::E)),                   // 0x8B
-            0b1000_1100 => Ok(Self::AddCarryR8(R8::H)),                   // 0x8C
-            0b1000_1101 => Ok(Self::AddCarryR8(R8::L)),                   // 0x8D
-            0b1000_1110 => Ok(Self::AddCarryR8(R8::HL)),                  // 0x8E
-            0b1000_1111 => Ok(Self::AddCarryR8(R8::A)),                   // 0x8F
-            0b1001_0000 => Ok(Self::SubR8(R8::B)),                        // 0x90
-            0b1001_0001 => Ok(Self::SubR8(R8::C)),                        // 0x91
-            0b1001_0010 => Ok(Self::SubR8(R8::D)),                        // 0x92
-            0b1001_0011 => Ok(Self::SubR8(R8::E)),                        // 0x93
-            0b1001_0100 => Ok(Self::SubR8(R8::H)),                        // 0x94
-            0b1001_0101 => Ok(Self::SubR8(R8::L)),                        // 0x95
-            0b1001_0110 => Ok(Self::SubR8(R8::HL)),                       // 0x96
-            0b1001_0111 => Ok(Self::SubR8(R8::A)),                        // 0x97
-            0b1001_1000 => Ok(Self::SubCarryR8(R8::B)),                   // 0x98
-            0b1001_1001 => Ok(Self::SubCarryR8(R8::C)),                   // 0x99
-            0b1001_1010 => Ok(Self::SubCarryR8(R8::D)),                   // 0x9A
-            0b1001_1011 => Ok(Self::SubCarryR8(R8::E)),                   // 0x9B
-            0b1001_1100 => Ok(Self::SubCarryR8(R8::H)),                   // 0x9C
-            0b1001_1101 => Ok(Self::SubCarryR8(R8::L)),                   // 0x9D
-            0b1001_1110 => Ok(Self::SubCarryR8(R8::HL)),                  // 0x9E
-            0b1001_1111 => Ok(Self::SubCarryR8(R8::A)),                   // 0x9F
-            0b1010_0000 => Ok(Self::AndR8(R8::B)),                        // 0xA0
-            0b1010_0001 => Ok(Self::AndR8(R8::C)),                        // 0xA1
-            0b1010_0010 => Ok(Self::AndR8(R8::D)),                        // 0xA2
-            0b1010_0011 => Ok(Self::AndR8(R8::E)),                        // 0xA3
-            0b1010_0100 => Ok(Self::AndR8(R8::H)),                        // 0xA4
-            0b1010_0101 => Ok(Self::AndR8(R8::L)),                        // 0xA5
-            0b1010_0110 => Ok(Self::AndR8(R8::HL)),                       // 0xA6
-            0b1010_0111 => Ok(Self::AndR8(R8::A)),                        // 0xA7
-            0b1010_1000 => Ok(Self::XorR8(R8::B)),                        // 0xA8
-            0b1010_1001 => Ok(Self::XorR8(R8::C)),                        // 0xA9
-            0b1010_1010 => Ok(Self::XorR8(R8::D)),                        // 0xAA
-            0b1010_1011 => Ok(Self::XorR8(R8::E)),                        // 0xAB
-            0b1010_1100 => Ok(Self::XorR8(R8::H)),                        // 0xAC
-            0b1010_1101 => Ok(Self::XorR8(R8::L)),                        // 0xAD
-            0b1010_1110 => Ok(Self::XorR8(R8::HL)),                       // 0xAE
-            0b1010_1111 => Ok(Self::XorR8(R8::A)),                        // 0xAF
-            0b1011_0000 => Ok(Self::OrR8(R8::B)),                         // 0xB0
-            0b1011_0001 => Ok(Self::OrR8(R8::C)),                         // 0xB1
-            0b1011_0010 => Ok(Self::OrR8(R8::D)),                         // 0xB2
-            0b1011_0011 => Ok(Self::OrR8(R8::E)),                         // 0xB3
-            0b1011_0100 => Ok(Self::OrR8(R8::H)),                         // 0xB4
-            0b1011_0101 => Ok(Self::OrR8(R8::L)),                         // 0xB5
-            0b1011_0110 => Ok(Self::OrR8(R8::HL)),                        // 0xB6
-            0b1011_0111 => Ok(Self::OrR8(R8::A)),                         // 0xB7
-            0b1011_1000 => Ok(Self::CompareR8(R8::B)),                    // 0xB8
-            0b1011_1001 => Ok(Self::CompareR8(R8::C)),                    // 0xB9
-            0b1011_1010 => Ok(Self::CompareR8(R8::D)),                    // 0xBA
-            0b1011_1011 => Ok(Self::CompareR8(R8::E)),                    // 0xBB
-            0b1011_1100 => Ok(Self::CompareR8(R8::H)),                    // 0xBC
-            0b1011_1101 => Ok(Self::CompareR8(R8::L)),                    // 0xBD
-            0b1011_1110 => Ok(Self::CompareR8(R8::HL)),                   // 0xBE
-            0b1011_1111 => Ok(Self::CompareR8(R8::A)),                    // 0xBF
-            0b1100_0000 => Ok(Self::ReturnCondition(JumpCondition::NotZero)), // 0xC0
-            0b1100_0001 => Ok(Self::PopR16(R16Stack::BC)),                // 0xC1
-            0b1100_0010 => Ok(Self::JpCondImm16(JumpCondition::NotZero)), // 0xC2
-            0b1100_0011 => Ok(Self::JpImm16),                             // 0xC3
-            0b1100_0100 => Ok(Self::CallCondition(JumpCondition::NotZero)), // 0xC4
-            0b1100_0101 => Ok(Self::PushR16(R16Stack::BC)),               // 0xC5
-            0b1100_0110 => Ok(Self::AddImm8),                             // 0xC6
-            0b1100_0111 => Ok(Self::RestartVector(0x00)),                 // 0xC7
-            0b1100_1000 => Ok(Self::ReturnCondition(JumpCondition::Zero)), // 0xC8
-            0b1100_1001 => Ok(Self::Return),                              // 0xC9
-            0b1100_1010 => Ok(Self::JpCondImm16(JumpCondition::Zero)),    // 0xCA
-            0b1100_1100 => Ok(Self::CallCondition(JumpCondition::Zero)),  // 0xCC
-            0b1100_1101 => Ok(Self::Call),                                // 0xCD
-            0b1100_1110 => Ok(Self::AddCarryImm8),                        // 0xCE
-            0b1100_1111 => Ok(Self::RestartVector(0x08)),                 // 0xCF
-            0b1101_0000 => Ok(Self::ReturnCondition(JumpCondition::NotCarry)), // 0xD0
-            0b1101_0001 => Ok(Self::PopR16(R16Stack::DE)),                // 0xD1
-            0b1101_0010 => Ok(Self::JpCondImm16(JumpCondition::NotCarry)), // 0xD2
-            0b1101_0100 => Ok(Self::CallCondition(JumpCondition::NotCarry)), // 0xD4
-            0b1101_0101 => Ok(Self::PushR16(R16Stack::DE)),               // 0xD5
-            0b1101_0110 => Ok(Self::SubImm8),                             // 0xD6
-            0b1101_0111 => Ok(Self::RestartVector(0x10)),                 // 0xD7
-            0b1101_1000 => Ok(Self::ReturnCondition(JumpCondition::Carry)), // 0xD8
-            0b1101_1001 => Ok(Self::ReturnEnableInterrupts),              // 0xD9
-            0b1101_1010 => Ok(Self::JpCondImm16(JumpCondition::Carry)),   // 0xDA,
-            0b1101_1100 => Ok(Self::CallCondition(JumpCondition::Carry)), // 0xDC
-            0b1101_1110 => Ok(Self::SubCarryImm8),                        // 0xDE
-            0b1101_1111 => Ok(Self::RestartVector(0x18)),                 // 0xDF
-            0b1110_0000 => Ok(Self::LoadHighImm8A),                       // 0xE0
-            0b1110_0001 => Ok(Self::PopR16(R16Stack::HL)),                // 0xE1
-            0b1110_0010 => Ok(Self::LoadHighCA),                          // 0xE2
-            0b1110_0101 => Ok(Self::PushR16(R16Stack::HL)),               // 0xE5
-            0b1110_0110 => Ok(Self::AndImm8),                             // 0xE6
-            0b1110_0111 => Ok(Self::RestartVector(0x0020)),               // 0xE7
-            0b1110_1000 => Ok(Self::AddSpImm8),                           // 0xE8
-            0b1110_1001 => Ok(Self::JpHL),                                // 0xE9
-            0b1110_1010 => Ok(Self::LoadImm16A),                          // 0xEA
-            0b1110_1110 => Ok(Self::XorImm8),                             // 0xEE
-            0b1110_1111 => Ok(Self::RestartVector(0x28)),                 // 0xEF
-            0b1111_0000 => Ok(Self::LoadHighAImm8),                       // 0xF0
-            0b1111_0001 => Ok(Self::PopR16(R16Stack::AF)),                // 0xF1
-            0b1111_0010 => Ok(Self::LoadHighAC),                          // 0xF2
-            0b1111_0011 => Ok(Self::DisableInterrupts),                   // 0xF3
-            0b1111_0101 => Ok(Self::PushR16(R16Stack::AF)),               // 0xF5
-            0b1111_0110 => Ok(Self::OrImm8),                              // 0xF6
-            0b1111_0111 => Ok(Self::RestartVector(0x30)),                 // 0xF7
-            0b1111_1000 => Ok(Self::LoadHlSpImm8),                        // 0xF8
-            0b1111_1001 => Ok(Self::LoadSpHl),                            // 0xF9
-            0b1111_1010 => Ok(Self::LoadAImm16),                          // 0xFA
-            0b1111_1011 => Ok(Self::EnableInterrupts),                    // 0xFB
-            0b1111_1110 => Ok(Self::CompareImm8),                         // 0xFE
-            0b1111_1111 => Ok(Self::RestartVector(0x38)),                 // 0xFF
-            _ => Err(format!("Illegal unprefixed instruction byte: {:02X}", byte).into()),
+            0b0000_0000 => Some(Self::Nop),                                // 0x00
+            0b0000_0001 => Some(Self::LoadR16Imm16(R16::BC)),              // 0x01
+            0b0000_0010 => Some(Self::LoadR16A(R16Mem::BC)),               // 0x02
+            0b0000_0011 => Some(Self::IncR16(R16::BC)),                    // 0x03
+            0b0000_0100 => Some(Self::IncR8(R8::B)),                       // 0x04
+            0b0000_0101 => Some(Self::DecR8(R8::B)),                       // 0x05
+            0b0000_0110 => Some(Self::LoadR8Imm8(R8::B)),                  // 0x06
+            0b0000_0111 => Some(Self::RotateLeftCircularA),                // 0x07
+            0b0000_1000 => Some(Self::LoadImm16SP),                        // 0x08
+            0b0000_1001 => Some(Self::AddHLR16(R16::BC)),                  // 0x09
+            0b0000_1010 => Some(Self::LoadAR16(R16Mem::BC)),               // 0x0A
+            0b0000_1011 => Some(Self::DecR16(R16::BC)),                    // 0x0B
+            0b0000_1100 => Some(Self::IncR8(R8::C)),                       // 0x0C
+            0b0000_1101 => Some(Self::DecR8(R8::C)),                       // 0x0D
+            0b0000_1110 => Some(Self::LoadR8Imm8(R8::C)),                  // 0x0E
+            0b0000_1111 => Some(Self::RotateRightCircularA),               // 0x0F
+            0b0001_0000 => Some(Self::Nop),                                // 0x10 ToDo: STOP
+            0b0001_0001 => Some(Self::LoadR16Imm16(R16::DE)),              // 0x11
+            0b0001_0010 => Some(Self::LoadR16A(R16Mem::DE)),               // 0x12
+            0b0001_0011 => Some(Self::IncR16(R16::DE)),                    // 0x13
+            0b0001_0100 => Some(Self::IncR8(R8::D)),                       // 0x14
+            0b0001_0101 => Some(Self::DecR8(R8::D)),                       // 0x15
+            0b0001_0110 => Some(Self::LoadR8Imm8(R8::D)),                  // 0x16
+            0b0001_0111 => Some(Self::RotateLeftA),                        // 0x17
+            0b0001_1000 => Some(Self::JrImm8),                             // 0x18
+            0b0001_1001 => Some(Self::AddHLR16(R16::DE)),                  // 0x19
+            0b0001_1010 => Some(Self::LoadAR16(R16Mem::DE)),               // 0x1A
+            0b0001_1011 => Some(Self::DecR16(R16::DE)),                    // 0x1B
+            0b0001_1100 => Some(Self::IncR8(R8::E)),                       // 0x1C
+            0b0001_1101 => Some(Self::DecR8(R8::E)),                       // 0x1D
+            0b0001_1110 => Some(Self::LoadR8Imm8(R8::E)),                  // 0x1E
+            0b0001_1111 => Some(Self::RotateRightA),                       // 0x1F
+            0b0010_0000 => Some(Self::JrCondImm8(JumpCondition::NotZero)), // 0x20
+            0b0010_0001 => Some(Self::LoadR16Imm16(R16::HL)),              // 0x21
+            0b0010_0010 => Some(Self::LoadR16A(R16Mem::HLI)),              // 0x22
+            0b0010_0011 => Some(Self::IncR16(R16::HL)),                    // 0x23
+            0b0010_0100 => Some(Self::IncR8(R8::H)),                       // 0x24
+            0b0010_0101 => Some(Self::DecR8(R8::H)),                       // 0x25
+            0b0010_0110 => Some(Self::LoadR8Imm8(R8::H)),                  // 0x26
+            0b0010_0111 => Some(Self::DAA),                                // 0x27
+            0b0010_1000 => Some(Self::JrCondImm8(JumpCondition::Zero)),    // 0x28
+            0b0010_1001 => Some(Self::AddHLR16(R16::HL)),                  // 0x29
+            0b0010_1111 => Some(Self::ComplementA),                        // 0x2F
+            0b0011_0000 => Some(Self::JrCondImm8(JumpCondition::NotCarry)), // 0x30
+            0b0010_1010 => Some(Self::LoadAR16(R16Mem::HLI)),              // 0x2A
+            0b0010_1011 => Some(Self::DecR16(R16::HL)),                    // 0x2B
+            0b0010_1100 => Some(Self::IncR8(R8::L)),                       // 0x2C
+            0b0010_1101 => Some(Self::DecR8(R8::L)),                       // 0x2D
+            0b0010_1110 => Some(Self::LoadR8Imm8(R8::L)),                  // 0x2E
+            0b0011_0001 => Some(Self::LoadR16Imm16(R16::SP)),              // 0x31
+            0b0011_0010 => Some(Self::LoadR16A(R16Mem::HLD)),              // 0x32
+            0b0011_0011 => Some(Self::IncR16(R16::SP)),                    // 0x33
+            0b0011_0100 => Some(Self::IncR8(R8::HL)),                      // 0x34
+            0b0011_0101 => Some(Self::DecR8(R8::HL)),                      // 0x35
+            0b0011_0110 => Some(Self::LoadR8Imm8(R8::HL)),                 // 0x36
+            0b0011_0111 => Some(Self::SetCarryFlag),                       // 0x37
+            0b0011_1000 => Some(Self::JrCondImm8(JumpCondition::Carry)),   // 0x38
+            0b0011_1001 => Some(Self::AddHLR16(R16::SP)),                  // 0x39
+            0b0011_1010 => Some(Self::LoadAR16(R16Mem::HLD)),              // 0x3A
+            0b0011_1011 => Some(Self::DecR16(R16::SP)),                    // 0x3B
+            0b0011_1100 => Some(Self::IncR8(R8::A)),                       // 0x3C
+            0b0011_1101 => Some(Self::DecR8(R8::A)),                       // 0x3D
+            0b0011_1110 => Some(Self::LoadR8Imm8(R8::A)),                  // 0x3E
+            0b0011_1111 => Some(Self::ComplementCarryFlag),                // 0x3F
+            0b0100_0000 => Some(Self::LoadR8R8((R8::B, R8::B))),           // 0x40
+            0b0100_0001 => Some(Self::LoadR8R8((R8::B, R8::C))),           // 0x41
+            0b0100_0010 => Some(Self::LoadR8R8((R8::B, R8::D))),           // 0x42
+            0b0100_0011 => Some(Self::LoadR8R8((R8::B, R8::E))),           // 0x43
+            0b0100_0100 => Some(Self::LoadR8R8((R8::B, R8::H))),           // 0x44
+            0b0100_0101 => Some(Self::LoadR8R8((R8::B, R8::L))),           // 0x45
+            0b0100_0110 => Some(Self::LoadR8R8((R8::B, R8::HL))),          // 0x46
+            0b0100_0111 => Some(Self::LoadR8R8((R8::B, R8::A))),           // 0x47
+            0b0100_1000 => Some(Self::LoadR8R8((R8::C, R8::B))),           // 0x48
+            0b0100_1001 => Some(Self::LoadR8R8((R8::C, R8::C))),           // 0x49
+            0b0100_1010 => Some(Self::LoadR8R8((R8::C, R8::D))),           // 0x4A
+            0b0100_1011 => Some(Self::LoadR8R8((R8::C, R8::E))),           // 0x4B
+            0b0100_1100 => Some(Self::LoadR8R8((R8::C, R8::H))),           // 0x4C
+            0b0100_1101 => Some(Self::LoadR8R8((R8::C, R8::L))),           // 0x4D
+            0b0100_1110 => Some(Self::LoadR8R8((R8::C, R8::HL))),          // 0x4E
+            0b0100_1111 => Some(Self::LoadR8R8((R8::C, R8::A))),           // 0x4F
+            0b0101_0000 => Some(Self::LoadR8R8((R8::D, R8::B))),           // 0x50
+            0b0101_0001 => Some(Self::LoadR8R8((R8::D, R8::C))),           // 0x51
+            0b0101_0010 => Some(Self::LoadR8R8((R8::D, R8::D))),           // 0x52
+            0b0101_0011 => Some(Self::LoadR8R8((R8::D, R8::E))),           // 0x53
+            0b0101_0100 => Some(Self::LoadR8R8((R8::D, R8::H))),           // 0x54
+            0b0101_0101 => Some(Self::LoadR8R8((R8::D, R8::L))),           // 0x55
+            0b0101_0110 => Some(Self::LoadR8R8((R8::D, R8::HL))),          // 0x56
+            0b0101_0111 => Some(Self::LoadR8R8((R8::D, R8::A))),           // 0x57
+            0b0101_1000 => Some(Self::LoadR8R8((R8::E, R8::B))),           // 0x58
+            0b0101_1001 => Some(Self::LoadR8R8((R8::E, R8::C))),           // 0x59
+            0b0101_1010 => Some(Self::LoadR8R8((R8::E, R8::D))),           // 0x5A
+            0b0101_1011 => Some(Self::LoadR8R8((R8::E, R8::E))),           // 0x5B
+            0b0101_1100 => Some(Self::LoadR8R8((R8::E, R8::H))),           // 0x5C
+            0b0101_1101 => Some(Self::LoadR8R8((R8::E, R8::L))),           // 0x5D
+            0b0101_1110 => Some(Self::LoadR8R8((R8::E, R8::HL))),          // 0x5E
+            0b0101_1111 => Some(Self::LoadR8R8((R8::E, R8::A))),           // 0x5F
+            0b0110_0000 => Some(Self::LoadR8R8((R8::H, R8::B))),           // 0x60
+            0b0110_0001 => Some(Self::LoadR8R8((R8::H, R8::C))),           // 0x61
+            0b0110_0010 => Some(Self::LoadR8R8((R8::H, R8::D))),           // 0x62
+            0b0110_0011 => Some(Self::LoadR8R8((R8::H, R8::E))),           // 0x63
+            0b0110_0100 => Some(Self::LoadR8R8((R8::H, R8::H))),           // 0x64
+            0b0110_0101 => Some(Self::LoadR8R8((R8::H, R8::L))),           // 0x65
+            0b0110_0110 => Some(Self::LoadR8R8((R8::H, R8::HL))),          // 0x66
+            0b0110_0111 => Some(Self::LoadR8R8((R8::H, R8::A))),           // 0x67
+            0b0110_1000 => Some(Self::LoadR8R8((R8::L, R8::B))),           // 0x68
+            0b0110_1001 => Some(Self::LoadR8R8((R8::L, R8::C))),           // 0x69
+            0b0110_1010 => Some(Self::LoadR8R8((R8::L, R8::D))),           // 0x6A
+            0b0110_1011 => Some(Self::LoadR8R8((R8::L, R8::E))),           // 0x6B
+            0b0110_1100 => Some(Self::LoadR8R8((R8::L, R8::H))),           // 0x6C
+            0b0110_1101 => Some(Self::LoadR8R8((R8::L, R8::L))),           // 0x6D
+            0b0110_1110 => Some(Self::LoadR8R8((R8::L, R8::HL))),          // 0x6E
+            0b0110_1111 => Some(Self::LoadR8R8((R8::L, R8::A))),           // 0x6F
+            0b0111_0000 => Some(Self::LoadR8R8((R8::HL, R8::B))),          // 0x70
+            0b0111_0001 => Some(Self::LoadR8R8((R8::HL, R8::C))),          // 0x71
+            0b0111_0010 => Some(Self::LoadR8R8((R8::HL, R8::D))),          // 0x72
+            0b0111_0011 => Some(Self::LoadR8R8((R8::HL, R8::E))),          // 0x73
+            0b0111_0100 => Some(Self::LoadR8R8((R8::HL, R8::H))),          // 0x74
+            0b0111_0101 => Some(Self::LoadR8R8((R8::HL, R8::L))),          // 0x75
+            0b0111_0110 => Some(Self::Halt),                               // 0x76
+            0b0111_0111 => Some(Self::LoadR8R8((R8::HL, R8::A))),          // 0x77
+            0b0111_1000 => Some(Self::LoadR8R8((R8::A, R8::B))),           // 0x78
+            0b0111_1001 => Some(Self::LoadR8R8((R8::A, R8::C))),           // 0x79
+            0b0111_1010 => Some(Self::LoadR8R8((R8::A, R8::D))),           // 0x7A
+            0b0111_1011 => Some(Self::LoadR8R8((R8::A, R8::E))),           // 0x7B
+            0b0111_1100 => Some(Self::LoadR8R8((R8::A, R8::H))),           // 0x7C
+            0b0111_1101 => Some(Self::LoadR8R8((R8::A, R8::L))),           // 0x7D
+            0b0111_1110 => Some(Self::LoadR8R8((R8::A, R8::HL))),          // 0x7E
+            0b0111_1111 => Some(Self::LoadR8R8((R8::A, R8::A))),           // 0x7F
+            0b1000_0000 => Some(Self::AddR8(R8::B)),                       // 0x80
+            0b1000_0001 => Some(Self::AddR8(R8::C)),                       // 0x81
+            0b1000_0010 => Some(Self::AddR8(R8::D)),                       // 0x82
+            0b1000_0011 => Some(Self::AddR8(R8::E)),                       // 0x83
+            0b1000_0100 => Some(Self::AddR8(R8::H)),                       // 0x84
+            0b1000_0101 => Some(Self::AddR8(R8::L)),                       // 0x85
+            0b1000_0110 => Some(Self::AddR8(R8::HL)),                      // 0x86
+            0b1000_0111 => Some(Self::AddR8(R8::A)),                       // 0x87
+            0b1000_1000 => Some(Self::AddCarryR8(R8::B)),                  // 0x88
+            0b1000_1001 => Some(Self::AddCarryR8(R8::C)),                  // 0x89
+            0b1000_1010 => Some(Self::AddCarryR8(R8::D)),                  // 0x8A
+            0b1000_1011 => Some(Self::AddCarryR8(R8::E)),                  // 0x8B
+            0b1000_1100 => Some(Self::AddCarryR8(R8::H)),                  // 0x8C
+            0b1000_1101 => Some(Self::AddCarryR8(R8::L)),                  // 0x8D
+            0b1000_1110 => Some(Self::AddCarryR8(R8::HL)),                 // 0x8E
+            0b1000_1111 => Some(Self::AddCarryR8(R8::A)),                  // 0x8F
+            0b1001_0000 => Some(Self::SubR8(R8::B)),                       // 0x90
+            0b1001_0001 => Some(Self::SubR8(R8::C)),                       // 0x91
+            0b1001_0010 => Some(Self::SubR8(R8::D)),                       // 0x92
+            0b1001_0011 => Some(Self::SubR8(R8::E)),                       // 0x93
+            0b1001_0100 => Some(Self::SubR8(R8::H)),                       // 0x94
+            0b1001_0101 => Some(Self::SubR8(R8::L)),                       // 0x95
+            0b1001_0110 => Some(Self::SubR8(R8::HL)),                      // 0x96
+            0b1001_0111 => Some(Self::SubR8(R8::A)),                       // 0x97
+            0b1001_1000 => Some(Self::SubCarryR8(R8::B)),                  // 0x98
+            0b1001_1001 => Some(Self::SubCarryR8(R8::C)),                  // 0x99
+            0b1001_1010 => Some(Self::SubCarryR8(R8::D)),                  // 0x9A
+            0b1001_1011 => Some(Self::SubCarryR8(R8::E)),                  // 0x9B
+            0b1001_1100 => Some(Self::SubCarryR8(R8::H)),                  // 0x9C
+            0b1001_1101 => Some(Self::SubCarryR8(R8::L)),                  // 0x9D
+            0b1001_1110 => Some(Self::SubCarryR8(R8::HL)),                 // 0x9E
+            0b1001_1111 => Some(Self::SubCarryR8(R8::A)),                  // 0x9F
+            0b1010_0000 => Some(Self::AndR8(R8::B)),                       // 0xA0
+            0b1010_0001 => Some(Self::AndR8(R8::C)),                       // 0xA1
+            0b1010_0010 => Some(Self::AndR8(R8::D)),                       // 0xA2
+            0b1010_0011 => Some(Self::AndR8(R8::E)),                       // 0xA3
+            0b1010_0100 => Some(Self::AndR8(R8::H)),                       // 0xA4
+            0b1010_0101 => Some(Self::AndR8(R8::L)),                       // 0xA5
+            0b1010_0110 => Some(Self::AndR8(R8::HL)),                      // 0xA6
+            0b1010_0111 => Some(Self::AndR8(R8::A)),                       // 0xA7
+            0b1010_1000 => Some(Self::XorR8(R8::B)),                       // 0xA8
+            0b1010_1001 => Some(Self::XorR8(R8::C)),                       // 0xA9
+            0b1010_1010 => Some(Self::XorR8(R8::D)),                       // 0xAA
+            0b1010_1011 => Some(Self::XorR8(R8::E)),                       // 0xAB
+            0b1010_1100 => Some(Self::XorR8(R8::H)),                       // 0xAC
+            0b1010_1101 => Some(Self::XorR8(R8::L)),                       // 0xAD
+            0b1010_1110 => Some(Self::XorR8(R8::HL)),                      // 0xAE
+            0b1010_1111 => Some(Self::XorR8(R8::A)),                       // 0xAF
+            0b1011_0000 => Some(Self::OrR8(R8::B)),                        // 0xB0
+            0b1011_0001 => Some(Self::OrR8(R8::C)),                        // 0xB1
+            0b1011_0010 => Some(Self::OrR8(R8::D)),                        // 0xB2
+            0b1011_0011 => Some(Self::OrR8(R8::E)),                        // 0xB3
+            0b1011_0100 => Some(Self::OrR8(R8::H)),                        // 0xB4
+            0b1011_0101 => Some(Self::OrR8(R8::L)),                        // 0xB5
+            0b1011_0110 => Some(Self::OrR8(R8::HL)),                       // 0xB6
+            0b1011_0111 => Some(Self::OrR8(R8::A)),                        // 0xB7
+            0b1011_1000 => Some(Self::CompareR8(R8::B)),                   // 0xB8
+            0b1011_1001 => Some(Self::CompareR8(R8::C)),                   // 0xB9
+            0b1011_1010 => Some(Self::CompareR8(R8::D)),                   // 0xBA
+            0b1011_1011 => Some(Self::CompareR8(R8::E)),                   // 0xBB
+            0b1011_1100 => Some(Self::CompareR8(R8::H)),                   // 0xBC
+            0b1011_1101 => Some(Self::CompareR8(R8::L)),                   // 0xBD
+            0b1011_1110 => Some(Self::CompareR8(R8::HL)),                  // 0xBE
+            0b1011_1111 => Some(Self::CompareR8(R8::A)),                   // 0xBF
+            0b1100_0000 => Some(Self::ReturnCondition(JumpCondition::NotZero)), // 0xC0
+            0b1100_0001 => Some(Self::PopR16(R16Stack::BC)),               // 0xC1
+            0b1100_0010 => Some(Self::JpCondImm16(JumpCondition::NotZero)), // 0xC2
+            0b1100_0011 => Some(Self::JpImm16),                            // 0xC3
+            0b1100_0100 => Some(Self::CallCondition(JumpCondition::NotZero)), // 0xC4
+            0b1100_0101 => Some(Self::PushR16(R16Stack::BC)),              // 0xC5
+            0b1100_0110 => Some(Self::AddImm8),                            // 0xC6
+            0b1100_0111 => Some(Self::RestartVector(0x00)),                // 0xC7
+            0b1100_1000 => Some(Self::ReturnCondition(JumpCondition::Zero)), // 0xC8
+            0b1100_1001 => Some(Self::Return),                             // 0xC9
+            0b1100_1010 => Some(Self::JpCondImm16(JumpCondition::Zero)),   // 0xCA
+            0b1100_1100 => Some(Self::CallCondition(JumpCondition::Zero)), // 0xCC
+            0b1100_1101 => Some(Self::Call),                               // 0xCD
+            0b1100_1110 => Some(Self::AddCarryImm8),                       // 0xCE
+            0b1100_1111 => Some(Self::RestartVector(0x08)),                // 0xCF
+            0b1101_0000 => Some(Self::ReturnCondition(JumpCondition::NotCarry)), // 0xD0
+            0b1101_0001 => Some(Self::PopR16(R16Stack::DE)),               // 0xD1
+            0b1101_0010 => Some(Self::JpCondImm16(JumpCondition::NotCarry)), // 0xD2
+            0b1101_0100 => Some(Self::CallCondition(JumpCondition::NotCarry)), // 0xD4
+            0b1101_0101 => Some(Self::PushR16(R16Stack::DE)),              // 0xD5
+            0b1101_0110 => Some(Self::SubImm8),                            // 0xD6
+            0b1101_0111 => Some(Self::RestartVector(0x10)),                // 0xD7
+            0b1101_1000 => Some(Self::ReturnCondition(JumpCondition::Carry)), // 0xD8
+            0b1101_1001 => Some(Self::ReturnEnableInterrupts),             // 0xD9
+            0b1101_1010 => Some(Self::JpCondImm16(JumpCondition::Carry)),  // 0xDA,
+            0b1101_1100 => Some(Self::CallCondition(JumpCondition::Carry)), // 0xDC
+            0b1101_1110 => Some(Self::SubCarryImm8),                       // 0xDE
+            0b1101_1111 => Some(Self::RestartVector(0x18)),                // 0xDF
+            0b1110_0000 => Some(Self::LoadHighImm8A),                      // 0xE0
+            0b1110_0001 => Some(Self::PopR16(R16Stack::HL)),               // 0xE1
+            0b1110_0010 => Some(Self::LoadHighCA),                         // 0xE2
+            0b1110_0101 => Some(Self::PushR16(R16Stack::HL)),              // 0xE5
+            0b1110_0110 => Some(Self::AndImm8),                            // 0xE6
+            0b1110_0111 => Some(Self::RestartVector(0x0020)),              // 0xE7
+            0b1110_1000 => Some(Self::AddSpImm8),                          // 0xE8
+            0b1110_1001 => Some(Self::JpHL),                               // 0xE9
+            0b1110_1010 => Some(Self::LoadImm16A),                         // 0xEA
+            0b1110_1110 => Some(Self::XorImm8),                            // 0xEE
+            0b1110_1111 => Some(Self::RestartVector(0x28)),                // 0xEF
+            0b1111_0000 => Some(Self::LoadHighAImm8),                      // 0xF0
+            0b1111_0001 => Some(Self::PopR16(R16Stack::AF)),               // 0xF1
+            0b1111_0010 => Some(Self::LoadHighAC),                         // 0xF2
+            0b1111_0011 => Some(Self::DisableInterrupts),                  // 0xF3
+            0b1111_0101 => Some(Self::PushR16(R16Stack::AF)),              // 0xF5
+            0b1111_0110 => Some(Self::OrImm8),                             // 0xF6
+            0b1111_0111 => Some(Self::RestartVector(0x30)),                // 0xF7
+            0b1111_1000 => Some(Self::LoadHlSpImm8),                       // 0xF8
+            0b1111_1001 => Some(Self::LoadSpHl),                           // 0xF9
+            0b1111_1010 => Some(Self::LoadAImm16),                         // 0xFA
+            0b1111_1011 => Some(Self::EnableInterrupts),                   // 0xFB
+            0b1111_1110 => Some(Self::CompareImm8),                        // 0xFE
+            0b1111_1111 => Some(Self::RestartVector(0x38)),                // 0xFF
+            _ => None,
         }
     }
 
     #[allow(unreachable_patterns)]
-    pub fn from_byte_prefixed(byte: u8) -> Self {
+    const fn decode_prefixed(byte: u8) -> Self {
         match byte {
             0b0000_0000 => Self::RotateLeftCircularR8(R8::B), // 0x00
             0b0000_0001 => Self::RotateLeftCircularR8(R8::C), // 0x01
@@ -701,107 +748,57 @@ impl Instruction {
             0b1111_1101 => Self::BitSetR8((7, R8::L)),        // 0xFD
             0b1111_1110 => Self::BitSetR8((7, R8::HL)),       // 0xFE
             0b1111_1111 => Self::BitSetR8((7, R8::A)),        // 0xFF
-            _ => panic!("Illegal prefixed instruction byte: {:02X}", byte),
+            _ => unreachable!(),
         }
     }
 
     pub fn get_length(&self) -> usize {
+        self.metadata().length
+    }
+
+    /// None for unconditional instructions
+    pub fn get_condition(&self) -> Option<JumpCondition> {
         match self {
-            Self::Nop
-            | Self::AddR8(_)
-            | Self::JpHL
-            | Self::PopR16(_)
-            | Self::PushR16(_)
-            | Self::LoadR16A(_)
-            | Self::LoadAR16(_)
-            | Self::IncR16(_)
-            | Self::DecR16(_)
-            | Self::IncR8(_)
-            | Self::DecR8(_)
-            | Self::AddHLR16(_)
-            | Self::ComplementA
-            | Self::ComplementCarryFlag
-            | Self::RotateLeftA
-            | Self::RotateRightA
-            | Self::RotateLeftCircularA
-            | Self::RotateRightCircularA
-            | Self::SetCarryFlag
-            | Self::DAA
-            | Self::DisableInterrupts
-            | Self::EnableInterrupts
-            | Self::Return
-            | Self::ReturnCondition(_)
-            | Self::ReturnEnableInterrupts
-            | Self::Halt
-            | Self::LoadR8R8(_)
-            | Self::AddCarryR8(_)
-            | Self::SubR8(_)
-            | Self::SubCarryR8(_)
-            | Self::AndR8(_)
-            | Self::XorR8(_)
-            | Self::OrR8(_)
-            | Self::CompareR8(_)
-            | Self::LoadHighAC
-            | Self::LoadHighCA
-            | Self::LoadSpHl
-            | Self::RestartVector(_) => 1,
-            Self::LoadR8Imm8(_)
-            | Self::JrImm8
-            | Self::JrCondImm8(_)
-            | Self::AddImm8
-            | Self::AddCarryImm8
-            | Self::AndImm8
-            | Self::CompareImm8
-            | Self::OrImm8
-            | Self::SubImm8
-            | Self::SubCarryImm8
-            | Self::XorImm8
-            | Self::LoadHighAImm8
-            | Self::LoadHighImm8A
-            | Self::AddSpImm8
-            | Self::LoadHlSpImm8
-            | Self::BitCheckR8(_)
-            | Self::BitResetR8(_)
-            | Self::BitSetR8(_)
-            | Self::RotateLeftR8(_)
-            | Self::RotateLeftCircularR8(_)
-            | Self::RotateRightR8(_)
-            | Self::RotateRightCircularR8(_)
-            | Self::ShiftLeftR8(_)
-            | Self::ShiftRightR8(_)
-            | Self::SwapR8(_)
-            | Self::ShiftRightLogicallyR8(_) => 2,
-            Self::JpImm16
-            | Self::JpCondImm16(_)
-            | Self::LoadR16Imm16(_)
-            | Self::LoadImm16SP
-            | Self::LoadAImm16
-            | Self::LoadImm16A
-            | Self::Call
-            | Self::CallCondition(_) => 3,
+            Self::CallCondition(condition)
+            | Self::JpCondImm16(condition)
+            | Self::JrCondImm8(condition)
+            | Self::ReturnCondition(condition) => Some(*condition),
+            _ => None,
+        }
+    }
+
+    /// M-cycles from the metadata table, unconditional instructions take the same either way
+    pub fn get_cycles(&self, condition_met: bool) -> u8 {
+        let metadata = self.metadata();
+        if condition_met {
+            metadata.max_cycles
+        } else {
+            metadata.min_cycles
         }
     }
 
-    pub fn parse_clear_text_instructions_from_data(
-        data: &[u8],
-        detailed: bool,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    /// Bytes which aren't a valid instruction become `DB` pseudo-instructions,
+    /// so data tables between the code don't end the listing
+    pub fn parse_clear_text_instructions_from_data(data: &[u8], detailed: bool) -> Vec<String> {
         let mut instructions = Vec::new();
         let mut i = 0;
 
         while i < data.len() {
-            let prefixed = if data[i] == PREFIX_INSTRUCTION_BYTE {
+            let prefixed = data[i] == PREFIX_INSTRUCTION_BYTE && i + 1 < data.len();
+            if prefixed {
                 i += 1;
-                if i == data.len() {
-                    break;
-                }
-                true
-            } else {
-                false
-            };
+            }
 
             let current_byte = data[i];
-            let instruction = Instruction::from_byte(current_byte, prefixed)?;
+            let Ok(instruction) = Instruction::from_byte(current_byte, prefixed) else {
+                instructions.push(format!(
+                    "[0x{:02X}] {}",
+                    current_byte,
+                    data_byte_text(current_byte, detailed)
+                ));
+                i += 1;
+                continue;
+            };
             let lsb = data.get(i + 1).copied().unwrap_or(0);
             let msb = data.get(i + 2).copied().unwrap_or(0);
 
@@ -815,7 +812,7 @@ impl Instruction {
             i += instruction.get_length();
         }
 
-        Ok(instructions)
+        instructions
     }
 
     /// Takes in the 2 following bytes after the instruction
@@ -1041,3 +1038,12 @@ impl Instruction {
         }
     }
 }
+
+/// The pseudo-instruction for a byte which can't be decoded, e.g. a data table or an illegal opcode
+pub fn data_byte_text(byte: u8, detailed: bool) -> String {
+    if detailed {
+        format!("Data byte 0x{:02X}", byte)
+    } else {
+        format!("DB 0x{:02X}", byte)
+    }
+}
@@ -0,0 +1,202 @@
+//! Static information about every instruction, following the Pan Docs / gbdev opcode tables.
+//! https://gbdev.io/gb-opcodes/optables/
+use crate::enums::parameter_groups::{R16Stack, R8};
+use crate::instructions::Instruction;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FlagEffect {
+    Unaffected,
+    Set,
+    Reset,
+    /// Depends on the result of the operation
+    Affected,
+}
+
+/// How an instruction changes the flags, in the order Z N H C
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FlagEffects {
+    pub zero: FlagEffect,
+    pub subtract: FlagEffect,
+    pub half_carry: FlagEffect,
+    pub carry: FlagEffect,
+}
+
+impl FlagEffects {
+    /// Parses the notation of the opcode tables, e.g. "Z0H-": a letter for affected, 0/1 for reset/set and - for unaffected
+    const fn from_notation(notation: &[u8; 4]) -> Self {
+        const fn parse(c: u8) -> FlagEffect {
+            match c {
+                b'-' => FlagEffect::Unaffected,
+                b'0' => FlagEffect::Reset,
+                b'1' => FlagEffect::Set,
+                _ => FlagEffect::Affected,
+            }
+        }
+        Self {
+            zero: parse(notation[0]),
+            subtract: parse(notation[1]),
+            half_carry: parse(notation[2]),
+            carry: parse(notation[3]),
+        }
+    }
+
+    pub fn as_array(&self) -> [FlagEffect; 4] {
+        [self.zero, self.subtract, self.half_carry, self.carry]
+    }
+}
+
+impl Display for FlagEffects {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (effect, name) in self.as_array().iter().zip(['Z', 'N', 'H', 'C']) {
+            let c = match effect {
+                FlagEffect::Unaffected => '-',
+                FlagEffect::Set => '1',
+                FlagEffect::Reset => '0',
+                FlagEffect::Affected => name,
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InstructionMetadata {
+    pub mnemonic: &'static str,
+    /// In bytes, including the prefix byte
+    pub length: usize,
+    /// M-cycles if a condition is not met
+    pub min_cycles: u8,
+    /// M-cycles if a condition is met, same as min_cycles for unconditional instructions
+    pub max_cycles: u8,
+    pub flags: FlagEffects,
+}
+
+impl InstructionMetadata {
+    const fn new(mnemonic: &'static str, length: usize, cycles: u8, flags: &[u8; 4]) -> Self {
+        Self::conditional(mnemonic, length, cycles, cycles, flags)
+    }
+
+    const fn conditional(
+        mnemonic: &'static str,
+        length: usize,
+        min_cycles: u8,
+        max_cycles: u8,
+        flags: &[u8; 4],
+    ) -> Self {
+        Self {
+            mnemonic,
+            length,
+            min_cycles,
+            max_cycles,
+            flags: FlagEffects::from_notation(flags),
+        }
+    }
+
+    /// Metadata of every opcode, None for illegal ones
+    pub fn table(prefixed: bool) -> Vec<Option<InstructionMetadata>> {
+        (0..=255u8)
+            .map(|byte| {
+                Instruction::from_byte(byte, prefixed)
+                    .ok()
+                    .map(|instruction| instruction.metadata())
+            })
+            .collect()
+    }
+}
+
+impl Instruction {
+    pub fn metadata(&self) -> InstructionMetadata {
+        use InstructionMetadata as M;
+
+        // Accessing [HL] costs an additional memory access
+        let r8_cycles = |r8: &R8, register: u8, memory: u8| {
+            if *r8 == R8::HL {
+                memory
+            } else {
+                register
+            }
+        };
+
+        match self {
+            Self::AddHLR16(_) => M::new("ADD", 1, 2, b"-0HC"),
+            Self::AddR8(r8) => M::new("ADD", 1, r8_cycles(r8, 1, 2), b"Z0HC"),
+            Self::AddImm8 => M::new("ADD", 2, 2, b"Z0HC"),
+            Self::AddCarryR8(r8) => M::new("ADC", 1, r8_cycles(r8, 1, 2), b"Z0HC"),
+            Self::AddCarryImm8 => M::new("ADC", 2, 2, b"Z0HC"),
+            Self::AddSpImm8 => M::new("ADD", 2, 4, b"00HC"),
+            Self::AndR8(r8) => M::new("AND", 1, r8_cycles(r8, 1, 2), b"Z010"),
+            Self::AndImm8 => M::new("AND", 2, 2, b"Z010"),
+            Self::Call => M::new("CALL", 3, 6, b"----"),
+            Self::CallCondition(_) => M::conditional("CALL", 3, 3, 6, b"----"),
+            Self::CompareR8(r8) => M::new("CP", 1, r8_cycles(r8, 1, 2), b"Z1HC"),
+            Self::CompareImm8 => M::new("CP", 2, 2, b"Z1HC"),
+            Self::ComplementA => M::new("CPL", 1, 1, b"-11-"),
+            Self::ComplementCarryFlag => M::new("CCF", 1, 1, b"-00C"),
+            Self::DAA => M::new("DAA", 1, 1, b"Z-0C"),
+            Self::DecR8(r8) => M::new("DEC", 1, r8_cycles(r8, 1, 3), b"Z1H-"),
+            Self::DecR16(_) => M::new("DEC", 1, 2, b"----"),
+            Self::DisableInterrupts => M::new("DI", 1, 1, b"----"),
+            Self::EnableInterrupts => M::new("EI", 1, 1, b"----"),
+            Self::Halt | Self::LoadR8R8((R8::HL, R8::HL)) => M::new("HALT", 1, 1, b"----"),
+            Self::IncR8(r8) => M::new("INC", 1, r8_cycles(r8, 1, 3), b"Z0H-"),
+            Self::IncR16(_) => M::new("INC", 1, 2, b"----"),
+            Self::JpHL => M::new("JP", 1, 1, b"----"),
+            Self::JpImm16 => M::new("JP", 3, 4, b"----"),
+            Self::JpCondImm16(_) => M::conditional("JP", 3, 3, 4, b"----"),
+            Self::JrImm8 => M::new("JR", 2, 3, b"----"),
+            Self::JrCondImm8(_) => M::conditional("JR", 2, 2, 3, b"----"),
+            Self::LoadAR16(_) | Self::LoadR16A(_) => M::new("LD", 1, 2, b"----"),
+            Self::LoadR16Imm16(_) => M::new("LD", 3, 3, b"----"),
+            Self::LoadR8Imm8(r8) => M::new("LD", 2, r8_cycles(r8, 2, 3), b"----"),
+            Self::LoadR8R8((target, source)) => {
+                let cycles = if *target == R8::HL || *source == R8::HL {
+                    2
+                } else {
+                    1
+                };
+                M::new("LD", 1, cycles, b"----")
+            }
+            Self::LoadHighAC | Self::LoadHighCA => M::new("LDH", 1, 2, b"----"),
+            Self::LoadHighAImm8 | Self::LoadHighImm8A => M::new("LDH", 2, 3, b"----"),
+            Self::LoadAImm16 | Self::LoadImm16A => M::new("LD", 3, 4, b"----"),
+            Self::LoadImm16SP => M::new("LD", 3, 5, b"----"),
+            Self::LoadHlSpImm8 => M::new("LD", 2, 3, b"00HC"),
+            Self::LoadSpHl => M::new("LD", 1, 2, b"----"),
+            Self::Nop => M::new("NOP", 1, 1, b"----"),
+            Self::OrR8(r8) => M::new("OR", 1, r8_cycles(r8, 1, 2), b"Z000"),
+            Self::OrImm8 => M::new("OR", 2, 2, b"Z000"),
+            Self::PopR16(R16Stack::AF) => M::new("POP", 1, 3, b"ZNHC"),
+            Self::PopR16(_) => M::new("POP", 1, 3, b"----"),
+            Self::PushR16(_) => M::new("PUSH", 1, 4, b"----"),
+            Self::RestartVector(_) => M::new("RST", 1, 4, b"----"),
+            Self::Return => M::new("RET", 1, 4, b"----"),
+            Self::ReturnCondition(_) => M::conditional("RET", 1, 2, 5, b"----"),
+            Self::ReturnEnableInterrupts => M::new("RETI", 1, 4, b"----"),
+            Self::RotateLeftA => M::new("RLA", 1, 1, b"000C"),
+            Self::RotateRightA => M::new("RRA", 1, 1, b"000C"),
+            Self::RotateLeftCircularA => M::new("RLCA", 1, 1, b"000C"),
+            Self::RotateRightCircularA => M::new("RRCA", 1, 1, b"000C"),
+            Self::SetCarryFlag => M::new("SCF", 1, 1, b"-001"),
+            Self::SubR8(r8) => M::new("SUB", 1, r8_cycles(r8, 1, 2), b"Z1HC"),
+            Self::SubImm8 => M::new("SUB", 2, 2, b"Z1HC"),
+            Self::SubCarryR8(r8) => M::new("SBC", 1, r8_cycles(r8, 1, 2), b"Z1HC"),
+            Self::SubCarryImm8 => M::new("SBC", 2, 2, b"Z1HC"),
+            Self::XorR8(r8) => M::new("XOR", 1, r8_cycles(r8, 1, 2), b"Z000"),
+            Self::XorImm8 => M::new("XOR", 2, 2, b"Z000"),
+            Self::BitCheckR8((_, r8)) => M::new("BIT", 2, r8_cycles(r8, 2, 3), b"Z01-"),
+            Self::BitResetR8((_, r8)) => M::new("RES", 2, r8_cycles(r8, 2, 4), b"----"),
+            Self::BitSetR8((_, r8)) => M::new("SET", 2, r8_cycles(r8, 2, 4), b"----"),
+            Self::RotateLeftR8(r8) => M::new("RL", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::RotateLeftCircularR8(r8) => M::new("RLC", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::RotateRightR8(r8) => M::new("RR", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::RotateRightCircularR8(r8) => M::new("RRC", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::ShiftLeftR8(r8) => M::new("SLA", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::ShiftRightR8(r8) => M::new("SRA", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+            Self::SwapR8(r8) => M::new("SWAP", 2, r8_cycles(r8, 2, 4), b"Z000"),
+            Self::ShiftRightLogicallyR8(r8) => M::new("SRL", 2, r8_cycles(r8, 2, 4), b"Z00C"),
+        }
+    }
+}
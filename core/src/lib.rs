@@ -0,0 +1,37 @@
+//! The Game Boy emulation without any frontend: a [`GameBoy`] runs a [`Cartridge`] one instruction or frame at a time,
+//! buttons are pressed and released on it and the frames are read from its frame buffer.
+//! Frontends like the GUI of lemon-gb-frontend build on top of it.
+//!
+//! The types needed to embed the emulator are re-exported here. The modules below them are public for tooling
+//! like debuggers, which look into the components, and for the [`input`] layer shared by every frontend.
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs an allocator, e.g. for handhelds built
+//! around a microcontroller. Loading and storing files, binary save states and the memory heatmap need `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod disassembler;
+pub mod enums;
+pub mod game_boy;
+mod helpers;
+pub mod input;
+pub mod instructions;
+pub mod logging;
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+pub use enums::button::{Button, Buttons};
+pub use game_boy::components::cartridge::rom_builder::RomBuilder;
+pub use game_boy::components::cartridge::Cartridge;
+pub use game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+pub use game_boy::components::ppu::{VBlankInfo, SCREEN_HEIGHT, SCREEN_WIDTH};
+pub use game_boy::config::GameBoyConfig;
+pub use game_boy::cycles::Cycles;
+pub use game_boy::hardware_model::HardwareModel;
+pub use game_boy::GameBoy;
+/// Decoding of the 2 bits per pixel tile format, e.g. for tile viewers
+pub use helpers::graphics;
+pub use helpers::listeners::ListenerId;
+pub use input::{InputLayer, InputMacro};
@@ -0,0 +1,66 @@
+//! Every subsystem logs to its own target (e.g. `lemon_gb::cpu`), so frontends can give each its own verbosity
+//! and tracing the PPU doesn't drown in a log line per CPU instruction.
+//! The core only emits through the `log` macros, installing a logger is up to the frontend.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    Cpu,
+    Ppu,
+    Mmu,
+    Interrupt,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 4] = [
+        Subsystem::Cpu,
+        Subsystem::Ppu,
+        Subsystem::Mmu,
+        Subsystem::Interrupt,
+    ];
+
+    /// The log target, pass it to the log macros via `target:`
+    pub const fn target(&self) -> &'static str {
+        match self {
+            Subsystem::Cpu => "lemon_gb::cpu",
+            Subsystem::Ppu => "lemon_gb::ppu",
+            Subsystem::Mmu => "lemon_gb::mmu",
+            Subsystem::Interrupt => "lemon_gb::interrupt",
+        }
+    }
+
+    pub fn from_target(target: &str) -> Option<Subsystem> {
+        Self::ALL.into_iter().find(|subsystem| {
+            target
+                .strip_prefix(subsystem.target())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+}
+
+impl Display for Subsystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Subsystem::Cpu => "cpu",
+            Subsystem::Ppu => "ppu",
+            Subsystem::Mmu => "mmu",
+            Subsystem::Interrupt => "interrupt",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Subsystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|subsystem| subsystem.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown log subsystem '{s}'"))
+    }
+}
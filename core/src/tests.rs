@@ -0,0 +1,70 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use std::fs::create_dir;
+use std::path::PathBuf;
+
+mod test_battery_save;
+mod test_camera;
+mod test_cartridge_type;
+mod test_cheats;
+mod test_color_scheme_preset;
+mod test_colorization;
+mod test_component;
+mod test_counters;
+pub mod test_cpu_fuzz;
+mod test_cpu_registers;
+mod test_cycles;
+mod test_diagnostics;
+mod test_disassembler;
+mod test_disassembly_listing;
+mod test_dma;
+mod test_doctor;
+mod test_frame_access;
+mod test_graphics;
+mod test_halt;
+mod test_hardware_model;
+mod test_input;
+mod test_input_stats;
+mod test_instruction_metadata;
+mod test_instructions;
+mod test_interrupts;
+mod test_io_hooks;
+mod test_io_registers;
+mod test_joypad;
+mod test_logging;
+mod test_mbc;
+mod test_mbc7;
+mod test_memory_regions;
+mod test_memory_snapshot;
+mod test_memory_stats;
+mod test_mmu_fuzz;
+mod test_movie;
+mod test_open_bus;
+mod test_peek_poke;
+mod test_ppu;
+mod test_replay;
+mod test_rom_builder;
+pub mod test_roms;
+mod test_sample_buffer;
+mod test_save_load;
+mod test_serial;
+mod test_thumbnail;
+mod test_timer;
+#[cfg(feature = "opcode-coverage")]
+mod test_zz_opcode_coverage;
+
+/// The test output directory in the workspace root
+pub fn setup_test_dir() -> PathBuf {
+    let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../test");
+    if !test_dir.exists() {
+        create_dir(&test_dir).unwrap();
+    }
+    test_dir
+}
+
+/// Starts one of the test ROMs with the default config
+pub fn load_test_rom(name: &str) -> GameBoy {
+    let cartridge = Cartridge::load(test_rom_file_path().join(name)).unwrap();
+    GameBoy::initialize(&cartridge).unwrap()
+}
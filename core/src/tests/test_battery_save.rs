@@ -0,0 +1,128 @@
+use crate::game_boy::battery_save::{BatterySave, RtcFooter, RtcRegisters};
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::RAM_BANK_SIZE;
+use crate::game_boy::GameBoy;
+use crate::tests::{load_test_rom, setup_test_dir};
+use rstest::rstest;
+
+fn ram() -> Vec<u8> {
+    (0..RAM_BANK_SIZE).map(|i| (i % 251) as u8).collect()
+}
+
+/// RTC footer as written by BGB: current and latched registers as u32, then the timestamp
+fn bgb_footer(legacy: bool) -> Vec<u8> {
+    let mut footer = Vec::new();
+    for register in [12u32, 34, 5, 0x2A, 0x01, 11, 33, 4, 0x29, 0x01] {
+        footer.extend_from_slice(&register.to_le_bytes());
+    }
+    if legacy {
+        footer.extend_from_slice(&0x5F5E_1000u32.to_le_bytes());
+    } else {
+        footer.extend_from_slice(&0x5F5E_1000u64.to_le_bytes());
+    }
+    footer
+}
+
+fn expected_footer() -> RtcFooter {
+    RtcFooter {
+        current: RtcRegisters {
+            seconds: 12,
+            minutes: 34,
+            hours: 5,
+            days_low: 0x2A,
+            days_high: 0x01,
+        },
+        latched: RtcRegisters {
+            seconds: 11,
+            minutes: 33,
+            hours: 4,
+            days_low: 0x29,
+            days_high: 0x01,
+        },
+        timestamp: 0x5F5E_1000,
+    }
+}
+
+/// A ROM only cartridge with a single bank of RAM
+fn build_game_boy() -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x149] = 0x02; // 8 KiB RAM
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+#[test]
+fn test_reset_keeps_cartridge_ram() {
+    let mut game_boy = build_game_boy();
+    game_boy.write_memory(0xA000, 0x42);
+    game_boy.write_memory(0xC000, 0x42);
+
+    game_boy.reset();
+    assert_eq!(game_boy.read_memory(0xA000), 0x42);
+    assert_eq!(game_boy.read_memory(0xC000), 0x00);
+}
+
+/// The footer is either missing, in the current format or in the legacy format with a 32-bit timestamp
+#[rstest]
+#[case::plain(None)]
+#[case::bgb_footer(Some(false))]
+#[case::legacy_footer(Some(true))]
+fn test_parse_battery_save(#[case] legacy_footer: Option<bool>) {
+    let mut data = ram();
+    if let Some(legacy) = legacy_footer {
+        data.extend(bgb_footer(legacy));
+    }
+
+    let save = BatterySave::parse(&data, RAM_BANK_SIZE).unwrap();
+    assert_eq!(save.ram, ram());
+    assert_eq!(save.rtc, legacy_footer.map(|_| expected_footer()));
+}
+
+#[rstest]
+#[case::unknown_trailer(RAM_BANK_SIZE + 16)]
+#[case::truncated_footer(RAM_BANK_SIZE + 40)]
+#[case::too_small(RAM_BANK_SIZE - 1)]
+fn test_parse_battery_save_rejects_unexpected_sizes(#[case] size: usize) {
+    assert!(BatterySave::parse(&vec![0; size], RAM_BANK_SIZE).is_err());
+}
+
+#[test]
+fn test_battery_save_file_round_trip() {
+    let path = setup_test_dir().join("battery.sav");
+
+    let mut data = ram();
+    data.extend(bgb_footer(false));
+    std::fs::write(&path, &data).unwrap();
+
+    let save = BatterySave::load(&path, RAM_BANK_SIZE).unwrap();
+    assert_eq!(save.to_bytes(), data);
+
+    let mut game_boy = build_game_boy();
+    game_boy.load_battery_save(&save).unwrap();
+    assert_eq!(game_boy.battery_save().ram, ram());
+    assert_eq!(game_boy.battery_save().rtc, None);
+}
+
+#[test]
+fn test_load_battery_save_with_wrong_ram_size() {
+    let mut game_boy = build_game_boy();
+    let save = BatterySave {
+        ram: vec![0; 2 * RAM_BANK_SIZE],
+        rtc: None,
+    };
+    assert!(game_boy.load_battery_save(&save).is_err());
+    assert_eq!(game_boy.battery_save().ram, vec![0; RAM_BANK_SIZE]);
+}
+
+#[test]
+fn test_load_battery_save_file() {
+    let path = setup_test_dir().join("import.sav");
+    std::fs::write(&path, ram()).unwrap();
+
+    let mut game_boy = build_game_boy();
+    game_boy.load_battery_save_file(&path).unwrap();
+    assert_eq!(game_boy.battery_save().ram, ram());
+
+    let mut without_ram = load_test_rom("cpu_instrs.gb");
+    let error = without_ram.load_battery_save_file(&path).unwrap_err();
+    assert!(error.to_string().contains("no cartridge RAM"), "{error}");
+}
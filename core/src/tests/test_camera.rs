@@ -0,0 +1,216 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::mbc::camera::{CAMERA_HEIGHT, CAMERA_WIDTH};
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+
+/// Exposure at which the sensor image is taken as it is
+const NEUTRAL_EXPOSURE: u16 = 0x0800;
+/// 129792 T-cycles to read the sensor, 2048 without the N flag and 64 per exposure step
+const NEUTRAL_CAPTURE_CYCLES: u32 = 129_792 + 2048 + NEUTRAL_EXPOSURE as u32 * 64;
+
+/// Pocket Camera with 64 ROM banks that start with their own index and 128 KiB of RAM
+fn build_cartridge() -> Cartridge {
+    let mut rom = vec![0u8; 64 * ROM_BANK_SIZE];
+    for bank in 0..64 {
+        rom[bank * ROM_BANK_SIZE] = bank as u8;
+    }
+    rom[0x147] = 0xFC; // Pocket Camera
+    rom[0x148] = 0x05; // 1 MiB ROM
+    rom[0x149] = 0x04; // 128 KiB RAM
+    Cartridge::from_bytes(&rom).unwrap()
+}
+
+fn build_mmu() -> MMU {
+    MMU::initialize(&build_cartridge()).unwrap()
+}
+
+/// The same 3 thresholds for every position of the dithering matrix, the registers stay mapped
+fn setup_capture(mmu: &mut MMU, thresholds: [u8; 3], invert: bool) {
+    mmu.write(0x4000, 0x10);
+    mmu.write(0xA002, (NEUTRAL_EXPOSURE >> 8) as u8);
+    mmu.write(0xA003, NEUTRAL_EXPOSURE as u8);
+    mmu.write(0xA004, if invert { 0b1000 } else { 0 });
+    for position in 0..16 {
+        for (i, &threshold) in thresholds.iter().enumerate() {
+            mmu.write(0xA006 + position * 3 + i as u16, threshold);
+        }
+    }
+}
+
+fn capture(mmu: &mut MMU) {
+    mmu.write(0xA000, 0x01);
+    mmu.step_cartridge(Cycles::from_t(NEUTRAL_CAPTURE_CYCLES));
+}
+
+/// The 2 bit color of a pixel in the captured tiles of RAM bank 0
+fn captured_color(mmu: &mut MMU, x: usize, y: usize) -> u8 {
+    mmu.write(0x4000, 0x00);
+    let tile = (y / 8) * (CAMERA_WIDTH / 8) + x / 8;
+    let row = 0xA100 + (tile * 16 + (y % 8) * 2) as u16;
+    let bit = 7 - (x % 8);
+    let low = (mmu.read(row) >> bit) & 1;
+    let high = (mmu.read(row + 1) >> bit) & 1;
+    high << 1 | low
+}
+
+#[rstest]
+#[case(0x05, 0x05)]
+#[case(0x3F, 0x3F)]
+#[case(0x45, 0x05)]
+#[case(0x00, 0x00)]
+fn test_rom_bank(#[case] value: u8, #[case] expected_bank: u8) {
+    let mut mmu = build_mmu();
+    assert_eq!(mmu.read(0x4000), 1);
+    mmu.write(0x2000, value);
+    assert_eq!(mmu.read(0x4000), expected_bank);
+    assert_eq!(mmu.read(0x0000), 0);
+}
+
+#[test]
+fn test_ram_is_readable_without_enable() {
+    let mut mmu = build_mmu();
+    mmu.write(0x4000, 0x03);
+    mmu.write(0xA000, 0x42);
+    assert_eq!(mmu.read(0xA000), 0x00, "Writes need the enable");
+
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0xA000, 0x42);
+    mmu.write(0x0000, 0x00);
+    assert_eq!(mmu.read(0xA000), 0x42);
+
+    mmu.write(0x4000, 0x04);
+    assert_eq!(mmu.read(0xA000), 0x00, "Other bank");
+    mmu.write(0x4000, 0x03);
+    assert_eq!(mmu.read(0xA000), 0x42);
+}
+
+#[test]
+fn test_register_bank_hides_ram() {
+    let mut mmu = build_mmu();
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0xA002, 0x42);
+
+    mmu.write(0x4000, 0x10);
+    assert_eq!(mmu.read(0xA002), 0x00, "Only A000 can be read");
+    mmu.write(0xA002, 0x99);
+    assert_eq!(mmu.read(0xA000), 0x00, "No capture running");
+
+    mmu.write(0x4000, 0x00);
+    assert_eq!(
+        mmu.read(0xA002),
+        0x42,
+        "The register write didn't reach RAM"
+    );
+}
+
+#[test]
+fn test_capture_is_busy_until_finished() {
+    let mut mmu = build_mmu();
+    setup_capture(&mut mmu, [0x40, 0x80, 0xC0], false);
+    mmu.write(0xA000, 0x07);
+    assert_eq!(mmu.read(0xA000), 0x07);
+
+    mmu.step_cartridge(Cycles::from_t(NEUTRAL_CAPTURE_CYCLES - 4));
+    assert_eq!(mmu.read(0xA000) & 1, 1);
+    mmu.step_cartridge(Cycles::from_t(4));
+    assert_eq!(mmu.read(0xA000), 0x06);
+}
+
+#[test]
+fn test_n_flag_shortens_capture() {
+    let mut mmu = build_mmu();
+    setup_capture(&mut mmu, [0x40, 0x80, 0xC0], false);
+    mmu.write(0xA001, 0x80);
+    mmu.write(0xA000, 0x01);
+    mmu.step_cartridge(Cycles::from_t(NEUTRAL_CAPTURE_CYCLES - 2048));
+    assert_eq!(mmu.read(0xA000) & 1, 0);
+}
+
+#[rstest]
+#[case::black(0x00, false, 3)]
+#[case::dark_gray(0x50, false, 2)]
+#[case::light_gray(0xA0, false, 1)]
+#[case::white(0xFF, false, 0)]
+#[case::inverted_black(0x00, true, 0)]
+#[case::inverted_white(0xFF, true, 3)]
+fn test_capture_dithers_brightness(
+    #[case] brightness: u8,
+    #[case] invert: bool,
+    #[case] expected_color: u8,
+) {
+    let mut mmu = build_mmu();
+    mmu.set_camera_image(&vec![brightness; CAMERA_WIDTH * CAMERA_HEIGHT])
+        .unwrap();
+    setup_capture(&mut mmu, [0x40, 0x80, 0xC0], invert);
+    capture(&mut mmu);
+
+    for (x, y) in [(0, 0), (7, 3), (64, 56), (127, 111)] {
+        assert_eq!(captured_color(&mut mmu, x, y), expected_color, "({x}, {y})");
+    }
+}
+
+#[test]
+fn test_capture_keeps_pixel_positions() {
+    let mut mmu = build_mmu();
+    let image: Vec<u8> = (0..CAMERA_WIDTH * CAMERA_HEIGHT)
+        .map(|i| {
+            if i % CAMERA_WIDTH < 10 && i / CAMERA_WIDTH < 20 {
+                0x00
+            } else {
+                0xFF
+            }
+        })
+        .collect();
+    mmu.set_camera_image(&image).unwrap();
+    setup_capture(&mut mmu, [0x40, 0x80, 0xC0], false);
+    capture(&mut mmu);
+
+    assert_eq!(captured_color(&mut mmu, 9, 19), 3);
+    assert_eq!(captured_color(&mut mmu, 10, 19), 0);
+    assert_eq!(captured_color(&mut mmu, 9, 20), 0);
+    assert_eq!(mmu.read(0xA0FF), 0x00, "Before the captured tiles");
+    assert_eq!(mmu.read(0xAF00), 0x00, "After the captured tiles");
+}
+
+#[test]
+fn test_capture_without_image_is_gray() {
+    let mut mmu = build_mmu();
+    setup_capture(&mut mmu, [0x40, 0x80, 0xC0], false);
+    capture(&mut mmu);
+    assert_eq!(captured_color(&mut mmu, 0, 0), 1);
+}
+
+#[rstest]
+#[case::too_small(CAMERA_WIDTH * CAMERA_HEIGHT - 1)]
+#[case::too_large(CAMERA_WIDTH * CAMERA_HEIGHT + 1)]
+fn test_camera_image_size_is_checked(#[case] size: usize) {
+    let mut mmu = build_mmu();
+    assert!(mmu.set_camera_image(&vec![0; size]).is_err());
+    assert_eq!(mmu.get_camera_image(), None);
+}
+
+#[test]
+fn test_camera_image_needs_camera() {
+    let mut rom = vec![0u8; 2 * ROM_BANK_SIZE];
+    rom[0x147] = 0x00;
+    let mut game_boy = GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    assert!(game_boy
+        .set_camera_image(&vec![0; CAMERA_WIDTH * CAMERA_HEIGHT])
+        .is_err());
+}
+
+/// The image is an input, so it stays when a state is loaded or the Game Boy is reset
+#[test]
+fn test_camera_image_survives_load_state_and_reset() {
+    let mut game_boy = GameBoy::initialize(&build_cartridge()).unwrap();
+    let state = game_boy.save();
+    let image = vec![0x12; CAMERA_WIDTH * CAMERA_HEIGHT];
+    game_boy.set_camera_image(&image).unwrap();
+
+    game_boy.load_state(state).unwrap();
+    assert_eq!(game_boy.get_camera_image(), Some(image.as_slice()));
+    game_boy.reset();
+    assert_eq!(game_boy.get_camera_image(), Some(image.as_slice()));
+}
@@ -0,0 +1,78 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::types::{CartridgeType, MbcType};
+use rstest::rstest;
+
+/// (ram, battery, rtc, rumble)
+#[rstest]
+#[case::rom_only(CartridgeType::RomOnly, (false, false, false, false))]
+#[case::mbc1_ram_battery(CartridgeType::MBC1RamBattery, (true, true, false, false))]
+#[case::mbc2(CartridgeType::MBC2, (true, false, false, false))]
+#[case::mbc3_timer_battery(CartridgeType::MBC3TimerBattery, (false, true, true, false))]
+#[case::mbc3_timer_ram_battery(CartridgeType::MBC3TimerRamBattery, (true, true, true, false))]
+#[case::mbc5_rumble(CartridgeType::MBC5Rumble, (false, false, false, true))]
+#[case::mbc5_rumble_ram_battery(CartridgeType::MBC5RumbleRamBattery, (true, true, false, true))]
+#[case::mbc5_ram_battery(CartridgeType::MBC5RamBattery, (true, true, false, false))]
+#[case::mbc7(CartridgeType::MBC7SensorRumbleRamBattery, (true, true, false, true))]
+#[case::camera(CartridgeType::PocketCamera, (true, true, false, false))]
+#[case::huc3(CartridgeType::HuC3, (true, true, true, false))]
+fn test_capability_flags(
+    #[case] cartridge_type: CartridgeType,
+    #[case] expected: (bool, bool, bool, bool),
+) {
+    let flags = (
+        cartridge_type.has_ram(),
+        cartridge_type.has_battery(),
+        cartridge_type.has_rtc(),
+        cartridge_type.has_rumble(),
+    );
+    assert_eq!(flags, expected);
+}
+
+#[rstest]
+#[case::rom_only(CartridgeType::RomOnly, MbcType::None)]
+#[case::mmm01(CartridgeType::MMM01Ram, MbcType::MBC1)]
+#[case::mbc3(CartridgeType::MBC3TimerRamBattery, MbcType::MBC3)]
+#[case::mbc5(CartridgeType::MBC5RumbleRam, MbcType::MBC5)]
+#[case::mbc7(CartridgeType::MBC7SensorRumbleRamBattery, MbcType::MBC7)]
+#[case::camera(CartridgeType::PocketCamera, MbcType::Camera)]
+#[case::huc1(
+    CartridgeType::HuC1RamBattery,
+    MbcType::Unsupported(CartridgeType::HuC1RamBattery)
+)]
+fn test_mapper_kind(#[case] cartridge_type: CartridgeType, #[case] expected: MbcType) {
+    assert_eq!(cartridge_type.mapper_kind(), expected);
+}
+
+#[test]
+fn test_capabilities() {
+    assert_eq!(
+        CartridgeType::MBC3TimerRamBattery.capabilities(),
+        vec!["RAM", "Battery", "RTC"]
+    );
+    assert!(CartridgeType::RomOnly.capabilities().is_empty());
+}
+
+#[test]
+fn test_describe_header() {
+    let mut rom = RomBuilder::new().title("INFO").build();
+    rom[0x147] = CartridgeType::MBC5RumbleRamBattery as u8;
+    rom[0x149] = 0x03;
+    let description = CartridgeHeader::parse(&rom).unwrap().describe();
+
+    assert!(description.starts_with("Title:        INFO\n"));
+    assert!(description.contains("Type:         MBC5RumbleRamBattery (MBC5)\n"));
+    assert!(description.contains("Capabilities: RAM, Battery, Rumble\n"));
+    assert!(description.contains("ROM:          2 banks, 32 KiB\n"));
+    assert!(description.contains("RAM:          4 banks, 32 KiB\n"));
+}
+
+#[test]
+fn test_describe_unsupported_mapper() {
+    let mut rom = RomBuilder::new().build();
+    rom[0x147] = CartridgeType::MBC3TimerBattery as u8;
+    let description = CartridgeHeader::parse(&rom).unwrap().describe();
+
+    assert!(description.contains("Type:         MBC3TimerBattery (MBC3, not emulated)\n"));
+    assert!(description.contains("Capabilities: Battery, RTC\n"));
+}
@@ -0,0 +1,140 @@
+use crate::game_boy::cheats::{Cheat, CheatCode, RamWrite, RomPatch};
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+
+/// 0x42 in place of 0x11 at 0x0200
+const PATCH_CODE: &str = "422-00F-A0E";
+
+#[rstest]
+#[case::game_genie(
+    "00A-17B-C49",
+    CheatCode::GameGenie(RomPatch { address: 0x4A17, value: 0x00, compare: Some(0xC8) })
+)]
+#[case::without_compare(
+    "3EA-17B",
+    CheatCode::GameGenie(RomPatch { address: 0x4A17, value: 0x3E, compare: None })
+)]
+#[case::lower_case(
+    "3ea-17b",
+    CheatCode::GameGenie(RomPatch { address: 0x4A17, value: 0x3E, compare: None })
+)]
+#[case::game_shark(
+    "010238CD",
+    CheatCode::GameShark(RamWrite { bank: 0x01, address: 0xCD38, value: 0x02 })
+)]
+fn test_parse_cheat_code(#[case] code: &str, #[case] expected: CheatCode) {
+    assert_eq!(CheatCode::parse(code).unwrap(), expected);
+}
+
+#[rstest]
+#[case::not_hex("XYZ-123-456")]
+#[case::too_short("0102")]
+#[case::game_shark_with_dashes("010-238-CD")]
+#[case::game_genie_outside_rom("00A-177")]
+#[case::game_shark_to_rom("01020040")]
+fn test_parse_invalid_cheat_code(#[case] code: &str) {
+    assert!(CheatCode::parse(code).is_err());
+}
+
+#[test]
+fn test_rom_patch_compare() {
+    let patch = RomPatch {
+        address: 0x4000,
+        value: 0x42,
+        compare: Some(0x11),
+    };
+    assert_eq!(patch.apply(0x4000, 0x11), Some(0x42));
+    assert_eq!(patch.apply(0x4000, 0x12), None);
+    assert_eq!(patch.apply(0x4001, 0x11), None);
+}
+
+#[test]
+fn test_new_cheat_is_normalized() {
+    let cheat = Cheat::new(" 3ea-17b ").unwrap();
+    assert_eq!(cheat.code, "3EA-17B");
+    assert!(cheat.enabled);
+    assert!(Cheat::new("nonsense").is_err());
+}
+
+/// Copies the ROM byte at 0x0200 to 0xC000 forever
+fn patched_game_boy() -> GameBoy {
+    // LD A, (0x0200) / LD (0xC000), A / JR to the start
+    let rom = RomBuilder::new()
+        .bytes(0x0200, &[0x11])
+        .program(&[0xFA, 0x00, 0x02, 0xEA, 0x00, 0xC0, 0x18, 0xF8])
+        .build();
+    GameBoy::headless(&rom).unwrap()
+}
+
+#[rstest]
+#[case::matching_compare(PATCH_CODE, 0x42)]
+#[case::without_compare("422-00F", 0x42)]
+#[case::other_compare("422-00F-A0F", 0x11)]
+fn test_game_genie_patches_rom_reads(#[case] code: &str, #[case] expected: u8) {
+    let mut game_boy = patched_game_boy();
+    game_boy.set_cheats(&[Cheat::new(code).unwrap()]).unwrap();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC000), expected);
+    // Debuggers see the original ROM
+    assert_eq!(game_boy.peek(0x0200), 0x11);
+}
+
+#[test]
+fn test_game_shark_writes_every_frame() {
+    let mut game_boy = patched_game_boy();
+    game_boy
+        .set_cheats(&[Cheat::new("019910C0").unwrap()])
+        .unwrap();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC010), 0x99);
+    game_boy.write_memory(0xC010, 0x00);
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC010), 0x99);
+}
+
+#[test]
+fn test_only_enabled_cheats_are_active() {
+    let mut game_boy = patched_game_boy();
+    let mut disabled = Cheat::new("019910C0").unwrap();
+    disabled.enabled = false;
+    game_boy
+        .set_cheats(&[Cheat::new(PATCH_CODE).unwrap(), disabled])
+        .unwrap();
+    assert_eq!(
+        game_boy.get_active_cheats(),
+        [CheatCode::parse(PATCH_CODE).unwrap()]
+    );
+
+    game_boy.set_cheats(&[]).unwrap();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC000), 0x11);
+}
+
+#[test]
+fn test_invalid_cheat_keeps_active_cheats() {
+    let mut game_boy = patched_game_boy();
+    game_boy
+        .set_cheats(&[Cheat::new(PATCH_CODE).unwrap()])
+        .unwrap();
+    let invalid = Cheat {
+        code: "INVALID".to_string(),
+        name: String::new(),
+        enabled: true,
+    };
+    assert!(game_boy.set_cheats(&[invalid]).is_err());
+    assert_eq!(game_boy.get_active_cheats().len(), 1);
+}
+
+#[test]
+fn test_cheats_survive_reset_and_load_state() {
+    let mut game_boy = patched_game_boy();
+    let state = game_boy.save();
+    game_boy
+        .set_cheats(&[Cheat::new(PATCH_CODE).unwrap()])
+        .unwrap();
+    game_boy.reset();
+    game_boy.load_state(state).unwrap();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC000), 0x42);
+}
@@ -0,0 +1,128 @@
+use crate::game_boy::components::ppu::color_scheme::{ColorScheme, Layer};
+use crate::game_boy::components::ppu::color_scheme_preset::ColorSchemePreset;
+use rstest::rstest;
+
+/// Linear RGB matrices simulating dichromacy, from Viénot, Brettel and Mollon (1999)
+const PROTANOPIA: [[f64; 3]; 3] = [
+    [0.11238, 0.88762, 0.0],
+    [0.11238, 0.88762, 0.0],
+    [0.00401, -0.00401, 1.0],
+];
+const DEUTERANOPIA: [[f64; 3]; 3] = [
+    [0.29275, 0.70725, 0.0],
+    [0.29275, 0.70725, 0.0],
+    [-0.02234, 0.02234, 1.0],
+];
+const NORMAL_VISION: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+fn to_linear(channel: u8) -> f64 {
+    let channel = channel as f64 / 255.0;
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn to_srgb(channel: f64) -> f64 {
+    let channel = channel.clamp(0.0, 1.0);
+    let encoded = if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    };
+    encoded * 255.0
+}
+
+/// The linear RGB color as seen with the vision deficiency
+fn simulate(vision: &[[f64; 3]; 3], color: [u8; 4]) -> [f64; 3] {
+    let linear = [color[0], color[1], color[2]].map(to_linear);
+    vision.map(|row| (0..3).map(|i| row[i] * linear[i]).sum())
+}
+
+fn relative_luminance(linear: [f64; 3]) -> f64 {
+    0.2126 * linear[0] + 0.7152 * linear[1] + 0.0722 * linear[2]
+}
+
+/// The WCAG contrast ratio, from 1 for the same brightness to 21 for black on white
+fn contrast_ratio(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Euclidean distance in 8 bit sRGB
+fn color_distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (0..3)
+        .map(|i| (to_srgb(a[i]) - to_srgb(b[i])).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Every shade is darker than the one before it, by at least the contrast ratio
+fn assert_shades_separated(color_scheme: &ColorScheme, vision: &[[f64; 3]; 3], minimum: f64) {
+    for layer in Layer::ALL {
+        let shades = color_scheme
+            .get_shades(layer)
+            .map(|color| simulate(vision, color));
+        for pair in shades.windows(2) {
+            assert!(
+                relative_luminance(pair[0]) > relative_luminance(pair[1]),
+                "{layer:?} gets lighter"
+            );
+            let ratio = contrast_ratio(pair[0], pair[1]);
+            assert!(ratio >= minimum, "{layer:?} contrast {ratio}");
+        }
+    }
+}
+
+#[rstest]
+#[case::deuteranopia(ColorSchemePreset::Deuteranopia, DEUTERANOPIA)]
+#[case::deuteranopia_normal_vision(ColorSchemePreset::Deuteranopia, NORMAL_VISION)]
+#[case::protanopia(ColorSchemePreset::Protanopia, PROTANOPIA)]
+#[case::protanopia_normal_vision(ColorSchemePreset::Protanopia, NORMAL_VISION)]
+fn test_color_blind_presets(#[case] preset: ColorSchemePreset, #[case] vision: [[f64; 3]; 3]) {
+    let color_scheme = preset.get_color_scheme();
+    assert_shades_separated(&color_scheme, &vision, 1.75);
+
+    // Sprites stay distinguishable from the background and each other in the middle shades
+    for shade in 1..=2 {
+        for (a, b) in [
+            (Layer::Background, Layer::Object0),
+            (Layer::Background, Layer::Object1),
+            (Layer::Object0, Layer::Object1),
+        ] {
+            let a_color = simulate(&vision, color_scheme.get_shades(a)[shade]);
+            let b_color = simulate(&vision, color_scheme.get_shades(b)[shade]);
+            let distance = color_distance(a_color, b_color);
+            assert!(
+                distance >= 50.0,
+                "{a:?} and {b:?} shade {shade}: {distance}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_high_contrast_preset() {
+    let color_scheme = ColorSchemePreset::HighContrast.get_color_scheme();
+    assert_shades_separated(&color_scheme, &NORMAL_VISION, 2.7);
+    assert_eq!(color_scheme.background[0], [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(color_scheme.background[3], [0x00, 0x00, 0x00, 0xFF]);
+}
+
+#[test]
+fn test_presets_cycle_and_are_found() {
+    let mut preset = ColorSchemePreset::default();
+    assert_eq!(preset.get_color_scheme(), ColorScheme::default());
+    for _ in 0..ColorSchemePreset::ALL.len() {
+        assert_eq!(
+            ColorSchemePreset::find(&preset.get_color_scheme()),
+            Some(preset)
+        );
+        preset = preset.next();
+    }
+    assert_eq!(preset, ColorSchemePreset::Pocket);
+
+    let custom = ColorScheme::monochrome([[0x12, 0x34, 0x56, 0xFF]; 4]);
+    assert_eq!(ColorSchemePreset::find(&custom), None);
+}
@@ -0,0 +1,128 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::ppu::color_correction::ColorCorrection;
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use crate::game_boy::components::ppu::colorization::{
+    colorize, is_nintendo_game, title_checksum, DEFAULT_COLORIZATION,
+};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+
+/// A ROM with the given title and licensee codes, everything else is zero
+fn build_rom(title: &str, old_licensee: u8, new_licensee: &[u8; 2]) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x134..0x134 + title.len()].copy_from_slice(title.as_bytes());
+    rom[0x144..=0x145].copy_from_slice(new_licensee);
+    rom[0x14B] = old_licensee;
+    rom
+}
+
+fn light_background_shade(color_scheme: &ColorScheme) -> [u8; 4] {
+    color_scheme.background[1]
+}
+
+#[rstest]
+#[case("TETRIS", 0xDB)]
+#[case("ZELDA", 0x70)]
+#[case("POKEMON RED", 0x14)]
+#[case("POKEMON BLUE", 0x61)]
+#[case("", 0x00)]
+fn test_title_checksum(#[case] title: &str, #[case] expected: u8) {
+    assert_eq!(title_checksum(&build_rom(title, 0x01, b"00")), expected);
+}
+
+#[rstest]
+#[case::old_nintendo(0x01, b"00", true)]
+#[case::old_other(0x08, b"01", false)]
+#[case::new_nintendo(0x33, b"01", true)]
+#[case::new_other(0x33, b"08", false)]
+fn test_is_nintendo_game(
+    #[case] old_licensee: u8,
+    #[case] new_licensee: &[u8; 2],
+    #[case] expected: bool,
+) {
+    let rom = build_rom("TETRIS", old_licensee, new_licensee);
+    assert_eq!(is_nintendo_game(&rom), expected);
+}
+
+#[rstest]
+#[case::red("POKEMON RED", 0x01, b"00", [0xFF, 0x84, 0x84, 0xFF])]
+#[case::blue("POKEMON BLUE", 0x33, b"01", [0x63, 0xA5, 0xFF, 0xFF])]
+#[case::not_in_table("SOME GAME", 0x01, b"00", [0x52, 0xFF, 0x00, 0xFF])]
+#[case::not_nintendo("POKEMON RED", 0x08, b"00", [0x52, 0xFF, 0x00, 0xFF])]
+fn test_colorize(
+    #[case] title: &str,
+    #[case] old_licensee: u8,
+    #[case] new_licensee: &[u8; 2],
+    #[case] expected: [u8; 4],
+) {
+    let color_scheme = colorize(&build_rom(title, old_licensee, new_licensee));
+    assert_eq!(light_background_shade(&color_scheme), expected);
+}
+
+#[test]
+fn test_colorize_header_too_small() {
+    assert_eq!(colorize(&[0x01; 0x100]), DEFAULT_COLORIZATION);
+}
+
+#[test]
+fn test_colorize_toggle() {
+    let rom = build_rom("POKEMON RED", 0x01, b"00");
+    let cartridge = Cartridge::from_bytes(&rom).unwrap();
+    let config = GameBoyConfig::default().colorize(true);
+    let mut game_boy = GameBoy::initialize_with_config(&cartridge, config.clone()).unwrap();
+    assert!(game_boy.is_colorized());
+    assert_eq!(*game_boy.get_color_scheme(), colorize(&rom));
+
+    game_boy.set_colorize(false);
+    assert_eq!(*game_boy.get_color_scheme(), ColorScheme::default());
+
+    game_boy.set_colorize(true);
+    assert_eq!(*game_boy.get_color_scheme(), colorize(&rom));
+
+    // Loading a save state keeps colorizing
+    let state = game_boy.save();
+    let loaded = GameBoy::load_with_config(state, &cartridge, config).unwrap();
+    assert_eq!(*loaded.get_color_scheme(), colorize(&rom));
+
+    // Explicit colors take over
+    let grayscale = ColorScheme::monochrome([[0x00, 0x00, 0x00, 0xFF]; 4]);
+    game_boy.set_color_scheme(grayscale);
+    assert!(!game_boy.is_colorized());
+    assert_eq!(*game_boy.get_color_scheme(), grayscale);
+}
+
+#[rstest]
+#[case::raw_keeps_colors(ColorCorrection::Raw, [0xFF, 0x84, 0x84, 0x80], [0xFF, 0x84, 0x84, 0x80])]
+#[case::black(ColorCorrection::Cgb, [0x00, 0x00, 0x00, 0xFF], [0x00, 0x00, 0x00, 0xFF])]
+#[case::white(ColorCorrection::Cgb, [0xFF, 0xFF, 0xFF, 0xFF], [0xF8, 0xF8, 0xF8, 0xFF])]
+#[case::red(ColorCorrection::Cgb, [0xFF, 0x00, 0x00, 0xFF], [0xC9, 0x00, 0x2E, 0xFF])]
+#[case::green(ColorCorrection::Cgb, [0x00, 0xFF, 0x00, 0xFF], [0x1F, 0xBA, 0x1F, 0xFF])]
+#[case::blue(ColorCorrection::Cgb, [0x00, 0x00, 0xFF, 0x80], [0x0F, 0x3E, 0xAA, 0x80])]
+fn test_color_correction(
+    #[case] color_correction: ColorCorrection,
+    #[case] color: [u8; 4],
+    #[case] expected: [u8; 4],
+) {
+    assert_eq!(color_correction.apply(color), expected);
+}
+
+#[test]
+fn test_color_correction_only_applies_to_colorized_games() {
+    let rom = build_rom("POKEMON RED", 0x01, b"00");
+    let cartridge = Cartridge::from_bytes(&rom).unwrap();
+    let config = GameBoyConfig::default()
+        .colorize(true)
+        .color_correction(ColorCorrection::Cgb);
+    let mut game_boy = GameBoy::initialize_with_config(&cartridge, config).unwrap();
+    let corrected = ColorCorrection::Cgb.apply_to_scheme(&colorize(&rom));
+    assert_ne!(corrected, colorize(&rom));
+    assert_eq!(*game_boy.get_color_scheme(), corrected);
+
+    game_boy.set_color_correction(ColorCorrection::Raw);
+    assert_eq!(*game_boy.get_color_scheme(), colorize(&rom));
+
+    game_boy.set_color_correction(ColorCorrection::Cgb);
+    game_boy.set_colorize(false);
+    assert_eq!(*game_boy.get_color_scheme(), ColorScheme::default());
+}
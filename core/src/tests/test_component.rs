@@ -0,0 +1,120 @@
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::dma::Dma;
+use crate::game_boy::components::mmu::{DMA_ADDRESS, LCDC_ADDRESS, MMU, SC_ADDRESS, TAC_ADDRESS};
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::{VBlankInfo, PPU};
+use crate::game_boy::components::serial::Serial;
+use crate::game_boy::components::timer::{Timer, TimerOverflowEvent};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::hardware_model::HardwareModel;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+/// Timer enabled at the fastest rate, a serial transfer and an OAM DMA running, LCD on
+fn build_mmu() -> MMU {
+    let mut mmu = MMU::builder()
+        .io(TAC_ADDRESS, 0b101)
+        .io(SC_ADDRESS, 0x81)
+        .io(LCDC_ADDRESS, 0b1001_0001)
+        .build();
+    mmu.write(DMA_ADDRESS, 0xC0);
+    mmu
+}
+
+fn tick<C: Component>(component: &mut C, mmu: &mut MMU, m_cycles: u32) {
+    for _ in 0..m_cycles {
+        component.tick(Cycles::from_m(1), mmu);
+    }
+}
+
+/// Loading a save has to bring back exactly the state it was saved from
+fn assert_save_round_trip<C>(mut component: C)
+where
+    C: Component,
+    C::SaveState: Clone + PartialEq + Debug,
+{
+    let mut mmu = build_mmu();
+    tick(&mut component, &mut mmu, 100);
+    let state = component.save();
+    tick(&mut component, &mut mmu, 100);
+    assert_ne!(component.save(), state);
+
+    component.load(state.clone()).unwrap();
+    assert_eq!(component.save(), state);
+}
+
+/// A reset has to bring back the state of a new component, no matter how far it ran
+fn assert_reset<C>(mut component: C, config: &GameBoyConfig)
+where
+    C: Component,
+    C::SaveState: PartialEq + Debug,
+{
+    let initial = component.save();
+    tick(&mut component, &mut build_mmu(), 100);
+    component.reset(config);
+    assert_eq!(component.save(), initial);
+}
+
+#[test]
+fn test_save_round_trip() {
+    assert_save_round_trip(Timer::default());
+    assert_save_round_trip(Serial::default());
+    assert_save_round_trip(Dma::default());
+    assert_save_round_trip(PPU::new());
+}
+
+#[test]
+fn test_reset() {
+    let config = GameBoyConfig::default().model(HardwareModel::Dmg);
+    assert_reset(Timer::initialize(config.get_div_counter()), &config);
+    assert_reset(Serial::default(), &config);
+    assert_reset(Dma::default(), &config);
+    assert_reset(PPU::new(), &config);
+}
+
+#[test]
+fn test_tick_reports_events() {
+    let mut mmu = build_mmu();
+    // TIMA overflows after 256 increments every 16 T-cycles
+    let mut timer = Timer::default();
+    assert!(!timer.tick(Cycles::from_t(255 * 16), &mut mmu));
+    assert!(timer.tick(Cycles::from_t(16), &mut mmu));
+
+    let mut dma = Dma::default();
+    assert!(!dma.tick(Cycles::from_m(159), &mut mmu));
+    assert!(dma.tick(Cycles::from_m(1), &mut mmu));
+
+    // The PPU has to be ticked in small steps, one frame takes 17556 M-cycles
+    let mut ppu = PPU::new();
+    let finished_frames = (0..17556 * 2)
+        .filter(|_| ppu.tick(Cycles::from_m(1), &mut mmu))
+        .count();
+    assert_eq!(finished_frames, 2);
+}
+
+/// Listeners aren't part of a save state, loading one mustn't drop them
+#[test]
+fn test_load_keeps_listeners() {
+    let mut mmu = build_mmu();
+    let overflows = Arc::new(Mutex::new(0));
+    let recorded = overflows.clone();
+    let mut timer = Timer::default();
+    timer.on_overflow(move |_: &TimerOverflowEvent| *recorded.lock().unwrap() += 1);
+    let state = timer.save();
+    timer.load(state).unwrap();
+    timer.tick(Cycles::from_t(256 * 16), &mut mmu);
+    assert_eq!(*overflows.lock().unwrap(), 1);
+
+    let frames = Arc::new(Mutex::new(0));
+    let recorded = frames.clone();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    ppu.on_frame(move |_: &VBlankInfo| *recorded.lock().unwrap() += 1);
+    let state = PPU::new().save();
+    ppu.load(state).unwrap();
+    tick(&mut ppu, &mut mmu, 17556);
+    ppu.notify_frame(0);
+    assert_eq!(*frames.lock().unwrap(), 1);
+    // The state was saved as RGBA, the loaded PPU keeps its own format
+    assert_eq!(ppu.get_frame_buffer_format(), FrameBufferFormat::Indexed);
+}
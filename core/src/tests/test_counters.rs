@@ -0,0 +1,81 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::GameBoy;
+use crate::tests::load_test_rom;
+use std::sync::{Arc, Mutex};
+
+/// T-cycles of a whole frame, 154 lines of 456 dots
+const CYCLES_PER_FRAME: u64 = 154 * 456;
+/// The longest instruction, a frame can end this far into the next one
+const LONGEST_INSTRUCTION: u64 = 24;
+
+#[test]
+fn test_counters_start_at_zero() {
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    assert_eq!(game_boy.total_cycles(), 0);
+    assert_eq!(game_boy.frame_count(), 0);
+}
+
+#[test]
+fn test_counters_follow_the_emulation() {
+    // Keeps the LCD on, the built-in ROM switches it off for a while
+    let rom = RomBuilder::new().program(&[0x18, 0xFE]).build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    game_boy.step();
+    assert!(game_boy.total_cycles() >= 4);
+    assert_eq!(game_boy.frame_count(), 0);
+
+    game_boy.finish_frame();
+    let start = game_boy.total_cycles();
+    for _ in 0..10 {
+        game_boy.finish_frame();
+    }
+    assert_eq!(game_boy.frame_count(), 11);
+    assert!(
+        game_boy
+            .total_cycles()
+            .abs_diff(start + 10 * CYCLES_PER_FRAME)
+            <= LONGEST_INSTRUCTION
+    );
+}
+
+#[test]
+fn test_counters_survive_reset_and_load_state() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let state = game_boy.save();
+    for _ in 0..3 {
+        game_boy.finish_frame();
+    }
+    let total_cycles = game_boy.total_cycles();
+
+    game_boy.reset();
+    assert_eq!(game_boy.total_cycles(), total_cycles);
+    assert_eq!(game_boy.frame_count(), 3);
+
+    game_boy.load_state(state).unwrap();
+    assert_eq!(game_boy.total_cycles(), total_cycles);
+    assert_eq!(game_boy.frame_count(), 3);
+
+    game_boy.finish_frame();
+    assert!(game_boy.total_cycles() > total_cycles);
+    assert_eq!(game_boy.frame_count(), 4);
+}
+
+/// Frame listeners are numbered by the frame counter, so a reset doesn't start them from 0 again
+#[test]
+fn test_frame_listener_numbers_follow_frame_count() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let numbers = Arc::new(Mutex::new(Vec::new()));
+    let recorded = numbers.clone();
+    game_boy.on_frame(move |info| recorded.lock().unwrap().push(info.frame_number));
+
+    for _ in 0..3 {
+        game_boy.finish_frame();
+    }
+    game_boy.reset();
+    for _ in 0..2 {
+        game_boy.finish_frame();
+    }
+
+    assert_eq!(*numbers.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(game_boy.frame_count(), 5);
+}
@@ -0,0 +1,145 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::{CPU, PREFIX_INSTRUCTION_BYTE};
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::instructions::Instruction;
+use rstest::rstest;
+
+const INSTRUCTIONS_PER_RUN: usize = 20_000;
+const ROUND_TRIP_INTERVAL: usize = 1_000;
+
+/// Small xorshift generator, so every run is reproducible from its seed
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn next_u8(&mut self) -> u8 {
+        self.next_u64() as u8
+    }
+
+    pub fn next_u16(&mut self) -> u16 {
+        self.next_u64() as u16
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 != 0
+    }
+
+    /// Any legal opcode, prefixed or not
+    pub fn next_instruction(&mut self) -> (u8, Instruction) {
+        loop {
+            let prefixed = self.next_bool();
+            let byte = self.next_u8();
+            if !prefixed && byte == PREFIX_INSTRUCTION_BYTE {
+                continue;
+            }
+            if let Ok(instruction) = Instruction::from_byte(byte, prefixed) {
+                return (byte, instruction);
+            }
+        }
+    }
+}
+
+fn random_cartridge(rng: &mut Rng) -> Cartridge {
+    let mut rom_banks = vec![[0u8; ROM_BANK_SIZE]; 2];
+    for byte in rom_banks.iter_mut().flatten() {
+        *byte = rng.next_u8();
+    }
+    Cartridge {
+        rom_banks,
+        header: CartridgeHeader::default(),
+    }
+}
+
+fn random_setup(rng: &mut Rng) -> (Cartridge, CPU, MMU) {
+    let cartridge = random_cartridge(rng);
+    let mut mmu = MMU::initialize(&cartridge).unwrap();
+    for address in (0x8000..0xA000).chain(0xC000..0xE000).chain(0xFF80..0xFFFF) {
+        mmu.write(address, rng.next_u8());
+    }
+
+    let cpu = CPU::builder()
+        .a(rng.next_u8())
+        .b(rng.next_u8())
+        .c(rng.next_u8())
+        .d(rng.next_u8())
+        .e(rng.next_u8())
+        .f(rng.next_u8())
+        .h(rng.next_u8())
+        .l(rng.next_u8())
+        .sp(rng.next_u16())
+        .pc(rng.next_u16())
+        .build();
+    (cartridge, cpu, mmu)
+}
+
+fn assert_round_trip(cpu: &CPU, mmu: &MMU, cartridge: &Cartridge) {
+    let json = serde_json::to_string(cpu).unwrap();
+    assert_eq!(&serde_json::from_str::<CPU>(&json).unwrap(), cpu);
+    let bytes = bincode::serialize(cpu).unwrap();
+    assert_eq!(&bincode::deserialize::<CPU>(&bytes).unwrap(), cpu);
+
+    let bytes = bincode::serialize(&mmu.save()).unwrap();
+    let loaded = MMU::load(bincode::deserialize(&bytes).unwrap(), cartridge).unwrap();
+    assert_eq!(&loaded, mmu);
+}
+
+/// Executes random instruction streams on random machine states and checks invariants that must always hold
+#[rstest]
+fn test_cpu_fuzz(
+    #[values(
+        0x1,
+        0xC0FFEE,
+        0xDEAD_BEEF,
+        0x1234_5678_9ABC_DEF0,
+        0xFFFF_FFFF_FFFF_FFFF
+    )]
+    seed: u64,
+) {
+    let mut rng = Rng(seed);
+    let (cartridge, mut cpu, mut mmu) = random_setup(&mut rng);
+
+    for i in 0..INSTRUCTIONS_PER_RUN {
+        let (byte, instruction) = rng.next_instruction();
+        let metadata = instruction.metadata();
+        let pc = cpu.get_pc();
+        let sp = cpu.get_sp();
+
+        let (next_pc, m_cycles) = cpu.execute(instruction, &mut mmu);
+        let context = format!("seed {seed:#X}, instruction {i}: {instruction:?} (0x{byte:02X})");
+
+        assert_eq!(cpu.get_f() & 0x0F, 0, "{context}");
+        assert!(
+            m_cycles == metadata.min_cycles || m_cycles == metadata.max_cycles,
+            "{context}"
+        );
+        match metadata.mnemonic {
+            "JP" | "JR" | "CALL" | "RET" | "RETI" | "RST" => {}
+            _ => assert_eq!(
+                next_pc,
+                pc.wrapping_add(metadata.length as u16),
+                "{context}"
+            ),
+        }
+        match metadata.mnemonic {
+            "PUSH" | "RST" => assert_eq!(cpu.get_sp(), sp.wrapping_sub(2), "{context}"),
+            "POP" | "RETI" => assert_eq!(cpu.get_sp(), sp.wrapping_add(2), "{context}"),
+            _ => {}
+        }
+
+        cpu.set_pc(next_pc);
+        if i % ROUND_TRIP_INTERVAL == 0 {
+            assert_round_trip(&cpu, &mmu, &cartridge);
+        }
+    }
+
+    assert_round_trip(&cpu, &mmu, &cartridge);
+}
@@ -0,0 +1,99 @@
+use crate::game_boy::components::cpu::builder::CpuBuilder;
+use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
+use crate::game_boy::components::cpu::registers::flags_register::CPUFlagsRegister;
+use crate::game_boy::components::cpu::registers::{CPURegisters, CpuRegistersAccessTrait};
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::mmu::MMU;
+use crate::tests::load_test_rom;
+use rstest::rstest;
+
+#[test]
+fn test_flag_registers() {
+    let mut test1 = CPUFlagsRegister::default();
+    assert!(!test1.get_zero());
+    assert!(!test1.get_subtract());
+    assert!(!test1.get_half_carry());
+    assert!(!test1.get_carry());
+
+    test1.set_zero(true);
+    assert!(test1.get_zero());
+    test1.set_subtract(true);
+    assert!(test1.get_subtract());
+    test1.set_half_carry(true);
+    assert!(test1.get_half_carry());
+    test1.set_carry(true);
+    assert!(test1.get_carry());
+    assert_eq!(u8::from(test1), 0b1111_0000);
+
+    let test2 = CPUFlagsRegister::from(0b1010_0000);
+    assert!(test2.get_zero());
+    assert!(!test2.get_subtract());
+    assert!(test2.get_half_carry());
+    assert!(!test2.get_carry());
+    assert_eq!(u8::from(test2), 0b1010_0000);
+
+    let mut test3 = CPUFlagsRegister::from(0b0101_1101);
+    assert!(!test3.get_zero());
+    assert!(test3.get_subtract());
+    assert!(!test3.get_half_carry());
+    assert!(test3.get_carry());
+    assert_eq!(u8::from(test3), 0b0101_0000);
+
+    test3.set_subtract(false);
+    test3.set_carry(false);
+    assert_eq!(u8::from(test3), 0b0000_0000);
+}
+
+#[test]
+fn test_bc() {
+    let mut registers = CPURegisters::default();
+    assert_eq!(registers.get_b(), 0);
+    assert_eq!(registers.get_c(), 0);
+
+    registers.set_bc(0x4FD2);
+    assert_eq!(registers.get_bc(), 0x4FD2);
+    assert_eq!(registers.get_b(), 0x4F);
+    assert_eq!(registers.get_c(), 0xD2);
+}
+
+/// Every path that writes F must leave its lower 4 bits at zero
+#[rstest]
+#[case::set_f(|cpu: &mut CPU, _: &mut MMU| cpu.set_f(0xFF))]
+#[case::set_af(|cpu: &mut CPU, _: &mut MMU| cpu.set_af(0x12FF))]
+#[case::builder_f(|cpu: &mut CPU, _: &mut MMU| *cpu = CPU::builder().f(0xFF).build())]
+#[case::builder_af(|cpu: &mut CPU, _: &mut MMU| *cpu = CPU::builder().af(0x12FF).build())]
+#[case::doctor_line(|cpu: &mut CPU, _: &mut MMU| {
+    *cpu = CpuBuilder::from_doctor_line(
+        "A:01 F:FF B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02",
+    )
+    .unwrap()
+    .build()
+})]
+#[case::pop_af(|cpu: &mut CPU, mmu: &mut MMU| {
+    mmu.force_write_rom(0, 0xF1);
+    mmu.write(0xC000, 0xFF);
+    mmu.write(0xC001, 0x12);
+    *cpu = CPU::builder().sp(0xC000).build();
+    cpu.step(mmu);
+})]
+#[case::save_state_load(|cpu: &mut CPU, _: &mut MMU| {
+    cpu.set_af(0x12FF);
+    let json = serde_json::to_string(&*cpu).unwrap();
+    *cpu = serde_json::from_str(&json).unwrap();
+})]
+fn test_f_lower_nibble_is_zero(#[case] write: fn(&mut CPU, &mut MMU)) {
+    let mut cpu = CPU::builder().build();
+    let mut mmu = MMU::default();
+    write(&mut cpu, &mut mmu);
+    assert_eq!(cpu.get_f(), 0xF0);
+    assert_eq!(cpu.get_af() & 0x00FF, 0x00F0);
+}
+
+#[test]
+fn test_edit_registers() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    game_boy.get_cpu_mut().set_pc(0x0150);
+    game_boy.get_cpu_mut().set_a(0x42);
+    assert_eq!(game_boy.get_pc(), 0x0150);
+    assert_eq!(game_boy.get_cpu().get_a(), 0x42);
+}
@@ -0,0 +1,22 @@
+use crate::game_boy::cycles::Cycles;
+use rstest::rstest;
+
+#[rstest]
+#[case(Cycles::from_m(1), 4, 1)]
+#[case(Cycles::from_t(456), 456, 114)]
+#[case(Cycles::from_t(7), 7, 1)]
+#[case(Cycles::from_m(6) + Cycles::from_t(2), 26, 6)]
+#[case(Cycles::from_m(3) - Cycles::from_m(1), 8, 2)]
+fn test_cycle_conversion(#[case] cycles: Cycles, #[case] t_cycles: u32, #[case] m_cycles: u32) {
+    assert_eq!(cycles.as_t(), t_cycles);
+    assert_eq!(cycles.as_m(), m_cycles);
+}
+
+#[test]
+fn test_cycles_accumulate() {
+    let mut total = Cycles::ZERO;
+    for _ in 0..17556 {
+        total += Cycles::from_m(1);
+    }
+    assert_eq!(total, Cycles::from_t(70224));
+}
@@ -0,0 +1,114 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::diagnostics::Diagnostic;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+/// MBC1 with 8 KiB RAM
+const MBC1_RAM_HEADER: [u8; 3] = [0x03, 0x00, 0x02];
+
+fn record_diagnostics(game_boy: &mut GameBoy) -> Arc<Mutex<Vec<Diagnostic>>> {
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let recorded = diagnostics.clone();
+    game_boy.on_diagnostic(move |diagnostic| recorded.lock().unwrap().push(*diagnostic));
+    diagnostics
+}
+
+#[rstest]
+// LD A, 0x01 / LD (0x2000), A / JR to the start, so the write repeats
+#[case::rom_write_without_mapper(
+    RomBuilder::new().program(&[0x3E, 0x01, 0xEA, 0x00, 0x20, 0x18, 0xF9]),
+    Diagnostic::RomWriteWithoutMapper { address: 0x2000, value: 0x01 }
+)]
+// LD A, (0xA010) / JR to the start
+#[case::disabled_ram_read(
+    RomBuilder::new().bytes(0x147, &MBC1_RAM_HEADER).program(&[0xFA, 0x10, 0xA0, 0x18, 0xFB]),
+    Diagnostic::DisabledRamRead { address: 0xA010 }
+)]
+// JP 0xFFFF, IE is 0x00 and runs as NOP, after which the program counter wraps around to the entry point
+#[case::execution_at_ffff(
+    RomBuilder::new().program(&[0xC3, 0xFF, 0xFF]),
+    Diagnostic::ExecutionAtFFFF
+)]
+// LD SP, 0xFF00 / PUSH BC / JR to the start
+#[case::stack_in_unusable_region(
+    RomBuilder::new().program(&[0x31, 0x00, 0xFF, 0xC5, 0x18, 0xFA]),
+    Diagnostic::StackInUnusableRegion { sp: 0xFEFE }
+)]
+fn test_diagnostic_reported_once(#[case] rom: RomBuilder, #[case] expected: Diagnostic) {
+    let mut game_boy = GameBoy::headless(&rom.build()).unwrap();
+    let diagnostics = record_diagnostics(&mut game_boy);
+    game_boy.finish_frame();
+    game_boy.finish_frame();
+    assert_eq!(*diagnostics.lock().unwrap(), [expected]);
+}
+
+#[test]
+fn test_no_diagnostics_for_well_behaved_games() {
+    // LD A, 0x0A / LD (0x0000), A / LD A, (0xA000) / JR to the LD A, (0xA000)
+    let rom = RomBuilder::new()
+        .bytes(0x147, &MBC1_RAM_HEADER)
+        .program(&[0x3E, 0x0A, 0xEA, 0x00, 0x00, 0xFA, 0x00, 0xA0, 0x18, 0xFB])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let diagnostics = record_diagnostics(&mut game_boy);
+    game_boy.finish_frame();
+    assert!(diagnostics.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_debugger_reads_report_nothing() {
+    let rom = RomBuilder::new()
+        .bytes(0x147, &MBC1_RAM_HEADER)
+        .program(&[0x18, 0xFE])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let diagnostics = record_diagnostics(&mut game_boy);
+    game_boy.peek(0xA000);
+    assert!(diagnostics.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_diagnostics_reported_again_after_reset() {
+    let rom = RomBuilder::new()
+        .program(&[0x3E, 0x01, 0xEA, 0x00, 0x20, 0x18, 0xF9])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let diagnostics = record_diagnostics(&mut game_boy);
+    game_boy.finish_frame();
+    game_boy.reset();
+    game_boy.finish_frame();
+    assert_eq!(diagnostics.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_remove_diagnostic_listener() {
+    let rom = RomBuilder::new()
+        .program(&[0x3E, 0x01, 0xEA, 0x00, 0x20, 0x18, 0xF9])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let recorded = diagnostics.clone();
+    let id = game_boy.on_diagnostic(move |diagnostic| recorded.lock().unwrap().push(*diagnostic));
+    game_boy.remove_diagnostic_listener(id);
+    game_boy.finish_frame();
+    assert!(diagnostics.lock().unwrap().is_empty());
+}
+
+#[rstest]
+#[case::rom_write(
+    Diagnostic::RomWriteWithoutMapper { address: 0x2000, value: 0x01 },
+    "Write of 0x01 to ROM at 0x2000, but the cartridge has no mapper"
+)]
+#[case::ram_read(
+    Diagnostic::DisabledRamRead { address: 0xA010 },
+    "Read of disabled cartridge RAM at 0xA010"
+)]
+#[case::execution(Diagnostic::ExecutionAtFFFF, "Execution reached 0xFFFF")]
+#[case::stack(
+    Diagnostic::StackInUnusableRegion { sp: 0xFEFE },
+    "Stack overflow into the unusable region, SP is 0xFEFE"
+)]
+fn test_diagnostic_messages(#[case] diagnostic: Diagnostic, #[case] expected: &str) {
+    assert_eq!(diagnostic.to_string(), expected);
+}
@@ -0,0 +1,106 @@
+use crate::disassembler::Disassembler;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::instructions::Instruction;
+use rstest::rstest;
+
+/// 4 bank MBC1 ROM where every switchable bank starts with a different LD A, n8
+fn build_banked_mmu() -> MMU {
+    let mut rom = vec![0u8; ROM_BANK_SIZE * 4];
+    rom[0x147] = 0x01; // MBC1
+    rom[0x148] = 0x01; // 64 KiB
+    for bank in 1..4 {
+        let start = bank * ROM_BANK_SIZE;
+        rom[start..start + 2].copy_from_slice(&[0x3E, bank as u8]);
+    }
+    MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+#[test]
+fn test_disassembler_bytes_and_addresses() {
+    let mmu = MMU::builder()
+        .rom(0x0150, 0x00) // NOP
+        .rom(0x0151, 0xC3) // JP 0x1234
+        .rom(0x0152, 0x34)
+        .rom(0x0153, 0x12)
+        .rom(0x0154, 0xCB) // SWAP A
+        .rom(0x0155, 0x37)
+        .rom(0x0156, 0x3E) // LD A, 0x42
+        .rom(0x0157, 0x42)
+        .build();
+
+    let disassembly: Vec<_> = Disassembler::iter_from(&mmu, None, 0x0150)
+        .take(4)
+        .collect();
+
+    let addresses: Vec<u16> = disassembly.iter().map(|i| i.address).collect();
+    assert_eq!(addresses, vec![0x0150, 0x0151, 0x0154, 0x0156]);
+    assert_eq!(disassembly[1].bytes, vec![0xC3, 0x34, 0x12]);
+    assert_eq!(disassembly[1].instruction, Some(Instruction::JpImm16));
+    assert_eq!(disassembly[1].get_clear_text(), "JP 0x1234");
+    assert_eq!(disassembly[2].bytes, vec![0xCB, 0x37]);
+    assert_eq!(disassembly[3].get_clear_text(), "LD A, 0x42");
+}
+
+#[test]
+fn test_disassembler_continues_after_illegal_opcode() {
+    let mmu = MMU::builder()
+        .rom(0x0002, 0xD3)
+        .rom(0x0003, 0x3E) // LD A, 0x42
+        .rom(0x0004, 0x42)
+        .build();
+    let disassembly: Vec<_> = Disassembler::iter_from(&mmu, None, 0x0000)
+        .take(4)
+        .collect();
+
+    assert_eq!(disassembly[2].address, 0x0002);
+    assert_eq!(disassembly[2].bytes, vec![0xD3]);
+    assert_eq!(disassembly[2].instruction, None);
+    assert_eq!(disassembly[2].get_clear_text(), "DB 0xD3");
+    assert_eq!(disassembly[2].get_description(), "Data byte 0xD3");
+    assert_eq!(disassembly[3].address, 0x0003);
+    assert_eq!(disassembly[3].get_clear_text(), "LD A, 0x42");
+}
+
+#[rstest]
+#[case::data_table(&[0xD3, 0xE4, 0x00], false, vec!["[0xD3] DB 0xD3", "[0xE4] DB 0xE4", "[0x00] NOP"])]
+#[case::trailing_prefix(&[0x00, 0xCB], false, vec!["[0x00] NOP", "[0xCB] DB 0xCB"])]
+#[case::detailed(&[0xFC], true, vec!["[0xFC] Data byte 0xFC"])]
+fn test_clear_text_continues_after_illegal_opcode(
+    #[case] data: &[u8],
+    #[case] detailed: bool,
+    #[case] expected: Vec<&str>,
+) {
+    assert_eq!(
+        Instruction::parse_clear_text_instructions_from_data(data, detailed),
+        expected
+    );
+}
+
+#[test]
+fn test_disassembler_stops_at_end_of_address_space() {
+    let mmu = MMU::default();
+    let last = Disassembler::iter_from(&mmu, None, 0xFFF0).last().unwrap();
+    assert_eq!(last.address, 0xFFFF);
+}
+
+#[rstest]
+#[case(None, 1)]
+#[case(Some(2), 2)]
+#[case(Some(3), 3)]
+fn test_disassembler_banking(#[case] bank: Option<usize>, #[case] expected_operand: u8) {
+    let mmu = build_banked_mmu();
+    let instruction = Disassembler::iter_from(&mmu, bank, 0x4000).next().unwrap();
+
+    assert_eq!(instruction.bank, expected_operand as usize);
+    assert_eq!(instruction.bytes, vec![0x3E, expected_operand]);
+}
+
+#[test]
+fn test_disassembler_follows_mapped_bank() {
+    let mut mmu = build_banked_mmu();
+    mmu.write(0x2000, 3);
+
+    let instruction = Disassembler::iter_from(&mmu, None, 0x4000).next().unwrap();
+    assert_eq!(instruction.bytes, vec![0x3E, 3]);
+}
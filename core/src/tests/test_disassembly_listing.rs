@@ -0,0 +1,163 @@
+use crate::disassembler::listing::{
+    disassemble_cartridge, format_listing, to_json, to_rgbds, Labels, ListingFormat, ListingOptions,
+};
+use crate::disassembler::{DisassembledInstruction, Disassembler};
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::MMU;
+use rstest::rstest;
+
+/// A loop jumping back to its start, a call out of the listing and a data byte
+const PROGRAM: [u8; 9] = [
+    0x3C, // INC A
+    0x20, 0xFD, // JR NZ, -3
+    0xCD, 0x00, 0x20, // CALL 0x2000
+    0xD3, // Illegal opcode
+    0xC3, 0x50, // JP 0x0150, the rest is cut off by the listing
+];
+
+fn build_rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x150..0x150 + PROGRAM.len()].copy_from_slice(&PROGRAM);
+    rom[0x159] = 0x01;
+    rom
+}
+
+fn disassemble(count: usize) -> Vec<DisassembledInstruction> {
+    let mmu = MMU::initialize(&Cartridge::from_bytes(&build_rom()).unwrap()).unwrap();
+    Disassembler::iter_from(&mmu, None, 0x0150)
+        .take(count)
+        .collect()
+}
+
+fn disassemble_bytes(bytes: &[u8]) -> DisassembledInstruction {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x200..0x200 + bytes.len()].copy_from_slice(bytes);
+    let mmu = MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    Disassembler::iter_from(&mmu, None, 0x0200).next().unwrap()
+}
+
+#[rstest]
+#[case(&[0x00], "nop", &[])]
+#[case(&[0x7E], "ld", &["a", "[hl]"])]
+#[case(&[0x2A], "ld", &["a", "[hl+]"])]
+#[case(&[0x01, 0x34, 0x12], "ld", &["bc", "$1234"])]
+#[case(&[0xFA, 0x34, 0x12], "ld", &["a", "[$1234]"])]
+#[case(&[0x08, 0x00, 0xC0], "ld", &["[$C000]", "sp"])]
+#[case(&[0xF0, 0x44], "ldh", &["a", "[$FF44]"])]
+#[case(&[0xE2], "ldh", &["[c]", "a"])]
+#[case(&[0xF8, 0xFE], "ld", &["hl", "sp - 2"])]
+#[case(&[0xF8, 0x05], "ld", &["hl", "sp + 5"])]
+#[case(&[0xE8, 0xFF], "add", &["sp", "-1"])]
+#[case(&[0xD6, 0x10], "sub", &["a", "$10"])]
+#[case(&[0xC0], "ret", &["nz"])]
+#[case(&[0xFF], "rst", &["$38"])]
+#[case(&[0xE9], "jp", &["hl"])]
+#[case(&[0x18, 0x05], "jr", &["$0207"])]
+#[case(&[0xDA, 0x00, 0x40], "jp", &["c", "$4000"])]
+#[case(&[0xCB, 0x7E], "bit", &["7", "[hl]"])]
+#[case(&[0xCB, 0x37], "swap", &["a"])]
+#[case(&[0xED], "db", &["$ED"])]
+fn test_rgbds_parts(#[case] bytes: &[u8], #[case] mnemonic: &str, #[case] operands: &[&str]) {
+    let instruction = disassemble_bytes(bytes);
+    assert_eq!(
+        instruction.get_rgbds_parts(&Labels::default()),
+        (mnemonic, operands.iter().map(|s| s.to_string()).collect())
+    );
+}
+
+#[test]
+fn test_labels_only_for_targets_in_listing() {
+    let instructions = disassemble(5);
+    let labels = Labels::collect(&instructions);
+
+    assert_eq!(instructions[1].get_target(), Some(0x0150));
+    assert_eq!(instructions[2].get_target(), Some(0x2000));
+    assert_eq!(labels.get(0x0150), Some("label_00_0150"));
+    assert_eq!(labels.get(0x2000), None);
+}
+
+#[test]
+fn test_rgbds_listing() {
+    let expected = "\
+SECTION \"ROM0 $0150\", ROM0[$0150]
+label_00_0150:
+    inc a                    ; $0150: 3C
+    jr nz, label_00_0150     ; $0151: 20 FD
+    call $2000               ; $0153: CD 00 20
+    db $D3                   ; $0156: D3
+    jp label_00_0150         ; $0157: C3 50 01
+";
+    assert_eq!(to_rgbds(&disassemble(5)), expected);
+}
+
+#[test]
+fn test_rgbds_listing_starts_a_section_per_bank() {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x3FFF] = 0x00;
+    let mmu = MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    let instructions: Vec<_> = Disassembler::iter_from(&mmu, None, 0x3FFF)
+        .take(2)
+        .collect();
+    let listing = to_rgbds(&instructions);
+
+    assert!(listing.starts_with("SECTION \"ROM0 $3FFF\", ROM0[$3FFF]\n"));
+    assert!(listing.contains("\n\nSECTION \"ROMX 01 $4000\", ROMX[$4000], BANK[$01]\n"));
+}
+
+#[test]
+fn test_json_listing() {
+    let json: serde_json::Value = serde_json::from_str(&to_json(&disassemble(5)).unwrap()).unwrap();
+
+    assert_eq!(json.as_array().unwrap().len(), 5);
+    assert_eq!(
+        json[0],
+        serde_json::json!({
+            "address": 0x0150,
+            "bank": 0,
+            "bytes": [0x3C],
+            "mnemonic": "inc",
+            "operands": ["a"],
+            "label": "label_00_0150",
+            "target": null,
+            "references": [],
+        })
+    );
+    assert_eq!(json[1]["operands"], serde_json::json!(["nz", "$0150"]));
+    assert_eq!(json[1]["target"], 0x0150);
+    assert_eq!(json[1]["references"], serde_json::json!(["label_00_0150"]));
+    assert_eq!(json[2]["target"], 0x2000);
+    assert_eq!(json[2]["references"], serde_json::json!([]));
+    assert_eq!(json[3]["mnemonic"], "db");
+}
+
+#[test]
+fn test_text_listing() {
+    let listing = format_listing(&disassemble(2), ListingFormat::Text).unwrap();
+    assert_eq!(
+        listing.lines().collect::<Vec<_>>(),
+        vec!["00:0150  3C        INC A", "00:0151  20 FD     JR NZ, 0xFD"]
+    );
+}
+
+#[test]
+fn test_disassemble_cartridge() {
+    let options = ListingOptions {
+        address: 0x0150,
+        count: 2,
+        format: ListingFormat::Rgbds,
+        ..ListingOptions::default()
+    };
+    let cartridge = Cartridge::from_bytes(&build_rom()).unwrap();
+    let listing = disassemble_cartridge(&cartridge, &options).unwrap();
+    assert!(listing.contains("jr nz, label_00_0150"));
+}
+
+#[rstest]
+#[case("text", Some(ListingFormat::Text))]
+#[case("JSON", Some(ListingFormat::Json))]
+#[case("rgbds", Some(ListingFormat::Rgbds))]
+#[case("asm", Some(ListingFormat::Rgbds))]
+#[case("xml", None)]
+fn test_parse_listing_format(#[case] input: &str, #[case] expected: Option<ListingFormat>) {
+    assert_eq!(input.parse::<ListingFormat>().ok(), expected);
+}
@@ -0,0 +1,61 @@
+use crate::game_boy::components::dma::{Dma, DMA_DURATION};
+use crate::game_boy::components::mmu::builder::MMUBuilder;
+use crate::game_boy::components::mmu::{DMA_ADDRESS, MMU, OAM_ADDRESS, OAM_SIZE};
+use crate::game_boy::cycles::Cycles;
+
+/// Page 0xC0 holds 0x00, 0x01, ... and page 0xC1 holds 0xFF, 0xFE, ...
+fn source_mmu_builder() -> MMUBuilder {
+    let mut builder = MMU::builder();
+    for i in 0..OAM_SIZE as u16 {
+        builder = builder
+            .write(0xC000 + i, i as u8)
+            .write(0xC100 + i, 0xFF - i as u8);
+    }
+    builder
+}
+
+#[test]
+fn test_dma_copies_one_byte_per_m_cycle() {
+    let mut mmu = source_mmu_builder().io(DMA_ADDRESS, 0xC0).build();
+    let mut dma = Dma::default();
+
+    assert!(!dma.step(Cycles::from_m(1), &mut mmu));
+    assert!(dma.is_active());
+    assert_eq!(mmu.read(OAM_ADDRESS), 0x00);
+    assert_eq!(mmu.read(OAM_ADDRESS + 1), 0x00);
+    assert_eq!(mmu.read(OAM_ADDRESS + 2), 0x00);
+
+    assert!(!dma.step(Cycles::from_m(2), &mut mmu));
+    assert_eq!(mmu.read(OAM_ADDRESS + 2), 0x02);
+    assert_eq!(mmu.read(OAM_ADDRESS + 3), 0x00);
+
+    assert!(dma.step(DMA_DURATION - Cycles::from_m(3), &mut mmu));
+    assert!(!dma.is_active());
+    for i in 0..OAM_SIZE as u16 {
+        assert_eq!(mmu.read(OAM_ADDRESS + i), i as u8);
+    }
+}
+
+#[test]
+fn test_dma_write_restarts_transfer() {
+    let mut mmu = source_mmu_builder().io(DMA_ADDRESS, 0xC0).build();
+    let mut dma = Dma::default();
+    dma.step(Cycles::from_m(100), &mut mmu);
+
+    mmu.write(DMA_ADDRESS, 0xC1);
+    assert!(!dma.step(Cycles::from_m(100), &mut mmu));
+    assert!(dma.step(DMA_DURATION - Cycles::from_m(100), &mut mmu));
+    for i in 0..OAM_SIZE as u16 {
+        assert_eq!(mmu.read(OAM_ADDRESS + i), 0xFF - i as u8);
+    }
+}
+
+#[test]
+fn test_dma_only_runs_after_write() {
+    let mut mmu = source_mmu_builder().build();
+    let mut dma = Dma::default();
+
+    assert!(!dma.step(DMA_DURATION, &mut mmu));
+    assert!(!dma.is_active());
+    assert!((0..OAM_SIZE as u16).all(|i| mmu.read(OAM_ADDRESS + i) == 0));
+}
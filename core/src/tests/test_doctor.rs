@@ -0,0 +1,88 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::cpu::builder::CpuBuilder;
+use crate::game_boy::components::cpu::doctor::DoctorLogLine;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::mmu::MMU;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+
+const POST_BOOT_LINE: &str =
+    "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,37,06";
+
+#[test]
+fn test_parse_doctor_line() {
+    let line: DoctorLogLine = POST_BOOT_LINE.parse().unwrap();
+    assert_eq!(
+        line,
+        DoctorLogLine {
+            a: 0x01,
+            f: 0xB0,
+            b: 0x00,
+            c: 0x13,
+            d: 0x00,
+            e: 0xD8,
+            h: 0x01,
+            l: 0x4D,
+            sp: 0xFFFE,
+            pc: 0x0100,
+            pcmem: [0x00, 0xC3, 0x37, 0x06],
+        }
+    );
+    assert_eq!(line.to_string(), POST_BOOT_LINE);
+}
+
+#[rstest]
+#[case::empty("")]
+#[case::missing_field("A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100")]
+#[case::unknown_field(
+    "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,37,06 X:00"
+)]
+#[case::invalid_value("A:0G F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,37,06")]
+#[case::short_pcmem("A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,37")]
+#[case::no_separator("A01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,37,06")]
+fn test_parse_doctor_line_errors(#[case] line: &str) {
+    assert!(line.parse::<DoctorLogLine>().is_err());
+}
+
+/// The emulator starts with the DMG0 register values
+const DMG0_POST_BOOT_LINE: &str =
+    "A:01 F:00 B:FF C:13 D:00 E:C1 H:84 L:03 SP:FFFE PC:0100 PCMEM:00,C3,37,06";
+
+#[test]
+fn test_game_boy_matches_post_boot_line() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let game_boy = GameBoy::initialize(&cartridge).unwrap();
+    assert_eq!(game_boy.doctor_log_line().to_string(), DMG0_POST_BOOT_LINE);
+}
+
+#[test]
+fn test_reproduce_doctor_line() {
+    // INC A with A = 0x0F, the half carry flag has to be set afterward
+    let line = "A:0F F:00 B:00 C:00 D:00 E:00 H:00 L:00 SP:FFFE PC:C000 PCMEM:3C,00,00,00";
+    let mut cpu = CpuBuilder::from_doctor_line(line).unwrap().build();
+    let parsed: DoctorLogLine = line.parse().unwrap();
+    let mut mmu = parsed.write_pcmem(MMU::builder()).build();
+
+    assert_eq!(DoctorLogLine::from_cpu(&cpu, &mmu), parsed);
+
+    cpu.step(&mut mmu);
+    assert_eq!(
+        DoctorLogLine::from_cpu(&cpu, &mmu).to_string(),
+        "A:10 F:20 B:00 C:00 D:00 E:00 H:00 L:00 SP:FFFE PC:C001 PCMEM:00,00,00,00"
+    );
+}
+
+#[test]
+fn test_cpu_builder_from_state() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..1000 {
+        game_boy.step();
+    }
+
+    let state = game_boy.save();
+    let cpu = CpuBuilder::from_state(&state).build();
+    assert_eq!(cpu, state.cpu);
+    assert_eq!(cpu.get_pc(), state.cpu.get_pc());
+}
@@ -0,0 +1,86 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::BGP_ADDRESS;
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::RGBA_FRAME_BUFFER_SIZE;
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+use std::thread;
+
+fn build_game_boy(format: FrameBufferFormat) -> GameBoy {
+    let cartridge = Cartridge::load(test_rom_file_path().join("dmg-acid2.gb")).unwrap();
+    let config = GameBoyConfig::default().frame_buffer_format(format);
+    GameBoy::initialize_with_config(&cartridge, config).unwrap()
+}
+
+#[rstest]
+#[case(FrameBufferFormat::Rgba8888, true)]
+#[case(FrameBufferFormat::Rgb565, false)]
+#[case(FrameBufferFormat::Indexed, false)]
+fn test_rgba_frame(#[case] format: FrameBufferFormat, #[case] available: bool) {
+    let mut game_boy = build_game_boy(format);
+    game_boy.finish_frame();
+    match game_boy.get_rgba_frame() {
+        Some(frame) => {
+            assert!(available);
+            assert_eq!(frame.len(), RGBA_FRAME_BUFFER_SIZE);
+            assert_eq!(frame.as_ptr(), game_boy.get_frame_buffer().as_ptr());
+        }
+        None => assert!(!available),
+    }
+}
+
+#[test]
+fn test_shared_frame_buffer_holds_last_finished_frame() {
+    let mut game_boy = build_game_boy(FrameBufferFormat::Rgba8888);
+    let shared = game_boy.share_frame_buffer();
+    assert_eq!(game_boy.share_frame_buffer(), shared);
+
+    for _ in 0..10 {
+        game_boy.finish_frame();
+    }
+    let finished = game_boy.get_frame_buffer().to_vec();
+    assert_eq!(*shared.lock(), finished);
+
+    // Lines of the next frame, drawn with another palette, don't show up before it is finished
+    game_boy.poke(BGP_ADDRESS, !game_boy.peek(BGP_ADDRESS));
+    for _ in 0..2000 {
+        game_boy.step();
+    }
+    assert_ne!(game_boy.get_frame_buffer(), finished);
+    assert_eq!(*shared.lock(), finished);
+
+    let reader = shared.clone();
+    let read = thread::spawn(move || reader.lock().len()).join().unwrap();
+    assert_eq!(read, RGBA_FRAME_BUFFER_SIZE);
+}
+
+#[test]
+fn test_shared_frame_buffer_survives_load_state() {
+    let mut game_boy = build_game_boy(FrameBufferFormat::Rgb565);
+    let state = game_boy.save();
+    let shared = game_boy.share_frame_buffer();
+
+    game_boy.load_state(state).unwrap();
+    game_boy.reset();
+    assert_eq!(game_boy.share_frame_buffer(), shared);
+    game_boy.finish_frame();
+    assert_eq!(*shared.lock(), game_boy.get_frame_buffer());
+}
+
+#[test]
+fn test_scanline_registers_of_finished_frame() {
+    // Keeps the LCD on and never touches the registers
+    let rom = RomBuilder::new().program(&[0x18, 0xFE]).build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    game_boy.finish_frame();
+
+    let lines = game_boy.get_scanline_registers();
+    assert_eq!(lines.len(), 144);
+    assert!(lines
+        .iter()
+        .all(|registers| registers
+            .is_some_and(|registers| registers.bgp == game_boy.peek(BGP_ADDRESS))));
+}
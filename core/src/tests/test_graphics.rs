@@ -0,0 +1,94 @@
+use crate::graphics::{
+    apply_palette, decode_pixel, decode_row, decode_tile, encode_row, get_tile_row, TILE_BYTES,
+};
+use rstest::rstest;
+
+/// The example tile of the Pan Docs, a rounded square with a shaded inside
+/// https://gbdev.io/pandocs/Tile_Data.html
+const PAN_DOCS_TILE: [u8; TILE_BYTES] = [
+    0x3C, 0x7E, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x7E, 0x5E, 0x7E, 0x0A, 0x7C, 0x56, 0x38, 0x7C,
+];
+const PAN_DOCS_PIXELS: [[u8; 8]; 8] = [
+    [0, 2, 3, 3, 3, 3, 2, 0],
+    [0, 3, 0, 0, 0, 0, 3, 0],
+    [0, 3, 0, 0, 0, 0, 3, 0],
+    [0, 3, 0, 0, 0, 0, 3, 0],
+    [0, 3, 1, 3, 3, 3, 3, 0],
+    [0, 1, 1, 1, 3, 1, 3, 0],
+    [0, 3, 1, 3, 1, 3, 2, 0],
+    [0, 2, 3, 3, 3, 2, 0, 0],
+];
+
+#[rstest]
+#[case(0b1000_0000, 0b0000_0000, 0, 1)]
+#[case(0b0000_0000, 0b1000_0000, 0, 2)]
+#[case(0b1000_0001, 0b0000_0001, 7, 3)]
+#[case(0b1000_0001, 0b0000_0001, 0, 1)]
+#[case(0b0111_1110, 0b1111_1111, 0, 2)]
+#[case(0xFF, 0xFF, 3, 3)]
+#[case(0x00, 0x00, 5, 0)]
+fn test_decode_pixel(#[case] low: u8, #[case] high: u8, #[case] x: u8, #[case] expected: u8) {
+    assert_eq!(decode_pixel(low, high, x), expected);
+}
+
+#[test]
+fn test_decode_tile() {
+    assert_eq!(decode_tile(&PAN_DOCS_TILE), PAN_DOCS_PIXELS);
+    for (y, row) in PAN_DOCS_PIXELS.iter().enumerate() {
+        assert_eq!(&get_tile_row(&PAN_DOCS_TILE, y), row, "Row {y}");
+        assert_eq!(
+            &decode_row(PAN_DOCS_TILE[y * 2], PAN_DOCS_TILE[y * 2 + 1]),
+            row
+        );
+    }
+}
+
+/// The lookup table of decode_row has to agree with decoding every pixel on its own
+#[test]
+fn test_decode_row_matches_decode_pixel() {
+    for low in 0..=u8::MAX {
+        for high in 0..=u8::MAX {
+            let expected: [u8; 8] = std::array::from_fn(|x| decode_pixel(low, high, x as u8));
+            assert_eq!(decode_row(low, high), expected, "{low:02X} {high:02X}");
+        }
+    }
+}
+
+#[test]
+fn test_encode_row_round_trip() {
+    for (y, row) in PAN_DOCS_PIXELS.iter().enumerate() {
+        assert_eq!(
+            encode_row(*row),
+            [PAN_DOCS_TILE[y * 2], PAN_DOCS_TILE[y * 2 + 1]]
+        );
+    }
+    for low in 0..=0xFF {
+        for high in [0x00, 0x5A, 0xFF] {
+            assert_eq!(encode_row(decode_row(low, high)), [low, high]);
+        }
+    }
+}
+
+#[test]
+fn test_encode_row_ignores_upper_bits() {
+    assert_eq!(encode_row([0xFF, 0x04, 0, 0, 0, 0, 0, 0x05]), [0x81, 0x80]);
+}
+
+#[rstest]
+#[case::identity(0b1110_0100, [0, 1, 2, 3])]
+#[case::inverted(0b0001_1011, [3, 2, 1, 0])]
+#[case::all_black(0xFF, [3, 3, 3, 3])]
+#[case::dmg_boot(0xFC, [0, 3, 3, 3])]
+fn test_apply_palette(#[case] palette: u8, #[case] expected: [u8; 4]) {
+    for color_id in 0..4 {
+        assert_eq!(
+            apply_palette(palette, color_id),
+            expected[color_id as usize]
+        );
+    }
+    assert_eq!(
+        apply_palette(palette, 4),
+        expected[0],
+        "Only 2 bits of the color ID"
+    );
+}
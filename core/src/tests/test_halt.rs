@@ -0,0 +1,218 @@
+use crate::enums::button::{Button, Buttons};
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::mmu::{IE_ADDRESS, IF_ADDRESS, MMU};
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+
+#[test]
+fn test_halt_no_ime() {
+    let mut mmu = MMU::builder()
+        .rom(0, 0x76)
+        .rom(1, 0x80) // Adds the register B to register A
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask()) // Enable VBlank interrupt
+        .build();
+    let mut cpu = CPU::builder().b(1).build();
+
+    // CPU will be in low power mode and won't execute instructions
+    for _ in 0..5 {
+        let m = cpu.step(&mut mmu).as_m();
+        assert_eq!(m, 1);
+        assert_eq!(cpu.get_pc(), 1);
+        assert_eq!(cpu.get_a(), 0);
+    }
+
+    // Trigger interrupt
+    mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+
+    // Interrupt will be detected but IME is disabled => wake from sleep (1 m-cycle) and continue
+    let m = cpu.step(&mut mmu).as_m();
+    assert_eq!(m, 2);
+    assert_eq!(cpu.get_pc(), 2);
+    assert_eq!(cpu.get_a(), 1);
+}
+
+#[test]
+fn test_halt_ime() {
+    let mut mmu = MMU::builder()
+        .rom(0, 0x76)
+        .rom(1, 0x80)
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .build();
+    let mut cpu = CPU::builder().b(1).ime(true).build();
+
+    // CPU will be in low power mode and won't execute instructions
+    for _ in 0..5 {
+        let m = cpu.step(&mut mmu).as_m();
+        assert_eq!(m, 1);
+        assert_eq!(cpu.get_pc(), 1);
+        assert_eq!(cpu.get_a(), 0);
+    }
+
+    // Trigger interrupt
+    mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+
+    // Interrupt will be detected and IME is enabled => wake from sleep (1 m-cycle) and jump to the interrupt handler
+    let m = cpu.step(&mut mmu).as_m();
+    assert_eq!(m, 6);
+    assert_eq!(cpu.get_pc(), Interrupt::Vblank.get_target_address());
+    assert_eq!(cpu.get_a(), 0);
+}
+
+#[test]
+fn test_halt_bug() {
+    let mut mmu = MMU::builder()
+        .rom(0, 0x76)
+        .rom(1, 0x80)
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .write(IF_ADDRESS, Interrupt::Vblank.get_mask())
+        .build();
+    let mut cpu = CPU::builder().b(1).build();
+
+    // We halt while there's an interrupt scheduled but IME is disabled => HALT bug
+    // The next instruction will be executed twice
+    let m = cpu.step(&mut mmu).as_m();
+    assert_eq!(m, 1);
+    assert_eq!(cpu.get_pc(), 1);
+    assert_eq!(cpu.get_a(), 1);
+
+    // After the 0x80 we had a NOP, but because of the halting bug, 0x80 was executed twice
+    let m = cpu.step(&mut mmu).as_m();
+    assert_eq!(m, 1);
+    assert_eq!(cpu.get_pc(), 2);
+    assert_eq!(cpu.get_a(), 2);
+}
+
+#[test]
+fn test_halt_bug_does_not_chain() {
+    let mut builder = MMU::builder()
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+    for address in 0..0x4000 {
+        builder = builder.rom(address, 0x76);
+    }
+    let mut mmu = builder.build();
+    let mut cpu = CPU::builder().build();
+
+    // Every HALT triggers the bug once and halts on the byte after it, instead of recursing through the whole ROM
+    for pc in 1..=100 {
+        cpu.step(&mut mmu);
+        assert_eq!(cpu.get_pc(), pc);
+    }
+}
+
+/// Mooneye `halt_ime0_ei` and `halt_ime1_timing`: EI followed by HALT.
+/// An interrupt pending at the HALT is serviced right away and returns to the HALT, no extra wake up cycle.
+/// One raised while halted wakes the CPU first and returns behind the HALT.
+#[rstest]
+#[case::pending_at_halt(true, 5, 0x0001)]
+#[case::raised_while_halted(false, 6, 0x0002)]
+fn test_ei_halt(
+    #[case] pending_at_halt: bool,
+    #[case] service_m_cycles: u32,
+    #[case] return_address: u16,
+) {
+    let mut mmu = MMU::builder()
+        .rom(0, 0xFB) // EI
+        .rom(1, 0x76) // HALT
+        .rom(2, 0x04) // INC B
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .build();
+    if pending_at_halt {
+        mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+    }
+    let mut cpu = CPU::builder().sp(0xFFFE).build();
+
+    assert_eq!(cpu.step(&mut mmu).as_m(), 1);
+    assert!(!cpu.get_ime());
+    assert_eq!(cpu.step(&mut mmu).as_m(), 1);
+    assert!(cpu.get_ime());
+    assert_eq!(cpu.is_halted(), !pending_at_halt);
+
+    if !pending_at_halt {
+        assert_eq!(cpu.step(&mut mmu).as_m(), 1);
+        mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+    }
+
+    assert_eq!(cpu.step(&mut mmu).as_m(), service_m_cycles);
+    assert_eq!(cpu.get_pc(), Interrupt::Vblank.get_target_address());
+    assert_eq!(cpu.get_sp(), 0xFFFC);
+    let pushed = u16::from_le_bytes([mmu.read(0xFFFC), mmu.read(0xFFFD)]);
+    assert_eq!(pushed, return_address);
+    assert_eq!(cpu.get_b(), 0);
+}
+
+#[rstest]
+#[case::d3(0xD3)]
+#[case::e4(0xE4)]
+#[case::fd(0xFD)]
+fn test_illegal_opcode_locks_cpu(#[case] opcode: u8) {
+    let mut mmu = MMU::builder()
+        .rom(0, opcode)
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .build();
+    let mut cpu = CPU::builder().ime(true).build();
+
+    assert_eq!(cpu.step(&mut mmu).as_m(), 1);
+    assert!(cpu.is_locked());
+
+    // Not even an interrupt gets the CPU going again
+    mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+    for _ in 0..5 {
+        assert_eq!(cpu.step(&mut mmu).as_m(), 1);
+        assert_eq!(cpu.get_pc(), 0);
+    }
+}
+
+/// Enables the given interrupts, runs the setup, clears IF and halts.
+/// After waking up 0x42 is stored at 0xC000.
+fn build_halting_game_boy(interrupt: Interrupt, setup: &[u8]) -> GameBoy {
+    let mut program = vec![0x3E, interrupt.get_mask(), 0xE0, 0xFF]; // LD A, mask / LDH (IE), A
+    program.extend_from_slice(setup);
+    program.extend_from_slice(&[
+        0xAF, 0xE0, 0x0F, // XOR A / LDH (IF), A
+        0x76, // HALT
+        0x3E, 0x42, 0xEA, 0x00, 0xC0, // LD A, 0x42 / LD (0xC000), A
+        0x18, 0xFE, // JR -2
+    ]);
+
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x103].copy_from_slice(&[0xC3, 0x50, 0x01]); // JP 0x0150
+    rom[0x150..0x150 + program.len()].copy_from_slice(&program);
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+/// Every interrupt source wakes the CPU from HALT when its component raises it
+#[rstest]
+#[case::vblank(Interrupt::Vblank, &[])]
+#[case::lcd(Interrupt::Lcd, &[0x3E, 0x40, 0xE0, 0x41, 0x3E, 0x0A, 0xE0, 0x45])] // LYC interrupt on line 10
+#[case::timer(Interrupt::Timer, &[0x3E, 0x05, 0xE0, 0x07])] // TAC: enabled, 16 T-cycles per increment
+#[case::serial(Interrupt::Serial, &[0x3E, 0x81, 0xE0, 0x02])] // SC: start transfer, internal clock
+#[case::joypad(Interrupt::Joypad, &[])] // A is pressed once the CPU is halted
+fn test_halt_woken_by_component(#[case] interrupt: Interrupt, #[case] setup: &[u8]) {
+    let mut game_boy = build_halting_game_boy(interrupt, setup);
+    while !game_boy.get_cpu().is_halted() {
+        game_boy.step();
+    }
+    for _ in 0..16 {
+        game_boy.step();
+    }
+    assert!(game_boy.get_cpu().is_halted());
+    assert_eq!(game_boy.read_memory(0xC000), 0);
+
+    if interrupt == Interrupt::Joypad {
+        game_boy.set_buttons(Buttons::NONE.with(Button::A));
+    }
+
+    let mut steps = 0;
+    while game_boy.read_memory(0xC000) != 0x42 {
+        game_boy.step();
+        steps += 1;
+        assert!(steps < 100_000, "{interrupt:?} never woke the CPU");
+    }
+    assert!(!game_boy.get_cpu().is_halted());
+    assert_ne!(game_boy.read_memory(IF_ADDRESS) & interrupt.get_mask(), 0);
+}
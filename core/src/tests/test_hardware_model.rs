@@ -0,0 +1,231 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::{
+    DIV_ADDRESS, DMA_ADDRESS, LY_ADDRESS, SC_ADDRESS, STAT_ADDRESS,
+};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::hardware_model::HardwareModel;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+
+const NR52_ADDRESS: u16 = 0xFF26;
+
+/// A ROM only with the header fields the boot ROMs look at
+fn build_cartridge(title: &str, old_licensee: u8, header_checksum: u8) -> Cartridge {
+    Cartridge::from_bytes(&build_rom(title, old_licensee, header_checksum)).unwrap()
+}
+
+fn build_rom(title: &str, old_licensee: u8, header_checksum: u8) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x134..0x134 + title.len()].copy_from_slice(title.as_bytes());
+    rom[0x14B] = old_licensee;
+    rom[0x14D] = header_checksum;
+    rom
+}
+
+fn initialize(cartridge: &Cartridge, model: HardwareModel) -> GameBoy {
+    GameBoy::initialize_with_config(cartridge, GameBoyConfig::default().model(model)).unwrap()
+}
+
+/// Without the memory at PC, which is the same for every model
+fn registers_line(game_boy: &GameBoy) -> String {
+    let line = game_boy.doctor_log_line().to_string();
+    line[..line.find(" PCMEM").unwrap()].to_string()
+}
+
+#[rstest]
+#[case::dmg0(
+    HardwareModel::Dmg0,
+    "A:01 F:00 B:FF C:13 D:00 E:C1 H:84 L:03 SP:FFFE PC:0100"
+)]
+#[case::dmg(
+    HardwareModel::Dmg,
+    "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100"
+)]
+#[case::mgb(
+    HardwareModel::Mgb,
+    "A:FF F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100"
+)]
+#[case::sgb(
+    HardwareModel::Sgb,
+    "A:01 F:00 B:00 C:14 D:00 E:00 H:C0 L:60 SP:FFFE PC:0100"
+)]
+#[case::sgb2(
+    HardwareModel::Sgb2,
+    "A:FF F:00 B:00 C:14 D:00 E:00 H:C0 L:60 SP:FFFE PC:0100"
+)]
+#[case::cgb(
+    HardwareModel::Cgb,
+    "A:11 F:80 B:00 C:00 D:00 E:08 H:00 L:7C SP:FFFE PC:0100"
+)]
+#[case::agb(
+    HardwareModel::Agb,
+    "A:11 F:00 B:01 C:00 D:00 E:08 H:00 L:7C SP:FFFE PC:0100"
+)]
+fn test_cpu_registers(#[case] model: HardwareModel, #[case] expected: &str) {
+    // cpu_instrs supports the CGB, a DMG only game is needed for the CGB and AGB
+    let cartridge = Cartridge::load(test_rom_file_path().join("dmg-acid2.gb")).unwrap();
+    assert_eq!(registers_line(&initialize(&cartridge, model)), expected);
+}
+
+#[rstest]
+#[case::checksum_set(HardwareModel::Dmg, 0x42, "A:01 F:B0")]
+#[case::checksum_zero(HardwareModel::Dmg, 0x00, "A:01 F:80")]
+#[case::mgb_checksum_zero(HardwareModel::Mgb, 0x00, "A:FF F:80")]
+fn test_dmg_flags_follow_header_checksum(
+    #[case] model: HardwareModel,
+    #[case] header_checksum: u8,
+    #[case] expected_af: &str,
+) {
+    let game_boy = initialize(&build_cartridge("TETRIS", 0x01, header_checksum), model);
+    assert!(registers_line(&game_boy).starts_with(expected_af));
+}
+
+/// B is the title checksum of Nintendo games, two of the checksums move HL
+#[rstest]
+#[case::nintendo("TETRIS", 0x01, "B:DB C:00 D:00 E:08 H:00 L:7C")]
+#[case::special_checksum("C", 0x01, "B:43 C:00 D:00 E:08 H:99 L:1A")]
+#[case::other_licensee("TETRIS", 0x08, "B:00 C:00 D:00 E:08 H:00 L:7C")]
+fn test_cgb_registers_follow_title(
+    #[case] title: &str,
+    #[case] old_licensee: u8,
+    #[case] expected: &str,
+) {
+    let game_boy = initialize(
+        &build_cartridge(title, old_licensee, 0x42),
+        HardwareModel::Cgb,
+    );
+    assert!(registers_line(&game_boy).contains(expected));
+}
+
+/// The AGB increments the B of the CGB, F holds the zero and half carry flags of that increment
+#[rstest]
+#[case::nintendo("TETRIS", 0x01, "A:11 F:00 B:DC C:00 D:00 E:08 H:00 L:7C")]
+#[case::special_checksum("C", 0x01, "A:11 F:00 B:44 C:00 D:00 E:08 H:99 L:1A")]
+#[case::half_carry("?", 0x01, "A:11 F:20 B:40")]
+#[case::zero("UUU", 0x01, "A:11 F:A0 B:00")]
+fn test_agb_registers_follow_title(
+    #[case] title: &str,
+    #[case] old_licensee: u8,
+    #[case] expected: &str,
+) {
+    let game_boy = initialize(
+        &build_cartridge(title, old_licensee, 0x42),
+        HardwareModel::Agb,
+    );
+    assert!(registers_line(&game_boy).starts_with(expected));
+}
+
+#[rstest]
+#[case::dmg0(HardwareModel::Dmg0, [0x7E, 0x18, 0xF1, 0x81, 0x91, 0xFF])]
+#[case::dmg(HardwareModel::Dmg, [0x7E, 0xAB, 0xF1, 0x85, 0x00, 0xFF])]
+#[case::mgb(HardwareModel::Mgb, [0x7E, 0xAB, 0xF1, 0x85, 0x00, 0xFF])]
+#[case::sgb(HardwareModel::Sgb, [0x7E, 0xAB, 0xF0, 0x85, 0x00, 0xFF])]
+#[case::sgb2(HardwareModel::Sgb2, [0x7E, 0xAB, 0xF0, 0x85, 0x00, 0xFF])]
+#[case::cgb(HardwareModel::Cgb, [0x7F, 0xAB, 0xF1, 0x85, 0x00, 0x00])]
+#[case::agb(HardwareModel::Agb, [0x7F, 0xAB, 0xF1, 0x85, 0x00, 0x00])]
+fn test_io_registers(#[case] model: HardwareModel, #[case] expected: [u8; 6]) {
+    let mut game_boy = initialize(&build_cartridge("TETRIS", 0x01, 0x42), model);
+    let addresses = [
+        SC_ADDRESS,
+        DIV_ADDRESS,
+        NR52_ADDRESS,
+        STAT_ADDRESS,
+        LY_ADDRESS,
+        DMA_ADDRESS,
+    ];
+    assert_eq!(addresses.map(|address| game_boy.peek(address)), expected);
+
+    // A reset returns to the state of the same model
+    game_boy.finish_frame();
+    game_boy.reset();
+    assert_eq!(addresses.map(|address| game_boy.peek(address)), expected);
+}
+
+/// The timer writes its counter to DIV on every step, so it has to start from the model's value as well
+#[test]
+fn test_timer_starts_at_model_div() {
+    let mut game_boy = initialize(&build_cartridge("TETRIS", 0x01, 0x42), HardwareModel::Dmg);
+    game_boy.step();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), 0xAB);
+}
+
+/// The lower byte of the counter decides after how many cycles DIV first increments
+#[rstest]
+#[case::dmg0(HardwareModel::Dmg0, 0x18, 64)]
+#[case::dmg(HardwareModel::Dmg, 0xAB, 13)]
+#[case::mgb(HardwareModel::Mgb, 0xAB, 13)]
+#[case::cgb(HardwareModel::Cgb, 0xAB, 64)]
+fn test_first_div_increment(
+    #[case] model: HardwareModel,
+    #[case] div: u8,
+    #[case] m_cycles_until_increment: u32,
+) {
+    let mut game_boy = initialize(&build_cartridge("TETRIS", 0x01, 0x42), model);
+    // The ROM is all NOPs, each of them takes one M-cycle
+    for _ in 0..m_cycles_until_increment - 1 {
+        game_boy.step();
+    }
+    assert_eq!(game_boy.peek(DIV_ADDRESS), div);
+    game_boy.step();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), div + 1);
+}
+
+#[test]
+fn test_config_overrides_div_counter() {
+    let config = GameBoyConfig::default()
+        .model(HardwareModel::Dmg)
+        .div_counter(0x12F8);
+    let mut game_boy =
+        GameBoy::initialize_with_config(&build_cartridge("TETRIS", 0x01, 0x42), config).unwrap();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), 0x12);
+    game_boy.step();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), 0x12);
+    game_boy.step();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), 0x13);
+
+    // A reset starts from the override again
+    game_boy.finish_frame();
+    game_boy.reset();
+    assert_eq!(game_boy.peek(DIV_ADDRESS), 0x12);
+}
+
+#[rstest]
+#[case::dmg0(HardwareModel::Dmg0, false, true)]
+#[case::dmg(HardwareModel::Dmg, false, true)]
+#[case::mgb(HardwareModel::Mgb, false, true)]
+#[case::sgb(HardwareModel::Sgb, false, true)]
+#[case::sgb2(HardwareModel::Sgb2, false, true)]
+#[case::cgb(HardwareModel::Cgb, true, false)]
+#[case::agb(HardwareModel::Agb, true, false)]
+fn test_capabilities(
+    #[case] model: HardwareModel,
+    #[case] supports_cgb_mode: bool,
+    #[case] has_oam_bug: bool,
+) {
+    assert_eq!(model.supports_cgb_mode(), supports_cgb_mode);
+    assert_eq!(model.colorizes_dmg_games(), supports_cgb_mode);
+    assert_eq!(model.has_oam_bug(), has_oam_bug);
+    assert_eq!(
+        GameBoyConfig::default().model(model).colorize,
+        supports_cgb_mode
+    );
+}
+
+/// CGB mode isn't emulated, so the CGB and AGB refuse games they would run in it
+#[rstest]
+#[case::dmg_on_cgb(HardwareModel::Cgb, 0x00, true)]
+#[case::compatible_on_cgb(HardwareModel::Cgb, 0x80, false)]
+#[case::cgb_only_on_agb(HardwareModel::Agb, 0xC0, false)]
+#[case::compatible_on_dmg(HardwareModel::Dmg, 0x80, true)]
+#[case::compatible_on_sgb(HardwareModel::Sgb, 0x80, true)]
+fn test_cgb_games(#[case] model: HardwareModel, #[case] cgb_flag: u8, #[case] runs: bool) {
+    let mut rom = build_rom("TETRIS", 0x01, 0x42);
+    rom[0x143] = cgb_flag;
+    let cartridge = Cartridge::from_bytes(&rom).unwrap();
+    let config = GameBoyConfig::default().model(model);
+    assert_eq!(
+        GameBoy::initialize_with_config(&cartridge, config).is_ok(),
+        runs
+    );
+}
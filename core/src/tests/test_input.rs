@@ -0,0 +1,96 @@
+use crate::enums::button::{Button, Buttons};
+use crate::input::{InputLayer, InputMacro};
+use rstest::rstest;
+
+fn run_frames(input: &mut InputLayer, held: Buttons, frames: usize) -> Vec<Buttons> {
+    (0..frames).map(|_| input.next_frame(held)).collect()
+}
+
+#[test]
+fn test_buttons() {
+    let mut buttons = Buttons::NONE.with(Button::A).with(Button::Down);
+    assert_eq!(buttons.bits(), 0b1000_0001);
+    assert!(buttons.is_pressed(Button::Down));
+
+    buttons.release(Button::A);
+    buttons.set(Button::Start, true);
+    assert_eq!(
+        buttons.iter().collect::<Vec<_>>(),
+        vec![Button::Start, Button::Down]
+    );
+}
+
+#[test]
+fn test_input_passes_through() {
+    let mut input = InputLayer::new();
+    let held = Buttons::NONE.with(Button::A).with(Button::Left);
+    assert_eq!(run_frames(&mut input, held, 5), vec![held; 5]);
+}
+
+#[rstest]
+#[case(1, vec![true, false, true, false, true, false])]
+#[case(2, vec![true, true, false, false, true, true])]
+#[case(3, vec![true, true, true, false, false, false])]
+fn test_turbo(#[case] interval: u8, #[case] expected: Vec<bool>) {
+    let mut input = InputLayer::new();
+    input.set_turbo(Button::A, true);
+    input.set_turbo_interval(interval);
+
+    let held = Buttons::NONE.with(Button::A).with(Button::Up);
+    let frames = run_frames(&mut input, held, expected.len());
+    let pressed: Vec<bool> = frames.iter().map(|b| b.is_pressed(Button::A)).collect();
+    assert_eq!(pressed, expected);
+    // Buttons without turbo are unaffected
+    assert!(frames.iter().all(|b| b.is_pressed(Button::Up)));
+}
+
+#[test]
+fn test_turbo_restarts_pressed() {
+    let mut input = InputLayer::new();
+    input.set_turbo(Button::B, true);
+    input.set_turbo_interval(1);
+    let held = Buttons::NONE.with(Button::B);
+
+    run_frames(&mut input, held, 1);
+    input.next_frame(Buttons::NONE);
+    assert!(input.next_frame(held).is_pressed(Button::B));
+}
+
+#[test]
+fn test_record_and_replay_macro() {
+    let mut input = InputLayer::new();
+    let sequence = [
+        Buttons::NONE.with(Button::Down),
+        Buttons::NONE.with(Button::Right),
+        Buttons::NONE.with(Button::A),
+    ];
+
+    input.start_recording(1);
+    for buttons in sequence {
+        input.next_frame(buttons);
+    }
+    assert_eq!(input.stop_recording(), Some(1));
+    assert_eq!(input.get_macro(1).unwrap().get_frames(), &sequence);
+
+    assert!(input.play_macro(1));
+    let held = Buttons::NONE.with(Button::Select);
+    let frames = run_frames(&mut input, held, 4);
+    for (frame, buttons) in frames.iter().zip(sequence) {
+        assert_eq!(*frame, buttons.union(held));
+    }
+    assert_eq!(frames[3], held);
+    assert!(!input.is_playing());
+}
+
+#[test]
+fn test_play_empty_macro_slot() {
+    let mut input = InputLayer::new();
+    assert!(!input.play_macro(0));
+
+    input.set_macro(0, InputMacro::default());
+    assert!(!input.play_macro(0));
+
+    input.start_recording(2);
+    assert_eq!(input.stop_recording(), None);
+    assert_eq!(input.get_macro(2), None);
+}
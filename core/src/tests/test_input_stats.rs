@@ -0,0 +1,148 @@
+use crate::enums::button::{Button, Buttons};
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::P1_ADDRESS;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::input_stats::InputStats;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+/// Runs the given program from 0x0150
+fn build_game_boy(program: &[u8]) -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x103].copy_from_slice(&[0xC3, 0x50, 0x01]); // JP 0x0150
+    rom[0x150..0x150 + program.len()].copy_from_slice(program);
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+/// LDH A, (P1) / JR -4
+const POLLING_PROGRAM: [u8; 4] = [0xF0, 0x00, 0x18, 0xFC];
+/// JR -2
+const IDLE_PROGRAM: [u8; 2] = [0x18, 0xFE];
+
+/// One frame per entry, true if the game read P1 during it
+#[rstest]
+#[case::always_polled(&[true, true, true], 0, 0)]
+#[case::never_polled(&[false, false, false], 3, 3)]
+#[case::streaks(&[false, false, true, false, true, false, false, false], 6, 3)]
+fn test_lag_frames(#[case] frames: &[bool], #[case] lag_frames: u64, #[case] longest: u64) {
+    let mut stats = InputStats::default();
+    for &polled in frames {
+        stats.step(Cycles::from_m(10), polled);
+        assert_eq!(stats.end_frame(), !polled);
+    }
+
+    assert_eq!(stats.get_frames(), frames.len() as u64);
+    assert_eq!(stats.get_lag_frames(), lag_frames);
+    assert_eq!(stats.get_longest_lag_streak(), longest);
+}
+
+#[test]
+fn test_lag_streak_resets_on_poll() {
+    let mut stats = InputStats::default();
+    stats.end_frame();
+    stats.end_frame();
+    assert_eq!(stats.get_lag_streak(), 2);
+
+    stats.step(Cycles::from_m(1), true);
+    stats.end_frame();
+    assert_eq!(stats.get_lag_streak(), 0);
+    assert_eq!(stats.get_longest_lag_streak(), 2);
+}
+
+/// Frame listeners see the same lag frames as the input statistics
+#[rstest]
+#[case::polling(&POLLING_PROGRAM, false)]
+#[case::idle(&IDLE_PROGRAM, true)]
+fn test_frame_listener_reports_lag_frames(#[case] program: &[u8], #[case] dropped: bool) {
+    let mut game_boy = build_game_boy(program);
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let recorded = frames.clone();
+    game_boy.on_frame(move |info| recorded.lock().unwrap().push(*info));
+    for _ in 0..3 {
+        game_boy.finish_frame();
+    }
+
+    let frames = frames.lock().unwrap();
+    assert_eq!(frames.len(), 3);
+    assert!(frames
+        .iter()
+        .all(|info| info.dropped == dropped && !info.lcd_off));
+    assert_eq!(
+        game_boy.get_input_stats().get_lag_frames(),
+        frames.len() as u64 * dropped as u64
+    );
+}
+
+#[test]
+fn test_input_latency() {
+    let mut stats = InputStats::default();
+    assert_eq!(stats.get_average_input_latency(), None);
+
+    // Read 100 T-cycles after the change, the step reading P1 counts fully
+    stats.record_input_change();
+    stats.step(Cycles::from_t(96), false);
+    stats.step(Cycles::from_t(4), true);
+
+    // A second change before the game read the first one doesn't restart the measurement
+    stats.record_input_change();
+    stats.step(Cycles::from_t(200), false);
+    stats.record_input_change();
+    stats.step(Cycles::from_t(100), true);
+
+    // Reads without an input change don't count
+    stats.step(Cycles::from_t(1000), true);
+
+    assert_eq!(stats.get_input_changes(), 2);
+    assert_eq!(stats.get_average_input_latency(), Some(Cycles::from_t(200)));
+    assert_eq!(stats.get_max_input_latency(), Some(Cycles::from_t(300)));
+
+    stats.reset();
+    assert_eq!(stats.get_input_changes(), 0);
+    assert_eq!(stats.get_max_input_latency(), None);
+}
+
+#[rstest]
+#[case::polling(&POLLING_PROGRAM, 0)]
+#[case::idle(&IDLE_PROGRAM, 10)]
+fn test_game_boy_lag_frames(#[case] program: &[u8], #[case] lag_frames: u64) {
+    let mut game_boy = build_game_boy(program);
+    for _ in 0..10 {
+        game_boy.finish_frame();
+    }
+    assert_eq!(game_boy.get_input_stats().get_frames(), 10);
+    assert_eq!(game_boy.get_input_stats().get_lag_frames(), lag_frames);
+}
+
+/// Only the CPU polls the joypad, a debugger reading P1 doesn't
+#[test]
+fn test_debug_reads_are_not_polls() {
+    let mut game_boy = build_game_boy(&IDLE_PROGRAM);
+    while !game_boy.step() {
+        game_boy.read_memory(P1_ADDRESS);
+    }
+    assert_eq!(game_boy.get_input_stats().get_lag_frames(), 1);
+}
+
+#[test]
+fn test_game_boy_input_latency() {
+    let mut game_boy = build_game_boy(&POLLING_PROGRAM);
+    game_boy.finish_frame();
+
+    game_boy.set_buttons(Buttons::NONE.with(Button::A));
+    // Holding the same buttons is not a change
+    game_boy.set_buttons(Buttons::NONE.with(Button::A));
+    game_boy.finish_frame();
+
+    let stats = game_boy.get_input_stats();
+    assert_eq!(stats.get_input_changes(), 1);
+    // One loop iteration takes 6 M-cycles, the read happens in its first instruction
+    let latency = stats.get_max_input_latency().unwrap();
+    assert!(latency <= Cycles::from_m(6), "{latency}");
+
+    // Reset keeps the statistics of the session
+    game_boy.reset();
+    assert_eq!(game_boy.get_input_stats().get_frames(), 2);
+    game_boy.reset_input_stats();
+    assert_eq!(game_boy.get_input_stats().get_frames(), 0);
+}
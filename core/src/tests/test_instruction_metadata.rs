@@ -0,0 +1,145 @@
+use crate::enums::parameter_groups::JumpCondition;
+use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::mmu::MMU;
+use crate::instructions::metadata::{FlagEffect, InstructionMetadata};
+use crate::instructions::Instruction;
+use rstest::rstest;
+
+const CONTROL_FLOW: [&str; 6] = ["JP", "JR", "CALL", "RET", "RETI", "RST"];
+
+/// Runs the instruction with all flags reset or set, returns (PC advance, M-cycles, F) per run
+fn execute(instruction: &Instruction, f: u8) -> (u16, u8, u8) {
+    let mut mmu = MMU::default();
+    let mut cpu = CPU::builder()
+        .pc(0xC000)
+        .sp(0xDFF0)
+        .hl(0xC800)
+        .a(0x3C)
+        .b(0x42)
+        .f(f)
+        .build();
+    let (next_pc, m_cycles) = cpu.execute(*instruction, &mut mmu);
+    (next_pc.wrapping_sub(0xC000), m_cycles, cpu.get_f())
+}
+
+/// The table is checked against what the CPU actually does, so both stay in sync
+#[rstest]
+fn test_metadata_matches_cpu(#[values(false, true)] prefixed: bool) {
+    for byte in 0..=255u8 {
+        let Ok(instruction) = Instruction::from_byte(byte, prefixed) else {
+            continue;
+        };
+        let metadata = instruction.metadata();
+
+        for f in [0x00, 0xF0] {
+            let (pc_advance, m_cycles, new_f) = execute(&instruction, f);
+            assert!(
+                m_cycles == metadata.min_cycles || m_cycles == metadata.max_cycles,
+                "{instruction:?}: {m_cycles} M-cycles, expected {metadata:?}"
+            );
+            if !CONTROL_FLOW.contains(&metadata.mnemonic) {
+                assert_eq!(pc_advance as usize, metadata.length, "{instruction:?}");
+            }
+
+            for (bit, effect) in (4..8).rev().zip(metadata.flags.as_array()) {
+                let before = f & (1 << bit) != 0;
+                let after = new_f & (1 << bit) != 0;
+                match effect {
+                    FlagEffect::Unaffected => {
+                        assert_eq!(after, before, "{instruction:?} F bit {bit}")
+                    }
+                    FlagEffect::Set => assert!(after, "{instruction:?} F bit {bit}"),
+                    FlagEffect::Reset => assert!(!after, "{instruction:?} F bit {bit}"),
+                    FlagEffect::Affected => {}
+                }
+            }
+        }
+    }
+}
+
+/// Every opcode returns exactly the cycles of the table, conditional ones for both outcomes
+#[rstest]
+fn test_cycles_match_metadata(#[values(false, true)] prefixed: bool) {
+    for byte in 0..=255u8 {
+        let Ok(instruction) = Instruction::from_byte(byte, prefixed) else {
+            continue;
+        };
+        let metadata = instruction.metadata();
+
+        // With all flags reset and all flags set, every condition is met once
+        let mut outcomes = Vec::new();
+        for f in [0x00, 0xF0] {
+            let (pc_advance, m_cycles, _) = execute(&instruction, f);
+            let zero = f & 0x80 != 0;
+            let carry = f & 0x10 != 0;
+            let condition_met = match instruction.get_condition() {
+                Some(JumpCondition::NotZero) => !zero,
+                Some(JumpCondition::Zero) => zero,
+                Some(JumpCondition::NotCarry) => !carry,
+                Some(JumpCondition::Carry) => carry,
+                None => true,
+            };
+            assert_eq!(
+                m_cycles,
+                instruction.get_cycles(condition_met),
+                "{instruction:?} with F={f:02X}"
+            );
+            if !condition_met {
+                assert_eq!(pc_advance as usize, metadata.length, "{instruction:?}");
+            }
+            outcomes.push(condition_met);
+        }
+
+        if instruction.get_condition().is_some() {
+            assert_ne!(
+                outcomes[0], outcomes[1],
+                "{instruction:?} only ran one branch"
+            );
+            assert!(metadata.min_cycles < metadata.max_cycles, "{instruction:?}");
+        } else {
+            assert_eq!(metadata.min_cycles, metadata.max_cycles, "{instruction:?}");
+        }
+    }
+}
+
+#[rstest]
+#[case(0x00, false, "NOP", 1, 1, 1, "----")]
+#[case(0x20, false, "JR", 2, 2, 3, "----")]
+#[case(0x34, false, "INC", 1, 3, 3, "Z0H-")]
+#[case(0xC0, false, "RET", 1, 2, 5, "----")]
+#[case(0xC2, false, "JP", 3, 3, 4, "----")]
+#[case(0xC4, false, "CALL", 3, 3, 6, "----")]
+#[case(0xE8, false, "ADD", 2, 4, 4, "00HC")]
+#[case(0xF1, false, "POP", 1, 3, 3, "ZNHC")]
+#[case(0x46, true, "BIT", 2, 3, 3, "Z01-")]
+#[case(0x36, true, "SWAP", 2, 4, 4, "Z000")]
+fn test_metadata(
+    #[case] byte: u8,
+    #[case] prefixed: bool,
+    #[case] mnemonic: &str,
+    #[case] length: usize,
+    #[case] min_cycles: u8,
+    #[case] max_cycles: u8,
+    #[case] flags: &str,
+) {
+    let metadata = Instruction::from_byte(byte, prefixed).unwrap().metadata();
+    assert_eq!(metadata.mnemonic, mnemonic);
+    assert_eq!(metadata.length, length);
+    assert_eq!(metadata.min_cycles, min_cycles);
+    assert_eq!(metadata.max_cycles, max_cycles);
+    assert_eq!(metadata.flags.to_string(), flags);
+}
+
+#[test]
+fn test_metadata_table() {
+    let unprefixed = InstructionMetadata::table(false);
+    let prefixed = InstructionMetadata::table(true);
+    assert_eq!(unprefixed.len(), 256);
+    // 11 illegal opcodes and the prefix byte
+    assert_eq!(unprefixed.iter().filter(|m| m.is_none()).count(), 12);
+    assert_eq!(unprefixed[0xCB], None);
+    assert!(prefixed.iter().all(|m| m.is_some()));
+    assert_eq!(unprefixed[0x76].unwrap().mnemonic, "HALT");
+}
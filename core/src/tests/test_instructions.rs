@@ -1,9 +1,11 @@
+#![allow(clippy::too_many_arguments)]
+
 use crate::enums::parameter_groups::R8;
 use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
 use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
 use crate::game_boy::components::cpu::{CPU, PREFIX_INSTRUCTION_BYTE};
 use crate::game_boy::components::mmu::MMU;
-use crate::helpers::bit_operations::{construct_u16, deconstruct_u16};
+use crate::instructions::Instruction;
 use rstest::rstest;
 
 /// ADD register (B, C, D, E, H, L)
@@ -107,7 +109,7 @@ fn test_add_register(
         .l(if opcode == 0x85 { value } else { 0 })
         .build();
 
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_registers().get_a(), expected_a);
     assert_eq!(cpu.get_registers().get_f_zero(), expected_z);
@@ -145,7 +147,7 @@ fn test_add_hl(
 
     let mut mmu = MMU::builder().rom(0, 0x86).rom(ADDRESS, value).build();
     let mut cpu = CPU::builder().a(a).hl(ADDRESS).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_registers().get_a(), expected_a);
     assert_eq!(cpu.get_registers().get_f_zero(), expected_z);
@@ -176,7 +178,7 @@ fn test_add_a(
 ) {
     let mut mmu = MMU::builder().rom(0, 0x87).build();
     let mut cpu = CPU::builder().a(a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_registers().get_a(), expected_a);
     assert_eq!(cpu.get_registers().get_f_zero(), expected_z);
@@ -205,7 +207,7 @@ fn test_add_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xC6).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -308,7 +310,7 @@ fn test_adc_r8(
         .f_carry(carry)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -347,7 +349,7 @@ fn test_adc_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xCE).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).f_carry(carry).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -393,7 +395,7 @@ fn test_add_hl_r16(
         .f_subtract(true)
         .f_zero(true)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -413,7 +415,7 @@ fn test_add_hl_r16(
 fn test_add_sp_imm8(#[case] value_sp: u16, #[case] imm: i8, #[case] expected_sp: u16) {
     let mut mmu = MMU::builder().rom(0, 0xE8).rom(1, imm as u8).build();
     let mut cpu = CPU::builder().sp(value_sp).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_pc(), 2);
@@ -451,7 +453,7 @@ fn test_and_r8(
         .hl(0xCCCC)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -478,7 +480,7 @@ fn test_and_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xE6).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -498,7 +500,7 @@ fn test_call() {
         .rom(0x11FF, 0xCC)
         .build();
     let mut cpu = CPU::builder().pc(0x11FD).sp(0xFFFE).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 6);
     assert_eq!(cpu.get_pc(), 0xCCFF);
@@ -525,7 +527,7 @@ fn test_call_cond(
     #[case] flag_carry: bool,
     #[case] expected_pc: u16,
     #[case] expected_sp: u16,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
 ) {
     let mut mmu = MMU::builder()
         .rom(initial_pc, opcode)
@@ -538,7 +540,7 @@ fn test_call_cond(
         .f_zero(flag_zero)
         .f_carry(flag_carry)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), expected_pc);
@@ -599,7 +601,7 @@ fn test_cp_r8(
         .hl(0xCCCC)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -631,7 +633,7 @@ fn test_cp_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xFE).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -647,7 +649,7 @@ fn test_cp_imm8(
 fn test_cpl() {
     let mut mmu = MMU::builder().rom(0, 0x2F).build();
     let mut cpu = CPU::builder().a(0b1010_1010).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -667,7 +669,7 @@ fn test_ccf() {
         .f_half_carry(true)
         .f_zero(true)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -703,7 +705,7 @@ fn test_daa(
         .f_half_carry(half_carry)
         .f_zero(false)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -719,6 +721,97 @@ fn test_daa(
     }
 }
 
+/// Reference DAA based on the BCD correction table from the Pan Docs, returns (A, carry)
+fn reference_daa(a: u8, subtract: bool, half_carry: bool, carry: bool) -> (u8, bool) {
+    let mut correction = 0u8;
+    let mut new_carry = carry;
+    if half_carry || (!subtract && a & 0x0F > 0x09) {
+        correction |= 0x06;
+    }
+    if carry || (!subtract && a > 0x99) {
+        correction |= 0x60;
+        new_carry = true;
+    }
+
+    if subtract {
+        (a.wrapping_sub(correction), new_carry)
+    } else {
+        (a.wrapping_add(correction), new_carry)
+    }
+}
+
+/// DAA (0x27) for every A value and every N/H/C combination, Z as input must not matter
+#[test]
+fn test_daa_exhaustive() {
+    for a in 0..=u8::MAX {
+        for flags in 0..16u8 {
+            let subtract = flags & 0b0001 != 0;
+            let half_carry = flags & 0b0010 != 0;
+            let carry = flags & 0b0100 != 0;
+            let zero = flags & 0b1000 != 0;
+
+            let mut mmu = MMU::builder().rom(0, 0x27).build();
+            let mut cpu = CPU::builder()
+                .a(a)
+                .f_zero(zero)
+                .f_subtract(subtract)
+                .f_half_carry(half_carry)
+                .f_carry(carry)
+                .build();
+            cpu.step(&mut mmu);
+
+            let (expected_a, expected_carry) = reference_daa(a, subtract, half_carry, carry);
+            let context = format!("A={a:#04X} Z={zero} N={subtract} H={half_carry} C={carry}");
+            assert_eq!(cpu.get_a(), expected_a, "{context}");
+            assert_eq!(cpu.get_f_carry(), expected_carry, "{context}");
+            assert_eq!(cpu.get_f_zero(), expected_a == 0, "{context}");
+            assert_eq!(cpu.get_f_subtract(), subtract, "{context}");
+            assert!(!cpu.get_f_half_carry(), "{context}");
+        }
+    }
+}
+
+fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// ADD/ADC/SUB/SBC B followed by DAA yields the correct BCD result for every pair of BCD operands
+#[rstest]
+#[case::add(0x80, false, false)]
+#[case::adc(0x88, false, true)]
+#[case::sub(0x90, true, false)]
+#[case::sbc(0x98, true, true)]
+fn test_daa_bcd_arithmetic(#[case] opcode: u8, #[case] subtract: bool, #[case] with_carry: bool) {
+    let carry_inputs: &[bool] = if with_carry { &[false, true] } else { &[false] };
+    for &carry_in in carry_inputs {
+        for left in 0..100u8 {
+            for right in 0..100u8 {
+                let mut mmu = MMU::builder().rom(0, opcode).rom(1, 0x27).build();
+                let mut cpu = CPU::builder()
+                    .a(to_bcd(left))
+                    .b(to_bcd(right))
+                    .f_carry(carry_in)
+                    .build();
+                cpu.step(&mut mmu);
+                cpu.step(&mut mmu);
+
+                let carry = carry_in as i16;
+                let (left, right) = (left as i16, right as i16);
+                let exact = if subtract {
+                    left - right - carry
+                } else {
+                    left + right + carry
+                };
+                let expected = to_bcd(exact.rem_euclid(100) as u8);
+                let context = format!("{left} {right} carry_in={carry_in}");
+                assert_eq!(cpu.get_a(), expected, "{context}");
+                assert_eq!(cpu.get_f_carry(), !(0..100).contains(&exact), "{context}");
+                assert_eq!(cpu.get_f_zero(), expected == 0, "{context}");
+            }
+        }
+    }
+}
+
 /// DEC r8 (except HL)
 #[rstest]
 #[case::decrement_b(0x05, 23)]
@@ -740,7 +833,7 @@ fn test_dec_r8(#[case] opcode: u8, #[case] value: u8) {
         .l(if opcode == 0x2D { value } else { 0 })
         .a(if opcode == 0x3D { value } else { 0 })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -769,7 +862,7 @@ fn test_dec_hl() {
 
     let mut mmu = MMU::builder().rom(0, 0x35).write(ADDRESS, VALUE).build();
     let mut cpu = CPU::builder().hl(ADDRESS).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 1);
@@ -790,7 +883,7 @@ fn test_dec_r16(#[case] opcode: u8, #[case] value: u16) {
         .hl(if opcode == 0x2B { value } else { 0 })
         .sp(if opcode == 0x3B { value } else { 0 })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -808,7 +901,7 @@ fn test_dec_r16(#[case] opcode: u8, #[case] value: u16) {
 fn test_di() {
     let mut mmu = MMU::builder().rom(0, 0xF3).build();
     let mut cpu = CPU::builder().ime(true).deferred_set_ime(true).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -821,7 +914,7 @@ fn test_di() {
 fn test_ei() {
     let mut mmu = MMU::builder().rom(0, 0xFB).build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -854,7 +947,7 @@ fn test_inc_r8(#[case] opcode: u8, #[case] value: u8) {
         .l(if opcode == 0x2C { value } else { 0 })
         .a(if opcode == 0x3C { value } else { 0 })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -883,7 +976,7 @@ fn test_inc_hl() {
 
     let mut mmu = MMU::builder().rom(0, 0x34).write(ADDRESS, VALUE).build();
     let mut cpu = CPU::builder().hl(ADDRESS).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 1);
@@ -904,7 +997,7 @@ fn test_inc_r16(#[case] opcode: u8, #[case] value: u16) {
         .hl(if opcode == 0x23 { value } else { 0 })
         .sp(if opcode == 0x33 { value } else { 0 })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -923,7 +1016,7 @@ fn test_nop() {
     let mut mmu = MMU::builder().rom(0, 0x00).build();
     let mut cpu = CPU::default();
 
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
     assert_eq!(cpu.get_pc(), 1);
     assert_eq!(m, 1);
 }
@@ -946,7 +1039,7 @@ fn test_ld_a_r16(#[case] opcode: u8, #[case] address: u16, #[case] value: u8) {
             0
         })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -977,7 +1070,7 @@ fn test_ld_r16_a(#[case] opcode: u8, #[case] address: u16, #[case] value: u8) {
             0
         })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -1000,13 +1093,13 @@ fn test_ld_imm16_sp(#[case] sp: u16, #[case] addr_lsb: u8, #[case] addr_msb: u8)
         .rom(2, addr_msb)
         .build();
     let mut cpu = CPU::builder().sp(sp).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 5);
     assert_eq!(cpu.get_pc(), 3);
 
-    let address = construct_u16(addr_lsb, addr_msb);
-    let (sp_lsb, sp_msb) = deconstruct_u16(cpu.get_sp());
+    let address = u16::from_le_bytes([addr_lsb, addr_msb]);
+    let [sp_lsb, sp_msb] = cpu.get_sp().to_le_bytes();
     assert_eq!(mmu.read(address), sp_lsb);
     assert_eq!(mmu.read(address + 1), sp_msb);
 }
@@ -1023,7 +1116,7 @@ fn test_ld_imm16_sp(#[case] sp: u16, #[case] addr_lsb: u8, #[case] addr_msb: u8)
 fn test_ld_r8_imm8(#[case] opcode: u8, #[case] value: u8) {
     let mut mmu = MMU::builder().rom(0, opcode).rom(1, value).build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -1048,7 +1141,7 @@ fn test_ld_hl_imm8() {
 
     let mut mmu = MMU::builder().rom(0, 0x36).rom(1, VALUE).build();
     let mut cpu = CPU::builder().hl(ADDRESS).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 2);
@@ -1127,7 +1220,7 @@ fn test_ld_r8_r8(#[case] opcode: u8, #[case] target_reg: R8, #[case] source_reg:
         .hl(0xCCCC)
         .r8(source_reg, 0xCC, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if target_reg == R8::HL || source_reg == R8::HL {
         assert_eq!(m, 2);
@@ -1156,7 +1249,7 @@ fn test_ld_r16_imm16(
         .rom(2, imm2)
         .build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     match opcode {
         0x01 => assert_eq!(cpu.get_bc(), expected_value),
@@ -1183,7 +1276,7 @@ fn test_jump_imm16(#[case] imm1: u8, #[case] imm2: u8, #[case] expected_pc: u16)
         .build();
     let mut cpu = CPU::default();
 
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
     assert_eq!(cpu.get_pc(), expected_pc);
     assert_eq!(m, 4);
 }
@@ -1193,7 +1286,7 @@ fn test_jump_imm16(#[case] imm1: u8, #[case] imm2: u8, #[case] expected_pc: u16)
 fn test_ldh_a_c() {
     let mut mmu = MMU::builder().rom(0, 0xF2).write(0xFF13, 0x68).build();
     let mut cpu = CPU::builder().c(0x13).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -1205,7 +1298,7 @@ fn test_ldh_a_c() {
 fn test_ldh_c_a() {
     let mut mmu = MMU::builder().rom(0, 0xE2).build();
     let mut cpu = CPU::builder().a(0x68).c(0x13).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -1221,7 +1314,7 @@ fn test_ldh_a_imm8() {
         .write(0xFF77, 0x68)
         .build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 2);
@@ -1233,7 +1326,7 @@ fn test_ldh_a_imm8() {
 fn test_ldh_imm8_a() {
     let mut mmu = MMU::builder().rom(0, 0xE0).rom(1, 0x77).build();
     let mut cpu = CPU::builder().a(0x68).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 2);
@@ -1250,7 +1343,7 @@ fn test_ld_a_imm16() {
         .write(0xCC33, 0x68)
         .build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_pc(), 3);
@@ -1266,7 +1359,7 @@ fn test_ld_imm16_a() {
         .rom(2, 0xCC)
         .build();
     let mut cpu = CPU::builder().a(0x68).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_pc(), 3);
@@ -1280,7 +1373,7 @@ fn test_ld_imm16_a() {
 fn test_load_hl_sp_imm8(#[case] sp: u16, #[case] imm: i8, #[case] expected_hl: u16) {
     let mut mmu = MMU::builder().rom(0, 0xF8).rom(1, imm as u8).build();
     let mut cpu = CPU::builder().sp(sp).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), 2);
@@ -1292,7 +1385,7 @@ fn test_load_hl_sp_imm8(#[case] sp: u16, #[case] imm: i8, #[case] expected_hl: u
 fn test_load_sp_hl() {
     let mut mmu = MMU::builder().rom(0, 0xF9).build();
     let mut cpu = CPU::builder().hl(0x1337).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -1316,7 +1409,7 @@ fn test_jump_cond_imm16(
     #[case] f_zero: bool,
     #[case] f_carry: bool,
     #[case] expected_pc: u16,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
 ) {
     let mut mmu = MMU::builder()
         .rom(0, opcode)
@@ -1324,7 +1417,7 @@ fn test_jump_cond_imm16(
         .rom(2, imm2)
         .build();
     let mut cpu = CPU::builder().f_zero(f_zero).f_carry(f_carry).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_pc(), expected_pc);
     assert_eq!(m, expected_m);
@@ -1336,7 +1429,7 @@ fn test_jump_cond_imm16(
 fn test_jump_hl(#[case] target_address: u16) {
     let mut mmu = MMU::builder().rom(0, 0xE9).build();
     let mut cpu = CPU::builder().hl(target_address).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_pc(), target_address);
     assert_eq!(m, 1);
@@ -1349,7 +1442,7 @@ fn test_jr_imm8() {
 
     let mut mmu = MMU::builder().rom(0, 0x18).rom(1, RELATIVE_JUMP).build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 3);
     assert_eq!(cpu.get_pc(), RELATIVE_JUMP as u16 + 2);
@@ -1371,7 +1464,7 @@ fn test_jr_cond_imm8(
     #[case] immediate: i8,
     #[case] pc: u16,
     #[case] target_pc: u16,
-    #[case] target_m: u8,
+    #[case] target_m: u32,
     #[case] zero_flag: bool,
     #[case] carry_flag: bool,
 ) {
@@ -1384,7 +1477,7 @@ fn test_jr_cond_imm8(
         .f_zero(zero_flag)
         .f_carry(carry_flag)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, target_m);
     assert_eq!(cpu.get_pc(), target_pc);
@@ -1421,7 +1514,7 @@ fn test_or_r8(
         .hl(0xCCCC)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -1448,7 +1541,7 @@ fn test_or_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xF6).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -1480,7 +1573,7 @@ fn test_push_r16(
         .hl(if opcode == 0xE5 { push_value } else { 0 })
         .af(if opcode == 0xF5 { push_value } else { 0 })
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_sp(), sp - 2);
     assert_eq!(mmu.read_16(cpu.get_sp()), expected_value);
@@ -1508,7 +1601,7 @@ fn test_pop_r16(
         .write(sp + 1, imm2)
         .build();
     let mut cpu = CPU::builder().sp(sp).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(cpu.get_sp(), sp + 2);
     assert_eq!(cpu.get_pc(), 1);
@@ -1536,11 +1629,11 @@ fn test_ret() {
         .write(SP + 1, ADDR_MSB)
         .build();
     let mut cpu = CPU::builder().sp(SP).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_sp(), SP + 2);
-    assert_eq!(cpu.get_pc(), construct_u16(ADDR_LSB, ADDR_MSB));
+    assert_eq!(cpu.get_pc(), u16::from_le_bytes([ADDR_LSB, ADDR_MSB]));
 }
 
 /// RET cond
@@ -1560,7 +1653,7 @@ fn test_ret_cond(
     #[case] addr_msb: u8,
     #[case] zero_flag: bool,
     #[case] carry_flag: bool,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_sp: u16,
     #[case] expected_pc: u16,
 ) {
@@ -1574,7 +1667,7 @@ fn test_ret_cond(
         .f_zero(zero_flag)
         .f_carry(carry_flag)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_sp(), expected_sp);
@@ -1593,11 +1686,11 @@ fn test_reti() {
         .write(SP + 1, ADDR_MSB)
         .build();
     let mut cpu = CPU::builder().sp(SP).ime(false).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_sp(), SP + 2);
-    assert_eq!(cpu.get_pc(), construct_u16(ADDR_LSB, ADDR_MSB));
+    assert_eq!(cpu.get_pc(), u16::from_le_bytes([ADDR_LSB, ADDR_MSB]));
     assert!(cpu.get_ime());
 }
 
@@ -1626,7 +1719,7 @@ fn test_rla_rra(
         .f_half_carry(true)
         .f_zero(true)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -1656,7 +1749,7 @@ fn test_rlca_rrca(
         .f_half_carry(true)
         .f_zero(true)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -1680,7 +1773,7 @@ fn test_rlca_rrca(
 fn test_rst(#[case] opcode: u8, #[case] expected_pc: u16) {
     let mut mmu = MMU::builder().rom(0, opcode).build();
     let mut cpu = CPU::default();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 4);
     assert_eq!(cpu.get_pc(), expected_pc);
@@ -1696,7 +1789,7 @@ fn test_scf() {
         .f_half_carry(true)
         .f_zero(true)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 1);
     assert_eq!(cpu.get_pc(), 1);
@@ -1760,7 +1853,7 @@ fn test_sub_r8(
         .hl(0xCCCC)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -1792,7 +1885,7 @@ fn test_sub_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xD6).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -1888,7 +1981,7 @@ fn test_sbc_r8(
         .f_carry(carry)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -1926,7 +2019,7 @@ fn test_sbc_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xDE).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).f_carry(carry).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -1968,7 +2061,7 @@ fn test_xor_r8(
         .hl(0xCCCC)
         .r8(register, value_r, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     if register == R8::HL {
         assert_eq!(m, 2);
@@ -1995,7 +2088,7 @@ fn test_xor_imm8(
 ) {
     let mut mmu = MMU::builder().rom(0, 0xEE).rom(1, imm).build();
     let mut cpu = CPU::builder().a(value_a).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, 2);
     assert_eq!(cpu.get_pc(), 2);
@@ -2142,7 +2235,7 @@ fn test_bit_check(
     #[case] opcode: u8,
     #[case] value: u8,
     #[case] register: R8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_zero: bool,
 ) {
     let mut mmu = MMU::builder()
@@ -2155,7 +2248,7 @@ fn test_bit_check(
         .r8(register, value, &mut mmu)
         .f_zero(false)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2235,7 +2328,7 @@ fn test_bit_reset(
     #[case] opcode: u8,
     #[case] register: R8,
     #[case] expected_value: u8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
 ) {
     let mut mmu = MMU::builder()
         .rom(0, PREFIX_INSTRUCTION_BYTE)
@@ -2246,7 +2339,7 @@ fn test_bit_reset(
         .hl(0xCCCC)
         .r8(register, 0xFF, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2323,7 +2416,7 @@ fn test_bit_set(
     #[case] opcode: u8,
     #[case] register: R8,
     #[case] expected_value: u8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
 ) {
     let mut mmu = MMU::builder()
         .rom(0, PREFIX_INSTRUCTION_BYTE)
@@ -2331,7 +2424,7 @@ fn test_bit_set(
         .write(0xCCCC, 0)
         .build();
     let mut cpu = CPU::builder().hl(0xCCCC).r8(register, 0, &mut mmu).build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2425,7 +2518,7 @@ fn test_rl_rr_r8(
     #[case] register: R8,
     #[case] value: u8,
     #[case] carry: bool,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_value: u8,
     #[case] expected_carry: bool,
     #[case] expected_zero: bool,
@@ -2442,7 +2535,7 @@ fn test_rl_rr_r8(
         .f_half_carry(true)
         .r8(register, value, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2507,7 +2600,7 @@ fn test_rlc_rrc_r8(
     #[case] opcode: u8,
     #[case] register: R8,
     #[case] value: u8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_value: u8,
     #[case] expected_carry: bool,
     #[case] expected_zero: bool,
@@ -2523,7 +2616,7 @@ fn test_rlc_rrc_r8(
         .f_half_carry(true)
         .r8(register, value, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2612,7 +2705,7 @@ fn test_sla_sra_srl_r8(
     #[case] opcode: u8,
     #[case] register: R8,
     #[case] value: u8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_value: u8,
     #[case] expected_carry: bool,
     #[case] expected_zero: bool,
@@ -2628,7 +2721,7 @@ fn test_sla_sra_srl_r8(
         .f_half_carry(true)
         .r8(register, value, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2661,7 +2754,7 @@ fn test_swap_r8(
     #[case] opcode: u8,
     #[case] register: R8,
     #[case] value: u8,
-    #[case] expected_m: u8,
+    #[case] expected_m: u32,
     #[case] expected_value: u8,
     #[case] expected_zero: bool,
 ) {
@@ -2677,7 +2770,7 @@ fn test_swap_r8(
         .f_half_carry(true)
         .r8(register, value, &mut mmu)
         .build();
-    let m = cpu.step(&mut mmu);
+    let m = cpu.step(&mut mmu).as_m();
 
     assert_eq!(m, expected_m);
     assert_eq!(cpu.get_pc(), 2);
@@ -2687,3 +2780,45 @@ fn test_swap_r8(
     assert!(!cpu.get_f_half_carry());
     assert!(!cpu.get_f_carry());
 }
+
+/// The decode tables hold exactly what decoding each byte on its own gives
+#[rstest]
+#[case(0xD3)]
+#[case(0xDB)]
+#[case(0xDD)]
+#[case(0xE3)]
+#[case(0xE4)]
+#[case(0xEB)]
+#[case(0xEC)]
+#[case(0xED)]
+#[case(0xF4)]
+#[case(0xFC)]
+#[case(0xFD)]
+fn test_decode_illegal_opcodes(#[case] byte: u8) {
+    assert_eq!(Instruction::decode(byte, false), None);
+    assert!(Instruction::from_byte(byte, false).is_err());
+    assert!(Instruction::decode(byte, true).is_some());
+}
+
+#[test]
+fn test_decode_matches_from_byte() {
+    for prefixed in [false, true] {
+        for byte in 0..=u8::MAX {
+            assert_eq!(
+                Instruction::decode(byte, prefixed),
+                Instruction::from_byte(byte, prefixed).ok(),
+                "{byte:02X}"
+            );
+        }
+    }
+    assert_eq!(Instruction::decode(0x00, false), Some(Instruction::Nop));
+    assert_eq!(
+        Instruction::decode(0x7C, true),
+        Some(Instruction::BitCheckR8((7, R8::H)))
+    );
+    let legal = (0..=u8::MAX)
+        .filter(|&byte| Instruction::decode(byte, false).is_some())
+        .count();
+    // 11 illegal opcodes and the 0xCB prefix
+    assert_eq!(legal, 244);
+}
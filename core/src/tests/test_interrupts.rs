@@ -0,0 +1,168 @@
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::cpu::registers::builder::CPURegistersBuilderTrait;
+use crate::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use crate::game_boy::components::cpu::CPU;
+use crate::game_boy::components::interrupt_controller::InterruptController;
+use crate::game_boy::components::mmu::{IE_ADDRESS, IF_ADDRESS, MMU, SB_ADDRESS, SC_ADDRESS};
+use crate::game_boy::components::serial::{Serial, TRANSFER_DURATION};
+use crate::game_boy::cycles::Cycles;
+use rstest::rstest;
+
+#[test]
+fn test_vblank_interrupt() {
+    let mut mmu = MMU::builder()
+        .write(IF_ADDRESS, 0b0000_0001)
+        .write(IE_ADDRESS, 0b0000_0001)
+        .build();
+    let mut cpu = CPU::builder().ime(true).build();
+
+    let m = cpu.step(&mut mmu).as_m();
+    assert_eq!(m, 5);
+    assert_eq!(cpu.get_pc(), Interrupt::Vblank.get_target_address());
+    assert!(!cpu.get_ime());
+}
+
+/// Runs the program with a pending VBlank interrupt, the expected return address is None if it's never serviced
+#[rstest]
+#[case::ei_nop(false, &[0xFB, 0x00], Some(0x0002))] // The instruction after EI still runs
+#[case::ei_di(false, &[0xFB, 0xF3, 0x00, 0x00], None)] // IME is never set
+#[case::ei_di_ei(false, &[0xFB, 0xF3, 0xFB, 0x00], Some(0x0004))]
+#[case::ei_ei(false, &[0xFB, 0xFB, 0x00], Some(0x0002))] // The second EI doesn't delay the first one
+#[case::ime_already_set(true, &[0xFB, 0x00], Some(0x0000))]
+fn test_deferred_ei(
+    #[case] ime: bool,
+    #[case] program: &[u8],
+    #[case] expected_return_address: Option<u16>,
+) {
+    let mut builder = MMU::builder()
+        .write(IF_ADDRESS, Interrupt::Vblank.get_mask())
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask());
+    for (address, byte) in program.iter().enumerate() {
+        builder = builder.rom(address as u16, *byte);
+    }
+    let mut mmu = builder.build();
+    let mut cpu = CPU::builder().ime(ime).sp(0xFFFE).build();
+
+    for _ in 0..program.len() + 1 {
+        if cpu.get_pc() == Interrupt::Vblank.get_target_address() {
+            break;
+        }
+        cpu.step(&mut mmu);
+    }
+
+    let Some(expected_return_address) = expected_return_address else {
+        assert_eq!(cpu.get_pc(), program.len() as u16 + 1);
+        assert!(!cpu.get_ime());
+        return;
+    };
+    assert_eq!(cpu.get_pc(), Interrupt::Vblank.get_target_address());
+    let pushed = u16::from_le_bytes([mmu.read(0xFFFC), mmu.read(0xFFFD)]);
+    assert_eq!(pushed, expected_return_address);
+
+    // The handler runs with the interrupts disabled
+    cpu.step(&mut mmu);
+    assert!(!cpu.get_ime());
+    assert!(!cpu.get_deferred_set_ime());
+}
+
+/// With IME already set, an interrupt raised right after an EI is serviced before the EI takes effect
+#[test]
+fn test_ei_as_last_instruction_before_interrupt() {
+    let mut mmu = MMU::builder()
+        .rom(0, 0xFB) // EI
+        .write(IE_ADDRESS, Interrupt::Vblank.get_mask())
+        .build();
+    let mut cpu = CPU::builder().ime(true).sp(0xFFFE).build();
+
+    cpu.step(&mut mmu);
+    assert!(cpu.get_deferred_set_ime());
+
+    mmu.write(IF_ADDRESS, Interrupt::Vblank.get_mask());
+    assert_eq!(cpu.step(&mut mmu).as_m(), 5);
+    assert_eq!(cpu.get_pc(), Interrupt::Vblank.get_target_address());
+
+    // The pending EI doesn't enable the interrupts again after the first instruction of the handler
+    cpu.step(&mut mmu);
+    assert!(!cpu.get_ime());
+    assert!(!cpu.get_deferred_set_ime());
+}
+
+#[rstest]
+#[case(0b0001_1111, 0b0001_1111, Some(Interrupt::Vblank), 0b0001_1110)]
+#[case(0b0001_1110, 0b0001_1111, Some(Interrupt::Lcd), 0b0001_1100)]
+#[case(0b0001_1100, 0b0001_0111, Some(Interrupt::Timer), 0b0001_1000)]
+#[case(0b0001_1000, 0b0001_0000, Some(Interrupt::Joypad), 0b0000_1000)]
+#[case(0b0000_1000, 0b0001_0111, None, 0b0000_1000)]
+fn test_interrupt_controller_acknowledge(
+    #[case] interrupt_flag: u8,
+    #[case] interrupt_enable: u8,
+    #[case] expected: Option<Interrupt>,
+    #[case] expected_flag: u8,
+) {
+    let mut controller = InterruptController::new(interrupt_flag, interrupt_enable);
+    assert_eq!(controller.acknowledge(), expected);
+    assert_eq!(controller.read_if(), expected_flag);
+    assert_eq!(controller.read_ie(), interrupt_enable);
+}
+
+#[test]
+fn test_interrupt_controller_request() {
+    let mut controller = InterruptController::default();
+    controller.request(Interrupt::Timer);
+    controller.request(Interrupt::Serial);
+    assert_eq!(controller.read_if(), 0b0000_1100);
+    assert!(controller.is_requested(Interrupt::Timer));
+    assert!(!controller.has_pending());
+
+    controller.write_ie(Interrupt::Serial.get_mask());
+    assert_eq!(controller.get_pending(), Some(Interrupt::Serial));
+
+    controller.clear(Interrupt::Serial);
+    assert!(!controller.has_pending());
+}
+
+#[test]
+fn test_interrupt_registers_are_mapped() {
+    let mut mmu = MMU::default();
+    mmu.interrupts_mut().request(Interrupt::Lcd);
+    mmu.write(IE_ADDRESS, 0b0000_0010);
+
+    assert_eq!(mmu.read(IF_ADDRESS), 0b0000_0010);
+    assert!(mmu.interrupts().is_enabled(Interrupt::Lcd));
+
+    mmu.write(IF_ADDRESS, 0b0000_0100);
+    assert!(mmu.interrupts().is_requested(Interrupt::Timer));
+    assert!(!mmu.interrupts().is_requested(Interrupt::Lcd));
+}
+
+#[test]
+fn test_interrupt_registers_survive_save_state() {
+    let mut mmu = MMU::default();
+    mmu.interrupts_mut().request(Interrupt::Joypad);
+    mmu.write(IE_ADDRESS, 0b0001_0001);
+
+    let loaded = MMU::load(mmu.save(), &Cartridge::default()).unwrap();
+    assert_eq!(loaded.interrupts(), mmu.interrupts());
+}
+
+/// Only internally clocked transfers complete, since there is no link partner providing a clock
+#[rstest]
+#[case::internal_clock(0x81, true)]
+#[case::external_clock(0x80, false)]
+fn test_serial_transfer_requests_interrupt(#[case] sc: u8, #[case] completes: bool) {
+    let mut mmu = MMU::builder()
+        .io(SB_ADDRESS, b'X')
+        .io(SC_ADDRESS, sc)
+        .build();
+    let mut serial = Serial::default();
+    assert_eq!(mmu.get_serial_output(), b"X");
+
+    assert!(!serial.step(TRANSFER_DURATION - Cycles::from_m(1), &mut mmu));
+    assert!(!mmu.interrupts().is_requested(Interrupt::Serial));
+
+    assert_eq!(serial.step(Cycles::from_m(1), &mut mmu), completes);
+    assert_eq!(mmu.interrupts().is_requested(Interrupt::Serial), completes);
+    assert_eq!(mmu.read(SC_ADDRESS) & 0x80 == 0, completes);
+    assert_eq!(mmu.read(SB_ADDRESS), if completes { 0xFF } else { b'X' });
+}
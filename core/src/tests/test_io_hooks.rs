@@ -0,0 +1,152 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::mmu::{IF_ADDRESS, SCX_ADDRESS, TIMA_ADDRESS};
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+/// Not used by the DMG
+const DEBUG_PORT: u16 = 0xFF7F;
+
+fn loop_game_boy() -> GameBoy {
+    GameBoy::headless(&RomBuilder::new().program(&[0x18, 0xFE]).build()).unwrap()
+}
+
+#[test]
+fn test_read_hook_answers_game_reads() {
+    // LDH A, (0x4C) / LD (0xC000), A / JR to the start
+    let rom = RomBuilder::new()
+        .program(&[0xF0, 0x4C, 0xEA, 0x00, 0xC0, 0x18, 0xF9])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    game_boy.add_io_read_hook(0xFF4C, |_| Some(0x42)).unwrap();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(0xC000), 0x42);
+    assert_eq!(game_boy.read_memory(0xFF4C), 0x42);
+}
+
+#[test]
+fn test_read_hook_without_value_falls_through() {
+    let mut game_boy = loop_game_boy();
+    game_boy.write_memory(SCX_ADDRESS, 0x12);
+    let reads = Arc::new(Mutex::new(0));
+    let counted = reads.clone();
+    game_boy
+        .add_io_read_hook(SCX_ADDRESS, move |_| {
+            *counted.lock().unwrap() += 1;
+            None
+        })
+        .unwrap();
+    assert_eq!(game_boy.read_memory(SCX_ADDRESS), 0x12);
+    assert_eq!(*reads.lock().unwrap(), 1);
+}
+
+#[test]
+fn test_latest_read_hook_wins() {
+    let mut game_boy = loop_game_boy();
+    game_boy
+        .add_io_read_hook(DEBUG_PORT, |_| Some(0x01))
+        .unwrap();
+    let id = game_boy
+        .add_io_read_hook(DEBUG_PORT, |_| Some(0x02))
+        .unwrap();
+    assert_eq!(game_boy.read_memory(DEBUG_PORT), 0x02);
+    game_boy.remove_io_hook(id);
+    assert_eq!(game_boy.read_memory(DEBUG_PORT), 0x01);
+}
+
+#[test]
+fn test_write_hook_as_debug_port() {
+    // Writes "HI" to the debug port: LD A, 'H' / LDH (0x7F), A / LD A, 'I' / LDH (0x7F), A / JR -2
+    let rom = RomBuilder::new()
+        .program(&[0x3E, b'H', 0xE0, 0x7F, 0x3E, b'I', 0xE0, 0x7F, 0x18, 0xFE])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let written = output.clone();
+    game_boy
+        .add_io_write_hook(DEBUG_PORT, move |_, value| {
+            written.lock().unwrap().push(value);
+            true
+        })
+        .unwrap();
+    game_boy.finish_frame();
+    assert_eq!(*output.lock().unwrap(), b"HI");
+}
+
+/// (HL) operands are CPU accesses as well
+#[test]
+fn test_hooks_see_hl_accesses() {
+    // LD HL, 0xFF4C / LD A, 0x12 / LD (HL), A / LD A, (HL) / LD (0xC000), A / JR -2
+    let rom = RomBuilder::new()
+        .program(&[
+            0x21, 0x4C, 0xFF, 0x3E, 0x12, 0x77, 0x7E, 0xEA, 0x00, 0xC0, 0x18, 0xFE,
+        ])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let output = written.clone();
+    game_boy
+        .add_io_write_hook(0xFF4C, move |_, value| {
+            output.lock().unwrap().push(value);
+            true
+        })
+        .unwrap();
+    game_boy.add_io_read_hook(0xFF4C, |_| Some(0x42)).unwrap();
+    game_boy.finish_frame();
+    assert_eq!(*written.lock().unwrap(), [0x12]);
+    assert_eq!(game_boy.read_memory(0xC000), 0x42);
+}
+
+#[rstest]
+#[case::handled(true, 0x00)]
+#[case::passed_on(false, 0x34)]
+fn test_write_hook_decides_if_the_register_is_written(#[case] handled: bool, #[case] expected: u8) {
+    let mut game_boy = loop_game_boy();
+    game_boy.write_memory(SCX_ADDRESS, 0x00);
+    game_boy
+        .add_io_write_hook(SCX_ADDRESS, move |_, _| handled)
+        .unwrap();
+    game_boy.write_memory(SCX_ADDRESS, 0x34);
+    assert_eq!(game_boy.peek(SCX_ADDRESS), expected);
+}
+
+/// Hooks only intercept the game, the timer still counts TIMA up and overflows
+#[test]
+fn test_swallowing_hook_does_not_stop_timer() {
+    // LD A, 0x05 / LDH (TAC), A / JR to itself: the timer counts every 16 cycles
+    let rom = RomBuilder::new()
+        .program(&[0x3E, 0x05, 0xE0, 0x07, 0x18, 0xFE])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    game_boy
+        .add_io_read_hook(TIMA_ADDRESS, |_| Some(0x00))
+        .unwrap();
+    game_boy
+        .add_io_write_hook(TIMA_ADDRESS, |_, _| true)
+        .unwrap();
+    game_boy.write_memory(IF_ADDRESS, 0x00);
+    game_boy.finish_frame();
+
+    assert_eq!(game_boy.peek(IF_ADDRESS) & 0b100, 0b100);
+    assert_eq!(game_boy.read_memory(TIMA_ADDRESS), 0x00);
+}
+
+#[test]
+fn test_hooks_survive_reset() {
+    let mut game_boy = loop_game_boy();
+    game_boy
+        .add_io_read_hook(DEBUG_PORT, |_| Some(0x99))
+        .unwrap();
+    game_boy.reset();
+    assert_eq!(game_boy.read_memory(DEBUG_PORT), 0x99);
+}
+
+#[rstest]
+#[case::wram(0xC000)]
+#[case::hram(0xFF80)]
+#[case::ie(0xFFFF)]
+fn test_hooks_only_for_io_registers(#[case] address: u16) {
+    let mut game_boy = loop_game_boy();
+    assert!(game_boy.add_io_read_hook(address, |_| Some(0x00)).is_err());
+    assert!(game_boy.add_io_write_hook(address, |_, _| true).is_err());
+}
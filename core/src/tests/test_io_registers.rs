@@ -0,0 +1,81 @@
+use crate::game_boy::components::mmu::io_registers::describe;
+use rstest::rstest;
+
+#[rstest]
+#[case::joypad(0xFF00, "P1", 0x3F, 0x30)]
+#[case::timer_control(0xFF07, "TAC", 0x07, 0x07)]
+#[case::interrupt_flag(0xFF0F, "IF", 0x1F, 0x1F)]
+#[case::write_only_period(0xFF13, "NR13", 0x00, 0xFF)]
+#[case::wave_ram(0xFF3A, "WAVE10", 0xFF, 0xFF)]
+#[case::lcd_status(0xFF41, "STAT", 0x7F, 0x78)]
+#[case::read_only_ly(0xFF44, "LY", 0xFF, 0x00)]
+#[case::interrupt_enable(0xFFFF, "IE", 0xFF, 0xFF)]
+fn test_describe(
+    #[case] address: u16,
+    #[case] name: &str,
+    #[case] read_mask: u8,
+    #[case] write_mask: u8,
+) {
+    let register = describe(address).unwrap();
+    assert_eq!(register.address, address);
+    assert_eq!(register.name, name);
+    assert_eq!(register.read_mask, read_mask);
+    assert_eq!(register.write_mask, write_mask);
+}
+
+#[rstest]
+#[case::unmapped_io(0xFF15)]
+#[case::cgb_only(0xFF4D)]
+#[case::hram(0xFF80)]
+#[case::wram(0xC000)]
+fn test_describe_unknown(#[case] address: u16) {
+    assert_eq!(describe(address), None);
+}
+
+#[test]
+fn test_decode_lcdc() {
+    let register = describe(0xFF40).unwrap();
+    assert_eq!(
+        register.decode(0x91),
+        vec![
+            ("LCD enable", 1),
+            ("Window tile map", 0),
+            ("Window enable", 0),
+            ("BG and window tiles", 1),
+            ("BG tile map", 0),
+            ("OBJ size", 0),
+            ("OBJ enable", 0),
+            ("BG and window enable", 1),
+        ]
+    );
+}
+
+#[rstest]
+#[case::fields(0xFF07, 0x05, "TAC=0x05 (Enable=1, Clock select=1)")]
+#[case::multi_bit_fields(0xFF47, 0xE4, "BGP=0xE4 (Color 3=3, Color 2=2, Color 1=1, Color 0=0)")]
+#[case::stat_mode(0xFF41, 0xC6, "STAT=0xC6 (LYC int select=1, Mode 2 int select=0, Mode 1 int select=0, Mode 0 int select=0, LYC == LY=1, PPU mode=2)")]
+#[case::plain_number(0xFF42, 0x10, "SCY=0x10")]
+fn test_format(#[case] address: u16, #[case] value: u8, #[case] expected: &str) {
+    assert_eq!(describe(address).unwrap().format(value), expected);
+}
+
+/// Bit fields must not overlap and only cover bits which can be read or written
+#[test]
+fn test_fields_fit_masks() {
+    for address in (0xFF00..=0xFF7F).chain([0xFFFF]) {
+        let Some(register) = describe(address) else {
+            continue;
+        };
+        let mut covered = 0u8;
+        for field in register.fields {
+            assert_eq!(covered & field.mask, 0, "{} {}", register.name, field.name);
+            covered |= field.mask;
+        }
+        assert_eq!(
+            covered & !(register.read_mask | register.write_mask),
+            0,
+            "{}",
+            register.name
+        );
+    }
+}
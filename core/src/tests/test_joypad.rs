@@ -0,0 +1,130 @@
+use crate::enums::button::{Button, Buttons};
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::mmu::{IF_ADDRESS, MMU, P1_ADDRESS};
+use rstest::rstest;
+
+/// Values written to P1, named after the lines they select
+const SELECT_BOTH: u8 = 0b0000_0000;
+const SELECT_ACTION: u8 = 0b0001_0000;
+const SELECT_DIRECTION: u8 = 0b0010_0000;
+const SELECT_NONE: u8 = 0b0011_0000;
+/// The P1 bit of each line, it is selected while the bit is 0
+const ACTION_LINE: u8 = 0b0010_0000;
+const DIRECTION_LINE: u8 = 0b0001_0000;
+
+fn build_mmu(select: u8, buttons: Buttons) -> MMU {
+    let mut mmu = MMU::builder().write(P1_ADDRESS, select).build();
+    mmu.set_buttons(buttons);
+    mmu.write(IF_ADDRESS, 0);
+    mmu
+}
+
+fn joypad_requested(mmu: &MMU) -> bool {
+    mmu.interrupts().is_requested(Interrupt::Joypad)
+}
+
+/// Every button pulls exactly one bit low, and only while its line is selected.
+/// Reads reflect the buttons right away, there is no debouncing.
+#[rstest]
+fn test_p1_matrix(
+    #[values(
+        (Button::A, ACTION_LINE, 0),
+        (Button::B, ACTION_LINE, 1),
+        (Button::Select, ACTION_LINE, 2),
+        (Button::Start, ACTION_LINE, 3),
+        (Button::Right, DIRECTION_LINE, 0),
+        (Button::Left, DIRECTION_LINE, 1),
+        (Button::Up, DIRECTION_LINE, 2),
+        (Button::Down, DIRECTION_LINE, 3)
+    )]
+    button: (Button, u8, u8),
+    #[values(SELECT_BOTH, SELECT_ACTION, SELECT_DIRECTION, SELECT_NONE)] select: u8,
+) {
+    let (button, line, bit) = button;
+    let mut mmu = build_mmu(select, Buttons::NONE);
+    assert_eq!(mmu.read(P1_ADDRESS), 0xC0 | select | 0x0F);
+
+    mmu.set_buttons(Buttons::NONE.with(button));
+    let visible = select & line == 0;
+    let expected_lines = if visible { 0x0F & !(1 << bit) } else { 0x0F };
+    assert_eq!(mmu.read(P1_ADDRESS), 0xC0 | select | expected_lines);
+
+    mmu.set_buttons(Buttons::NONE);
+    assert_eq!(mmu.read(P1_ADDRESS), 0xC0 | select | 0x0F);
+}
+
+#[rstest]
+#[case::both(SELECT_BOTH, 0b0000_0000)]
+#[case::action(SELECT_ACTION, 0b0000_0101)]
+#[case::direction(SELECT_DIRECTION, 0b0000_1010)]
+#[case::none(SELECT_NONE, 0b0000_1111)]
+fn test_p1_all_buttons(#[case] select: u8, #[case] expected_lines: u8) {
+    // Right + Up on the direction line, B + Start on the action line
+    let buttons = Buttons::NONE
+        .with(Button::Right)
+        .with(Button::Up)
+        .with(Button::B)
+        .with(Button::Start);
+    let mmu = build_mmu(select, buttons);
+    assert_eq!(mmu.read(P1_ADDRESS), 0xC0 | select | expected_lines);
+}
+
+/// Only the select lines are writable, the upper two bits always read as 1
+#[rstest]
+#[case(0xFF, 0xFF)]
+#[case(0x0F, 0xCF)]
+#[case(0xC0, 0xCF)]
+#[case(0x2A, 0xEF)]
+fn test_p1_write_only_changes_select_lines(#[case] value: u8, #[case] expected: u8) {
+    let mut mmu = build_mmu(SELECT_NONE, Buttons::NONE);
+    mmu.write(P1_ADDRESS, value);
+    assert_eq!(mmu.read(P1_ADDRESS), expected);
+}
+
+#[rstest]
+#[case::press_selected(SELECT_ACTION, Buttons::NONE, Buttons::NONE.with(Button::A), true)]
+#[case::press_on_both(SELECT_BOTH, Buttons::NONE, Buttons::NONE.with(Button::Down), true)]
+#[case::press_unselected(SELECT_DIRECTION, Buttons::NONE, Buttons::NONE.with(Button::A), false)]
+#[case::press_none_selected(SELECT_NONE, Buttons::NONE, Buttons::from_bits(0xFF), false)]
+#[case::release(SELECT_ACTION, Buttons::NONE.with(Button::A), Buttons::NONE, false)]
+#[case::hold(SELECT_ACTION, Buttons::NONE.with(Button::A), Buttons::NONE.with(Button::A), false)]
+#[case::press_second(
+    SELECT_ACTION,
+    Buttons::NONE.with(Button::A),
+    Buttons::NONE.with(Button::A).with(Button::B),
+    true
+)]
+// A and Right share bit 0, with both lines selected it is already low
+#[case::shared_bit_already_low(
+    SELECT_BOTH,
+    Buttons::NONE.with(Button::Right),
+    Buttons::NONE.with(Button::Right).with(Button::A),
+    false
+)]
+fn test_joypad_interrupt_on_press(
+    #[case] select: u8,
+    #[case] before: Buttons,
+    #[case] after: Buttons,
+    #[case] expected: bool,
+) {
+    let mut mmu = build_mmu(select, before);
+    mmu.set_buttons(after);
+    assert_eq!(joypad_requested(&mmu), expected);
+}
+
+/// Selecting a line with a held button pulls its bit low, which counts as a press
+#[rstest]
+#[case::select_held(Button::Start, SELECT_NONE, SELECT_ACTION, true)]
+#[case::select_other_line(Button::Start, SELECT_NONE, SELECT_DIRECTION, false)]
+#[case::deselect_held(Button::Up, SELECT_DIRECTION, SELECT_NONE, false)]
+#[case::switch_line(Button::Left, SELECT_ACTION, SELECT_DIRECTION, true)]
+fn test_joypad_interrupt_on_select(
+    #[case] button: Button,
+    #[case] initial_select: u8,
+    #[case] select: u8,
+    #[case] expected: bool,
+) {
+    let mut mmu = build_mmu(initial_select, Buttons::NONE.with(button));
+    mmu.write(P1_ADDRESS, select);
+    assert_eq!(joypad_requested(&mmu), expected);
+}
@@ -0,0 +1,23 @@
+use crate::logging::Subsystem;
+use rstest::rstest;
+
+#[rstest]
+#[case::cpu("lemon_gb::cpu", Some(Subsystem::Cpu))]
+#[case::nested("lemon_gb::ppu::fetcher", Some(Subsystem::Ppu))]
+#[case::interrupt("lemon_gb::interrupt", Some(Subsystem::Interrupt))]
+#[case::similar_prefix("lemon_gb::mmu_stats", None)]
+#[case::other_crate("winit::window", None)]
+#[case::module_path("lemon_gb::game_boy", None)]
+fn test_subsystem_from_target(#[case] target: &str, #[case] expected: Option<Subsystem>) {
+    assert_eq!(Subsystem::from_target(target), expected);
+}
+
+#[test]
+fn test_subsystem_names_round_trip() {
+    for subsystem in Subsystem::ALL {
+        assert_eq!(subsystem.to_string().parse::<Subsystem>(), Ok(subsystem));
+        assert_eq!(Subsystem::from_target(subsystem.target()), Some(subsystem));
+    }
+    assert_eq!("PPU".parse::<Subsystem>(), Ok(Subsystem::Ppu));
+    assert!("apu".parse::<Subsystem>().is_err());
+}
@@ -0,0 +1,310 @@
+use crate::game_boy::components::cartridge::types::CartridgeType;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::mbc::mbc1::Mbc1;
+use crate::game_boy::components::mmu::mbc::mbc5::Mbc5;
+use crate::game_boy::components::mmu::mbc::{
+    wrap_bank, MapperWrite, MapperWriteEvent, Mbc, RumbleEvent,
+};
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::game_boy::hardware_model::HardwareModel;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_mbc1_initial_state() {
+    let mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+    assert_eq!(mbc1.get_lower_rom_index(), 0);
+    assert_eq!(mbc1.get_upper_rom_index(), 1);
+    assert_eq!(mbc1.get_ram_index(), 0);
+    assert!(!mbc1.ram_enabled());
+}
+
+#[test]
+fn test_mbc1_ram_enable() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+
+    // RAM should be disabled by default
+    assert!(!mbc1.ram_enabled());
+
+    // Writing 0x0A to 0x0000-0x1FFF enables RAM
+    mbc1.handle_write(0x0000, 0x0A);
+    assert!(mbc1.ram_enabled());
+
+    // Any other value should disable RAM
+    mbc1.handle_write(0x0000, 0x00);
+    assert!(!mbc1.ram_enabled());
+}
+
+#[test]
+fn test_mbc1_rom_bank_switching() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+
+    // Test ROM bank selection (0x2000-0x3FFF)
+    // Writing 0 is treated as 1
+    mbc1.handle_write(0x2000, 0x00);
+    assert_eq!(mbc1.get_upper_rom_index(), 1);
+
+    // Test various bank numbers
+    mbc1.handle_write(0x2000, 0x1F); // Max 5-bit value
+    assert_eq!(mbc1.get_upper_rom_index(), 0x1F);
+
+    // Test that values get masked properly
+    mbc1.handle_write(0x2000, 0xFF);
+    assert_eq!(mbc1.get_upper_rom_index(), 0x1F); // Should mask to 5 bits
+}
+
+#[test]
+fn test_mbc1_ram_bank_mode_selection() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+
+    // Test RAM banking mode selection (0x6000-0x7FFF)
+    // Default is ROM banking mode (0)
+    assert_eq!(mbc1.get_ram_index(), 0);
+
+    // Switch to RAM banking mode (1)
+    mbc1.handle_write(0x6000, 0x01);
+    mbc1.handle_write(0x4000, 0x03); // Set RAM bank to 3
+    assert_eq!(mbc1.get_ram_index(), 3);
+
+    // Switch back to ROM banking mode
+    mbc1.handle_write(0x6000, 0x00);
+    assert_eq!(mbc1.get_ram_index(), 0);
+}
+
+#[test]
+fn test_mbc1_upper_bits_banking() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+
+    // Test upper bits (bank2) selection (0x4000-0x5FFF)
+    mbc1.handle_write(0x4000, 0x03); // Set upper bits
+    mbc1.handle_write(0x2000, 0x1F); // Set lower bits
+
+    // In ROM mode, this affects the upper ROM bank bits
+    assert_eq!(mbc1.get_upper_rom_index(), 0x7F); // Combined value
+}
+
+#[test]
+fn test_mbc1_multicart() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(true));
+
+    // Test multicart ROM banking behavior
+    mbc1.handle_write(0x2000, 0x0F); // Set lower bits
+    mbc1.handle_write(0x4000, 0x03); // Set upper bits
+
+    // In multicart mode, upper bits are shifted differently
+    assert_eq!(mbc1.get_upper_rom_index(), 0x3F); // Combined value for multicart
+
+    // Test banking mode switching in multicart
+    mbc1.handle_write(0x6000, 0x01); // Switch to RAM banking mode
+    assert_eq!(mbc1.get_lower_rom_index(), 0x30); // Upper bits affect lower bank
+}
+
+#[test]
+fn test_mbc1_invalid_writes() {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+
+    // Writing to invalid addresses should have no effect
+    let original_state = mbc1.clone();
+    mbc1.handle_write(0x8000, 0xFF);
+    assert_eq!(mbc1, original_state);
+}
+
+#[rstest]
+#[case::in_range(3, 4, 3)]
+#[case::mirrored(5, 4, 1)]
+#[case::mbc1_max_bank(0x7F, 2, 1)]
+#[case::non_power_of_two(7, 3, 0)]
+#[case::no_banks(3, 0, 0)]
+fn test_wrap_bank(#[case] bank: usize, #[case] bank_count: usize, #[case] expected: usize) {
+    assert_eq!(wrap_bank(bank, bank_count), expected);
+}
+
+/// MBC1 cartridge whose header declares 8 banks (128 KiB) and 32 KiB of RAM, but only the first 4 banks are in the file.
+/// Every ROM bank starts with its own index.
+fn build_undersized_mmu() -> MMU {
+    let mut rom = vec![0u8; 4 * ROM_BANK_SIZE];
+    for bank in 0..4 {
+        rom[bank * ROM_BANK_SIZE] = bank as u8;
+    }
+    rom[0x147] = 0x03; // MBC1 + RAM + Battery
+    rom[0x148] = 0x02; // 128 KiB ROM
+    rom[0x149] = 0x03; // 32 KiB RAM
+    MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+#[rstest]
+#[case::populated(3, 3)]
+#[case::beyond_file(6, 2)]
+#[case::beyond_header(0x1F, 3)]
+fn test_rom_banks_beyond_the_file_are_mirrored(#[case] selected: u8, #[case] expected_bank: u8) {
+    let mut mmu = build_undersized_mmu();
+    assert_eq!(mmu.get_rom_bank_count(), 4);
+
+    mmu.write(0x2000, selected);
+    assert_eq!(mmu.read(0x4000), expected_bank);
+    assert_eq!(mmu.get_current_rom_bank(), expected_bank as usize);
+}
+
+#[test]
+fn test_mbc1_upper_register_writes_select_ram_bank() {
+    let mut mmu = build_undersized_mmu();
+    mmu.write(0x0000, 0x0A); // Enable RAM
+    mmu.write(0x6000, 0x01); // RAM banking mode
+
+    for bank in 0..4u8 {
+        mmu.write(0x4000, bank);
+        mmu.write(0xA000, 0x10 + bank);
+    }
+    for bank in 0..4u8 {
+        mmu.write(0x4000, bank);
+        assert_eq!(mmu.read(0xA000), 0x10 + bank);
+    }
+}
+
+#[rstest]
+#[case::ram_enable(0x0000, 0x0A, MapperWrite::RamEnable(true))]
+#[case::ram_disable(0x1FFF, 0x0B, MapperWrite::RamEnable(false))]
+#[case::rom_bank_masked(0x2000, 0xE5, MapperWrite::RomBankLow(0x05))]
+#[case::rom_bank_zero(0x3FFF, 0x00, MapperWrite::RomBankLow(0x01))]
+#[case::upper_bits(0x4000, 0xFE, MapperWrite::UpperBits(2))]
+#[case::banking_mode(0x6000, 0x01, MapperWrite::BankingMode(true))]
+#[case::outside_rom(0x8000, 0x01, MapperWrite::Ignored)]
+fn test_mbc1_write_effect(#[case] address: u16, #[case] value: u8, #[case] expected: MapperWrite) {
+    let mut mbc1 = Mbc::Mbc1(Mbc1::initialize(false));
+    assert_eq!(mbc1.handle_write(address, value), expected);
+}
+
+#[test]
+fn test_no_mbc_ignores_writes() {
+    assert_eq!(Mbc::None.handle_write(0x2000, 0x05), MapperWrite::Ignored);
+}
+
+#[test]
+fn test_mapper_write_events() {
+    let mut mmu = build_undersized_mmu();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let id =
+        mmu.on_mapper_write(move |event: &MapperWriteEvent| recorded.lock().unwrap().push(*event));
+
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0x2000, 0x06);
+    mmu.write(0xC000, 0x06);
+    mmu.remove_mapper_write_listener(id);
+    mmu.write(0x2000, 0x03);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[1],
+        MapperWriteEvent {
+            address: 0x2000,
+            value: 0x06,
+            effect: MapperWrite::RomBankLow(0x06),
+            lower_rom_bank: 0,
+            upper_rom_bank: 2,
+            ram_bank: 0,
+            ram_enabled: true,
+        }
+    );
+    assert_eq!(
+        events[1].to_string(),
+        "[0x2000] <- 0x06: ROM bank low bits = 0x06 => ROM 0x00/0x02, RAM 0x00 enabled"
+    );
+}
+
+#[rstest]
+#[case::ignored(MapperWrite::Ignored, "ignored")]
+#[case::ram_disabled(MapperWrite::RamEnable(false), "RAM disabled")]
+#[case::upper_bits(MapperWrite::UpperBits(3), "upper bank bits = 3")]
+#[case::advanced_mode(MapperWrite::BankingMode(true), "banking mode 1 (advanced)")]
+fn test_mapper_write_display(#[case] effect: MapperWrite, #[case] expected: &str) {
+    assert_eq!(effect.to_string(), expected);
+}
+
+#[test]
+fn test_mbc5_rom_bank_switching() {
+    let mut mbc5 = Mbc::Mbc5(Mbc5::initialize(false));
+    assert_eq!(mbc5.get_upper_rom_index(), 1);
+
+    // Unlike MBC1, bank 0 can be mapped into 0x4000-0x7FFF
+    mbc5.handle_write(0x2000, 0x00);
+    assert_eq!(mbc5.get_upper_rom_index(), 0);
+
+    mbc5.handle_write(0x2FFF, 0x34);
+    mbc5.handle_write(0x3000, 0xFF);
+    assert_eq!(mbc5.get_upper_rom_index(), 0x134);
+    assert_eq!(mbc5.get_lower_rom_index(), 0);
+
+    mbc5.handle_write(0x3FFF, 0x00);
+    assert_eq!(mbc5.get_upper_rom_index(), 0x34);
+}
+
+#[rstest]
+#[case::ram_enable(false, 0x0000, 0x0A, MapperWrite::RamEnable(true))]
+#[case::ram_enable_checks_all_bits(false, 0x0000, 0x1A, MapperWrite::RamEnable(false))]
+#[case::rom_bank_low(false, 0x2000, 0xFF, MapperWrite::RomBankLow(0xFF))]
+#[case::rom_bank_high(false, 0x3000, 0x03, MapperWrite::RomBankHigh(1))]
+#[case::ram_bank(false, 0x4000, 0x1F, MapperWrite::RamBank(0x0F))]
+#[case::ram_bank_motor(true, 0x4000, 0x1B, MapperWrite::RamBankMotor { ram_bank: 3, motor_on: true })]
+#[case::outside_registers(false, 0x6000, 0x01, MapperWrite::Ignored)]
+fn test_mbc5_write_effect(
+    #[case] rumble: bool,
+    #[case] address: u16,
+    #[case] value: u8,
+    #[case] expected: MapperWrite,
+) {
+    let mut mbc5 = Mbc::Mbc5(Mbc5::initialize(rumble));
+    assert_eq!(mbc5.handle_write(address, value), expected);
+    assert_eq!(
+        mbc5.motor_on(),
+        rumble && value & 0x08 != 0 && address >= 0x4000
+    );
+}
+
+#[rstest]
+#[case::mbc5_rumble(CartridgeType::MBC5RumbleRamBattery, true)]
+#[case::mbc5(CartridgeType::MBC5RamBattery, false)]
+#[case::mbc7(CartridgeType::MBC7SensorRumbleRamBattery, true)]
+#[case::mbc1(CartridgeType::MBC1RamBattery, false)]
+fn test_has_rumble(#[case] cartridge_type: CartridgeType, #[case] expected: bool) {
+    assert_eq!(cartridge_type.has_rumble(), expected);
+}
+
+/// MBC5 rumble cartridge with 4 ROM banks and 2 RAM banks
+fn build_rumble_mmu() -> MMU {
+    let mut rom = vec![0u8; 4 * ROM_BANK_SIZE];
+    rom[0x147] = 0x1D; // MBC5 + Rumble + RAM
+    rom[0x148] = 0x01; // 64 KiB ROM
+    rom[0x149] = 0x03; // 32 KiB RAM
+    MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+#[test]
+fn test_rumble_events_on_motor_changes() {
+    let mut mmu = build_rumble_mmu();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    mmu.on_rumble(move |event: &RumbleEvent| recorded.lock().unwrap().push(event.motor_on));
+
+    mmu.write(0x4000, 0x08);
+    mmu.write(0x4000, 0x09); // Still on, only the RAM bank changed
+    assert!(mmu.is_motor_on());
+    mmu.write(0x4000, 0x01);
+    mmu.write(0x4000, 0x08);
+    mmu.reset(HardwareModel::default());
+    assert!(!mmu.is_motor_on());
+
+    assert_eq!(*events.lock().unwrap(), vec![true, false, true, false]);
+}
+
+#[test]
+fn test_rumble_bit_doesnt_select_ram() {
+    let mut mmu = build_rumble_mmu();
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0x4000, 0x01);
+    mmu.write(0xA000, 0x42);
+
+    mmu.write(0x4000, 0x09);
+    assert_eq!(mmu.read(0xA000), 0x42);
+}
@@ -0,0 +1,216 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::mbc::mbc7::Mbc7;
+use crate::game_boy::components::mmu::mbc::{MapperWrite, Mbc};
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::game_boy::hardware_model::HardwareModel;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+
+const EEPROM_REGISTER: u16 = 0xA080;
+const CHIP_SELECT: u8 = 0b1000_0000;
+const CLOCK: u8 = 0b0100_0000;
+const DATA_IN: u8 = 0b0000_0010;
+
+/// MBC7 cartridge with 4 ROM banks, both RAM enables set
+fn build_mmu() -> MMU {
+    let mut rom = vec![0u8; 4 * ROM_BANK_SIZE];
+    rom[0x147] = 0x22; // MBC7 + Sensor + Rumble + RAM + Battery
+    rom[0x148] = 0x01; // 64 KiB ROM
+    let mut mmu = MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0x4000, 0x40);
+    mmu
+}
+
+/// Shifts the bits into the EEPROM, MSB first, and returns the data out bit after every clock
+fn clock_bits(mmu: &mut MMU, value: u32, count: u8) -> u32 {
+    let mut data_out = 0;
+    for bit in (0..count).rev() {
+        let data_in = if value & (1 << bit) != 0 { DATA_IN } else { 0 };
+        mmu.write(EEPROM_REGISTER, CHIP_SELECT | data_in);
+        mmu.write(EEPROM_REGISTER, CHIP_SELECT | CLOCK | data_in);
+        data_out = data_out << 1 | (mmu.read(EEPROM_REGISTER) & 1) as u32;
+    }
+    data_out
+}
+
+/// Start bit, opcode and address, then the chip is deselected unless data follows
+fn command(mmu: &mut MMU, opcode: u32, address: u32) {
+    clock_bits(mmu, 0b1 << 10 | opcode << 8 | address, 11);
+}
+
+fn deselect(mmu: &mut MMU) {
+    mmu.write(EEPROM_REGISTER, 0x00);
+}
+
+fn write_word(mmu: &mut MMU, address: u32, value: u16) {
+    command(mmu, 0b01, address);
+    clock_bits(mmu, value as u32, 16);
+    deselect(mmu);
+}
+
+fn read_word(mmu: &mut MMU, address: u32) -> u16 {
+    command(mmu, 0b10, address);
+    assert_eq!(mmu.read(EEPROM_REGISTER) & 1, 0, "Dummy bit");
+    let word = clock_bits(mmu, 0, 16) as u16;
+    deselect(mmu);
+    word
+}
+
+fn enable_writes(mmu: &mut MMU) {
+    command(mmu, 0b00, 0b1100_0000);
+    deselect(mmu);
+}
+
+#[rstest]
+#[case::ram_enable(0x0000, 0x0A, MapperWrite::RamEnable(true))]
+#[case::rom_bank(0x2000, 0x00, MapperWrite::RomBankLow(0x00))]
+#[case::second_ram_enable(0x4000, 0x40, MapperWrite::SecondRamEnable(true))]
+#[case::second_ram_disable(0x5FFF, 0x41, MapperWrite::SecondRamEnable(false))]
+#[case::no_register(0x6000, 0x01, MapperWrite::Ignored)]
+fn test_mbc7_write_effect(#[case] address: u16, #[case] value: u8, #[case] expected: MapperWrite) {
+    let mut mbc7 = Mbc::Mbc7(Mbc7::initialize());
+    assert_eq!(mbc7.handle_write(address, value), expected);
+}
+
+#[rstest]
+#[case::both_enabled(0x0A, 0x40, 0x00)]
+#[case::first_missing(0x00, 0x40, 0xFF)]
+#[case::second_missing(0x0A, 0x00, 0xFF)]
+fn test_registers_need_both_enables(#[case] first: u8, #[case] second: u8, #[case] expected: u8) {
+    let mut mmu = build_mmu();
+    mmu.write(0x0000, first);
+    mmu.write(0x4000, second);
+    // Ax6x always reads 0 while mapped
+    assert_eq!(mmu.read(0xA060), expected);
+    assert_eq!(mmu.read(0xB060), 0xFF);
+}
+
+#[rstest]
+#[case::flat(0.0, 0.0, 0x81D0, 0x81D0)]
+#[case::right_down(1.0, 1.0, 0x8160, 0x8240)]
+#[case::half_left_up(-0.5, -0.5, 0x8208, 0x8198)]
+#[case::clamped(-3.0, f32::NAN, 0x8240, 0x81D0)]
+fn test_accelerometer_latch(
+    #[case] x: f32,
+    #[case] y: f32,
+    #[case] expected_x: u16,
+    #[case] expected_y: u16,
+) {
+    let mut mmu = build_mmu();
+    mmu.set_tilt(x, y);
+
+    // Latching only works after erasing
+    mmu.write(0xA010, 0xAA);
+    assert_eq!(mmu.read(0xA020), 0x00);
+    assert_eq!(mmu.read(0xA030), 0x80);
+
+    mmu.write(0xA000, 0x55);
+    mmu.write(0xA010, 0xAA);
+    let read_axis = |mmu: &MMU, low| u16::from_le_bytes([mmu.read(low), mmu.read(low + 0x10)]);
+    assert_eq!(read_axis(&mmu, 0xA020), expected_x);
+    assert_eq!(read_axis(&mmu, 0xA040), expected_y);
+
+    // The latched reading stays until it is erased and latched again
+    mmu.set_tilt(0.25, 0.25);
+    mmu.write(0xA010, 0xAA);
+    assert_eq!(read_axis(&mmu, 0xA020), expected_x);
+}
+
+#[test]
+fn test_eeprom_write_and_read() {
+    let mut mmu = build_mmu();
+    assert_eq!(read_word(&mut mmu, 0x12), 0xFFFF);
+
+    // Writes are ignored until they are enabled
+    write_word(&mut mmu, 0x12, 0x1234);
+    assert_eq!(read_word(&mut mmu, 0x12), 0xFFFF);
+
+    enable_writes(&mut mmu);
+    write_word(&mut mmu, 0x12, 0xBEEF);
+    write_word(&mut mmu, 0x13, 0xCAFE);
+    assert_eq!(read_word(&mut mmu, 0x12), 0xBEEF);
+    // The highest address bit is ignored
+    assert_eq!(read_word(&mut mmu, 0x93), 0xCAFE);
+
+    // Reads continue with the next word
+    command(&mut mmu, 0b10, 0x12);
+    assert_eq!(clock_bits(&mut mmu, 0, 32), 0xBEEF_CAFE);
+    deselect(&mut mmu);
+
+    // ERASE, then EWDS protects the remaining words
+    command(&mut mmu, 0b11, 0x12);
+    deselect(&mut mmu);
+    command(&mut mmu, 0b00, 0b0000_0000);
+    deselect(&mut mmu);
+    write_word(&mut mmu, 0x13, 0x0000);
+    assert_eq!(read_word(&mut mmu, 0x12), 0xFFFF);
+    assert_eq!(read_word(&mut mmu, 0x13), 0xCAFE);
+}
+
+#[test]
+fn test_eeprom_write_and_erase_all() {
+    let mut mmu = build_mmu();
+    enable_writes(&mut mmu);
+
+    // WRAL
+    command(&mut mmu, 0b00, 0b0100_0000);
+    clock_bits(&mut mmu, 0x5AA5, 16);
+    deselect(&mut mmu);
+    assert_eq!(read_word(&mut mmu, 0x00), 0x5AA5);
+    assert_eq!(read_word(&mut mmu, 0x7F), 0x5AA5);
+
+    // ERAL
+    command(&mut mmu, 0b00, 0b1000_0000);
+    deselect(&mut mmu);
+    assert_eq!(mmu.get_cartridge_ram(), vec![0xFF; 256]);
+}
+
+#[test]
+fn test_deselect_aborts_write() {
+    let mut mmu = build_mmu();
+    enable_writes(&mut mmu);
+    command(&mut mmu, 0b01, 0x01);
+    clock_bits(&mut mmu, 0x00, 8);
+    deselect(&mut mmu);
+    assert_eq!(read_word(&mut mmu, 0x01), 0xFFFF);
+}
+
+#[test]
+fn test_eeprom_is_the_battery_save() {
+    let mut mmu = build_mmu();
+    enable_writes(&mut mmu);
+    write_word(&mut mmu, 0x00, 0x1234);
+
+    let save = mmu.get_cartridge_ram();
+    assert_eq!(save.len(), 256);
+    assert_eq!(save[..2], [0x34, 0x12]);
+
+    let mut loaded = build_mmu();
+    assert!(loaded.set_cartridge_ram(&[0; 255]).is_err());
+    loaded.set_cartridge_ram(&save).unwrap();
+    assert_eq!(read_word(&mut loaded, 0x00), 0x1234);
+
+    // The EEPROM survives a reset, like cartridge RAM
+    loaded.reset(HardwareModel::default());
+    assert_eq!(loaded.get_cartridge_ram(), save);
+}
+
+#[test]
+fn test_eeprom_and_latch_in_save_state() {
+    let mut rom = vec![0u8; 4 * ROM_BANK_SIZE];
+    rom[0x147] = 0x22;
+    rom[0x148] = 0x01;
+    let cartridge = Cartridge::from_bytes(&rom).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    game_boy.write_memory(0x0000, 0x0A);
+    game_boy.write_memory(0x4000, 0x40);
+    game_boy.set_tilt(0.5, 0.0);
+    game_boy.write_memory(0xA000, 0x55);
+    game_boy.write_memory(0xA010, 0xAA);
+    game_boy.set_tilt(0.0, 0.0);
+
+    let loaded = GameBoy::load(game_boy.save(), &cartridge).unwrap();
+    assert_eq!(loaded, game_boy);
+    assert_eq!(loaded.read_memory(0xA020), 0x98);
+}
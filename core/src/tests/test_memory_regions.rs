@@ -0,0 +1,48 @@
+use crate::game_boy::components::mmu::region::MemoryRegion;
+use crate::tests::load_test_rom;
+use rstest::rstest;
+
+#[rstest]
+#[case(0x0000, MemoryRegion::RomBank0)]
+#[case(0x7FFF, MemoryRegion::RomBankN)]
+#[case(0x9800, MemoryRegion::Vram)]
+#[case(0xA000, MemoryRegion::ExternalRam)]
+#[case(0xE123, MemoryRegion::EchoRam)]
+#[case(0xFEA0, MemoryRegion::Unusable)]
+#[case(0xFF0F, MemoryRegion::IoRegisters)]
+#[case(0xFFFE, MemoryRegion::Hram)]
+#[case(0xFFFF, MemoryRegion::InterruptEnable)]
+fn test_memory_region(#[case] address: u16, #[case] expected: MemoryRegion) {
+    let region = MemoryRegion::from_address(address);
+    assert_eq!(region, expected);
+    assert!(region.get_range().contains(&address));
+}
+
+#[test]
+fn test_memory_regions_cover_address_space() {
+    let covered: usize = MemoryRegion::ALL
+        .iter()
+        .map(|region| region.get_range().len())
+        .sum();
+    assert_eq!(covered, 0x10000);
+}
+
+#[test]
+fn test_memory_dump_and_search() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    game_boy.write_memory(0xC100, 0xDE);
+    game_boy.write_memory(0xC101, 0xAD);
+    game_boy.write_memory(0xC102, 0xBE);
+
+    assert_eq!(
+        game_boy.dump_memory(0xC0FF, 4),
+        vec![0x00, 0xDE, 0xAD, 0xBE]
+    );
+    assert_eq!(game_boy.dump_memory(0xFFFE, 8).len(), 2);
+    // WRAM is mirrored in echo RAM
+    assert_eq!(
+        game_boy.search_memory(&[0xDE, 0xAD, 0xBE]),
+        vec![0xC100, 0xE100]
+    );
+    assert!(game_boy.search_memory(&[]).is_empty());
+}
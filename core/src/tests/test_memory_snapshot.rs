@@ -0,0 +1,59 @@
+use crate::game_boy::components::mmu::region::MemoryRegion;
+use crate::game_boy::components::mmu::MMU;
+use rstest::rstest;
+
+fn build_mmu() -> MMU {
+    let mut mmu = MMU::builder().rom(0x0150, 0x42).build();
+    for region in MemoryRegion::ALL {
+        let range = region.get_range();
+        // Marks the first and last byte of every writable region
+        mmu.poke(*range.start(), 0xA1);
+        mmu.poke(*range.end(), 0xB2);
+    }
+    mmu
+}
+
+/// Snapshots are exactly what peeking every address of the region returns
+#[test]
+fn test_snapshot_matches_peek() {
+    let mmu = build_mmu();
+    for region in MemoryRegion::ALL {
+        let expected: Vec<u8> = region
+            .get_range()
+            .map(|address| mmu.peek(address))
+            .collect();
+        assert_eq!(mmu.snapshot_region(region), expected, "{region}");
+    }
+}
+
+#[rstest]
+#[case(MemoryRegion::Vram, true)]
+#[case(MemoryRegion::Wram, true)]
+#[case(MemoryRegion::EchoRam, true)]
+#[case(MemoryRegion::Oam, true)]
+#[case(MemoryRegion::Hram, true)]
+#[case(MemoryRegion::RomBank0, false)]
+#[case(MemoryRegion::RomBankN, false)]
+#[case(MemoryRegion::ExternalRam, false)]
+#[case(MemoryRegion::Unusable, false)]
+#[case(MemoryRegion::IoRegisters, false)]
+#[case(MemoryRegion::InterruptEnable, false)]
+fn test_borrowed_regions(#[case] region: MemoryRegion, #[case] borrowed: bool) {
+    let mmu = build_mmu();
+    match mmu.get_region(region) {
+        Some(memory) => {
+            assert!(borrowed);
+            assert_eq!(memory, mmu.snapshot_region(region));
+        }
+        None => assert!(!borrowed),
+    }
+}
+
+#[test]
+fn test_wram_and_echo_ram_share_memory() {
+    let mut mmu = build_mmu();
+    mmu.poke(0xC123, 0x77);
+    assert_eq!(mmu.get_region(MemoryRegion::Wram).unwrap()[0x123], 0x77);
+    assert_eq!(mmu.get_region(MemoryRegion::EchoRam).unwrap()[0x123], 0x77);
+    assert_eq!(mmu.snapshot_region(MemoryRegion::EchoRam).len(), 0x1E00);
+}
@@ -0,0 +1,117 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::mmu::builder::MMUBuilder;
+use crate::game_boy::components::mmu::stats::{MemoryStats, HEATMAP_SIZE};
+use crate::game_boy::components::mmu::{MMU, TAC_ADDRESS};
+use crate::game_boy::components::ppu::PPU;
+use crate::game_boy::components::timer::Timer;
+use crate::game_boy::cycles::Cycles;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+
+#[test]
+fn test_stats_count_accesses_per_page() {
+    let mut mmu = MMU::default();
+    assert!(mmu.get_stats().is_none());
+    mmu.cpu_read(0xC000);
+
+    mmu.set_stats_enabled(true);
+    mmu.cpu_read(0xC000);
+    mmu.cpu_read(0xC0FF);
+    mmu.cpu_read(0xC100);
+    mmu.cpu_write(0xC0AB, 0x42);
+    mmu.cpu_write(0xFF80, 0x42);
+
+    let stats = mmu.get_stats().unwrap();
+    assert_eq!(stats.get_reads(0xC0), 2);
+    assert_eq!(stats.get_reads(0xC1), 1);
+    assert_eq!(stats.get_writes(0xC0), 1);
+    assert_eq!(stats.get_writes(0xFF), 1);
+    assert_eq!(stats.get_reads(0xFF), 0);
+
+    mmu.set_stats_enabled(false);
+    assert!(mmu.get_stats().is_none());
+}
+
+/// The components' own register traffic isn't what the game touches
+#[test]
+fn test_stats_ignore_timer_and_ppu() {
+    let mut mmu = MMUBuilder::new().io(TAC_ADDRESS, 0x05).build();
+    mmu.set_stats_enabled(true);
+    let mut timer = Timer::default();
+    let mut ppu = PPU::new();
+    for _ in 0..17556 {
+        timer.tick(Cycles::from_m(1), &mut mmu);
+        ppu.tick(Cycles::from_m(1), &mut mmu);
+    }
+    mmu.read(0xC000);
+    mmu.write(0xC000, 0x42);
+
+    let stats = mmu.get_stats().unwrap();
+    assert!((0..=0xFF).all(|page| stats.get_reads(page) == 0 && stats.get_writes(page) == 0));
+}
+
+/// (HL) operands are counted like every other access of the CPU
+#[test]
+fn test_stats_count_hl_accesses() {
+    // LD HL, 0xC100 / LD (HL), A / LD A, (HL) / JR -2
+    let rom = RomBuilder::new()
+        .program(&[0x21, 0x00, 0xC1, 0x77, 0x7E, 0x18, 0xFE])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    game_boy.set_memory_stats_enabled(true);
+    game_boy.finish_frame();
+
+    let stats = game_boy.get_memory_stats().unwrap();
+    assert_eq!(stats.get_reads(0xC1), 1);
+    assert_eq!(stats.get_writes(0xC1), 1);
+}
+
+#[test]
+fn test_stats_csv() {
+    let mut stats = MemoryStats::default();
+    stats.record_read(0x0150);
+    stats.record_write(0x0150);
+    stats.record_write(0x01FF);
+
+    let csv = stats.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 257);
+    assert_eq!(lines[0], "start,end,reads,writes");
+    assert_eq!(lines[1], "0x0000,0x00FF,0,0");
+    assert_eq!(lines[2], "0x0100,0x01FF,1,2");
+    assert_eq!(lines[256], "0xFF00,0xFFFF,0,0");
+}
+
+#[test]
+fn test_stats_heatmap() {
+    let mut stats = MemoryStats::default();
+    for _ in 0..100 {
+        stats.record_read(0xC000);
+    }
+    stats.record_read(0xC100);
+    stats.record_write(0xFF80);
+
+    let heatmap = stats.render_heatmap();
+    assert_eq!(heatmap.len(), HEATMAP_SIZE * HEATMAP_SIZE * 4);
+
+    let pixel = |page: usize| &heatmap[page * 4..page * 4 + 4];
+    assert_eq!(pixel(0xC0), [0x00, 0xFF, 0x00, 0xFF]);
+    assert!(pixel(0xC1)[1] > 0 && pixel(0xC1)[1] < 0xFF);
+    assert_eq!(pixel(0xFF), [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(pixel(0x00), [0x00, 0x00, 0x00, 0xFF]);
+}
+
+#[test]
+fn test_game_boy_memory_stats() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    game_boy.set_memory_stats_enabled(true);
+    game_boy.finish_frame();
+
+    let stats = game_boy.get_memory_stats().unwrap();
+    // The CPU fetches its instructions from ROM and polls the IO registers
+    assert!((0x00..0x80).any(|page| stats.get_reads(page) > 0));
+    assert!(stats.get_reads(0xFF) > 0);
+}
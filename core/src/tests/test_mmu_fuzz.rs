@@ -0,0 +1,117 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::types::CartridgeType;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::mbc::Mbc;
+use crate::game_boy::components::mmu::{MMU, ROM_BANK_SIZE};
+use crate::game_boy::GameBoy;
+use crate::tests::test_cpu_fuzz::Rng;
+use rstest::rstest;
+
+const ACCESSES_PER_RUN: usize = 50_000;
+const ROUND_TRIP_INTERVAL: usize = 5_000;
+
+fn cartridge(cartridge_type: CartridgeType, rom_banks: usize, ram_banks: usize) -> Cartridge {
+    Cartridge {
+        rom_banks: vec![[0xAB; ROM_BANK_SIZE]; rom_banks],
+        header: CartridgeHeader {
+            cartridge_type,
+            rom_size: rom_banks,
+            ram_size: ram_banks,
+            ..Default::default()
+        },
+    }
+}
+
+/// Mostly uniform addresses, with extra weight on the MBC registers and the IO page
+fn random_address(rng: &mut Rng) -> u16 {
+    match rng.next_u8() % 4 {
+        0 => rng.next_u16() & 0x7FFF,
+        1 => 0xFF00 | rng.next_u8() as u16,
+        _ => rng.next_u16(),
+    }
+}
+
+/// MBC registers straight from a save state, which may hold values no write could produce
+fn random_mbc1(rng: &mut Rng) -> Mbc {
+    serde_json::from_value(serde_json::json!({
+        "Mbc1": {
+            "bank1": rng.next_u8(),
+            "bank2": rng.next_u8(),
+            "ram_enabled": rng.next_bool(),
+            "banking_mode": rng.next_bool(),
+            "multicart": rng.next_bool(),
+        }
+    }))
+    .unwrap()
+}
+
+#[rstest]
+fn test_mmu_random_accesses(
+    #[values(0x1234_5678_9ABC_DEF0, 0xDEAD_BEEF, 0x0F0F_F0F0_1234)] seed: u64,
+    #[values(
+        (CartridgeType::RomOnly, 2, 0),
+        (CartridgeType::RomOnly, 1, 1),
+        (CartridgeType::MBC1, 3, 0),
+        (CartridgeType::MBC1RamBattery, 5, 3),
+        (CartridgeType::MBC1RamBattery, 64, 4),
+        (CartridgeType::MBC1, 0, 0),
+        (CartridgeType::MBC5RamBattery, 300, 16),
+        (CartridgeType::MBC5RumbleRam, 7, 2),
+        (CartridgeType::MBC7SensorRumbleRamBattery, 64, 0),
+        (CartridgeType::PocketCamera, 64, 16)
+    )]
+    shape: (CartridgeType, usize, usize),
+) {
+    let mut rng = Rng(seed);
+    let cartridge = cartridge(shape.0, shape.1, shape.2);
+    let mut mmu = MMU::initialize(&cartridge).unwrap();
+    mmu.set_stats_enabled(true);
+
+    for access in 1..=ACCESSES_PER_RUN {
+        let address = random_address(&mut rng);
+        if rng.next_bool() {
+            mmu.write(address, rng.next_u8());
+        } else {
+            mmu.read(address);
+            mmu.read_16(address);
+        }
+
+        if access % ROUND_TRIP_INTERVAL == 0 {
+            mmu.read_with_rom_bank(rng.next_u64() as usize, address);
+            mmu.dump(address, usize::MAX);
+            mmu.get_current_rom_bank();
+            mmu = MMU::load(mmu.save(), &cartridge).unwrap();
+        }
+    }
+}
+
+#[rstest]
+fn test_mmu_random_mapper_states(
+    #[values(0x5EED, 0xC0FFEE, 0xBADC0DE)] seed: u64,
+    #[values((1, 0), (2, 1), (7, 3), (128, 4))] banks: (usize, usize),
+) {
+    let mut rng = Rng(seed);
+    let cartridge = cartridge(CartridgeType::MBC1RamBattery, banks.0, banks.1);
+    let initial = MMU::initialize(&cartridge).unwrap();
+
+    for _ in 0..64 {
+        let mut state = initial.save();
+        state.mbc = random_mbc1(&mut rng);
+        let mut mmu = MMU::load(state, &cartridge).unwrap();
+
+        for address in (0x0000..0x8000).step_by(0x3FF).chain(0xA000..0xC000) {
+            let value = mmu.read(address);
+            mmu.write(address, value);
+        }
+    }
+}
+
+#[rstest]
+#[case::mbc3(CartridgeType::MBC3TimerRamBattery)]
+#[case::mbc2(CartridgeType::MBC2)]
+#[case::huc3(CartridgeType::HuC3)]
+fn test_unsupported_mbc_is_an_error(#[case] cartridge_type: CartridgeType) {
+    let cartridge = cartridge(cartridge_type, 2, 0);
+    assert!(MMU::initialize(&cartridge).is_err());
+    assert!(GameBoy::initialize(&cartridge).is_err());
+}
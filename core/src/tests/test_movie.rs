@@ -0,0 +1,117 @@
+use crate::enums::button::{Button, Buttons};
+use crate::input::movie::{import_bk2_input_log, import_movie, import_vbm};
+use crate::tests::setup_test_dir;
+use rstest::rstest;
+
+/// A VBM header followed by the controller data right after it, 2 bytes per controller and frame
+fn build_vbm(start_flags: u8, controller_flags: u8, frame_count: u32, inputs: &[u16]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x100];
+    data[0..4].copy_from_slice(b"VBM\x1A");
+    data[0x04..0x08].copy_from_slice(&1u32.to_le_bytes());
+    data[0x0C..0x10].copy_from_slice(&frame_count.to_le_bytes());
+    data[0x14] = start_flags;
+    data[0x15] = controller_flags;
+    data[0x3C..0x40].copy_from_slice(&0x100u32.to_le_bytes());
+    for input in inputs {
+        data.extend_from_slice(&input.to_le_bytes());
+    }
+    data
+}
+
+const BK2_LOG: &str = "[Input]
+LogKey:#Up|Down|Left|Right|Start|Select|B|A|Power|
+|.........|
+|U......A.|
+|...RS....|
+|.D....B..|
+[/Input]
+";
+
+fn expected_frames() -> Vec<Buttons> {
+    vec![
+        Buttons::NONE,
+        Buttons::NONE.with(Button::Up).with(Button::A),
+        Buttons::NONE.with(Button::Right).with(Button::Start),
+        Buttons::NONE.with(Button::Down).with(Button::B),
+    ]
+}
+
+#[test]
+fn test_import_vbm() {
+    let inputs = expected_frames()
+        .iter()
+        .map(|buttons| buttons.bits() as u16)
+        .collect::<Vec<_>>();
+    let movie = import_vbm(&build_vbm(0, 0b0001, 4, &inputs)).unwrap();
+    assert_eq!(movie.get_frames(), expected_frames());
+}
+
+/// Only the first controller is imported, the frames still contain the words of the others
+#[test]
+fn test_import_vbm_multiple_controllers() {
+    let inputs = [0x0001, 0x0080, 0x0002, 0x0040];
+    let movie = import_vbm(&build_vbm(0, 0b0011, 2, &inputs)).unwrap();
+    assert_eq!(
+        movie.get_frames(),
+        [Buttons::NONE.with(Button::A), Buttons::NONE.with(Button::B)]
+    );
+}
+
+#[rstest]
+#[case::no_signature(build_vbm(0, 1, 1, &[0])[1..].to_vec())]
+#[case::header_too_short(b"VBM\x1A".to_vec())]
+#[case::from_savestate(build_vbm(0b01, 1, 1, &[0]))]
+#[case::from_sram(build_vbm(0b10, 1, 1, &[0]))]
+#[case::no_controller(build_vbm(0, 0, 1, &[0]))]
+#[case::truncated(build_vbm(0, 1, 3, &[0, 0]))]
+#[case::reset(build_vbm(0, 1, 2, &[0, 0x0400]))]
+fn test_import_vbm_errors(#[case] data: Vec<u8>) {
+    assert!(import_vbm(&data).is_err());
+}
+
+#[test]
+fn test_import_bk2_input_log() {
+    let movie = import_bk2_input_log(BK2_LOG).unwrap();
+    assert_eq!(movie.get_frames(), expected_frames());
+}
+
+#[test]
+fn test_import_bk2_player_prefix() {
+    let log = "LogKey:#P1 A|P1 B|\n|A.|\n|.B|\n";
+    let movie = import_bk2_input_log(log).unwrap();
+    assert_eq!(
+        movie.get_frames(),
+        [Buttons::NONE.with(Button::A), Buttons::NONE.with(Button::B)]
+    );
+}
+
+#[rstest]
+#[case::no_log_key("|....|\n")]
+#[case::frame_before_log_key("|.|\nLogKey:#A|\n")]
+#[case::unknown_button("LogKey:#A|Turbo|\n|..|\n")]
+#[case::wrong_width("LogKey:#A|B|\n|...|\n")]
+#[case::power("LogKey:#A|Power|\n|..|\n|.P|\n")]
+fn test_import_bk2_errors(#[case] log: &str) {
+    assert!(import_bk2_input_log(log).is_err());
+}
+
+#[test]
+fn test_import_movie_detects_format() {
+    let directory = setup_test_dir();
+
+    let vbm_path = directory.join("movie.vbm");
+    std::fs::write(&vbm_path, build_vbm(0, 1, 1, &[0x0010])).unwrap();
+    let movie = import_movie(&vbm_path).unwrap();
+    assert_eq!(movie.get_frames(), [Buttons::NONE.with(Button::Right)]);
+
+    let log_path = directory.join("Input Log.txt");
+    std::fs::write(&log_path, BK2_LOG).unwrap();
+    assert_eq!(
+        import_movie(&log_path).unwrap().get_frames(),
+        expected_frames()
+    );
+
+    let bk2_path = directory.join("movie.bk2");
+    std::fs::write(&bk2_path, b"PK\x03\x04").unwrap();
+    assert!(import_movie(&bk2_path).is_err());
+}
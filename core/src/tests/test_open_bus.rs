@@ -0,0 +1,120 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::MMU;
+use crate::game_boy::components::ppu::mode::PPUMode;
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+
+/// MBC1 with 8 KiB of RAM, which stays disabled until 0x0A is written to 0x0000-0x1FFF
+const MBC1_RAM: u8 = 0x02;
+const RAM_8_KIB: u8 = 0x02;
+/// The first byte of the program at 0x0150
+const PROGRAM_BYTE: u8 = 0x18;
+
+fn build_mmu(open_bus: bool, cartridge_type: u8, ram_size: u8) -> MMU {
+    let rom = RomBuilder::new()
+        .bytes(0x0147, &[cartridge_type, 0x00, ram_size])
+        .program(&[PROGRAM_BYTE, 0xFE])
+        .build();
+    let mut mmu = MMU::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    mmu.set_open_bus(open_bus);
+    mmu
+}
+
+#[rstest]
+#[case::fixed_value(false, PPUMode::OAMSearch, 0x00)]
+#[case::hblank(true, PPUMode::HBlank, 0x00)]
+#[case::vblank(true, PPUMode::VBlank, 0x00)]
+#[case::oam_search(true, PPUMode::OAMSearch, 0xFF)]
+#[case::pixel_transfer(true, PPUMode::PixelTransfer, 0xFF)]
+fn test_unusable_region(#[case] open_bus: bool, #[case] mode: PPUMode, #[case] expected: u8) {
+    let mut mmu = build_mmu(open_bus, 0x00, 0x00);
+    mmu.ppu_update_stat(mode, false);
+    for address in [0xFEA0, 0xFECC, 0xFEFF] {
+        mmu.write(address, 0x12);
+        assert_eq!(mmu.read(address), expected);
+    }
+}
+
+#[rstest]
+#[case::disabled_ram(MBC1_RAM, RAM_8_KIB)]
+#[case::no_ram(0x00, 0x00)]
+fn test_missing_cartridge_ram(#[case] cartridge_type: u8, #[case] ram_size: u8) {
+    let mmu = build_mmu(false, cartridge_type, ram_size);
+    mmu.read(0x0150);
+    assert_eq!(mmu.read(0xA000), 0xFF);
+
+    let mut mmu = build_mmu(true, cartridge_type, ram_size);
+    mmu.read(0x0150);
+    assert_eq!(mmu.read(0xA000), PROGRAM_BYTE);
+    // WRAM is on the same bus, VRAM and HRAM aren't
+    mmu.write(0xC000, 0x42);
+    mmu.read(0x8000);
+    mmu.read(0xFF80);
+    assert_eq!(mmu.read(0xBFFF), 0x42);
+    // The last read keeps the bus at its own value
+    assert_eq!(mmu.read(0xA123), 0x42);
+}
+
+#[rstest]
+fn test_enabled_cartridge_ram(#[values(false, true)] open_bus: bool) {
+    let mut mmu = build_mmu(open_bus, MBC1_RAM, RAM_8_KIB);
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0xA000, 0x12);
+    mmu.read(0x0150);
+    assert_eq!(mmu.read(0xA000), 0x12);
+}
+
+/// (address, written value, read with open bus)
+#[rstest]
+#[case::unmapped(0xFF03, 0x00, 0xFF)]
+#[case::unmapped_between_sound_registers(0xFF27, 0x12, 0xFF)]
+#[case::unmapped_after_lcd_registers(0xFF4C, 0x00, 0xFF)]
+#[case::last_io_address(0xFF7F, 0x00, 0xFF)]
+#[case::write_only(0xFF13, 0x00, 0xFF)]
+#[case::partially_readable(0xFF07, 0x05, 0xFD)]
+#[case::fully_readable(0xFF42, 0x34, 0x34)]
+fn test_io_registers(#[case] address: u16, #[case] value: u8, #[case] expected: u8) {
+    let mut mmu = build_mmu(false, 0x00, 0x00);
+    mmu.write(address, value);
+    assert_eq!(mmu.read(address), value);
+
+    mmu.set_open_bus(true);
+    assert!(mmu.has_open_bus());
+    assert_eq!(mmu.read(address), expected);
+}
+
+#[test]
+fn test_open_bus_config() {
+    let rom = RomBuilder::new().program(&[0x18, 0xFE]).build();
+    let cartridge = Cartridge::from_bytes(&rom).unwrap();
+    let config = GameBoyConfig::default().open_bus(true);
+    let mut game_boy = GameBoy::initialize_with_config(&cartridge, config.clone()).unwrap();
+    assert_eq!(game_boy.peek(0xFF03), 0xFF);
+
+    // The option belongs to the config, not to the state
+    let state = game_boy.save();
+    let loaded = GameBoy::load_with_config(state.clone(), &cartridge, config).unwrap();
+    assert_eq!(loaded.peek(0xFF03), 0xFF);
+    game_boy.load_state(state).unwrap();
+    assert_eq!(game_boy.peek(0xFF03), 0xFF);
+    let loaded = GameBoy::load(game_boy.save(), &cartridge).unwrap();
+    assert_eq!(loaded.peek(0xFF03), 0x00);
+}
+
+/// The masked IO reads don't confuse the timer or the interrupt handling
+#[test]
+fn test_instr_timing_with_open_bus() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("instr_timing.gb")).unwrap();
+    let config = GameBoyConfig::default().open_bus(true);
+    let mut game_boy = GameBoy::initialize_with_config(&cartridge, config).unwrap();
+    for _ in 0..500_000 {
+        game_boy.step();
+    }
+    assert_eq!(
+        game_boy.get_frame_buffer(),
+        include_bytes!("../../../test_roms/reference_data/instr_timing.bin")
+    );
+}
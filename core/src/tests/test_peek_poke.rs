@@ -0,0 +1,114 @@
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::{
+    DIV_ADDRESS, DMA_ADDRESS, LY_ADDRESS, MMU, P1_ADDRESS, ROM_BANK_SIZE,
+};
+use crate::tests::load_test_rom;
+use rstest::rstest;
+
+/// MBC1 with 8 ROM banks that start with their own index and 8 KiB of RAM
+fn build_cartridge() -> Cartridge {
+    let mut rom = vec![0u8; 8 * ROM_BANK_SIZE];
+    for bank in 0..8 {
+        rom[bank * ROM_BANK_SIZE] = bank as u8;
+    }
+    rom[0x147] = 0x03; // MBC1+RAM+BATTERY
+    rom[0x148] = 0x02; // 128 KiB ROM
+    rom[0x149] = 0x02; // 8 KiB RAM
+    Cartridge::from_bytes(&rom).unwrap()
+}
+
+fn build_mmu() -> MMU {
+    MMU::initialize(&build_cartridge()).unwrap()
+}
+
+#[test]
+fn test_peek_and_poke_ignore_disabled_ram() {
+    let mut mmu = build_mmu();
+    mmu.write(0x0000, 0x0A);
+    mmu.write(0xA000, 0x42);
+    mmu.write(0x0000, 0x00);
+    assert_eq!(mmu.read(0xA000), 0xFF);
+    assert_eq!(mmu.peek(0xA000), 0x42);
+
+    mmu.poke(0xA001, 0x99);
+    mmu.write(0x0000, 0x0A);
+    assert_eq!(mmu.read(0xA001), 0x99);
+}
+
+#[test]
+fn test_poke_rom_patches_mapped_bank() {
+    let mut mmu = build_mmu();
+    mmu.write(0x2000, 0x03);
+    mmu.poke(0x2000, 0x05);
+    assert_eq!(mmu.peek(0x2000), 0x05);
+    assert_eq!(mmu.read(0x4000), 0x03, "The MBC still maps bank 3");
+
+    mmu.poke(0x4000, 0x33);
+    assert_eq!(mmu.read(0x4000), 0x33);
+    mmu.write(0x2000, 0x04);
+    assert_eq!(mmu.read(0x4000), 0x04, "Only bank 3 is patched");
+}
+
+#[rstest]
+#[case::div(DIV_ADDRESS, 0x12)]
+#[case::ly(LY_ADDRESS, 0x90)]
+#[case::dma(DMA_ADDRESS, 0xC0)]
+fn test_poke_io_register_without_side_effects(#[case] address: u16, #[case] value: u8) {
+    let mut mmu = build_mmu();
+    mmu.poke(address, value);
+    assert_eq!(mmu.peek(address), value);
+    assert_eq!(mmu.take_dma_request(), None);
+}
+
+#[test]
+fn test_cpu_writes_keep_side_effects() {
+    let mut mmu = build_mmu();
+    mmu.write(DIV_ADDRESS, 0x12);
+    assert_eq!(mmu.read(DIV_ADDRESS), 0x00);
+    mmu.write(DMA_ADDRESS, 0xC0);
+    assert_eq!(mmu.take_dma_request(), Some(0xC0));
+}
+
+#[test]
+fn test_peek_is_not_a_joypad_poll() {
+    let mut mmu = build_mmu();
+    mmu.peek(P1_ADDRESS);
+    assert!(!mmu.take_joypad_polled());
+    mmu.read(P1_ADDRESS);
+    assert!(mmu.take_joypad_polled());
+}
+
+#[test]
+fn test_peek_and_poke_are_not_recorded() {
+    let mut mmu = build_mmu();
+    mmu.set_stats_enabled(true);
+    mmu.poke(0xC000, 0x42);
+    assert_eq!(mmu.peek(0xC000), 0x42);
+    let stats = mmu.get_stats().unwrap();
+    assert_eq!(stats.get_reads(0xC0), 0);
+    assert_eq!(stats.get_writes(0xC0), 0);
+
+    mmu.cpu_write(0xC000, 0x42);
+    mmu.cpu_read(0xC000);
+    let stats = mmu.get_stats().unwrap();
+    assert_eq!(stats.get_reads(0xC0), 1);
+    assert_eq!(stats.get_writes(0xC0), 1);
+}
+
+/// Tooling sees the same memory as the CPU outside of the differences above
+#[test]
+fn test_game_boy_peek_matches_cpu_reads() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    for _ in 0..5 {
+        game_boy.finish_frame();
+    }
+    game_boy.poke(0xC123, 0x42);
+    for address in (0x0000..=0xFFFF).filter(|&address| address != P1_ADDRESS) {
+        assert_eq!(
+            game_boy.peek(address),
+            game_boy.read_memory(address),
+            "0x{address:04X}"
+        );
+    }
+    assert_eq!(game_boy.read_memory(0xC123), 0x42);
+}
@@ -0,0 +1,1086 @@
+use crate::enums::interrupts::Interrupt;
+use crate::game_boy::components::component::Component;
+use crate::game_boy::components::dma::Dma;
+use crate::game_boy::components::mmu::builder::MMUBuilder;
+use crate::game_boy::components::mmu::TileMap;
+use crate::game_boy::components::mmu::{
+    BGP_ADDRESS, DMA_ADDRESS, LCDC_ADDRESS, LYC_ADDRESS, LY_ADDRESS, MMU, OBP0_ADDRESS,
+    OBP1_ADDRESS, SCX_ADDRESS, SCY_ADDRESS, STAT_ADDRESS, WX_ADDRESS, WY_ADDRESS,
+};
+use crate::game_boy::components::ppu::color_scheme::ColorScheme;
+use crate::game_boy::components::ppu::debug;
+use crate::game_boy::components::ppu::debug::{
+    ScanlineRegisters, TILE_DATA_HEIGHT, TILE_DATA_WIDTH, TILE_MAP_SIZE,
+};
+use crate::game_boy::components::ppu::frame_buffer_format::{
+    rgb565_to_rgba, rgba_to_rgb565, FrameBufferFormat,
+};
+use crate::game_boy::components::ppu::mode::PPUMode;
+use crate::game_boy::components::ppu::sprite::Sprite;
+use crate::game_boy::components::ppu::timing::{pixel_dot, pixel_transfer_dots, OAM_SEARCH_DOTS};
+use crate::game_boy::components::ppu::{
+    VBlankInfo, COLOR_SCHEME, PPU, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::cycles::Cycles;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+/// 70224 dots per frame, 4 dots per M-cycle
+const M_CYCLES_PER_FRAME: usize = 70224 / 4;
+
+/// A tile where every row is made of the given low and high bit planes
+fn tile_with_rows(first_row: [u8; 2], other_rows: [u8; 2]) -> [u8; 16] {
+    let mut data = [0u8; 16];
+    for row in 0..8 {
+        let [low, high] = if row == 0 { first_row } else { other_rows };
+        data[row * 2] = low;
+        data[row * 2 + 1] = high;
+    }
+    data
+}
+
+/// Background enabled, tile data at 0x8000, tile map at 0x9800, every pixel using color index 1
+fn build_single_color_mmu(bgp: u8) -> MMU {
+    MMU::builder()
+        .io(LCDC_ADDRESS, 0b1001_0001)
+        .io(BGP_ADDRESS, bgp)
+        .tile(0, tile_with_rows([0xFF, 0x00], [0xFF, 0x00]))
+        .build()
+}
+
+fn render_frame(ppu: &mut PPU, mmu: &mut MMU) {
+    for _ in 0..M_CYCLES_PER_FRAME {
+        ppu.step(Cycles::from_m(1), mmu);
+    }
+}
+
+#[rstest]
+#[case(FrameBufferFormat::Rgba8888, 4)]
+#[case(FrameBufferFormat::Rgb565, 2)]
+#[case(FrameBufferFormat::Indexed, 1)]
+fn test_frame_buffer_size(#[case] format: FrameBufferFormat, #[case] bytes_per_pixel: usize) {
+    let ppu = PPU::with_format(format);
+    assert_eq!(
+        ppu.get_frame_buffer().len(),
+        SCREEN_WIDTH * SCREEN_HEIGHT * bytes_per_pixel
+    );
+}
+
+#[rstest]
+#[case(0b1110_0100, 1)]
+#[case(0b0000_1100, 3)]
+#[case(0b1111_0010, 0)]
+fn test_frame_buffer_formats(#[case] bgp: u8, #[case] shade: u8) {
+    let expected_rgba = COLOR_SCHEME[shade as usize];
+    let expected_rgb565 = rgba_to_rgb565(expected_rgba).to_le_bytes();
+
+    for format in [
+        FrameBufferFormat::Rgba8888,
+        FrameBufferFormat::Rgb565,
+        FrameBufferFormat::Indexed,
+    ] {
+        let mut ppu = PPU::with_format(format);
+        let mut mmu = build_single_color_mmu(bgp);
+        render_frame(&mut ppu, &mut mmu);
+
+        let expected_pixel: &[u8] = match format {
+            FrameBufferFormat::Rgba8888 => &expected_rgba,
+            FrameBufferFormat::Rgb565 => &expected_rgb565,
+            FrameBufferFormat::Indexed => &[shade],
+        };
+        for pixel in ppu
+            .get_frame_buffer()
+            .chunks_exact(format.bytes_per_pixel())
+        {
+            assert_eq!(pixel, expected_pixel);
+        }
+    }
+}
+
+#[test]
+fn test_indexed_to_rgba_matches_rgba_output() {
+    let mut rgba_ppu = PPU::with_format(FrameBufferFormat::Rgba8888);
+    let mut indexed_ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu_a = build_single_color_mmu(0b1110_0100);
+    let mut mmu_b = mmu_a.clone();
+
+    render_frame(&mut rgba_ppu, &mut mmu_a);
+    render_frame(&mut indexed_ppu, &mut mmu_b);
+
+    assert_eq!(
+        indexed_ppu.get_rgba_frame_buffer(),
+        rgba_ppu.get_frame_buffer()
+    );
+}
+
+#[rstest]
+#[case([0xFF, 0xFF, 0xFF, 0xFF], 0xFFFF)]
+#[case([0x00, 0x00, 0x00, 0xFF], 0x0000)]
+#[case([0xFF, 0x00, 0x00, 0xFF], 0xF800)]
+#[case([0x00, 0xFF, 0x00, 0xFF], 0x07E0)]
+#[case([0x00, 0x00, 0xFF, 0xFF], 0x001F)]
+fn test_rgb565_conversion(#[case] rgba: [u8; 4], #[case] rgb565: u16) {
+    assert_eq!(rgba_to_rgb565(rgba), rgb565);
+    assert_eq!(rgb565_to_rgba(rgb565), rgba);
+}
+
+const SOLID_3_TILE: u8 = 1;
+const SOLID_1_TILE: u8 = 2;
+/// Left half transparent, right half color 2
+const RIGHT_HALF_TILE: u8 = 3;
+/// Only the top row has color 3, used to check vertical flips and 8x16 sprites
+const TOP_ROW_TILE: u8 = 4;
+
+/// LCD and sprites enabled, background tile map full of the transparent tile 0,
+/// BGP/OBP0 mapping every color ID to itself and OBP1 reversing them
+fn sprite_mmu_builder(lcdc: u8) -> MMUBuilder {
+    MMU::builder()
+        .io(LCDC_ADDRESS, lcdc)
+        .io(BGP_ADDRESS, 0b1110_0100)
+        .io(OBP0_ADDRESS, 0b1110_0100)
+        .io(OBP1_ADDRESS, 0b0001_1011)
+        .io(DMA_ADDRESS, 0xFF)
+        .tile(
+            SOLID_3_TILE as u16,
+            tile_with_rows([0xFF, 0xFF], [0xFF, 0xFF]),
+        )
+        .tile(
+            SOLID_1_TILE as u16,
+            tile_with_rows([0xFF, 0x00], [0xFF, 0x00]),
+        )
+        .tile(
+            RIGHT_HALF_TILE as u16,
+            tile_with_rows([0x00, 0x0F], [0x00, 0x0F]),
+        )
+        .tile(
+            TOP_ROW_TILE as u16,
+            tile_with_rows([0xFF, 0xFF], [0x00, 0x00]),
+        )
+}
+
+fn render_indexed(mut mmu: MMU) -> PPU {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    render_frame(&mut ppu, &mut mmu);
+    ppu
+}
+
+fn shade_at(ppu: &PPU, x: usize, y: usize) -> u8 {
+    ppu.get_frame_buffer()[y * SCREEN_WIDTH + x]
+}
+
+const LCDC_SPRITES_8X8: u8 = 0b1001_0011;
+const LCDC_SPRITES_8X16: u8 = 0b1001_0111;
+
+#[test]
+fn test_sprite_position() {
+    let mmu = sprite_mmu_builder(LCDC_SPRITES_8X8).sprite(0, 36, 18, SOLID_3_TILE, 0);
+    let ppu = render_indexed(mmu.build());
+
+    // Sprite covers x 10..18 and y 20..28
+    assert_eq!(shade_at(&ppu, 10, 20), 3);
+    assert_eq!(shade_at(&ppu, 17, 27), 3);
+    assert_eq!(shade_at(&ppu, 9, 20), 0);
+    assert_eq!(shade_at(&ppu, 18, 20), 0);
+    assert_eq!(shade_at(&ppu, 10, 19), 0);
+    assert_eq!(shade_at(&ppu, 10, 28), 0);
+}
+
+#[test]
+fn test_sprites_disabled() {
+    let mmu = sprite_mmu_builder(0b1001_0001).sprite(0, 36, 18, SOLID_3_TILE, 0);
+    let ppu = render_indexed(mmu.build());
+    assert_eq!(shade_at(&ppu, 10, 20), 0);
+}
+
+#[test]
+fn test_sprite_partially_offscreen() {
+    let mmu = sprite_mmu_builder(LCDC_SPRITES_8X8).sprite(0, 16, 4, SOLID_3_TILE, 0);
+    let ppu = render_indexed(mmu.build());
+    assert_eq!(shade_at(&ppu, 0, 0), 3);
+    assert_eq!(shade_at(&ppu, 3, 0), 3);
+    assert_eq!(shade_at(&ppu, 4, 0), 0);
+}
+
+/// (OAM index, x, tile) of two overlapping sprites and the expected shade where they overlap
+#[rstest]
+#[case::lower_x_wins_over_oam_order((0, 24, SOLID_1_TILE), (1, 20, SOLID_3_TILE), 3)]
+#[case::lower_x_wins_in_oam_order((0, 20, SOLID_3_TILE), (1, 24, SOLID_1_TILE), 3)]
+#[case::tie_broken_by_oam_index((0, 20, SOLID_1_TILE), (1, 20, SOLID_3_TILE), 1)]
+#[case::tie_broken_by_oam_index_reversed((0, 20, SOLID_3_TILE), (1, 20, SOLID_1_TILE), 3)]
+fn test_sprite_x_priority(
+    #[case] first: (u8, u8, u8),
+    #[case] second: (u8, u8, u8),
+    #[case] expected_shade: u8,
+) {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    for (index, x, tile) in [first, second] {
+        builder = builder.sprite(index, 16, x, tile, 0);
+    }
+    let ppu = render_indexed(builder.build());
+
+    // Both sprites cover screen x 16..20
+    assert_eq!(shade_at(&ppu, 16, 0), expected_shade);
+}
+
+#[test]
+fn test_sprite_transparent_pixels_show_lower_priority() {
+    // The winning sprite is transparent on its left half, the other sprite shows through there,
+    // but on the right half the winner is drawn
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    builder = builder.sprite(0, 16, 20, RIGHT_HALF_TILE, 0);
+    builder = builder.sprite(1, 16, 20, SOLID_1_TILE, 0);
+    let ppu = render_indexed(builder.build());
+
+    assert_eq!(shade_at(&ppu, 12, 0), 1);
+    assert_eq!(shade_at(&ppu, 16, 0), 2);
+}
+
+#[rstest]
+#[case::ten_sprites(10, 10)]
+#[case::eleven_sprites(11, 10)]
+#[case::twenty_sprites(20, 10)]
+fn test_sprites_per_line_limit(#[case] sprite_count: u8, #[case] visible: usize) {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    for index in 0..sprite_count {
+        builder = builder.sprite(index, 16, 8 + index * 8, SOLID_3_TILE, 0);
+    }
+    let ppu = render_indexed(builder.build());
+
+    let drawn = (0..sprite_count as usize)
+        .filter(|index| shade_at(&ppu, index * 8, 0) == 3)
+        .count();
+    assert_eq!(drawn, visible);
+    // The limit is applied in OAM order, not by X position
+    assert_eq!(shade_at(&ppu, 0, 0), 3);
+}
+
+#[test]
+fn test_offscreen_sprites_count_towards_limit() {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    for index in 0..10 {
+        builder = builder.sprite(index, 16, 0, SOLID_3_TILE, 0);
+    }
+    builder = builder.sprite(10, 16, 8, SOLID_3_TILE, 0);
+    let ppu = render_indexed(builder.build());
+
+    assert_eq!(shade_at(&ppu, 0, 0), 0);
+}
+
+#[test]
+fn test_sprite_limit_is_per_line() {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    for index in 0..10 {
+        builder = builder.sprite(index, 16, 8 + index * 8, SOLID_3_TILE, 0);
+    }
+    // On the next tile row, so it does not share a line with the others
+    builder = builder.sprite(10, 24, 8, SOLID_1_TILE, 0);
+    let ppu = render_indexed(builder.build());
+
+    assert_eq!(shade_at(&ppu, 0, 7), 3);
+    assert_eq!(shade_at(&ppu, 0, 8), 1);
+}
+
+/// BG priority hides the sprite only behind background color IDs 1-3
+#[rstest]
+#[case::no_priority_over_color_0(0b0000_0000, 0x00, 3)]
+#[case::no_priority_over_color_1(0b0000_0000, 0xFF, 3)]
+#[case::priority_over_color_0(0b1000_0000, 0x00, 3)]
+#[case::priority_over_color_1(0b1000_0000, 0xFF, 1)]
+fn test_sprite_bg_priority(#[case] flags: u8, #[case] bg_low_byte: u8, #[case] expected: u8) {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8)
+        .tile(0, tile_with_rows([bg_low_byte, 0x00], [bg_low_byte, 0x00]));
+    builder = builder.sprite(0, 16, 8, SOLID_3_TILE, flags);
+    let ppu = render_indexed(builder.build());
+
+    assert_eq!(shade_at(&ppu, 0, 0), expected);
+}
+
+#[test]
+fn test_sprite_palettes() {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    builder = builder.sprite(0, 16, 8, SOLID_1_TILE, 0b0000_0000);
+    builder = builder.sprite(1, 16, 16, SOLID_1_TILE, 0b0001_0000);
+    let ppu = render_indexed(builder.build());
+
+    assert_eq!(shade_at(&ppu, 0, 0), 1);
+    assert_eq!(shade_at(&ppu, 8, 0), 2);
+}
+
+/// Every layer is drawn with its own colors, the shades alone would be the same
+#[test]
+fn test_color_scheme_layers() {
+    let mut scheme = ColorScheme::monochrome(COLOR_SCHEME);
+    scheme.object0[1] = [0xFF, 0x00, 0x00, 0xFF];
+    scheme.object1[2] = [0x00, 0x00, 0xFF, 0xFF];
+    let mut mmu = sprite_mmu_builder(LCDC_SPRITES_8X8)
+        .sprite(0, 16, 8, SOLID_1_TILE, 0b0000_0000)
+        .sprite(1, 16, 16, SOLID_1_TILE, 0b0001_0000)
+        .build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Rgba8888);
+    ppu.set_color_scheme(scheme);
+    render_frame(&mut ppu, &mut mmu);
+
+    let image = ppu.get_frame_buffer();
+    assert_eq!(rgba_at(image, SCREEN_WIDTH, 0, 0), [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(rgba_at(image, SCREEN_WIDTH, 8, 0), [0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(rgba_at(image, SCREEN_WIDTH, 24, 0), COLOR_SCHEME[0]);
+}
+
+#[rstest]
+#[case::no_flip(0b0000_0000, (0, 0), 0)]
+#[case::no_flip_right(0b0000_0000, (7, 0), 2)]
+#[case::x_flip(0b0010_0000, (0, 0), 2)]
+#[case::x_flip_right(0b0010_0000, (7, 0), 0)]
+fn test_sprite_x_flip(#[case] flags: u8, #[case] position: (usize, usize), #[case] expected: u8) {
+    let builder = sprite_mmu_builder(LCDC_SPRITES_8X8).sprite(0, 16, 8, RIGHT_HALF_TILE, flags);
+    let ppu = render_indexed(builder.build());
+    assert_eq!(shade_at(&ppu, position.0, position.1), expected);
+}
+
+#[rstest]
+#[case::no_flip_8x8(LCDC_SPRITES_8X8, 0, &[0])]
+#[case::y_flip_8x8(LCDC_SPRITES_8X8, 0b0100_0000, &[7])]
+#[case::no_flip_8x16(LCDC_SPRITES_8X16, 0, &[0, 8])]
+#[case::y_flip_8x16(LCDC_SPRITES_8X16, 0b0100_0000, &[7, 15])]
+fn test_sprite_y_flip_and_size(
+    #[case] lcdc: u8,
+    #[case] flags: u8,
+    #[case] colored_rows: &[usize],
+) {
+    // Tile 5 has its top row colored as well. 8x16 sprites use tile 4 (top) and 5 (bottom),
+    // because the index is rounded down to even, 8x8 sprites only use tile 5
+    let builder = sprite_mmu_builder(lcdc)
+        .tile(
+            TOP_ROW_TILE as u16 + 1,
+            tile_with_rows([0xFF, 0xFF], [0x00, 0x00]),
+        )
+        .sprite(0, 16, 8, TOP_ROW_TILE | 1, flags);
+    let ppu = render_indexed(builder.build());
+
+    let rows: Vec<usize> = (0..16).filter(|&y| shade_at(&ppu, 0, y) == 3).collect();
+    assert_eq!(rows, colored_rows);
+}
+
+#[rstest]
+#[case::map_9800(TileMap::Map9800, 0b1001_0001)]
+#[case::map_9c00(TileMap::Map9C00, 0b1001_1001)]
+fn test_background_tilemap(#[case] map: TileMap, #[case] lcdc: u8) {
+    let mmu = MMU::builder()
+        .io(LCDC_ADDRESS, lcdc)
+        .io(BGP_ADDRESS, 0b1110_0100)
+        .tile(1, tile_with_rows([0xFF, 0xFF], [0xFF, 0xFF]))
+        .tilemap_entry(map, 2, 1, 1)
+        .build();
+    let ppu = render_indexed(mmu);
+
+    assert_eq!(shade_at(&ppu, 16, 8), 3);
+    assert_eq!(shade_at(&ppu, 23, 15), 3);
+    assert_eq!(shade_at(&ppu, 15, 8), 0);
+    assert_eq!(shade_at(&ppu, 24, 8), 0);
+    assert_eq!(shade_at(&ppu, 16, 16), 0);
+}
+
+/// Background and window enabled, the window using the 0x9C00 map
+const LCDC_WINDOW: u8 = 0b1111_0001;
+/// 456 dots per line
+const M_CYCLES_PER_LINE: usize = 114;
+
+/// Scrolling a 3 colored tile in the last map column and a 1 colored tile in the first one across the left screen edge
+#[rstest]
+#[case::whole_tile(248)]
+#[case::sub_tile(251)]
+#[case::last_pixel(255)]
+fn test_scroll_x_wraps_around(#[case] scroll_x: u8) {
+    let mmu = sprite_mmu_builder(LCDC_SPRITES_8X8)
+        .io(SCX_ADDRESS, scroll_x)
+        .tilemap_entry(TileMap::Map9800, 31, 0, SOLID_3_TILE)
+        .tilemap_entry(TileMap::Map9800, 0, 0, SOLID_1_TILE)
+        .build();
+    let ppu = render_indexed(mmu);
+
+    let wrap_x = 256 - scroll_x as usize;
+    assert_eq!(shade_at(&ppu, 0, 0), 3);
+    assert_eq!(shade_at(&ppu, wrap_x - 1, 0), 3);
+    assert_eq!(shade_at(&ppu, wrap_x, 0), 1);
+    assert_eq!(shade_at(&ppu, wrap_x + 7, 0), 1);
+    assert_eq!(shade_at(&ppu, wrap_x + 8, 0), 0);
+}
+
+#[rstest]
+#[case::whole_tile(248)]
+#[case::sub_tile(251)]
+#[case::last_line(255)]
+fn test_scroll_y_wraps_around(#[case] scroll_y: u8) {
+    let mmu = sprite_mmu_builder(LCDC_SPRITES_8X8)
+        .io(SCY_ADDRESS, scroll_y)
+        .tilemap_entry(TileMap::Map9800, 0, 31, SOLID_3_TILE)
+        .tilemap_entry(TileMap::Map9800, 0, 0, SOLID_1_TILE)
+        .build();
+    let ppu = render_indexed(mmu);
+
+    let wrap_y = 256 - scroll_y as usize;
+    assert_eq!(shade_at(&ppu, 0, 0), 3);
+    assert_eq!(shade_at(&ppu, 0, wrap_y - 1), 3);
+    assert_eq!(shade_at(&ppu, 0, wrap_y), 1);
+    assert_eq!(shade_at(&ppu, 0, wrap_y + 7), 1);
+    assert_eq!(shade_at(&ppu, 0, wrap_y + 8), 0);
+}
+
+/// Scrolled to the bottom right corner, the 4 corner tiles of the map meet on screen
+#[test]
+fn test_scroll_wraps_both_axes() {
+    let mmu = sprite_mmu_builder(LCDC_SPRITES_8X8)
+        .io(SCX_ADDRESS, 252)
+        .io(SCY_ADDRESS, 252)
+        .tilemap_entry(TileMap::Map9800, 31, 31, SOLID_3_TILE)
+        .tilemap_entry(TileMap::Map9800, 0, 31, SOLID_1_TILE)
+        .tilemap_entry(TileMap::Map9800, 31, 0, RIGHT_HALF_TILE)
+        .build();
+    let ppu = render_indexed(mmu);
+
+    assert_eq!(shade_at(&ppu, 3, 3), 3);
+    assert_eq!(shade_at(&ppu, 4, 3), 1);
+    assert_eq!(shade_at(&ppu, 3, 4), 2);
+    assert_eq!(shade_at(&ppu, 4, 4), 0);
+}
+
+/// Background full of color 1, the window map full of color 0 except for the right half tile in its top left corner
+fn window_mmu_builder(window_x: u8, window_y: u8) -> MMUBuilder {
+    let mut builder = sprite_mmu_builder(LCDC_WINDOW)
+        .io(WX_ADDRESS, window_x)
+        .io(WY_ADDRESS, window_y)
+        .tilemap_entry(TileMap::Map9C00, 0, 0, RIGHT_HALF_TILE);
+    for y in 0..32 {
+        for x in 0..32 {
+            builder = builder.tilemap_entry(TileMap::Map9800, x, y, SOLID_1_TILE);
+        }
+    }
+    builder
+}
+
+/// The window starts at WX - 7, with WX 0-6 its left edge is cut off instead
+#[rstest]
+fn test_window_x(#[values(0, 1, 3, 6, 7, 8, 100, 159, 166, 167, 255)] window_x: u8) {
+    let ppu = render_indexed(window_mmu_builder(window_x, 0).build());
+
+    for x in 0..SCREEN_WIDTH {
+        let window_pixel = (x + 7).checked_sub(window_x as usize);
+        let expected = match window_pixel {
+            _ if window_x >= 167 => 1,
+            None => 1,
+            Some(4..=7) => 2,
+            Some(_) => 0,
+        };
+        assert_eq!(shade_at(&ppu, x, 0), expected, "WX {window_x}, x {x}");
+    }
+}
+
+#[rstest]
+#[case::top(0, 0)]
+#[case::middle(70, 70)]
+#[case::last_line(143, 143)]
+#[case::below_screen(144, SCREEN_HEIGHT)]
+#[case::never(255, SCREEN_HEIGHT)]
+fn test_window_y(#[case] window_y: u8, #[case] first_line: usize) {
+    let ppu = render_indexed(window_mmu_builder(7, window_y).build());
+
+    for y in 0..SCREEN_HEIGHT {
+        let expected = match y.checked_sub(first_line) {
+            None => 1,
+            Some(0..=7) => 2,
+            Some(_) => 0,
+        };
+        assert_eq!(shade_at(&ppu, 4, y), expected, "WY {window_y}, y {y}");
+    }
+}
+
+#[test]
+fn test_window_disabled_by_lcdc() {
+    let mmu = window_mmu_builder(7, 0)
+        .io(LCDC_ADDRESS, LCDC_WINDOW & !0b0010_0000)
+        .build();
+    let ppu = render_indexed(mmu);
+    assert_eq!(shade_at(&ppu, 4, 0), 1);
+    assert_eq!(shade_at(&ppu, 0, 0), 1);
+}
+
+/// The window only advances its own line counter on lines it was drawn on,
+/// after being hidden it continues where it left off instead of jumping ahead with LY
+#[test]
+fn test_window_line_counter_pauses_while_hidden() {
+    let mut mmu = window_mmu_builder(7, 0)
+        .tilemap_entry(TileMap::Map9C00, 0, 1, SOLID_3_TILE)
+        .build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+
+    let run_lines = |ppu: &mut PPU, mmu: &mut MMU, window_x: u8, lines: usize| {
+        mmu.write(WX_ADDRESS, window_x);
+        for _ in 0..lines * M_CYCLES_PER_LINE {
+            ppu.step(Cycles::from_m(1), mmu);
+        }
+    };
+    run_lines(&mut ppu, &mut mmu, 7, 4);
+    run_lines(&mut ppu, &mut mmu, 167, 10);
+    run_lines(&mut ppu, &mut mmu, 7, 140);
+
+    assert_eq!(shade_at(&ppu, 4, 0), 2);
+    assert_eq!(shade_at(&ppu, 4, 3), 2);
+    assert_eq!(shade_at(&ppu, 4, 4), 1);
+    assert_eq!(shade_at(&ppu, 4, 13), 1);
+    // Line 14 continues with window line 4 instead of 14
+    assert_eq!(shade_at(&ppu, 4, 14), 2);
+    assert_eq!(shade_at(&ppu, 4, 17), 2);
+    assert_eq!(shade_at(&ppu, 4, 18), 3);
+    assert_eq!(shade_at(&ppu, 4, 25), 3);
+    assert_eq!(shade_at(&ppu, 4, 26), 0);
+}
+
+/// The window counter restarts with every frame
+#[test]
+fn test_window_same_every_frame() {
+    let mut mmu = window_mmu_builder(7, 0).build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    render_frame(&mut ppu, &mut mmu);
+    let first_frame = ppu.get_frame_buffer().to_vec();
+    render_frame(&mut ppu, &mut mmu);
+    assert_eq!(ppu.get_frame_buffer(), first_frame);
+}
+
+#[test]
+#[should_panic(expected = "is not an IO register")]
+fn test_builder_io_rejects_other_addresses() {
+    MMU::builder().io(0xC000, 0x00);
+}
+
+fn rgba_at(image: &[u8], width: usize, x: usize, y: usize) -> [u8; 4] {
+    let index = (y * width + x) * 4;
+    image[index..index + 4].try_into().unwrap()
+}
+
+#[test]
+fn test_debug_tile_data_image() {
+    let mmu = MMU::builder()
+        .io(BGP_ADDRESS, 0b1110_0100)
+        .tile(17, tile_with_rows([0xFF, 0xFF], [0x00, 0x00]))
+        .build();
+
+    let image = debug::render_tile_data(&mmu);
+    assert_eq!(image.len(), TILE_DATA_WIDTH * TILE_DATA_HEIGHT * 4);
+    // Tile 17 is the second tile of the second row, only its first row is color 3
+    assert_eq!(rgba_at(&image, TILE_DATA_WIDTH, 8, 8), COLOR_SCHEME[3]);
+    assert_eq!(rgba_at(&image, TILE_DATA_WIDTH, 15, 8), COLOR_SCHEME[3]);
+    assert_eq!(rgba_at(&image, TILE_DATA_WIDTH, 8, 9), COLOR_SCHEME[0]);
+    assert_eq!(rgba_at(&image, TILE_DATA_WIDTH, 0, 8), COLOR_SCHEME[0]);
+
+    let info = debug::get_tile_info(&mmu, 12, 13).unwrap();
+    assert_eq!(info.index, 17);
+    assert_eq!(info.address, 0x8110);
+    assert_eq!(info.palette, 0b1110_0100);
+    assert_eq!(debug::get_tile_info(&mmu, TILE_DATA_WIDTH, 0), None);
+}
+
+/// Tile ID 1 is read from 0x8010 or 0x9010 depending on the LCDC addressing mode
+#[rstest]
+#[case(0b1001_0001, 0x001, 0x8010)]
+#[case(0b1000_0001, 0x101, 0x9010)]
+fn test_debug_tile_map_image(#[case] lcdc: u8, #[case] tile_index: u16, #[case] data_address: u16) {
+    let mmu = MMU::builder()
+        .io(LCDC_ADDRESS, lcdc)
+        .io(BGP_ADDRESS, 0b1110_0100)
+        .tile(tile_index, tile_with_rows([0xFF, 0x00], [0xFF, 0x00]))
+        .tilemap_entry(TileMap::Map9C00, 31, 2, 0x01)
+        .build();
+
+    let image = debug::render_tile_map(&mmu, TileMap::Map9C00);
+    assert_eq!(image.len(), TILE_MAP_SIZE * TILE_MAP_SIZE * 4);
+    assert_eq!(rgba_at(&image, TILE_MAP_SIZE, 255, 23), COLOR_SCHEME[1]);
+    assert_eq!(rgba_at(&image, TILE_MAP_SIZE, 0, 0), COLOR_SCHEME[0]);
+
+    let entry = debug::get_tile_map_entry(&mmu, TileMap::Map9C00, 250, 20).unwrap();
+    assert_eq!(entry.map_address, 0x9C00 + 2 * 32 + 31);
+    assert_eq!(entry.tile_id, 0x01);
+    assert_eq!(entry.data_address, data_address);
+}
+
+#[test]
+fn test_debug_oam_entries_first_hit() {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8);
+    builder = builder.sprite(0, 20, 28, SOLID_3_TILE, 0);
+    // Lower X, so its opaque right half covers the first 4 columns of sprite 0
+    builder = builder.sprite(1, 20, 24, RIGHT_HALF_TILE, 0);
+    builder = builder.sprite(2, 20, 0, SOLID_3_TILE, 0);
+    // Flipped vertically, only the last row is drawn
+    builder = builder.sprite(3, 40, 50, TOP_ROW_TILE, 0b0100_0000);
+    let mut mmu = builder.build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    while !ppu.step(Cycles::from_m(1), &mut mmu).2 {}
+
+    let entries = debug::get_oam_entries(&mmu, ppu.get_sprite_hits());
+    assert_eq!(entries.len(), 40);
+    assert_eq!(entries[3].address, 0xFE0C);
+    assert_eq!(entries[3].sprite.x, 50);
+    let hits: Vec<_> = entries[..5]
+        .iter()
+        .map(|entry| entry.first_hit.map(|hit| (hit.line, hit.dot)))
+        .collect();
+    // 80 dots of OAM search and 12 of the first fetches before the pixel. On line 4 the sprite at X 0 waits 11 dots,
+    // sprite 1 waits 5 for its tile and both sprites 6 for their fetches. Sprite 3 waits 3 for its tile and 6.
+    assert_eq!(
+        hits,
+        [
+            Some((4, 92 + 24 + 28)),
+            Some((4, 92 + 20 + 28)),
+            None,
+            Some((31, 92 + 42 + 9)),
+            None
+        ]
+    );
+
+    // 10 lines of VBlank, the next frame starts without hits
+    for _ in 0..11 * 456 / 4 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_eq!(ppu.get_sprite_hits().get(0), None);
+}
+
+/// A palette and scroll change in the middle of the frame shows up from the next drawn line on
+#[test]
+fn test_scanline_registers() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    mmu.write(OBP0_ADDRESS, 0x12);
+    mmu.write(OBP1_ADDRESS, 0x34);
+    assert_eq!(ppu.get_scanline_log().get(0), None);
+
+    while mmu.read(LY_ADDRESS) < 72 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    mmu.write(BGP_ADDRESS, 0b0001_1011);
+    mmu.write(SCX_ADDRESS, 5);
+    mmu.write(SCY_ADDRESS, 9);
+    while mmu.read(LY_ADDRESS) < 144 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+
+    let before = ScanlineRegisters {
+        bgp: 0b1110_0100,
+        obp0: 0x12,
+        obp1: 0x34,
+        scx: 0,
+        scy: 0,
+    };
+    let after = ScanlineRegisters {
+        bgp: 0b0001_1011,
+        scx: 5,
+        scy: 9,
+        ..before
+    };
+    let log = ppu.get_scanline_log();
+    assert_eq!(log.get_lines().len(), SCREEN_HEIGHT);
+    assert_eq!(log.get(0), Some(before));
+    assert_eq!(log.get(71), Some(before));
+    assert_eq!(log.get(72), Some(after));
+    assert_eq!(log.get(143), Some(after));
+    assert_eq!(log.get(144), None);
+
+    // The frame buffer turns white while the LCD is off, so no line was drawn with any registers
+    mmu.write(LCDC_ADDRESS, 0b0001_0001);
+    ppu.step(Cycles::from_m(1), &mut mmu);
+    assert!(ppu
+        .get_scanline_log()
+        .get_lines()
+        .iter()
+        .all(Option::is_none));
+}
+
+#[test]
+fn test_lcd_off_skips_ppu_work() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    render_frame(&mut ppu, &mut mmu);
+    for _ in 0..1000 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_ne!(mmu.read(LY_ADDRESS), 0);
+
+    mmu.write(LCDC_ADDRESS, 0b0001_0001);
+    mmu.interrupts_mut().write_if(0);
+    let frames_finished = (0..M_CYCLES_PER_FRAME * 2)
+        .filter(|_| ppu.step(Cycles::from_m(1), &mut mmu).2)
+        .count();
+
+    assert_eq!(frames_finished, 2);
+    assert_eq!(mmu.read(LY_ADDRESS), 0);
+    assert_eq!(mmu.read(STAT_ADDRESS) & 0b11, 0);
+    assert_eq!(mmu.interrupts().read_if(), 0);
+    // The LCD shows white while it is off
+    assert!(ppu.get_frame_buffer().iter().all(|shade| *shade == 0));
+}
+
+fn sprites_at(xs: &[u8]) -> Vec<Sprite> {
+    xs.iter()
+        .enumerate()
+        .map(|(index, x)| Sprite::from_oam_bytes([16, *x, 0, 0], index as u8))
+        .collect()
+}
+
+/// https://gbdev.io/pandocs/Rendering.html#mode-3-length
+#[rstest]
+#[case::nothing(0, None, &[], 172)]
+#[case::fine_scroll(3, None, &[], 175)]
+#[case::coarse_scroll_is_free(8, None, &[], 172)]
+#[case::window(0, Some(7), &[], 178)]
+#[case::sprite_aligned_with_tile(0, None, &[8], 183)]
+#[case::sprite_in_middle_of_tile(0, None, &[12], 179)]
+#[case::sprite_at_end_of_tile(0, None, &[15], 178)]
+#[case::scroll_moves_tile_boundary(4, None, &[12], 187)]
+#[case::sprite_at_x_0(5, None, &[0], 188)]
+#[case::sprite_right_of_screen(0, None, &[168], 172)]
+#[case::second_sprite_in_same_tile(0, None, &[8, 9], 189)]
+#[case::sprites_in_different_tiles(0, None, &[8, 16], 194)]
+#[case::sprite_on_window_tile(0, Some(11), &[12], 189)]
+#[case::ten_sprites_at_x_0(0, None, &[0; 10], 282)]
+fn test_pixel_transfer_dots(
+    #[case] scroll_x: u8,
+    #[case] window_x: Option<u8>,
+    #[case] xs: &[u8],
+    #[case] expected: u32,
+) {
+    assert_eq!(
+        pixel_transfer_dots(scroll_x, window_x, &sprites_at(xs)),
+        expected
+    );
+    // The pixels are timed by the same penalties, the one after the last ends mode 3
+    assert_eq!(
+        pixel_dot(scroll_x, window_x, &sprites_at(xs), SCREEN_WIDTH),
+        OAM_SEARCH_DOTS + expected
+    );
+}
+
+/// Penalties of sprites right of a pixel don't delay it
+#[rstest]
+#[case::left_of_sprite(0, None, &[40], 10, 80 + 12 + 10)]
+#[case::at_sprite(0, None, &[40], 32, 80 + 12 + 32 + 11)]
+#[case::fine_scroll(3, None, &[], 0, 80 + 12 + 3)]
+#[case::before_window(0, Some(87), &[], 79, 80 + 12 + 79)]
+#[case::in_window(0, Some(87), &[], 80, 80 + 12 + 80 + 6)]
+fn test_pixel_dot(
+    #[case] scroll_x: u8,
+    #[case] window_x: Option<u8>,
+    #[case] xs: &[u8],
+    #[case] x: usize,
+    #[case] expected: u32,
+) {
+    assert_eq!(pixel_dot(scroll_x, window_x, &sprites_at(xs), x), expected);
+}
+
+/// The sprites on a line delay the switch to HBlank, the line still takes 456 dots
+#[rstest]
+#[case::no_sprites(0, 0, 172)]
+#[case::ten_sprites(10, 0, 282)]
+#[case::only_first_ten_count(20, 0, 282)]
+#[case::scrolled(0, 5, 177)]
+fn test_pixel_transfer_length(
+    #[case] sprite_count: u8,
+    #[case] scroll_x: u8,
+    #[case] expected: u32,
+) {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8).io(SCX_ADDRESS, scroll_x);
+    for index in 0..sprite_count {
+        builder = builder.sprite(index, 16, 0, SOLID_3_TILE, 0);
+    }
+    let mut mmu = builder.build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+
+    let mut pixel_transfer = 0;
+    while mmu.read(LY_ADDRESS) == 0 {
+        ppu.step(Cycles::from_t(1), &mut mmu);
+        if ppu.get_mode() == PPUMode::PixelTransfer {
+            pixel_transfer += 1;
+        }
+    }
+    assert_eq!(pixel_transfer, expected);
+    assert_eq!(ppu.get_pixel_transfer_dots(), expected);
+    assert_eq!(ppu.get_mode(), PPUMode::OAMSearch);
+
+    // Every line still starts 456 dots after the previous one
+    for _ in 0..456 {
+        ppu.step(Cycles::from_t(1), &mut mmu);
+    }
+    assert_eq!(mmu.read(LY_ADDRESS), 2);
+}
+
+#[test]
+fn test_sprites_disabled_dont_extend_pixel_transfer() {
+    let mut builder = sprite_mmu_builder(0b1001_0001);
+    for index in 0..10 {
+        builder = builder.sprite(index, 16, 0, SOLID_3_TILE, 0);
+    }
+    let mut mmu = builder.build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    for _ in 0..(80 + 4) / 4 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_eq!(ppu.get_pixel_transfer_dots(), 172);
+}
+
+/// A game waiting for the mode 0 STAT interrupt sees it later on lines with sprites
+#[test]
+fn test_sprites_delay_hblank_interrupt() {
+    let mut builder = sprite_mmu_builder(LCDC_SPRITES_8X8).io(STAT_ADDRESS, 0b0000_1000);
+    for index in 0..10 {
+        builder = builder.sprite(index, 16, 0, SOLID_3_TILE, 0);
+    }
+    let mut mmu = builder.build();
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+
+    let first_interrupt = (1..=456u32)
+        .find(|_| ppu.step(Cycles::from_t(1), &mut mmu).1)
+        .unwrap();
+    assert_eq!(first_interrupt, 80 + 282);
+}
+
+#[test]
+fn test_lcd_on_restarts_at_first_line() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    mmu.write(LCDC_ADDRESS, 0b0001_0001);
+    for _ in 0..5000 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+
+    mmu.write(LCDC_ADDRESS, 0b1001_0001);
+    // 80 dots OAM search, 172 dots pixel transfer, 204 dots HBlank
+    for _ in 0..(456 / 4) {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_eq!(mmu.read(LY_ADDRESS), 1);
+
+    render_frame(&mut ppu, &mut mmu);
+    assert!(ppu.get_frame_buffer().iter().all(|shade| *shade == 1));
+}
+
+#[test]
+fn test_render_interval() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    ppu.set_render_interval(2);
+    assert_eq!(ppu.get_render_interval(), 2);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+
+    render_frame(&mut ppu, &mut mmu);
+    assert_eq!(ppu.get_frame_buffer()[0], 1);
+
+    // The second frame is skipped and keeps the first one, the third is rendered again
+    mmu.write(BGP_ADDRESS, 0b0000_1100);
+    render_frame(&mut ppu, &mut mmu);
+    assert_eq!(ppu.get_frame_buffer()[0], 1);
+    assert!(mmu.interrupts().is_requested(Interrupt::Vblank));
+    render_frame(&mut ppu, &mut mmu);
+    assert_eq!(ppu.get_frame_buffer()[0], 3);
+
+    ppu.set_render_interval(0);
+    assert_eq!(ppu.get_render_interval(), 1);
+}
+
+/// Writes only reach the interrupt selects, the mode and LYC=LY bits stay with the PPU
+#[rstest]
+#[case::set_all(0xFF, 0b0111_1000)]
+#[case::clear_all(0x00, 0b0000_0000)]
+#[case::lyc_select(0b0100_0111, 0b0100_0000)]
+fn test_stat_write_keeps_ppu_bits(#[case] value: u8, #[case] expected_selects: u8) {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    mmu.write(LYC_ADDRESS, 3);
+    // Line 3, in the middle of the pixel transfer
+    for _ in 0..(3 * 456 + 100) / 4 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_eq!(ppu.get_mode(), PPUMode::PixelTransfer);
+
+    mmu.write(STAT_ADDRESS, value);
+    assert_eq!(
+        mmu.read(STAT_ADDRESS),
+        0b1000_0000 | expected_selects | 0b0000_0100 | PPUMode::PixelTransfer.get_mode_bits()
+    );
+}
+
+#[test]
+fn test_ly_is_read_only() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    for _ in 0..(2 * 456) / 4 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+
+    mmu.write(LY_ADDRESS, 100);
+    assert_eq!(mmu.read(LY_ADDRESS), 2);
+}
+
+#[test]
+fn test_stat_mode_forced_to_hblank_while_lcd_off() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    for _ in 0..(144 * 456 + 8) / 4 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+    }
+    assert_eq!(
+        mmu.read(STAT_ADDRESS) & 0b11,
+        PPUMode::VBlank.get_mode_bits()
+    );
+
+    mmu.write(LCDC_ADDRESS, 0b0001_0001);
+    for _ in 0..1000 {
+        ppu.step(Cycles::from_m(1), &mut mmu);
+        mmu.write(STAT_ADDRESS, 0xFF);
+        assert_eq!(
+            mmu.read(STAT_ADDRESS) & 0b11,
+            PPUMode::HBlank.get_mode_bits()
+        );
+        assert_eq!(ppu.get_mode(), PPUMode::HBlank);
+    }
+}
+
+/// OAM DMA doesn't block IO, STAT keeps following the PPU while a transfer runs
+#[test]
+fn test_stat_live_during_dma() {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut dma = Dma::default();
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    mmu.write(LYC_ADDRESS, 1);
+
+    let mut seen_modes = Vec::new();
+    for m_cycle in 0..(3 * 456) / 4 {
+        if m_cycle % 100 == 0 {
+            mmu.write(DMA_ADDRESS, 0xC0);
+        }
+        dma.step(Cycles::from_m(1), &mut mmu);
+        ppu.step(Cycles::from_m(1), &mut mmu);
+        assert!(dma.is_active());
+
+        let stat = mmu.read(STAT_ADDRESS);
+        assert_eq!(stat & 0b11, ppu.get_mode().get_mode_bits());
+        assert_eq!(stat & 0b100 != 0, mmu.read(LY_ADDRESS) == 1);
+        if !seen_modes.contains(&ppu.get_mode()) {
+            seen_modes.push(ppu.get_mode());
+        }
+    }
+    assert_eq!(
+        seen_modes,
+        [PPUMode::OAMSearch, PPUMode::PixelTransfer, PPUMode::HBlank]
+    );
+}
+
+/// The STAT interrupt fires when any selected source becomes active while none was before.
+/// HBlank going straight into OAM search, or LY=LYC following HBlank, keeps the line high without a new request.
+#[rstest]
+#[case::lyc(0b0100_0000, 1, 1)]
+#[case::hblank(0b0000_1000, 1, 144)]
+#[case::vblank(0b0001_0000, 1, 1)]
+#[case::oam_search(0b0010_0000, 1, 144)]
+#[case::hblank_and_oam_search(0b0010_1000, 1, 145)]
+#[case::hblank_and_lyc(0b0100_1000, 1, 143)]
+#[case::hblank_and_unreached_lyc(0b0100_1000, 200, 144)]
+fn test_stat_interrupt_on_rising_edge(
+    #[case] stat: u8,
+    #[case] lyc: u8,
+    #[case] expected_interrupts: usize,
+) {
+    let mut ppu = PPU::with_format(FrameBufferFormat::Indexed);
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    mmu.write(STAT_ADDRESS, stat);
+    mmu.write(LYC_ADDRESS, lyc);
+
+    // The last M-cycle of the frame would already start the OAM search of the next one
+    let interrupts = (0..M_CYCLES_PER_FRAME - 1)
+        .filter(|_| ppu.step(Cycles::from_m(1), &mut mmu).1)
+        .count();
+    assert_eq!(interrupts, expected_interrupts);
+}
+
+/// Steps the PPU by the given amount of M-cycles at once and records every finished frame, numbered from 0
+fn record_frames(ppu: &mut PPU, mmu: &mut MMU, m_cycles: u32, steps: usize) -> Vec<VBlankInfo> {
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let recorded = frames.clone();
+    let id = ppu.on_frame(move |info: &VBlankInfo| recorded.lock().unwrap().push(*info));
+    let mut frame_number = 0;
+    for _ in 0..steps {
+        if ppu.step(Cycles::from_m(m_cycles), mmu).2 {
+            ppu.notify_frame(frame_number);
+            frame_number += 1;
+        }
+    }
+    ppu.remove_frame_listener(id);
+    let frames = frames.lock().unwrap().clone();
+    frames
+}
+
+/// The PPU starts at line 0, so the first frame ends 144 lines later at the first VBlank.
+/// Steps running past the end of a frame count towards the next one.
+#[rstest]
+fn test_vblank_info_timing(#[values(1, 3, 5, 6)] m_cycles: u32) {
+    let mut ppu = PPU::new();
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    let steps = (M_CYCLES_PER_FRAME * 4) / m_cycles as usize;
+    let frames = record_frames(&mut ppu, &mut mmu, m_cycles, steps);
+
+    assert_eq!(frames.len(), 4);
+    for (number, info) in frames.iter().enumerate() {
+        assert_eq!(info.frame_number, number as u64);
+        assert!(!info.lcd_off);
+    }
+    assert_eq!(frames[0].cycles_this_frame, Cycles::from_t(144 * 456));
+    let total: u32 = frames
+        .iter()
+        .map(|info| info.cycles_this_frame.as_t())
+        .sum();
+    assert_eq!(total, 144 * 456 + 3 * 70224);
+}
+
+#[test]
+fn test_vblank_info_lcd_off() {
+    let mut ppu = PPU::new();
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    render_frame(&mut ppu, &mut mmu);
+
+    // A whole frame from line 0 ends 10 lines into the next frame, the LCD is switched off 1000 M-cycles later
+    record_frames(&mut ppu, &mut mmu, 1, 1000);
+    mmu.write(LCDC_ADDRESS, 0b0001_0001);
+    let frames = record_frames(&mut ppu, &mut mmu, 1, M_CYCLES_PER_FRAME * 2);
+
+    assert_eq!(frames.len(), 2);
+    assert!(frames.iter().all(|info| info.lcd_off));
+    assert_eq!(
+        frames[0].cycles_this_frame,
+        Cycles::from_t(10 * 456 + 4000 + 70224)
+    );
+    assert_eq!(frames[1].cycles_this_frame, Cycles::from_t(70224));
+
+    // The first frame after switching the LCD back on takes the rest of the frame with the LCD off and 144 lines
+    record_frames(&mut ppu, &mut mmu, 1, 1000);
+    mmu.write(LCDC_ADDRESS, 0b1001_0001);
+    let frames = record_frames(&mut ppu, &mut mmu, 1, M_CYCLES_PER_FRAME);
+    assert_eq!(frames.len(), 1);
+    assert!(!frames[0].lcd_off);
+    assert_eq!(
+        frames[0].cycles_this_frame,
+        Cycles::from_t(4000 + 144 * 456)
+    );
+}
+
+#[test]
+fn test_frame_listener_survives_reset() {
+    let mut ppu = PPU::new();
+    let mut mmu = build_single_color_mmu(0b1110_0100);
+    let frames = Arc::new(Mutex::new(0));
+    let recorded = frames.clone();
+    ppu.on_frame(move |_: &VBlankInfo| *recorded.lock().unwrap() += 1);
+
+    render_frame(&mut ppu, &mut mmu);
+    ppu.notify_frame(0);
+    ppu.reset(&GameBoyConfig::default());
+    render_frame(&mut ppu, &mut mmu);
+    ppu.notify_frame(1);
+
+    assert_eq!(*frames.lock().unwrap(), 2);
+}
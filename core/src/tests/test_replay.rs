@@ -0,0 +1,97 @@
+use crate::enums::button::{Button, Buttons};
+use crate::game_boy::components::mmu::BGP_ADDRESS;
+use crate::input::replay::{
+    frame_checksum, play_replay, FrameChecksums, FrameDivergence, FrameVerification,
+};
+use crate::input::InputMacro;
+use crate::tests::{load_test_rom, setup_test_dir};
+
+fn replay() -> InputMacro {
+    let mut frames = vec![Buttons::NONE; 10];
+    frames[4] = Buttons::NONE.with(Button::Start);
+    InputMacro::new(frames)
+}
+
+#[test]
+fn test_frame_checksum() {
+    assert_eq!(frame_checksum(b""), 0);
+    assert_eq!(frame_checksum(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_replay_without_verification() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let result = play_replay(&mut game_boy, &replay(), &FrameVerification::Off);
+    assert_eq!(result.frames, 10);
+    assert_eq!(result.checksums, None);
+    assert_eq!(result.divergence, None);
+}
+
+#[test]
+fn test_replay_verification() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    for _ in 0..60 {
+        game_boy.finish_frame();
+    }
+    // Verifying from a save state works the same as from power on
+    let start = game_boy.save();
+
+    let recorded = play_replay(&mut game_boy, &replay(), &FrameVerification::Record)
+        .checksums
+        .unwrap();
+    assert_eq!(recorded.len(), 10);
+
+    game_boy.load_state(start.clone()).unwrap();
+    let verification = FrameVerification::Compare(recorded.clone());
+    let result = play_replay(&mut game_boy, &replay(), &verification);
+    assert_eq!(result.checksums.as_ref(), Some(&recorded));
+    assert_eq!(result.divergence, None);
+
+    // Only the colors change, the memory the game uses stays the same
+    game_boy.load_state(start).unwrap();
+    let result = play_replay(
+        &mut game_boy,
+        &InputMacro::new(replay().get_frames()[..3].to_vec()),
+        &verification,
+    );
+    assert_eq!(result.divergence, None);
+    game_boy.poke(BGP_ADDRESS, !game_boy.peek(BGP_ADDRESS));
+    let result = play_replay(
+        &mut game_boy,
+        &InputMacro::new(replay().get_frames()[3..].to_vec()),
+        &FrameVerification::Compare(FrameChecksums::new(recorded.get_checksums()[3..].to_vec())),
+    );
+    let divergence = result.divergence.unwrap();
+    assert_eq!(divergence.frame, 0);
+    assert_eq!(divergence.expected, recorded.get_checksums()[3]);
+    assert!(result
+        .summary()
+        .starts_with("Video output diverged at frame 0"));
+}
+
+#[test]
+fn test_first_divergence() {
+    let reference = FrameChecksums::new(vec![1, 2, 3, 4]);
+    assert_eq!(reference.first_divergence(&reference), None);
+    assert_eq!(
+        reference.first_divergence(&FrameChecksums::new(vec![1, 2, 5, 6])),
+        Some(FrameDivergence {
+            frame: 2,
+            expected: 3,
+            actual: 5,
+        })
+    );
+    // A shorter run is compared as far as it goes
+    assert_eq!(
+        reference.first_divergence(&FrameChecksums::new(vec![1, 2])),
+        None
+    );
+}
+
+#[test]
+fn test_store_and_load_frame_checksums() {
+    let checksums = FrameChecksums::new(vec![0xDEAD_BEEF, 0, 42]);
+    let path = setup_test_dir().join("frame_checksums.json");
+    checksums.store(&path).unwrap();
+    assert_eq!(FrameChecksums::load(&path).unwrap(), checksums);
+}
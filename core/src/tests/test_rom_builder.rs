@@ -0,0 +1,59 @@
+use crate::game_boy::components::cartridge::header::CartridgeHeader;
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::types::CartridgeType;
+
+#[test]
+fn test_rom_builder_header() {
+    let rom = RomBuilder::new()
+        .title("TEST")
+        .program(&[0x18, 0xFE])
+        .build();
+    let header = CartridgeHeader::parse(&rom).unwrap();
+
+    assert_eq!(rom.len(), 0x8000);
+    assert!(header.valid_nintendo_logo);
+    assert_eq!(header.title, "TEST");
+    assert_eq!(header.cartridge_type, CartridgeType::RomOnly);
+    assert_eq!(header.rom_size, 2);
+    assert_eq!(
+        header.entry_point,
+        ["[0x00] No Operation", "[0xC3] Jump to address 0x0150"]
+    );
+    assert_eq!(&rom[0x150..0x152], &[0x18, 0xFE]);
+
+    let header_checksum = rom[0x134..0x14D]
+        .iter()
+        .fold(0u8, |checksum, &byte| checksum.wrapping_sub(byte + 1));
+    assert_eq!(header.header_checksum, header_checksum);
+    let global_checksum = rom
+        .iter()
+        .fold(0u16, |checksum, &byte| checksum.wrapping_add(byte as u16))
+        .wrapping_sub(rom[0x14E] as u16 + rom[0x14F] as u16);
+    assert_eq!(header.global_checksum, global_checksum);
+}
+
+#[test]
+fn test_rom_builder_title_is_cut_off() {
+    let rom = RomBuilder::new()
+        .title("A TITLE TOO LONG FOR THE HEADER")
+        .build();
+    assert_eq!(
+        CartridgeHeader::parse(&rom).unwrap().title,
+        "A TITLE TOO LON"
+    );
+    assert_eq!(rom[0x143], 0x00);
+}
+
+#[test]
+fn test_compute_checksums() {
+    let rom = RomBuilder::new().title("CHECKSUMS").build();
+    let header = CartridgeHeader::parse(&rom).unwrap();
+    assert_eq!(
+        CartridgeHeader::compute_header_checksum(&rom),
+        header.header_checksum
+    );
+    assert_eq!(
+        CartridgeHeader::compute_global_checksum(&rom),
+        header.global_checksum
+    );
+}
@@ -1,21 +1,25 @@
 use crate::game_boy::components::cartridge::Cartridge;
 use crate::game_boy::GameBoy;
 use crate::tests::setup_test_dir;
+use crate::tests::test_roms::test_screenshots::capture_screenshot;
 use std::fs::File;
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::path::PathBuf;
 
-mod test_cpu_instrs;
-mod test_instr_timing;
+mod test_conformance;
+mod test_fixtures;
+pub mod test_screenshots;
 
+/// The test ROMs are shared with the frontend in the workspace root
 pub fn test_rom_file_path() -> PathBuf {
-    PathBuf::from("./test_roms")
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../test_roms")
 }
 
 pub fn test_run_game_boy(rom_path: &Path, max_steps: u32) -> GameBoy {
     let path = PathBuf::from(rom_path);
     let cartridge = Cartridge::load(path).unwrap();
-    let mut game_boy = GameBoy::initialize(&cartridge);
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
 
     for _ in 0..max_steps {
         game_boy.step();
@@ -24,6 +28,7 @@ pub fn test_run_game_boy(rom_path: &Path, max_steps: u32) -> GameBoy {
     game_boy
 }
 
+#[allow(dead_code)]
 pub fn run_and_dump(rom_path: &Path, max_steps: u32, output_directory: &Path) {
     let image_dump_path = output_directory
         .join(rom_path.file_name().unwrap())
@@ -33,7 +38,7 @@ pub fn run_and_dump(rom_path: &Path, max_steps: u32, output_directory: &Path) {
         .with_extension("bin");
 
     let game_boy = test_run_game_boy(rom_path, max_steps);
-    let frame_image = game_boy.render_image(1.0);
+    let frame_image = capture_screenshot(&game_boy);
     frame_image.save(image_dump_path).unwrap();
 
     let frame_buffer = game_boy.get_frame_buffer();
@@ -41,8 +46,9 @@ pub fn run_and_dump(rom_path: &Path, max_steps: u32, output_directory: &Path) {
     file.write_all(frame_buffer).unwrap();
 }
 
+#[allow(dead_code)]
 fn run_and_dump_example() {
-    let rom_path = PathBuf::from("./test_roms/cpu_instrs.gb");
+    let rom_path = test_rom_file_path().join("cpu_instrs.gb");
     let test_dir = setup_test_dir();
     run_and_dump(&rom_path, 25_000_000, &test_dir);
 }
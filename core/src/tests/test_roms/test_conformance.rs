@@ -0,0 +1,280 @@
+//! Runs every test ROM of the suite on its own thread with its own [`GameBoy`], so the multi-second ROMs don't queue up.
+//! A progress bar on stderr shows which ROMs finished, failures are collected into one report instead of stopping at the first.
+
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::GameBoy;
+use crate::tests::test_roms::test_rom_file_path;
+use crate::tests::test_roms::test_screenshots::{check_screenshot, Tolerance};
+use std::any::Any;
+use std::io::Write;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CPU_INSTRS_FRAME_BUFFER: &[u8] =
+    include_bytes!("../../../../test_roms/reference_data/cpu_instrs.bin");
+const INSTR_TIMING_FRAME_BUFFER: &[u8] =
+    include_bytes!("../../../../test_roms/reference_data/instr_timing.bin");
+const PROGRESS_BAR_WIDTH: usize = 20;
+const FETCH_COMMAND: &str =
+    "cargo test -p lemon-gb-core --features fetch-test-roms fetch_test_roms -- --ignored";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RunLength {
+    Steps(u32),
+    Frames(u32),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Expectation {
+    /// The raw frame buffer has to match exactly
+    FrameBuffer(&'static [u8]),
+    /// The screen has to match the reference PNG of the same name
+    Screenshot(Tolerance),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ConformanceCase {
+    /// File name inside the test ROM directory
+    pub rom: &'static str,
+    pub length: RunLength,
+    pub expectation: Expectation,
+}
+
+impl ConformanceCase {
+    pub const fn new(rom: &'static str, length: RunLength, expectation: Expectation) -> Self {
+        Self {
+            rom,
+            length,
+            expectation,
+        }
+    }
+
+    fn run(&self) -> Result<(), String> {
+        let cartridge = Cartridge::load(test_rom_file_path().join(self.rom))
+            .map_err(|e| format!("{e}, fetch missing test ROMs with `{FETCH_COMMAND}`"))?;
+        let mut game_boy = GameBoy::initialize(&cartridge).map_err(|e| e.to_string())?;
+        match self.length {
+            RunLength::Steps(steps) => {
+                for _ in 0..steps {
+                    game_boy.step();
+                }
+            }
+            RunLength::Frames(frames) => {
+                for _ in 0..frames {
+                    game_boy.finish_frame();
+                }
+            }
+        }
+
+        match self.expectation {
+            Expectation::FrameBuffer(reference) => {
+                let differing = game_boy
+                    .get_frame_buffer()
+                    .iter()
+                    .zip(reference)
+                    .filter(|(actual, expected)| actual != expected)
+                    .count();
+                if differing == 0 && game_boy.get_frame_buffer().len() == reference.len() {
+                    Ok(())
+                } else {
+                    Err(format!("{differing} bytes of the frame buffer differ"))
+                }
+            }
+            Expectation::Screenshot(tolerance) => check_screenshot(self.rom, &game_boy, tolerance),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseResult {
+    pub rom: &'static str,
+    pub duration: Duration,
+    pub outcome: Result<(), String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceReport {
+    /// In the order of the cases, not in the order they finished
+    pub results: Vec<CaseResult>,
+    pub duration: Duration,
+}
+
+impl ConformanceReport {
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.outcome.is_ok())
+    }
+
+    pub fn summary(&self) -> String {
+        let passed = self
+            .results
+            .iter()
+            .filter(|result| result.outcome.is_ok())
+            .count();
+        let mut lines = vec![format!(
+            "{passed}/{} test ROMs passed in {:.1}s",
+            self.results.len(),
+            self.duration.as_secs_f32()
+        )];
+        for result in &self.results {
+            let seconds = result.duration.as_secs_f32();
+            lines.push(match &result.outcome {
+                Ok(()) => format!("  PASS {} ({seconds:.1}s)", result.rom),
+                Err(error) => format!("  FAIL {} ({seconds:.1}s): {error}", result.rom),
+            });
+        }
+        lines.join("\n")
+    }
+}
+
+/// E.g. `[##########----------] 2/4 cpu_instrs.gb`
+pub fn progress_line(finished: usize, total: usize, rom: &str) -> String {
+    let filled = (finished * PROGRESS_BAR_WIDTH)
+        .checked_div(total)
+        .unwrap_or(0);
+    format!(
+        "[{}{}] {finished}/{total} {rom}",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+/// Runs all cases in parallel and waits for every one of them, a panicking case counts as a failure
+pub fn run_conformance(cases: &[ConformanceCase]) -> ConformanceReport {
+    let start = Instant::now();
+    let finished = Mutex::new(0);
+    let results = thread::scope(|scope| {
+        let handles: Vec<_> = cases
+            .iter()
+            .map(|case| {
+                let finished = &finished;
+                scope.spawn(move || {
+                    let case_start = Instant::now();
+                    let outcome = case.run();
+                    let mut finished = finished.lock().unwrap();
+                    *finished += 1;
+                    eprint!("\r{}", progress_line(*finished, cases.len(), case.rom));
+                    let _ = std::io::stderr().flush();
+                    (case_start.elapsed(), outcome)
+                })
+            })
+            .collect();
+
+        cases
+            .iter()
+            .zip(handles)
+            .map(|(case, handle)| {
+                let (duration, outcome) = handle
+                    .join()
+                    .unwrap_or_else(|panic| (Duration::ZERO, Err(panic_message(panic))));
+                CaseResult {
+                    rom: case.rom,
+                    duration,
+                    outcome,
+                }
+            })
+            .collect()
+    });
+    eprintln!();
+
+    ConformanceReport {
+        results,
+        duration: start.elapsed(),
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    format!("Panicked: {message}")
+}
+
+pub fn conformance_suite() -> Vec<ConformanceCase> {
+    vec![
+        ConformanceCase::new(
+            "cpu_instrs.gb",
+            RunLength::Steps(25_000_000),
+            Expectation::FrameBuffer(CPU_INSTRS_FRAME_BUFFER),
+        ),
+        ConformanceCase::new(
+            "instr_timing.gb",
+            RunLength::Steps(500_000),
+            Expectation::FrameBuffer(INSTR_TIMING_FRAME_BUFFER),
+        ),
+        ConformanceCase::new(
+            "instr_timing.gb",
+            RunLength::Frames(100),
+            Expectation::Screenshot(Tolerance::EXACT),
+        ),
+        ConformanceCase::new(
+            "dmg-acid2.gb",
+            RunLength::Frames(10),
+            Expectation::Screenshot(Tolerance::EXACT),
+        ),
+    ]
+}
+
+#[test]
+fn test_conformance_suite() {
+    let report = run_conformance(&conformance_suite());
+    assert!(report.is_success(), "{}", report.summary());
+}
+
+#[test]
+fn test_game_boy_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<GameBoy>();
+}
+
+#[test]
+fn test_conformance_collects_failures() {
+    let cases = [
+        ConformanceCase::new(
+            "instr_timing.gb",
+            RunLength::Steps(1000),
+            Expectation::FrameBuffer(INSTR_TIMING_FRAME_BUFFER),
+        ),
+        ConformanceCase::new(
+            "missing.gb",
+            RunLength::Frames(1),
+            Expectation::FrameBuffer(&[]),
+        ),
+    ];
+    let report = run_conformance(&cases);
+
+    assert!(!report.is_success());
+    assert_eq!(report.results.len(), 2);
+    assert_eq!(report.results[0].rom, "instr_timing.gb");
+    assert!(report.results[0]
+        .outcome
+        .as_ref()
+        .is_err_and(|error| error.ends_with("bytes of the frame buffer differ")));
+    assert!(report.results[1].outcome.is_err());
+
+    let summary = report.summary();
+    assert!(summary.starts_with("0/2 test ROMs passed"));
+    assert!(summary.contains("  FAIL missing.gb"));
+}
+
+#[test]
+fn test_progress_line() {
+    assert_eq!(
+        progress_line(0, 4, "a.gb"),
+        "[--------------------] 0/4 a.gb"
+    );
+    assert_eq!(
+        progress_line(2, 4, "a.gb"),
+        "[##########----------] 2/4 a.gb"
+    );
+    assert_eq!(
+        progress_line(4, 4, "a.gb"),
+        "[####################] 4/4 a.gb"
+    );
+    assert_eq!(
+        progress_line(0, 0, "a.gb"),
+        "[--------------------] 0/0 a.gb"
+    );
+}
@@ -0,0 +1,224 @@
+//! The third-party test ROMs are listed in `test_roms/fixtures.toml` with their source and SHA-256 hash,
+//! so the conformance suite always runs against the same binaries.
+//! Fetch missing ones with `cargo test -p lemon-gb-core --features fetch-test-roms fetch_test_roms -- --ignored`,
+//! which needs `curl` and, for zip archives, `unzip`.
+
+use crate::tests::test_roms::test_conformance::conformance_suite;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+use serde::Deserialize;
+use std::error::Error;
+use std::path::Path;
+
+const MANIFEST_FILE: &str = "fixtures.toml";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fixture {
+    /// File name inside the test ROM directory
+    pub file: String,
+    pub url: String,
+    /// Path of the ROM inside the zip archive at the URL, None if the URL is the ROM itself
+    pub member: Option<String>,
+    /// Lower case hex
+    pub sha256: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    fixture: Vec<Fixture>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixtureStatus {
+    Verified,
+    Missing,
+    /// With the hash of the file which is there instead
+    Mismatch(String),
+}
+
+pub fn read_fixtures() -> Result<Vec<Fixture>, Box<dyn Error>> {
+    let manifest = std::fs::read_to_string(test_rom_file_path().join(MANIFEST_FILE))?;
+    Ok(toml::from_str::<Manifest>(&manifest)?.fixture)
+}
+
+pub fn check_fixture(fixture: &Fixture, directory: &Path) -> Result<FixtureStatus, Box<dyn Error>> {
+    let path = directory.join(&fixture.file);
+    if !path.exists() {
+        return Ok(FixtureStatus::Missing);
+    }
+    let hash = sha256_hex(&std::fs::read(path)?);
+    if hash == fixture.sha256 {
+        Ok(FixtureStatus::Verified)
+    } else {
+        Ok(FixtureStatus::Mismatch(hash))
+    }
+}
+
+/// Downloads the ROM and only writes it into the directory if its hash matches
+#[cfg(feature = "fetch-test-roms")]
+pub fn fetch_fixture(fixture: &Fixture, directory: &Path) -> Result<(), Box<dyn Error>> {
+    use std::process::Command;
+
+    let run = |command: &mut Command| -> Result<Vec<u8>, Box<dyn Error>> {
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).into_owned().into());
+        }
+        Ok(output.stdout)
+    };
+    let download = run(Command::new("curl").args([
+        "--fail",
+        "--silent",
+        "--show-error",
+        "--location",
+        &fixture.url,
+    ]))
+    .map_err(|e| format!("Failed to download {}: {e}", fixture.url))?;
+    let rom = match &fixture.member {
+        Some(member) => {
+            let archive = directory.join(format!("{}.zip", fixture.file));
+            std::fs::write(&archive, download)?;
+            let extracted = run(Command::new("unzip").arg("-p").arg(&archive).arg(member));
+            std::fs::remove_file(&archive)?;
+            extracted
+                .map_err(|e| format!("Failed to extract {member} from {}: {e}", fixture.url))?
+        }
+        None => download,
+    };
+
+    let hash = sha256_hex(&rom);
+    if hash != fixture.sha256 {
+        return Err(format!(
+            "{} from {} has the SHA-256 hash {hash} instead of {}",
+            fixture.file, fixture.url, fixture.sha256
+        )
+        .into());
+    }
+    Ok(std::fs::write(directory.join(&fixture.file), rom)?)
+}
+
+/// SHA-256 as lower case hex, https://en.wikipedia.org/wiki/SHA-2#Pseudocode
+pub fn sha256_hex(data: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut hash: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    // A single 1 bit, zeros up to 8 bytes before the end of a block and the length in bits
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (index, word) in block.chunks_exact(4).enumerate() {
+            w[index] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = hash;
+        for (k, w) in K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+        for (value, added) in hash.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *value = value.wrapping_add(added);
+        }
+    }
+
+    hash.iter().map(|value| format!("{value:08x}")).collect()
+}
+
+#[rstest]
+#[case::empty(
+    b"",
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+)]
+#[case::abc(
+    b"abc",
+    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+)]
+// Padding needs a second block
+#[case::two_blocks(
+    b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+    "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+)]
+fn test_sha256(#[case] data: &[u8], #[case] expected: &str) {
+    assert_eq!(sha256_hex(data), expected);
+}
+
+#[test]
+fn test_manifest_covers_conformance_suite() {
+    let fixtures = read_fixtures().unwrap();
+    for case in conformance_suite() {
+        assert!(
+            fixtures.iter().any(|fixture| fixture.file == case.rom),
+            "{} is missing from {MANIFEST_FILE}",
+            case.rom
+        );
+    }
+}
+
+/// ROMs which aren't there are skipped, the conformance suite reports them
+#[test]
+fn test_present_fixtures_match_their_hashes() {
+    for fixture in read_fixtures().unwrap() {
+        let status = check_fixture(&fixture, &test_rom_file_path()).unwrap();
+        if let FixtureStatus::Mismatch(hash) = status {
+            panic!(
+                "{} has the SHA-256 hash {hash} instead of {}",
+                fixture.file, fixture.sha256
+            );
+        }
+    }
+}
+
+#[cfg(feature = "fetch-test-roms")]
+#[test]
+#[ignore = "downloads the test ROMs, see module docs"]
+fn fetch_test_roms() {
+    let directory = test_rom_file_path();
+    for fixture in read_fixtures().unwrap() {
+        if check_fixture(&fixture, &directory).unwrap() != FixtureStatus::Verified {
+            fetch_fixture(&fixture, &directory).unwrap();
+        }
+    }
+}
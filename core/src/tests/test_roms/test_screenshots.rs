@@ -0,0 +1,171 @@
+//! Compares the screen of test ROMs against reference PNGs in `test_roms/reference_data`, the ROMs run in the conformance suite.
+//! Set `LEMON_GB_UPDATE_SCREENSHOTS=1` to write the current screens as the new references instead.
+
+use crate::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::game_boy::GameBoy;
+use crate::tests::setup_test_dir;
+use crate::tests::test_roms::test_rom_file_path;
+use image::{Rgba, RgbaImage};
+use std::path::PathBuf;
+
+const UPDATE_ENV_VAR: &str = "LEMON_GB_UPDATE_SCREENSHOTS";
+/// Matching pixels are dimmed in the diff image, so the differing red ones stand out
+const DIFF_DIM_FACTOR: u8 = 4;
+const DIFF_COLOR: Rgba<u8> = Rgba([0xFF, 0x00, 0x00, 0xFF]);
+
+/// How far a screenshot may be off from its reference
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tolerance {
+    /// The largest difference of a color channel at which two pixels still match
+    pub channel: u8,
+    /// How many pixels may differ
+    pub pixels: usize,
+}
+
+impl Tolerance {
+    pub const EXACT: Tolerance = Tolerance {
+        channel: 0,
+        pixels: 0,
+    };
+}
+
+fn reference_path(name: &str) -> PathBuf {
+    test_rom_file_path()
+        .join("reference_data")
+        .join(name)
+        .with_extension("png")
+}
+
+pub fn capture_screenshot(game_boy: &GameBoy) -> RgbaImage {
+    RgbaImage::from_raw(
+        SCREEN_WIDTH as u32,
+        SCREEN_HEIGHT as u32,
+        game_boy.get_rgba_frame_buffer(),
+    )
+    .unwrap()
+}
+
+/// The pixels which differ by more than the channel tolerance, drawn red on top of the dimmed reference
+pub fn compare_screenshots(
+    actual: &RgbaImage,
+    reference: &RgbaImage,
+    tolerance: Tolerance,
+) -> (usize, RgbaImage) {
+    let mut diff = RgbaImage::new(reference.width(), reference.height());
+    let mut differing = 0;
+    for (x, y, reference_pixel) in reference.enumerate_pixels() {
+        let matches = actual.get_pixel_checked(x, y).is_some_and(|actual_pixel| {
+            actual_pixel
+                .0
+                .iter()
+                .zip(reference_pixel.0)
+                .all(|(a, b)| a.abs_diff(b) <= tolerance.channel)
+        });
+
+        if matches {
+            let [r, g, b, a] = reference_pixel.0;
+            diff.put_pixel(
+                x,
+                y,
+                Rgba([
+                    r / DIFF_DIM_FACTOR,
+                    g / DIFF_DIM_FACTOR,
+                    b / DIFF_DIM_FACTOR,
+                    a,
+                ]),
+            );
+        } else {
+            differing += 1;
+            diff.put_pixel(x, y, DIFF_COLOR);
+        }
+    }
+    (differing, diff)
+}
+
+/// Compares the current screen against the reference of the same name.
+/// On failure the actual screen and a diff image are written to the test directory.
+pub fn check_screenshot(
+    rom_name: &str,
+    game_boy: &GameBoy,
+    tolerance: Tolerance,
+) -> Result<(), String> {
+    let actual = capture_screenshot(game_boy);
+
+    let reference_path = reference_path(rom_name);
+    if std::env::var(UPDATE_ENV_VAR).is_ok_and(|value| value == "1") {
+        return actual
+            .save(&reference_path)
+            .map_err(|error| error.to_string());
+    }
+
+    let reference = image::open(&reference_path)
+        .map_err(|error| {
+            format!(
+                "Missing reference {}, create it with {UPDATE_ENV_VAR}=1: {error}",
+                reference_path.display()
+            )
+        })?
+        .to_rgba8();
+    if reference.dimensions() != (SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32) {
+        return Err(format!(
+            "Reference {} has the wrong size {:?}",
+            reference_path.display(),
+            reference.dimensions()
+        ));
+    }
+
+    let (differing, diff) = compare_screenshots(&actual, &reference, tolerance);
+    if differing <= tolerance.pixels {
+        return Ok(());
+    }
+
+    let test_dir = setup_test_dir();
+    let actual_path = test_dir.join(rom_name).with_extension("actual.png");
+    let diff_path = test_dir.join(rom_name).with_extension("diff.png");
+    actual
+        .save(&actual_path)
+        .map_err(|error| error.to_string())?;
+    diff.save(&diff_path).map_err(|error| error.to_string())?;
+    Err(format!(
+        "{rom_name}: {differing} pixels differ from {} (tolerance {tolerance:?}), see {} and {}",
+        reference_path.display(),
+        actual_path.display(),
+        diff_path.display()
+    ))
+}
+
+fn solid_image(color: [u8; 4]) -> RgbaImage {
+    RgbaImage::from_pixel(SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32, Rgba(color))
+}
+
+#[test]
+fn test_compare_identical_screenshots() {
+    let image = solid_image([0x10, 0x20, 0x30, 0xFF]);
+    let (differing, _) = compare_screenshots(&image, &image, Tolerance::EXACT);
+    assert_eq!(differing, 0);
+}
+
+#[test]
+fn test_compare_screenshots_channel_tolerance() {
+    let reference = solid_image([0x10, 0x20, 0x30, 0xFF]);
+    let mut actual = reference.clone();
+    actual.put_pixel(0, 0, Rgba([0x12, 0x20, 0x30, 0xFF]));
+    actual.put_pixel(5, 7, Rgba([0x10, 0x20, 0x40, 0xFF]));
+
+    let tolerance = Tolerance {
+        channel: 2,
+        pixels: 0,
+    };
+    let (differing, diff) = compare_screenshots(&actual, &reference, tolerance);
+    assert_eq!(differing, 1);
+    assert_eq!(*diff.get_pixel(5, 7), DIFF_COLOR);
+    assert_eq!(*diff.get_pixel(0, 0), Rgba([0x04, 0x08, 0x0C, 0xFF]));
+}
+
+#[test]
+fn test_compare_screenshots_of_other_size() {
+    let reference = solid_image([0x00, 0x00, 0x00, 0xFF]);
+    let actual = RgbaImage::from_pixel(10, 10, Rgba([0x00, 0x00, 0x00, 0xFF]));
+    let (differing, _) = compare_screenshots(&actual, &reference, Tolerance::EXACT);
+    assert_eq!(differing, SCREEN_WIDTH * SCREEN_HEIGHT - 100);
+}
@@ -0,0 +1,81 @@
+use crate::game_boy::sample_buffer::{sample_buffer, SampleBufferStats};
+use std::thread;
+
+#[test]
+fn test_samples_come_out_in_order() {
+    let (mut producer, mut consumer) = sample_buffer(4);
+    assert!(consumer.is_empty());
+    assert_eq!(producer.push_slice(&[0.25, -0.5, 1.0]), 3);
+    assert_eq!(consumer.len(), 3);
+
+    assert_eq!(consumer.pop(), Some(0.25));
+    assert_eq!(consumer.pop(), Some(-0.5));
+    assert_eq!(consumer.pop(), Some(1.0));
+    assert_eq!(consumer.pop(), None);
+    assert_eq!(producer.get_stats(), SampleBufferStats::default());
+}
+
+/// A full buffer drops the new samples, the ones already in it are played
+#[test]
+fn test_overruns_drop_new_samples() {
+    let (mut producer, mut consumer) = sample_buffer(2);
+    assert_eq!(producer.push_slice(&[0.1, 0.2, 0.3, 0.4]), 2);
+    assert!(!producer.push(0.5));
+    assert_eq!(producer.get_stats().overruns, 3);
+
+    assert_eq!(consumer.pop(), Some(0.1));
+    assert!(producer.push(0.6));
+    assert_eq!(consumer.pop(), Some(0.2));
+    assert_eq!(consumer.pop(), Some(0.6));
+}
+
+/// A callback which runs out of samples plays silence for the rest and counts one underrun
+#[test]
+fn test_underruns_fill_with_silence() {
+    let (mut producer, mut consumer) = sample_buffer(8);
+    producer.push_slice(&[0.5, 0.5]);
+
+    let mut output = [1.0; 4];
+    assert_eq!(consumer.fill(&mut output), 2);
+    assert_eq!(output, [0.5, 0.5, 0.0, 0.0]);
+    assert_eq!(consumer.fill(&mut output), 0);
+    assert_eq!(output, [0.0; 4]);
+    assert_eq!(
+        consumer.get_stats(),
+        SampleBufferStats {
+            underruns: 2,
+            overruns: 0
+        }
+    );
+
+    producer.push_slice(&[0.1; 4]);
+    assert_eq!(consumer.fill(&mut output), 4);
+    assert_eq!(consumer.get_stats().underruns, 2);
+}
+
+/// Every sample pushed from one thread arrives in order on the other, wrapping around the buffer many times
+#[test]
+fn test_samples_cross_threads() {
+    const SAMPLES: u32 = 100_000;
+    let (mut producer, mut consumer) = sample_buffer(64);
+    let writer = thread::spawn(move || {
+        for sample in 0..SAMPLES {
+            while !producer.push(sample as f32) {
+                thread::yield_now();
+            }
+        }
+    });
+
+    let mut expected = 0;
+    while expected < SAMPLES {
+        match consumer.pop() {
+            Some(sample) => {
+                assert_eq!(sample, expected as f32);
+                expected += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    writer.join().unwrap();
+    assert!(consumer.is_empty());
+}
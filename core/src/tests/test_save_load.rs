@@ -0,0 +1,276 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::ppu::frame_buffer_format::FrameBufferFormat;
+use crate::game_boy::components::ppu::mode::PPUMode;
+use crate::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::game_boy::config::GameBoyConfig;
+use crate::game_boy::save_state::{GameBoySaveState, SAVE_STATE_SIZE_BUDGET};
+use crate::game_boy::GameBoy;
+use crate::tests::setup_test_dir;
+use crate::tests::test_roms::test_rom_file_path;
+use rstest::rstest;
+
+#[test]
+fn test_save_load() {
+    let test_rom_path = test_rom_file_path().join("cpu_instrs.gb");
+    let test_dir = setup_test_dir();
+    let save_path_json = test_dir.join("test.json");
+    let save_path_bin = test_dir.join("test.bin");
+    let cartridge = Cartridge::load(test_rom_path).unwrap();
+
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    // Stop in the middle of a frame, after the test ROM printed something over serial
+    for _ in 0..1_000_123 {
+        game_boy.step();
+    }
+
+    let save_state = game_boy.save();
+    save_state.store_json(&save_path_json).unwrap();
+    save_state.store_binary(&save_path_bin).unwrap();
+
+    let save_state_json = GameBoySaveState::load_json(&save_path_json).unwrap();
+    let save_state_bin = GameBoySaveState::load_binary(&save_path_bin).unwrap();
+
+    let game_boy_json = GameBoy::load(save_state_json, &cartridge).unwrap();
+    let game_boy_bin = GameBoy::load(save_state_bin, &cartridge).unwrap();
+
+    assert_eq!(game_boy_json, game_boy_bin);
+    assert_eq!(game_boy, game_boy_bin);
+}
+
+#[test]
+fn test_load_mid_frame_continues_identically() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..500_057 {
+        game_boy.step();
+    }
+
+    let mut loaded = GameBoy::load(game_boy.save(), &cartridge).unwrap();
+    assert_eq!(loaded, game_boy);
+
+    for _ in 0..100_000 {
+        game_boy.step();
+        loaded.step();
+    }
+    assert_eq!(loaded.get_frame_buffer(), game_boy.get_frame_buffer());
+    assert_eq!(loaded.get_serial_output(), game_boy.get_serial_output());
+    assert_eq!(loaded, game_boy);
+}
+
+#[test]
+fn test_reset_matches_fresh_game_boy() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..500_057 {
+        game_boy.step();
+    }
+    assert!(!game_boy.get_serial_output().is_empty());
+
+    game_boy.reset();
+    let mut fresh = GameBoy::initialize(&cartridge).unwrap();
+    assert_eq!(game_boy.save(), fresh.save());
+
+    for _ in 0..100_000 {
+        game_boy.step();
+        fresh.step();
+    }
+    assert_eq!(game_boy, fresh);
+}
+
+#[test]
+fn test_load_with_other_frame_buffer_format() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    game_boy.finish_frame();
+
+    let config = GameBoyConfig::default().frame_buffer_format(FrameBufferFormat::Indexed);
+    let loaded = GameBoy::load_with_config(game_boy.save(), &cartridge, config).unwrap();
+    assert_eq!(loaded.get_frame_buffer_format(), FrameBufferFormat::Indexed);
+    assert_eq!(
+        loaded.get_frame_buffer().len(),
+        SCREEN_WIDTH * SCREEN_HEIGHT
+    );
+}
+
+/// States the PPU could never reach are rejected instead of running LY past the last line
+#[rstest]
+#[case::line_out_of_range(PPUMode::VBlank, 154, 0)]
+#[case::drawing_in_vblank_lines(PPUMode::HBlank, 150, 0)]
+#[case::clock_overflow(PPUMode::OAMSearch, 10, u32::MAX)]
+fn test_load_rejects_invalid_ppu_state(
+    #[case] mode: PPUMode,
+    #[case] line: u8,
+    #[case] mode_clock: u32,
+) {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut state = GameBoy::initialize(&cartridge).unwrap().save();
+    state.ppu_state.mode = mode;
+    state.ppu_state.current_line = line;
+    state.ppu_state.mode_clock = mode_clock;
+
+    assert!(GameBoy::load(state, &cartridge).is_err());
+}
+
+#[rstest]
+#[case::shorter_than_possible(171)]
+#[case::longer_than_the_line(377)]
+fn test_load_rejects_invalid_pixel_transfer_length(#[case] dots: u32) {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut state = GameBoy::initialize(&cartridge).unwrap().save();
+    state.ppu_state.pixel_transfer_dots = dots;
+
+    assert!(GameBoy::load(state, &cartridge).is_err());
+}
+
+#[test]
+fn test_binary_save_state_round_trip() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..250_031 {
+        game_boy.step();
+    }
+
+    let bytes = game_boy.save().to_bytes().unwrap();
+    assert!(bytes.len() < SAVE_STATE_SIZE_BUDGET);
+
+    let loaded = GameBoy::load(GameBoySaveState::from_bytes(&bytes).unwrap(), &cartridge).unwrap();
+    assert_eq!(loaded, game_boy);
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_compressed_save_state_within_budget() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..1_000_123 {
+        game_boy.step();
+    }
+
+    let save_state = game_boy.save();
+    let compressed = save_state.to_compressed_bytes().unwrap();
+    assert!(compressed.len() < SAVE_STATE_SIZE_BUDGET);
+    assert!(compressed.len() < save_state.to_bytes().unwrap().len());
+
+    let save_path = setup_test_dir().join("test.bin.zst");
+    save_state.store_compressed(&save_path).unwrap();
+    let loaded = GameBoySaveState::load_compressed(&save_path).unwrap();
+    assert_eq!(GameBoy::load(loaded, &cartridge).unwrap(), game_boy);
+}
+
+#[test]
+fn test_load_state_checks_cartridge() {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..100_000 {
+        game_boy.step();
+    }
+    let state = game_boy.save();
+
+    let other_cartridge = Cartridge::from_bytes(&RomBuilder::new().title("OTHER").build()).unwrap();
+    let mut other = GameBoy::initialize(&other_cartridge).unwrap();
+    let error = other.load_state(state.clone()).unwrap_err().to_string();
+    assert!(error.contains("CPU_INSTRS"), "{error}");
+    assert!(error.contains("OTHER"), "{error}");
+    assert_eq!(other, GameBoy::initialize(&other_cartridge).unwrap());
+
+    let mut same = GameBoy::initialize(&cartridge).unwrap();
+    same.load_state(state).unwrap();
+    assert_eq!(same, game_boy);
+}
+
+fn saved_state() -> GameBoySaveState {
+    let cartridge = Cartridge::load(test_rom_file_path().join("cpu_instrs.gb")).unwrap();
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for _ in 0..10_000 {
+        game_boy.step();
+    }
+    game_boy.save()
+}
+
+#[rstest]
+#[case::json("any.json", GameBoySaveState::store_json)]
+#[case::binary("any.bin", GameBoySaveState::store_binary)]
+fn test_load_any_detects_format(
+    #[case] file_name: &str,
+    #[case] store: fn(&GameBoySaveState, &std::path::Path) -> std::io::Result<()>,
+) {
+    let path = setup_test_dir().join(file_name);
+    let state = saved_state();
+    store(&state, &path).unwrap();
+    assert_eq!(GameBoySaveState::load_any(&path).unwrap(), state);
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_load_any_detects_compressed() {
+    let path = setup_test_dir().join("any.zst");
+    let state = saved_state();
+    state.store_compressed(&path).unwrap();
+    assert_eq!(GameBoySaveState::load_any(&path).unwrap(), state);
+}
+
+/// 128 KiB of ROM where every bank starts with its own index, and 32 KiB of RAM if the mapper has any
+fn build_mapper_cartridge(cartridge_type: u8) -> Cartridge {
+    let mut rom = vec![0u8; 8 * 0x4000];
+    for bank in 0..8 {
+        rom[bank * 0x4000] = bank as u8;
+    }
+    rom[0x147] = cartridge_type;
+    rom[0x148] = 0x02; // 128 KiB ROM
+    rom[0x149] = if cartridge_type == 0x00 { 0x02 } else { 0x03 };
+    Cartridge::from_bytes(&rom).unwrap()
+}
+
+/// Every mapper register differs from its power on value, so nothing falls back to a default on load
+#[rstest]
+#[case::no_mbc(0x00, &[])]
+#[case::mbc1_simple_mode(0x03, &[(0x0000, 0x0A), (0x2000, 0x05), (0x4000, 0x01)])]
+#[case::mbc1_advanced_mode(0x03, &[(0x0000, 0x0A), (0x2000, 0x03), (0x4000, 0x02), (0x6000, 0x01)])]
+#[case::mbc1_ram_disabled(0x03, &[(0x2000, 0x00), (0x6000, 0x01)])]
+#[case::mbc5(0x1B, &[(0x0000, 0x0A), (0x2000, 0x06), (0x3000, 0x01), (0x4000, 0x03)])]
+#[case::mbc5_rumble_motor_on(0x1E, &[(0x0000, 0x0A), (0x2000, 0x00), (0x4000, 0x0A)])]
+#[case::camera(0xFC, &[(0x0000, 0x0A), (0x2000, 0x05), (0x4000, 0x02)])]
+#[case::camera_registers(0xFC, &[(0x2000, 0x06), (0x4000, 0x10), (0xA002, 0x12), (0xA006, 0x80)])]
+fn test_mapper_state_round_trip(#[case] cartridge_type: u8, #[case] writes: &[(u16, u8)]) {
+    let cartridge = build_mapper_cartridge(cartridge_type);
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    for &(address, value) in writes {
+        game_boy.write_memory(address, value);
+    }
+    game_boy.write_memory(0xA123, 0x42);
+
+    let state = game_boy.save();
+    let from_json: GameBoySaveState =
+        serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+    let from_bytes = GameBoySaveState::from_bytes(&state.to_bytes().unwrap()).unwrap();
+    for loaded_state in [from_json, from_bytes] {
+        assert_eq!(loaded_state, state);
+        let mut loaded = GameBoy::load(loaded_state, &cartridge).unwrap();
+        assert_eq!(loaded, game_boy);
+        for address in [0x0000, 0x4000, 0xA123] {
+            assert_eq!(loaded.read_memory(address), game_boy.read_memory(address));
+        }
+
+        // The restored registers keep interpreting writes like the original ones
+        let mut original = game_boy.clone();
+        for game_boy in [&mut original, &mut loaded] {
+            game_boy.write_memory(0x4000, 0x00);
+            game_boy.write_memory(0x2000, 0x07);
+        }
+        assert_eq!(loaded, original);
+        assert_eq!(loaded.read_memory(0x4000), original.read_memory(0x4000));
+    }
+}
+
+#[test]
+fn test_load_rejects_state_of_other_mapper() {
+    let state = GameBoy::initialize(&build_mapper_cartridge(0x00))
+        .unwrap()
+        .save();
+    let error = GameBoy::load(state, &build_mapper_cartridge(0x03)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The save state has no MBC registers, but the cartridge uses MBC1"
+    );
+}
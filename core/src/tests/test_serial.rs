@@ -0,0 +1,167 @@
+use crate::game_boy::components::cartridge::rom_builder::RomBuilder;
+use crate::game_boy::components::cartridge::Cartridge;
+use crate::game_boy::components::mmu::{SB_ADDRESS, SC_ADDRESS, SERIAL_OUTPUT_LIMIT};
+use crate::game_boy::components::serial::SerialLink;
+use crate::game_boy::GameBoy;
+use rstest::rstest;
+use std::sync::{Arc, Mutex};
+
+/// Sends "OK" over the serial port, stores 0x42 at 0xC000 and loops forever
+const PROGRAM: [u8; 23] = [
+    0x3E, b'O', 0xE0, 0x01, 0x3E, 0x81, 0xE0,
+    0x02, // LD A, 'O' / LDH (SB), A / LD A, 0x81 / LDH (SC), A
+    0x3E, b'K', 0xE0, 0x01, 0x3E, 0x81, 0xE0,
+    0x02, // LD A, 'K' / LDH (SB), A / LD A, 0x81 / LDH (SC), A
+    0x3E, 0x42, 0xEA, 0x00, 0xC0, // LD A, 0x42 / LD (0xC000), A
+    0x18, 0xFE, // JR -2
+];
+
+fn build_game_boy() -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x103].copy_from_slice(&[0xC3, 0x50, 0x01]); // JP 0x0150
+    rom[0x150..0x150 + PROGRAM.len()].copy_from_slice(&PROGRAM);
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+#[test]
+fn test_serial_output_is_captured() {
+    let mut game_boy = build_game_boy();
+    game_boy.finish_frame();
+    assert_eq!(game_boy.get_serial_output(), b"OK");
+}
+
+#[test]
+fn test_take_serial_output() {
+    let mut game_boy = build_game_boy();
+    for _ in 0..8 {
+        game_boy.step();
+    }
+    assert_eq!(game_boy.take_serial_output(), b"O");
+    game_boy.finish_frame();
+    assert_eq!(game_boy.take_serial_output(), b"K");
+    assert!(game_boy.take_serial_output().is_empty());
+    assert!(game_boy.get_serial_output().is_empty());
+}
+
+/// Once the limit is reached the older half is dropped, also from save states
+#[test]
+fn test_serial_output_keeps_latest_bytes() {
+    let mut game_boy = build_game_boy();
+    for value in 0..=SERIAL_OUTPUT_LIMIT {
+        game_boy.write_memory(SB_ADDRESS, value as u8);
+        game_boy.write_memory(SC_ADDRESS, 0x81);
+    }
+
+    let output = game_boy.get_serial_output();
+    assert_eq!(output.len(), SERIAL_OUTPUT_LIMIT / 2 + 1);
+    assert_eq!(output.last(), Some(&(SERIAL_OUTPUT_LIMIT as u8)));
+    assert_eq!(
+        game_boy.save().mmu_state.serial_output.len(),
+        SERIAL_OUTPUT_LIMIT / 2 + 1
+    );
+}
+
+/// LD A, 0x42 / LDH (SB), A / LD A, 0x81 / LDH (SC), A / JR to itself
+const INTERNAL_CLOCK_PROGRAM: [u8; 10] =
+    [0x3E, 0x42, 0xE0, 0x01, 0x3E, 0x81, 0xE0, 0x02, 0x18, 0xFE];
+/// Like [`INTERNAL_CLOCK_PROGRAM`], but sends 0x24 and waits for the partner's clock
+const EXTERNAL_CLOCK_PROGRAM: [u8; 10] =
+    [0x3E, 0x24, 0xE0, 0x01, 0x3E, 0x80, 0xE0, 0x02, 0x18, 0xFE];
+/// Writes 0x24 to SB without starting a transfer
+const IDLE_PROGRAM: [u8; 6] = [0x3E, 0x24, 0xE0, 0x01, 0x18, 0xFE];
+
+/// Answers every transfer with the same byte and records what it was sent
+#[derive(Default)]
+struct ScriptedLink {
+    answer: Option<u8>,
+    /// Clocked by the partner once
+    incoming: Option<u8>,
+    exchanged: Arc<Mutex<Vec<u8>>>,
+    responses: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SerialLink for ScriptedLink {
+    fn exchange(&mut self, byte: u8) -> Option<u8> {
+        self.exchanged.lock().unwrap().push(byte);
+        self.answer
+    }
+
+    fn poll(&mut self) -> Option<u8> {
+        self.incoming.take()
+    }
+
+    fn respond(&mut self, byte: u8) {
+        self.responses.lock().unwrap().push(byte);
+    }
+}
+
+fn game_boy(program: &[u8]) -> GameBoy {
+    GameBoy::headless(&RomBuilder::new().program(program).build()).unwrap()
+}
+
+#[rstest]
+#[case::answered(Some(0x99), 0x99)]
+#[case::no_answer(None, 0xFF)]
+fn test_internal_clock_exchanges_with_partner(#[case] answer: Option<u8>, #[case] expected: u8) {
+    let mut game_boy = game_boy(&INTERNAL_CLOCK_PROGRAM);
+    let link = ScriptedLink {
+        answer,
+        ..ScriptedLink::default()
+    };
+    let exchanged = link.exchanged.clone();
+    game_boy.connect_link(link);
+    game_boy.finish_frame();
+    assert_eq!(*exchanged.lock().unwrap(), [0x42]);
+    assert_eq!(game_boy.read_memory(SB_ADDRESS), expected);
+    // Transfers with a partner aren't output of a test ROM
+    assert!(game_boy.get_serial_output().is_empty());
+}
+
+#[test]
+fn test_external_clock_answers_partner() {
+    let mut game_boy = game_boy(&EXTERNAL_CLOCK_PROGRAM);
+    game_boy.finish_frame();
+    assert_eq!(game_boy.read_memory(SB_ADDRESS), 0x24);
+
+    let link = ScriptedLink {
+        incoming: Some(0x55),
+        ..ScriptedLink::default()
+    };
+    let responses = link.responses.clone();
+    game_boy.connect_link(link);
+    game_boy.finish_frame();
+    assert_eq!(*responses.lock().unwrap(), [0x24]);
+    assert_eq!(game_boy.read_memory(SB_ADDRESS), 0x55);
+}
+
+#[test]
+fn test_idle_line_without_transfer() {
+    let mut game_boy = game_boy(&IDLE_PROGRAM);
+    let link = ScriptedLink {
+        incoming: Some(0x55),
+        ..ScriptedLink::default()
+    };
+    let responses = link.responses.clone();
+    game_boy.connect_link(link);
+    game_boy.finish_frame();
+    assert_eq!(*responses.lock().unwrap(), [0xFF]);
+    assert_eq!(game_boy.read_memory(SB_ADDRESS), 0x24);
+}
+
+#[test]
+fn test_link_survives_loaded_state() {
+    let mut game_boy = game_boy(&IDLE_PROGRAM);
+    let state = game_boy.save();
+    game_boy.connect_link(ScriptedLink::default());
+    game_boy.load_state(state).unwrap();
+    assert!(game_boy.is_link_connected());
+
+    game_boy.write_memory(SB_ADDRESS, 0x24);
+    game_boy.write_memory(SC_ADDRESS, 0x81);
+    assert!(game_boy.get_serial_output().is_empty());
+
+    game_boy.disconnect_link();
+    assert!(!game_boy.is_link_connected());
+    game_boy.write_memory(SC_ADDRESS, 0x81);
+    assert_eq!(game_boy.get_serial_output(), [0x24]);
+}
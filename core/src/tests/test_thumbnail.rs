@@ -0,0 +1,36 @@
+use crate::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::game_boy::thumbnail::{Thumbnail, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH};
+
+/// Red is 0 on the left half and rises with x on the right half, green and blue are the same everywhere
+fn test_frame() -> Vec<u8> {
+    let mut frame = vec![0; SCREEN_WIDTH * SCREEN_HEIGHT * 4];
+    for y in 0..SCREEN_HEIGHT {
+        for x in 0..SCREEN_WIDTH {
+            let value = if x < SCREEN_WIDTH / 2 { 0 } else { x as u8 };
+            let index = (y * SCREEN_WIDTH + x) * 4;
+            frame[index..index + 4].copy_from_slice(&[value, 0x10, 0xF0, 0xFF]);
+        }
+    }
+    frame
+}
+
+#[test]
+fn test_thumbnail_averages_blocks() {
+    let mut frame = test_frame();
+    // One white pixel of the top left block
+    frame[0..4].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+    let thumbnail = Thumbnail::from_rgba_frame(&frame);
+
+    assert_eq!(thumbnail.get_pixel(0, 0), [0x3F, 0x4B, 0xF3]);
+    assert_eq!(thumbnail.get_pixel(1, 0), [0x00, 0x10, 0xF0]);
+    assert_eq!(thumbnail.get_pixel(50, 30), [100, 0x10, 0xF0]);
+    assert_eq!(
+        thumbnail.get_pixel(THUMBNAIL_WIDTH - 1, THUMBNAIL_HEIGHT - 1),
+        [158, 0x10, 0xF0]
+    );
+    assert_eq!(
+        thumbnail.get_pixel(THUMBNAIL_WIDTH, 0),
+        [0; 3],
+        "Out of bounds"
+    );
+}
@@ -1,6 +1,8 @@
 use crate::game_boy::components::mmu::{DIV_ADDRESS, MMU, TAC_ADDRESS, TIMA_ADDRESS, TMA_ADDRESS};
-use crate::game_boy::components::timer::Timer;
+use crate::game_boy::components::timer::{Timer, TimerOverflowEvent};
+use crate::game_boy::cycles::Cycles;
 use rstest::rstest;
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn test_div_increment() {
@@ -9,11 +11,11 @@ fn test_div_increment() {
 
     // DIV increments every 64 cycles (16 M-cycles)
     for _ in 0..63 {
-        timer.step(1, &mut mmu);
+        timer.step(Cycles::from_m(1), &mut mmu);
         assert_eq!(mmu.read(DIV_ADDRESS), 0);
     }
 
-    timer.step(1, &mut mmu);
+    timer.step(Cycles::from_m(1), &mut mmu);
     assert_eq!(mmu.read(DIV_ADDRESS), 1);
 }
 
@@ -24,7 +26,7 @@ fn test_div_reset() {
 
     // Run enough cycles to get DIV > 0
     for _ in 0..200 {
-        timer.step(1, &mut mmu);
+        timer.step(Cycles::from_m(1), &mut mmu);
     }
 
     let before_reset = mmu.read(DIV_ADDRESS);
@@ -34,7 +36,7 @@ fn test_div_reset() {
     mmu.write(DIV_ADDRESS, 123); // Value doesn't matter
     assert_eq!(mmu.read(DIV_ADDRESS), 0);
 
-    timer.step(1, &mut mmu);
+    timer.step(Cycles::from_m(1), &mut mmu);
     assert_eq!(mmu.read(DIV_ADDRESS), 0);
 }
 
@@ -52,12 +54,12 @@ fn test_tima_increment_rates(#[case] tac: u8, #[case] cycles: u16) {
 
     // Step just before increment
     for _ in 0..(cycles - 1) {
-        timer.step(1, &mut mmu);
+        timer.step(Cycles::from_m(1), &mut mmu);
     }
     assert_eq!(mmu.read(TIMA_ADDRESS), 0);
 
     // Step to trigger increment
-    timer.step(1, &mut mmu);
+    timer.step(Cycles::from_m(1), &mut mmu);
     assert_eq!(mmu.read(TIMA_ADDRESS), 1,);
 }
 
@@ -72,7 +74,7 @@ fn test_tima_overflow() {
     mmu.write(TMA_ADDRESS, 0x42);
 
     // Trigger overflow
-    timer.step(4, &mut mmu);
+    timer.step(Cycles::from_m(4), &mut mmu);
     assert_eq!(mmu.read(TIMA_ADDRESS), 0x42);
 }
 
@@ -86,19 +88,19 @@ fn test_timer_disable_edge_case() {
     mmu.write(TIMA_ADDRESS, 0);
 
     // Run for a few cycles
-    timer.step(2, &mut mmu);
+    timer.step(Cycles::from_m(2), &mut mmu);
     let initial_tima = mmu.read(TIMA_ADDRESS);
 
     // Disable timer by clearing the enable bit
     // According to docs, this can cause one more increment due to the falling edge
     mmu.write(TAC_ADDRESS, 0b001);
-    timer.step(1, &mut mmu);
+    timer.step(Cycles::from_m(1), &mut mmu);
 
     assert_eq!(mmu.read(TIMA_ADDRESS), initial_tima + 1);
 
     // Further cycles should not increment TIMA
     let tima_after_disable = mmu.read(TIMA_ADDRESS);
-    timer.step(100, &mut mmu);
+    timer.step(Cycles::from_m(100), &mut mmu);
     assert_eq!(mmu.read(TIMA_ADDRESS), tima_after_disable);
 }
 
@@ -112,7 +114,7 @@ fn test_div_write_affects_timer() {
     mmu.write(TIMA_ADDRESS, 0);
 
     // Run for a bit to get DIV != 0
-    timer.step(100, &mut mmu);
+    timer.step(Cycles::from_m(100), &mut mmu);
     let initial_tima = mmu.read(TIMA_ADDRESS);
 
     // Writing to DIV resets internal counter, which can trigger TIMA increment
@@ -121,3 +123,59 @@ fn test_div_write_affects_timer() {
     // This might cause a TIMA increment due to falling edge
     assert!(mmu.read(TIMA_ADDRESS) >= initial_tima);
 }
+
+#[rstest]
+#[case::clock_16(0b101, 0xFF, 0)]
+#[case::clock_16(0b101, 0x00, 0)]
+#[case::clock_64(0b110, 0xF0, 37)]
+#[case::clock_256(0b111, 0xFE, 5)]
+#[case::clock_1024(0b100, 0xFD, 300)]
+fn test_cycles_until_overflow(#[case] tac: u8, #[case] tima: u8, #[case] warmup: u16) {
+    let mut timer = Timer::default();
+    let mut mmu = MMU::default();
+    mmu.write(TAC_ADDRESS, tac);
+    for _ in 0..warmup {
+        timer.step(Cycles::from_m(1), &mut mmu);
+    }
+    mmu.write(TIMA_ADDRESS, tima);
+
+    let predicted = timer.cycles_until_overflow(&mmu).unwrap();
+    for _ in 1..predicted.as_m() {
+        assert!(!timer.step(Cycles::from_m(1), &mut mmu));
+    }
+    assert!(timer.step(Cycles::from_m(1), &mut mmu));
+}
+
+#[test]
+fn test_cycles_until_overflow_disabled() {
+    let timer = Timer::default();
+    let mut mmu = MMU::default();
+    mmu.write(TAC_ADDRESS, 0b001);
+    assert_eq!(timer.cycles_until_overflow(&mmu), None);
+}
+
+#[test]
+fn test_overflow_listener() {
+    let mut timer = Timer::default();
+    let mut mmu = MMU::default();
+    mmu.write(TAC_ADDRESS, 0b101);
+    mmu.write(TIMA_ADDRESS, 0xFE);
+    mmu.write(TMA_ADDRESS, 0xFE);
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let id =
+        timer.on_overflow(move |event: &TimerOverflowEvent| recorded.lock().unwrap().push(*event));
+
+    // TIMA increments every 4 M-cycles and overflows on every second increment
+    timer.step(Cycles::from_m(24), &mut mmu);
+    {
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.tma == 0xFE));
+    }
+
+    timer.remove_overflow_listener(id);
+    timer.step(Cycles::from_m(100), &mut mmu);
+    assert_eq!(events.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,17 @@
+//! Run with `cargo test -p lemon-gb-core --features opcode-coverage -- --include-ignored --test-threads=1`.
+//! Tests run one after another in alphabetical order then, so this module has to sort last
+//! to see the opcodes executed by every other test.
+
+use crate::game_boy::components::cpu::opcode_coverage;
+
+#[test]
+#[ignore = "requires the whole suite to run first, see module docs"]
+fn test_all_opcodes_executed() {
+    let (unprefixed, prefixed) = opcode_coverage::missing_opcodes();
+    assert!(
+        unprefixed.is_empty() && prefixed.is_empty(),
+        "{} opcodes were never executed by the test suite:\n{}",
+        unprefixed.len() + prefixed.len(),
+        opcode_coverage::report()
+    );
+}
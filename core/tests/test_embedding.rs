@@ -0,0 +1,76 @@
+//! Smoke test of the embedding API, built like an embedder would against the public API only.
+//! It starts from the example of [`GameBoy::headless`] and covers the frame and button calls around it.
+
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::components::ppu::{
+    RGBA_FRAME_BUFFER_SIZE, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+
+/// Selects the action buttons and copies them to 0xC000 forever, a pressed button reads as 0
+const JOYPAD_PROGRAM: [u8; 11] = [
+    0x3E, 0x10, // LD A, 0x10
+    0xE0, 0x00, // LDH (P1), A
+    0xF0, 0x00, // LDH A, (P1)
+    0xEA, 0x00, 0xC0, // LD (0xC000), A
+    0x18, 0xF5, // JR -11
+];
+
+fn build_game_boy() -> GameBoy {
+    let rom = RomBuilder::new().program(&JOYPAD_PROGRAM).build();
+    GameBoy::headless(&rom).unwrap()
+}
+
+#[test]
+fn test_embedding_example() {
+    let mut game_boy = build_game_boy();
+
+    game_boy.press_button(Button::A);
+    let frame = game_boy.run_frame();
+    assert_eq!(frame.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 4);
+    assert_eq!(game_boy.read_memory(0xC000) & 0x01, 0);
+
+    game_boy.release_button(Button::A);
+    game_boy.run_frame();
+    assert_eq!(game_boy.read_memory(0xC000) & 0x01, 1);
+}
+
+#[test]
+fn test_headless_rejects_invalid_rom() {
+    assert!(GameBoy::headless(&[0x00; 0x10]).is_err());
+}
+
+#[test]
+fn test_run_frame_returns_the_finished_frame() {
+    let mut game_boy = build_game_boy();
+    let mut reference = game_boy.clone();
+    reference.finish_frame();
+
+    assert_eq!(game_boy.run_frame().len(), RGBA_FRAME_BUFFER_SIZE);
+    assert_eq!(game_boy, reference);
+}
+
+#[rstest]
+#[case::a(Button::A, 0b0001)]
+#[case::b(Button::B, 0b0010)]
+#[case::select(Button::Select, 0b0100)]
+#[case::start(Button::Start, 0b1000)]
+fn test_press_and_release_button(#[case] button: Button, #[case] mask: u8) {
+    let mut game_boy = build_game_boy();
+    game_boy.set_buttons(Buttons::NONE.with(Button::Up));
+
+    game_boy.press_button(button);
+    game_boy.run_frame();
+    assert_eq!(
+        game_boy.get_buttons(),
+        Buttons::NONE.with(Button::Up).with(button)
+    );
+    assert_eq!(game_boy.read_memory(0xC000) & 0x0F, 0x0F & !mask);
+
+    game_boy.release_button(button);
+    game_boy.run_frame();
+    assert_eq!(game_boy.get_buttons(), Buttons::NONE.with(Button::Up));
+    assert_eq!(game_boy.read_memory(0xC000) & 0x0F, 0x0F);
+}
@@ -0,0 +1,154 @@
+//! Speedrun timing driven by memory conditions, e.g. splitting as soon as `0xD356 == 0x05`.
+//! Conditions fire once when they become true, not on every frame they stay true.
+//! The timer counts emulated frames, so it isn't affected by fast forward or slowdowns of the host.
+
+use crate::headless::MemoryCondition;
+use crate::throttle::GAME_BOY_FRAME_DURATION;
+use lemon_gb_core::game_boy::GameBoy;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(feature = "livesplit")]
+pub mod livesplit;
+
+/// The conditions of a single game, stored in its [`GameProfile`](crate::profiles::GameProfile)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutosplitConfig {
+    pub start: Option<MemoryCondition>,
+    /// Checked in order, only the next split can fire
+    pub splits: Vec<MemoryCondition>,
+    pub reset: Option<MemoryCondition>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SplitEvent {
+    Start,
+    /// Index of the split in [`AutosplitConfig::splits`]
+    Split(usize),
+    Reset,
+}
+
+impl SplitEvent {
+    /// The command of the LiveSplit Server protocol
+    pub fn livesplit_command(&self) -> &'static str {
+        match self {
+            Self::Start => "starttimer",
+            Self::Split(_) => "split",
+            Self::Reset => "reset",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Autosplitter {
+    config: AutosplitConfig,
+    /// [`GameBoy::frame_count`] at the last update
+    frame: u64,
+    /// Frame of the start event, None while the timer isn't running
+    started_at: Option<u64>,
+    /// Frames since the start at which each split fired
+    split_frames: Vec<u64>,
+    start_was_met: bool,
+    split_was_met: bool,
+    reset_was_met: bool,
+}
+
+impl Autosplitter {
+    pub fn new(config: AutosplitConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_config(&self) -> &AutosplitConfig {
+        &self.config
+    }
+
+    /// Checks the conditions, has to be called once after every emulated frame
+    pub fn update(&mut self, game_boy: &GameBoy) -> Vec<SplitEvent> {
+        self.frame = game_boy.frame_count();
+        let mut events = Vec::new();
+
+        let reset_met = self.is_met(self.config.reset, game_boy);
+        if reset_met && !self.reset_was_met && self.started_at.is_some() {
+            self.started_at = None;
+            self.split_frames.clear();
+            events.push(SplitEvent::Reset);
+        }
+        self.reset_was_met = reset_met;
+
+        let start_met = self.is_met(self.config.start, game_boy);
+        if start_met && !self.start_was_met && self.started_at.is_none() {
+            self.started_at = Some(self.frame);
+            // A split which is already true at the start only fires once it becomes true again
+            self.split_was_met = self.is_met(self.get_next_split(), game_boy);
+            events.push(SplitEvent::Start);
+        }
+        self.start_was_met = start_met;
+
+        if let Some(started_at) = self.started_at {
+            let split_met = self.is_met(self.get_next_split(), game_boy);
+            if split_met && !self.split_was_met {
+                events.push(SplitEvent::Split(self.split_frames.len()));
+                self.split_frames.push(self.frame - started_at);
+                self.split_was_met = self.is_met(self.get_next_split(), game_boy);
+            } else {
+                self.split_was_met = split_met;
+            }
+        }
+
+        events
+    }
+
+    /// Whether the timer runs, it keeps running after the last split until the run is reset
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /// All splits fired
+    pub fn is_finished(&self) -> bool {
+        self.is_running() && self.split_frames.len() == self.config.splits.len()
+    }
+
+    /// Emulated time since the start, the time of the last split once the run is finished
+    pub fn get_elapsed(&self) -> Option<Duration> {
+        let started_at = self.started_at?;
+        let frames = match self.split_frames.last() {
+            Some(&last) if self.is_finished() => last,
+            _ => self.frame - started_at,
+        };
+        Some(frames_to_duration(frames))
+    }
+
+    /// Emulated time from the start to each split that fired
+    pub fn get_split_times(&self) -> Vec<Duration> {
+        self.split_frames
+            .iter()
+            .map(|&frames| frames_to_duration(frames))
+            .collect()
+    }
+
+    fn get_next_split(&self) -> Option<MemoryCondition> {
+        self.config.splits.get(self.split_frames.len()).copied()
+    }
+
+    fn is_met(&self, condition: Option<MemoryCondition>, game_boy: &GameBoy) -> bool {
+        condition.is_some_and(|condition| condition.is_met(game_boy))
+    }
+}
+
+/// Minutes, seconds and hundredths like a speedrun timer, e.g. `1:02.35`
+pub fn format_time(time: Duration) -> String {
+    let hundredths = time.as_millis() / 10;
+    format!(
+        "{}:{:02}.{:02}",
+        hundredths / 6000,
+        hundredths / 100 % 60,
+        hundredths % 100
+    )
+}
+
+fn frames_to_duration(frames: u64) -> Duration {
+    GAME_BOY_FRAME_DURATION * frames as u32
+}
@@ -0,0 +1,27 @@
+//! Sends the autosplit events to LiveSplit, which has to run its server component.
+//! https://github.com/LiveSplit/LiveSplit#the-livesplit-server
+
+use crate::autosplit::SplitEvent;
+use std::error::Error;
+use std::io::Write;
+use std::net::TcpStream;
+
+/// The port the LiveSplit Server listens on by default
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:16834";
+
+pub struct LiveSplitClient {
+    stream: TcpStream,
+}
+
+impl LiveSplitClient {
+    pub fn connect(address: &str) -> Result<Self, Box<dyn Error>> {
+        let stream = TcpStream::connect(address)
+            .map_err(|e| format!("Unable to connect to LiveSplit at {address}: {e}"))?;
+        stream.set_nodelay(true)?;
+        Ok(Self { stream })
+    }
+
+    pub fn send(&mut self, event: SplitEvent) -> std::io::Result<()> {
+        write!(self.stream, "{}\r\n", event.livesplit_command())
+    }
+}
@@ -0,0 +1,197 @@
+//! The ROM the GUI boots into when it is started without a ROM: a bouncing title above a short help text.
+//! It is assembled with the [`RomBuilder`] at runtime, so there is no binary to keep in sync.
+
+use crate::font;
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+
+pub const TITLE: &str = "LEMON-GB";
+
+const MEMCPY_ADDRESS: u16 = 0x0200;
+const FONT_ADDRESS: u16 = 0x1000;
+const BACKGROUND_MAP_ADDRESS: u16 = 0x1400;
+const WINDOW_MAP_ADDRESS: u16 = 0x1700;
+/// 256 byte aligned, so the frame counter can be used as the low byte of the address
+const BOUNCE_TABLE_ADDRESS: u16 = 0x1800;
+const BOUNCE_TABLE_LENGTH: usize = 64;
+/// Pixels the title moves up and down
+const BOUNCE_HEIGHT: f64 = 4.0;
+/// Tile 0 is blank, the glyphs of the font follow it
+const FONT_TILES_SIZE: u16 = (font::GLYPHS.len() as u16 + 1) * 16;
+
+const SCREEN_COLUMNS: usize = 20;
+const MAP_COLUMNS: usize = 32;
+const BACKGROUND_ROWS: usize = 18;
+/// The window covers the last rows of the screen and doesn't bounce
+const WINDOW_ROWS: usize = 5;
+const WINDOW_Y: u8 = 104;
+
+/// (row, text) of the bouncing background
+const BACKGROUND_TEXT: [(usize, &str); 2] = [(5, "LEMON GB"), (8, "NO CARTRIDGE")];
+/// (row, text) of the window
+const WINDOW_TEXT: [(usize, &str); 3] = [
+    (1, "START WITH"),
+    (2, "LEMON-GB RUN ROM.GB"),
+    (4, "ESC TO QUIT"),
+];
+
+/// Waits for VBlank to switch the LCD off, copies the font and both tile maps to VRAM,
+/// then moves the background with the bounce table on every VBlank interrupt
+const MAIN: [u8; 87] = [
+    0xF0,
+    0x44, // LDH A, (LY)
+    0xFE,
+    0x90, // CP 144
+    0x38,
+    0xFA, // JR C, -6
+    0xAF, // XOR A
+    0xE0,
+    0x40, // LDH (LCDC), A
+    0x21,
+    0x00,
+    0x80, // LD HL, 0x8000
+    0x11,
+    0x00,
+    0x10, // LD DE, FONT_ADDRESS
+    0x01,
+    FONT_TILES_SIZE as u8,
+    (FONT_TILES_SIZE >> 8) as u8, // LD BC, FONT_TILES_SIZE
+    0xCD,
+    0x00,
+    0x02, // CALL MEMCPY_ADDRESS
+    0x21,
+    0x00,
+    0x98, // LD HL, 0x9800
+    0x11,
+    0x00,
+    0x14, // LD DE, BACKGROUND_MAP_ADDRESS
+    0x01,
+    0x40,
+    0x02, // LD BC, 32 * 18
+    0xCD,
+    0x00,
+    0x02, // CALL MEMCPY_ADDRESS
+    0x21,
+    0x00,
+    0x9C, // LD HL, 0x9C00
+    0x11,
+    0x00,
+    0x17, // LD DE, WINDOW_MAP_ADDRESS
+    0x01,
+    0xA0,
+    0x00, // LD BC, 32 * 5
+    0xCD,
+    0x00,
+    0x02, // CALL MEMCPY_ADDRESS
+    0x3E,
+    0xE4, // LD A, 0xE4
+    0xE0,
+    0x47, // LDH (BGP), A
+    0x3E,
+    WINDOW_Y, // LD A, WINDOW_Y
+    0xE0,
+    0x4A, // LDH (WY), A
+    0x3E,
+    0x07, // LD A, 7
+    0xE0,
+    0x4B, // LDH (WX), A
+    0xAF, // XOR A
+    0xE0,
+    0x80, // LDH (0xFF80), A
+    0xE0,
+    0x0F, // LDH (IF), A
+    0x3E,
+    0x01, // LD A, VBlank
+    0xE0,
+    0xFF, // LDH (IE), A
+    0x3E,
+    0xF1, // LD A, LCD on, window at 0x9C00 on, tiles at 0x8000, background on
+    0xE0,
+    0x40, // LDH (LCDC), A
+    0xFB, // EI
+    // Frame loop
+    0x76, // HALT
+    0xF0,
+    0x80, // LDH A, (0xFF80)
+    0x3C, // INC A
+    0xE0,
+    0x80, // LDH (0xFF80), A
+    0xE6,
+    0x3F, // AND BOUNCE_TABLE_LENGTH - 1
+    0x6F, // LD L, A
+    0x26,
+    0x18, // LD H, BOUNCE_TABLE_ADDRESS >> 8
+    0x7E, // LD A, (HL)
+    0xE0,
+    0x42, // LDH (SCY), A
+    0x18,
+    0xF0, // JR -16
+];
+
+/// Copies BC bytes from DE to HL
+const MEMCPY: [u8; 9] = [
+    0x1A, // LD A, (DE)
+    0x22, // LD (HL+), A
+    0x13, // INC DE
+    0x0B, // DEC BC
+    0x78, // LD A, B
+    0xB1, // OR C
+    0x20, 0xF8, // JR NZ, -8
+    0xC9, // RET
+];
+
+const VBLANK_VECTOR: u16 = 0x0040;
+const RETI: u8 = 0xD9;
+
+pub fn build_rom() -> Vec<u8> {
+    RomBuilder::new()
+        .title(TITLE)
+        .program(&MAIN)
+        .bytes(VBLANK_VECTOR, &[RETI])
+        .bytes(MEMCPY_ADDRESS, &MEMCPY)
+        .bytes(FONT_ADDRESS, &font_tiles())
+        .bytes(
+            BACKGROUND_MAP_ADDRESS,
+            &tile_map(BACKGROUND_ROWS, &BACKGROUND_TEXT),
+        )
+        .bytes(WINDOW_MAP_ADDRESS, &tile_map(WINDOW_ROWS, &WINDOW_TEXT))
+        .bytes(BOUNCE_TABLE_ADDRESS, &bounce_table())
+        .build()
+}
+
+/// See [`build_rom`]
+pub fn cartridge() -> Cartridge {
+    Cartridge::from_bytes(&build_rom()).expect("The built-in ROM has a valid header")
+}
+
+/// The blank tile followed by the font, both bit planes are the same so the glyphs use color 3
+fn font_tiles() -> Vec<u8> {
+    std::iter::once([0u8; font::GLYPH_SIZE])
+        .chain(font::GLYPHS)
+        .flat_map(|glyph| glyph.into_iter().flat_map(|row| [row, row]))
+        .collect()
+}
+
+/// Tile IDs of the centered text lines, MAP_COLUMNS per row
+fn tile_map(rows: usize, lines: &[(usize, &str)]) -> Vec<u8> {
+    let mut map = vec![0u8; rows * MAP_COLUMNS];
+    for (row, text) in lines {
+        let start = row * MAP_COLUMNS + (SCREEN_COLUMNS - text.len()) / 2;
+        for (offset, character) in text.chars().enumerate() {
+            map[start + offset] = tile_id(character);
+        }
+    }
+    map
+}
+
+fn tile_id(character: char) -> u8 {
+    font::glyph_index(character).map_or(0, |index| index as u8 + 1)
+}
+
+/// SCY for every frame of one bounce
+fn bounce_table() -> [u8; BOUNCE_TABLE_LENGTH] {
+    std::array::from_fn(|frame| {
+        let angle = frame as f64 / BOUNCE_TABLE_LENGTH as f64 * std::f64::consts::TAU;
+        (angle.sin() * BOUNCE_HEIGHT).round() as i8 as u8
+    })
+}
@@ -0,0 +1,178 @@
+//! Cheats manager drawn over the whole screen: lists the cheats of the game with whether they are active,
+//! toggles and removes them and edits their codes. The line after the last cheat adds a new one.
+
+use crate::locale::{Language, Text};
+use crate::panel::{
+    draw_background, draw_column, highlight_line, Panel, PanelState, TextInput, ACTIVE_COLOR,
+    INACTIVE_COLOR, LINES, TEXT_COLOR,
+};
+use lemon_gb_core::game_boy::cheats::Cheat;
+use std::error::Error;
+
+/// `ABC-DEF-GHI`, the longest code
+pub const MAX_CODE_LENGTH: usize = 11;
+/// The title takes the first line
+pub const VISIBLE_LINES: usize = LINES - 1;
+/// The on/off status is at most 3 characters long
+const CODE_COLUMN: usize = 4;
+const NAME_COLUMN: usize = CODE_COLUMN + MAX_CODE_LENGTH + 1;
+
+#[derive(Debug, Default, Clone)]
+pub struct CheatManager {
+    /// The input is the edited code
+    state: PanelState,
+    cheats: Vec<Cheat>,
+    selected: usize,
+    /// Of the labels
+    language: Language,
+}
+
+impl CheatManager {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    /// Shows the cheats of the game, the selection is kept from the last time if it still exists
+    pub fn open(&mut self, cheats: Vec<Cheat>) {
+        self.selected = self.selected.min(cheats.len());
+        self.cheats = cheats;
+        self.state.open();
+    }
+
+    pub fn get_cheats(&self) -> &[Cheat] {
+        &self.cheats
+    }
+
+    /// Equal to the number of cheats on the line which adds a new one
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Moves through the cheats and the line for a new one, leaving the list on one end enters it on the other.
+    /// The selection stays on the edited code.
+    pub fn move_selection(&mut self, rows: isize) {
+        if self.is_editing() {
+            return;
+        }
+        let lines = self.cheats.len() as isize + 1;
+        self.selected = (self.selected as isize + rows).rem_euclid(lines) as usize;
+    }
+
+    /// Returns whether a cheat was selected
+    pub fn toggle_selected(&mut self) -> bool {
+        match self.cheats.get_mut(self.selected) {
+            Some(cheat) if self.state.get_input().is_none() => {
+                cheat.enabled = !cheat.enabled;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether a cheat was selected
+    pub fn remove_selected(&mut self) -> bool {
+        if self.is_editing() || self.selected >= self.cheats.len() {
+            return false;
+        }
+        self.cheats.remove(self.selected);
+        true
+    }
+
+    /// Starts with the code of the selected cheat, or without any code on the line for a new one
+    pub fn start_editing(&mut self) {
+        let code = self
+            .cheats
+            .get(self.selected)
+            .map(|cheat| cheat.code.clone())
+            .unwrap_or_default();
+        self.state
+            .start_input(TextInput::new(&code, MAX_CODE_LENGTH, code_character));
+    }
+
+    pub fn get_edited_code(&self) -> Option<&str> {
+        self.state.get_input().map(TextInput::get_text)
+    }
+
+    /// Replaces the code of the selected cheat or adds a new enabled cheat.
+    /// An invalid code is kept for editing and the error returned.
+    pub fn finish_editing(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(code) = self.get_edited_code() else {
+            return Ok(());
+        };
+        let new_cheat = Cheat::new(code)?;
+        match self.cheats.get_mut(self.selected) {
+            Some(cheat) => cheat.code = new_cheat.code,
+            None => {
+                self.cheats.push(new_cheat);
+                self.selected = self.cheats.len();
+            }
+        }
+        self.state.cancel_input();
+        Ok(())
+    }
+
+    /// Draws the list onto an RGBA8888 frame if the manager is open
+    pub fn draw(&self, frame: &mut [u8]) {
+        if !self.is_open() {
+            return;
+        }
+        draw_background(frame);
+        let title = self.language.text(Text::CheatsLabel);
+        draw_column(frame, 0, 0, title, TEXT_COLOR);
+
+        // Scrolls just far enough to show the selected line
+        let first_line = self.selected.saturating_sub(VISIBLE_LINES - 1);
+        let last_line = (first_line + VISIBLE_LINES).min(self.cheats.len() + 1);
+        for (row, line) in (first_line..last_line).enumerate() {
+            let row = row + 1;
+            if line == self.selected {
+                highlight_line(frame, row);
+            }
+
+            let edited_code = self.state.get_input().filter(|_| line == self.selected);
+            let Some(cheat) = self.cheats.get(line) else {
+                match edited_code {
+                    Some(code) => {
+                        draw_column(frame, CODE_COLUMN, row, &code.with_cursor(), TEXT_COLOR)
+                    }
+                    None => {
+                        let label = self.language.text(Text::NewCheatLabel);
+                        draw_column(frame, CODE_COLUMN, row, label, INACTIVE_COLOR);
+                    }
+                }
+                continue;
+            };
+
+            let (status, color) = if cheat.enabled {
+                (Text::CheatOnLabel, ACTIVE_COLOR)
+            } else {
+                (Text::CheatOffLabel, INACTIVE_COLOR)
+            };
+            draw_column(frame, 0, row, self.language.text(status), color);
+            match edited_code {
+                Some(code) => draw_column(frame, CODE_COLUMN, row, &code.with_cursor(), TEXT_COLOR),
+                None => draw_column(frame, CODE_COLUMN, row, &cheat.code, color),
+            }
+            draw_column(frame, NAME_COLUMN, row, &cheat.name, color);
+        }
+    }
+}
+
+/// [`CheatManager::open`] takes the cheats to show and is used instead of [`Panel::open`]
+impl Panel for CheatManager {
+    fn get_state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+}
+
+/// Hex digits and dashes in upper case
+fn code_character(character: char) -> Option<char> {
+    (character.is_ascii_hexdigit() || character == '-').then(|| character.to_ascii_uppercase())
+}
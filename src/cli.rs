@@ -0,0 +1,240 @@
+use crate::headless::{strip_hex_prefix, HeadlessOptions};
+use lemon_gb_core::disassembler::listing::ListingOptions;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: lemon-gb run <rom> [--headless] [--max-frames N]
+                  [--exit-on-serial TEXT] [--fail-on-serial TEXT]
+                  [--exit-on-memory ADDRESS=VALUE]
+                  [--vblank-timeout MILLION_CYCLES] [--serial-timeout FRAMES]
+       lemon-gb disassemble <rom> [--address ADDRESS] [--bank N] [--count N]
+                  [--format text|json|rgbds]
+       lemon-gb info <rom>
+       lemon-gb scan <directory> [--output FILE]
+       lemon-gb replay <rom> <movie> [--record FILE] [--verify FILE]
+       lemon-gb scenario <file.toml>
+
+Headless runs exit with status 0 if an exit condition is met and 1 otherwise.
+The timeouts abort a headless run stuck without VBlanks or serial output and print the emulator state.
+Scenarios press buttons and take screenshots at given frames or memory conditions, see src/scenario.rs.
+Replays record the checksum of every frame with --record, --verify fails at the first frame that differs.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Open the ROM in the GUI
+    Run { rom: PathBuf },
+    /// Run the ROM without any output until an exit condition is met
+    RunHeadless {
+        rom: PathBuf,
+        options: HeadlessOptions,
+    },
+    /// Print a disassembly of the ROM without running it
+    Disassemble {
+        rom: PathBuf,
+        options: ListingOptions,
+    },
+    /// Print the cartridge header and what hardware the cartridge has
+    Info { rom: PathBuf },
+    /// Index the headers of all ROMs in a directory as JSON
+    Scan {
+        directory: PathBuf,
+        /// Printed to stdout if not set
+        output: Option<PathBuf>,
+    },
+    /// Play a movie without any output, optionally recording or verifying the frame checksums
+    Replay {
+        rom: PathBuf,
+        movie: PathBuf,
+        record: Option<PathBuf>,
+        verify: Option<PathBuf>,
+    },
+    /// Run the steps of a TOML scenario without any output except its screenshots
+    Scenario { path: PathBuf },
+}
+
+/// Parses the command line arguments (without the program name).
+/// Returns None if no command was given.
+pub fn parse_args<I>(args: I) -> Result<Option<Command>, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(None),
+        Some("run") => parse_run(args).map(Some),
+        Some("disassemble") => parse_disassemble(args).map(Some),
+        Some("info") => parse_info(args).map(Some),
+        Some("scan") => parse_scan(args).map(Some),
+        Some("replay") => parse_replay(args).map(Some),
+        Some("scenario") => parse_scenario(args).map(Some),
+        Some(other) => Err(format!("Unknown command '{other}'").into()),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let mut rom = None;
+    let mut headless = false;
+    let mut options = HeadlessOptions::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {name}"))
+        };
+        match arg.as_str() {
+            "--headless" => headless = true,
+            "--max-frames" => {
+                let frames = value("--max-frames")?;
+                options.max_frames = frames
+                    .parse()
+                    .map_err(|e| format!("Invalid frame count '{frames}': {e}"))?;
+            }
+            "--exit-on-serial" => options.exit_on_serial = Some(value("--exit-on-serial")?),
+            "--fail-on-serial" => options.fail_on_serial = Some(value("--fail-on-serial")?),
+            "--exit-on-memory" => {
+                options.exit_on_memory = Some(value("--exit-on-memory")?.parse()?)
+            }
+            "--vblank-timeout" => {
+                let million_cycles = value("--vblank-timeout")?;
+                let million_cycles: u64 = million_cycles
+                    .parse()
+                    .map_err(|e| format!("Invalid cycle count '{million_cycles}': {e}"))?;
+                options.vblank_timeout = Some(
+                    million_cycles
+                        .checked_mul(1_000_000)
+                        .ok_or_else(|| format!("Cycle count '{million_cycles}' is too large"))?,
+                );
+            }
+            "--serial-timeout" => {
+                let frames = value("--serial-timeout")?;
+                options.serial_timeout = Some(
+                    frames
+                        .parse()
+                        .map_err(|e| format!("Invalid frame count '{frames}': {e}"))?,
+                );
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'").into()),
+            path if rom.is_none() => rom = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument '{extra}'").into()),
+        }
+    }
+
+    let rom = rom.ok_or("Missing ROM path")?;
+    if headless {
+        Ok(Command::RunHeadless { rom, options })
+    } else {
+        Ok(Command::Run { rom })
+    }
+}
+
+fn parse_disassemble(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let mut rom = None;
+    let mut options = ListingOptions::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {name}"))
+        };
+        match arg.as_str() {
+            "--address" => {
+                let address = value("--address")?;
+                options.address = u16::from_str_radix(strip_hex_prefix(&address), 16)
+                    .map_err(|e| format!("Invalid address '{address}': {e}"))?;
+            }
+            "--bank" => {
+                let bank = value("--bank")?;
+                options.bank = Some(
+                    bank.parse()
+                        .map_err(|e| format!("Invalid bank '{bank}': {e}"))?,
+                );
+            }
+            "--count" => {
+                let count = value("--count")?;
+                options.count = count
+                    .parse()
+                    .map_err(|e| format!("Invalid instruction count '{count}': {e}"))?;
+            }
+            "--format" => options.format = value("--format")?.parse()?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'").into()),
+            path if rom.is_none() => rom = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument '{extra}'").into()),
+        }
+    }
+
+    let rom = rom.ok_or("Missing ROM path")?;
+    Ok(Command::Disassemble { rom, options })
+}
+
+fn parse_info(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let rom = args.next().ok_or("Missing ROM path")?;
+    if let Some(extra) = args.next() {
+        return Err(format!("Unexpected argument '{extra}'").into());
+    }
+    Ok(Command::Info {
+        rom: PathBuf::from(rom),
+    })
+}
+
+fn parse_scan(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let mut directory = None;
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => {
+                output = Some(PathBuf::from(
+                    args.next().ok_or("Missing value for --output")?,
+                ))
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'").into()),
+            path if directory.is_none() => directory = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument '{extra}'").into()),
+        }
+    }
+
+    let directory = directory.ok_or("Missing ROM directory")?;
+    Ok(Command::Scan { directory, output })
+}
+
+fn parse_replay(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    let mut record = None;
+    let mut verify = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => {
+                record = Some(PathBuf::from(
+                    args.next().ok_or("Missing value for --record")?,
+                ))
+            }
+            "--verify" => {
+                verify = Some(PathBuf::from(
+                    args.next().ok_or("Missing value for --verify")?,
+                ))
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{flag}'").into()),
+            path if paths.len() < 2 => paths.push(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument '{extra}'").into()),
+        }
+    }
+
+    let mut paths = paths.into_iter();
+    let rom = paths.next().ok_or("Missing ROM path")?;
+    let movie = paths.next().ok_or("Missing movie path")?;
+    Ok(Command::Replay {
+        rom,
+        movie,
+        record,
+        verify,
+    })
+}
+
+fn parse_scenario(mut args: impl Iterator<Item = String>) -> Result<Command, Box<dyn Error>> {
+    let path = PathBuf::from(args.next().ok_or("Missing scenario path")?);
+    if let Some(extra) = args.next() {
+        return Err(format!("Unexpected argument '{extra}'").into());
+    }
+    Ok(Command::Scenario { path })
+}
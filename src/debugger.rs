@@ -0,0 +1,195 @@
+use lemon_gb_core::disassembler::DisassembledInstruction;
+use lemon_gb_core::game_boy::GameBoy;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// `LD B,B`, which BGB and Emulicious treat as a breakpoint placed by the program itself
+pub const SOFTWARE_BREAKPOINT_OPCODE: u8 = 0x40;
+/// `LD D,D`, followed by the message in the format of BGB and Emulicious:
+/// `JR end / dw $6464 / dw $0000 / db "text" / end:`
+pub const DEBUG_MESSAGE_OPCODE: u8 = 0x52;
+const DEBUG_MESSAGE_SIGNATURE: [u8; 4] = [0x64, 0x64, 0x00, 0x00];
+const JR_OPCODE: u8 = 0x18;
+
+/// Why [`Debugger::run_frame`] handed control back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugEvent {
+    FrameFinished,
+    /// PC reached a breakpoint, the instruction at that address was not executed yet
+    BreakpointHit(u16),
+    /// PC reached an `LD B,B` while software breakpoints are enabled, it was not executed yet
+    SoftwareBreakpointHit(u16),
+    Paused,
+}
+
+/// Text a program printed with `LD D,D`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DebugMessage {
+    /// Address of the `LD D,D`
+    pub address: u16,
+    pub text: String,
+}
+
+/// Breakpoints and run/pause/step control, meant to sit between a frontend and the [`GameBoy`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Debugger {
+    breakpoints: BTreeSet<u16>,
+    paused: bool,
+    /// Whether `LD B,B` pauses like a breakpoint
+    software_breakpoints: bool,
+    /// Whether `LD D,D` messages are collected
+    debug_messages: bool,
+    messages: Vec<DebugMessage>,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the breakpoint is now set
+    pub fn toggle_breakpoint(&mut self, address: u16) -> bool {
+        if self.breakpoints.remove(&address) {
+            return false;
+        }
+        self.breakpoints.insert(address);
+        true
+    }
+
+    pub fn set_breakpoint(&mut self, address: u16) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: u16) {
+        self.breakpoints.remove(&address);
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    pub fn has_breakpoint(&self, address: u16) -> bool {
+        self.breakpoints.contains(&address)
+    }
+
+    pub fn get_breakpoints(&self) -> impl Iterator<Item = u16> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    pub fn set_software_breakpoints(&mut self, enabled: bool) {
+        self.software_breakpoints = enabled;
+    }
+
+    pub fn has_software_breakpoints(&self) -> bool {
+        self.software_breakpoints
+    }
+
+    pub fn set_debug_messages(&mut self, enabled: bool) {
+        self.debug_messages = enabled;
+    }
+
+    pub fn has_debug_messages(&self) -> bool {
+        self.debug_messages
+    }
+
+    /// The messages printed since the last call, oldest first
+    pub fn take_debug_messages(&mut self) -> Vec<DebugMessage> {
+        std::mem::take(&mut self.messages)
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Executes a single instruction regardless of breakpoints, returns true if a frame finished
+    pub fn step_instruction(&mut self, game_boy: &mut GameBoy) -> bool {
+        self.execute(game_boy)
+    }
+
+    /// Runs until the current frame is finished or a breakpoint is reached, which pauses the debugger.
+    /// The instruction at the current PC is always executed, so resuming from a breakpoint does not hit it again.
+    pub fn run_frame(&mut self, game_boy: &mut GameBoy) -> DebugEvent {
+        if self.paused {
+            return DebugEvent::Paused;
+        }
+
+        let mut frame_finished = self.execute(game_boy);
+        while !frame_finished {
+            let pc = game_boy.get_pc();
+            if self.has_breakpoint(pc) {
+                self.paused = true;
+                return DebugEvent::BreakpointHit(pc);
+            }
+            if self.software_breakpoints && is_executing(game_boy, SOFTWARE_BREAKPOINT_OPCODE) {
+                self.paused = true;
+                return DebugEvent::SoftwareBreakpointHit(pc);
+            }
+            frame_finished = self.execute(game_boy);
+        }
+        DebugEvent::FrameFinished
+    }
+
+    /// Runs one frame like [`run_frame`](Self::run_frame) even while paused, the debugger stays paused afterwards.
+    /// Stops at breakpoints as well, which pauses a running debugger.
+    pub fn advance_frame(&mut self, game_boy: &mut GameBoy) -> DebugEvent {
+        let paused = self.paused;
+        self.paused = false;
+        let event = self.run_frame(game_boy);
+        self.paused |= paused;
+        event
+    }
+
+    fn execute(&mut self, game_boy: &mut GameBoy) -> bool {
+        if self.debug_messages && is_executing(game_boy, DEBUG_MESSAGE_OPCODE) {
+            if let Some(text) = read_debug_message(game_boy, game_boy.get_pc()) {
+                self.messages.push(DebugMessage {
+                    address: game_boy.get_pc(),
+                    text,
+                });
+            }
+        }
+        game_boy.step()
+    }
+
+    /// The next instructions starting at PC, for a disassembly view following execution
+    pub fn disassembly_at_pc(
+        &self,
+        game_boy: &GameBoy,
+        count: usize,
+    ) -> Vec<DisassembledInstruction> {
+        game_boy.disassemble(game_boy.get_pc(), count)
+    }
+}
+
+/// A halted CPU doesn't execute the instruction at PC, so it mustn't trigger on every HALT cycle
+fn is_executing(game_boy: &GameBoy, opcode: u8) -> bool {
+    !game_boy.get_cpu().is_halted() && game_boy.peek(game_boy.get_pc()) == opcode
+}
+
+/// None if the `LD D,D` isn't followed by the message format
+fn read_debug_message(game_boy: &GameBoy, address: u16) -> Option<String> {
+    if game_boy.peek(address.wrapping_add(1)) != JR_OPCODE {
+        return None;
+    }
+    let offset = game_boy.peek(address.wrapping_add(2)) as i8;
+    let text_start = address.wrapping_add(3 + DEBUG_MESSAGE_SIGNATURE.len() as u16);
+    let end = address.wrapping_add(3).wrapping_add_signed(offset as i16);
+    let signature = (0..DEBUG_MESSAGE_SIGNATURE.len() as u16)
+        .map(|index| game_boy.peek(address.wrapping_add(3 + index)));
+    if !signature.eq(DEBUG_MESSAGE_SIGNATURE) || end < text_start {
+        return None;
+    }
+    Some(
+        (text_start..end)
+            .map(|address| game_boy.peek(address) as char)
+            .collect(),
+    )
+}
@@ -0,0 +1,247 @@
+//! Debugger drawn over the whole screen: the registers on top and the disassembly following PC below them.
+//! Space toggles a breakpoint on the selected line and registers are edited like the codes of the cheats manager.
+//! Running, pausing and stepping go through the [`Debugger`] of the frontend, so the panel only shows its state.
+
+use crate::debugger::Debugger;
+use crate::locale::{Language, Text};
+use crate::panel::{
+    draw_background, draw_column, highlight, highlight_line, Panel, PanelState, TextInput,
+    ACTIVE_COLOR, INACTIVE_COLOR, LINES, TEXT_COLOR,
+};
+use lemon_gb_core::game_boy::components::cpu::registers::CpuRegistersAccessTrait;
+use lemon_gb_core::game_boy::GameBoy;
+use std::error::Error;
+
+/// Below the title, two registers per line
+const REGISTER_LINE: usize = 1;
+const REGISTER_COLUMN_WIDTH: usize = 10;
+/// Below the registers, shows whether `LD B,B` and `LD D,D` are handled
+const CONVENTIONS_LINE: usize = REGISTER_LINE + Register::ALL.len() / 2;
+const DISASSEMBLY_LINE: usize = CONVENTIONS_LINE + 1;
+/// The instructions starting at PC which fit below the registers
+pub const DISASSEMBLY_LINES: usize = LINES - DISASSEMBLY_LINE;
+/// The hex digits of a 16-bit register
+pub const MAX_VALUE_LENGTH: usize = 4;
+const PC_COLOR: [u8; 4] = [0xFF, 0xE0, 0x40, 0xFF];
+const BREAKPOINT_COLOR: [u8; 4] = [0xFF, 0x60, 0x60, 0xFF];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Register {
+    AF,
+    BC,
+    DE,
+    HL,
+    SP,
+    PC,
+}
+
+impl Register {
+    /// In the order of the panel
+    pub const ALL: [Register; 6] = [
+        Register::AF,
+        Register::BC,
+        Register::DE,
+        Register::HL,
+        Register::SP,
+        Register::PC,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Register::AF => "AF",
+            Register::BC => "BC",
+            Register::DE => "DE",
+            Register::HL => "HL",
+            Register::SP => "SP",
+            Register::PC => "PC",
+        }
+    }
+
+    pub fn get(&self, game_boy: &GameBoy) -> u16 {
+        let cpu = game_boy.get_cpu();
+        match self {
+            Register::AF => cpu.get_af(),
+            Register::BC => cpu.get_bc(),
+            Register::DE => cpu.get_de(),
+            Register::HL => cpu.get_hl(),
+            Register::SP => cpu.get_sp(),
+            Register::PC => cpu.get_pc(),
+        }
+    }
+
+    /// The lower 4 bits of F are discarded
+    pub fn set(&self, game_boy: &mut GameBoy, value: u16) {
+        let cpu = game_boy.get_cpu_mut();
+        match self {
+            Register::AF => cpu.set_af(value),
+            Register::BC => cpu.set_bc(value),
+            Register::DE => cpu.set_de(value),
+            Register::HL => cpu.set_hl(value),
+            Register::SP => cpu.set_sp(value),
+            Register::PC => cpu.set_pc(value),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DebuggerPanel {
+    /// The input is the value of the edited register
+    state: PanelState,
+    /// The registers come first, followed by the lines of the disassembly
+    selected: usize,
+    /// Of the labels
+    language: Language,
+}
+
+impl DebuggerPanel {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Moves through the registers and the disassembly, leaving the panel on one end enters it on the other.
+    /// The selection stays on the edited register.
+    pub fn move_selection(&mut self, rows: isize) {
+        if self.is_editing() {
+            return;
+        }
+        let lines = (Register::ALL.len() + DISASSEMBLY_LINES) as isize;
+        self.selected = (self.selected as isize + rows).rem_euclid(lines) as usize;
+    }
+
+    pub fn get_selected_register(&self) -> Option<Register> {
+        Register::ALL.get(self.selected).copied()
+    }
+
+    /// The address of the selected line of the disassembly, None if a register is selected
+    pub fn get_selected_address(&self, game_boy: &GameBoy) -> Option<u16> {
+        let line = self.selected.checked_sub(Register::ALL.len())?;
+        let disassembly = game_boy.disassemble(game_boy.get_pc(), DISASSEMBLY_LINES);
+        disassembly.get(line).map(|instruction| instruction.address)
+    }
+
+    /// Returns whether a line of the disassembly was selected
+    pub fn toggle_breakpoint(&self, game_boy: &GameBoy, debugger: &mut Debugger) -> bool {
+        match self.get_selected_address(game_boy) {
+            Some(address) if !self.is_editing() => {
+                debugger.toggle_breakpoint(address);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Starts with the current value, returns whether a register was selected
+    pub fn start_editing(&mut self, game_boy: &GameBoy) -> bool {
+        let Some(register) = self.get_selected_register() else {
+            return false;
+        };
+        let value = format!("{:04X}", register.get(game_boy));
+        self.state
+            .start_input(TextInput::hex(&value, MAX_VALUE_LENGTH));
+        true
+    }
+
+    pub fn get_edited_value(&self) -> Option<&str> {
+        self.state.get_input().map(TextInput::get_text)
+    }
+
+    /// Writes the value to the selected register. Without any digits the value is kept for editing and an error returned.
+    pub fn finish_editing(&mut self, game_boy: &mut GameBoy) -> Result<(), Box<dyn Error>> {
+        let (Some(value), Some(register)) = (self.get_edited_value(), self.get_selected_register())
+        else {
+            return Ok(());
+        };
+        let value = u16::from_str_radix(value, 16)
+            .map_err(|_| format!("{} needs a hex value", register.label()))?;
+        register.set(game_boy, value);
+        self.state.cancel_input();
+        Ok(())
+    }
+
+    /// Draws the panel onto an RGBA8888 frame if it is open
+    pub fn draw(&self, frame: &mut [u8], game_boy: &GameBoy, debugger: &Debugger) {
+        if !self.is_open() {
+            return;
+        }
+        draw_background(frame);
+        let title = self.language.text(Text::DebuggerLabel);
+        draw_column(frame, 0, 0, title, TEXT_COLOR);
+        let (status, color) = if debugger.is_paused() {
+            (Text::DebuggerPausedLabel, PC_COLOR)
+        } else {
+            (Text::DebuggerRunningLabel, ACTIVE_COLOR)
+        };
+        draw_column(
+            frame,
+            REGISTER_COLUMN_WIDTH,
+            0,
+            self.language.text(status),
+            color,
+        );
+
+        for (index, register) in Register::ALL.iter().enumerate() {
+            let line = REGISTER_LINE + index / 2;
+            let column = index % 2 * REGISTER_COLUMN_WIDTH;
+            let selected = index == self.selected;
+            if selected {
+                highlight(frame, column, line, REGISTER_COLUMN_WIDTH);
+            }
+            let value = match self.state.get_input() {
+                Some(value) if selected => value.with_cursor(),
+                _ => format!("{:04X}", register.get(game_boy)),
+            };
+            draw_column(frame, column, line, register.label(), INACTIVE_COLOR);
+            draw_column(frame, column + 3, line, &value, TEXT_COLOR);
+        }
+
+        for (column, enabled) in [
+            (0, debugger.has_software_breakpoints()),
+            (REGISTER_COLUMN_WIDTH, debugger.has_debug_messages()),
+        ] {
+            let label = if column == 0 { "LD B,B" } else { "LD D,D" };
+            let color = if enabled {
+                ACTIVE_COLOR
+            } else {
+                INACTIVE_COLOR
+            };
+            draw_column(frame, column, CONVENTIONS_LINE, label, color);
+        }
+
+        let pc = game_boy.get_pc();
+        let disassembly = game_boy.disassemble(pc, DISASSEMBLY_LINES);
+        for (index, instruction) in disassembly.iter().enumerate() {
+            let line = DISASSEMBLY_LINE + index;
+            if Register::ALL.len() + index == self.selected {
+                highlight_line(frame, line);
+            }
+            let address_color = if debugger.has_breakpoint(instruction.address) {
+                BREAKPOINT_COLOR
+            } else if instruction.address == pc {
+                PC_COLOR
+            } else {
+                INACTIVE_COLOR
+            };
+            let address = format!("{:04X}", instruction.address);
+            draw_column(frame, 0, line, &address, address_color);
+            draw_column(frame, 5, line, &instruction.get_clear_text(), TEXT_COLOR);
+        }
+    }
+}
+
+/// The selection is kept from the last time
+impl Panel for DebuggerPanel {
+    fn get_state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+}
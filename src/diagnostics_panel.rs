@@ -0,0 +1,70 @@
+//! Log panel over the top of the frame listing the latest diagnostics of the running game, see [`Diagnostic`].
+
+use crate::font;
+use crate::osd::{draw_text, fill_rectangle, wrap_text, CHARACTER_WIDTH};
+use lemon_gb_core::game_boy::components::ppu::SCREEN_WIDTH;
+use lemon_gb_core::game_boy::diagnostics::Diagnostic;
+
+/// Older lines scroll out of the panel
+pub const MAX_LINES: usize = 8;
+/// Distance of the text to the edges of the panel
+const PADDING: usize = 1;
+const LINE_HEIGHT: usize = font::GLYPH_SIZE;
+/// Continuation lines of a wrapped entry are indented by this many characters
+const INDENT: &str = "  ";
+const TEXT_COLOR: [u8; 4] = [0xFF, 0xD0, 0x40, 0xFF];
+const BACKGROUND_COLOR: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+
+#[derive(Debug, Default, Clone)]
+pub struct DiagnosticsPanel {
+    visible: bool,
+    lines: Vec<String>,
+}
+
+impl DiagnosticsPanel {
+    /// Appends the message of the diagnostic, wrapped to the width of the screen
+    pub fn push(&mut self, diagnostic: &Diagnostic) {
+        let max_line_length = (SCREEN_WIDTH - 2 * PADDING) / CHARACTER_WIDTH;
+        let mut wrapped = wrap_text(&diagnostic.to_string(), max_line_length - INDENT.len());
+        for line in wrapped.iter_mut().skip(1) {
+            line.insert_str(0, INDENT);
+        }
+        self.lines.extend(wrapped);
+        let overflow = self.lines.len().saturating_sub(MAX_LINES);
+        self.lines.drain(..overflow);
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    pub fn get_lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Draws the lines onto an RGBA8888 frame if the panel is visible and not empty
+    pub fn draw(&self, frame: &mut [u8]) {
+        if !self.visible || self.lines.is_empty() {
+            return;
+        }
+        let height = self.lines.len() * LINE_HEIGHT + 2 * PADDING;
+        fill_rectangle(frame, 0, 0, SCREEN_WIDTH, height, BACKGROUND_COLOR);
+        for (index, line) in self.lines.iter().enumerate() {
+            draw_text(
+                frame,
+                PADDING,
+                PADDING + index * LINE_HEIGHT,
+                line,
+                TEXT_COLOR,
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! A 5x7 pixel font of upper case letters (including the German umlauts), digits and some punctuation, one byte per row with 8 rows per glyph.
+//! Used by the built-in ROM and for on screen messages.
+
+pub const GLYPH_SIZE: usize = 8;
+pub const CHARACTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-.:_,/!?'()ÄÖÜ+[]";
+/// The glyphs in the order of [`CHARACTERS`], the leftmost column and the last row are always empty
+pub const GLYPHS: [[u8; GLYPH_SIZE]; 53] = [
+    [0x38, 0x44, 0x44, 0x7C, 0x44, 0x44, 0x44, 0x00], // A
+    [0x78, 0x44, 0x44, 0x78, 0x44, 0x44, 0x78, 0x00], // B
+    [0x38, 0x44, 0x40, 0x40, 0x40, 0x44, 0x38, 0x00], // C
+    [0x78, 0x44, 0x44, 0x44, 0x44, 0x44, 0x78, 0x00], // D
+    [0x7C, 0x40, 0x40, 0x78, 0x40, 0x40, 0x7C, 0x00], // E
+    [0x7C, 0x40, 0x40, 0x78, 0x40, 0x40, 0x40, 0x00], // F
+    [0x38, 0x44, 0x40, 0x5C, 0x44, 0x44, 0x3C, 0x00], // G
+    [0x44, 0x44, 0x44, 0x7C, 0x44, 0x44, 0x44, 0x00], // H
+    [0x38, 0x10, 0x10, 0x10, 0x10, 0x10, 0x38, 0x00], // I
+    [0x1C, 0x08, 0x08, 0x08, 0x08, 0x48, 0x30, 0x00], // J
+    [0x44, 0x48, 0x50, 0x60, 0x50, 0x48, 0x44, 0x00], // K
+    [0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x7C, 0x00], // L
+    [0x44, 0x6C, 0x54, 0x54, 0x44, 0x44, 0x44, 0x00], // M
+    [0x44, 0x44, 0x64, 0x54, 0x4C, 0x44, 0x44, 0x00], // N
+    [0x38, 0x44, 0x44, 0x44, 0x44, 0x44, 0x38, 0x00], // O
+    [0x78, 0x44, 0x44, 0x78, 0x40, 0x40, 0x40, 0x00], // P
+    [0x38, 0x44, 0x44, 0x44, 0x54, 0x48, 0x34, 0x00], // Q
+    [0x78, 0x44, 0x44, 0x78, 0x50, 0x48, 0x44, 0x00], // R
+    [0x3C, 0x40, 0x40, 0x38, 0x04, 0x04, 0x78, 0x00], // S
+    [0x7C, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // T
+    [0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x38, 0x00], // U
+    [0x44, 0x44, 0x44, 0x44, 0x44, 0x28, 0x10, 0x00], // V
+    [0x44, 0x44, 0x44, 0x54, 0x54, 0x54, 0x28, 0x00], // W
+    [0x44, 0x44, 0x28, 0x10, 0x28, 0x44, 0x44, 0x00], // X
+    [0x44, 0x44, 0x28, 0x10, 0x10, 0x10, 0x10, 0x00], // Y
+    [0x7C, 0x04, 0x08, 0x10, 0x20, 0x40, 0x7C, 0x00], // Z
+    [0x38, 0x44, 0x4C, 0x54, 0x64, 0x44, 0x38, 0x00], // 0
+    [0x10, 0x30, 0x10, 0x10, 0x10, 0x10, 0x38, 0x00], // 1
+    [0x38, 0x44, 0x04, 0x08, 0x10, 0x20, 0x7C, 0x00], // 2
+    [0x7C, 0x08, 0x10, 0x08, 0x04, 0x44, 0x38, 0x00], // 3
+    [0x08, 0x18, 0x28, 0x48, 0x7C, 0x08, 0x08, 0x00], // 4
+    [0x7C, 0x40, 0x78, 0x04, 0x04, 0x44, 0x38, 0x00], // 5
+    [0x18, 0x20, 0x40, 0x78, 0x44, 0x44, 0x38, 0x00], // 6
+    [0x7C, 0x04, 0x08, 0x10, 0x20, 0x20, 0x20, 0x00], // 7
+    [0x38, 0x44, 0x44, 0x38, 0x44, 0x44, 0x38, 0x00], // 8
+    [0x38, 0x44, 0x44, 0x3C, 0x04, 0x08, 0x30, 0x00], // 9
+    [0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x00], // .
+    [0x00, 0x30, 0x30, 0x00, 0x30, 0x30, 0x00, 0x00], // :
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7C, 0x00], // _
+    [0x00, 0x00, 0x00, 0x00, 0x30, 0x10, 0x20, 0x00], // ,
+    [0x04, 0x04, 0x08, 0x10, 0x20, 0x40, 0x40, 0x00], // /
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x10, 0x00], // !
+    [0x38, 0x44, 0x04, 0x08, 0x10, 0x00, 0x10, 0x00], // ?
+    [0x10, 0x10, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00], // '
+    [0x08, 0x10, 0x20, 0x20, 0x20, 0x10, 0x08, 0x00], // (
+    [0x20, 0x10, 0x08, 0x08, 0x08, 0x10, 0x20, 0x00], // )
+    [0x28, 0x00, 0x38, 0x44, 0x7C, 0x44, 0x44, 0x00], // Ä
+    [0x28, 0x00, 0x38, 0x44, 0x44, 0x44, 0x38, 0x00], // Ö
+    [0x28, 0x00, 0x44, 0x44, 0x44, 0x44, 0x38, 0x00], // Ü
+    [0x00, 0x10, 0x10, 0x7C, 0x10, 0x10, 0x00, 0x00], // +
+    [0x38, 0x20, 0x20, 0x20, 0x20, 0x20, 0x38, 0x00], // [
+    [0x38, 0x08, 0x08, 0x08, 0x08, 0x08, 0x38, 0x00], // ]
+];
+
+/// Lower case letters use the upper case glyphs, None for characters without one.
+/// Letters like ß which upper case to several characters have none either.
+pub fn glyph_index(character: char) -> Option<usize> {
+    let mut upper_case = character.to_uppercase();
+    let upper_case = match (upper_case.next(), upper_case.next()) {
+        (Some(upper_case), None) => upper_case,
+        _ => return None,
+    };
+    CHARACTERS.chars().position(|glyph| glyph == upper_case)
+}
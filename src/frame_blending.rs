@@ -0,0 +1,63 @@
+/// How much of the previous frame is mixed into the current one by default
+pub const DEFAULT_PREVIOUS_FRAME_WEIGHT: f32 = 0.5;
+
+/// Mixes every frame with the one before it for presenting, like the slow LCD of the Game Boy did.
+/// Games flickering sprites at 30 Hz rely on that to show them as transparent instead of blinking.
+///
+/// Works on RGBA8888 frames, only the presented image is changed, the emulation is not affected.
+#[derive(Debug, Clone)]
+pub struct FrameBlender {
+    previous_weight: f32,
+    previous_frame: Option<Vec<u8>>,
+}
+
+impl FrameBlender {
+    pub fn new() -> Self {
+        Self::with_previous_weight(DEFAULT_PREVIOUS_FRAME_WEIGHT)
+    }
+
+    /// 0.0 shows only the current frame, 0.5 averages both frames
+    pub fn with_previous_weight(previous_weight: f32) -> Self {
+        Self {
+            previous_weight: previous_weight.clamp(0.0, 1.0),
+            previous_frame: None,
+        }
+    }
+
+    pub fn get_previous_weight(&self) -> f32 {
+        self.previous_weight
+    }
+
+    /// Writes the weighted average of the frame and the previous one to the output, both have to be the same size.
+    /// The first frame, or one of another size, is copied as it is.
+    pub fn blend(&mut self, frame: &[u8], output: &mut [u8]) {
+        match &mut self.previous_frame {
+            Some(previous_frame) if previous_frame.len() == frame.len() => {
+                let current_weight = 1.0 - self.previous_weight;
+                for ((out, &current), previous) in
+                    output.iter_mut().zip(frame).zip(previous_frame.iter_mut())
+                {
+                    let mixed =
+                        current as f32 * current_weight + *previous as f32 * self.previous_weight;
+                    *out = mixed.round() as u8;
+                    *previous = current;
+                }
+            }
+            _ => {
+                output.copy_from_slice(frame);
+                self.previous_frame = Some(frame.to_vec());
+            }
+        }
+    }
+
+    /// Forgets the previous frame, e.g. after a reset or loading a save state
+    pub fn reset(&mut self) {
+        self.previous_frame = None;
+    }
+}
+
+impl Default for FrameBlender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +0,0 @@
-pub mod cartridge;
-pub mod cpu;
-pub mod mmu;
-pub mod ppu;
-pub mod timer;
@@ -1,20 +1,103 @@
-use crate::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
-use crate::game_boy::GameBoy;
-use log::error;
+#[cfg(feature = "livesplit")]
+use crate::autosplit::livesplit::{LiveSplitClient, DEFAULT_ADDRESS};
+use crate::autosplit::{format_time, Autosplitter, SplitEvent};
+use crate::cheat_manager::CheatManager;
+use crate::debugger::{DebugEvent, Debugger};
+use crate::debugger_panel::DebuggerPanel;
+use crate::diagnostics_panel::DiagnosticsPanel;
+use crate::frame_blending::FrameBlender;
+use crate::input_display::InputDisplay;
+use crate::link_cable::{parse_port, LinkSession};
+use crate::link_panel::{LinkAction, LinkPanel};
+use crate::locale::{Language, Text};
+use crate::memory_editor::{MemoryEditor, MemoryInput, BYTES_PER_ROW, VISIBLE_ROWS};
+use crate::osd::Osd;
+use crate::panel::Panel;
+use crate::profiles::{Profiles, DEFAULT_PROFILES_PATH};
+#[cfg(feature = "rpc")]
+use crate::rpc::server::{RpcServer, DEFAULT_ADDRESS as RPC_ADDRESS};
+use crate::rpc::Controller;
+#[cfg(feature = "image")]
+use crate::screenshot;
+use crate::settings::FrontendSettings;
+use crate::state_picker::{
+    read_slots, slot_path, store_slot, Slot, StatePicker, DEFAULT_STATES_DIRECTORY,
+};
+use crate::throttle::Throttle;
+use crate::vram_viewer::VramViewer;
+use lemon_gb_core::game_boy::cheats::Cheat;
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::ppu::color_correction::ColorCorrection;
+use lemon_gb_core::game_boy::components::ppu::color_scheme_preset::ColorSchemePreset;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::diagnostics::Diagnostic;
+use lemon_gb_core::game_boy::save_state::GameBoySaveState;
+use lemon_gb_core::game_boy::GameBoy;
+use lemon_gb_core::ListenerId;
+use log::{error, info};
 use pixels::{Pixels, SurfaceTexture};
-use std::thread::sleep;
-use std::time::{Duration, Instant};
+use std::error::Error;
+use std::path::Path;
+use std::sync::mpsc::{channel, Sender};
 use winit::dpi::LogicalSize;
 use winit::event::{Event, WindowEvent};
 use winit::event_loop::EventLoop;
-use winit::keyboard::KeyCode;
+use winit::keyboard::{Key, KeyCode};
 use winit::window::WindowBuilder;
 use winit_input_helper::WinitInputHelper;
 
-const GAME_BOY_FPS: f64 = 59.7;
 const WINDOW_SCALE_FACTOR: u32 = 3;
+/// Only every nth frame is rendered while the window is minimized or hidden
+const BACKGROUND_RENDER_INTERVAL: u8 = 8;
+const RESET_KEY: KeyCode = KeyCode::F5;
+const COLORIZE_KEY: KeyCode = KeyCode::F6;
+const COLOR_CORRECTION_KEY: KeyCode = KeyCode::F7;
+const FRAME_BLENDING_KEY: KeyCode = KeyCode::F8;
+/// Cycles through the built-in color schemes, including the color blind and high contrast ones
+const COLOR_SCHEME_KEY: KeyCode = KeyCode::F9;
+const LARGE_OSD_TEXT_KEY: KeyCode = KeyCode::F10;
+/// Opens the save state picker, in which S saves to the selected slot and Enter loads it
+const STATE_PICKER_KEY: KeyCode = KeyCode::F2;
+/// Shows the held buttons, e.g. for recordings
+const INPUT_DISPLAY_KEY: KeyCode = KeyCode::F3;
+/// Shows the log of suspicious things the game did, see [`Diagnostic`]
+const DIAGNOSTICS_KEY: KeyCode = KeyCode::F4;
+const SAVE_SLOT_KEY: KeyCode = KeyCode::KeyS;
+const LOAD_SLOT_KEY: KeyCode = KeyCode::Enter;
+/// Opens the cheats manager, in which Enter edits the selected code and Space switches it on or off
+const CHEATS_KEY: KeyCode = KeyCode::F1;
+const EDIT_CHEAT_KEY: KeyCode = KeyCode::Enter;
+const TOGGLE_CHEAT_KEY: KeyCode = KeyCode::Space;
+const REMOVE_CHEAT_KEY: KeyCode = KeyCode::Delete;
+/// Opens the link cable panel, in which Enter hosts, joins or disconnects
+const LINK_KEY: KeyCode = KeyCode::F11;
+const LINK_ACTION_KEY: KeyCode = KeyCode::Enter;
+/// Opens the debugger, in which Enter edits the selected register and Space toggles a breakpoint on the selected line
+const DEBUGGER_KEY: KeyCode = KeyCode::F12;
+const EDIT_REGISTER_KEY: KeyCode = KeyCode::Enter;
+const TOGGLE_BREAKPOINT_KEY: KeyCode = KeyCode::Space;
+const PAUSE_KEY: KeyCode = KeyCode::KeyP;
+/// Pauses the game and executes one instruction
+const STEP_KEY: KeyCode = KeyCode::KeyN;
+/// Switch `LD B,B` breakpoints and `LD D,D` messages on or off
+const SOFTWARE_BREAKPOINTS_KEY: KeyCode = KeyCode::KeyB;
+const DEBUG_MESSAGES_KEY: KeyCode = KeyCode::KeyD;
+/// Switches from the debugger to the VRAM viewer to the memory editor and back while one of them is open
+const NEXT_DEBUG_PANEL_KEY: KeyCode = KeyCode::Tab;
+/// Switches the VRAM viewer between the tile data and the two tile maps
+const VRAM_VIEW_KEY: KeyCode = KeyCode::Space;
+/// Enter edits the selected byte in the memory editor, G jumps to an address, F searches for bytes
+/// and N selects the next match
+const EDIT_BYTE_KEY: KeyCode = KeyCode::Enter;
+const GO_TO_KEY: KeyCode = KeyCode::KeyG;
+const SEARCH_KEY: KeyCode = KeyCode::KeyF;
+const NEXT_MATCH_KEY: KeyCode = KeyCode::KeyN;
 
-pub fn run(game_boy: &mut GameBoy) {
+pub fn run(
+    game_boy: &mut GameBoy,
+    settings: &FrontendSettings,
+    mut autosplitter: Option<Autosplitter>,
+) {
     let event_loop = EventLoop::new().unwrap();
     let mut input = WinitInputHelper::new();
 
@@ -38,16 +121,94 @@ pub fn run(game_boy: &mut GameBoy) {
             .expect("Failed to create pixel buffer")
     };
 
-    const FRAME_DURATION: Duration = Duration::from_nanos((1_000_000_000.0 / GAME_BOY_FPS) as u64);
+    let mut throttle = Throttle::new();
+    let mut shown_lag_frames = 0;
+    let mut frame_blender = settings.frame_blending.then(FrameBlender::new);
+    let mut osd = Osd::default();
+    osd.set_large_text(settings.large_osd_text);
+    let language = settings.language;
+    let mut state_picker = StatePicker::new(language);
+    let mut cheat_manager = CheatManager::new(language);
+    let mut link_panel = LinkPanel::new(language);
+    let mut debugger_panel = DebuggerPanel::new(language);
+    let mut vram_viewer = VramViewer::new(language);
+    let mut memory_editor = MemoryEditor::new(language);
+    let mut link_session = LinkSession::default();
+    let mut input_display = InputDisplay::default();
+    let mut diagnostics_panel = DiagnosticsPanel::default();
+    let (diagnostic_sender, diagnostics) = channel();
+    let mut diagnostic_listener = None;
+    watch_diagnostics(game_boy, &mut diagnostic_listener, &diagnostic_sender);
+    #[cfg(feature = "livesplit")]
+    let mut livesplit = autosplitter.as_ref().and_then(|_| {
+        LiveSplitClient::connect(DEFAULT_ADDRESS)
+            .map_err(|error| {
+                error!("{error}");
+                osd.show(&error.to_string());
+            })
+            .ok()
+    });
+
+    // Runs the frames, so breakpoints pause the game
+    let mut controller = Controller::default();
+    #[cfg(feature = "rpc")]
+    let rpc_server = RpcServer::bind(RPC_ADDRESS)
+        .map_err(|error| {
+            error!("{error}");
+            osd.show(&error.to_string());
+        })
+        .ok();
 
     let _ = event_loop.run(|event, elwt| {
+        if let Event::WindowEvent {
+            event: WindowEvent::Occluded(occluded),
+            ..
+        } = &event
+        {
+            game_boy.set_render_interval(if *occluded {
+                BACKGROUND_RENDER_INTERVAL
+            } else {
+                1
+            });
+        }
+
+        if let Event::WindowEvent {
+            event: WindowEvent::DroppedFile(path),
+            ..
+        } = &event
+        {
+            match load_dropped_file(game_boy, path, language) {
+                Ok(message) => osd.show(language.text(message)),
+                Err(error) => {
+                    error!("Failed to load {}: {error}", path.display());
+                    osd.show(&error.to_string());
+                }
+            }
+            watch_diagnostics(game_boy, &mut diagnostic_listener, &diagnostic_sender);
+            if let Some(frame_blender) = &mut frame_blender {
+                frame_blender.reset();
+            }
+        }
+
         if let Event::WindowEvent {
             event: WindowEvent::RedrawRequested,
             ..
         } = event
         {
             let frame = pixels.frame_mut();
-            frame.copy_from_slice(game_boy.get_frame_buffer());
+            match &mut frame_blender {
+                Some(frame_blender) => frame_blender.blend(game_boy.get_frame_buffer(), frame),
+                None => frame.copy_from_slice(game_boy.get_frame_buffer()),
+            }
+            input_display.draw(frame, game_boy.get_buttons());
+            diagnostics_panel.draw(frame);
+            state_picker.draw(frame);
+            cheat_manager.draw(frame);
+            link_panel.draw(frame, link_session.get_state());
+            debugger_panel.draw(frame, game_boy, controller.get_debugger());
+            vram_viewer.draw(frame, game_boy);
+            memory_editor.draw(frame, game_boy);
+            osd.draw(frame);
 
             if let Err(err) = pixels.render() {
                 error!("pixels.render error: {}", err);
@@ -57,11 +218,167 @@ pub fn run(game_boy: &mut GameBoy) {
         }
 
         if input.update(&event) {
-            if input.key_pressed(KeyCode::Escape) || input.close_requested() {
+            if input.close_requested() {
                 elwt.exit();
                 return;
             }
 
+            // Escape closes the picker, the cheats manager, the link panel and the debug panels before it closes the window,
+            // a code, register or memory input which is typed is discarded first
+            if input.key_pressed(KeyCode::Escape) {
+                if cheat_manager.is_editing() {
+                    cheat_manager.cancel_editing();
+                } else if debugger_panel.is_editing() {
+                    debugger_panel.cancel_editing();
+                } else if memory_editor.is_editing() {
+                    memory_editor.cancel_editing();
+                } else if cheat_manager.is_open() {
+                    cheat_manager.close();
+                } else if state_picker.is_open() {
+                    state_picker.close();
+                } else if link_panel.is_open() {
+                    link_panel.close();
+                } else if debugger_panel.is_open() {
+                    debugger_panel.close();
+                } else if vram_viewer.is_open() {
+                    vram_viewer.close();
+                } else if memory_editor.is_open() {
+                    memory_editor.close();
+                } else {
+                    elwt.exit();
+                    return;
+                }
+            }
+
+            if input.key_pressed(CHEATS_KEY) {
+                if cheat_manager.is_open() {
+                    cheat_manager.close();
+                } else {
+                    match read_cheats(game_boy.get_cartridge_header()) {
+                        Ok(cheats) => {
+                            state_picker.close();
+                            link_panel.close();
+                            debugger_panel.close();
+                            vram_viewer.close();
+                            memory_editor.close();
+                            cheat_manager.open(cheats);
+                        }
+                        Err(error) => {
+                            error!("Failed to read the cheats: {error}");
+                            osd.show(&error.to_string());
+                        }
+                    }
+                }
+            }
+
+            if input.key_pressed(STATE_PICKER_KEY) {
+                if state_picker.is_open() {
+                    state_picker.close();
+                } else {
+                    cheat_manager.close();
+                    link_panel.close();
+                    debugger_panel.close();
+                    vram_viewer.close();
+                    memory_editor.close();
+                    let directory = Path::new(DEFAULT_STATES_DIRECTORY);
+                    state_picker.open(read_slots(directory, game_boy.get_cartridge_header()));
+                }
+            }
+
+            if input.key_pressed(LINK_KEY) {
+                if link_panel.is_open() {
+                    link_panel.close();
+                } else {
+                    cheat_manager.close();
+                    state_picker.close();
+                    debugger_panel.close();
+                    vram_viewer.close();
+                    memory_editor.close();
+                    link_panel.open();
+                }
+            }
+
+            if input.key_pressed(DEBUGGER_KEY) {
+                if debugger_panel.is_open() || vram_viewer.is_open() || memory_editor.is_open() {
+                    debugger_panel.close();
+                    vram_viewer.close();
+                    memory_editor.close();
+                } else {
+                    cheat_manager.close();
+                    state_picker.close();
+                    link_panel.close();
+                    debugger_panel.open();
+                }
+            } else if input.key_pressed(NEXT_DEBUG_PANEL_KEY) {
+                if debugger_panel.is_open() {
+                    debugger_panel.close();
+                    vram_viewer.open();
+                } else if vram_viewer.is_open() {
+                    vram_viewer.close();
+                    memory_editor.open();
+                } else if memory_editor.is_open() {
+                    memory_editor.close();
+                    debugger_panel.open();
+                }
+            }
+
+            if input.key_pressed(INPUT_DISPLAY_KEY) {
+                input_display.set_visible(!input_display.is_visible());
+            }
+
+            if input.key_pressed(DIAGNOSTICS_KEY) {
+                diagnostics_panel.set_visible(!diagnostics_panel.is_visible());
+            }
+
+            if input.key_pressed(RESET_KEY) {
+                game_boy.reset();
+                if let Some(frame_blender) = &mut frame_blender {
+                    frame_blender.reset();
+                }
+            }
+
+            if input.key_pressed(COLORIZE_KEY) {
+                game_boy.set_colorize(!game_boy.is_colorized());
+            }
+
+            if input.key_pressed(COLOR_CORRECTION_KEY) {
+                game_boy.set_color_correction(match game_boy.get_color_correction() {
+                    ColorCorrection::Raw => ColorCorrection::Cgb,
+                    ColorCorrection::Cgb => ColorCorrection::Raw,
+                });
+            }
+
+            if input.key_pressed(COLOR_SCHEME_KEY) {
+                // Custom color schemes of a profile continue with the first preset
+                let preset = ColorSchemePreset::find(&game_boy.get_config().color_scheme)
+                    .map_or(ColorSchemePreset::Pocket, |preset| preset.next());
+                game_boy.set_color_scheme(preset.get_color_scheme());
+                osd.show(language.text(preset.into()));
+            }
+
+            if input.key_pressed(LARGE_OSD_TEXT_KEY) {
+                osd.set_large_text(!osd.is_large_text());
+            }
+
+            if input.key_pressed(FRAME_BLENDING_KEY) {
+                frame_blender = match frame_blender {
+                    Some(_) => None,
+                    None => Some(FrameBlender::new()),
+                };
+            }
+
+            // The cursor tilts accelerometer cartridges, the center of the screen is flat
+            if let Some(cursor) = input.cursor() {
+                let (x, y) = pixels
+                    .window_pos_to_pixel(cursor)
+                    .unwrap_or_else(|position| pixels.clamp_pixel_pos(position));
+                game_boy.set_tilt(tilt_axis(x, SCREEN_WIDTH), tilt_axis(y, SCREEN_HEIGHT));
+                // Hovering a tile of the VRAM viewer selects it
+                if vram_viewer.is_open() && input.cursor_diff() != (0.0, 0.0) {
+                    vram_viewer.select_at(x, y);
+                }
+            }
+
             if let Some(size) = input.window_resized() {
                 if let Err(err) = pixels.resize_surface(size.width, size.height) {
                     error!("pixels.resize_surface error: {}", err);
@@ -70,16 +387,440 @@ pub fn run(game_boy: &mut GameBoy) {
                 }
             }
 
-            let frame_start = Instant::now();
+            #[cfg(feature = "rpc")]
+            if let Some(rpc_server) = &rpc_server {
+                rpc_server.handle_pending(&mut controller, game_boy);
+            }
+            link_session.update(game_boy);
+            link_panel.update_activity(link_session.get_transfers());
+            // The game keeps running while the debug panels are open, so they are handled before a pause returns
+            if debugger_panel.is_open() {
+                let debugger = controller.get_debugger_mut();
+                if let Err(error) =
+                    handle_debugger_panel(&input, game_boy, &mut debugger_panel, debugger)
+                {
+                    error!("Debugger: {error}");
+                    osd.show(&error.to_string());
+                }
+            }
+            if vram_viewer.is_open() {
+                handle_vram_viewer(&input, &mut vram_viewer);
+            }
+            if memory_editor.is_open() {
+                if let Err(error) = handle_memory_editor(&input, game_boy, &mut memory_editor) {
+                    error!("Memory editor: {error}");
+                    osd.show(&error.to_string());
+                }
+            }
+            if controller.is_paused() {
+                throttle.wait();
+                window.request_redraw();
+                return;
+            }
+
+            // The game is paused while the cheats are managed
+            if cheat_manager.is_open() {
+                if let Err(error) = handle_cheat_manager(&input, game_boy, &mut cheat_manager) {
+                    error!("Cheats: {error}");
+                    osd.show(&error.to_string());
+                }
+                throttle.wait();
+                window.request_redraw();
+                return;
+            }
+
+            // The game is paused while a session is set up, connecting continues in the background
+            if link_panel.is_open() {
+                if let Err(error) =
+                    handle_link_panel(&input, game_boy, &mut link_panel, &mut link_session)
+                {
+                    error!("Link cable: {error}");
+                    osd.show(&error.to_string());
+                }
+                throttle.wait();
+                window.request_redraw();
+                return;
+            }
+
+            // The game is paused while a slot is picked
+            if state_picker.is_open() {
+                match handle_state_picker(&input, game_boy, &mut state_picker, language) {
+                    Ok(Some(message)) => osd.show(language.text(message)),
+                    Ok(None) => {}
+                    Err(error) => {
+                        error!(
+                            "Save state slot {}: {error}",
+                            state_picker.get_selected() + 1
+                        );
+                        osd.show(&error.to_string());
+                    }
+                }
+                // Loading a slot closes the picker
+                if !state_picker.is_open() {
+                    watch_diagnostics(game_boy, &mut diagnostic_listener, &diagnostic_sender);
+                    if let Some(frame_blender) = &mut frame_blender {
+                        frame_blender.reset();
+                    }
+                }
+                throttle.wait();
+                window.request_redraw();
+                return;
+            }
 
-            game_boy.finish_frame();
-            let elapsed = frame_start.elapsed();
+            match controller.run_frame(game_boy) {
+                DebugEvent::BreakpointHit(address) | DebugEvent::SoftwareBreakpointHit(address) => {
+                    osd.show(&format!(
+                        "{} {address:04X}",
+                        language.text(Text::BreakpointHit)
+                    ));
+                }
+                DebugEvent::FrameFinished | DebugEvent::Paused => {}
+            }
+            for message in controller.get_debugger_mut().take_debug_messages() {
+                info!("Debug message at {:04X}: {}", message.address, message.text);
+                osd.show(&message.text);
+            }
+            if let Some(autosplitter) = &mut autosplitter {
+                for event in autosplitter.update(game_boy) {
+                    #[cfg(feature = "livesplit")]
+                    if let Some(client) = &mut livesplit {
+                        if let Err(error) = client.send(event) {
+                            error!("Lost the connection to LiveSplit: {error}");
+                            livesplit = None;
+                        }
+                    }
+                    osd.show(&split_event_text(event, autosplitter, language));
+                }
+            }
+            for diagnostic in diagnostics.try_iter() {
+                diagnostics_panel.push(&diagnostic);
+                // The panel is hidden by default, so new entries are announced on the OSD
+                if !diagnostics_panel.is_visible() {
+                    osd.show(&diagnostic.to_string());
+                }
+            }
+            throttle.wait();
 
-            if elapsed < FRAME_DURATION {
-                sleep(FRAME_DURATION - elapsed);
+            let lag_frames = game_boy.get_input_stats().get_lag_frames();
+            if lag_frames != shown_lag_frames {
+                shown_lag_frames = lag_frames;
+                window.set_title(&format!(
+                    "LemonGB ({lag_frames} {})",
+                    language.text(Text::LagFrames)
+                ));
             }
 
             window.request_redraw();
         }
     });
 }
+
+/// Forwards the diagnostics of the game to the sender. Loading a save state replaces the listeners,
+/// so this is called again after every load and replaces the previous listener in case it survived.
+fn watch_diagnostics(
+    game_boy: &mut GameBoy,
+    listener: &mut Option<ListenerId>,
+    sender: &Sender<Diagnostic>,
+) {
+    if let Some(id) = listener.take() {
+        game_boy.remove_diagnostic_listener(id);
+    }
+    let sender = sender.clone();
+    *listener = Some(game_boy.on_diagnostic(move |diagnostic| {
+        let _ = sender.send(*diagnostic);
+    }));
+}
+
+fn split_event_text(event: SplitEvent, autosplitter: &Autosplitter, language: Language) -> String {
+    match event {
+        SplitEvent::Start => language.text(Text::TimerStarted).into(),
+        SplitEvent::Split(index) => {
+            let time = autosplitter.get_split_times()[index];
+            format!(
+                "{} {} {}",
+                language.text(Text::Split),
+                index + 1,
+                format_time(time)
+            )
+        }
+        SplitEvent::Reset => language.text(Text::TimerReset).into(),
+    }
+}
+
+/// `.state` files replace the running state, `.sav` files the cartridge RAM, `.png` files what the camera sees
+fn load_dropped_file(
+    game_boy: &mut GameBoy,
+    path: &Path,
+    language: Language,
+) -> Result<Text, Box<dyn Error>> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("state") => {
+            game_boy.load_state(GameBoySaveState::load_any(path)?)?;
+            Ok(Text::SaveStateLoaded)
+        }
+        Some("sav") => {
+            game_boy.load_battery_save_file(path)?;
+            Ok(Text::BatterySaveLoaded)
+        }
+        #[cfg(feature = "image")]
+        Some("png") => {
+            screenshot::load_camera_image(game_boy, path)?;
+            Ok(Text::CameraImageLoaded)
+        }
+        _ => Err(language.text(Text::UnsupportedDroppedFile).into()),
+    }
+}
+
+/// Moves the selection and saves or loads the selected slot, returns a message if one of them happened
+fn handle_state_picker(
+    input: &WinitInputHelper,
+    game_boy: &mut GameBoy,
+    state_picker: &mut StatePicker,
+    language: Language,
+) -> Result<Option<Text>, Box<dyn Error>> {
+    for (key, columns, rows) in [
+        (KeyCode::ArrowLeft, -1, 0),
+        (KeyCode::ArrowRight, 1, 0),
+        (KeyCode::ArrowUp, 0, -1),
+        (KeyCode::ArrowDown, 0, 1),
+    ] {
+        if input.key_pressed(key) {
+            state_picker.move_selection(columns, rows);
+        }
+    }
+
+    let selected = state_picker.get_selected();
+    let path = slot_path(
+        Path::new(DEFAULT_STATES_DIRECTORY),
+        game_boy.get_cartridge_header(),
+        selected,
+    );
+    if input.key_pressed(SAVE_SLOT_KEY) {
+        let state = game_boy.save_with_thumbnail();
+        store_slot(&state, &path)?;
+        state_picker.set_slot(selected, Slot::Saved(state.thumbnail));
+        return Ok(Some(Text::StateSaved));
+    }
+    if input.key_pressed(LOAD_SLOT_KEY) {
+        if state_picker.get_slot(selected) == Some(&Slot::Empty) {
+            return Err(language.text(Text::SlotEmpty).into());
+        }
+        game_boy.load_state(GameBoySaveState::load_any(&path)?)?;
+        state_picker.close();
+        return Ok(Some(Text::StateLoaded));
+    }
+    Ok(None)
+}
+
+/// Changed cheats are activated right away and stored in the game's profile
+fn handle_cheat_manager(
+    input: &WinitInputHelper,
+    game_boy: &mut GameBoy,
+    cheat_manager: &mut CheatManager,
+) -> Result<(), Box<dyn Error>> {
+    let changed = if cheat_manager.is_editing() {
+        for key in input.text() {
+            if let Key::Character(text) = key {
+                cheat_manager.type_text(text);
+            }
+        }
+        if input.key_pressed(KeyCode::Backspace) {
+            cheat_manager.delete_character();
+        }
+        if input.key_pressed(EDIT_CHEAT_KEY) {
+            cheat_manager.finish_editing()?;
+            true
+        } else {
+            false
+        }
+    } else {
+        for (key, rows) in [(KeyCode::ArrowUp, -1), (KeyCode::ArrowDown, 1)] {
+            if input.key_pressed(key) {
+                cheat_manager.move_selection(rows);
+            }
+        }
+        if input.key_pressed(EDIT_CHEAT_KEY) {
+            cheat_manager.start_editing();
+        }
+        (input.key_pressed(TOGGLE_CHEAT_KEY) && cheat_manager.toggle_selected())
+            || (input.key_pressed(REMOVE_CHEAT_KEY) && cheat_manager.remove_selected())
+    };
+
+    if changed {
+        game_boy.set_cheats(cheat_manager.get_cheats())?;
+        store_cheats(game_boy.get_cartridge_header(), cheat_manager.get_cheats())?;
+    }
+    Ok(())
+}
+
+/// Edits the address and hosts, joins or disconnects with the selected action
+fn handle_link_panel(
+    input: &WinitInputHelper,
+    game_boy: &mut GameBoy,
+    link_panel: &mut LinkPanel,
+    link_session: &mut LinkSession,
+) -> Result<(), Box<dyn Error>> {
+    for (key, rows) in [(KeyCode::ArrowUp, -1), (KeyCode::ArrowDown, 1)] {
+        if input.key_pressed(key) {
+            link_panel.move_selection(rows);
+        }
+    }
+    for key in input.text() {
+        if let Key::Character(text) = key {
+            link_panel.type_text(text);
+        }
+    }
+    if input.key_pressed(KeyCode::Backspace) {
+        link_panel.delete_character();
+    }
+
+    if input.key_pressed(LINK_ACTION_KEY) {
+        match link_panel.get_selected_action() {
+            LinkAction::Host => {
+                link_session.host(game_boy, parse_port(link_panel.get_address())?)?
+            }
+            LinkAction::Join => link_session.join(game_boy, link_panel.get_address()),
+            LinkAction::Disconnect => link_session.disconnect(game_boy),
+        }
+    }
+    Ok(())
+}
+
+/// Stepping pauses the game, so the disassembly stays on the executed instruction
+fn handle_debugger_panel(
+    input: &WinitInputHelper,
+    game_boy: &mut GameBoy,
+    debugger_panel: &mut DebuggerPanel,
+    debugger: &mut Debugger,
+) -> Result<(), Box<dyn Error>> {
+    if debugger_panel.is_editing() {
+        for key in input.text() {
+            if let Key::Character(text) = key {
+                debugger_panel.type_text(text);
+            }
+        }
+        if input.key_pressed(KeyCode::Backspace) {
+            debugger_panel.delete_character();
+        }
+        if input.key_pressed(EDIT_REGISTER_KEY) {
+            debugger_panel.finish_editing(game_boy)?;
+        }
+        return Ok(());
+    }
+
+    for (key, rows) in [(KeyCode::ArrowUp, -1), (KeyCode::ArrowDown, 1)] {
+        if input.key_pressed(key) {
+            debugger_panel.move_selection(rows);
+        }
+    }
+    if input.key_pressed(EDIT_REGISTER_KEY) {
+        debugger_panel.start_editing(game_boy);
+    }
+    if input.key_pressed(TOGGLE_BREAKPOINT_KEY) {
+        debugger_panel.toggle_breakpoint(game_boy, debugger);
+    }
+    if input.key_pressed(PAUSE_KEY) {
+        if debugger.is_paused() {
+            debugger.resume();
+        } else {
+            debugger.pause();
+        }
+    }
+    if input.key_pressed(STEP_KEY) {
+        debugger.pause();
+        debugger.step_instruction(game_boy);
+    }
+    if input.key_pressed(SOFTWARE_BREAKPOINTS_KEY) {
+        debugger.set_software_breakpoints(!debugger.has_software_breakpoints());
+    }
+    if input.key_pressed(DEBUG_MESSAGES_KEY) {
+        debugger.set_debug_messages(!debugger.has_debug_messages());
+    }
+    Ok(())
+}
+
+fn handle_vram_viewer(input: &WinitInputHelper, vram_viewer: &mut VramViewer) {
+    for (key, columns, rows) in [
+        (KeyCode::ArrowLeft, -1, 0),
+        (KeyCode::ArrowRight, 1, 0),
+        (KeyCode::ArrowUp, 0, -1),
+        (KeyCode::ArrowDown, 0, 1),
+    ] {
+        if input.key_pressed(key) {
+            vram_viewer.move_selection(columns, rows);
+        }
+    }
+    if input.key_pressed(VRAM_VIEW_KEY) {
+        vram_viewer.next_view();
+    }
+}
+
+fn handle_memory_editor(
+    input: &WinitInputHelper,
+    game_boy: &mut GameBoy,
+    memory_editor: &mut MemoryEditor,
+) -> Result<(), Box<dyn Error>> {
+    if memory_editor.is_editing() {
+        for key in input.text() {
+            if let Key::Character(text) = key {
+                memory_editor.type_text(text);
+            }
+        }
+        if input.key_pressed(KeyCode::Backspace) {
+            memory_editor.delete_character();
+        }
+        if input.key_pressed(EDIT_BYTE_KEY) {
+            memory_editor.finish_input(game_boy)?;
+        }
+        return Ok(());
+    }
+
+    let page = (VISIBLE_ROWS * BYTES_PER_ROW) as isize;
+    for (key, bytes) in [
+        (KeyCode::ArrowLeft, -1),
+        (KeyCode::ArrowRight, 1),
+        (KeyCode::ArrowUp, -(BYTES_PER_ROW as isize)),
+        (KeyCode::ArrowDown, BYTES_PER_ROW as isize),
+        (KeyCode::PageUp, -page),
+        (KeyCode::PageDown, page),
+    ] {
+        if input.key_pressed(key) {
+            memory_editor.move_selection(bytes);
+        }
+    }
+    for (key, memory_input) in [
+        (EDIT_BYTE_KEY, MemoryInput::Value),
+        (GO_TO_KEY, MemoryInput::Address),
+        (SEARCH_KEY, MemoryInput::Search),
+    ] {
+        if input.key_pressed(key) {
+            memory_editor.start_input(memory_input);
+        }
+    }
+    if input.key_pressed(NEXT_MATCH_KEY) {
+        memory_editor.next_match();
+    }
+    Ok(())
+}
+
+/// The cheats of the game's profile, none if it has no profile
+fn read_cheats(header: &CartridgeHeader) -> Result<Vec<Cheat>, Box<dyn Error>> {
+    let profiles = Profiles::load_or_default(Path::new(DEFAULT_PROFILES_PATH))?;
+    Ok(profiles
+        .get(header)
+        .map(|profile| profile.cheats.clone())
+        .unwrap_or_default())
+}
+
+/// Only replaces the cheats, the other settings of the profile are kept
+fn store_cheats(header: &CartridgeHeader, cheats: &[Cheat]) -> Result<(), Box<dyn Error>> {
+    let path = Path::new(DEFAULT_PROFILES_PATH);
+    let mut profiles = Profiles::load_or_default(path)?;
+    profiles.set_cheats(header, cheats.to_vec());
+    Ok(profiles.store(path)?)
+}
+
+/// -1.0 at the first pixel, 1.0 at the last one
+fn tilt_axis(position: usize, size: usize) -> f32 {
+    position as f32 / (size - 1) as f32 * 2.0 - 1.0
+}
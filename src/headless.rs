@@ -0,0 +1,278 @@
+use lemon_gb_core::game_boy::components::mmu::{
+    IE_ADDRESS, IF_ADDRESS, LCDC_ADDRESS, STAT_ADDRESS,
+};
+use lemon_gb_core::game_boy::components::ppu::VBlankInfo;
+use lemon_gb_core::game_boy::GameBoy;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Instructions from PC on disassembled into the diagnostics of a watchdog abort
+const DIAGNOSTIC_INSTRUCTIONS: usize = 5;
+
+/// A single byte of memory which has to hold a specific value.
+/// Stored as the same `ADDRESS=VALUE` text that is parsed from the command line.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MemoryCondition {
+    pub address: u16,
+    pub value: u8,
+}
+
+impl MemoryCondition {
+    pub fn is_met(&self, game_boy: &GameBoy) -> bool {
+        game_boy.peek(self.address) == self.value
+    }
+}
+
+/// Parses `ADDRESS=VALUE` or `ADDRESS == VALUE`, both in hex with an optional `0x` or `$` prefix, e.g. `0xA000=0x00`
+impl FromStr for MemoryCondition {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid memory condition '{s}', expected ADDRESS=VALUE"))?;
+        let value = value.strip_prefix('=').unwrap_or(value);
+        Ok(Self {
+            address: u16::from_str_radix(strip_hex_prefix(address), 16)
+                .map_err(|e| format!("Invalid address '{address}': {e}"))?,
+            value: u8::from_str_radix(strip_hex_prefix(value), 16)
+                .map_err(|e| format!("Invalid value '{value}': {e}"))?,
+        })
+    }
+}
+
+impl TryFrom<String> for MemoryCondition {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<MemoryCondition> for String {
+    fn from(condition: MemoryCondition) -> Self {
+        format!("0x{:04X}=0x{:02X}", condition.address, condition.value)
+    }
+}
+
+impl Display for MemoryCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[0x{:04X}] == 0x{:02X}", self.address, self.value)
+    }
+}
+
+/// Also used by other command line options taking addresses
+pub fn strip_hex_prefix(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .or_else(|| value.strip_prefix('$'))
+        .unwrap_or(value)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessOptions {
+    /// The run fails if none of the exit conditions are met within this many frames
+    pub max_frames: u64,
+    /// The run passes as soon as the serial output contains this text
+    pub exit_on_serial: Option<String>,
+    /// The run fails as soon as the serial output contains this text
+    pub fail_on_serial: Option<String>,
+    /// The run passes as soon as this memory condition is met (checked once per frame)
+    pub exit_on_memory: Option<MemoryCondition>,
+    /// Watchdog: the run is aborted if there is no VBlank for this many T-cycles, e.g. because the LCD stays off
+    pub vblank_timeout: Option<u64>,
+    /// Watchdog: the run is aborted if no serial byte is sent for this many frames
+    pub serial_timeout: Option<u64>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            max_frames: 60 * 60,
+            exit_on_serial: None,
+            fail_on_serial: None,
+            exit_on_memory: None,
+            vblank_timeout: None,
+            serial_timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessResult {
+    pub passed: bool,
+    pub frames: u64,
+    pub reason: String,
+    pub serial_output: String,
+    /// State of the emulator when a watchdog aborted the run
+    pub diagnostics: Option<String>,
+}
+
+impl HeadlessResult {
+    pub fn exit_code(&self) -> i32 {
+        if self.passed {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Serial output followed by a single summary line, meant to be printed to stdout
+    pub fn summary(&self) -> String {
+        let status = if self.passed { "PASSED" } else { "FAILED" };
+        let mut summary = self.serial_output.clone();
+        if !summary.is_empty() && !summary.ends_with('\n') {
+            summary.push('\n');
+        }
+        summary.push_str(&format!(
+            "{status} after {} frames: {}",
+            self.frames, self.reason
+        ));
+        if let Some(diagnostics) = &self.diagnostics {
+            summary.push('\n');
+            summary.push_str(diagnostics);
+        }
+        summary
+    }
+}
+
+/// Runs the emulator frame by frame without any output until an exit condition is met or a watchdog aborts the run
+pub fn run_headless(game_boy: &mut GameBoy, options: &HeadlessOptions) -> HeadlessResult {
+    let last_frame = Arc::new(Mutex::new(None));
+    let recorded = last_frame.clone();
+    let listener =
+        game_boy.on_frame(move |info: &VBlankInfo| *recorded.lock().unwrap() = Some(*info));
+    let mut watchdog = Watchdog::new(game_boy);
+    let mut frames = 0;
+    let mut outcome = None;
+    let mut diagnostics = None;
+
+    while frames < options.max_frames {
+        game_boy.finish_frame();
+        frames += 1;
+
+        outcome = check_conditions(game_boy, options);
+        if outcome.is_some() {
+            break;
+        }
+
+        if let Some(frame) = last_frame.lock().unwrap().take() {
+            if let Some(reason) = watchdog.update(game_boy, &frame, options) {
+                outcome = Some((false, format!("watchdog: {reason}")));
+                diagnostics = Some(describe_state(game_boy));
+                break;
+            }
+        }
+    }
+    game_boy.remove_frame_listener(listener);
+
+    let (passed, reason) = outcome.unwrap_or_else(|| {
+        (
+            false,
+            format!("no exit condition met within {} frames", options.max_frames),
+        )
+    });
+
+    HeadlessResult {
+        passed,
+        frames,
+        reason,
+        serial_output: String::from_utf8_lossy(game_boy.get_serial_output()).to_string(),
+        diagnostics,
+    }
+}
+
+/// Tracks how long it has been since the last VBlank and the last serial byte
+struct Watchdog {
+    cycles_since_vblank: u64,
+    frames_since_serial: u64,
+    serial_length: usize,
+}
+
+impl Watchdog {
+    fn new(game_boy: &GameBoy) -> Self {
+        Self {
+            cycles_since_vblank: 0,
+            frames_since_serial: 0,
+            serial_length: game_boy.get_serial_output().len(),
+        }
+    }
+
+    /// Returns why the run has to be aborted, called after every frame
+    fn update(
+        &mut self,
+        game_boy: &GameBoy,
+        frame: &VBlankInfo,
+        options: &HeadlessOptions,
+    ) -> Option<String> {
+        if frame.lcd_off {
+            self.cycles_since_vblank += frame.cycles_this_frame.as_t() as u64;
+        } else {
+            self.cycles_since_vblank = 0;
+        }
+
+        let serial_length = game_boy.get_serial_output().len();
+        if serial_length != self.serial_length {
+            self.serial_length = serial_length;
+            self.frames_since_serial = 0;
+        } else {
+            self.frames_since_serial += 1;
+        }
+
+        if let Some(timeout) = options.vblank_timeout {
+            if self.cycles_since_vblank >= timeout {
+                return Some(format!("no VBlank within {timeout} cycles"));
+            }
+        }
+        if let Some(timeout) = options.serial_timeout {
+            if self.frames_since_serial >= timeout {
+                return Some(format!("no serial output within {timeout} frames"));
+            }
+        }
+        None
+    }
+}
+
+/// The CPU registers, the interrupt and LCD registers and the next instructions
+fn describe_state(game_boy: &GameBoy) -> String {
+    let mut state = format!(
+        "{}\nIF:{:02X} IE:{:02X} LCDC:{:02X} STAT:{:02X}",
+        game_boy.doctor_log_line(),
+        game_boy.peek(IF_ADDRESS),
+        game_boy.peek(IE_ADDRESS),
+        game_boy.peek(LCDC_ADDRESS),
+        game_boy.peek(STAT_ADDRESS),
+    );
+    for instruction in game_boy.disassemble(game_boy.get_pc(), DIAGNOSTIC_INSTRUCTIONS) {
+        state.push_str(&format!("\n{instruction}"));
+    }
+    state
+}
+
+fn check_conditions(game_boy: &GameBoy, options: &HeadlessOptions) -> Option<(bool, String)> {
+    let serial_output = String::from_utf8_lossy(game_boy.get_serial_output());
+
+    if let Some(text) = &options.fail_on_serial {
+        if serial_output.contains(text.as_str()) {
+            return Some((false, format!("serial output contains '{text}'")));
+        }
+    }
+    if let Some(text) = &options.exit_on_serial {
+        if serial_output.contains(text.as_str()) {
+            return Some((true, format!("serial output contains '{text}'")));
+        }
+    }
+    if let Some(condition) = &options.exit_on_memory {
+        if condition.is_met(game_boy) {
+            return Some((true, format!("memory condition {condition} met")));
+        }
+    }
+
+    None
+}
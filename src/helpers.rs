@@ -1 +0,0 @@
-pub mod bit_operations;
@@ -0,0 +1,72 @@
+//! Shows the held buttons in the top right corner of the frame, so recordings and movie playback show the inputs.
+
+use crate::osd::fill_rectangle;
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::components::ppu::SCREEN_WIDTH;
+
+pub const WIDTH: usize = 42;
+pub const HEIGHT: usize = 18;
+/// Distance to the top and right edge of the screen
+const MARGIN: usize = 1;
+const BACKGROUND_COLOR: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+const RELEASED_COLOR: [u8; 4] = [0x50, 0x50, 0x50, 0xFF];
+const PRESSED_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+
+/// Left, top, width and height of every button inside of the display, laid out like on the Game Boy
+const BUTTON_RECTANGLES: [(Button, usize, usize, usize, usize); 8] = [
+    (Button::Up, 6, 1, 5, 5),
+    (Button::Left, 1, 6, 5, 5),
+    (Button::Right, 11, 6, 5, 5),
+    (Button::Down, 6, 11, 5, 5),
+    (Button::Select, 18, 14, 6, 3),
+    (Button::Start, 26, 14, 6, 3),
+    (Button::B, 27, 6, 6, 6),
+    (Button::A, 35, 2, 6, 6),
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct InputDisplay {
+    visible: bool,
+}
+
+impl InputDisplay {
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Draws the buttons onto an RGBA8888 frame if the display is visible
+    pub fn draw(&self, frame: &mut [u8], buttons: Buttons) {
+        if !self.visible {
+            return;
+        }
+        let (left, top) = get_position();
+        fill_rectangle(frame, left, top, WIDTH, HEIGHT, BACKGROUND_COLOR);
+        for (button, x, y, width, height) in BUTTON_RECTANGLES {
+            let color = if buttons.is_pressed(button) {
+                PRESSED_COLOR
+            } else {
+                RELEASED_COLOR
+            };
+            fill_rectangle(frame, left + x, top + y, width, height, color);
+        }
+    }
+}
+
+/// The top left corner of the display on the screen
+pub fn get_position() -> (usize, usize) {
+    (SCREEN_WIDTH - WIDTH - MARGIN, MARGIN)
+}
+
+/// Where a button is drawn on the screen, as left, top, width and height
+pub fn get_button_rectangle(button: Button) -> (usize, usize, usize, usize) {
+    let (left, top) = get_position();
+    BUTTON_RECTANGLES
+        .iter()
+        .find(|(other, ..)| *other == button)
+        .map(|&(_, x, y, width, height)| (left + x, top + y, width, height))
+        .unwrap_or_default()
+}
@@ -0,0 +1,314 @@
+//! Link cable sessions over TCP: one player hosts on a port, the other joins the host's address.
+//! Every transfer is a message from the side which clocks it, the other side answers with the byte it shifted out.
+//! The clocking side waits for the answer, so transfers are only as fast as the round trip to the partner.
+
+use lemon_gb_core::game_boy::components::serial::SerialLink;
+use lemon_gb_core::game_boy::GameBoy;
+use std::error::Error;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PORT: u16 = 8766;
+/// How long a transfer waits for the partner's answer before it shifts in 0xFF like an unplugged cable
+pub const EXCHANGE_TIMEOUT: Duration = Duration::from_millis(100);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Checking the socket on every CPU step would slow the emulation down, so only every nth poll reads it
+const POLL_INTERVAL: u32 = 64;
+
+const TRANSFER: u8 = 0x01;
+const REPLY: u8 = 0x02;
+/// Kind, sequence number and the transferred byte. Replies carry the number of their transfer,
+/// so a reply which arrives after the timeout isn't taken for the answer to the next transfer.
+const MESSAGE_LENGTH: usize = 3;
+
+/// What the frontend sees of a connection the Game Boy owns
+#[derive(Debug, Default)]
+pub struct LinkActivity {
+    transfers: AtomicU64,
+    lost: AtomicBool,
+}
+
+impl LinkActivity {
+    /// Transfers in both directions, the activity LED lights up whenever this changes
+    pub fn get_transfers(&self) -> u64 {
+        self.transfers.load(Ordering::Relaxed)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Relaxed)
+    }
+}
+
+/// A partner connected over TCP, plugged into the Game Boy with [`GameBoy::connect_link`].
+/// Once the connection is lost every transfer shifts in 0xFF.
+pub struct TcpLink {
+    stream: TcpStream,
+    /// Bytes of messages which didn't arrive completely yet
+    received: Vec<u8>,
+    /// Of the last transfer this side clocked
+    sequence: u8,
+    /// Of the last transfer the partner clocked
+    partner_sequence: u8,
+    polls: u32,
+    activity: Arc<LinkActivity>,
+}
+
+impl TcpLink {
+    pub fn new(stream: TcpStream) -> Result<Self, Box<dyn Error>> {
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            received: Vec::new(),
+            sequence: 0,
+            partner_sequence: 0,
+            polls: 0,
+            activity: Arc::default(),
+        })
+    }
+
+    pub fn get_activity(&self) -> Arc<LinkActivity> {
+        self.activity.clone()
+    }
+
+    fn send(&mut self, kind: u8, sequence: u8, byte: u8) {
+        let result = self
+            .stream
+            .set_nonblocking(false)
+            .and_then(|_| self.stream.write_all(&[kind, sequence, byte]));
+        if result.is_err() {
+            self.activity.lost.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Waits for the next message until the deadline, without one only what already arrived is read
+    fn receive(&mut self, deadline: Option<Instant>) -> Option<[u8; MESSAGE_LENGTH]> {
+        while self.received.len() < MESSAGE_LENGTH {
+            if self.activity.is_lost() {
+                return None;
+            }
+            let timeout = match deadline {
+                Some(deadline) => Some(
+                    deadline
+                        .checked_duration_since(Instant::now())
+                        .filter(|remaining| !remaining.is_zero())?,
+                ),
+                None => None,
+            };
+            let mut buffer = [0; 64];
+            let result = self
+                .stream
+                .set_nonblocking(timeout.is_none())
+                .and_then(|_| self.stream.set_read_timeout(timeout))
+                .and_then(|_| self.stream.read(&mut buffer));
+            match result {
+                Ok(0) => self.activity.lost.store(true, Ordering::Relaxed),
+                Ok(length) => self.received.extend_from_slice(&buffer[..length]),
+                Err(error)
+                    if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    // Nothing arrived yet, which is only worth another try while waiting
+                    deadline?;
+                }
+                Err(_) => self.activity.lost.store(true, Ordering::Relaxed),
+            }
+        }
+        let message = [self.received[0], self.received[1], self.received[2]];
+        self.received.drain(..MESSAGE_LENGTH);
+        Some(message)
+    }
+
+    fn count_transfer(&self) {
+        self.activity.transfers.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl SerialLink for TcpLink {
+    fn exchange(&mut self, byte: u8) -> Option<u8> {
+        self.sequence = self.sequence.wrapping_add(1);
+        self.send(TRANSFER, self.sequence, byte);
+        let deadline = Instant::now() + EXCHANGE_TIMEOUT;
+        while let Some([kind, sequence, value]) = self.receive(Some(deadline)) {
+            match kind {
+                REPLY if sequence == self.sequence => {
+                    self.count_transfer();
+                    return Some(value);
+                }
+                // Both sides clock a transfer at the same time, neither of them receives anything
+                TRANSFER => self.send(REPLY, sequence, 0xFF),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn poll(&mut self) -> Option<u8> {
+        self.polls += 1;
+        if self.polls < POLL_INTERVAL {
+            return None;
+        }
+        self.polls = 0;
+        // Late replies to transfers which timed out are dropped
+        while let Some([kind, sequence, value]) = self.receive(None) {
+            if kind == TRANSFER {
+                self.partner_sequence = sequence;
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn respond(&mut self, byte: u8) {
+        self.send(REPLY, self.partner_sequence, byte);
+        self.count_transfer();
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum LinkState {
+    #[default]
+    Disconnected,
+    /// Waiting for the partner to join
+    Hosting(SocketAddr),
+    Joining(String),
+    /// To the address of the partner
+    Connected(SocketAddr),
+    /// Why the last session couldn't be started or ended
+    Failed(String),
+}
+
+/// Hosts or joins a session in the background and plugs the connection into the Game Boy
+#[derive(Default)]
+pub struct LinkSession {
+    state: LinkState,
+    listener: Option<TcpListener>,
+    connecting: Option<Receiver<Result<TcpStream, String>>>,
+    activity: Option<Arc<LinkActivity>>,
+}
+
+impl LinkSession {
+    /// Ends the previous session and accepts the first partner which joins on the port, port 0 picks a free one
+    pub fn host(&mut self, game_boy: &mut GameBoy, port: u16) -> Result<(), Box<dyn Error>> {
+        self.disconnect(game_boy);
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| format!("Unable to host a link session on port {port}: {e}"))?;
+        listener.set_nonblocking(true)?;
+        self.state = LinkState::Hosting(listener.local_addr()?);
+        self.listener = Some(listener);
+        Ok(())
+    }
+
+    /// Ends the previous session and connects in the background, the port defaults to [`DEFAULT_PORT`]
+    pub fn join(&mut self, game_boy: &mut GameBoy, address: &str) {
+        self.disconnect(game_boy);
+        let address = with_default_port(address);
+        let (sender, receiver) = channel();
+        let target = address.clone();
+        thread::spawn(move || {
+            let _ = sender.send(connect(&target));
+        });
+        self.state = LinkState::Joining(address);
+        self.connecting = Some(receiver);
+    }
+
+    /// Unplugs the partner from the Game Boy
+    pub fn disconnect(&mut self, game_boy: &mut GameBoy) {
+        if self.activity.take().is_some() {
+            game_boy.disconnect_link();
+        }
+        self.listener = None;
+        self.connecting = None;
+        self.state = LinkState::Disconnected;
+    }
+
+    /// Plugs a partner which just connected into the Game Boy and notices lost connections,
+    /// has to be called regularly by the frontend
+    pub fn update(&mut self, game_boy: &mut GameBoy) {
+        let connection = if let Some(listener) = &self.listener {
+            match listener.accept() {
+                Ok((stream, _)) => Some(Ok(stream)),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => None,
+                Err(error) => Some(Err(format!("Unable to accept the partner: {error}"))),
+            }
+        } else {
+            self.connecting
+                .as_ref()
+                .and_then(|connecting| connecting.try_recv().ok())
+        };
+        if let Some(connection) = connection {
+            self.listener = None;
+            self.connecting = None;
+            if let Err(error) = connection.and_then(|stream| self.plug_in(game_boy, stream)) {
+                self.state = LinkState::Failed(error);
+            }
+        }
+
+        if let Some(activity) = &self.activity {
+            // The Game Boy drops the link when a different one replaces it
+            if activity.is_lost() || Arc::strong_count(activity) == 1 {
+                self.disconnect(game_boy);
+                self.state = LinkState::Failed("Lost the connection to the partner".to_string());
+            }
+        }
+    }
+
+    pub fn get_state(&self) -> &LinkState {
+        &self.state
+    }
+
+    /// Of the current connection, see [`LinkActivity::get_transfers`]
+    pub fn get_transfers(&self) -> u64 {
+        self.activity
+            .as_ref()
+            .map_or(0, |activity| activity.get_transfers())
+    }
+
+    fn plug_in(&mut self, game_boy: &mut GameBoy, stream: TcpStream) -> Result<(), String> {
+        let partner = stream.peer_addr().map_err(|e| e.to_string())?;
+        let link = TcpLink::new(stream).map_err(|e| e.to_string())?;
+        self.activity = Some(link.get_activity());
+        game_boy.connect_link(link);
+        self.state = LinkState::Connected(partner);
+        Ok(())
+    }
+}
+
+fn connect(address: &str) -> Result<TcpStream, String> {
+    let unreachable = |e: &dyn Error| format!("Unable to join {address}: {e}");
+    let socket_address = address
+        .to_socket_addrs()
+        .map_err(|e| unreachable(&e))?
+        .next()
+        .ok_or_else(|| format!("Unable to join {address}: unknown address"))?;
+    TcpStream::connect_timeout(&socket_address, CONNECT_TIMEOUT).map_err(|e| unreachable(&e))
+}
+
+/// Appends [`DEFAULT_PORT`] to an address without a port.
+/// IPv6 addresses only have a port in brackets like `[::1]:8766`, a bare one like `::1` is put into brackets.
+pub fn with_default_port(address: &str) -> String {
+    let address = address.trim();
+    match address.rsplit_once(':') {
+        Some((host, port))
+            if (!host.contains(':') || host.ends_with(']')) && port.parse::<u16>().is_ok() =>
+        {
+            address.to_string()
+        }
+        Some(_) if !address.starts_with('[') => format!("[{address}]:{DEFAULT_PORT}"),
+        _ => format!("{address}:{DEFAULT_PORT}"),
+    }
+}
+
+/// The port of an address like `192.168.0.2:8766`, a port on its own or [`DEFAULT_PORT`] if the input is empty
+pub fn parse_port(input: &str) -> Result<u16, Box<dyn Error>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(DEFAULT_PORT);
+    }
+    let port = input.rsplit_once(':').map_or(input, |(_, port)| port);
+    port.parse()
+        .map_err(|_| format!("Invalid port '{port}', expected a number up to 65535").into())
+}
@@ -0,0 +1,203 @@
+//! Panel drawn over the whole screen to host or join a link cable session, see [`LinkSession`](crate::link_cable::LinkSession).
+//! The address is typed on top, the actions are listed below it and the state of the session at the bottom.
+//! While a partner is connected the activity LED stays in the corner after the panel was closed.
+
+use crate::link_cable::LinkState;
+use crate::locale::{Language, Text};
+use crate::osd::{fill_rectangle, wrap_text, CHARACTER_WIDTH};
+use crate::panel::{
+    draw_background, draw_column, highlight_line, Panel, PanelState, TextInput, ACTIVE_COLOR,
+    INACTIVE_COLOR, PADDING, TEXT_COLOR,
+};
+use lemon_gb_core::game_boy::components::ppu::SCREEN_WIDTH;
+
+/// Fits onto a line next to the cursor
+pub const MAX_ADDRESS_LENGTH: usize = 24;
+/// How long the LED stays lit after a transfer, in calls of [`LinkPanel::update_activity`]
+pub const LED_FRAMES: u8 = 4;
+const LED_SIZE: usize = 5;
+const ERROR_COLOR: [u8; 4] = [0xFF, 0x60, 0x60, 0xFF];
+const LED_ON_COLOR: [u8; 4] = ACTIVE_COLOR;
+const LED_OFF_COLOR: [u8; 4] = [0x20, 0x50, 0x20, 0xFF];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LinkAction {
+    /// On the port of the address
+    Host,
+    Join,
+    Disconnect,
+}
+
+impl LinkAction {
+    /// In the order of the panel
+    pub const ALL: [LinkAction; 3] = [LinkAction::Host, LinkAction::Join, LinkAction::Disconnect];
+
+    fn label(&self) -> Text {
+        match self {
+            Self::Host => Text::LinkHostLabel,
+            Self::Join => Text::LinkJoinLabel,
+            Self::Disconnect => Text::LinkDisconnectLabel,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkPanel {
+    state: PanelState,
+    /// Always typed, independent of the input of the state
+    address: TextInput,
+    /// Index into [`LinkAction::ALL`]
+    selected: usize,
+    last_transfers: u64,
+    led_frames: u8,
+    /// Of the labels
+    language: Language,
+}
+
+impl Default for LinkPanel {
+    fn default() -> Self {
+        Self {
+            state: PanelState::default(),
+            address: TextInput::new("", MAX_ADDRESS_LENGTH, address_character),
+            selected: 0,
+            last_transfers: 0,
+            led_frames: 0,
+            language: Language::default(),
+        }
+    }
+}
+
+impl LinkPanel {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_address(&self) -> &str {
+        self.address.get_text()
+    }
+
+    /// Leaving the list on one end enters it on the other
+    pub fn move_selection(&mut self, rows: isize) {
+        let actions = LinkAction::ALL.len() as isize;
+        self.selected = (self.selected as isize + rows).rem_euclid(actions) as usize;
+    }
+
+    pub fn get_selected_action(&self) -> LinkAction {
+        LinkAction::ALL[self.selected]
+    }
+
+    /// Lights the LED for [`LED_FRAMES`] calls whenever the transfer count changed, has to be called once per frame
+    pub fn update_activity(&mut self, transfers: u64) {
+        if transfers != self.last_transfers {
+            self.last_transfers = transfers;
+            self.led_frames = LED_FRAMES;
+        } else {
+            self.led_frames = self.led_frames.saturating_sub(1);
+        }
+    }
+
+    pub fn is_led_lit(&self) -> bool {
+        self.led_frames > 0
+    }
+
+    /// Draws the panel onto an RGBA8888 frame if it is open, otherwise only the LED while connected
+    pub fn draw(&self, frame: &mut [u8], state: &LinkState) {
+        let connected = matches!(state, LinkState::Connected(_));
+        if !self.is_open() {
+            if connected {
+                self.draw_led(frame);
+            }
+            return;
+        }
+        draw_background(frame);
+        draw_column(
+            frame,
+            0,
+            0,
+            self.language.text(Text::LinkCableLabel),
+            TEXT_COLOR,
+        );
+        if connected {
+            self.draw_led(frame);
+        }
+        draw_column(
+            frame,
+            0,
+            1,
+            self.language.text(Text::LinkAddressLabel),
+            INACTIVE_COLOR,
+        );
+        draw_column(frame, 0, 2, &self.address.with_cursor(), TEXT_COLOR);
+
+        for (index, action) in LinkAction::ALL.iter().enumerate() {
+            let line = 4 + index;
+            if index == self.selected {
+                highlight_line(frame, line);
+            }
+            draw_column(
+                frame,
+                0,
+                line,
+                self.language.text(action.label()),
+                TEXT_COLOR,
+            );
+        }
+
+        let (status, details, color) = match state {
+            LinkState::Disconnected => (Text::LinkNotConnectedLabel, String::new(), INACTIVE_COLOR),
+            LinkState::Hosting(address) => (
+                Text::LinkWaitingLabel,
+                address.port().to_string(),
+                TEXT_COLOR,
+            ),
+            LinkState::Joining(address) => (Text::LinkConnectingLabel, address.clone(), TEXT_COLOR),
+            LinkState::Connected(partner) => {
+                (Text::LinkConnectedLabel, partner.to_string(), LED_ON_COLOR)
+            }
+            LinkState::Failed(error) => (Text::LinkNotConnectedLabel, error.clone(), ERROR_COLOR),
+        };
+        draw_column(frame, 0, 8, self.language.text(status), color);
+        let max_line_length = (SCREEN_WIDTH - 2 * PADDING) / CHARACTER_WIDTH;
+        for (index, line) in wrap_text(&details, max_line_length).iter().enumerate() {
+            draw_column(frame, 0, 9 + index, line, color);
+        }
+    }
+
+    /// In the top right corner
+    fn draw_led(&self, frame: &mut [u8]) {
+        let color = if self.is_led_lit() {
+            LED_ON_COLOR
+        } else {
+            LED_OFF_COLOR
+        };
+        let left = SCREEN_WIDTH - LED_SIZE - PADDING;
+        fill_rectangle(frame, left, PADDING, LED_SIZE, LED_SIZE, color);
+    }
+}
+
+/// The address and the selection are kept when the panel is closed
+impl Panel for LinkPanel {
+    fn get_state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+
+    /// Appends the characters of host names, IP addresses and ports up to [`MAX_ADDRESS_LENGTH`]
+    fn type_text(&mut self, text: &str) {
+        self.address.type_text(text);
+    }
+
+    fn delete_character(&mut self) {
+        self.address.delete_character();
+    }
+}
+
+fn address_character(character: char) -> Option<char> {
+    (character.is_ascii_alphanumeric() || matches!(character, '.' | ':' | '-')).then_some(character)
+}
@@ -0,0 +1,267 @@
+//! The texts the frontend shows in the window title, on screen messages and the state picker, one table per language.
+//! Messages of the emulation core, e.g. why a save state doesn't fit the cartridge, stay in English.
+//! Every text has to be drawable with the [font](crate::font), which has no ß.
+
+use lemon_gb_core::game_boy::components::ppu::color_scheme_preset::ColorSchemePreset;
+use std::str::FromStr;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Picks the language of a locale like `de_DE.UTF-8` from the LANG environment variable, English if it isn't supported
+    pub fn from_env() -> Self {
+        std::env::var("LANG")
+            .ok()
+            .and_then(|locale| locale.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get_code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn text(&self, text: Text) -> &'static str {
+        match self {
+            Language::English => english(text),
+            Language::German => german(text),
+        }
+    }
+}
+
+/// Accepts the language code, optionally followed by a region and encoding like `de_AT.UTF-8`
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(locale: &str) -> Result<Self, Self::Err> {
+        let code = locale
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.get_code() == code)
+            .ok_or_else(|| format!("Unsupported language '{locale}'"))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Text {
+    SaveStateLoaded,
+    BatterySaveLoaded,
+    CameraImageLoaded,
+    UnsupportedDroppedFile,
+    StateSaved,
+    StateLoaded,
+    SlotEmpty,
+    /// The label of an empty slot in the state picker, at most 13 characters
+    EmptySlotLabel,
+    /// The label of a slot without a thumbnail in the state picker, at most 13 characters
+    NoPreviewLabel,
+    /// Appended to the count in the window title
+    LagFrames,
+    PocketColorScheme,
+    DeuteranopiaColorScheme,
+    ProtanopiaColorScheme,
+    HighContrastColorScheme,
+    TimerStarted,
+    /// Followed by the number of the split and its time
+    Split,
+    TimerReset,
+    /// The title of the cheats manager
+    CheatsLabel,
+    /// The line which adds a cheat in the cheats manager, at most 22 characters
+    NewCheatLabel,
+    /// Status of an active cheat in the cheats manager, at most 3 characters
+    CheatOnLabel,
+    /// Status of a disabled cheat in the cheats manager, at most 3 characters
+    CheatOffLabel,
+    /// The title of the link cable panel
+    LinkCableLabel,
+    LinkAddressLabel,
+    LinkHostLabel,
+    LinkJoinLabel,
+    LinkDisconnectLabel,
+    LinkNotConnectedLabel,
+    /// Followed by the port on the next line
+    LinkWaitingLabel,
+    /// Followed by the address on the next line
+    LinkConnectingLabel,
+    /// Followed by the address of the partner on the next line
+    LinkConnectedLabel,
+    /// Followed by the address at which the game was paused
+    BreakpointHit,
+    DebuggerLabel,
+    DebuggerPausedLabel,
+    DebuggerRunningLabel,
+    TileDataLabel,
+    /// Followed by the address of the map
+    TileMapLabel,
+    TileLabel,
+    MemoryLabel,
+    ValueLabel,
+    GoToLabel,
+    SearchLabel,
+}
+
+impl Text {
+    pub const ALL: [Text; 41] = [
+        Text::SaveStateLoaded,
+        Text::BatterySaveLoaded,
+        Text::CameraImageLoaded,
+        Text::UnsupportedDroppedFile,
+        Text::StateSaved,
+        Text::StateLoaded,
+        Text::SlotEmpty,
+        Text::EmptySlotLabel,
+        Text::NoPreviewLabel,
+        Text::LagFrames,
+        Text::PocketColorScheme,
+        Text::DeuteranopiaColorScheme,
+        Text::ProtanopiaColorScheme,
+        Text::HighContrastColorScheme,
+        Text::TimerStarted,
+        Text::Split,
+        Text::TimerReset,
+        Text::CheatsLabel,
+        Text::NewCheatLabel,
+        Text::CheatOnLabel,
+        Text::CheatOffLabel,
+        Text::LinkCableLabel,
+        Text::LinkAddressLabel,
+        Text::LinkHostLabel,
+        Text::LinkJoinLabel,
+        Text::LinkDisconnectLabel,
+        Text::LinkNotConnectedLabel,
+        Text::LinkWaitingLabel,
+        Text::LinkConnectingLabel,
+        Text::LinkConnectedLabel,
+        Text::BreakpointHit,
+        Text::DebuggerLabel,
+        Text::DebuggerPausedLabel,
+        Text::DebuggerRunningLabel,
+        Text::TileDataLabel,
+        Text::TileMapLabel,
+        Text::TileLabel,
+        Text::MemoryLabel,
+        Text::ValueLabel,
+        Text::GoToLabel,
+        Text::SearchLabel,
+    ];
+}
+
+/// The name of the preset
+impl From<ColorSchemePreset> for Text {
+    fn from(preset: ColorSchemePreset) -> Self {
+        match preset {
+            ColorSchemePreset::Pocket => Text::PocketColorScheme,
+            ColorSchemePreset::Deuteranopia => Text::DeuteranopiaColorScheme,
+            ColorSchemePreset::Protanopia => Text::ProtanopiaColorScheme,
+            ColorSchemePreset::HighContrast => Text::HighContrastColorScheme,
+        }
+    }
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::SaveStateLoaded => "Save state loaded",
+        Text::BatterySaveLoaded => "Battery save loaded",
+        Text::CameraImageLoaded => "Camera image loaded",
+        Text::UnsupportedDroppedFile => "Only .state, .sav and .png files can be dropped",
+        Text::StateSaved => "State saved",
+        Text::StateLoaded => "State loaded",
+        Text::SlotEmpty => "The slot is empty",
+        Text::EmptySlotLabel => "EMPTY",
+        Text::NoPreviewLabel => "NO PREVIEW",
+        Text::LagFrames => "lag frames",
+        Text::PocketColorScheme => "Pocket colors",
+        Text::DeuteranopiaColorScheme => "Deuteranopia colors",
+        Text::ProtanopiaColorScheme => "Protanopia colors",
+        Text::HighContrastColorScheme => "High contrast",
+        Text::TimerStarted => "Timer started",
+        Text::Split => "Split",
+        Text::TimerReset => "Timer reset",
+        Text::CheatsLabel => "CHEATS",
+        Text::NewCheatLabel => "NEW CODE",
+        Text::CheatOnLabel => "ON",
+        Text::CheatOffLabel => "OFF",
+        Text::LinkCableLabel => "LINK CABLE",
+        Text::LinkAddressLabel => "ADDRESS",
+        Text::LinkHostLabel => "HOST",
+        Text::LinkJoinLabel => "JOIN",
+        Text::LinkDisconnectLabel => "DISCONNECT",
+        Text::LinkNotConnectedLabel => "NOT CONNECTED",
+        Text::LinkWaitingLabel => "WAITING ON PORT",
+        Text::LinkConnectingLabel => "CONNECTING TO",
+        Text::LinkConnectedLabel => "CONNECTED TO",
+        Text::BreakpointHit => "Breakpoint at",
+        Text::DebuggerLabel => "DEBUGGER",
+        Text::DebuggerPausedLabel => "PAUSED",
+        Text::DebuggerRunningLabel => "RUNNING",
+        Text::TileDataLabel => "TILE DATA",
+        Text::TileMapLabel => "TILE MAP",
+        Text::TileLabel => "TILE",
+        Text::MemoryLabel => "MEMORY",
+        Text::ValueLabel => "VALUE",
+        Text::GoToLabel => "GO TO",
+        Text::SearchLabel => "SEARCH",
+    }
+}
+
+fn german(text: Text) -> &'static str {
+    match text {
+        Text::SaveStateLoaded => "Spielstand geladen",
+        Text::BatterySaveLoaded => "Batteriespeicher geladen",
+        Text::CameraImageLoaded => "Kamerabild geladen",
+        Text::UnsupportedDroppedFile => {
+            "Nur .state-, .sav- und .png-Dateien können abgelegt werden"
+        }
+        Text::StateSaved => "Spielstand gespeichert",
+        Text::StateLoaded => "Spielstand geladen",
+        Text::SlotEmpty => "Der Platz ist leer",
+        Text::EmptySlotLabel => "LEER",
+        Text::NoPreviewLabel => "OHNE VORSCHAU",
+        Text::LagFrames => "Lag-Frames",
+        Text::PocketColorScheme => "Pocket-Farben",
+        Text::DeuteranopiaColorScheme => "Farben für Deuteranopie",
+        Text::ProtanopiaColorScheme => "Farben für Protanopie",
+        Text::HighContrastColorScheme => "Hoher Kontrast",
+        Text::TimerStarted => "Timer gestartet",
+        Text::Split => "Zwischenzeit",
+        Text::TimerReset => "Timer zurückgesetzt",
+        Text::CheatsLabel => "CHEAT-CODES",
+        Text::NewCheatLabel => "NEUER CODE",
+        Text::CheatOnLabel => "AN",
+        Text::CheatOffLabel => "AUS",
+        Text::LinkCableLabel => "LINK-KABEL",
+        Text::LinkAddressLabel => "ADRESSE",
+        Text::LinkHostLabel => "HOSTEN",
+        Text::LinkJoinLabel => "BEITRETEN",
+        Text::LinkDisconnectLabel => "TRENNEN",
+        Text::LinkNotConnectedLabel => "NICHT VERBUNDEN",
+        Text::LinkWaitingLabel => "WARTET AUF PORT",
+        Text::LinkConnectingLabel => "VERBINDE MIT",
+        Text::LinkConnectedLabel => "VERBUNDEN MIT",
+        Text::BreakpointHit => "Haltepunkt bei",
+        Text::DebuggerLabel => "DEBUGGEN",
+        Text::DebuggerPausedLabel => "ANGEHALTEN",
+        Text::DebuggerRunningLabel => "LÄUFT",
+        Text::TileDataLabel => "KACHELDATEN",
+        Text::TileMapLabel => "KACHELKARTE",
+        Text::TileLabel => "KACHEL",
+        Text::MemoryLabel => "SPEICHER",
+        Text::ValueLabel => "WERT",
+        Text::GoToLabel => "GEHE ZU",
+        Text::SearchLabel => "SUCHE",
+    }
+}
@@ -0,0 +1,86 @@
+//! Log output of the frontend: env_logger behind a filter with one level per [`Subsystem`] of the core.
+//! Levels can be changed at any time, messages of other targets use the default level.
+
+use lemon_gb_core::logging::Subsystem;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DEFAULT_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Error as usize);
+static SUBSYSTEM_LEVELS: [AtomicUsize; Subsystem::ALL.len()] =
+    [const { AtomicUsize::new(LevelFilter::Error as usize) }; Subsystem::ALL.len()];
+
+/// Installs env_logger behind the per-subsystem filter, every level starts at `default_level`
+pub fn init(default_level: LevelFilter) -> Result<(), log::SetLoggerError> {
+    let inner = env_logger::Builder::new()
+        .filter_level(LevelFilter::Trace)
+        .build();
+    log::set_boxed_logger(Box::new(SubsystemLogger { inner }))?;
+
+    set_default_level(default_level);
+    for subsystem in Subsystem::ALL {
+        set_level(subsystem, default_level);
+    }
+    Ok(())
+}
+
+pub fn set_level(subsystem: Subsystem, level: LevelFilter) {
+    SUBSYSTEM_LEVELS[subsystem as usize].store(level as usize, Ordering::Relaxed);
+    update_max_level();
+}
+
+pub fn get_level(subsystem: Subsystem) -> LevelFilter {
+    level_from_usize(SUBSYSTEM_LEVELS[subsystem as usize].load(Ordering::Relaxed))
+}
+
+/// Level of every target which doesn't belong to a subsystem
+pub fn set_default_level(level: LevelFilter) {
+    DEFAULT_LEVEL.store(level as usize, Ordering::Relaxed);
+    update_max_level();
+}
+
+pub fn get_default_level() -> LevelFilter {
+    level_from_usize(DEFAULT_LEVEL.load(Ordering::Relaxed))
+}
+
+/// The level that applies to messages of the given target
+pub fn get_target_level(target: &str) -> LevelFilter {
+    match Subsystem::from_target(target) {
+        Some(subsystem) => get_level(subsystem),
+        None => get_default_level(),
+    }
+}
+
+/// The log macros bail out early below the global maximum, keep it as low as possible so disabled logs stay cheap
+fn update_max_level() {
+    let max = Subsystem::ALL
+        .into_iter()
+        .map(get_level)
+        .chain([get_default_level()])
+        .max()
+        .unwrap_or(LevelFilter::Off);
+    log::set_max_level(max);
+}
+
+fn level_from_usize(value: usize) -> LevelFilter {
+    LevelFilter::iter().nth(value).unwrap_or(LevelFilter::Trace)
+}
+
+struct SubsystemLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for SubsystemLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= get_target_level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
@@ -1,28 +1,137 @@
-use crate::game_boy::components::cartridge::Cartridge;
-use crate::game_boy::GameBoy;
+use crate::autosplit::Autosplitter;
+use crate::cli::Command;
+use crate::headless::run_headless;
+use crate::locale::Language;
+use crate::profiles::{Profiles, DEFAULT_PROFILES_PATH};
+use crate::rom_library::RomLibrary;
+use crate::scenario::{run_scenario, Scenario};
+use crate::settings::FrontendSettings;
+use lemon_gb_core::disassembler::listing::disassemble_cartridge;
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::config::GameBoyConfig;
+use lemon_gb_core::game_boy::GameBoy;
+use lemon_gb_core::input::movie::import_movie;
+use lemon_gb_core::input::replay::{play_replay, FrameChecksums, FrameVerification};
 use log::LevelFilter;
-use std::path::PathBuf;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::exit;
 
-pub mod enums;
-pub mod game_boy;
+pub mod autosplit;
+pub mod built_in;
+pub mod cheat_manager;
+mod cli;
+pub mod debugger;
+pub mod debugger_panel;
+pub mod diagnostics_panel;
+pub mod font;
+pub mod frame_blending;
 #[cfg(feature = "gui")]
 mod gui;
-mod helpers;
-pub mod instructions;
+pub mod headless;
+pub mod input_display;
+pub mod link_cable;
+pub mod link_panel;
+pub mod locale;
+pub mod logging;
+pub mod memory_editor;
+pub mod osd;
+pub mod panel;
+pub mod profiles;
+pub mod rom_library;
+pub mod rpc;
+pub mod scenario;
+#[cfg(feature = "image")]
+pub mod screenshot;
+pub mod settings;
+pub mod state_picker;
 #[cfg(test)]
 mod tests;
+pub mod throttle;
+pub mod vram_viewer;
 
 fn main() {
-    env_logger::Builder::new()
-        .filter_level(LevelFilter::Error)
-        .init();
+    logging::init(LevelFilter::Error).expect("Failed to initialize logging");
 
-    let path = PathBuf::from("./test_roms/cpu_instrs.gb");
-    let cartridge = Cartridge::load(path).unwrap();
-    let mut game_boy = GameBoy::initialize(&cartridge);
+    let command = cli::parse_args(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{error}\n\n{}", cli::USAGE);
+        exit(2);
+    });
+
+    let cartridge = match command {
+        Some(Command::RunHeadless { rom, options }) => {
+            let cartridge = load_cartridge(rom);
+            let mut game_boy = initialize_game_boy(&cartridge, &load_profiles());
+            let result = run_headless(&mut game_boy, &options);
+            println!("{}", result.summary());
+            exit(result.exit_code());
+        }
+        Some(Command::Disassemble { rom, options }) => {
+            let cartridge = load_cartridge(rom);
+            match disassemble_cartridge(&cartridge, &options) {
+                Ok(listing) => print!("{listing}"),
+                Err(error) => {
+                    eprintln!("Failed to disassemble {}: {error}", cartridge.header.title);
+                    exit(1);
+                }
+            }
+            exit(0);
+        }
+        Some(Command::Scan { directory, output }) => {
+            if let Err(error) = scan_rom_library(&directory, output.as_deref()) {
+                eprintln!("Failed to scan {}: {error}", directory.display());
+                exit(1);
+            }
+            exit(0);
+        }
+        Some(Command::Replay {
+            rom,
+            movie,
+            record,
+            verify,
+        }) => {
+            let cartridge = load_cartridge(rom);
+            let mut game_boy = initialize_game_boy(&cartridge, &load_profiles());
+            match replay_movie(&mut game_boy, &movie, record.as_deref(), verify.as_deref()) {
+                Ok(diverged) => exit(diverged as i32),
+                Err(error) => {
+                    eprintln!("Failed to replay {}: {error}", movie.display());
+                    exit(1);
+                }
+            }
+        }
+        Some(Command::Scenario { path }) => {
+            if let Err(error) = play_scenario(&path) {
+                eprintln!("Scenario {} failed: {error}", path.display());
+                exit(1);
+            }
+            exit(0);
+        }
+        Some(Command::Info { rom }) => {
+            println!("{}", load_cartridge(rom).header.describe());
+            exit(0);
+        }
+        Some(Command::Run { rom }) => load_cartridge(rom),
+        // Without a ROM the built-in one shows how to start a game
+        None => built_in::cartridge(),
+    };
+    let profiles = load_profiles();
+    #[cfg_attr(not(feature = "gui"), allow(unused_mut, unused_variables))]
+    let mut game_boy = initialize_game_boy(&cartridge, &profiles);
+    let base = FrontendSettings {
+        language: Language::from_env(),
+        ..FrontendSettings::default()
+    };
+    #[cfg_attr(not(feature = "gui"), allow(unused_variables))]
+    let settings = profiles.settings_for(&cartridge.header, base);
+    #[cfg_attr(not(feature = "gui"), allow(unused_variables))]
+    let autosplitter = profiles
+        .get(&cartridge.header)
+        .and_then(|profile| profile.autosplit.clone())
+        .map(Autosplitter::new);
 
     #[cfg(feature = "gui")]
-    gui::run(&mut game_boy);
+    gui::run(&mut game_boy, &settings, autosplitter);
 
     //
     //
@@ -35,3 +144,85 @@ fn main() {
     //let state_json = PathBuf::from("./test/test.json");
     //game_boy.save().store_json(&state_json).unwrap();
 }
+
+/// Prints the index as JSON or writes it to the output file
+fn scan_rom_library(directory: &Path, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let library = RomLibrary::scan(directory)?;
+    match output {
+        Some(output) => {
+            library.store(output)?;
+            println!(
+                "Indexed {} ROMs ({} skipped) into {}",
+                library.roms.len(),
+                library.skipped.len(),
+                output.display()
+            );
+        }
+        None => println!("{}", library.to_json()?),
+    }
+    Ok(())
+}
+
+fn play_scenario(path: &Path) -> Result<(), Box<dyn Error>> {
+    let scenario = Scenario::load(path)?;
+    let cartridge = load_cartridge(scenario.rom.clone());
+    let mut game_boy = initialize_game_boy(&cartridge, &load_profiles());
+    let result = run_scenario(&mut game_boy, &scenario)?;
+    for screenshot in &result.screenshots {
+        println!("Saved {}", screenshot.display());
+    }
+    println!("Finished after {} frames", result.frames);
+    Ok(())
+}
+
+/// Returns whether the video output diverged from the verified checksums
+fn replay_movie(
+    game_boy: &mut GameBoy,
+    movie: &Path,
+    record: Option<&Path>,
+    verify: Option<&Path>,
+) -> Result<bool, Box<dyn Error>> {
+    let replay = import_movie(movie)?;
+    let verification = match (verify, record) {
+        (Some(verify), _) => FrameVerification::Compare(FrameChecksums::load(verify)?),
+        (None, Some(_)) => FrameVerification::Record,
+        (None, None) => FrameVerification::Off,
+    };
+    let result = play_replay(game_boy, &replay, &verification);
+    if let (Some(record), Some(checksums)) = (record, &result.checksums) {
+        checksums.store(record)?;
+    }
+    println!("{}", result.summary());
+    Ok(result.divergence.is_some())
+}
+
+fn load_cartridge(path: PathBuf) -> Cartridge {
+    Cartridge::load(path.clone()).unwrap_or_else(|error| {
+        eprintln!("Failed to load ROM {}: {error}", path.display());
+        exit(1);
+    })
+}
+
+/// A broken profiles file is reported and ignored
+fn load_profiles() -> Profiles {
+    Profiles::load_or_default(Path::new(DEFAULT_PROFILES_PATH)).unwrap_or_else(|error| {
+        eprintln!("Ignoring profiles {DEFAULT_PROFILES_PATH}: {error}");
+        Profiles::default()
+    })
+}
+
+/// Applies the game's profile, including its cheats
+fn initialize_game_boy(cartridge: &Cartridge, profiles: &Profiles) -> GameBoy {
+    let config = profiles.config_for(&cartridge.header, GameBoyConfig::default());
+
+    let mut game_boy = GameBoy::initialize_with_config(cartridge, config).unwrap_or_else(|error| {
+        eprintln!("Failed to start {}: {error}", cartridge.header.title);
+        exit(1);
+    });
+    if let Some(profile) = profiles.get(&cartridge.header) {
+        if let Err(error) = game_boy.set_cheats(&profile.cheats) {
+            eprintln!("Ignoring the cheats of the profile: {error}");
+        }
+    }
+    game_boy
+}
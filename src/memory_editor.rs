@@ -0,0 +1,266 @@
+//! Hex editor of the whole address space drawn over the screen, eight bytes per line colored by their memory region.
+//! Bytes are read without side effects and written like the CPU writes them, see [`GameBoy::write_memory`].
+//! Enter edits the selected byte, an address can be jumped to and byte sequences searched for.
+
+use crate::locale::{Language, Text};
+use crate::osd::{draw_text, fill_rectangle, CHARACTER_WIDTH};
+use crate::panel::{
+    draw_background, draw_column, Panel, PanelState, TextInput, INACTIVE_COLOR, LINES, LINE_HEIGHT,
+    PADDING, SELECTION_COLOR, TEXT_COLOR,
+};
+use lemon_gb_core::game_boy::components::mmu::region::MemoryRegion;
+use lemon_gb_core::game_boy::GameBoy;
+use std::error::Error;
+
+pub const BYTES_PER_ROW: usize = 8;
+/// The title takes the first line and the input or the selected address the last one
+pub const VISIBLE_ROWS: usize = LINES - 2;
+const VISIBLE_BYTES: usize = VISIBLE_ROWS * BYTES_PER_ROW;
+const ADDRESS_SPACE_SIZE: usize = 0x10000;
+/// Two hex digits and a small gap
+const BYTE_WIDTH: usize = 2 * CHARACTER_WIDTH + 3;
+const BYTES_LEFT: usize = 4 * CHARACTER_WIDTH + 5;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryInput {
+    /// The new value of the selected byte
+    #[default]
+    Value,
+    /// Where to jump to
+    Address,
+    /// The bytes to search for, as hex digits without spaces
+    Search,
+}
+
+impl MemoryInput {
+    /// In hex digits
+    pub fn get_max_length(&self) -> usize {
+        match self {
+            MemoryInput::Value => 2,
+            MemoryInput::Address => 4,
+            MemoryInput::Search => 12,
+        }
+    }
+
+    fn label(&self) -> Text {
+        match self {
+            MemoryInput::Value => Text::ValueLabel,
+            MemoryInput::Address => Text::GoToLabel,
+            MemoryInput::Search => Text::SearchLabel,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MemoryEditor {
+    /// The input is the typed text, what it is for is kept in `input_kind`
+    state: PanelState,
+    input_kind: MemoryInput,
+    selected: u16,
+    /// The address of the top line, a multiple of [`BYTES_PER_ROW`]
+    first_row: u16,
+    /// Of the last search together with the selected one, None before the first search
+    matches: Option<(Vec<u16>, usize)>,
+    /// Of the labels
+    language: Language,
+}
+
+impl MemoryEditor {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_selected(&self) -> u16 {
+        self.selected
+    }
+
+    /// The address of the top line
+    pub fn get_first_row(&self) -> u16 {
+        self.first_row
+    }
+
+    /// Moves by a number of bytes, wrapping around the address space. The selection stays while typing.
+    pub fn move_selection(&mut self, bytes: isize) {
+        if self.is_editing() {
+            return;
+        }
+        let address = (self.selected as isize + bytes).rem_euclid(ADDRESS_SPACE_SIZE as isize);
+        self.select(address as u16);
+    }
+
+    /// Scrolls as little as possible to keep the address visible
+    fn select(&mut self, address: u16) {
+        self.selected = address;
+        let row = address as usize / BYTES_PER_ROW * BYTES_PER_ROW;
+        let first_row = (self.first_row as usize)
+            .min(row)
+            .max((row + BYTES_PER_ROW).saturating_sub(VISIBLE_BYTES));
+        self.first_row = first_row as u16;
+    }
+
+    /// Starts without any digits
+    pub fn start_input(&mut self, input: MemoryInput) {
+        self.input_kind = input;
+        self.state
+            .start_input(TextInput::hex("", input.get_max_length()));
+    }
+
+    pub fn get_input(&self) -> Option<(MemoryInput, &str)> {
+        let typed = self.state.get_input()?;
+        Some((self.input_kind, typed.get_text()))
+    }
+
+    /// Writes the value and selects the next byte, jumps to the address or searches for the bytes and selects
+    /// the first match. Invalid input is kept for editing and an error returned.
+    pub fn finish_input(&mut self, game_boy: &mut GameBoy) -> Result<(), Box<dyn Error>> {
+        let Some((input, typed)) = self.get_input() else {
+            return Ok(());
+        };
+        match input {
+            MemoryInput::Value => {
+                let value =
+                    u8::from_str_radix(typed, 16).map_err(|_| "The value needs hex digits")?;
+                game_boy.write_memory(self.selected, value);
+                self.state.cancel_input();
+                self.move_selection(1);
+            }
+            MemoryInput::Address => {
+                let address =
+                    u16::from_str_radix(typed, 16).map_err(|_| "The address needs hex digits")?;
+                self.state.cancel_input();
+                self.select(address);
+            }
+            MemoryInput::Search => {
+                let pattern = parse_bytes(typed)?;
+                let matches = game_boy.search_memory(&pattern);
+                self.state.cancel_input();
+                if let Some(&address) = matches.first() {
+                    self.select(address);
+                }
+                self.matches = Some((matches, 0));
+            }
+        }
+        Ok(())
+    }
+
+    /// The addresses found by the last search, None before the first one
+    pub fn get_matches(&self) -> Option<&[u16]> {
+        self.matches.as_ref().map(|(matches, _)| matches.as_slice())
+    }
+
+    /// Selects the next match of the last search, after the last one follows the first one again.
+    /// Returns whether there was one.
+    pub fn next_match(&mut self) -> bool {
+        let Some((matches, index)) = &mut self.matches else {
+            return false;
+        };
+        if matches.is_empty() || self.state.get_input().is_some() {
+            return false;
+        }
+        *index = (*index + 1) % matches.len();
+        let address = matches[*index];
+        self.select(address);
+        true
+    }
+
+    /// Draws the editor onto an RGBA8888 frame if it is open
+    pub fn draw(&self, frame: &mut [u8], game_boy: &GameBoy) {
+        if !self.is_open() {
+            return;
+        }
+        draw_background(frame);
+        let title = self.language.text(Text::MemoryLabel);
+        draw_column(frame, 0, 0, title, TEXT_COLOR);
+
+        let bytes = game_boy.dump_memory(self.first_row, VISIBLE_BYTES);
+        for (row, bytes) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+            let top = (row + 1) * LINE_HEIGHT + PADDING;
+            let row_address = self.first_row as usize + row * BYTES_PER_ROW;
+            let address = format!("{row_address:04X}");
+            draw_column(frame, 0, row + 1, &address, INACTIVE_COLOR);
+            for (column, value) in bytes.iter().enumerate() {
+                let address = (row_address + column) as u16;
+                let left = BYTES_LEFT + column * BYTE_WIDTH;
+                let typed = match (self.input_kind, self.state.get_input()) {
+                    (MemoryInput::Value, Some(typed)) if address == self.selected => {
+                        Some(typed.with_cursor())
+                    }
+                    _ => None,
+                };
+                if address == self.selected {
+                    let width = BYTE_WIDTH - 1;
+                    fill_rectangle(
+                        frame,
+                        left - 1,
+                        top - 1,
+                        width,
+                        LINE_HEIGHT,
+                        SELECTION_COLOR,
+                    );
+                }
+                let text = typed.unwrap_or_else(|| format!("{value:02X}"));
+                let color = region_color(MemoryRegion::from_address(address));
+                draw_text(frame, left, top, &text, color);
+            }
+        }
+
+        draw_column(frame, 0, LINES - 1, &self.get_status_text(), TEXT_COLOR);
+    }
+
+    /// What is typed, otherwise the region of the selected byte and which match of the last search was selected
+    pub fn get_status_text(&self) -> String {
+        match self.state.get_input() {
+            Some(typed) => {
+                let label = self.language.text(self.input_kind.label());
+                format!("{label}: {}", typed.with_cursor())
+            }
+            None => {
+                let region = MemoryRegion::from_address(self.selected);
+                let mut text = format!("{region} {:04X}", self.selected);
+                if let Some((matches, index)) = &self.matches {
+                    let position = if matches.is_empty() { 0 } else { index + 1 };
+                    text += &format!("  {position}/{}", matches.len());
+                }
+                text
+            }
+        }
+    }
+}
+
+/// The selection and the matches are kept from the last time
+impl Panel for MemoryEditor {
+    fn get_state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+}
+
+/// Two hex digits per byte
+fn parse_bytes(digits: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return Err("The search needs two hex digits per byte".into());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| Ok(u8::from_str_radix(&digits[index..index + 2], 16)?))
+        .collect()
+}
+
+fn region_color(region: MemoryRegion) -> [u8; 4] {
+    match region {
+        MemoryRegion::RomBank0 | MemoryRegion::RomBankN => [0xA0, 0xC0, 0xFF, 0xFF],
+        MemoryRegion::Vram => [0x60, 0xFF, 0x60, 0xFF],
+        MemoryRegion::ExternalRam => [0xFF, 0xA0, 0x40, 0xFF],
+        MemoryRegion::Wram => [0xFF, 0xFF, 0xFF, 0xFF],
+        MemoryRegion::EchoRam | MemoryRegion::Unusable => [0x90, 0x90, 0x90, 0xFF],
+        MemoryRegion::Oam => [0xFF, 0x80, 0xFF, 0xFF],
+        MemoryRegion::IoRegisters | MemoryRegion::InterruptEnable => [0xFF, 0xE0, 0x40, 0xFF],
+        MemoryRegion::Hram => [0x40, 0xE0, 0xE0, 0xFF],
+    }
+}
@@ -0,0 +1,173 @@
+//! On screen display for short messages of the frontend, drawn over the bottom of the presented frame.
+
+use crate::font;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+/// About 3 seconds
+pub const MESSAGE_FRAMES: u32 = 180;
+/// Glyphs are 5 pixels wide with a 1 pixel gap
+pub const CHARACTER_WIDTH: usize = 6;
+const LINE_HEIGHT: usize = font::GLYPH_SIZE;
+const MAX_LINES: usize = 4;
+/// Large text doubles the size of every glyph pixel
+const LARGE_TEXT_SCALE: usize = 2;
+const TEXT_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+const BACKGROUND_COLOR: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+
+#[derive(Debug, Default, Clone)]
+pub struct Osd {
+    lines: Vec<String>,
+    remaining_frames: u32,
+    large_text: bool,
+}
+
+impl Osd {
+    /// Replaces the current message, long messages are wrapped at word boundaries
+    pub fn show(&mut self, message: &str) {
+        self.lines = wrap(message, self.get_scale());
+        self.remaining_frames = MESSAGE_FRAMES;
+    }
+
+    /// Twice as large text fits fewer characters into a line, so the current message is wrapped again
+    pub fn set_large_text(&mut self, large_text: bool) {
+        self.large_text = large_text;
+        self.lines = wrap(&self.lines.join(" "), self.get_scale());
+    }
+
+    pub fn is_large_text(&self) -> bool {
+        self.large_text
+    }
+
+    pub fn get_lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.remaining_frames > 0
+    }
+
+    /// Draws the message onto an RGBA8888 frame, call it once for every presented frame
+    pub fn draw(&mut self, frame: &mut [u8]) {
+        if !self.is_visible() || self.lines.is_empty() {
+            return;
+        }
+        self.remaining_frames -= 1;
+
+        let scale = self.get_scale();
+        let line_height = LINE_HEIGHT * scale;
+        let top = SCREEN_HEIGHT - self.lines.len() * line_height - scale;
+        fill_rectangle(
+            frame,
+            0,
+            top,
+            SCREEN_WIDTH,
+            SCREEN_HEIGHT - top,
+            BACKGROUND_COLOR,
+        );
+
+        for (line_index, line) in self.lines.iter().enumerate() {
+            draw_scaled_text(
+                frame,
+                scale,
+                top + scale + line_index * line_height,
+                line,
+                TEXT_COLOR,
+                scale,
+            );
+        }
+    }
+
+    fn get_scale(&self) -> usize {
+        if self.large_text {
+            LARGE_TEXT_SCALE
+        } else {
+            1
+        }
+    }
+}
+
+/// Draws a single line of text onto an RGBA8888 frame, characters without a glyph are left out.
+/// Whatever lies outside of the screen is cut off.
+pub fn draw_text(frame: &mut [u8], left: usize, top: usize, text: &str, color: [u8; 4]) {
+    draw_scaled_text(frame, left, top, text, color, 1);
+}
+
+/// Like [`draw_text`], but every glyph pixel is drawn as a square of `scale` x `scale` pixels
+pub fn draw_scaled_text(
+    frame: &mut [u8],
+    left: usize,
+    top: usize,
+    text: &str,
+    color: [u8; 4],
+    scale: usize,
+) {
+    for (column, character) in text.chars().enumerate() {
+        let Some(glyph) = font::glyph_index(character).map(|index| font::GLYPHS[index]) else {
+            continue;
+        };
+        let character_left = left + column * CHARACTER_WIDTH * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for bit in 0..8 {
+                if bits & (0x80 >> bit) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = character_left + bit * scale + dx;
+                        write_pixel(frame, x, top + row * scale + dy, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whatever lies outside of the screen is cut off
+pub fn fill_rectangle(
+    frame: &mut [u8],
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    color: [u8; 4],
+) {
+    for y in top..top + height {
+        for x in left..left + width {
+            write_pixel(frame, x, y, color);
+        }
+    }
+}
+
+pub fn write_pixel(frame: &mut [u8], x: usize, y: usize, color: [u8; 4]) {
+    if x >= SCREEN_WIDTH || y >= SCREEN_HEIGHT {
+        return;
+    }
+    let index = (y * SCREEN_WIDTH + x) * 4;
+    frame[index..index + 4].copy_from_slice(&color);
+}
+
+/// Splits the message into lines which fit the screen at the scale, words longer than a line are cut
+fn wrap(message: &str, scale: usize) -> Vec<String> {
+    let mut lines = wrap_text(
+        message,
+        (SCREEN_WIDTH - 2 * scale) / (CHARACTER_WIDTH * scale),
+    );
+    lines.truncate(MAX_LINES);
+    lines
+}
+
+/// Splits the text into lines of at most `max_line_length` characters at word boundaries, words longer than a line are cut
+pub fn wrap_text(text: &str, max_line_length: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let word: String = word.chars().take(max_line_length).collect();
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_line_length => {
+                line.push(' ');
+                line.push_str(&word);
+            }
+            _ => lines.push(word),
+        }
+    }
+    lines
+}
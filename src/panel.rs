@@ -0,0 +1,177 @@
+//! What the panels drawn over the whole screen share: their layout and colors, drawing text by lines and columns,
+//! and whether they are open together with the text typed into them.
+
+use crate::font;
+use crate::osd::{draw_text, fill_rectangle, CHARACTER_WIDTH};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+pub const LINE_HEIGHT: usize = font::GLYPH_SIZE + 1;
+/// The lines which fit onto the screen
+pub const LINES: usize = SCREEN_HEIGHT / LINE_HEIGHT;
+pub const PADDING: usize = 1;
+pub const BACKGROUND_COLOR: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+pub const SELECTION_COLOR: [u8; 4] = [0x40, 0x40, 0x40, 0xFF];
+pub const TEXT_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+pub const ACTIVE_COLOR: [u8; 4] = [0x60, 0xFF, 0x60, 0xFF];
+pub const INACTIVE_COLOR: [u8; 4] = [0x90, 0x90, 0x90, 0xFF];
+pub const CURSOR: char = '_';
+
+/// Text typed into a panel, e.g. a code or a value. Only the characters the filter lets through are appended,
+/// up to the maximum length.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    text: String,
+    max_length: usize,
+    /// Returns the character to append, None if it isn't allowed
+    filter: fn(char) -> Option<char>,
+}
+
+impl TextInput {
+    /// The initial text is taken as it is
+    pub fn new(text: &str, max_length: usize, filter: fn(char) -> Option<char>) -> Self {
+        Self {
+            text: text.to_string(),
+            max_length,
+            filter,
+        }
+    }
+
+    /// Hex digits in upper case
+    pub fn hex(text: &str, max_length: usize) -> Self {
+        Self::new(text, max_length, hex_digit)
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// The text followed by the cursor, how it is drawn
+    pub fn with_cursor(&self) -> String {
+        format!("{}{CURSOR}", self.text)
+    }
+
+    pub fn type_text(&mut self, text: &str) {
+        for character in text.chars().filter_map(self.filter) {
+            if self.text.len() < self.max_length {
+                self.text.push(character);
+            }
+        }
+    }
+
+    pub fn delete_character(&mut self) {
+        self.text.pop();
+    }
+}
+
+/// The filter of [`TextInput::hex`]
+pub fn hex_digit(character: char) -> Option<char> {
+    character
+        .is_ascii_hexdigit()
+        .then(|| character.to_ascii_uppercase())
+}
+
+/// Whether a panel is open and what is typed into it
+#[derive(Debug, Default, Clone)]
+pub struct PanelState {
+    open: bool,
+    /// None while nothing is typed
+    input: Option<TextInput>,
+}
+
+impl PanelState {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Input which is still typed is discarded
+    pub fn close(&mut self) {
+        self.input = None;
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Replaces what is typed so far
+    pub fn start_input(&mut self, input: TextInput) {
+        self.input = Some(input);
+    }
+
+    pub fn get_input(&self) -> Option<&TextInput> {
+        self.input.as_ref()
+    }
+
+    pub fn get_input_mut(&mut self) -> Option<&mut TextInput> {
+        self.input.as_mut()
+    }
+
+    pub fn cancel_input(&mut self) {
+        self.input = None;
+    }
+}
+
+/// Opening, closing and typing of a panel, built on its [`PanelState`].
+/// Typed text is ignored while the panel doesn't edit anything.
+pub trait Panel {
+    fn get_state(&self) -> &PanelState;
+
+    fn get_state_mut(&mut self) -> &mut PanelState;
+
+    fn open(&mut self) {
+        self.get_state_mut().open();
+    }
+
+    fn close(&mut self) {
+        self.get_state_mut().close();
+    }
+
+    fn is_open(&self) -> bool {
+        self.get_state().is_open()
+    }
+
+    fn is_editing(&self) -> bool {
+        self.get_state().get_input().is_some()
+    }
+
+    fn type_text(&mut self, text: &str) {
+        if let Some(input) = self.get_state_mut().get_input_mut() {
+            input.type_text(text);
+        }
+    }
+
+    fn delete_character(&mut self) {
+        if let Some(input) = self.get_state_mut().get_input_mut() {
+            input.delete_character();
+        }
+    }
+
+    fn cancel_editing(&mut self) {
+        self.get_state_mut().cancel_input();
+    }
+}
+
+/// Covers the whole screen
+pub fn draw_background(frame: &mut [u8]) {
+    fill_rectangle(frame, 0, 0, SCREEN_WIDTH, SCREEN_HEIGHT, BACKGROUND_COLOR);
+}
+
+/// Columns are counted in characters
+pub fn draw_column(frame: &mut [u8], column: usize, line: usize, text: &str, color: [u8; 4]) {
+    let left = PADDING + column * CHARACTER_WIDTH;
+    draw_text(frame, left, line * LINE_HEIGHT + PADDING, text, color);
+}
+
+/// Highlights `width` characters of the line starting at the column
+pub fn highlight(frame: &mut [u8], column: usize, line: usize, width: usize) {
+    let top = line * LINE_HEIGHT + PADDING - 1;
+    let left = column * CHARACTER_WIDTH;
+    let width = width * CHARACTER_WIDTH;
+    fill_rectangle(frame, left, top, width, LINE_HEIGHT, SELECTION_COLOR);
+}
+
+/// Highlights the line across the whole screen
+pub fn highlight_line(frame: &mut [u8], line: usize) {
+    let top = line * LINE_HEIGHT + PADDING - 1;
+    fill_rectangle(frame, 0, top, SCREEN_WIDTH, LINE_HEIGHT, SELECTION_COLOR);
+}
@@ -0,0 +1,125 @@
+//! Per-game settings, stored in a single JSON file and applied whenever a matching ROM is loaded.
+
+use crate::autosplit::AutosplitConfig;
+use crate::settings::FrontendSettings;
+use lemon_gb_core::game_boy::cheats::Cheat;
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::ppu::color_scheme::ColorScheme;
+use lemon_gb_core::game_boy::config::GameBoyConfig;
+use lemon_gb_core::game_boy::hardware_model::HardwareModel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Where the frontend looks for profiles, relative to the working directory
+pub const DEFAULT_PROFILES_PATH: &str = "./profiles.json";
+
+/// Overrides for a single game, unset options keep the value of the base config
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameProfile {
+    /// For games which behave differently on the hardware revisions
+    pub model: Option<HardwareModel>,
+    pub color_scheme: Option<ColorScheme>,
+    pub colorize: Option<bool>,
+    /// For games which flicker sprites and rely on the LCD blending the frames, see [`FrontendSettings`]
+    pub frame_blending: Option<bool>,
+    /// Speedrun timing, not part of the [`GameBoyConfig`] but read by the frontend
+    pub autosplit: Option<AutosplitConfig>,
+    /// Activated by the frontend when the game starts, edited in the cheats manager
+    #[serde(default)]
+    pub cheats: Vec<Cheat>,
+}
+
+impl GameProfile {
+    pub fn apply(&self, mut config: GameBoyConfig) -> GameBoyConfig {
+        if let Some(model) = self.model {
+            config = config.model(model);
+        }
+        if let Some(color_scheme) = self.color_scheme {
+            config.color_scheme = color_scheme;
+        }
+        if let Some(colorize) = self.colorize {
+            config.colorize = colorize;
+        }
+        config
+    }
+
+    pub fn apply_settings(&self, mut settings: FrontendSettings) -> FrontendSettings {
+        if let Some(frame_blending) = self.frame_blending {
+            settings.frame_blending = frame_blending;
+        }
+        settings
+    }
+}
+
+/// All game profiles, keyed by [`Profiles::key`]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    profiles: BTreeMap<String, GameProfile>,
+}
+
+impl Profiles {
+    /// Identifies a game by its title and both header checksums, e.g. `TETRIS-0A16BF`.
+    /// The header checksum covers the whole header and the global checksum the whole ROM,
+    /// so revisions and ROM hacks with the same title get their own profile.
+    pub fn key(header: &CartridgeHeader) -> String {
+        format!(
+            "{}-{:02X}{:04X}",
+            header.title, header.header_checksum, header.global_checksum
+        )
+    }
+
+    pub fn get(&self, header: &CartridgeHeader) -> Option<&GameProfile> {
+        self.profiles.get(&Self::key(header))
+    }
+
+    pub fn set(&mut self, header: &CartridgeHeader, profile: GameProfile) {
+        self.profiles.insert(Self::key(header), profile);
+    }
+
+    /// Replaces the cheats of the game's profile, which is created if the game has none
+    pub fn set_cheats(&mut self, header: &CartridgeHeader, cheats: Vec<Cheat>) {
+        self.profiles.entry(Self::key(header)).or_default().cheats = cheats;
+    }
+
+    pub fn remove(&mut self, header: &CartridgeHeader) -> Option<GameProfile> {
+        self.profiles.remove(&Self::key(header))
+    }
+
+    /// The base config with the overrides of the game's profile, if it has one
+    pub fn config_for(&self, header: &CartridgeHeader, base: GameBoyConfig) -> GameBoyConfig {
+        match self.get(header) {
+            Some(profile) => profile.apply(base),
+            None => base,
+        }
+    }
+
+    /// The base settings with the overrides of the game's profile, if it has one
+    pub fn settings_for(
+        &self,
+        header: &CartridgeHeader,
+        base: FrontendSettings,
+    ) -> FrontendSettings {
+        match self.get(header) {
+            Some(profile) => profile.apply_settings(base),
+            None => base,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Like [`Profiles::load`], but a missing file is the same as having no profiles
+    pub fn load_or_default(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load(path)
+    }
+
+    pub fn store(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
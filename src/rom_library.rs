@@ -0,0 +1,145 @@
+//! Scans a directory of ROMs into a JSON index of their headers, for ROM browsers and `lemon-gb scan`.
+//! Copies of the same game are found by their global checksum and listed only once,
+//! ROMs whose global checksum doesn't match their contents are never merged.
+
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::cartridge::types::{
+    CartridgeCGBFlag, CartridgeType, MbcType,
+};
+use lemon_gb_core::game_boy::components::mmu::{RAM_BANK_SIZE, ROM_BANK_SIZE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Files with other extensions are ignored
+pub const ROM_EXTENSIONS: [&str; 2] = ["gb", "gbc"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RomEntry {
+    /// Relative to the scanned directory
+    pub path: PathBuf,
+    pub title: String,
+    pub cartridge_type: CartridgeType,
+    pub mapper: MbcType,
+    pub rom_kib: usize,
+    pub ram_kib: usize,
+    pub cgb_flag: CartridgeCGBFlag,
+    pub global_checksum: u16,
+    /// A wrong header checksum locks up the boot ROM of a real Game Boy
+    pub header_checksum_valid: bool,
+    /// No hardware checks it, a mismatch usually means a modified or damaged ROM
+    pub global_checksum_valid: bool,
+    /// Further files with the same global checksum
+    pub duplicates: Vec<PathBuf>,
+}
+
+impl RomEntry {
+    pub fn from_rom(path: PathBuf, rom: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let header = CartridgeHeader::parse(rom)?;
+        Ok(Self {
+            path,
+            title: header.title.clone(),
+            cartridge_type: header.cartridge_type,
+            mapper: header.cartridge_type.mapper_kind(),
+            rom_kib: header.rom_size * ROM_BANK_SIZE / 1024,
+            ram_kib: header.ram_size * RAM_BANK_SIZE / 1024,
+            cgb_flag: header.cgb_flag,
+            global_checksum: header.global_checksum,
+            header_checksum_valid: CartridgeHeader::compute_header_checksum(rom)
+                == header.header_checksum,
+            global_checksum_valid: CartridgeHeader::compute_global_checksum(rom)
+                == header.global_checksum,
+            duplicates: Vec::new(),
+        })
+    }
+}
+
+/// A file with a ROM extension which couldn't be read or has no valid header
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RomLibrary {
+    /// Sorted by path
+    pub roms: Vec<RomEntry>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+impl RomLibrary {
+    /// Reads every ROM in the directory and its subdirectories.
+    /// Fails only if a directory can't be listed, unreadable ROMs end up in [`RomLibrary::skipped`].
+    pub fn scan(directory: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut paths = Vec::new();
+        collect_rom_paths(directory, &mut paths)?;
+        paths.sort();
+
+        let mut library = Self::default();
+        let mut by_checksum: HashMap<u16, usize> = HashMap::new();
+        for path in paths {
+            let relative = path.strip_prefix(directory).unwrap_or(&path).to_path_buf();
+            let entry = std::fs::read(&path)
+                .map_err(|error| error.into())
+                .and_then(|rom| RomEntry::from_rom(relative.clone(), &rom));
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    library.skipped.push(SkippedFile {
+                        path: relative,
+                        reason: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let duplicate_of = by_checksum.get(&entry.global_checksum).copied();
+            match duplicate_of {
+                Some(index) if entry.global_checksum_valid => {
+                    library.roms[index].duplicates.push(entry.path)
+                }
+                _ => {
+                    if entry.global_checksum_valid {
+                        by_checksum.insert(entry.global_checksum, library.roms.len());
+                    }
+                    library.roms.push(entry);
+                }
+            }
+        }
+        Ok(library)
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn store(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+}
+
+fn collect_rom_paths(directory: &Path, paths: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let entries = std::fs::read_dir(directory)
+        .map_err(|e| format!("Unable to scan {}: {e}", directory.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rom_paths(&path, paths)?;
+        } else if is_rom_path(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_rom_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| ROM_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()))
+}
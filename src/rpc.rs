@@ -0,0 +1,341 @@
+//! JSON-RPC 2.0 control of a running emulator, so external tools and test frameworks can drive the GUI.
+//! Every request and response is a single line of JSON, the server only listens on the local machine.
+//! The frames of the GUI run through the [`Debugger`] of the [`Controller`], so breakpoints pause it like `pause` does.
+//! https://www.jsonrpc.org/specification
+//!
+//! | Method                  | Params                                                         | Result                       |
+//! |-------------------------|----------------------------------------------------------------|------------------------------|
+//! | `status`                |                                                                | `{paused, pc, title}`        |
+//! | `pause`                 |                                                                | `null`                       |
+//! | `resume`                |                                                                | `null`                       |
+//! | `frame_advance`         | `{frames}`, 1 if omitted                                       | `null`, stops at breakpoints |
+//! | `step`                  | `{instructions}`, 1 if omitted                                 | `null`                       |
+//! | `set_breakpoint`        | `{address}`                                                    | `null`                       |
+//! | `remove_breakpoint`     | `{address}`                                                    | `null`                       |
+//! | `get_breakpoints`       |                                                                | list of addresses            |
+//! | `set_debug_conventions` | `{software_breakpoints, debug_messages}`, unchanged if omitted | `null`                       |
+//! | `take_debug_messages`   |                                                                | list of `{address, text}`    |
+//! | `read_memory`           | `{address, length}`, 1 if omitted                              | list of bytes                |
+//! | `write_memory`          | `{address, bytes}`                                             | `null`                       |
+//! | `set_buttons`           | `{buttons}`, e.g. `["A", "Start"]`                             | `null`                       |
+//! | `get_buttons`           |                                                                | list of buttons              |
+//! | `save_state`            | `{path}`                                                       | `null`                       |
+//! | `load_state`            | `{path}`                                                       | `null`                       |
+//! | `screenshot`            | `{path, scale}`, 1 if omitted                                  | `null`                       |
+
+use crate::debugger::{DebugEvent, Debugger};
+#[cfg(feature = "image")]
+use crate::screenshot;
+use crate::state_picker::store_slot;
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::save_state::GameBoySaveState;
+use lemon_gb_core::game_boy::GameBoy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+#[cfg(feature = "rpc")]
+pub mod server;
+
+/// Error codes of the JSON-RPC specification
+pub const PARSE_ERROR: i32 = -32700;
+pub const INVALID_REQUEST: i32 = -32600;
+pub const METHOD_NOT_FOUND: i32 = -32601;
+pub const INVALID_PARAMS: i32 = -32602;
+/// The request was valid, but the emulator failed to execute it, e.g. a file couldn't be written
+pub const EXECUTION_ERROR: i32 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Request {
+    /// Requests without an id are answered as well, with a null id
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Response {
+    fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FramesParams {
+    #[serde(default = "one")]
+    frames: u32,
+}
+
+#[derive(Deserialize)]
+struct StepParams {
+    #[serde(default = "one")]
+    instructions: u32,
+}
+
+#[derive(Deserialize)]
+struct AddressParams {
+    address: u16,
+}
+
+/// `LD B,B` breakpoints and `LD D,D` messages
+#[derive(Deserialize)]
+struct DebugConventionsParams {
+    software_breakpoints: Option<bool>,
+    debug_messages: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct ReadMemoryParams {
+    address: u16,
+    #[serde(default = "one")]
+    length: u32,
+}
+
+#[derive(Deserialize)]
+struct WriteMemoryParams {
+    address: u16,
+    bytes: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct ButtonsParams {
+    buttons: Vec<Button>,
+}
+
+#[derive(Deserialize)]
+struct PathParams {
+    path: PathBuf,
+}
+
+#[cfg(feature = "image")]
+#[derive(Deserialize)]
+struct ScreenshotParams {
+    path: PathBuf,
+    #[serde(default = "one")]
+    scale: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// Executes requests on the emulator, owned by the frontend which runs its frames through it
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Controller {
+    debugger: Debugger,
+}
+
+impl Controller {
+    /// While paused by a request or a breakpoint the frontend doesn't run any frames, only `frame_advance` does
+    pub fn is_paused(&self) -> bool {
+        self.debugger.is_paused()
+    }
+
+    /// Runs the next frame of the frontend, see [`Debugger::run_frame`]
+    pub fn run_frame(&mut self, game_boy: &mut GameBoy) -> DebugEvent {
+        self.debugger.run_frame(game_boy)
+    }
+
+    pub fn get_debugger(&self) -> &Debugger {
+        &self.debugger
+    }
+
+    pub fn get_debugger_mut(&mut self) -> &mut Debugger {
+        &mut self.debugger
+    }
+
+    /// Parses a request line and executes it
+    pub fn handle_line(&mut self, game_boy: &mut GameBoy, line: &str) -> Response {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(error) => {
+                return Response::new(
+                    Value::Null,
+                    Err(RpcError::new(PARSE_ERROR, error.to_string())),
+                )
+            }
+        };
+        let id = value.get("id").cloned().unwrap_or_default();
+        match serde_json::from_value(value) {
+            Ok(request) => self.handle(game_boy, request),
+            Err(error) => Response::new(id, Err(RpcError::new(INVALID_REQUEST, error.to_string()))),
+        }
+    }
+
+    pub fn handle(&mut self, game_boy: &mut GameBoy, request: Request) -> Response {
+        let result = self.execute(game_boy, &request.method, request.params);
+        Response::new(request.id, result)
+    }
+
+    fn execute(
+        &mut self,
+        game_boy: &mut GameBoy,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, RpcError> {
+        match method {
+            "status" => Ok(json!({
+                "paused": self.is_paused(),
+                "pc": game_boy.get_pc(),
+                "title": game_boy.get_cartridge_header().title,
+            })),
+            "pause" => {
+                self.debugger.pause();
+                Ok(Value::Null)
+            }
+            "resume" => {
+                self.debugger.resume();
+                Ok(Value::Null)
+            }
+            "frame_advance" => {
+                let params: FramesParams = parse_params(params)?;
+                for _ in 0..params.frames {
+                    if self.debugger.advance_frame(game_boy) != DebugEvent::FrameFinished {
+                        break;
+                    }
+                }
+                Ok(Value::Null)
+            }
+            "step" => {
+                let params: StepParams = parse_params(params)?;
+                for _ in 0..params.instructions {
+                    self.debugger.step_instruction(game_boy);
+                }
+                Ok(Value::Null)
+            }
+            "set_breakpoint" => {
+                let params: AddressParams = parse_params(params)?;
+                self.debugger.set_breakpoint(params.address);
+                Ok(Value::Null)
+            }
+            "remove_breakpoint" => {
+                let params: AddressParams = parse_params(params)?;
+                self.debugger.remove_breakpoint(params.address);
+                Ok(Value::Null)
+            }
+            "get_breakpoints" => Ok(json!(self.debugger.get_breakpoints().collect::<Vec<_>>())),
+            "set_debug_conventions" => {
+                let params: DebugConventionsParams = parse_params(params)?;
+                if let Some(enabled) = params.software_breakpoints {
+                    self.debugger.set_software_breakpoints(enabled);
+                }
+                if let Some(enabled) = params.debug_messages {
+                    self.debugger.set_debug_messages(enabled);
+                }
+                Ok(Value::Null)
+            }
+            "take_debug_messages" => Ok(json!(self.debugger.take_debug_messages())),
+            "read_memory" => {
+                let params: ReadMemoryParams = parse_params(params)?;
+                if params.address as u32 + params.length > 0x10000 {
+                    return Err(RpcError::new(INVALID_PARAMS, "The range ends after 0xFFFF"));
+                }
+                let bytes: Vec<u8> = (0..params.length)
+                    .map(|offset| game_boy.peek(params.address + offset as u16))
+                    .collect();
+                Ok(json!(bytes))
+            }
+            "write_memory" => {
+                let params: WriteMemoryParams = parse_params(params)?;
+                if params.address as usize + params.bytes.len() > 0x10000 {
+                    return Err(RpcError::new(INVALID_PARAMS, "The range ends after 0xFFFF"));
+                }
+                for (offset, byte) in params.bytes.into_iter().enumerate() {
+                    game_boy.poke(params.address + offset as u16, byte);
+                }
+                Ok(Value::Null)
+            }
+            "set_buttons" => {
+                let params: ButtonsParams = parse_params(params)?;
+                let buttons = params
+                    .buttons
+                    .into_iter()
+                    .fold(Buttons::NONE, |buttons, button| buttons.with(button));
+                game_boy.set_buttons(buttons);
+                Ok(Value::Null)
+            }
+            "get_buttons" => {
+                let buttons = game_boy.get_buttons();
+                let pressed: Vec<Button> = Button::ALL
+                    .into_iter()
+                    .filter(|button| buttons.is_pressed(*button))
+                    .collect();
+                Ok(json!(pressed))
+            }
+            "save_state" => {
+                let params: PathParams = parse_params(params)?;
+                store_slot(&game_boy.save(), &params.path).map_err(execution_error)?;
+                Ok(Value::Null)
+            }
+            "load_state" => {
+                let params: PathParams = parse_params(params)?;
+                let state = GameBoySaveState::load_any(&params.path).map_err(execution_error)?;
+                game_boy.load_state(state).map_err(execution_error)?;
+                Ok(Value::Null)
+            }
+            #[cfg(feature = "image")]
+            "screenshot" => {
+                let params: ScreenshotParams = parse_params(params)?;
+                screenshot::save_screenshot(game_boy, &params.path, params.scale)
+                    .map_err(execution_error)?;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{method}'"),
+            )),
+        }
+    }
+}
+
+/// Omitted params are treated like an empty object, so every parameter with a default can be left out
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|error| RpcError::new(INVALID_PARAMS, error.to_string()))
+}
+
+fn execution_error(error: impl ToString) -> RpcError {
+    RpcError::new(EXECUTION_ERROR, error.to_string())
+}
@@ -0,0 +1,95 @@
+//! Accepts connections on a background thread, the requests are executed on the frontend's thread.
+//! Each connection waits for the response to its current request before it reads the next line.
+//! A line which isn't a JSON-RPC request closes the connection after its error response. Otherwise a web page could
+//! post to the port and have a request in its body executed, the HTTP request line in front of it is malformed.
+
+use crate::rpc::{Controller, Response, INVALID_REQUEST, PARSE_ERROR};
+use lemon_gb_core::game_boy::GameBoy;
+use log::warn;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8765";
+/// Longer request lines, including the line break, close the connection instead of being buffered
+pub const MAX_LINE_LENGTH: usize = 0x100000; // 1MB
+
+/// A request line together with the channel its response goes back through
+struct PendingRequest {
+    line: String,
+    reply: Sender<Response>,
+}
+
+pub struct RpcServer {
+    address: SocketAddr,
+    requests: Receiver<PendingRequest>,
+}
+
+impl RpcServer {
+    /// Port 0 picks a free port, see [`get_address`](Self::get_address)
+    pub fn bind(address: &str) -> Result<Self, Box<dyn Error>> {
+        let listener = TcpListener::bind(address)
+            .map_err(|e| format!("Unable to start the RPC server at {address}: {e}"))?;
+        let address = listener.local_addr()?;
+        let (sender, requests) = channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    if let Err(error) = serve_connection(stream, sender) {
+                        warn!("RPC connection closed: {error}");
+                    }
+                });
+            }
+        });
+        Ok(Self { address, requests })
+    }
+
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Executes every request that arrived since the last call, has to be called regularly by the frontend
+    pub fn handle_pending(&self, controller: &mut Controller, game_boy: &mut GameBoy) {
+        while let Ok(request) = self.requests.try_recv() {
+            let response = controller.handle_line(game_boy, &request.line);
+            let _ = request.reply.send(response);
+        }
+    }
+}
+
+fn serve_connection(
+    stream: TcpStream,
+    requests: Sender<PendingRequest>,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = Vec::new();
+        let length = (&mut reader)
+            .take(MAX_LINE_LENGTH as u64 + 1)
+            .read_until(b'\n', &mut line)?;
+        if length == 0 {
+            return Ok(());
+        }
+        if line.len() > MAX_LINE_LENGTH {
+            return Err(format!("request line longer than {MAX_LINE_LENGTH} bytes").into());
+        }
+        let line = String::from_utf8(line)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (reply, response) = channel();
+        requests.send(PendingRequest { line, reply })?;
+        let response = response.recv()?;
+        writer.write_all(response.to_line().as_bytes())?;
+        if let Some(error) = &response.error {
+            if matches!(error.code, PARSE_ERROR | INVALID_REQUEST) {
+                return Err(format!("malformed request: {}", error.message).into());
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Scripted headless runs described in a small TOML file, e.g. to generate screenshots for documentation
+//! or regression baselines. The steps run in order, each one waits for its frame or memory condition,
+//! then takes its screenshot and presses its buttons.
+//!
+//! ```toml
+//! rom = "game.gb"
+//! max_frames = 3600
+//!
+//! [[step]]
+//! frame = 120
+//! press = ["Start"]
+//!
+//! [[step]]
+//! until = "0xC0A0=0x01"
+//! screenshot = "title.png"
+//! scale = 2
+//! ```
+//!
+//! Paths are relative to the scenario file, frames are counted from power on.
+
+use crate::headless::MemoryCondition;
+#[cfg(feature = "image")]
+use crate::screenshot::save_screenshot;
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::GameBoy;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub rom: PathBuf,
+    /// The scenario fails if a step is still waiting after this many frames
+    #[serde(default = "default_max_frames")]
+    pub max_frames: u64,
+    #[serde(default, rename = "step")]
+    pub steps: Vec<ScenarioStep>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioStep {
+    /// Waits until this many frames have been emulated, fails if they already have
+    pub frame: Option<u64>,
+    /// Waits until the memory condition is met (checked once per frame)
+    pub until: Option<MemoryCondition>,
+    /// Saved as PNG as soon as the step is reached
+    pub screenshot: Option<PathBuf>,
+    #[serde(default = "default_scale")]
+    pub scale: u32,
+    /// Pressed after the screenshot and released again after `hold` frames
+    #[serde(default)]
+    pub press: Vec<Button>,
+    #[serde(default = "default_hold")]
+    pub hold: u64,
+}
+
+fn default_max_frames() -> u64 {
+    60 * 60
+}
+
+fn default_scale() -> u32 {
+    1
+}
+
+fn default_hold() -> u64 {
+    1
+}
+
+impl Scenario {
+    pub fn parse(toml: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Parses the file and resolves the ROM and screenshot paths relative to it
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read scenario {}: {e}", path.display()))?;
+        let mut scenario = Self::parse(&toml)?;
+        let directory = path.parent().unwrap_or(Path::new(""));
+        scenario.rom = directory.join(&scenario.rom);
+        for step in &mut scenario.steps {
+            if let Some(screenshot) = &mut step.screenshot {
+                *screenshot = directory.join(&screenshot);
+            }
+        }
+        Ok(scenario)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScenarioResult {
+    pub frames: u64,
+    /// In the order they were taken
+    pub screenshots: Vec<PathBuf>,
+}
+
+/// Runs the steps on a freshly initialized Game Boy, stops at the first step which can't be completed
+pub fn run_scenario(
+    game_boy: &mut GameBoy,
+    scenario: &Scenario,
+) -> Result<ScenarioResult, Box<dyn Error>> {
+    let start = game_boy.frame_count();
+    let frames = |game_boy: &GameBoy| game_boy.frame_count() - start;
+    let mut result = ScenarioResult::default();
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let number = index + 1;
+        if let Some(frame) = step.frame {
+            if frame < frames(game_boy) {
+                return Err(format!(
+                    "Step {number} waits for frame {frame}, but frame {} was already reached",
+                    frames(game_boy)
+                )
+                .into());
+            }
+            if frame > scenario.max_frames {
+                return Err(format!(
+                    "Step {number} waits for frame {frame}, after the maximum of {} frames",
+                    scenario.max_frames
+                )
+                .into());
+            }
+            while frames(game_boy) < frame {
+                game_boy.finish_frame();
+            }
+        }
+        if let Some(condition) = &step.until {
+            while !condition.is_met(game_boy) {
+                if frames(game_boy) >= scenario.max_frames {
+                    return Err(format!(
+                        "Step {number} waits for {condition}, which wasn't met within {} frames",
+                        scenario.max_frames
+                    )
+                    .into());
+                }
+                game_boy.finish_frame();
+            }
+        }
+
+        if let Some(path) = &step.screenshot {
+            save_screenshot(game_boy, path, step.scale)
+                .map_err(|e| format!("Step {number}: {e}"))?;
+            result.screenshots.push(path.clone());
+        }
+
+        if !step.press.is_empty() {
+            let buttons = step
+                .press
+                .iter()
+                .fold(Buttons::NONE, |buttons, button| buttons.with(*button));
+            game_boy.set_buttons(buttons);
+            for _ in 0..step.hold {
+                game_boy.finish_frame();
+            }
+            game_boy.set_buttons(Buttons::NONE);
+        }
+    }
+
+    result.frames = frames(game_boy);
+    Ok(result)
+}
+
+#[cfg(not(feature = "image"))]
+fn save_screenshot(_game_boy: &GameBoy, _path: &Path, _scale: u32) -> Result<(), Box<dyn Error>> {
+    Err("Screenshots need the image feature".into())
+}
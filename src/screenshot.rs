@@ -0,0 +1,42 @@
+//! Image files of the emulator: screenshots of the screen and pictures fed to the Pocket Camera.
+//! The core only deals in raw pixels, so it doesn't depend on an image library.
+
+use image::imageops::{FilterType, Nearest};
+use image::{imageops, RgbaImage};
+use lemon_gb_core::game_boy::components::mmu::mbc::camera::{CAMERA_HEIGHT, CAMERA_WIDTH};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::GameBoy;
+use std::error::Error;
+use std::path::Path;
+
+/// The current screen, scaled by the factor without smoothing
+pub fn render_image(game_boy: &GameBoy, scale_factor: f32) -> RgbaImage {
+    let image = RgbaImage::from_raw(
+        SCREEN_WIDTH as u32,
+        SCREEN_HEIGHT as u32,
+        game_boy.get_rgba_frame_buffer(),
+    )
+    .unwrap();
+
+    let scaled_width = (SCREEN_WIDTH as f32 * scale_factor) as u32;
+    let scaled_height = (SCREEN_HEIGHT as f32 * scale_factor) as u32;
+
+    imageops::resize(&image, scaled_width, scaled_height, Nearest)
+}
+
+/// Saves the current screen, the format follows from the file extension
+pub fn save_screenshot(game_boy: &GameBoy, path: &Path, scale: u32) -> Result<(), Box<dyn Error>> {
+    render_image(game_boy, scale as f32)
+        .save(path)
+        .map_err(|e| format!("Unable to save the screenshot {}: {e}", path.display()).into())
+}
+
+/// Feeds a picture to the Pocket Camera sensor, scaled and cropped to fill it
+pub fn load_camera_image(game_boy: &mut GameBoy, path: &Path) -> Result<(), Box<dyn Error>> {
+    let image = image::open(path)?.resize_to_fill(
+        CAMERA_WIDTH as u32,
+        CAMERA_HEIGHT as u32,
+        FilterType::Triangle,
+    );
+    game_boy.set_camera_image(&image.to_luma8().into_raw())
+}
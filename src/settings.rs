@@ -0,0 +1,15 @@
+//! Options of the frontend which the emulation doesn't know about.
+//! Like the [`GameBoyConfig`](lemon_gb_core::game_boy::config::GameBoyConfig) they are chosen at startup
+//! and can be overridden per game by a [`GameProfile`](crate::profiles::GameProfile).
+
+use crate::locale::Language;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FrontendSettings {
+    /// Mix consecutive frames with the [`FrameBlender`](crate::frame_blending::FrameBlender)
+    pub frame_blending: bool,
+    /// Draw on screen messages twice as large, F10 toggles it
+    pub large_osd_text: bool,
+    /// Of the window title, on screen messages and panels
+    pub language: Language,
+}
@@ -0,0 +1,188 @@
+//! Save state slots of the frontend and a picker drawn over the screen, which shows the thumbnail of every slot.
+
+use crate::locale::{Language, Text};
+use crate::osd::{draw_text, write_pixel};
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::ppu::SCREEN_WIDTH;
+use lemon_gb_core::game_boy::save_state::GameBoySaveState;
+use lemon_gb_core::game_boy::thumbnail::{Thumbnail, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_STATES_DIRECTORY: &str = "./states";
+/// The picker shows the slots in a 2x2 grid of thumbnails, which covers the whole screen
+pub const SLOT_COUNT: usize = 4;
+const COLUMNS: usize = SCREEN_WIDTH / THUMBNAIL_WIDTH;
+const EMPTY_COLOR: [u8; 4] = [0x20, 0x20, 0x20, 0xFF];
+const TEXT_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+const LABEL_BACKGROUND_COLOR: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+const SELECTION_COLOR: [u8; 4] = [0xFF, 0xD0, 0x00, 0xFF];
+
+/// What the picker knows about a slot without loading the state
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Slot {
+    #[default]
+    Empty,
+    /// States saved before thumbnails existed have none
+    Saved(Option<Thumbnail>),
+}
+
+impl Slot {
+    /// Empty if there is no readable state at the path
+    pub fn read(path: &Path) -> Self {
+        match GameBoySaveState::load_any(path) {
+            Ok(state) => Slot::Saved(state.thumbnail),
+            Err(_) => Slot::Empty,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StatePicker {
+    open: bool,
+    selected: usize,
+    slots: [Slot; SLOT_COUNT],
+    /// Of the slot labels
+    language: Language,
+}
+
+impl StatePicker {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    /// Shows the picker with the current contents of the slots, the selection is kept from the last time
+    pub fn open(&mut self, slots: [Slot; SLOT_COUNT]) {
+        self.slots = slots;
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn get_slot(&self, index: usize) -> Option<&Slot> {
+        self.slots.get(index)
+    }
+
+    pub fn set_slot(&mut self, index: usize, slot: Slot) {
+        if let Some(existing) = self.slots.get_mut(index) {
+            *existing = slot;
+        }
+    }
+
+    /// Moves through the grid, leaving it on one side enters it on the other
+    pub fn move_selection(&mut self, columns: isize, rows: isize) {
+        let rows_count = SLOT_COUNT / COLUMNS;
+        let column = (self.selected % COLUMNS) as isize + columns;
+        let row = (self.selected / COLUMNS) as isize + rows;
+        self.selected = row.rem_euclid(rows_count as isize) as usize * COLUMNS
+            + column.rem_euclid(COLUMNS as isize) as usize;
+    }
+
+    /// Draws the grid onto an RGBA8888 frame if the picker is open
+    pub fn draw(&self, frame: &mut [u8]) {
+        if !self.open {
+            return;
+        }
+        for (index, slot) in self.slots.iter().enumerate() {
+            let left = index % COLUMNS * THUMBNAIL_WIDTH;
+            let top = index / COLUMNS * THUMBNAIL_HEIGHT;
+            for y in 0..THUMBNAIL_HEIGHT {
+                for x in 0..THUMBNAIL_WIDTH {
+                    let color = match slot {
+                        Slot::Saved(Some(thumbnail)) => {
+                            let [r, g, b] = thumbnail.get_pixel(x, y);
+                            [r, g, b, 0xFF]
+                        }
+                        _ => EMPTY_COLOR,
+                    };
+                    write_pixel(frame, left + x, top + y, color);
+                }
+            }
+
+            let label = match slot {
+                Slot::Empty => Some(Text::EmptySlotLabel),
+                Slot::Saved(None) => Some(Text::NoPreviewLabel),
+                Slot::Saved(Some(_)) => None,
+            };
+            if let Some(label) = label {
+                let text = self.language.text(label);
+                draw_text(frame, left + 2, top + 32, text, TEXT_COLOR);
+            }
+
+            // The slot number in the top left corner, on a background so it stays readable
+            for y in 0..9 {
+                for x in 0..8 {
+                    write_pixel(frame, left + x, top + y, LABEL_BACKGROUND_COLOR);
+                }
+            }
+            draw_text(
+                frame,
+                left + 1,
+                top + 1,
+                &(index + 1).to_string(),
+                TEXT_COLOR,
+            );
+
+            if index == self.selected {
+                draw_border(frame, left, top);
+            }
+        }
+    }
+}
+
+/// Where a slot of the game is stored, e.g. `./states/TETRIS.2.state` for the second slot
+pub fn slot_path(directory: &Path, header: &CartridgeHeader, index: usize) -> PathBuf {
+    let title: String = header
+        .title
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let title = if title.is_empty() { "UNTITLED" } else { &title };
+    directory.join(format!("{}.{}.state", title, index + 1))
+}
+
+/// The slots of the game, unreadable states count as empty
+pub fn read_slots(directory: &Path, header: &CartridgeHeader) -> [Slot; SLOT_COUNT] {
+    std::array::from_fn(|index| Slot::read(&slot_path(directory, header, index)))
+}
+
+/// Creates the directory if needed, the state is compressed if the feature is enabled
+pub fn store_slot(state: &GameBoySaveState, path: &Path) -> std::io::Result<()> {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    #[cfg(feature = "compression")]
+    let bytes = state.to_compressed_bytes()?;
+    #[cfg(not(feature = "compression"))]
+    let bytes = state.to_bytes()?;
+    std::fs::write(path, bytes)
+}
+
+/// 2 pixels wide, inside of the thumbnail
+fn draw_border(frame: &mut [u8], left: usize, top: usize) {
+    for y in 0..THUMBNAIL_HEIGHT {
+        for x in 0..THUMBNAIL_WIDTH {
+            if x < 2 || y < 2 || x >= THUMBNAIL_WIDTH - 2 || y >= THUMBNAIL_HEIGHT - 2 {
+                write_pixel(frame, left + x, top + y, SELECTION_COLOR);
+            }
+        }
+    }
+}
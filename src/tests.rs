@@ -1,14 +1,35 @@
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::GameBoy;
 use std::fs::create_dir;
 use std::path::PathBuf;
 
-mod test_cpu_registers;
-mod test_halt;
-mod test_instructions;
-mod test_interrupts;
-mod test_mbc;
-pub mod test_roms;
-mod test_save_load;
-mod test_timer;
+mod test_autosplit;
+mod test_built_in;
+mod test_cartridge_type;
+mod test_cheat_manager;
+mod test_cheats;
+mod test_debugger;
+mod test_debugger_panel;
+mod test_diagnostics_panel;
+mod test_frame_blending;
+mod test_headless;
+mod test_input_display;
+mod test_link;
+mod test_locale;
+mod test_logging;
+mod test_memory_editor;
+mod test_osd;
+mod test_panel;
+mod test_profiles;
+mod test_replay;
+mod test_rom_library;
+mod test_rpc;
+mod test_scenario;
+#[cfg(feature = "image")]
+mod test_screenshot;
+mod test_state_picker;
+mod test_throttle;
+mod test_vram_viewer;
 
 pub fn setup_test_dir() -> PathBuf {
     let test_dir = PathBuf::from("./test");
@@ -17,3 +38,9 @@ pub fn setup_test_dir() -> PathBuf {
     }
     test_dir
 }
+
+/// Starts one of the ROMs in ./test_roms with the default config
+pub fn load_test_rom(name: &str) -> GameBoy {
+    let cartridge = Cartridge::load(PathBuf::from("./test_roms").join(name)).unwrap();
+    GameBoy::initialize(&cartridge).unwrap()
+}
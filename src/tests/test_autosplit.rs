@@ -0,0 +1,179 @@
+use crate::autosplit::{format_time, AutosplitConfig, Autosplitter, SplitEvent};
+use crate::headless::MemoryCondition;
+use crate::profiles::GameProfile;
+use crate::throttle::GAME_BOY_FRAME_DURATION;
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+use std::time::Duration;
+
+const LEVEL_ADDRESS: u16 = 0xC000;
+const TITLE_SCREEN_ADDRESS: u16 = 0xC001;
+
+/// Loops forever, the conditions are driven by poking WRAM between frames
+fn build_game_boy() -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x102].copy_from_slice(&[0x18, 0xFE]); // JR -2
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+fn condition(address: u16, value: u8) -> Option<MemoryCondition> {
+    Some(MemoryCondition { address, value })
+}
+
+/// Starts when level 1 is entered, splits at levels 2 and 3 and resets on the title screen
+fn build_autosplitter() -> Autosplitter {
+    Autosplitter::new(AutosplitConfig {
+        start: condition(LEVEL_ADDRESS, 1),
+        splits: vec![
+            condition(LEVEL_ADDRESS, 2).unwrap(),
+            condition(LEVEL_ADDRESS, 3).unwrap(),
+        ],
+        reset: condition(TITLE_SCREEN_ADDRESS, 1),
+    })
+}
+
+/// Pokes the value, runs a frame and returns the events of that frame
+fn run_frame(
+    game_boy: &mut GameBoy,
+    autosplitter: &mut Autosplitter,
+    poke: Option<(u16, u8)>,
+) -> Vec<SplitEvent> {
+    if let Some((address, value)) = poke {
+        game_boy.poke(address, value);
+    }
+    game_boy.finish_frame();
+    autosplitter.update(game_boy)
+}
+
+#[test]
+fn test_run() {
+    let mut game_boy = build_game_boy();
+    let mut autosplitter = build_autosplitter();
+    let mut frame = |poke| run_frame(&mut game_boy, &mut autosplitter, poke);
+
+    assert_eq!(frame(None), vec![]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 1))), vec![SplitEvent::Start]);
+    // Conditions only fire when they become true
+    assert_eq!(frame(None), vec![]);
+    assert_eq!(frame(None), vec![]);
+    assert_eq!(
+        frame(Some((LEVEL_ADDRESS, 3))),
+        vec![],
+        "Splits fire in order"
+    );
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 2))), vec![SplitEvent::Split(0)]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 3))), vec![SplitEvent::Split(1)]);
+
+    assert!(autosplitter.is_finished());
+    assert_eq!(
+        autosplitter.get_split_times(),
+        vec![GAME_BOY_FRAME_DURATION * 4, GAME_BOY_FRAME_DURATION * 5]
+    );
+    game_boy.finish_frame();
+    autosplitter.update(&game_boy);
+    assert_eq!(
+        autosplitter.get_elapsed(),
+        Some(GAME_BOY_FRAME_DURATION * 5)
+    );
+}
+
+#[test]
+fn test_reset() {
+    let mut game_boy = build_game_boy();
+    let mut autosplitter = build_autosplitter();
+    let mut frame = |poke| run_frame(&mut game_boy, &mut autosplitter, poke);
+
+    assert_eq!(
+        frame(Some((TITLE_SCREEN_ADDRESS, 1))),
+        vec![],
+        "Not running yet"
+    );
+    assert_eq!(frame(Some((TITLE_SCREEN_ADDRESS, 0))), vec![]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 1))), vec![SplitEvent::Start]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 2))), vec![SplitEvent::Split(0)]);
+    assert_eq!(
+        frame(Some((TITLE_SCREEN_ADDRESS, 1))),
+        vec![SplitEvent::Reset]
+    );
+    assert!(!autosplitter.is_running());
+    assert!(autosplitter.get_split_times().is_empty());
+    assert_eq!(autosplitter.get_elapsed(), None);
+}
+
+#[test]
+fn test_reset_and_start_in_the_same_frame() {
+    let mut game_boy = build_game_boy();
+    let mut autosplitter = build_autosplitter();
+    run_frame(&mut game_boy, &mut autosplitter, Some((LEVEL_ADDRESS, 1)));
+    run_frame(&mut game_boy, &mut autosplitter, Some((LEVEL_ADDRESS, 0)));
+
+    game_boy.poke(TITLE_SCREEN_ADDRESS, 1);
+    let events = run_frame(&mut game_boy, &mut autosplitter, Some((LEVEL_ADDRESS, 1)));
+    assert_eq!(events, vec![SplitEvent::Reset, SplitEvent::Start]);
+    assert_eq!(autosplitter.get_elapsed(), Some(Duration::ZERO));
+}
+
+#[test]
+fn test_split_already_met_at_the_start() {
+    let mut game_boy = build_game_boy();
+    let mut autosplitter = Autosplitter::new(AutosplitConfig {
+        start: condition(TITLE_SCREEN_ADDRESS, 0),
+        splits: vec![condition(LEVEL_ADDRESS, 0).unwrap()],
+        reset: None,
+    });
+    let mut frame = |poke| run_frame(&mut game_boy, &mut autosplitter, poke);
+
+    assert_eq!(frame(None), vec![SplitEvent::Start]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 1))), vec![]);
+    assert_eq!(frame(Some((LEVEL_ADDRESS, 0))), vec![SplitEvent::Split(0)]);
+}
+
+#[rstest]
+#[case(SplitEvent::Start, "starttimer")]
+#[case(SplitEvent::Split(3), "split")]
+#[case(SplitEvent::Reset, "reset")]
+fn test_livesplit_commands(#[case] event: SplitEvent, #[case] expected: &str) {
+    assert_eq!(event.livesplit_command(), expected);
+}
+
+#[rstest]
+#[case(Duration::ZERO, "0:00.00")]
+#[case(Duration::from_millis(62_359), "1:02.35")]
+#[case(Duration::from_secs(3600), "60:00.00")]
+fn test_format_time(#[case] time: Duration, #[case] expected: &str) {
+    assert_eq!(format_time(time), expected);
+}
+
+#[test]
+fn test_profile_stores_conditions_as_text() {
+    let json =
+        r#"{"autosplit": {"start": "0xD356 == 0x05", "splits": ["$C000=$02"], "reset": null}}"#;
+    let profile: GameProfile = serde_json::from_str(json).unwrap();
+    let config = profile.autosplit.unwrap();
+
+    assert_eq!(config.start, condition(0xD356, 0x05));
+    assert_eq!(config.splits, vec![condition(0xC000, 0x02).unwrap()]);
+    let stored = serde_json::to_value(&config).unwrap();
+    assert_eq!(stored["start"], "0xD356=0x05");
+}
+
+#[cfg(feature = "livesplit")]
+#[test]
+fn test_livesplit_client_sends_commands() {
+    use crate::autosplit::livesplit::LiveSplitClient;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let mut client = LiveSplitClient::connect(&address).unwrap();
+    client.send(SplitEvent::Start).unwrap();
+    client.send(SplitEvent::Split(0)).unwrap();
+    drop(client);
+
+    let mut received = String::new();
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.read_to_string(&mut received).unwrap();
+    assert_eq!(received, "starttimer\r\nsplit\r\n");
+}
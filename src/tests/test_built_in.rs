@@ -0,0 +1,36 @@
+use crate::built_in;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::GameBoy;
+
+const SCY_ADDRESS: u16 = 0xFF42;
+
+fn screen_rows(game_boy: &GameBoy, rows: std::ops::Range<usize>) -> Vec<u8> {
+    game_boy.get_frame_buffer()[rows.start * SCREEN_WIDTH * 4..rows.end * SCREEN_WIDTH * 4].to_vec()
+}
+
+#[test]
+fn test_built_in_rom_bounces_title_above_help() {
+    let cartridge = built_in::cartridge();
+    assert_eq!(cartridge.header.title, built_in::TITLE);
+
+    let mut game_boy = GameBoy::initialize(&cartridge).unwrap();
+    let mut frames = Vec::new();
+    for _ in 0..32 {
+        game_boy.finish_frame();
+        frames.push((
+            game_boy.read_memory(SCY_ADDRESS),
+            screen_rows(&game_boy, 0..104),
+            screen_rows(&game_boy, 104..SCREEN_HEIGHT),
+        ));
+    }
+
+    let (first_scy, first_title, first_help) = &frames[4];
+    let (other_scy, other_title, other_help) = &frames[20];
+    assert_ne!(first_scy, other_scy);
+    assert_ne!(first_title, other_title);
+    assert_eq!(first_help, other_help);
+
+    // The help text is drawn, not just a blank screen
+    let first_pixel = &first_help[..4];
+    assert!(first_help.chunks_exact(4).any(|pixel| pixel != first_pixel));
+}
@@ -0,0 +1,16 @@
+use crate::cli::{parse_args, Command};
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_args_info() {
+    let args = ["info", "game.gb"].map(String::from);
+    assert_eq!(
+        parse_args(args).unwrap(),
+        Some(Command::Info {
+            rom: PathBuf::from("game.gb")
+        })
+    );
+
+    assert!(parse_args(["info"].map(String::from)).is_err());
+    assert!(parse_args(["info", "a.gb", "b.gb"].map(String::from)).is_err());
+}
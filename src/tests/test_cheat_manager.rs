@@ -0,0 +1,88 @@
+use crate::cheat_manager::{CheatManager, MAX_CODE_LENGTH};
+use crate::locale::Language;
+use crate::panel::Panel;
+use lemon_gb_core::game_boy::cheats::Cheat;
+
+fn open_manager() -> CheatManager {
+    let mut disabled = Cheat::new("019910C0").unwrap();
+    disabled.enabled = false;
+    let mut manager = CheatManager::new(Language::English);
+    manager.open(vec![Cheat::new("3EA-17B").unwrap(), disabled]);
+    manager
+}
+
+#[test]
+fn test_add_cheat() {
+    let mut manager = open_manager();
+    manager.move_selection(-1);
+    assert_eq!(manager.get_selected(), 2);
+
+    manager.start_editing();
+    assert_eq!(manager.get_edited_code(), Some(""));
+    manager.type_text("00a-17b-c49");
+    manager.finish_editing().unwrap();
+
+    assert!(!manager.is_editing());
+    assert_eq!(manager.get_cheats()[2], Cheat::new("00A-17B-C49").unwrap());
+    // Ready for the next code
+    assert_eq!(manager.get_selected(), 3);
+}
+
+#[test]
+fn test_edit_cheat() {
+    let mut manager = open_manager();
+    manager.start_editing();
+    assert_eq!(manager.get_edited_code(), Some("3EA-17B"));
+    manager.delete_character();
+    manager.type_text("C");
+    manager.finish_editing().unwrap();
+    assert_eq!(manager.get_cheats()[0].code, "3EA-17C");
+    assert_eq!(manager.get_cheats().len(), 2);
+}
+
+#[test]
+fn test_invalid_code_stays_edited() {
+    let mut manager = open_manager();
+    manager.start_editing();
+    manager.delete_character();
+    manager.delete_character();
+    assert!(manager.finish_editing().is_err());
+    assert_eq!(manager.get_edited_code(), Some("3EA-1"));
+
+    manager.cancel_editing();
+    assert_eq!(manager.get_cheats()[0].code, "3EA-17B");
+}
+
+#[test]
+fn test_typed_text_is_filtered() {
+    let mut manager = open_manager();
+    manager.move_selection(2);
+    manager.start_editing();
+    manager.type_text("0g1 x0-ffffffffffff");
+    assert_eq!(manager.get_edited_code(), Some("010-FFFFFFF"));
+    assert_eq!(manager.get_edited_code().unwrap().len(), MAX_CODE_LENGTH);
+}
+
+#[test]
+fn test_toggle_and_remove() {
+    let mut manager = open_manager();
+    assert!(manager.toggle_selected());
+    assert!(!manager.get_cheats()[0].enabled);
+
+    assert!(manager.remove_selected());
+    assert_eq!(manager.get_cheats().len(), 1);
+    assert_eq!(manager.get_cheats()[0].code, "019910C0");
+
+    // The line for a new cheat has nothing to toggle or remove
+    manager.move_selection(1);
+    assert!(!manager.toggle_selected());
+    assert!(!manager.remove_selected());
+}
+
+#[test]
+fn test_no_toggle_while_editing() {
+    let mut manager = open_manager();
+    manager.start_editing();
+    assert!(!manager.toggle_selected());
+    assert!(!manager.remove_selected());
+}
@@ -0,0 +1,41 @@
+use crate::profiles::{GameProfile, Profiles};
+use crate::tests::setup_test_dir;
+use lemon_gb_core::game_boy::cheats::Cheat;
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+
+/// 0x42 in place of 0x11 at 0x0200
+const PATCH_CODE: &str = "422-00F-A0E";
+
+#[test]
+fn test_cheats_stored_in_profile() {
+    let header = CartridgeHeader {
+        title: "CHEATS".to_string(),
+        ..CartridgeHeader::default()
+    };
+    let mut profiles = Profiles::default();
+    profiles.set(
+        &header,
+        GameProfile {
+            colorize: Some(true),
+            ..Default::default()
+        },
+    );
+    profiles.set_cheats(&header, vec![Cheat::new(PATCH_CODE).unwrap()]);
+    let profile = profiles.get(&header).unwrap();
+    assert_eq!(profile.colorize, Some(true));
+    assert_eq!(profile.cheats, [Cheat::new(PATCH_CODE).unwrap()]);
+
+    let path = setup_test_dir().join("cheat_profiles.json");
+    profiles.store(&path).unwrap();
+    assert_eq!(Profiles::load(&path).unwrap(), profiles);
+}
+
+#[test]
+fn test_profiles_without_cheats_load() {
+    let profile: GameProfile = serde_json::from_str(r#"{"colorize": true}"#).unwrap();
+    assert!(profile.cheats.is_empty());
+
+    let profile: GameProfile =
+        serde_json::from_str(r#"{"cheats": [{"code": "3EA-17B"}]}"#).unwrap();
+    assert_eq!(profile.cheats, [Cheat::new("3EA-17B").unwrap()]);
+}
@@ -0,0 +1,132 @@
+use crate::debugger::{DebugEvent, DebugMessage, Debugger};
+use crate::tests::load_test_rom;
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+
+/// NOP, LD B,B, then LD D,D with the message at 0x0152 and an endless loop at 0x015F
+const DEBUG_CONVENTIONS_PROGRAM: [u8; 17] = [
+    0x00, // NOP
+    0x40, // LD B,B
+    0x52, // LD D,D
+    0x18, 0x0A, // JR +10
+    0x64, 0x64, 0x00, 0x00, // Signature
+    b'H', b'E', b'L', b'L', b'O', b'!', 0x18, 0xFE, // Message / JR -2
+];
+
+#[test]
+fn test_toggle_breakpoint() {
+    let mut debugger = Debugger::new();
+    assert!(debugger.toggle_breakpoint(0x0150));
+    debugger.set_breakpoint(0x0100);
+    assert!(debugger.has_breakpoint(0x0150));
+    assert_eq!(
+        debugger.get_breakpoints().collect::<Vec<_>>(),
+        vec![0x0100, 0x0150]
+    );
+
+    assert!(!debugger.toggle_breakpoint(0x0150));
+    assert!(!debugger.has_breakpoint(0x0150));
+    debugger.clear_breakpoints();
+    assert_eq!(debugger.get_breakpoints().count(), 0);
+}
+
+#[test]
+fn test_run_frame_stops_at_breakpoint() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut debugger = Debugger::new();
+    // cpu_instrs jumps from the entry point to 0x0637
+    debugger.set_breakpoint(0x0637);
+
+    assert_eq!(
+        debugger.run_frame(&mut game_boy),
+        DebugEvent::BreakpointHit(0x0637)
+    );
+    assert_eq!(game_boy.get_pc(), 0x0637);
+    assert!(debugger.is_paused());
+    assert_eq!(debugger.run_frame(&mut game_boy), DebugEvent::Paused);
+    assert_eq!(game_boy.get_pc(), 0x0637);
+
+    // Resuming executes the instruction under the breakpoint instead of hitting it again
+    debugger.remove_breakpoint(0x0637);
+    debugger.resume();
+    assert_eq!(debugger.run_frame(&mut game_boy), DebugEvent::FrameFinished);
+    assert!(!debugger.is_paused());
+}
+
+fn build_debug_conventions_game_boy(program: &[u8]) -> GameBoy {
+    let rom = RomBuilder::new().program(program).build();
+    GameBoy::headless(&rom).unwrap()
+}
+
+#[test]
+fn test_debug_conventions_disabled_by_default() {
+    let mut game_boy = build_debug_conventions_game_boy(&DEBUG_CONVENTIONS_PROGRAM);
+    let mut debugger = Debugger::new();
+
+    assert_eq!(debugger.run_frame(&mut game_boy), DebugEvent::FrameFinished);
+    assert!(debugger.take_debug_messages().is_empty());
+}
+
+#[test]
+fn test_software_breakpoint() {
+    let mut game_boy = build_debug_conventions_game_boy(&DEBUG_CONVENTIONS_PROGRAM);
+    let mut debugger = Debugger::new();
+    debugger.set_software_breakpoints(true);
+
+    assert_eq!(
+        debugger.run_frame(&mut game_boy),
+        DebugEvent::SoftwareBreakpointHit(0x0151)
+    );
+    assert!(debugger.is_paused());
+
+    debugger.resume();
+    assert_eq!(debugger.run_frame(&mut game_boy), DebugEvent::FrameFinished);
+    assert_eq!(game_boy.get_pc(), 0x015F);
+}
+
+#[test]
+fn test_debug_message() {
+    let mut game_boy = build_debug_conventions_game_boy(&DEBUG_CONVENTIONS_PROGRAM);
+    let mut debugger = Debugger::new();
+    debugger.set_debug_messages(true);
+
+    assert_eq!(debugger.run_frame(&mut game_boy), DebugEvent::FrameFinished);
+    assert_eq!(
+        debugger.take_debug_messages(),
+        vec![DebugMessage {
+            address: 0x0152,
+            text: "HELLO!".to_string()
+        }]
+    );
+    assert!(debugger.take_debug_messages().is_empty());
+}
+
+/// An LD D,D which isn't followed by the message format is just an instruction
+#[rstest]
+#[case::no_jump(&[0x52, 0x00, 0x18, 0xFE])]
+#[case::wrong_signature(&[0x52, 0x18, 0x05, 0x64, 0x65, 0x00, 0x00, b'A', 0x18, 0xFE])]
+#[case::jump_backwards(&[0x52, 0x18, 0xFE])]
+fn test_malformed_debug_message(#[case] program: &[u8]) {
+    let mut game_boy = build_debug_conventions_game_boy(program);
+    let mut debugger = Debugger::new();
+    debugger.set_debug_messages(true);
+
+    debugger.run_frame(&mut game_boy);
+    assert!(debugger.take_debug_messages().is_empty());
+}
+
+#[test]
+fn test_step_instruction_follows_pc() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut debugger = Debugger::new();
+
+    let disassembly = debugger.disassembly_at_pc(&game_boy, 3);
+    assert_eq!(disassembly.len(), 3);
+    assert_eq!(disassembly[0].address, 0x0100);
+    assert_eq!(disassembly[1].address, 0x0101);
+
+    debugger.step_instruction(&mut game_boy);
+    assert_eq!(game_boy.get_pc(), 0x0101);
+    assert_eq!(debugger.disassembly_at_pc(&game_boy, 1)[0].address, 0x0101);
+}
@@ -0,0 +1,88 @@
+use crate::debugger::Debugger;
+use crate::debugger_panel::{DebuggerPanel, Register, DISASSEMBLY_LINES, MAX_VALUE_LENGTH};
+use crate::locale::Language;
+use crate::panel::Panel;
+use crate::tests::load_test_rom;
+
+fn open_panel() -> DebuggerPanel {
+    let mut panel = DebuggerPanel::new(Language::English);
+    panel.open();
+    panel
+}
+
+#[test]
+fn test_selection_wraps() {
+    let mut panel = open_panel();
+    panel.move_selection(-1);
+    assert_eq!(
+        panel.get_selected(),
+        Register::ALL.len() + DISASSEMBLY_LINES - 1
+    );
+    panel.move_selection(1);
+    assert_eq!(panel.get_selected(), 0);
+    assert_eq!(panel.get_selected_register(), Some(Register::AF));
+}
+
+#[test]
+fn test_toggle_breakpoint_on_selected_line() {
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    let mut debugger = Debugger::new();
+    let mut panel = open_panel();
+    // A register has no address
+    assert!(!panel.toggle_breakpoint(&game_boy, &mut debugger));
+
+    // The entry point is a NOP followed by a jump
+    panel.move_selection(Register::ALL.len() as isize + 1);
+    assert_eq!(panel.get_selected_address(&game_boy), Some(0x0101));
+    assert!(panel.toggle_breakpoint(&game_boy, &mut debugger));
+    assert_eq!(debugger.get_breakpoints().collect::<Vec<_>>(), vec![0x0101]);
+    assert!(panel.toggle_breakpoint(&game_boy, &mut debugger));
+    assert!(!debugger.has_breakpoint(0x0101));
+}
+
+#[test]
+fn test_edit_register() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut panel = open_panel();
+    panel.move_selection(5);
+    assert!(panel.start_editing(&game_boy));
+    assert_eq!(panel.get_edited_value(), Some("0100"));
+    for _ in 0..MAX_VALUE_LENGTH {
+        panel.delete_character();
+    }
+    panel.type_text("637");
+    panel.finish_editing(&mut game_boy).unwrap();
+
+    assert!(!panel.is_editing());
+    assert_eq!(game_boy.get_pc(), 0x0637);
+    assert_eq!(Register::PC.get(&game_boy), 0x0637);
+}
+
+#[test]
+fn test_empty_value_stays_edited() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let af = Register::AF.get(&game_boy);
+    let mut panel = open_panel();
+    panel.start_editing(&game_boy);
+    for _ in 0..MAX_VALUE_LENGTH {
+        panel.delete_character();
+    }
+    assert!(panel.finish_editing(&mut game_boy).is_err());
+    assert!(panel.is_editing());
+
+    panel.cancel_editing();
+    assert_eq!(Register::AF.get(&game_boy), af);
+}
+
+#[test]
+fn test_only_registers_are_edited() {
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    let mut panel = open_panel();
+    panel.move_selection(Register::ALL.len() as isize);
+    assert!(!panel.start_editing(&game_boy));
+    assert!(!panel.is_editing());
+
+    panel.move_selection(-1);
+    assert!(panel.start_editing(&game_boy));
+    assert_eq!(panel.get_edited_value(), Some("0100"));
+}
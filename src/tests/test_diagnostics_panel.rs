@@ -0,0 +1,48 @@
+use crate::diagnostics_panel::{DiagnosticsPanel, MAX_LINES};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::diagnostics::Diagnostic;
+
+#[test]
+fn test_panel_wraps_and_keeps_the_latest_lines() {
+    let mut panel = DiagnosticsPanel::default();
+    panel.push(&Diagnostic::ExecutionAtFFFF);
+    assert_eq!(panel.get_lines(), ["Execution reached 0xFFFF"]);
+
+    panel.push(&Diagnostic::StackInUnusableRegion { sp: 0xFEFE });
+    assert_eq!(
+        panel.get_lines()[1..],
+        [
+            "Stack overflow into the",
+            "  unusable region, SP is",
+            "  0xFEFE"
+        ]
+    );
+
+    for _ in 0..MAX_LINES {
+        panel.push(&Diagnostic::DisabledRamRead { address: 0xA000 });
+    }
+    assert_eq!(panel.get_lines().len(), MAX_LINES);
+    assert_eq!(panel.get_lines()[0], "Read of disabled");
+
+    panel.clear();
+    assert!(panel.get_lines().is_empty());
+}
+
+#[test]
+fn test_panel_only_drawn_while_visible() {
+    let blank_frame = vec![0x80; SCREEN_WIDTH * SCREEN_HEIGHT * 4];
+    let mut panel = DiagnosticsPanel::default();
+    panel.push(&Diagnostic::ExecutionAtFFFF);
+    let mut frame = blank_frame.clone();
+    panel.draw(&mut frame);
+    assert_eq!(frame, blank_frame);
+
+    panel.set_visible(true);
+    panel.draw(&mut frame);
+    assert_ne!(frame, blank_frame);
+    // The panel covers only the top of the screen
+    assert_eq!(
+        frame[SCREEN_WIDTH * 4 * 20..],
+        blank_frame[SCREEN_WIDTH * 4 * 20..]
+    );
+}
@@ -0,0 +1,51 @@
+use crate::frame_blending::FrameBlender;
+use rstest::rstest;
+
+#[test]
+fn test_first_frame_is_copied() {
+    let mut blender = FrameBlender::new();
+    let mut output = [0u8; 4];
+    blender.blend(&[0x10, 0x20, 0x30, 0xFF], &mut output);
+    assert_eq!(output, [0x10, 0x20, 0x30, 0xFF]);
+}
+
+#[rstest]
+#[case::average(0.5, [0x80, 0x80, 0x80, 0xFF])]
+#[case::current_only(0.0, [0x00, 0x00, 0x00, 0xFF])]
+#[case::mostly_previous(0.75, [0xBF, 0xBF, 0xBF, 0xFF])]
+#[case::clamped(2.0, [0xFF, 0xFF, 0xFF, 0xFF])]
+fn test_blend_weights(#[case] previous_weight: f32, #[case] expected: [u8; 4]) {
+    let mut blender = FrameBlender::with_previous_weight(previous_weight);
+    let mut output = [0u8; 4];
+    blender.blend(&[0xFF, 0xFF, 0xFF, 0xFF], &mut output);
+    blender.blend(&[0x00, 0x00, 0x00, 0xFF], &mut output);
+    assert_eq!(output, expected);
+}
+
+/// A sprite flickering at 30 Hz stays at half strength instead of blinking
+#[test]
+fn test_flicker_is_blended_with_the_unblended_previous_frame() {
+    let mut blender = FrameBlender::new();
+    let mut output = [0u8; 1];
+    blender.blend(&[0xFF], &mut output);
+    for frame in 0..10 {
+        let value = if frame % 2 == 0 { 0x00 } else { 0xFF };
+        blender.blend(&[value], &mut output);
+        assert_eq!(output, [0x80]);
+    }
+}
+
+#[test]
+fn test_reset_forgets_previous_frame() {
+    let mut blender = FrameBlender::new();
+    let mut output = [0u8; 2];
+    blender.blend(&[0xFF, 0xFF], &mut output);
+    blender.reset();
+    blender.blend(&[0x00, 0x00], &mut output);
+    assert_eq!(output, [0x00, 0x00]);
+
+    // A frame of another size starts over as well
+    let mut output = [0u8; 3];
+    blender.blend(&[0x40, 0x40, 0x40], &mut output);
+    assert_eq!(output, [0x40, 0x40, 0x40]);
+}
@@ -0,0 +1,203 @@
+use crate::cli::{parse_args, Command};
+use crate::headless::{run_headless, HeadlessOptions, MemoryCondition};
+use lemon_gb_core::disassembler::listing::{ListingFormat, ListingOptions};
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+use std::path::PathBuf;
+
+/// Sends "OK" over the serial port, stores 0x42 at 0xC000 and loops forever
+const PROGRAM: [u8; 23] = [
+    0x3E, b'O', 0xE0, 0x01, 0x3E, 0x81, 0xE0,
+    0x02, // LD A, 'O' / LDH (SB), A / LD A, 0x81 / LDH (SC), A
+    0x3E, b'K', 0xE0, 0x01, 0x3E, 0x81, 0xE0,
+    0x02, // LD A, 'K' / LDH (SB), A / LD A, 0x81 / LDH (SC), A
+    0x3E, 0x42, 0xEA, 0x00, 0xC0, // LD A, 0x42 / LD (0xC000), A
+    0x18, 0xFE, // JR -2
+];
+
+fn build_game_boy() -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x103].copy_from_slice(&[0xC3, 0x50, 0x01]); // JP 0x0150
+    rom[0x150..0x150 + PROGRAM.len()].copy_from_slice(&PROGRAM);
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[rstest]
+#[case(Some("OK"), None, None, true, 1)]
+#[case(Some("Passed"), None, None, false, 5)]
+#[case(Some("OK"), Some("O"), None, false, 1)]
+#[case(None, None, Some("0xC000=0x42"), true, 1)]
+#[case(None, None, Some("C000=00"), false, 5)]
+fn test_headless_outcome(
+    #[case] exit_on_serial: Option<&str>,
+    #[case] fail_on_serial: Option<&str>,
+    #[case] exit_on_memory: Option<&str>,
+    #[case] passed: bool,
+    #[case] frames: u64,
+) {
+    let options = HeadlessOptions {
+        max_frames: 5,
+        exit_on_serial: exit_on_serial.map(String::from),
+        fail_on_serial: fail_on_serial.map(String::from),
+        exit_on_memory: exit_on_memory.map(|condition| condition.parse().unwrap()),
+        ..HeadlessOptions::default()
+    };
+
+    let result = run_headless(&mut build_game_boy(), &options);
+    assert_eq!(result.passed, passed);
+    assert_eq!(result.exit_code(), if passed { 0 } else { 1 });
+    assert_eq!(result.frames, frames);
+    assert_eq!(result.serial_output, "OK");
+    assert_eq!(result.diagnostics, None);
+}
+
+#[test]
+fn test_watchdog_serial_timeout() {
+    let options = HeadlessOptions {
+        max_frames: 60,
+        serial_timeout: Some(3),
+        ..HeadlessOptions::default()
+    };
+
+    // "OK" is sent in the first frame, the program then loops without sending anything
+    let result = run_headless(&mut build_game_boy(), &options);
+    assert!(!result.passed);
+    assert_eq!(result.frames, 4);
+    assert_eq!(result.reason, "watchdog: no serial output within 3 frames");
+    let diagnostics = result.diagnostics.clone().unwrap();
+    assert!(diagnostics.contains("PC:0165"));
+    assert!(diagnostics.contains("JR"));
+    assert!(result.summary().ends_with(&diagnostics));
+}
+
+#[test]
+fn test_watchdog_vblank_timeout() {
+    let mut rom = vec![0u8; 0x8000];
+    // LD A, 0x00 / LDH (LCDC), A / JR -2
+    rom[0x100..0x106].copy_from_slice(&[0x3E, 0x00, 0xE0, 0x40, 0x18, 0xFE]);
+    let mut game_boy = GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap();
+    let options = HeadlessOptions {
+        max_frames: 60,
+        vblank_timeout: Some(200_000),
+        serial_timeout: Some(30),
+        ..HeadlessOptions::default()
+    };
+
+    let result = run_headless(&mut game_boy, &options);
+    assert!(!result.passed);
+    assert!(result.frames < 5);
+    assert_eq!(result.reason, "watchdog: no VBlank within 200000 cycles");
+    assert!(result.diagnostics.unwrap().contains("LCDC:00"));
+}
+
+#[rstest]
+#[case("0xFF80=0x01", 0xFF80, 0x01)]
+#[case("$D000=$FF", 0xD000, 0xFF)]
+#[case("a000=7", 0xA000, 0x07)]
+#[case("0xD356 == 0x05", 0xD356, 0x05)]
+fn test_memory_condition_parsing(#[case] input: &str, #[case] address: u16, #[case] value: u8) {
+    let condition: MemoryCondition = input.parse().unwrap();
+    assert_eq!(condition, MemoryCondition { address, value });
+}
+
+#[rstest]
+#[case("0xFF80")]
+#[case("0x10000=0x00")]
+#[case("0xC000=0x100")]
+#[case("0xC000===0x01")]
+fn test_memory_condition_parsing_errors(#[case] input: &str) {
+    assert!(input.parse::<MemoryCondition>().is_err());
+}
+
+#[test]
+fn test_parse_args_headless() {
+    let command = parse_args(args(&[
+        "run",
+        "game.gb",
+        "--headless",
+        "--max-frames",
+        "600",
+        "--exit-on-serial",
+        "Passed",
+        "--exit-on-memory",
+        "0xA000=0x00",
+        "--vblank-timeout",
+        "5",
+        "--serial-timeout",
+        "120",
+    ]))
+    .unwrap();
+
+    let expected_options = HeadlessOptions {
+        max_frames: 600,
+        exit_on_serial: Some("Passed".into()),
+        fail_on_serial: None,
+        exit_on_memory: Some(MemoryCondition {
+            address: 0xA000,
+            value: 0x00,
+        }),
+        vblank_timeout: Some(5_000_000),
+        serial_timeout: Some(120),
+    };
+    assert_eq!(
+        command,
+        Some(Command::RunHeadless {
+            rom: PathBuf::from("game.gb"),
+            options: expected_options,
+        })
+    );
+}
+
+#[test]
+fn test_parse_args_disassemble() {
+    let command = parse_args(args(&[
+        "disassemble",
+        "game.gb",
+        "--address",
+        "$4000",
+        "--bank",
+        "3",
+        "--count",
+        "10",
+        "--format",
+        "json",
+    ]))
+    .unwrap();
+
+    let expected_options = ListingOptions {
+        bank: Some(3),
+        address: 0x4000,
+        count: 10,
+        format: ListingFormat::Json,
+    };
+    assert_eq!(
+        command,
+        Some(Command::Disassemble {
+            rom: PathBuf::from("game.gb"),
+            options: expected_options,
+        })
+    );
+}
+
+#[rstest]
+#[case(&[], Ok(None))]
+#[case(&["run", "game.gb"], Ok(Some(Command::Run { rom: PathBuf::from("game.gb") })))]
+#[case(&["run"], Err(()))]
+#[case(&["play", "game.gb"], Err(()))]
+#[case(&["run", "game.gb", "--max-frames"], Err(()))]
+#[case(&["run", "game.gb", "--max-frames", "many"], Err(()))]
+#[case(&["run", "game.gb", "--turbo"], Err(()))]
+#[case(&["run", "game.gb", "--vblank-timeout", "18446744073709551615"], Err(()))]
+#[case(&["run", "game.gb", "--serial-timeout", "-1"], Err(()))]
+#[case(&["run", "game.gb", "other.gb"], Err(()))]
+#[case(&["disassemble"], Err(()))]
+#[case(&["disassemble", "game.gb", "--format", "xml"], Err(()))]
+#[case(&["disassemble", "game.gb", "--address", "0x10000"], Err(()))]
+fn test_parse_args(#[case] input: &[&str], #[case] expected: Result<Option<Command>, ()>) {
+    assert_eq!(parse_args(args(input)).map_err(|_| ()), expected);
+}
@@ -0,0 +1,71 @@
+use crate::input_display::{get_button_rectangle, get_position, InputDisplay, HEIGHT, WIDTH};
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use rstest::rstest;
+
+const PRESSED_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+const RELEASED_COLOR: [u8; 4] = [0x50, 0x50, 0x50, 0xFF];
+
+fn blank_frame() -> Vec<u8> {
+    vec![0x80; SCREEN_WIDTH * SCREEN_HEIGHT * 4]
+}
+
+fn pixel(frame: &[u8], x: usize, y: usize) -> [u8; 4] {
+    let index = (y * SCREEN_WIDTH + x) * 4;
+    frame[index..index + 4].try_into().unwrap()
+}
+
+/// The color in the middle of the button
+fn button_color(frame: &[u8], button: Button) -> [u8; 4] {
+    let (left, top, width, height) = get_button_rectangle(button);
+    pixel(frame, left + width / 2, top + height / 2)
+}
+
+#[test]
+fn test_hidden_by_default() {
+    let display = InputDisplay::default();
+    assert!(!display.is_visible());
+    let mut frame = blank_frame();
+    display.draw(&mut frame, Buttons::NONE.with(Button::A));
+    assert_eq!(frame, blank_frame());
+}
+
+#[rstest]
+#[case::nothing(Buttons::NONE)]
+#[case::a(Buttons::NONE.with(Button::A))]
+#[case::start_and_up(Buttons::NONE.with(Button::Start).with(Button::Up))]
+#[case::everything(Buttons::from_bits(0xFF))]
+fn test_draws_pressed_buttons(#[case] buttons: Buttons) {
+    let mut display = InputDisplay::default();
+    display.set_visible(true);
+    let mut frame = blank_frame();
+    display.draw(&mut frame, buttons);
+
+    for button in Button::ALL {
+        let expected = if buttons.is_pressed(button) {
+            PRESSED_COLOR
+        } else {
+            RELEASED_COLOR
+        };
+        assert_eq!(button_color(&frame, button), expected, "{button:?}");
+    }
+}
+
+/// The display stays in the top right corner and leaves the rest of the frame alone
+#[test]
+fn test_stays_in_corner() {
+    let (left, top) = get_position();
+    assert_eq!(left + WIDTH, SCREEN_WIDTH - 1);
+    assert_eq!(top, 1);
+
+    let mut display = InputDisplay::default();
+    display.set_visible(true);
+    let mut frame = blank_frame();
+    display.draw(&mut frame, Buttons::from_bits(0xFF));
+    for y in 0..SCREEN_HEIGHT {
+        for x in 0..SCREEN_WIDTH {
+            let inside = (left..left + WIDTH).contains(&x) && (top..top + HEIGHT).contains(&y);
+            assert_eq!(pixel(&frame, x, y) != [0x80; 4], inside, "({x}, {y})");
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::link_cable::{parse_port, with_default_port, LinkSession, LinkState, TcpLink};
+use crate::link_panel::{LinkAction, LinkPanel, LED_FRAMES, MAX_ADDRESS_LENGTH};
+use crate::locale::Language;
+use crate::panel::Panel;
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::components::mmu::SB_ADDRESS;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// LD A, 0x42 / LDH (SB), A / LD A, 0x81 / LDH (SC), A / JR to itself
+const INTERNAL_CLOCK_PROGRAM: [u8; 10] =
+    [0x3E, 0x42, 0xE0, 0x01, 0x3E, 0x81, 0xE0, 0x02, 0x18, 0xFE];
+/// Like [`INTERNAL_CLOCK_PROGRAM`], but sends 0x24 and waits for the partner's clock
+const EXTERNAL_CLOCK_PROGRAM: [u8; 10] =
+    [0x3E, 0x24, 0xE0, 0x01, 0x3E, 0x80, 0xE0, 0x02, 0x18, 0xFE];
+/// Writes 0x24 to SB without starting a transfer
+const IDLE_PROGRAM: [u8; 6] = [0x3E, 0x24, 0xE0, 0x01, 0x18, 0xFE];
+
+fn game_boy(program: &[u8]) -> GameBoy {
+    GameBoy::headless(&RomBuilder::new().program(program).build()).unwrap()
+}
+
+#[test]
+fn test_tcp_link_between_two_game_boys() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let joined = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (accepted, _) = listener.accept().unwrap();
+    let (ready_sender, ready) = channel();
+
+    let external = thread::spawn(move || {
+        let mut game_boy = game_boy(&EXTERNAL_CLOCK_PROGRAM);
+        let link = TcpLink::new(accepted).unwrap();
+        let activity = link.get_activity();
+        game_boy.connect_link(link);
+        game_boy.finish_frame();
+        ready_sender.send(()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while game_boy.read_memory(SB_ADDRESS) == 0x24 && Instant::now() < deadline {
+            game_boy.step();
+        }
+        (game_boy.read_memory(SB_ADDRESS), activity.get_transfers())
+    });
+
+    // The transfer is only started once the partner waits for it, otherwise it would see an idle line
+    ready.recv().unwrap();
+    let mut game_boy = game_boy(&INTERNAL_CLOCK_PROGRAM);
+    let link = TcpLink::new(joined).unwrap();
+    let activity = link.get_activity();
+    game_boy.connect_link(link);
+    game_boy.finish_frame();
+
+    assert_eq!(game_boy.read_memory(SB_ADDRESS), 0x24);
+    assert_eq!(activity.get_transfers(), 1);
+    assert_eq!(external.join().unwrap(), (0x42, 1));
+}
+
+#[test]
+fn test_session_hosts_and_joins() {
+    let mut host = game_boy(&IDLE_PROGRAM);
+    let mut guest = game_boy(&IDLE_PROGRAM);
+    let mut hosting = LinkSession::default();
+    let mut joining = LinkSession::default();
+    hosting.host(&mut host, 0).unwrap();
+    let LinkState::Hosting(address) = hosting.get_state().clone() else {
+        panic!("Not hosting: {:?}", hosting.get_state());
+    };
+    joining.join(&mut guest, &format!("127.0.0.1:{}", address.port()));
+    assert!(matches!(joining.get_state(), LinkState::Joining(_)));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !(host.is_link_connected() && guest.is_link_connected()) && Instant::now() < deadline {
+        hosting.update(&mut host);
+        joining.update(&mut guest);
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert!(matches!(hosting.get_state(), LinkState::Connected(_)));
+    assert!(matches!(joining.get_state(), LinkState::Connected(_)));
+
+    hosting.disconnect(&mut host);
+    assert!(!host.is_link_connected());
+    assert_eq!(hosting.get_state(), &LinkState::Disconnected);
+}
+
+#[test]
+fn test_session_notices_replaced_game_boy() {
+    let mut host = game_boy(&IDLE_PROGRAM);
+    let mut session = LinkSession::default();
+    session.host(&mut host, 0).unwrap();
+    let LinkState::Hosting(address) = session.get_state().clone() else {
+        panic!("Not hosting: {:?}", session.get_state());
+    };
+    let _partner = TcpStream::connect(("127.0.0.1", address.port())).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !host.is_link_connected() && Instant::now() < deadline {
+        session.update(&mut host);
+    }
+
+    host = game_boy(&IDLE_PROGRAM);
+    session.update(&mut host);
+    assert!(matches!(session.get_state(), LinkState::Failed(_)));
+}
+
+#[rstest]
+#[case::host_name("localhost", "localhost:8766")]
+#[case::ip("192.168.0.2", "192.168.0.2:8766")]
+#[case::with_port(" 192.168.0.2:5000 ", "192.168.0.2:5000")]
+#[case::ipv6("::1", "[::1]:8766")]
+#[case::ipv6_ending_in_number("fe80::2:1", "[fe80::2:1]:8766")]
+#[case::ipv6_in_brackets("[::1]", "[::1]:8766")]
+#[case::ipv6_with_port("[::1]:5000", "[::1]:5000")]
+fn test_with_default_port(#[case] address: &str, #[case] expected: &str) {
+    assert_eq!(with_default_port(address), expected);
+}
+
+#[rstest]
+#[case::empty("", Some(8766))]
+#[case::port("5000", Some(5000))]
+#[case::address("192.168.0.2:5000", Some(5000))]
+#[case::invalid("host", None)]
+#[case::too_large("70000", None)]
+fn test_parse_port(#[case] input: &str, #[case] expected: Option<u16>) {
+    assert_eq!(parse_port(input).ok(), expected);
+}
+
+#[test]
+fn test_panel_address_and_selection() {
+    let mut panel = LinkPanel::new(Language::English);
+    panel.type_text("192.168.0.2:5000 !/");
+    assert_eq!(panel.get_address(), "192.168.0.2:5000");
+    panel.delete_character();
+    assert_eq!(panel.get_address(), "192.168.0.2:500");
+    panel.type_text(&"a".repeat(MAX_ADDRESS_LENGTH));
+    assert_eq!(panel.get_address().len(), MAX_ADDRESS_LENGTH);
+
+    assert_eq!(panel.get_selected_action(), LinkAction::Host);
+    panel.move_selection(-1);
+    assert_eq!(panel.get_selected_action(), LinkAction::Disconnect);
+    panel.move_selection(2);
+    assert_eq!(panel.get_selected_action(), LinkAction::Join);
+}
+
+#[test]
+fn test_panel_led_lights_up_after_transfers() {
+    let mut panel = LinkPanel::default();
+    panel.update_activity(0);
+    assert!(!panel.is_led_lit());
+    panel.update_activity(3);
+    for _ in 1..LED_FRAMES {
+        panel.update_activity(3);
+    }
+    assert!(panel.is_led_lit());
+    panel.update_activity(3);
+    assert!(!panel.is_led_lit());
+}
+
+#[test]
+fn test_panel_only_draws_led_while_closed_and_connected() {
+    let blank_frame = vec![0x80; SCREEN_WIDTH * SCREEN_HEIGHT * 4];
+    let connected = LinkState::Connected("127.0.0.1:8766".parse().unwrap());
+    let mut panel = LinkPanel::default();
+    let mut frame = blank_frame.clone();
+    panel.draw(&mut frame, &LinkState::Disconnected);
+    assert_eq!(frame, blank_frame);
+
+    panel.draw(&mut frame, &connected);
+    let changed = frame
+        .chunks(4)
+        .zip(blank_frame.chunks(4))
+        .filter(|(pixel, blank)| pixel != blank)
+        .count();
+    assert_eq!(changed, 5 * 5);
+
+    panel.open();
+    panel.draw(&mut frame, &LinkState::Disconnected);
+    assert!(frame.chunks(4).all(|pixel| pixel != [0x80; 4]));
+}
@@ -0,0 +1,94 @@
+use crate::font::glyph_index;
+use crate::locale::{Language, Text};
+use rstest::rstest;
+
+#[rstest]
+#[case::code("en", Language::English)]
+#[case::german("de", Language::German)]
+#[case::region("de_AT", Language::German)]
+#[case::encoding("de_DE.UTF-8", Language::German)]
+#[case::hyphen("en-US", Language::English)]
+#[case::uppercase("DE", Language::German)]
+fn test_parse_language(#[case] locale: &str, #[case] expected: Language) {
+    assert_eq!(locale.parse::<Language>().unwrap(), expected);
+}
+
+#[rstest]
+#[case::unsupported("fr_FR.UTF-8")]
+#[case::posix("C")]
+#[case::empty("")]
+fn test_parse_unsupported_language(#[case] locale: &str) {
+    assert!(locale.parse::<Language>().is_err());
+}
+
+/// The OSD can only draw characters of the font
+#[test]
+fn test_texts_are_drawable() {
+    for language in Language::ALL {
+        for text in Text::ALL {
+            let string = language.text(text);
+            assert!(!string.is_empty(), "{language:?} {text:?}");
+            for character in string.chars().filter(|character| *character != ' ') {
+                assert!(
+                    glyph_index(character).is_some(),
+                    "{language:?} {text:?} '{character}'"
+                );
+            }
+        }
+    }
+}
+
+/// Slot labels have to fit into a thumbnail of the state picker
+#[rstest]
+#[case(Text::EmptySlotLabel)]
+#[case(Text::NoPreviewLabel)]
+fn test_slot_labels_fit(#[case] text: Text) {
+    for language in Language::ALL {
+        assert!(language.text(text).chars().count() <= 13, "{language:?}");
+    }
+}
+
+#[rstest]
+#[case::on(Text::CheatOnLabel, 3)]
+#[case::off(Text::CheatOffLabel, 3)]
+#[case::new(Text::NewCheatLabel, 22)]
+fn test_cheat_labels_fit(#[case] text: Text, #[case] max_length: usize) {
+    for language in Language::ALL {
+        assert!(
+            language.text(text).chars().count() <= max_length,
+            "{language:?}"
+        );
+    }
+}
+
+/// The title is followed by the status in the 11th column of the debugger
+#[rstest]
+#[case::title(Text::DebuggerLabel, 9)]
+#[case::paused(Text::DebuggerPausedLabel, 16)]
+#[case::running(Text::DebuggerRunningLabel, 16)]
+fn test_debugger_labels_fit(#[case] text: Text, #[case] max_length: usize) {
+    for language in Language::ALL {
+        assert!(
+            language.text(text).chars().count() <= max_length,
+            "{language:?}"
+        );
+    }
+}
+
+#[test]
+fn test_german_is_translated() {
+    for text in Text::ALL {
+        assert_ne!(
+            Language::German.text(text),
+            Language::English.text(text),
+            "{text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_lowercase_umlauts_use_uppercase_glyphs() {
+    assert_eq!(glyph_index('ö'), glyph_index('Ö'));
+    assert!(glyph_index('ü').is_some());
+    assert_eq!(glyph_index('ß'), None);
+}
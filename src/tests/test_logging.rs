@@ -0,0 +1,17 @@
+use crate::logging::{get_level, get_target_level, set_level};
+use lemon_gb_core::logging::Subsystem;
+use log::LevelFilter;
+
+/// The only test touching the global levels, so parallel tests can't interfere
+#[test]
+fn test_levels_are_per_subsystem() {
+    let previous = get_level(Subsystem::Ppu);
+
+    set_level(Subsystem::Ppu, LevelFilter::Trace);
+    assert_eq!(get_target_level("lemon_gb::ppu"), LevelFilter::Trace);
+    assert_eq!(get_target_level("lemon_gb::cpu"), get_level(Subsystem::Cpu));
+    assert!(log::max_level() >= LevelFilter::Trace);
+
+    set_level(Subsystem::Ppu, previous);
+    assert_eq!(get_level(Subsystem::Ppu), previous);
+}
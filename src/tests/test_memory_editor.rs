@@ -0,0 +1,124 @@
+use crate::locale::Language;
+use crate::memory_editor::{MemoryEditor, MemoryInput, BYTES_PER_ROW, VISIBLE_ROWS};
+use crate::panel::Panel;
+use crate::tests::load_test_rom;
+use lemon_gb_core::game_boy::GameBoy;
+
+fn open_editor() -> MemoryEditor {
+    let mut editor = MemoryEditor::new(Language::English);
+    editor.open();
+    editor
+}
+
+fn enter(editor: &mut MemoryEditor, game_boy: &mut GameBoy, input: MemoryInput, text: &str) {
+    editor.start_input(input);
+    editor.type_text(text);
+    editor.finish_input(game_boy).unwrap();
+}
+
+#[test]
+fn test_selection_wraps_and_scrolls() {
+    let mut editor = open_editor();
+    editor.move_selection(-1);
+    assert_eq!(editor.get_selected(), 0xFFFF);
+    assert_eq!(
+        editor.get_first_row() as usize,
+        0x10000 - VISIBLE_ROWS * BYTES_PER_ROW
+    );
+
+    editor.move_selection(1);
+    assert_eq!(editor.get_selected(), 0x0000);
+    assert_eq!(editor.get_first_row(), 0x0000);
+}
+
+#[test]
+fn test_go_to_address() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut editor = open_editor();
+    enter(&mut editor, &mut game_boy, MemoryInput::Address, "c123");
+    assert_eq!(editor.get_selected(), 0xC123);
+    // The line of the address is the last visible one
+    assert_eq!(
+        editor.get_first_row() as usize,
+        0xC128 - VISIBLE_ROWS * BYTES_PER_ROW
+    );
+    assert_eq!(editor.get_status_text(), "WRAM C123");
+}
+
+#[test]
+fn test_edit_value() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut editor = open_editor();
+    enter(&mut editor, &mut game_boy, MemoryInput::Address, "C000");
+    enter(&mut editor, &mut game_boy, MemoryInput::Value, "3e");
+    assert_eq!(game_boy.read_memory(0xC000), 0x3E);
+    // Ready for the next byte
+    assert_eq!(editor.get_selected(), 0xC001);
+    assert!(!editor.is_editing());
+}
+
+#[test]
+fn test_invalid_input_stays_edited() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut editor = open_editor();
+    editor.start_input(MemoryInput::Value);
+    assert!(editor.finish_input(&mut game_boy).is_err());
+    assert_eq!(editor.get_input(), Some((MemoryInput::Value, "")));
+
+    editor.cancel_editing();
+    editor.start_input(MemoryInput::Search);
+    editor.type_text("ABC");
+    assert!(editor.finish_input(&mut game_boy).is_err());
+    assert_eq!(editor.get_input(), Some((MemoryInput::Search, "ABC")));
+    assert_eq!(editor.get_matches(), None);
+}
+
+#[test]
+fn test_input_length() {
+    let mut editor = open_editor();
+    editor.start_input(MemoryInput::Value);
+    editor.type_text("123");
+    assert_eq!(editor.get_input(), Some((MemoryInput::Value, "12")));
+    assert_eq!(editor.get_status_text(), "VALUE: 12_");
+
+    editor.start_input(MemoryInput::Search);
+    editor.type_text(&"f".repeat(20));
+    assert_eq!(
+        editor.get_input(),
+        Some((MemoryInput::Search, "FFFFFFFFFFFF"))
+    );
+}
+
+#[test]
+fn test_search() {
+    let mut game_boy = load_test_rom("cpu_instrs.gb");
+    let mut editor = open_editor();
+    for (offset, value) in [0xDE, 0xAD, 0xBE, 0xEF, 0x42, 0x42].into_iter().enumerate() {
+        game_boy.write_memory(0xD000 + offset as u16, value);
+    }
+    enter(
+        &mut editor,
+        &mut game_boy,
+        MemoryInput::Search,
+        "deadbeef4242",
+    );
+    // Echo RAM mirrors WRAM
+    assert_eq!(editor.get_matches(), Some([0xD000, 0xF000].as_slice()));
+    assert_eq!(editor.get_selected(), 0xD000);
+    assert_eq!(editor.get_status_text(), "WRAM D000  1/2");
+
+    assert!(editor.next_match());
+    assert_eq!(editor.get_selected(), 0xF000);
+    assert!(editor.next_match());
+    assert_eq!(editor.get_selected(), 0xD000);
+
+    enter(
+        &mut editor,
+        &mut game_boy,
+        MemoryInput::Search,
+        "deadbeef4243",
+    );
+    assert_eq!(editor.get_matches(), Some([].as_slice()));
+    assert!(!editor.next_match());
+    assert_eq!(editor.get_status_text(), "WRAM D000  0/0");
+}
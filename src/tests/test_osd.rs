@@ -0,0 +1,73 @@
+use crate::osd::{Osd, MESSAGE_FRAMES};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use rstest::rstest;
+
+fn blank_frame() -> Vec<u8> {
+    vec![0x80; SCREEN_WIDTH * SCREEN_HEIGHT * 4]
+}
+
+fn row_is_blank(frame: &[u8], y: usize) -> bool {
+    frame[y * SCREEN_WIDTH * 4..(y + 1) * SCREEN_WIDTH * 4]
+        .iter()
+        .all(|&byte| byte == 0x80)
+}
+
+#[rstest]
+#[case::short("Loaded state", &["Loaded state"])]
+#[case::wrapped(
+    "The save state is for TETRIS, not POKEMON RED",
+    &["The save state is for", "TETRIS, not POKEMON RED"]
+)]
+#[case::long_word("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123", &["ABCDEFGHIJKLMNOPQRSTUVWXYZ"])]
+#[case::at_most_four_lines(
+    "aaaa bbbb cccc dddd eeee ffff gggg hhhh iiii jjjj kkkk llll mmmm nnnn oooo pppp qqqq rrrr ssss tttt uuuu vvvv",
+    &["aaaa bbbb cccc dddd eeee", "ffff gggg hhhh iiii jjjj", "kkkk llll mmmm nnnn oooo", "pppp qqqq rrrr ssss tttt"]
+)]
+fn test_osd_wraps_messages(#[case] message: &str, #[case] expected: &[&str]) {
+    let mut osd = Osd::default();
+    osd.show(message);
+    assert_eq!(osd.get_lines(), expected);
+}
+
+#[test]
+fn test_osd_draws_at_bottom_until_expired() {
+    let mut osd = Osd::default();
+    let mut frame = blank_frame();
+    osd.draw(&mut frame);
+    assert_eq!(frame, blank_frame());
+
+    osd.show("Loaded battery save");
+    for _ in 0..MESSAGE_FRAMES {
+        assert!(osd.is_visible());
+        let mut frame = blank_frame();
+        osd.draw(&mut frame);
+        assert!(row_is_blank(&frame, SCREEN_HEIGHT - 10));
+        assert!(!row_is_blank(&frame, SCREEN_HEIGHT - 9));
+        assert!(!row_is_blank(&frame, SCREEN_HEIGHT - 1));
+    }
+
+    assert!(!osd.is_visible());
+    let mut frame = blank_frame();
+    osd.draw(&mut frame);
+    assert_eq!(frame, blank_frame());
+}
+
+#[test]
+fn test_osd_large_text() {
+    let mut osd = Osd::default();
+    osd.show("Loaded the battery save");
+    assert_eq!(osd.get_lines(), ["Loaded the battery save"]);
+
+    osd.set_large_text(true);
+    assert!(osd.is_large_text());
+    assert_eq!(osd.get_lines(), ["Loaded the", "battery save"]);
+
+    // 2 lines of 16 pixels and 2 pixels of border
+    let mut frame = blank_frame();
+    osd.draw(&mut frame);
+    assert!(row_is_blank(&frame, SCREEN_HEIGHT - 35));
+    assert!(!row_is_blank(&frame, SCREEN_HEIGHT - 34));
+
+    osd.set_large_text(false);
+    assert_eq!(osd.get_lines(), ["Loaded the battery save"]);
+}
@@ -0,0 +1,90 @@
+use crate::cheat_manager::CheatManager;
+use crate::debugger::Debugger;
+use crate::debugger_panel::DebuggerPanel;
+use crate::locale::Language;
+use crate::memory_editor::{MemoryEditor, MemoryInput};
+use crate::panel::{Panel, PanelState, TextInput};
+use crate::tests::load_test_rom;
+use crate::vram_viewer::VramViewer;
+use lemon_gb_core::game_boy::cheats::Cheat;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+/// Panels cover the whole screen while they are open and leave the frame alone otherwise
+fn assert_draws_only_while_open<P: Panel>(panel: &mut P, draw: impl Fn(&P, &mut [u8])) {
+    let blank_frame = vec![0x80; SCREEN_WIDTH * SCREEN_HEIGHT * 4];
+    panel.close();
+    let mut frame = blank_frame.clone();
+    draw(panel, &mut frame);
+    assert_eq!(frame, blank_frame);
+
+    panel.open();
+    draw(panel, &mut frame);
+    assert!(frame
+        .chunks(4)
+        .all(|pixel| pixel != [0x80, 0x80, 0x80, 0x80]));
+}
+
+#[test]
+fn test_hex_input_is_filtered() {
+    let mut input = TextInput::hex("", 4);
+    input.type_text("g1 x-caffee");
+    assert_eq!(input.get_text(), "1CAF");
+    input.delete_character();
+    assert_eq!(input.get_text(), "1CA");
+    assert_eq!(input.with_cursor(), "1CA_");
+}
+
+#[test]
+fn test_close_discards_input() {
+    let mut state = PanelState::default();
+    state.open();
+    state.start_input(TextInput::hex("12", 4));
+    state.close();
+    assert!(!state.is_open());
+    assert!(state.get_input().is_none());
+}
+
+#[test]
+fn test_selection_stays_while_editing() {
+    let mut cheat_manager = CheatManager::new(Language::English);
+    cheat_manager.open(vec![Cheat::new("3EA-17B").unwrap()]);
+    cheat_manager.start_editing();
+    cheat_manager.move_selection(1);
+    assert_eq!(cheat_manager.get_selected(), 0);
+
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    let mut debugger_panel = DebuggerPanel::new(Language::English);
+    debugger_panel.open();
+    debugger_panel.start_editing(&game_boy);
+    debugger_panel.move_selection(1);
+    assert_eq!(debugger_panel.get_selected(), 0);
+
+    let mut memory_editor = MemoryEditor::new(Language::English);
+    memory_editor.open();
+    memory_editor.start_input(MemoryInput::Value);
+    memory_editor.move_selection(1);
+    assert_eq!(memory_editor.get_selected(), 0x0000);
+}
+
+#[test]
+fn test_panels_draw_only_while_open() {
+    let mut cheat_manager = CheatManager::new(Language::English);
+    assert_draws_only_while_open(&mut cheat_manager, CheatManager::draw);
+
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    let debugger = Debugger::new();
+    let mut debugger_panel = DebuggerPanel::new(Language::English);
+    assert_draws_only_while_open(&mut debugger_panel, |panel, frame| {
+        panel.draw(frame, &game_boy, &debugger)
+    });
+
+    let mut vram_viewer = VramViewer::new(Language::English);
+    assert_draws_only_while_open(&mut vram_viewer, |viewer, frame| {
+        viewer.draw(frame, &game_boy)
+    });
+
+    let mut memory_editor = MemoryEditor::new(Language::English);
+    assert_draws_only_while_open(&mut memory_editor, |editor, frame| {
+        editor.draw(frame, &game_boy)
+    });
+}
@@ -0,0 +1,142 @@
+use crate::profiles::{GameProfile, Profiles};
+use crate::settings::FrontendSettings;
+use crate::tests::setup_test_dir;
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::components::ppu::color_scheme::ColorScheme;
+use lemon_gb_core::game_boy::config::GameBoyConfig;
+use lemon_gb_core::game_boy::hardware_model::HardwareModel;
+use lemon_gb_core::game_boy::GameBoy;
+use std::path::PathBuf;
+
+const GREEN: [[u8; 4]; 4] = [
+    [0x9B, 0xBC, 0x0F, 0xFF],
+    [0x8B, 0xAC, 0x0F, 0xFF],
+    [0x30, 0x62, 0x30, 0xFF],
+    [0x0F, 0x38, 0x0F, 0xFF],
+];
+
+fn header(title: &str, global_checksum: u16) -> CartridgeHeader {
+    CartridgeHeader {
+        title: title.to_string(),
+        header_checksum: 0x0A,
+        global_checksum,
+        ..Default::default()
+    }
+}
+
+fn green_profile() -> GameProfile {
+    GameProfile {
+        model: None,
+        color_scheme: Some(ColorScheme::monochrome(GREEN)),
+        colorize: None,
+        frame_blending: None,
+        autosplit: None,
+        cheats: Vec::new(),
+    }
+}
+
+#[test]
+fn test_profile_key() {
+    assert_eq!(Profiles::key(&header("TETRIS", 0x16BF)), "TETRIS-0A16BF");
+}
+
+#[test]
+fn test_profile_applies_only_to_its_game() {
+    let mut profiles = Profiles::default();
+    profiles.set(&header("TETRIS", 0x16BF), green_profile());
+
+    let config = profiles.config_for(&header("TETRIS", 0x16BF), GameBoyConfig::default());
+    assert_eq!(config.color_scheme, ColorScheme::monochrome(GREEN));
+
+    // Another revision of the same game
+    let config = profiles.config_for(&header("TETRIS", 0x1234), GameBoyConfig::default());
+    assert_eq!(config, GameBoyConfig::default());
+
+    assert_eq!(
+        profiles.remove(&header("TETRIS", 0x16BF)),
+        Some(green_profile())
+    );
+    assert!(profiles.get(&header("TETRIS", 0x16BF)).is_none());
+}
+
+#[test]
+fn test_empty_profile_keeps_base_config() {
+    let base = GameBoyConfig::default().color_scheme(ColorScheme::monochrome(GREEN));
+    assert_eq!(GameProfile::default().apply(base.clone()), base);
+}
+
+#[test]
+fn test_profile_enables_frame_blending() {
+    let mut profiles = Profiles::default();
+    let profile = GameProfile {
+        frame_blending: Some(true),
+        ..Default::default()
+    };
+    profiles.set(&header("SML2", 0x0001), profile);
+
+    let settings = profiles.settings_for(&header("SML2", 0x0001), FrontendSettings::default());
+    assert!(settings.frame_blending);
+    let config = profiles.config_for(&header("SML2", 0x0001), GameBoyConfig::default());
+    assert_eq!(config, GameBoyConfig::default());
+    // Other games keep the base settings
+    let settings = profiles.settings_for(&header("SML", 0x0001), FrontendSettings::default());
+    assert!(!settings.frame_blending);
+}
+
+#[test]
+fn test_profiles_store_load() {
+    let path = setup_test_dir().join("profiles.json");
+    let mut profiles = Profiles::default();
+    profiles.set(&header("TETRIS", 0x16BF), green_profile());
+    profiles.set(&header("ZELDA", 0x0001), GameProfile::default());
+
+    profiles.store(&path).unwrap();
+    assert_eq!(Profiles::load(&path).unwrap(), profiles);
+    assert_eq!(Profiles::load_or_default(&path).unwrap(), profiles);
+}
+
+#[test]
+fn test_missing_profiles_file_is_empty() {
+    let path = PathBuf::from("./test/missing_profiles.json");
+    assert!(Profiles::load(&path).is_err());
+    assert_eq!(
+        Profiles::load_or_default(&path).unwrap(),
+        Profiles::default()
+    );
+}
+
+#[test]
+fn test_profile_color_scheme_reaches_frame_buffer() {
+    let cartridge = Cartridge::load(PathBuf::from("./test_roms/cpu_instrs.gb")).unwrap();
+    let mut profiles = Profiles::default();
+    profiles.set(&cartridge.header, green_profile());
+
+    let config = profiles.config_for(&cartridge.header, GameBoyConfig::default());
+    let mut game_boy = GameBoy::initialize_with_config(&cartridge, config).unwrap();
+    for _ in 0..10 {
+        game_boy.finish_frame();
+    }
+
+    assert_eq!(*game_boy.get_color_scheme(), ColorScheme::monochrome(GREEN));
+    assert!(game_boy
+        .get_frame_buffer()
+        .chunks_exact(4)
+        .all(|pixel| GREEN.contains(&pixel.try_into().unwrap())));
+}
+
+#[test]
+fn test_profile_selects_model() {
+    let profile = GameProfile {
+        model: Some(HardwareModel::Cgb),
+        ..GameProfile::default()
+    };
+    assert_eq!(
+        profile.apply(GameBoyConfig::default()).model,
+        HardwareModel::Cgb
+    );
+    assert_eq!(
+        GameProfile::default().apply(GameBoyConfig::default()).model,
+        HardwareModel::Dmg0
+    );
+}
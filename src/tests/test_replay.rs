@@ -0,0 +1,19 @@
+use crate::cli::{parse_args, Command};
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_args_replay() {
+    let args = ["replay", "game.gb", "run.vbm", "--verify", "run.json"].map(String::from);
+    assert_eq!(
+        parse_args(args).unwrap(),
+        Some(Command::Replay {
+            rom: PathBuf::from("game.gb"),
+            movie: PathBuf::from("run.vbm"),
+            record: None,
+            verify: Some(PathBuf::from("run.json")),
+        })
+    );
+
+    assert!(parse_args(["replay", "game.gb"].map(String::from)).is_err());
+    assert!(parse_args(["replay", "game.gb", "run.vbm", "--record"].map(String::from)).is_err());
+}
@@ -0,0 +1,105 @@
+use crate::cli::{parse_args, Command};
+use crate::rom_library::{RomEntry, RomLibrary};
+use crate::tests::setup_test_dir;
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::components::cartridge::types::{
+    CartridgeCGBFlag, CartridgeType, MbcType,
+};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+
+#[test]
+fn test_rom_entry() {
+    let rom = RomBuilder::new().title("ENTRY").build();
+    let entry = RomEntry::from_rom(PathBuf::from("entry.gb"), &rom).unwrap();
+
+    assert_eq!(entry.title, "ENTRY");
+    assert_eq!(entry.cartridge_type, CartridgeType::RomOnly);
+    assert_eq!(entry.mapper, MbcType::None);
+    assert_eq!(entry.rom_kib, 32);
+    assert_eq!(entry.ram_kib, 0);
+    assert_eq!(entry.cgb_flag, CartridgeCGBFlag::None);
+    assert!(entry.header_checksum_valid);
+    assert!(entry.global_checksum_valid);
+}
+
+#[test]
+fn test_rom_entry_with_wrong_checksums() {
+    let mut rom = RomBuilder::new().title("BROKEN").build();
+    rom[0x14D] ^= 0xFF;
+    let entry = RomEntry::from_rom(PathBuf::from("broken.gb"), &rom).unwrap();
+    assert!(!entry.header_checksum_valid);
+    assert!(!entry.global_checksum_valid);
+
+    rom[0x14D] ^= 0xFF;
+    rom[0x7FFF] = 0x01;
+    let entry = RomEntry::from_rom(PathBuf::from("broken.gb"), &rom).unwrap();
+    assert!(entry.header_checksum_valid);
+    assert!(!entry.global_checksum_valid);
+}
+
+#[test]
+fn test_scan_rom_library() {
+    let directory = setup_test_dir().join("rom_library");
+    let _ = remove_dir_all(&directory);
+    create_dir_all(directory.join("copies")).unwrap();
+
+    let first = RomBuilder::new().title("FIRST").build();
+    // Only the title would be compensated by the header checksum, which is part of the global checksum
+    let second = RomBuilder::new()
+        .title("SECOND")
+        .program(&[0x18, 0xFE])
+        .build();
+    let mut modified = first.clone();
+    modified[0x7FFF] = 0x01;
+    write(directory.join("first.gb"), &first).unwrap();
+    write(directory.join("copies").join("first copy.GB"), &first).unwrap();
+    write(directory.join("second.gbc"), &second).unwrap();
+    write(directory.join("modified.gb"), &modified).unwrap();
+    write(directory.join("modified copy.gb"), &modified).unwrap();
+    write(directory.join("truncated.gb"), [0x00; 0x20]).unwrap();
+    write(directory.join("notes.txt"), "not a ROM").unwrap();
+
+    let library = RomLibrary::scan(&directory).unwrap();
+    let paths: Vec<PathBuf> = library.roms.iter().map(|rom| rom.path.clone()).collect();
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("copies").join("first copy.GB"),
+            PathBuf::from("modified copy.gb"),
+            PathBuf::from("modified.gb"),
+            PathBuf::from("second.gbc"),
+        ]
+    );
+    assert_eq!(library.roms[0].duplicates, [PathBuf::from("first.gb")]);
+    // A wrong global checksum doesn't identify the game, so these are kept apart
+    assert!(library.roms[1].duplicates.is_empty());
+    assert!(library.roms[2].duplicates.is_empty());
+
+    assert_eq!(library.skipped.len(), 1);
+    assert_eq!(library.skipped[0].path, PathBuf::from("truncated.gb"));
+
+    let index_path = setup_test_dir().join("rom_library.json");
+    library.store(&index_path).unwrap();
+    assert_eq!(RomLibrary::load(&index_path).unwrap(), library);
+}
+
+#[test]
+fn test_scan_missing_directory() {
+    assert!(RomLibrary::scan(&setup_test_dir().join("no_such_directory")).is_err());
+}
+
+#[test]
+fn test_parse_args_scan() {
+    let args = ["scan", "roms", "--output", "index.json"].map(String::from);
+    assert_eq!(
+        parse_args(args).unwrap(),
+        Some(Command::Scan {
+            directory: PathBuf::from("roms"),
+            output: Some(PathBuf::from("index.json")),
+        })
+    );
+
+    assert!(parse_args(["scan"].map(String::from)).is_err());
+    assert!(parse_args(["scan", "roms", "--output"].map(String::from)).is_err());
+}
@@ -0,0 +1,375 @@
+use crate::debugger::DebugEvent;
+use crate::rpc::{
+    Controller, EXECUTION_ERROR, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+};
+use crate::tests::setup_test_dir;
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+/// Increments 0xC000 in a loop, so running frames changes memory
+fn build_game_boy() -> GameBoy {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x134..0x138].copy_from_slice(b"TEST");
+    rom[0x100..0x103].copy_from_slice(&[0xC3, 0x50, 0x01]); // JP 0x0150
+    rom[0x150..0x157].copy_from_slice(&[
+        0x21, 0x00, 0xC0, // LD HL, 0xC000
+        0x34, // INC (HL)
+        0x18, 0xFD, // JR -3
+        0x00,
+    ]);
+    GameBoy::initialize(&Cartridge::from_bytes(&rom).unwrap()).unwrap()
+}
+
+/// Sends a request and returns the parsed response
+fn call(controller: &mut Controller, game_boy: &mut GameBoy, method: &str, params: Value) -> Value {
+    let request = json!({"jsonrpc": "2.0", "id": 7, "method": method, "params": params});
+    let response = controller.handle_line(game_boy, &request.to_string());
+    let response: Value = serde_json::from_str(&response.to_line()).unwrap();
+    assert_eq!(response["jsonrpc"], "2.0");
+    assert_eq!(response["id"], 7);
+    response
+}
+
+#[test]
+fn test_pause_and_resume() {
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    let response = call(&mut controller, &mut game_boy, "pause", Value::Null);
+    assert_eq!(response, json!({"jsonrpc": "2.0", "id": 7, "result": null}));
+    assert!(controller.is_paused());
+    let status = call(&mut controller, &mut game_boy, "status", Value::Null);
+    assert_eq!(status["result"]["paused"], true);
+    assert_eq!(status["result"]["title"], "TEST");
+    assert_eq!(status["result"]["pc"], 0x0100);
+
+    call(&mut controller, &mut game_boy, "resume", Value::Null);
+    assert!(!controller.is_paused());
+}
+
+#[test]
+fn test_frame_advance() {
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    call(
+        &mut controller,
+        &mut game_boy,
+        "frame_advance",
+        json!({"frames": 2}),
+    );
+    let mut reference = build_game_boy();
+    reference.finish_frame();
+    reference.finish_frame();
+    assert_eq!(game_boy.peek(0xC000), reference.peek(0xC000));
+
+    // Without params a single frame is run
+    call(&mut controller, &mut game_boy, "frame_advance", Value::Null);
+    reference.finish_frame();
+    assert_eq!(game_boy.peek(0xC000), reference.peek(0xC000));
+}
+
+/// Breakpoints set over RPC pause the frames the GUI runs through the controller
+#[test]
+fn test_breakpoints() {
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    // INC (HL) of the loop
+    let params = json!({"address": 0x0153});
+    call(&mut controller, &mut game_boy, "set_breakpoint", params);
+    let response = call(
+        &mut controller,
+        &mut game_boy,
+        "get_breakpoints",
+        Value::Null,
+    );
+    assert_eq!(response["result"], json!([0x0153]));
+
+    assert_eq!(
+        controller.run_frame(&mut game_boy),
+        DebugEvent::BreakpointHit(0x0153)
+    );
+    let status = call(&mut controller, &mut game_boy, "status", Value::Null);
+    assert_eq!(status["result"]["paused"], true);
+    assert_eq!(status["result"]["pc"], 0x0153);
+    assert_eq!(controller.run_frame(&mut game_boy), DebugEvent::Paused);
+
+    // Frame advance stops at the breakpoint after one loop
+    call(&mut controller, &mut game_boy, "frame_advance", Value::Null);
+    assert_eq!(game_boy.get_pc(), 0x0153);
+    assert_eq!(game_boy.peek(0xC000), 1);
+
+    // Without the breakpoint a whole frame is advanced and the game stays paused
+    let params = json!({"address": 0x0153});
+    call(&mut controller, &mut game_boy, "remove_breakpoint", params);
+    call(&mut controller, &mut game_boy, "frame_advance", Value::Null);
+    assert!(game_boy.peek(0xC000) > 1);
+    assert!(controller.is_paused());
+
+    call(&mut controller, &mut game_boy, "resume", Value::Null);
+    assert_eq!(
+        controller.run_frame(&mut game_boy),
+        DebugEvent::FrameFinished
+    );
+}
+
+/// NOP, LD B,B, then LD D,D with the message "HI" and an endless loop at 0x015B
+const DEBUG_CONVENTIONS_PROGRAM: [u8; 13] = [
+    0x00, // NOP
+    0x40, // LD B,B
+    0x52, // LD D,D
+    0x18, 0x06, // JR +6
+    0x64, 0x64, 0x00, 0x00, // Signature
+    b'H', b'I', 0x18, 0xFE, // Message / JR -2
+];
+
+#[test]
+fn test_debug_conventions() {
+    let rom = RomBuilder::new()
+        .program(&DEBUG_CONVENTIONS_PROGRAM)
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let mut controller = Controller::default();
+
+    let params = json!({"software_breakpoints": true, "debug_messages": true});
+    call(
+        &mut controller,
+        &mut game_boy,
+        "set_debug_conventions",
+        params,
+    );
+    assert_eq!(
+        controller.run_frame(&mut game_boy),
+        DebugEvent::SoftwareBreakpointHit(0x0151)
+    );
+
+    call(&mut controller, &mut game_boy, "step", Value::Null);
+    assert_eq!(game_boy.get_pc(), 0x0152);
+    call(&mut controller, &mut game_boy, "resume", Value::Null);
+    assert_eq!(
+        controller.run_frame(&mut game_boy),
+        DebugEvent::FrameFinished
+    );
+    assert_eq!(game_boy.get_pc(), 0x015B);
+
+    let response = call(
+        &mut controller,
+        &mut game_boy,
+        "take_debug_messages",
+        Value::Null,
+    );
+    assert_eq!(
+        response["result"],
+        json!([{"address": 0x0152, "text": "HI"}])
+    );
+    let response = call(
+        &mut controller,
+        &mut game_boy,
+        "take_debug_messages",
+        Value::Null,
+    );
+    assert_eq!(response["result"], json!([]));
+
+    // Omitted conventions stay as they are
+    let params = json!({"debug_messages": false});
+    call(
+        &mut controller,
+        &mut game_boy,
+        "set_debug_conventions",
+        params,
+    );
+    assert!(controller.get_debugger().has_software_breakpoints());
+    assert!(!controller.get_debugger().has_debug_messages());
+}
+
+#[test]
+fn test_memory_access() {
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    let params = json!({"address": 0xC100, "bytes": [1, 2, 3]});
+    call(&mut controller, &mut game_boy, "write_memory", params);
+    let response = call(
+        &mut controller,
+        &mut game_boy,
+        "read_memory",
+        json!({"address": 0xC0FF, "length": 5}),
+    );
+    assert_eq!(response["result"], json!([0, 1, 2, 3, 0]));
+
+    let response = call(
+        &mut controller,
+        &mut game_boy,
+        "read_memory",
+        json!({"address": 0xC101}),
+    );
+    assert_eq!(response["result"], json!([2]));
+}
+
+#[test]
+fn test_buttons() {
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    call(
+        &mut controller,
+        &mut game_boy,
+        "set_buttons",
+        json!({"buttons": ["A", "Start"]}),
+    );
+    assert_eq!(
+        game_boy.get_buttons(),
+        Buttons::NONE.with(Button::A).with(Button::Start)
+    );
+    let response = call(&mut controller, &mut game_boy, "get_buttons", Value::Null);
+    assert_eq!(response["result"], json!(["A", "Start"]));
+
+    call(
+        &mut controller,
+        &mut game_boy,
+        "set_buttons",
+        json!({"buttons": []}),
+    );
+    assert_eq!(game_boy.get_buttons(), Buttons::NONE);
+}
+
+#[test]
+fn test_save_and_load_state() {
+    let path = setup_test_dir().join("rpc.state");
+    let path = path.to_str().unwrap();
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    game_boy.finish_frame();
+    let saved = game_boy.save();
+    call(
+        &mut controller,
+        &mut game_boy,
+        "save_state",
+        json!({"path": path}),
+    );
+    game_boy.finish_frame();
+    assert_ne!(game_boy.save(), saved);
+
+    call(
+        &mut controller,
+        &mut game_boy,
+        "load_state",
+        json!({"path": path}),
+    );
+    assert_eq!(game_boy.save(), saved);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_screenshot() {
+    let path = setup_test_dir().join("rpc_screenshot.png");
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+
+    let params = json!({"path": path.to_str().unwrap(), "scale": 2});
+    let response = call(&mut controller, &mut game_boy, "screenshot", params);
+    assert_eq!(response["result"], Value::Null);
+    let image = image::open(&path).unwrap();
+    assert_eq!((image.width(), image.height()), (320, 288));
+}
+
+#[rstest]
+#[case::unknown_method("reboot", json!({}), METHOD_NOT_FOUND)]
+#[case::missing_param("write_memory", json!({"address": 0xC000}), INVALID_PARAMS)]
+#[case::wrong_type("read_memory", json!({"address": "C000"}), INVALID_PARAMS)]
+#[case::unknown_button("set_buttons", json!({"buttons": ["Turbo"]}), INVALID_PARAMS)]
+#[case::range_too_long("read_memory", json!({"address": 0xFFFF, "length": 2}), INVALID_PARAMS)]
+#[case::missing_address("set_breakpoint", json!({}), INVALID_PARAMS)]
+#[case::missing_file("load_state", json!({"path": "./test/missing.state"}), EXECUTION_ERROR)]
+fn test_errors(#[case] method: &str, #[case] params: Value, #[case] code: i32) {
+    let mut game_boy = build_game_boy();
+    let response = call(&mut Controller::default(), &mut game_boy, method, params);
+    assert_eq!(response["error"]["code"], code);
+    assert!(response.get("result").is_none());
+}
+
+#[rstest]
+#[case::not_json("{\"method\": ", PARSE_ERROR, Value::Null)]
+#[case::no_method("{\"id\": 3}", INVALID_REQUEST, json!(3))]
+fn test_malformed_requests(#[case] line: &str, #[case] code: i32, #[case] id: Value) {
+    let response = Controller::default().handle_line(&mut build_game_boy(), line);
+    let response: Value = serde_json::from_str(&response.to_line()).unwrap();
+    assert_eq!(response["error"]["code"], code);
+    assert_eq!(response["id"], id);
+}
+
+#[cfg(feature = "rpc")]
+#[test]
+fn test_server() {
+    use crate::rpc::server::RpcServer;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let address = server.get_address();
+    let client = thread::spawn(move || {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"{\"id\": 1, \"method\": \"pause\"}\n\n{\"id\": 2, \"method\": \"read_memory\", \"params\": {\"address\": 49152}}\n")
+            .unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        (
+            lines.next().unwrap().unwrap(),
+            lines.next().unwrap().unwrap(),
+        )
+    });
+
+    let mut game_boy = build_game_boy();
+    game_boy.poke(0xC000, 0x42);
+    let mut controller = Controller::default();
+    while !client.is_finished() {
+        server.handle_pending(&mut controller, &mut game_boy);
+    }
+
+    let (first, second) = client.join().unwrap();
+    assert_eq!(first, r#"{"jsonrpc":"2.0","id":1,"result":null}"#);
+    assert_eq!(second, r#"{"jsonrpc":"2.0","id":2,"result":[66]}"#);
+    assert!(controller.is_paused());
+}
+
+/// A web page posting to the server sends HTTP headers first, the JSON in the body mustn't be executed
+#[cfg(feature = "rpc")]
+#[test]
+fn test_server_closes_connection_on_malformed_request() {
+    use crate::rpc::server::RpcServer;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    let server = RpcServer::bind("127.0.0.1:0").unwrap();
+    let address = server.get_address();
+    let client = thread::spawn(move || {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"POST / HTTP/1.1\nContent-Type: text/plain\n\n{\"id\": 1, \"method\": \"pause\"}\n")
+            .unwrap();
+        BufReader::new(stream)
+            .lines()
+            .map_while(Result::ok)
+            .collect::<Vec<_>>()
+    });
+
+    let mut game_boy = build_game_boy();
+    let mut controller = Controller::default();
+    while !client.is_finished() {
+        server.handle_pending(&mut controller, &mut game_boy);
+    }
+
+    let lines = client.join().unwrap();
+    assert_eq!(lines.len(), 1);
+    let response: Value = serde_json::from_str(&lines[0]).unwrap();
+    assert_eq!(response["error"]["code"], PARSE_ERROR);
+    assert!(!controller.is_paused());
+}
@@ -0,0 +1,166 @@
+use crate::built_in;
+use crate::cli::{parse_args, Command};
+use crate::headless::MemoryCondition;
+use crate::scenario::{run_scenario, Scenario, ScenarioStep};
+#[cfg(feature = "image")]
+use crate::tests::setup_test_dir;
+use lemon_gb_core::enums::button::{Button, Buttons};
+use lemon_gb_core::game_boy::components::cartridge::rom_builder::RomBuilder;
+use lemon_gb_core::game_boy::GameBoy;
+use std::path::PathBuf;
+
+const EXAMPLE: &str = r#"
+rom = "game.gb"
+max_frames = 3600
+
+[[step]]
+frame = 120
+press = ["Start"]
+
+[[step]]
+until = "0xC0A0=0x01"
+screenshot = "title.png"
+scale = 2
+"#;
+
+fn scenario(max_frames: u64, steps: Vec<ScenarioStep>) -> Scenario {
+    Scenario {
+        rom: PathBuf::from("game.gb"),
+        max_frames,
+        steps,
+    }
+}
+
+#[test]
+fn test_parse_scenario() {
+    let scenario = Scenario::parse(EXAMPLE).unwrap();
+    assert_eq!(scenario.rom, PathBuf::from("game.gb"));
+    assert_eq!(scenario.max_frames, 3600);
+    assert_eq!(
+        scenario.steps,
+        [
+            ScenarioStep {
+                frame: Some(120),
+                press: vec![Button::Start],
+                scale: 1,
+                hold: 1,
+                ..Default::default()
+            },
+            ScenarioStep {
+                until: Some(MemoryCondition {
+                    address: 0xC0A0,
+                    value: 0x01,
+                }),
+                screenshot: Some(PathBuf::from("title.png")),
+                scale: 2,
+                hold: 1,
+                ..Default::default()
+            },
+        ]
+    );
+
+    assert!(Scenario::parse("rom = \"game.gb\"\n[[step]]\nframes = 1").is_err());
+    assert!(Scenario::parse("[[step]]\nframe = 1").is_err());
+}
+
+#[test]
+fn test_run_scenario_frames() {
+    let mut game_boy = GameBoy::initialize(&built_in::cartridge()).unwrap();
+    let steps = vec![
+        ScenarioStep {
+            frame: Some(10),
+            press: vec![Button::Start, Button::A],
+            hold: 5,
+            ..Default::default()
+        },
+        ScenarioStep {
+            frame: Some(30),
+            ..Default::default()
+        },
+    ];
+
+    let result = run_scenario(&mut game_boy, &scenario(60, steps)).unwrap();
+    assert_eq!(result.frames, 30);
+    assert!(result.screenshots.is_empty());
+    assert_eq!(game_boy.get_buttons(), Buttons::NONE);
+}
+
+#[test]
+fn test_run_scenario_frame_already_passed() {
+    let mut game_boy = GameBoy::initialize(&built_in::cartridge()).unwrap();
+    let steps = vec![
+        ScenarioStep {
+            frame: Some(10),
+            press: vec![Button::Start],
+            hold: 5,
+            ..Default::default()
+        },
+        ScenarioStep {
+            frame: Some(12),
+            ..Default::default()
+        },
+    ];
+    let error = run_scenario(&mut game_boy, &scenario(60, steps)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Step 2 waits for frame 12, but frame 15 was already reached"
+    );
+}
+
+#[test]
+fn test_run_scenario_until() {
+    // LD A, 0x01; LD (0xC000), A; JR -2
+    let rom = RomBuilder::new()
+        .program(&[0x3E, 0x01, 0xEA, 0x00, 0xC0, 0x18, 0xFE])
+        .build();
+    let mut game_boy = GameBoy::headless(&rom).unwrap();
+    let met = ScenarioStep {
+        until: Some("0xC000=0x01".parse().unwrap()),
+        ..Default::default()
+    };
+    let result = run_scenario(&mut game_boy, &scenario(10, vec![met])).unwrap();
+    assert!(result.frames <= 1);
+
+    let never_met = ScenarioStep {
+        until: Some("0xC000=0x02".parse().unwrap()),
+        ..Default::default()
+    };
+    let error = run_scenario(&mut game_boy, &scenario(10, vec![never_met])).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Step 1 waits for [0xC000] == 0x02, which wasn't met within 10 frames"
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_run_scenario_file_with_screenshot() {
+    let directory = setup_test_dir().join("scenario");
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("screenshot.toml");
+    std::fs::write(
+        &path,
+        "rom = \"game.gb\"\n[[step]]\nframe = 5\nscreenshot = \"frame_5.png\"\nscale = 2",
+    )
+    .unwrap();
+
+    let scenario = Scenario::load(&path).unwrap();
+    assert_eq!(scenario.rom, directory.join("game.gb"));
+    let mut game_boy = GameBoy::initialize(&built_in::cartridge()).unwrap();
+    let result = run_scenario(&mut game_boy, &scenario).unwrap();
+
+    assert_eq!(result.screenshots, [directory.join("frame_5.png")]);
+    let image = image::open(&result.screenshots[0]).unwrap();
+    assert_eq!((image.width(), image.height()), (320, 288));
+}
+
+#[test]
+fn test_parse_args_scenario() {
+    assert_eq!(
+        parse_args(["scenario", "docs.toml"].map(String::from)).unwrap(),
+        Some(Command::Scenario {
+            path: PathBuf::from("docs.toml"),
+        })
+    );
+    assert!(parse_args(["scenario"].map(String::from)).is_err());
+}
@@ -0,0 +1,31 @@
+use lemon_gb_core::game_boy::components::cartridge::Cartridge;
+use lemon_gb_core::game_boy::components::mmu::mbc::camera::{CAMERA_HEIGHT, CAMERA_WIDTH};
+use lemon_gb_core::game_boy::components::mmu::ROM_BANK_SIZE;
+use lemon_gb_core::game_boy::GameBoy;
+
+/// Pocket Camera with 64 ROM banks and 128 KiB of RAM
+fn build_cartridge() -> Cartridge {
+    let mut rom = vec![0u8; 64 * ROM_BANK_SIZE];
+    rom[0x147] = 0xFC; // Pocket Camera
+    rom[0x148] = 0x05; // 1 MiB ROM
+    rom[0x149] = 0x04; // 128 KiB RAM
+    Cartridge::from_bytes(&rom).unwrap()
+}
+
+/// A 256x224 picture with a black left half is scaled down to the sensor
+#[test]
+fn test_load_camera_image() {
+    let path = crate::tests::setup_test_dir().join("camera.png");
+    image::GrayImage::from_fn(256, 224, |x, _| {
+        image::Luma([if x < 128 { 0 } else { 0xFF }])
+    })
+    .save(&path)
+    .unwrap();
+
+    let mut game_boy = GameBoy::initialize(&build_cartridge()).unwrap();
+    crate::screenshot::load_camera_image(&mut game_boy, &path).unwrap();
+    let image = game_boy.get_camera_image().unwrap();
+    assert_eq!(image.len(), CAMERA_WIDTH * CAMERA_HEIGHT);
+    assert_eq!(image[0], 0x00);
+    assert_eq!(image[CAMERA_WIDTH * CAMERA_HEIGHT - 1], 0xFF);
+}
@@ -0,0 +1,167 @@
+use crate::built_in;
+use crate::state_picker::{read_slots, slot_path, store_slot, Slot, StatePicker, SLOT_COUNT};
+use crate::tests::setup_test_dir;
+use lemon_gb_core::game_boy::components::cartridge::header::CartridgeHeader;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::save_state::GameBoySaveState;
+use lemon_gb_core::game_boy::thumbnail::{Thumbnail, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH};
+use lemon_gb_core::game_boy::GameBoy;
+use rstest::rstest;
+use std::path::Path;
+
+const SELECTION_COLOR: [u8; 4] = [0xFF, 0xD0, 0x00, 0xFF];
+
+fn header(title: &str) -> CartridgeHeader {
+    CartridgeHeader {
+        title: title.to_string(),
+        ..CartridgeHeader::default()
+    }
+}
+
+fn pixel(frame: &[u8], x: usize, y: usize) -> [u8; 4] {
+    let index = (y * SCREEN_WIDTH + x) * 4;
+    frame[index..index + 4].try_into().unwrap()
+}
+
+/// Red is 0 on the left half and rises with x on the right half, green and blue are the same everywhere
+fn test_frame() -> Vec<u8> {
+    let mut frame = vec![0; SCREEN_WIDTH * SCREEN_HEIGHT * 4];
+    for y in 0..SCREEN_HEIGHT {
+        for x in 0..SCREEN_WIDTH {
+            let value = if x < SCREEN_WIDTH / 2 { 0 } else { x as u8 };
+            let index = (y * SCREEN_WIDTH + x) * 4;
+            frame[index..index + 4].copy_from_slice(&[value, 0x10, 0xF0, 0xFF]);
+        }
+    }
+    frame
+}
+
+#[test]
+fn test_save_with_thumbnail() {
+    let mut game_boy = GameBoy::initialize(&built_in::cartridge()).unwrap();
+    for _ in 0..10 {
+        game_boy.finish_frame();
+    }
+    assert_eq!(game_boy.save().thumbnail, None);
+
+    let state = game_boy.save_with_thumbnail();
+    let expected = Thumbnail::from_rgba_frame(game_boy.get_frame_buffer());
+    assert_eq!(state.thumbnail, Some(expected));
+    assert_eq!(
+        GameBoySaveState {
+            thumbnail: None,
+            ..state.clone()
+        },
+        game_boy.save()
+    );
+
+    // The thumbnail survives both formats, and loading ignores it
+    let from_bytes = GameBoySaveState::from_bytes(&state.to_bytes().unwrap()).unwrap();
+    let from_json: GameBoySaveState =
+        serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+    for loaded_state in [from_bytes, from_json] {
+        assert_eq!(loaded_state, state);
+        let mut loaded = GameBoy::initialize(&built_in::cartridge()).unwrap();
+        loaded.load_state(loaded_state).unwrap();
+        assert_eq!(loaded.save(), game_boy.save());
+    }
+}
+
+#[rstest]
+#[case::plain("TETRIS", 0, "TETRIS.1.state")]
+#[case::spaces("POKEMON RED", 3, "POKEMON_RED.4.state")]
+#[case::symbols("A/B:C", 1, "A_B_C.2.state")]
+#[case::untitled("", 0, "UNTITLED.1.state")]
+fn test_slot_path(#[case] title: &str, #[case] index: usize, #[case] file_name: &str) {
+    let directory = Path::new("./states");
+    assert_eq!(
+        slot_path(directory, &header(title), index),
+        directory.join(file_name)
+    );
+}
+
+#[rstest]
+#[case::right(0, 1, 0, 1)]
+#[case::right_wraps(1, 1, 0, 0)]
+#[case::left_wraps(0, -1, 0, 1)]
+#[case::down(1, 0, 1, 3)]
+#[case::up_wraps(1, 0, -1, 3)]
+#[case::down_wraps(2, 0, 1, 0)]
+fn test_move_selection(
+    #[case] start: usize,
+    #[case] columns: isize,
+    #[case] rows: isize,
+    #[case] expected: usize,
+) {
+    let mut picker = StatePicker::default();
+    // Reach the start slot from slot 0
+    picker.move_selection((start % 2) as isize, (start / 2) as isize);
+    assert_eq!(picker.get_selected(), start);
+    picker.move_selection(columns, rows);
+    assert_eq!(picker.get_selected(), expected);
+}
+
+#[test]
+fn test_store_and_read_slots() {
+    let directory = setup_test_dir().join("states");
+    let _ = std::fs::remove_dir_all(&directory);
+    let header = built_in::cartridge().header;
+    assert_eq!(
+        read_slots(&directory, &header),
+        <[Slot; SLOT_COUNT]>::default()
+    );
+
+    let mut game_boy = GameBoy::initialize(&built_in::cartridge()).unwrap();
+    game_boy.finish_frame();
+    let state = game_boy.save_with_thumbnail();
+    store_slot(&state, &slot_path(&directory, &header, 2)).unwrap();
+    store_slot(&game_boy.save(), &slot_path(&directory, &header, 3)).unwrap();
+    std::fs::write(slot_path(&directory, &header, 0), b"broken").unwrap();
+
+    let slots = read_slots(&directory, &header);
+    assert_eq!(slots[0], Slot::Empty, "Unreadable");
+    assert_eq!(slots[1], Slot::Empty);
+    assert_eq!(slots[2], Slot::Saved(state.thumbnail.clone()));
+    assert_eq!(slots[3], Slot::Saved(None));
+    assert_eq!(
+        GameBoySaveState::load_any(&slot_path(&directory, &header, 2)).unwrap(),
+        state
+    );
+}
+
+#[test]
+fn test_picker_draws_only_while_open() {
+    let frame = test_frame();
+    let thumbnail = Thumbnail::from_rgba_frame(&frame);
+    let mut picker = StatePicker::default();
+
+    let mut drawn = frame.clone();
+    picker.draw(&mut drawn);
+    assert_eq!(drawn, frame);
+
+    let mut slots: [Slot; SLOT_COUNT] = Default::default();
+    slots[3] = Slot::Saved(Some(thumbnail.clone()));
+    picker.open(slots);
+    picker.move_selection(1, 0);
+    picker.draw(&mut drawn);
+
+    // The thumbnail of slot 4 fills the bottom right quarter
+    let [r, g, b] = thumbnail.get_pixel(40, 40);
+    assert_eq!(
+        pixel(&drawn, THUMBNAIL_WIDTH + 40, THUMBNAIL_HEIGHT + 40),
+        [r, g, b, 0xFF]
+    );
+    // Slot 2 is selected and gets a border, slot 1 doesn't
+    assert_eq!(pixel(&drawn, THUMBNAIL_WIDTH + 40, 0), SELECTION_COLOR);
+    assert_ne!(pixel(&drawn, 40, 0), SELECTION_COLOR);
+    // Empty slots are dark
+    assert_eq!(
+        pixel(&drawn, 40, THUMBNAIL_HEIGHT + 10),
+        [0x20, 0x20, 0x20, 0xFF]
+    );
+
+    picker.close();
+    let mut closed = frame.clone();
+    picker.draw(&mut closed);
+    assert_eq!(closed, frame);
+}
@@ -0,0 +1,46 @@
+use crate::throttle::{Throttle, GAME_BOY_FRAME_DURATION};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const FRAME: Duration = Duration::from_millis(4);
+
+#[test]
+fn test_throttle_paces_frames() {
+    let mut throttle = Throttle::with_frame_duration(FRAME);
+    let start = Instant::now();
+    for _ in 0..10 {
+        throttle.wait();
+    }
+    let elapsed = start.elapsed();
+    assert!(elapsed >= FRAME * 10, "{elapsed:?}");
+    assert!(elapsed < FRAME * 100, "{elapsed:?}");
+}
+
+#[test]
+fn test_throttle_corrects_small_delays() {
+    let mut throttle = Throttle::with_frame_duration(FRAME);
+    throttle.wait();
+    // A slow frame is made up for by waiting less on the next one
+    sleep(FRAME + FRAME / 2);
+    assert_eq!(throttle.wait(), Duration::ZERO);
+    assert!(throttle.wait() < FRAME);
+}
+
+#[test]
+fn test_throttle_resets_after_long_stall() {
+    let mut throttle = Throttle::with_frame_duration(FRAME);
+    throttle.wait();
+    sleep(Duration::from_millis(150));
+    // Instead of rushing through the missed frames a new schedule is started
+    assert!(throttle.wait() > Duration::ZERO);
+}
+
+#[test]
+fn test_throttle_speed() {
+    let mut throttle = Throttle::new();
+    assert_eq!(throttle.get_frame_duration(), GAME_BOY_FRAME_DURATION);
+    throttle.set_speed(2.0);
+    assert_eq!(throttle.get_frame_duration(), GAME_BOY_FRAME_DURATION / 2);
+    throttle.set_speed(0.0);
+    assert_eq!(throttle.get_frame_duration(), GAME_BOY_FRAME_DURATION / 2);
+}
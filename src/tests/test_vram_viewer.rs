@@ -0,0 +1,86 @@
+use crate::locale::Language;
+use crate::osd::CHARACTER_WIDTH;
+use crate::panel::Panel;
+use crate::tests::load_test_rom;
+use crate::vram_viewer::{VramView, VramViewer, VISIBLE_COLUMNS, VISIBLE_ROWS};
+use lemon_gb_core::game_boy::components::mmu::TileMap;
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+fn open_viewer() -> VramViewer {
+    let mut viewer = VramViewer::new(Language::English);
+    viewer.open();
+    viewer
+}
+
+#[test]
+fn test_next_view() {
+    let mut viewer = open_viewer();
+    viewer.move_selection(1, 1);
+    viewer.next_view();
+    assert_eq!(viewer.get_view(), VramView::TileMap(TileMap::Map9800));
+    assert_eq!(viewer.get_selected(), (0, 0));
+    viewer.next_view();
+    viewer.next_view();
+    assert_eq!(viewer.get_view(), VramView::TileData);
+}
+
+#[test]
+fn test_selection_stops_at_edges() {
+    let mut viewer = open_viewer();
+    viewer.move_selection(-1, -1);
+    assert_eq!(viewer.get_selected(), (0, 0));
+    viewer.move_selection(100, 100);
+    assert_eq!(viewer.get_selected(), (15, 23));
+}
+
+#[test]
+fn test_selection_scrolls_the_view() {
+    let mut viewer = open_viewer();
+    viewer.next_view();
+    viewer.move_selection(VISIBLE_COLUMNS as isize, VISIBLE_ROWS as isize);
+    // The selected tile is in the bottom right corner, so the screen position of the top left one is outside
+    assert!(viewer.select_at(SCREEN_WIDTH - 1, 9 + VISIBLE_ROWS * 8 - 1));
+    assert_eq!(viewer.get_selected(), (VISIBLE_COLUMNS, VISIBLE_ROWS));
+    assert!(viewer.select_at(0, 9));
+    assert_eq!(viewer.get_selected(), (1, 1));
+}
+
+#[test]
+fn test_select_at() {
+    let mut viewer = open_viewer();
+    assert!(viewer.select_at(12, 9 + 13));
+    assert_eq!(viewer.get_selected(), (1, 1));
+    // The title, the line below the image and the right of the tile data aren't tiles
+    assert!(!viewer.select_at(12, 0));
+    assert!(!viewer.select_at(12, SCREEN_HEIGHT - 1));
+    assert!(!viewer.select_at(SCREEN_WIDTH - 1, 20));
+    assert_eq!(viewer.get_selected(), (1, 1));
+}
+
+#[test]
+fn test_selected_text() {
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    let mut viewer = open_viewer();
+    viewer.move_selection(1, 1);
+    assert_eq!(viewer.get_selected_text(&game_boy), "TILE 011 8110 BGP FC");
+
+    viewer.next_view();
+    viewer.move_selection(1, 2);
+    assert_eq!(
+        viewer.get_selected_text(&game_boy),
+        "9841 TILE 00 8000 BGP FC"
+    );
+}
+
+/// The longest text has to fit onto the bottom line
+#[test]
+fn test_selected_text_fits() {
+    let game_boy = load_test_rom("cpu_instrs.gb");
+    for language in Language::ALL {
+        let mut viewer = VramViewer::new(language);
+        viewer.next_view();
+        assert!(
+            viewer.get_selected_text(&game_boy).chars().count() * CHARACTER_WIDTH < SCREEN_WIDTH
+        );
+    }
+}
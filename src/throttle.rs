@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+/// 70224 dots per frame at 4.194304 MHz, roughly 59.73 frames per second
+pub const GAME_BOY_FRAME_DURATION: Duration = Duration::from_nanos(16_742_706);
+/// The last stretch before a deadline is spun instead of slept, sleeping is not precise enough
+const SPIN_THRESHOLD: Duration = Duration::from_micros(1500);
+/// If the caller falls further behind than this, the schedule is reset instead of rushing frames to catch up
+const MAX_LAG: Duration = Duration::from_millis(100);
+
+/// Paces frames at real-time (or a multiple of it) for embedders.
+/// Call [`Throttle::wait`] once after every emulated frame.
+///
+/// Deadlines are scheduled from the previous deadline instead of the time `wait` was called,
+/// so small delays are corrected on the next frames instead of accumulating.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    frame_duration: Duration,
+    next_deadline: Option<Instant>,
+}
+
+impl Throttle {
+    pub fn new() -> Self {
+        Self::with_frame_duration(GAME_BOY_FRAME_DURATION)
+    }
+
+    pub fn with_frame_duration(frame_duration: Duration) -> Self {
+        Self {
+            frame_duration,
+            next_deadline: None,
+        }
+    }
+
+    /// 2.0 runs twice as fast as real-time, 0.5 half as fast
+    pub fn set_speed(&mut self, multiplier: f64) {
+        if multiplier > 0.0 {
+            self.frame_duration = GAME_BOY_FRAME_DURATION.div_f64(multiplier);
+        }
+    }
+
+    pub fn get_frame_duration(&self) -> Duration {
+        self.frame_duration
+    }
+
+    /// Forgets the schedule, e.g. after pausing, so the next frame is not rushed
+    pub fn reset(&mut self) {
+        self.next_deadline = None;
+    }
+
+    /// Blocks until the current frame is due, returns how long it waited
+    pub fn wait(&mut self) -> Duration {
+        let now = Instant::now();
+        let deadline = match self.next_deadline {
+            Some(deadline) if now.saturating_duration_since(deadline) <= MAX_LAG => deadline,
+            _ => now + self.frame_duration,
+        };
+        self.next_deadline = Some(deadline + self.frame_duration);
+
+        let remaining = deadline.saturating_duration_since(now);
+        if remaining > SPIN_THRESHOLD {
+            std::thread::sleep(remaining - SPIN_THRESHOLD);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+        remaining
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,208 @@
+//! Viewer of VRAM drawn over the whole screen: the tile data or one of the two tile maps, rendered anew on every draw.
+//! The selected tile is framed and described on the bottom line, hovering a tile with the cursor selects it.
+
+use crate::locale::{Language, Text};
+use crate::osd::write_pixel;
+use crate::panel::{
+    draw_background, draw_column, Panel, PanelState, INACTIVE_COLOR, LINES, LINE_HEIGHT, TEXT_COLOR,
+};
+use lemon_gb_core::game_boy::components::mmu::TileMap;
+use lemon_gb_core::game_boy::components::ppu::debug::{
+    TILE_DATA_HEIGHT, TILE_DATA_WIDTH, TILE_MAP_SIZE,
+};
+use lemon_gb_core::game_boy::components::ppu::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use lemon_gb_core::game_boy::GameBoy;
+
+const TILE_SIZE: usize = 8;
+/// The image is shown below the title at its original size
+const VIEW_TOP: usize = LINE_HEIGHT;
+pub const VISIBLE_COLUMNS: usize = SCREEN_WIDTH / TILE_SIZE;
+/// The last line describes the selected tile
+pub const VISIBLE_ROWS: usize = (SCREEN_HEIGHT - 2 * LINE_HEIGHT) / TILE_SIZE;
+const INFO_LINE: usize = LINES - 1;
+/// The frame around the selected tile
+const SELECTION_COLOR: [u8; 4] = [0xFF, 0x60, 0x60, 0xFF];
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum VramView {
+    /// All tiles of 0x8000-0x97FF
+    #[default]
+    TileData,
+    TileMap(TileMap),
+}
+
+impl VramView {
+    /// In the order they are switched through
+    pub const ALL: [VramView; 3] = [
+        VramView::TileData,
+        VramView::TileMap(TileMap::Map9800),
+        VramView::TileMap(TileMap::Map9C00),
+    ];
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|view| view == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Width and height in tiles
+    pub fn get_size(&self) -> (usize, usize) {
+        match self {
+            VramView::TileData => (TILE_DATA_WIDTH / TILE_SIZE, TILE_DATA_HEIGHT / TILE_SIZE),
+            VramView::TileMap(_) => (TILE_MAP_SIZE / TILE_SIZE, TILE_MAP_SIZE / TILE_SIZE),
+        }
+    }
+
+    fn render(&self, game_boy: &GameBoy) -> Vec<u8> {
+        match self {
+            VramView::TileData => game_boy.render_tile_data(),
+            VramView::TileMap(map) => game_boy.render_tile_map(*map),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VramViewer {
+    state: PanelState,
+    view: VramView,
+    /// Column and row of the selected tile
+    selected: (usize, usize),
+    /// Column and row of the tile in the top left corner
+    scroll: (usize, usize),
+    /// Of the labels
+    language: Language,
+}
+
+impl VramViewer {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_view(&self) -> VramView {
+        self.view
+    }
+
+    /// Starts at the first tile of the next view
+    pub fn next_view(&mut self) {
+        self.view = self.view.next();
+        self.selected = (0, 0);
+        self.scroll = (0, 0);
+    }
+
+    pub fn get_selected(&self) -> (usize, usize) {
+        self.selected
+    }
+
+    /// Stops at the edges, the view scrolls to keep the selected tile visible
+    pub fn move_selection(&mut self, columns: isize, rows: isize) {
+        let (width, height) = self.view.get_size();
+        let (column, row) = self.selected;
+        self.selected = (
+            column.saturating_add_signed(columns).min(width - 1),
+            row.saturating_add_signed(rows).min(height - 1),
+        );
+        self.scroll = (
+            scroll_to(self.scroll.0, self.selected.0, VISIBLE_COLUMNS),
+            scroll_to(self.scroll.1, self.selected.1, VISIBLE_ROWS),
+        );
+    }
+
+    /// Selects the tile under a pixel of the screen, returns whether there was one
+    pub fn select_at(&mut self, x: usize, y: usize) -> bool {
+        let Some(y) = y.checked_sub(VIEW_TOP) else {
+            return false;
+        };
+        let (width, height) = self.view.get_size();
+        let column = self.scroll.0 + x / TILE_SIZE;
+        let row = self.scroll.1 + y / TILE_SIZE;
+        if x >= SCREEN_WIDTH || y >= VISIBLE_ROWS * TILE_SIZE || column >= width || row >= height {
+            return false;
+        }
+        self.selected = (column, row);
+        true
+    }
+
+    /// The index, address and BGP of the selected tile, for tile maps with the map entry in front
+    pub fn get_selected_text(&self, game_boy: &GameBoy) -> String {
+        let tile = self.language.text(Text::TileLabel);
+        let (x, y) = (self.selected.0 * TILE_SIZE, self.selected.1 * TILE_SIZE);
+        match self.view {
+            VramView::TileData => {
+                let info = game_boy.get_tile_info(x, y).unwrap();
+                format!(
+                    "{tile} {:03X} {:04X} BGP {:02X}",
+                    info.index, info.address, info.palette
+                )
+            }
+            VramView::TileMap(map) => {
+                let entry = game_boy.get_tile_map_entry(map, x, y).unwrap();
+                format!(
+                    "{:04X} {tile} {:02X} {:04X} BGP {:02X}",
+                    entry.map_address, entry.tile_id, entry.data_address, entry.palette
+                )
+            }
+        }
+    }
+
+    /// Draws the viewer onto an RGBA8888 frame if it is open
+    pub fn draw(&self, frame: &mut [u8], game_boy: &GameBoy) {
+        if !self.is_open() {
+            return;
+        }
+        draw_background(frame);
+        let title = match self.view {
+            VramView::TileData => self.language.text(Text::TileDataLabel).to_string(),
+            VramView::TileMap(map) => format!(
+                "{} {:04X}",
+                self.language.text(Text::TileMapLabel),
+                map.get_address()
+            ),
+        };
+        draw_column(frame, 0, 0, &title, TEXT_COLOR);
+
+        let image = self.view.render(game_boy);
+        let (width, height) = self.view.get_size();
+        let image_width = width * TILE_SIZE;
+        let left = self.scroll.0 * TILE_SIZE;
+        let visible_width = (image_width - left).min(SCREEN_WIDTH);
+        let visible_height = ((height - self.scroll.1) * TILE_SIZE).min(VISIBLE_ROWS * TILE_SIZE);
+        for y in 0..visible_height {
+            let source = ((self.scroll.1 * TILE_SIZE + y) * image_width + left) * 4;
+            let target = ((VIEW_TOP + y) * SCREEN_WIDTH) * 4;
+            frame[target..target + visible_width * 4]
+                .copy_from_slice(&image[source..source + visible_width * 4]);
+        }
+
+        let left = (self.selected.0 - self.scroll.0) * TILE_SIZE;
+        let top = VIEW_TOP + (self.selected.1 - self.scroll.1) * TILE_SIZE;
+        for offset in 0..TILE_SIZE {
+            write_pixel(frame, left + offset, top, SELECTION_COLOR);
+            write_pixel(frame, left + offset, top + TILE_SIZE - 1, SELECTION_COLOR);
+            write_pixel(frame, left, top + offset, SELECTION_COLOR);
+            write_pixel(frame, left + TILE_SIZE - 1, top + offset, SELECTION_COLOR);
+        }
+
+        let text = self.get_selected_text(game_boy);
+        draw_column(frame, 0, INFO_LINE, &text, INACTIVE_COLOR);
+    }
+}
+
+/// The view and selection are kept from the last time, nothing is typed into the viewer
+impl Panel for VramViewer {
+    fn get_state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+}
+
+/// The first visible line, moved as little as possible so the selected one is visible
+fn scroll_to(scroll: usize, selected: usize, visible: usize) -> usize {
+    scroll
+        .min(selected)
+        .max((selected + 1).saturating_sub(visible))
+}